@@ -0,0 +1,60 @@
+//! Benchmark the weighted Kendall's Tau-b rank correlation.
+//!
+//! The runtime of `weighted_kendall_tau_b` should grow as _O(n log n)_, also
+//! with heavily tied (8-bit) data. Run with `cargo bench --bench kendall_tau`
+//! and compare the time per element across input sizes, which should grow
+//! only logarithmically (a quadratic implementation grows linearly).
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+use imgal::statistics::weighted_kendall_tau_b;
+
+// input sizes, each 4 times larger than the previous one
+const SIZES: [usize; 4] = [1_024, 4_096, 16_384, 65_536];
+
+/// Create correlated 8-bit like datasets with heavy ties and their weights.
+fn tied_data(n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let a: Vec<f64> = (0..n).map(|i| ((i * 7919) % 256) as f64).collect();
+    let b: Vec<f64> = a
+        .iter()
+        .enumerate()
+        .map(|(i, v)| ((*v as usize + (i * 31) % 64) % 256) as f64)
+        .collect();
+    let w: Vec<f64> = (0..n).map(|i| 1.0 / (1.0 + (i % 13) as f64)).collect();
+
+    (a, b, w)
+}
+
+/// Create correlated datasets without ties and their weights.
+fn untied_data(n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let a: Vec<f64> = (0..n)
+        .map(|i| (i as f64 * 0.618).sin() * n as f64)
+        .collect();
+    let b: Vec<f64> = a
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + (i as f64 * 1.618).cos() * 0.1 * n as f64)
+        .collect();
+    let w: Vec<f64> = (0..n).map(|i| 1.0 / (1.0 + (i % 13) as f64)).collect();
+
+    (a, b, w)
+}
+
+fn bench_weighted_kendall_tau_b(c: &mut Criterion) {
+    let mut group = c.benchmark_group("weighted_kendall_tau_b");
+    for n in SIZES {
+        group.throughput(Throughput::Elements(n as u64));
+        let (a, b, w) = tied_data(n);
+        group.bench_with_input(BenchmarkId::new("tied", n), &n, |bench, _| {
+            bench.iter(|| weighted_kendall_tau_b(black_box(&a), black_box(&b), black_box(&w)))
+        });
+        let (a, b, w) = untied_data(n);
+        group.bench_with_input(BenchmarkId::new("untied", n), &n, |bench, _| {
+            bench.iter(|| weighted_kendall_tau_b(black_box(&a), black_box(&b), black_box(&w)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_weighted_kendall_tau_b);
+criterion_main!(benches);
@@ -0,0 +1,207 @@
+use ndarray::{ArrayD, ArrayViewD, IxDyn};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+
+use crate::colocalization::{icq, manders, pearson};
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Colocalization metrics available for bootstrap confidence intervals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BootstrapMetric {
+    /// Pearson correlation coefficient.
+    Pearson,
+    /// Manders' M1 coefficient with the image `A` and `B` thresholds.
+    MandersM1 { threshold_a: f64, threshold_b: f64 },
+    /// Manders' M2 coefficient with the image `A` and `B` thresholds.
+    MandersM2 { threshold_a: f64, threshold_b: f64 },
+    /// Intensity correlation quotient.
+    Icq,
+}
+
+impl BootstrapMetric {
+    /// Compute the metric on paired 1-dimensional samples.
+    fn compute(&self, samples_a: ArrayD<f64>, samples_b: ArrayD<f64>) -> Result<f64, ImgalError> {
+        let (a, b) = (samples_a.view(), samples_b.view());
+        match *self {
+            BootstrapMetric::Pearson => pearson(a, b),
+            BootstrapMetric::MandersM1 {
+                threshold_a,
+                threshold_b,
+            } => manders(a, b, Some(threshold_a), Some(threshold_b)).map(|(m1, _)| m1),
+            BootstrapMetric::MandersM2 {
+                threshold_a,
+                threshold_b,
+            } => manders(a, b, Some(threshold_a), Some(threshold_b)).map(|(_, m2)| m2),
+            BootstrapMetric::Icq => icq(a, b),
+        }
+    }
+}
+
+/// Compute a bootstrap confidence interval for a colocalization metric.
+///
+/// # Description
+///
+/// This function estimates a percentile bootstrap confidence interval for a
+/// colocalization metric (Pearson, Manders' or ICQ) between two images. Each
+/// bootstrap replicate resamples the pixels of the images with replacement
+/// and recomputes the metric. Because neighboring pixels are spatially
+/// correlated (_e.g._ by the point spread function), pixel resampling tends to
+/// underestimate the interval width. Setting `block_size` resamples
+/// non-overlapping square (or cube) blocks of pixels instead, preserving the
+/// spatial correlation within each block (_i.e._ a block bootstrap). The
+/// interval bounds are the `(1 - confidence) / 2` and `(1 + confidence) / 2`
+/// quantiles of the replicate metrics.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+/// * `metric`: The colocalization metric.
+/// * `n_resamples`: The number of bootstrap replicates, default = 1000.
+/// * `confidence`: The confidence level, between 0.0 and 1.0, default = 0.95.
+/// * `block_size`: The side length of the resampled blocks in pixels, default =
+///    1 (_i.e._ pixel resampling).
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value for
+///    reproducible intervals.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64, f64))`: The metric of the input images and the lower and
+///    upper bounds of the confidence interval.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match. If
+///    `n_resamples` or `block_size` are 0. If `confidence` is not between 0.0
+///    and 1.0.
+pub fn bootstrap_ci<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    metric: BootstrapMetric,
+    n_resamples: Option<usize>,
+    confidence: Option<f64>,
+    block_size: Option<usize>,
+    seed: Option<u64>,
+) -> Result<(f64, f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input images have the same shape
+    if data_a.shape() != data_b.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data_a.shape().to_vec(),
+            shape_b: data_b.shape().to_vec(),
+        });
+    }
+
+    // set optional parameters if needed
+    let n_resamples = n_resamples.unwrap_or(1000);
+    let confidence = confidence.unwrap_or(0.95);
+    let block_size = block_size.unwrap_or(1);
+
+    // check parameters are valid
+    if n_resamples == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "n_resamples",
+            value: 0,
+        });
+    }
+    if block_size == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "block_size",
+            value: 0,
+        });
+    }
+    if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "confidence",
+            value: confidence,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    // compute the metric of the input images
+    let flat_a: Vec<f64> = data_a.iter().map(|v| v.to_f64()).collect();
+    let flat_b: Vec<f64> = data_b.iter().map(|v| v.to_f64()).collect();
+    let n = flat_a.len();
+    let estimate = metric.compute(
+        ArrayD::from_shape_vec(IxDyn(&[n]), flat_a.clone()).unwrap(),
+        ArrayD::from_shape_vec(IxDyn(&[n]), flat_b.clone()).unwrap(),
+    )?;
+
+    // group the flat pixel indices into non-overlapping blocks
+    let blocks = pixel_blocks(data_a.shape(), block_size);
+
+    // compute the metric of each bootstrap replicate in parallel
+    let base_seed = seed.unwrap_or_else(|| rand::rng().next_u64());
+    let mut replicates = (0..n_resamples)
+        .into_par_iter()
+        .map(|r| {
+            let mut rng = StdRng::seed_from_u64(base_seed.wrapping_add(r as u64));
+            let mut samples_a = Vec::with_capacity(n);
+            let mut samples_b = Vec::with_capacity(n);
+            (0..blocks.len()).for_each(|_| {
+                let block = &blocks[rng.random_range(0..blocks.len())];
+                block.iter().for_each(|&i| {
+                    samples_a.push(flat_a[i]);
+                    samples_b.push(flat_b[i]);
+                });
+            });
+            let len = samples_a.len();
+            metric.compute(
+                ArrayD::from_shape_vec(IxDyn(&[len]), samples_a).unwrap(),
+                ArrayD::from_shape_vec(IxDyn(&[len]), samples_b).unwrap(),
+            )
+        })
+        .collect::<Result<Vec<f64>, ImgalError>>()?;
+
+    // find the percentile interval bounds
+    replicates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let alpha = 1.0 - confidence;
+    let lower = quantile(&replicates, alpha / 2.0);
+    let upper = quantile(&replicates, 1.0 - alpha / 2.0);
+
+    Ok((estimate, lower, upper))
+}
+
+/// Group the flat (row-major) pixel indices of an n-dimensional shape into
+/// non-overlapping blocks with the given side length.
+fn pixel_blocks(shape: &[usize], block_size: usize) -> Vec<Vec<usize>> {
+    // number of blocks along each axis, edge blocks may be smaller
+    let n_blocks: Vec<usize> = shape.iter().map(|&s| s.div_ceil(block_size)).collect();
+    let total_blocks: usize = n_blocks.iter().product();
+    let mut blocks: Vec<Vec<usize>> = vec![Vec::new(); total_blocks];
+
+    // walk every pixel and assign it to its block
+    let n: usize = shape.iter().product();
+    let mut idx = vec![0_usize; shape.len()];
+    (0..n).for_each(|flat| {
+        let block_id = idx
+            .iter()
+            .zip(n_blocks.iter())
+            .fold(0, |acc, (&i, &nb)| acc * nb + i / block_size);
+        blocks[block_id].push(flat);
+        // increment the n-dimensional index in row-major order
+        for d in (0..shape.len()).rev() {
+            idx[d] += 1;
+            if idx[d] < shape[d] {
+                break;
+            }
+            idx[d] = 0;
+        }
+    });
+
+    blocks
+}
+
+/// Linearly interpolated quantile of sorted data.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    let frac = pos - lo as f64;
+
+    sorted[lo] + (sorted[hi] - sorted[lo]) * frac
+}
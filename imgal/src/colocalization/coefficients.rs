@@ -0,0 +1,326 @@
+use ndarray::{ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Pearson correlation coefficient between two images.
+///
+/// # Description
+///
+/// This function computes the Pearson correlation coefficient (PCC) between
+/// the pixel intensities of two n-dimensional images:
+///
+/// ```text
+/// r = Σ (aᵢ - ā)(bᵢ - b̄) / √(Σ (aᵢ - ā)² * Σ (bᵢ - b̄)²)
+/// ```
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Pearson correlation coefficient, ranging between -1.0
+///    (anti-correlation), 0.0 (no correlation) and 1.0 (correlation). If either
+///    image has no variance, 0.0 is returned.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1152/ajpcell.00462.2010>
+pub fn pearson<T>(data_a: ArrayViewD<T>, data_b: ArrayViewD<T>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input images have the same shape
+    check_shapes(&data_a, &data_b)?;
+
+    // compute the image means
+    let n = data_a.len() as f64;
+    if n == 0.0 {
+        return Ok(0.0);
+    }
+    let mean_a = data_a.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mean_b = data_b.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+
+    // accumulate the covariance and variances
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    Zip::from(&data_a).and(&data_b).for_each(|&a, &b| {
+        let da = a.to_f64() - mean_a;
+        let db = b.to_f64() - mean_b;
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    });
+
+    let denom = (var_a * var_b).sqrt();
+    if denom == 0.0 {
+        Ok(0.0)
+    } else {
+        Ok((cov / denom).clamp(-1.0, 1.0))
+    }
+}
+
+/// Compute the Costes automatic thresholds of two images.
+///
+/// # Description
+///
+/// This function computes the Costes automatic thresholds, separating
+/// correlated signal from uncorrelated background, for use with [`manders`].
+/// An orthogonal (total least squares) regression line `b = m * a + c` is fit
+/// to the pixel intensities. The threshold of image `A`, `Tₐ`, is then lowered
+/// along the regression line (with `T_b = m * Tₐ + c`) by bisection until the
+/// Pearson correlation coefficient of the pixels below either threshold,
+/// `aᵢ < Tₐ` or `bᵢ < T_b`, drops to 0.0.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The thresholds of image `A` and `B`.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
+///    If the images are not positively correlated.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1529/biophysj.103.038422>
+pub fn costes_threshold<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input images have the same shape
+    check_shapes(&data_a, &data_b)?;
+
+    // compute the image means, variances and covariance
+    let n = data_a.len() as f64;
+    let mean_a = data_a.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mean_b = data_b.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mut s_aa = 0.0;
+    let mut s_bb = 0.0;
+    let mut s_ab = 0.0;
+    Zip::from(&data_a).and(&data_b).for_each(|a, b| {
+        let da = a.to_f64() - mean_a;
+        let db = b.to_f64() - mean_b;
+        s_aa += da * da;
+        s_bb += db * db;
+        s_ab += da * db;
+    });
+    if s_ab.is_nan() || s_ab <= 0.0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid arrays, Costes thresholds require positively correlated images.",
+        });
+    }
+
+    // fit the orthogonal regression line
+    let slope = (s_bb - s_aa + ((s_bb - s_aa).powi(2) + 4.0 * s_ab * s_ab).sqrt()) / (2.0 * s_ab);
+    let intercept = mean_b - slope * mean_a;
+
+    // bisect for the highest threshold with no correlation below it
+    let (mut lo, mut hi) = data_a
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v.to_f64()), hi.max(v.to_f64()))
+        });
+    for _ in 0..64 {
+        let t = 0.5 * (lo + hi);
+        let r = pearson_below(&data_a, &data_b, t, slope * t + intercept);
+        if r.is_nan() || r <= 0.0 {
+            lo = t;
+        } else {
+            hi = t;
+        }
+    }
+
+    Ok((lo, slope * lo + intercept))
+}
+
+/// Compute the intensity correlation quotient (ICQ) between two images.
+///
+/// # Description
+///
+/// This function computes the intensity correlation quotient (ICQ) between two
+/// n-dimensional images. The ICQ is the fraction of pixels with a positive
+/// product of the differences from the mean (PDM), shifted to be centered on
+/// zero:
+///
+/// ```text
+/// ICQ = N₊ / N - 0.5
+/// ```
+///
+/// Where "N₊" is the number of pixels where `(aᵢ - ā)(bᵢ - b̄) > 0` and "N" is
+/// the total number of pixels.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The intensity correlation quotient, ranging between -0.5
+///    (segregated staining), 0.0 (random staining) and 0.5 (dependent
+///    staining).
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1523/JNEUROSCI.3937-03.2004>
+pub fn icq<T>(data_a: ArrayViewD<T>, data_b: ArrayViewD<T>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input images have the same shape
+    check_shapes(&data_a, &data_b)?;
+
+    // compute the image means
+    let n = data_a.len() as f64;
+    if n == 0.0 {
+        return Ok(0.0);
+    }
+    let mean_a = data_a.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mean_b = data_b.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+
+    // count the pixels with a positive product of the differences from the mean
+    let mut n_pos = 0;
+    Zip::from(&data_a).and(&data_b).for_each(|&a, &b| {
+        if (a.to_f64() - mean_a) * (b.to_f64() - mean_b) > 0.0 {
+            n_pos += 1;
+        }
+    });
+
+    Ok(n_pos as f64 / n - 0.5)
+}
+
+/// Compute the Manders' colocalization coefficients between two images.
+///
+/// # Description
+///
+/// This function computes the thresholded Manders' colocalization coefficients
+/// (M1 and M2) between two n-dimensional images. M1 is the fraction of image
+/// `A`'s intensity in pixels where image `B` is above its threshold, and M2 is
+/// the fraction of image `B`'s intensity in pixels where image `A` is above its
+/// threshold:
+///
+/// ```text
+/// M1 = Σ aᵢ,coloc / Σ aᵢ    where aᵢ,coloc = aᵢ if bᵢ > t_b
+/// M2 = Σ bᵢ,coloc / Σ bᵢ    where bᵢ,coloc = bᵢ if aᵢ > t_a
+/// ```
+///
+/// Only pixels above their own channel's threshold contribute to the sums.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+/// * `threshold_a`: Pixel intensity threshold value for image `A`, default =
+///    0.
+/// * `threshold_b`: Pixel intensity threshold value for image `B`, default =
+///    0.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The Manders' M1 and M2 coefficients, ranging between
+///    0.0 (no colocalization) and 1.0 (complete colocalization).
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1111/j.1365-2818.1993.tb03313.x>
+pub fn manders<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    threshold_a: Option<T>,
+    threshold_b: Option<T>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input images have the same shape
+    check_shapes(&data_a, &data_b)?;
+
+    // set optional parameters if needed
+    let ta = threshold_a.unwrap_or_default();
+    let tb = threshold_b.unwrap_or_default();
+
+    // accumulate the total and colocalized intensities
+    let mut sum_a = 0.0;
+    let mut sum_b = 0.0;
+    let mut coloc_a = 0.0;
+    let mut coloc_b = 0.0;
+    Zip::from(&data_a).and(&data_b).for_each(|&a, &b| {
+        let above_a = a > ta;
+        let above_b = b > tb;
+        if above_a {
+            sum_a += a.to_f64();
+            if above_b {
+                coloc_a += a.to_f64();
+            }
+        }
+        if above_b {
+            sum_b += b.to_f64();
+            if above_a {
+                coloc_b += b.to_f64();
+            }
+        }
+    });
+
+    let m1 = if sum_a > 0.0 { coloc_a / sum_a } else { 0.0 };
+    let m2 = if sum_b > 0.0 { coloc_b / sum_b } else { 0.0 };
+
+    Ok((m1, m2))
+}
+
+/// Pearson correlation coefficient of the pixels below either threshold.
+fn pearson_below<T>(data_a: &ArrayViewD<T>, data_b: &ArrayViewD<T>, ta: f64, tb: f64) -> f64
+where
+    T: ToFloat64,
+{
+    let (mut n, mut sa, mut sb, mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    Zip::from(data_a).and(data_b).for_each(|a, b| {
+        let (a, b) = (a.to_f64(), b.to_f64());
+        if a < ta || b < tb {
+            n += 1.0;
+            sa += a;
+            sb += b;
+            saa += a * a;
+            sbb += b * b;
+            sab += a * b;
+        }
+    });
+    let cov = sab - sa * sb / n;
+    let var = (saa - sa * sa / n) * (sbb - sb * sb / n);
+    if var > 0.0 {
+        cov / var.sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Check that two n-dimensional images have the same shape.
+fn check_shapes<T>(data_a: &ArrayViewD<T>, data_b: &ArrayViewD<T>) -> Result<(), ImgalError> {
+    if data_a.shape() != data_b.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data_a.shape().to_vec(),
+            shape_b: data_b.shape().to_vec(),
+        });
+    }
+
+    Ok(())
+}
@@ -1,5 +1,19 @@
 //! Colocalization analysis functions (2D and 3D).
+pub mod bootstrap;
+pub use bootstrap::BootstrapMetric;
+pub use bootstrap::bootstrap_ci;
+pub mod coefficients;
+pub use coefficients::costes_threshold;
+pub use coefficients::icq;
+pub use coefficients::manders;
+pub use coefficients::pearson;
+pub mod pairwise;
+pub use pairwise::PairwiseMetric;
+pub use pairwise::pairwise_matrix;
 pub mod saca;
 pub use saca::saca_2d;
+pub use saca::saca_2d_intermediate;
 pub use saca::saca_3d;
+pub use saca::saca_3d_intermediate;
 pub use saca::saca_significance_mask;
+pub use saca::{SacaKernel, SacaOptions};
@@ -0,0 +1,205 @@
+use ndarray::{Array2, ArrayD, ArrayViewD, Axis, Ix2, Ix3, Zip};
+use rayon::prelude::*;
+
+use crate::colocalization::{SacaOptions, manders, pearson, saca_2d, saca_3d};
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+// pairwise metric matrix with optional per-pair maps
+type PairwiseOutput = (Array2<f64>, Option<Vec<ArrayD<f64>>>);
+// channel pair indices, (i, j) and (j, i) metric values and optional map
+type PairResult = ((usize, usize), (f64, f64), Option<ArrayD<f64>>);
+
+/// Colocalization metrics available for pairwise channel analysis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PairwiseMetric {
+    /// Pearson correlation coefficient, symmetric.
+    Pearson,
+    /// Manders' colocalization coefficient, where entry `(i, j)` is the
+    /// fraction of channel `i`'s intensity colocalized with channel `j`
+    /// (_i.e._ M1 of the pair).
+    Manders,
+    /// Mean SACA _z-score_, symmetric.
+    Saca(Option<SacaOptions>),
+}
+
+/// Compute a colocalization metric for every channel pair of a multichannel
+/// image.
+///
+/// # Description
+///
+/// This function computes the chosen colocalization metric between every pair
+/// of channels in a multichannel image and returns a `(c, c)` matrix of the
+/// results. The diagonal is the metric of each channel with itself. Pairs are
+/// computed in parallel for the Pearson and Manders' metrics, SACA is already
+/// parallelized per pixel and its pairs are computed sequentially.
+///
+/// Optionally the per-pair maps are returned for each pair `(i, j)` with
+/// `i < j`, ordered row by row (_i.e._ `(0, 1)`, `(0, 2)`, ..., `(1, 2)`, ...):
+///
+/// - Pearson: the per-pixel standardized product
+///   `(aᵢ - ā)(bᵢ - b̄) / (σₐσ_b)`, whose mean is the correlation coefficient.
+/// - Manders: the colocalization mask, 1.0 where both channels are above
+///   their thresholds and 0.0 elsewhere.
+/// - SACA: the pixel-wise _z-score_.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional (c, row, col) or 4-dimensional
+///    (c, pln, row, col) multichannel image.
+/// * `metric`: The colocalization metric to compute.
+/// * `thresholds`: The per-channel pixel intensity thresholds used by the
+///    Manders' and SACA metrics. Must be the same length as the number of
+///    channels. If `None`, all thresholds are 0.
+/// * `return_maps`: If `true`, the per-pair maps are returned, default =
+///    `false`.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Option<Vec<ArrayD<f64>>>))`: The `(c, c)` pairwise
+///    metric matrix and the optional per-pair maps.
+/// * `Err(ImgalError)`: If `data` is not 3 or 4-dimensional. If the number of
+///    thresholds does not match the number of channels. If the SACA parameters
+///    are invalid.
+pub fn pairwise_matrix<T>(
+    data: ArrayViewD<T>,
+    metric: PairwiseMetric,
+    thresholds: Option<&[T]>,
+    return_maps: Option<bool>,
+) -> Result<PairwiseOutput, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check the multichannel image dimensions
+    let ndim = data.ndim();
+    if ndim != 3 && ndim != 4 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Pairwise colocalization requires a 3 or 4-dimensional multichannel image.",
+        });
+    }
+    let c = data.len_of(Axis(0));
+
+    // set optional parameters if needed
+    let default_thresholds = vec![T::default(); c];
+    let thresholds = thresholds.unwrap_or(&default_thresholds);
+    if thresholds.len() != c {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: c,
+            b_arr_len: thresholds.len(),
+        });
+    }
+    let return_maps = return_maps.unwrap_or(false);
+
+    // build the list of channel pairs, including the diagonal
+    let pairs: Vec<(usize, usize)> = (0..c).flat_map(|i| (i..c).map(move |j| (i, j))).collect();
+
+    // compute the metric (and map) of each pair
+    let results: Vec<PairResult> = match metric {
+        PairwiseMetric::Pearson => pairs
+            .par_iter()
+            .map(|&(i, j)| {
+                let a = data.index_axis(Axis(0), i);
+                let b = data.index_axis(Axis(0), j);
+                let r = pearson(a.view(), b.view())?;
+                let map = if return_maps && i != j {
+                    Some(standardized_product(a, b))
+                } else {
+                    None
+                };
+                Ok(((i, j), (r, r), map))
+            })
+            .collect::<Result<_, ImgalError>>()?,
+        PairwiseMetric::Manders => pairs
+            .par_iter()
+            .map(|&(i, j)| {
+                let a = data.index_axis(Axis(0), i);
+                let b = data.index_axis(Axis(0), j);
+                let (ta, tb) = (thresholds[i], thresholds[j]);
+                let (m1, m2) = manders(a.view(), b.view(), Some(ta), Some(tb))?;
+                let map = if return_maps && i != j {
+                    let mut mask = ArrayD::<f64>::zeros(a.shape());
+                    Zip::from(&mut mask)
+                        .and(&a)
+                        .and(&b)
+                        .for_each(|m, &va, &vb| {
+                            *m = if va > ta && vb > tb { 1.0 } else { 0.0 };
+                        });
+                    Some(mask)
+                } else {
+                    None
+                };
+                Ok(((i, j), (m1, m2), map))
+            })
+            .collect::<Result<_, ImgalError>>()?,
+        PairwiseMetric::Saca(options) => {
+            let mut saca_results = Vec::with_capacity(pairs.len());
+            for &(i, j) in pairs.iter() {
+                let a = data.index_axis(Axis(0), i);
+                let b = data.index_axis(Axis(0), j);
+                let (ta, tb) = (thresholds[i], thresholds[j]);
+                let z = if ndim == 3 {
+                    let a = a.into_dimensionality::<Ix2>().unwrap();
+                    let b = b.into_dimensionality::<Ix2>().unwrap();
+                    saca_2d(a, b, ta, tb, options)?.into_dyn()
+                } else {
+                    let a = a.into_dimensionality::<Ix3>().unwrap();
+                    let b = b.into_dimensionality::<Ix3>().unwrap();
+                    saca_3d(a, b, ta, tb, options)?.into_dyn()
+                };
+                let mean_z = z.mean().unwrap_or(0.0);
+                let map = if return_maps && i != j { Some(z) } else { None };
+                saca_results.push(((i, j), (mean_z, mean_z), map));
+            }
+            saca_results
+        }
+    };
+
+    // fill the symmetric (or asymmetric for Manders') matrix and collect maps
+    let mut matrix = Array2::<f64>::zeros((c, c));
+    let mut maps = Vec::new();
+    results.into_iter().for_each(|((i, j), (v_ij, v_ji), map)| {
+        matrix[[i, j]] = v_ij;
+        matrix[[j, i]] = v_ji;
+        if let Some(m) = map {
+            maps.push(m);
+        }
+    });
+    let maps = if return_maps { Some(maps) } else { None };
+
+    Ok((matrix, maps))
+}
+
+/// Compute the per-pixel standardized product of two images.
+fn standardized_product<T>(data_a: ArrayViewD<T>, data_b: ArrayViewD<T>) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    let n = data_a.len() as f64;
+    let mean_a = data_a.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mean_b = data_b.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let std_a = (data_a
+        .iter()
+        .map(|v| (v.to_f64() - mean_a).powi(2))
+        .sum::<f64>()
+        / n)
+        .sqrt();
+    let std_b = (data_b
+        .iter()
+        .map(|v| (v.to_f64() - mean_b).powi(2))
+        .sum::<f64>()
+        / n)
+        .sqrt();
+    let denom = std_a * std_b;
+
+    let mut product = ArrayD::<f64>::zeros(data_a.shape());
+    if denom > 0.0 {
+        Zip::from(&mut product)
+            .and(&data_a)
+            .and(&data_b)
+            .for_each(|p, &a, &b| {
+                *p = (a.to_f64() - mean_a) * (b.to_f64() - mean_b) / denom;
+            });
+    }
+
+    product
+}
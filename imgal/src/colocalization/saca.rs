@@ -8,11 +8,126 @@ use rayon::prelude::*;
 
 use crate::distribution::inverse_normal_cdf;
 use crate::error::ImgalError;
-use crate::kernel::neighborhood::{weighted_circle, weighted_sphere};
+use crate::kernel::neighborhood::{
+    circle, gaussian_circle, gaussian_sphere, sphere, weighted_circle, weighted_sphere,
+};
 use crate::statistics::{effective_sample_size, weighted_kendall_tau_b};
 use crate::threshold::manual_mask;
 use crate::traits::numeric::ToFloat64;
 
+// SACA z-score with per-iteration tau and sqrt(n) snapshots
+type SacaIntermediate2d = (Array2<f64>, Array3<f64>, Array3<f64>);
+type SacaIntermediate3d = (Array3<f64>, Array4<f64>, Array4<f64>);
+
+/// Neighborhood kernels available to SACA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SacaKernel {
+    /// Linearly decaying weighted circle (2D) or sphere (3D) kernel, as used in
+    /// the reference.
+    Weighted,
+    /// Uniformly weighted boolean circle (2D) or sphere (3D) kernel.
+    Circle,
+    /// Gaussian weighted circle (2D) or sphere (3D) kernel, with sigma equal to
+    /// the kernel radius.
+    Gaussian,
+}
+
+/// SACA parameters.
+///
+/// The defaults reproduce the parameters of the reference implementation.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SacaOptions {
+    /// The total number of iterations (_i.e._ scales), default = 15.
+    pub tu: usize,
+    /// The iteration after which the stopping rule is applied, must be less
+    /// than `tu`, default = 8.
+    pub tl: usize,
+    /// The kernel radius growth factor per iteration, must be greater than
+    /// 1.0, default = 1.15.
+    pub step_size: f64,
+    /// The stopping rule threshold multiplier applied to "Dₙ", must be greater
+    /// than 0.0, default = 1.0.
+    pub lambda_multiplier: f64,
+    /// The _z-score_ scaling applied to "τ√n", must be greater than 0.0,
+    /// default = 1.5. The default matches the reference implementation and is
+    /// the asymptotic standard score of Kendall's Tau-b, where
+    /// `Var(τ) ≈ 4 / 9n` and therefore `z = τ / √Var(τ) ≈ 1.5 * τ√n`.
+    pub z_scale: f64,
+    /// The neighborhood kernel, default = `SacaKernel::Weighted`.
+    pub kernel: SacaKernel,
+}
+
+impl Default for SacaOptions {
+    fn default() -> Self {
+        SacaOptions {
+            tu: 15,
+            tl: 8,
+            step_size: 1.15,
+            lambda_multiplier: 1.0,
+            z_scale: 1.5,
+            kernel: SacaKernel::Weighted,
+        }
+    }
+}
+
+impl SacaOptions {
+    /// Validate the SACA parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If all parameters are valid.
+    /// * `Err(ImgalError)`: If `tu` is 0, `tl` is not less than `tu`,
+    ///    `step_size` is <= 1.0 or `lambda_multiplier` or `z_scale` are <= 0.0.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        if self.tu == 0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "tu",
+                value: 0.0,
+                min: 1.0,
+                max: f64::INFINITY,
+            });
+        }
+        if self.tl >= self.tu {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "tl",
+                value: self.tl as f64,
+                min: 0.0,
+                max: (self.tu - 1) as f64,
+            });
+        }
+        if self.step_size.is_nan() || self.step_size <= 1.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "step_size",
+                value: self.step_size,
+                min: 1.0,
+                max: f64::INFINITY,
+            });
+        }
+        if self.lambda_multiplier.is_nan() || self.lambda_multiplier <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "lambda_multiplier",
+                value: self.lambda_multiplier,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+        if self.z_scale.is_nan() || self.z_scale <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "z_scale",
+                value: self.z_scale,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 /// Compute colocalization strength using 2-dimensional Spatially Adaptive
 /// Colocalization Analysis (SACA)
 ///
@@ -40,13 +155,15 @@ use crate::traits::numeric::ToFloat64;
 /// * `threshold_b`: Pixel intensity threshold value for image `B`. Pixels below
 ///    this value are given a weight of 0.0 if the pixel is in the circular
 ///    neighborhood.
+/// * `options`: The SACA parameters, default = `SacaOptions::default()`.
 ///
 /// # Returns
 ///
 /// * `OK(Array2<f64>)`: The pixel-wise _z-score_ indicating colocalization or
 ///    anti-colocalization by its sign and the degree or strength of the
 ///    relationship through its absolute values.
-/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match. If
+///    the SACA parameters are invalid.
 ///
 /// # Reference
 ///
@@ -56,7 +173,239 @@ pub fn saca_2d<T>(
     data_b: ArrayView2<T>,
     threshold_a: T,
     threshold_b: T,
+    options: Option<SacaOptions>,
 ) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_saca_2d(
+        data_a,
+        data_b,
+        threshold_a,
+        threshold_b,
+        options.unwrap_or_default(),
+        false,
+    )
+    .map(|(result, _, _)| result)
+}
+
+/// Compute 2-dimensional SACA colocalization strength and record the
+/// intermediate results of each iteration.
+///
+/// # Description
+///
+/// This function computes the pixel-wise _z-score_ of [`saca_2d`] and also
+/// records a snapshot of the per-pixel Kendall's Tau-b and square root of the
+/// effective sample size ("√n") after each iteration (_i.e._ scale). The
+/// snapshots show how the adaptive neighborhood of each pixel converges, which
+/// is useful for method development and for diagnosing unexpected _z-score_
+/// maps.
+///
+/// # Arguments
+///
+/// * `data_a`: The 2-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: Ihe 2-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+/// * `threshold_a`: Pixel intensity threshold value for image `A`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the
+///    neighborhood.
+/// * `threshold_b`: Pixel intensity threshold value for image `B`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the
+///    neighborhood.
+/// * `options`: The SACA parameters, default = `SacaOptions::default()`.
+///
+/// # Returns
+///
+/// * `OK((Array2<f64>, Array3<f64>, Array3<f64>))`: The pixel-wise _z-score_,
+///    the per-iteration Kendall's Tau-b snapshots and the per-iteration "√n"
+///    snapshots. The snapshots are stacked along the first axis, with one
+///    snapshot per iteration ("tu" in total).
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match. If
+///    the SACA parameters are invalid.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn saca_2d_intermediate<T>(
+    data_a: ArrayView2<T>,
+    data_b: ArrayView2<T>,
+    threshold_a: T,
+    threshold_b: T,
+    options: Option<SacaOptions>,
+) -> Result<SacaIntermediate2d, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_saca_2d(
+        data_a,
+        data_b,
+        threshold_a,
+        threshold_b,
+        options.unwrap_or_default(),
+        true,
+    )
+}
+
+/// Compute colocalization strength using 3-dimensional Spatially Adaptive
+/// Colocalization Analysis (SACA)
+///
+/// # Description
+///
+/// This function computes a pixel-wise _z-score_ indicating colocalization and
+/// anti-colocalization strength on 3-dimensional input images using the
+/// Spatially Adaptive Colocalization Analysis (SACA) framework. Per pixel SACA
+/// utilizes a propagation and separation strategy to adaptively expand a
+/// weighted spherical kernel that defines the pixel of consideration's
+/// neighborhood. The pixels within the neighborhood are assigned weights based
+/// on their distance from the center pixel (decreasing with distance), ranked
+/// and their colocalization coefficient computed using Kendall's Tau-b rank
+/// correlation.
+///
+/// # Arguments
+///
+/// * `data_a`: The 3-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: Ihe 3-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+/// * `threshold_a`: Pixel intensity threshold value for image `A`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the circular
+///    neighborhood.
+/// * `threshold_b`: Pixel intensity threshold value for image `B`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the circular
+///    neighborhood.
+/// * `options`: The SACA parameters, default = `SacaOptions::default()`.
+///
+/// # Returns
+///
+/// * `OK(Array3<f64>)`: The pixel-wise _z-score_ indicating colocalization or
+///    anti-colocalization by its sign and the degree or strength of the
+///    relationship through its absolute values.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match. If
+///    the SACA parameters are invalid.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn saca_3d<T>(
+    data_a: ArrayView3<T>,
+    data_b: ArrayView3<T>,
+    threshold_a: T,
+    threshold_b: T,
+    options: Option<SacaOptions>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_saca_3d(
+        data_a,
+        data_b,
+        threshold_a,
+        threshold_b,
+        options.unwrap_or_default(),
+        false,
+    )
+    .map(|(result, _, _)| result)
+}
+
+/// Compute 3-dimensional SACA colocalization strength and record the
+/// intermediate results of each iteration.
+///
+/// # Description
+///
+/// This function computes the pixel-wise _z-score_ of [`saca_3d`] and also
+/// records a snapshot of the per-pixel Kendall's Tau-b and square root of the
+/// effective sample size ("√n") after each iteration (_i.e._ scale). The
+/// snapshots show how the adaptive neighborhood of each pixel converges, which
+/// is useful for method development and for diagnosing unexpected _z-score_
+/// maps.
+///
+/// # Arguments
+///
+/// * `data_a`: The 3-dimensional input image, `A`. Image `A` must have the same
+///    shape as image `B`.
+/// * `data_b`: Ihe 3-dimensional input image, `B`. Image `B` must have the same
+///    shape as image `A`.
+/// * `threshold_a`: Pixel intensity threshold value for image `A`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the
+///    neighborhood.
+/// * `threshold_b`: Pixel intensity threshold value for image `B`. Pixels below
+///    this value are given a weight of 0.0 if the pixel is in the
+///    neighborhood.
+/// * `options`: The SACA parameters, default = `SacaOptions::default()`.
+///
+/// # Returns
+///
+/// * `OK((Array3<f64>, Array4<f64>, Array4<f64>))`: The pixel-wise _z-score_,
+///    the per-iteration Kendall's Tau-b snapshots and the per-iteration "√n"
+///    snapshots. The snapshots are stacked along the first axis, with one
+///    snapshot per iteration ("tu" in total).
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match. If
+///    the SACA parameters are invalid.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn saca_3d_intermediate<T>(
+    data_a: ArrayView3<T>,
+    data_b: ArrayView3<T>,
+    threshold_a: T,
+    threshold_b: T,
+    options: Option<SacaOptions>,
+) -> Result<SacaIntermediate3d, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_saca_3d(
+        data_a,
+        data_b,
+        threshold_a,
+        threshold_b,
+        options.unwrap_or_default(),
+        true,
+    )
+}
+
+/// Create a significant pixel mask from a pixel-wise _z-score_ array.
+///
+/// # Description
+///
+/// This function applies Bonferroni correction to adjust for multiple
+/// comparisons and creates a boolean array representing the significant pixel
+/// mask. For other corrections (_e.g._ false discovery rate control), convert
+/// the _z-scores_ with [`crate::statistics::z_to_p`] and adjust them with
+/// [`crate::statistics::p_adjust`].
+///
+/// # Arguments
+///
+/// * `data`: The pixel-wise _z-score_ indicating colocalization or
+///    anti-colocalization strength.
+/// * `alpha`: The significance level representing the maximum type I error
+///    (_i.e._ false positive error) allowed (default = 0.05).
+///
+/// # Returns
+///
+/// * `ArrayD<bool>`: The significant pixel mask where `true` pixels represent
+///    significant _z-score_ values.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn saca_significance_mask(data: ArrayViewD<f64>, alpha: Option<f64>) -> ArrayD<bool> {
+    let alpha = alpha.unwrap_or(0.05);
+    let q = inverse_normal_cdf(1.0 - (alpha / data.len() as f64)).unwrap();
+    manual_mask(data, q)
+}
+
+/// Run the 2-dimensional SACA framework, optionally recording intermediates.
+fn compute_saca_2d<T>(
+    data_a: ArrayView2<T>,
+    data_b: ArrayView2<T>,
+    threshold_a: T,
+    threshold_b: T,
+    opts: SacaOptions,
+    record: bool,
+) -> Result<SacaIntermediate2d, ImgalError>
 where
     T: ToFloat64,
 {
@@ -70,6 +419,9 @@ where
         });
     }
 
+    // validate saca parameters
+    opts.validate()?;
+
     // create image buffers
     let mut result = Array2::<f64>::zeros(dims_a);
     let mut new_tau = Array2::<f64>::zeros(dims_a);
@@ -78,19 +430,24 @@ where
     let mut old_sqrt_n = Array2::<f64>::ones(dims_a);
     let mut stop = Array3::<f64>::zeros((dims_a.0, dims_a.1, 3));
 
+    // create intermediate snapshot buffers, empty if not recorded
+    let stack_dims = if record {
+        (opts.tu, dims_a.0, dims_a.1)
+    } else {
+        (0, dims_a.0, dims_a.1)
+    };
+    let mut tau_stack = Array3::<f64>::zeros(stack_dims);
+    let mut sqrt_n_stack = Array3::<f64>::zeros(stack_dims);
+
     // set up saca parameters, see reference on "n" value selection for lambda
     let dn = ((dims_a.0 * dims_a.1) as f64).ln().sqrt() * 2.0;
-    let lambda = dn * 1.0;
-    let tu: usize = 15;
-    let tl: usize = 8;
     let mut size_f: f64 = 1.0;
-    let mut radius: usize = 1;
-    let step_size: f64 = 1.15;
     let mut lower_bound_check = false;
 
     // run the multiscale adaptive analysis
-    (0..tu).for_each(|s| {
-        radius = size_f.floor() as usize;
+    for s in 0..opts.tu {
+        let radius = size_f.floor() as usize;
+        let kernel = kernel_2d(radius, opts.kernel)?;
         single_iteration_2d(
             data_a,
             data_b,
@@ -102,16 +459,20 @@ where
             stop.view_mut(),
             old_tau.view_mut(),
             old_sqrt_n.view_mut(),
-            radius,
+            kernel.view(),
             dn,
-            lambda,
+            &opts,
             lower_bound_check,
         );
         // swap array memory, faster than copying
         mem::swap(&mut old_tau, &mut new_tau);
         mem::swap(&mut old_sqrt_n, &mut new_sqrt_n);
-        size_f *= step_size;
-        if s == tl {
+        if record {
+            tau_stack.index_axis_mut(Axis(0), s).assign(&old_tau);
+            sqrt_n_stack.index_axis_mut(Axis(0), s).assign(&old_sqrt_n);
+        }
+        size_f *= opts.step_size;
+        if s == opts.tl {
             lower_bound_check = true;
             let lanes = stop.lanes_mut(Axis(2));
             Zip::from(lanes)
@@ -122,55 +483,20 @@ where
                     ln[2] = *ns;
                 });
         }
-    });
+    }
 
-    Ok(result)
+    Ok((result, tau_stack, sqrt_n_stack))
 }
 
-/// Compute colocalization strength using 3-dimensional Spatially Adaptive
-/// Colocalization Analysis (SACA)
-///
-/// # Description
-///
-/// This function computes a pixel-wise _z-score_ indicating colocalization and
-/// anti-colocalization strength on 3-dimensional input images using the
-/// Spatially Adaptive Colocalization Analysis (SACA) framework. Per pixel SACA
-/// utilizes a propagation and separation strategy to adaptively expand a
-/// weighted spherical kernel that defines the pixel of consideration's
-/// neighborhood. The pixels within the neighborhood are assigned weights based
-/// on their distance from the center pixel (decreasing with distance), ranked
-/// and their colocalization coefficient computed using Kendall's Tau-b rank
-/// correlation.
-///
-/// # Arguments
-///
-/// * `data_a`: The 3-dimensional input image, `A`. Image `A` must have the same
-///    shape as image `B`.
-/// * `data_b`: Ihe 3-dimensional input image, `B`. Image `B` must have the same
-///    shape as image `A`.
-/// * `threshold_a`: Pixel intensity threshold value for image `A`. Pixels below
-///    this value are given a weight of 0.0 if the pixel is in the circular
-///    neighborhood.
-/// * `threshold_b`: Pixel intensity threshold value for image `B`. Pixels below
-///    this value are given a weight of 0.0 if the pixel is in the circular
-///    neighborhood.
-///
-/// # Returns
-///
-/// * `OK(Array3<f64>)`: The pixel-wise _z-score_ indicating colocalization or
-///    anti-colocalization by its sign and the degree or strength of the
-///    relationship through its absolute values.
-/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
-///
-/// # Reference
-///
-/// <https://doi.org/10.1109/TIP.2019.2909194>
-pub fn saca_3d<T>(
+/// Run the 3-dimensional SACA framework, optionally recording intermediates.
+fn compute_saca_3d<T>(
     data_a: ArrayView3<T>,
     data_b: ArrayView3<T>,
     threshold_a: T,
     threshold_b: T,
-) -> Result<Array3<f64>, ImgalError>
+    opts: SacaOptions,
+    record: bool,
+) -> Result<SacaIntermediate3d, ImgalError>
 where
     T: ToFloat64,
 {
@@ -184,6 +510,9 @@ where
         });
     }
 
+    // validate saca parameters
+    opts.validate()?;
+
     // create image buffers
     let mut result = Array3::<f64>::zeros(dims_a);
     let mut new_tau = Array3::<f64>::zeros(dims_a);
@@ -192,19 +521,24 @@ where
     let mut old_sqrt_n = Array3::<f64>::ones(dims_a);
     let mut stop = Array4::<f64>::zeros((dims_a.0, dims_a.1, dims_a.2, 3));
 
+    // create intermediate snapshot buffers, empty if not recorded
+    let stack_dims = if record {
+        (opts.tu, dims_a.0, dims_a.1, dims_a.2)
+    } else {
+        (0, dims_a.0, dims_a.1, dims_a.2)
+    };
+    let mut tau_stack = Array4::<f64>::zeros(stack_dims);
+    let mut sqrt_n_stack = Array4::<f64>::zeros(stack_dims);
+
     // set up saca parameters, see reference on "n" value selection for lambda
     let dn = ((dims_a.0 * dims_a.1 * dims_a.2) as f64).ln().sqrt() * 2.0;
-    let lambda = dn * 1.0;
-    let tu: usize = 15;
-    let tl: usize = 8;
     let mut size_f: f64 = 1.0;
-    let mut radius: usize = 1;
-    let step_size: f64 = 1.15;
     let mut lower_bound_check = false;
 
     // run the multiscale adaptive analysis
-    (0..tu).for_each(|s| {
-        radius = size_f.floor() as usize;
+    for s in 0..opts.tu {
+        let radius = size_f.floor() as usize;
+        let kernel = kernel_3d(radius, opts.kernel)?;
         single_iteration_3d(
             data_a,
             data_b,
@@ -216,16 +550,20 @@ where
             stop.view_mut(),
             old_tau.view_mut(),
             old_sqrt_n.view_mut(),
-            radius,
+            kernel.view(),
             dn,
-            lambda,
+            &opts,
             lower_bound_check,
         );
         // swap array memory, faster than copying
         mem::swap(&mut old_tau, &mut new_tau);
         mem::swap(&mut old_sqrt_n, &mut new_sqrt_n);
-        size_f *= step_size;
-        if s == tl {
+        if record {
+            tau_stack.index_axis_mut(Axis(0), s).assign(&old_tau);
+            sqrt_n_stack.index_axis_mut(Axis(0), s).assign(&old_sqrt_n);
+        }
+        size_f *= opts.step_size;
+        if s == opts.tl {
             lower_bound_check = true;
             let lanes = stop.lanes_mut(Axis(3));
             Zip::from(lanes)
@@ -236,38 +574,9 @@ where
                     ln[2] = *ns;
                 });
         }
-    });
-
-    Ok(result)
-}
+    }
 
-/// Create a significant pixel mask from a pixel-wise _z-score_ array.
-///
-/// # Description
-///
-/// This function applies Bonferroni correction to adjust for multiple
-/// comparisons and creates a boolean array representing the significant pixel
-/// mask.
-///
-/// # Arguments
-///
-/// * `data`: The pixel-wise _z-score_ indicating colocalization or
-///    anti-colocalization strength.
-/// * `alpha`: The significance level representing the maximum type I error
-///    (_i.e._ false positive error) allowed (default = 0.05).
-///
-/// # Returns
-///
-/// * `ArrayD<bool>`: The significant pixel mask where `true` pixels represent
-///    significant _z-score_ values.
-///
-/// # Reference
-///
-/// <https://doi.org/10.1109/TIP.2019.2909194>
-pub fn saca_significance_mask(data: ArrayViewD<f64>, alpha: Option<f64>) -> ArrayD<bool> {
-    let alpha = alpha.unwrap_or(0.05);
-    let q = inverse_normal_cdf(1.0 - (alpha / data.len() as f64)).unwrap();
-    manual_mask(data, q)
+    Ok((result, tau_stack, sqrt_n_stack))
 }
 
 /// Fill working buffers from 2-dimensional data.
@@ -395,6 +704,24 @@ fn fill_buffers_3d<T>(
     buf_w[i..].fill(0.0);
 }
 
+/// Create the 2-dimensional SACA neighborhood kernel for the given radius.
+fn kernel_2d(radius: usize, kernel: SacaKernel) -> Result<Array2<f64>, ImgalError> {
+    match kernel {
+        SacaKernel::Weighted => weighted_circle(radius, radius as f64 * (2.5_f64).sqrt(), None),
+        SacaKernel::Circle => Ok(circle(radius)?.mapv(|v| if v { 1.0 } else { 0.0 })),
+        SacaKernel::Gaussian => gaussian_circle(radius, radius as f64, None),
+    }
+}
+
+/// Create the 3-dimensional SACA neighborhood kernel for the given radius.
+fn kernel_3d(radius: usize, kernel: SacaKernel) -> Result<Array3<f64>, ImgalError> {
+    match kernel {
+        SacaKernel::Weighted => weighted_sphere(radius, radius as f64 * (2.5_f64).sqrt(), None),
+        SacaKernel::Circle => Ok(sphere(radius)?.mapv(|v| if v { 1.0 } else { 0.0 })),
+        SacaKernel::Gaussian => gaussian_sphere(radius, radius as f64, None),
+    }
+}
+
 /// Get the end position for filling the buffers along an axis.
 fn get_end_position(location: usize, radius: usize, boundary: usize) -> usize {
     let end = location + radius;
@@ -422,19 +749,17 @@ fn single_iteration_2d<T>(
     mut stop: ArrayViewMut3<f64>,
     old_tau: ArrayViewMut2<f64>,
     old_sqrt_n: ArrayViewMut2<f64>,
-    radius: usize,
+    kernel: ArrayView2<f64>,
     dn: f64,
-    lambda: f64,
+    options: &SacaOptions,
     bound_check: bool,
 ) where
     T: ToFloat64,
 {
-    // get weighted circle kernel
-    let falloff = radius as f64 * (2.5_f64).sqrt();
-    let kernel = weighted_circle(radius, falloff, None).unwrap();
-
     // set up buffers and parameters
-    let d = 2 * radius + 1;
+    let d = kernel.dim().0;
+    let radius = d / 2;
+    let lambda = dn * options.lambda_multiplier;
     let buf_size = d * d;
 
     // compute weighted kendall's tau and write to output
@@ -466,7 +791,7 @@ fn single_iteration_2d<T>(
             fill_buffers_2d(
                 data_a,
                 data_b,
-                kernel.view(),
+                kernel,
                 old_tau.view(),
                 old_sqrt_n.view(),
                 &mut buf_a,
@@ -499,7 +824,7 @@ fn single_iteration_2d<T>(
             } else {
                 let tau = weighted_kendall_tau_b(&buf_a, &buf_b, &buf_w).unwrap_or(0.0);
                 *nt = tau;
-                *re = tau * *nn * 2.5;
+                *re = tau * *nn * options.z_scale;
             }
             if bound_check {
                 tau_diff = (ln[1] - *nt).abs() * ln[2];
@@ -524,19 +849,17 @@ fn single_iteration_3d<T>(
     mut stop: ArrayViewMut4<f64>,
     old_tau: ArrayViewMut3<f64>,
     old_sqrt_n: ArrayViewMut3<f64>,
-    radius: usize,
+    kernel: ArrayView3<f64>,
     dn: f64,
-    lambda: f64,
+    options: &SacaOptions,
     bound_check: bool,
 ) where
     T: ToFloat64,
 {
-    // get weighted circle kernel
-    let falloff = radius as f64 * (2.5_f64).sqrt();
-    let kernel = weighted_sphere(radius, falloff, None).unwrap();
-
     // set up buffers and parameters
-    let d = 2 * radius + 1;
+    let d = kernel.dim().0;
+    let radius = d / 2;
+    let lambda = dn * options.lambda_multiplier;
     let buf_size = d * d * d;
 
     // compute weighted kendall's tau and write to output
@@ -570,7 +893,7 @@ fn single_iteration_3d<T>(
             fill_buffers_3d(
                 data_a,
                 data_b,
-                kernel.view(),
+                kernel,
                 old_tau.view(),
                 old_sqrt_n.view(),
                 &mut buf_a,
@@ -606,7 +929,7 @@ fn single_iteration_3d<T>(
             } else {
                 let tau = weighted_kendall_tau_b(&buf_a, &buf_b, &buf_w).unwrap_or(0.0);
                 *nt = tau;
-                *re = tau * *nn * 2.5;
+                *re = tau * *nn * options.z_scale;
             }
             if bound_check {
                 tau_diff = (ln[1] - *nt).abs() * ln[2];
@@ -0,0 +1,3 @@
+//! Synthetic test images (requires the `data` feature).
+pub mod phantoms;
+pub use phantoms::{beads, cells, flim_cube};
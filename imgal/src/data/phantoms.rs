@@ -0,0 +1,121 @@
+use ndarray::{Array2, Array3, Axis, Zip};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::simulation::decay::ideal_exponential_1d;
+use crate::simulation::noise::{poisson_2d, poisson_3d};
+
+/// The seed of all phantoms, so each phantom is identical across calls.
+const SEED: u64 = 42;
+
+/// Create a synthetic fluorescent cells image.
+///
+/// # Description
+///
+/// This function generates a 2-dimensional (row, col) 256 x 256 image of 24
+/// elliptical cells with a bright center on a dim background, with Poisson
+/// noise applied. Cells may touch or overlap, making the image suitable to
+/// exercise filters, thresholds, segmentation and region measurements. The
+/// image is identical across calls.
+///
+/// # Returns
+///
+/// * `Array2<u16>`: The synthetic cells image.
+pub fn cells() -> Array2<u16> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let ellipses: Vec<[f64; 5]> = (0..24)
+        .map(|_| {
+            [
+                rng.random_range(16.0..240.0),
+                rng.random_range(16.0..240.0),
+                rng.random_range(8.0..16.0),
+                rng.random_range(6.0..12.0),
+                rng.random_range(0.0..std::f64::consts::PI),
+            ]
+        })
+        .collect();
+
+    // draw each cell with an intensity decreasing from its center
+    let mut data = Array2::<f64>::from_elem((256, 256), 20.0);
+    Zip::indexed(&mut data).par_for_each(|(r, c), d| {
+        for &[cr, cc, a, b, theta] in &ellipses {
+            let (dr, dc) = (r as f64 - cr, c as f64 - cc);
+            let u = (dr * theta.cos() + dc * theta.sin()) / a;
+            let v = (-dr * theta.sin() + dc * theta.cos()) / b;
+            let dist = u * u + v * v;
+            if dist < 1.0 {
+                *d = d.max(150.0 + 100.0 * (1.0 - dist));
+            }
+        }
+    });
+
+    poisson_2d(data.view(), 1.0, Some(SEED)).mapv(|v| v as u16)
+}
+
+/// Create a synthetic fluorescent beads image.
+///
+/// # Description
+///
+/// This function generates a 2-dimensional (row, col) 128 x 128 image of 32
+/// diffraction limited beads, modeled as Gaussian spots with a standard
+/// deviation of 1.5 pixels, on a dim background with Poisson noise applied.
+/// The image is suitable to exercise spot detection, registration and focus
+/// measurements. The image is identical across calls.
+///
+/// # Returns
+///
+/// * `Array2<u16>`: The synthetic beads image.
+pub fn beads() -> Array2<u16> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let spots: Vec<[f64; 3]> = (0..32)
+        .map(|_| {
+            [
+                rng.random_range(4.0..124.0),
+                rng.random_range(4.0..124.0),
+                rng.random_range(200.0..1000.0),
+            ]
+        })
+        .collect();
+
+    // sum the Gaussian spots of all beads
+    let sigma_2 = 2.0 * 1.5 * 1.5;
+    let mut data = Array2::<f64>::from_elem((128, 128), 10.0);
+    Zip::indexed(&mut data).par_for_each(|(r, c), d| {
+        *d += spots
+            .iter()
+            .map(|&[sr, sc, amp]| {
+                let (dr, dc) = (r as f64 - sr, c as f64 - sc);
+                amp * (-(dr * dr + dc * dc) / sigma_2).exp()
+            })
+            .sum::<f64>();
+    });
+
+    poisson_2d(data.view(), 1.0, Some(SEED)).mapv(|v| v as u16)
+}
+
+/// Create a synthetic fluorescence lifetime imaging (FLIM) decay image.
+///
+/// # Description
+///
+/// This function generates a 3-dimensional (row, col, time) 64 x 64 x 256
+/// decay image over a 12.5 ns period (_i.e._ an 80 MHz laser) with Poisson
+/// noise applied. The left half of the image has a monoexponential lifetime of
+/// 1.0 ns and the right half a monoexponential lifetime of 3.0 ns, with 2000
+/// and 4000 total photon counts per pixel in the top and bottom halves
+/// respectively. The decay axis is 2. The image is identical across calls.
+///
+/// # Returns
+///
+/// * `Array3<f64>`: The synthetic FLIM decay image.
+pub fn flim_cube() -> Array3<f64> {
+    let (rows, cols, samples, period) = (64, 64, 256, 12.5);
+    let mut data = Array3::<f64>::zeros((rows, cols, samples));
+    Zip::indexed(data.lanes_mut(Axis(2))).par_for_each(|(r, c), mut lane| {
+        let tau = if c < cols / 2 { 1.0 } else { 3.0 };
+        let counts = if r < rows / 2 { 2000.0 } else { 4000.0 };
+        let decay = ideal_exponential_1d(samples, period, &[tau], &[1.0], counts).unwrap();
+        lane.iter_mut().zip(decay).for_each(|(l, d)| *l = d);
+    });
+
+    poisson_3d(data.view(), 1.0, Some(SEED), None).unwrap()
+}
@@ -1,5 +1,5 @@
 //! Adjustable distribution functions.
 pub mod cdf;
-pub use cdf::inverse_normal_cdf;
+pub use cdf::{f_cdf, inverse_normal_cdf, normal_cdf};
 pub mod gaussian;
 pub use gaussian::gaussian;
@@ -114,3 +114,15 @@ impl fmt::Display for ImgalError {
 }
 
 impl error::Error for ImgalError {}
+
+/// Check that an axis index is valid for an array with `dim_len` dimensions.
+pub(crate) fn check_axis(axis: usize, dim_len: usize) -> Result<(), ImgalError> {
+    if axis >= dim_len {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len,
+        });
+    }
+
+    Ok(())
+}
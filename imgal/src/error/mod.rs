@@ -1,3 +1,4 @@
 //! Internal error module.
 pub mod imgal_error;
 pub use imgal_error::ImgalError;
+pub(crate) use imgal_error::check_axis;
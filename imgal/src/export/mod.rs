@@ -0,0 +1,8 @@
+//! Measurement table export functions (the Parquet writer requires the
+//! `parquet` feature).
+pub mod table;
+pub use table::{Column, Table};
+pub mod writers;
+pub use writers::write_csv;
+#[cfg(feature = "parquet")]
+pub use writers::write_parquet;
@@ -0,0 +1,77 @@
+use crate::measure::LabelTable;
+use crate::segmentation::RegionProperties;
+
+/// A named column of a measurement table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    /// An unsigned integer column (_e.g._ labels and counts).
+    UInt(Vec<usize>),
+    /// A floating point column, where missing values are NaN.
+    Float(Vec<f64>),
+}
+
+impl Column {
+    /// The number of rows of the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Column::UInt(v) => v.len(),
+            Column::Float(v) => v.len(),
+        }
+    }
+
+    /// Whether the column has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A measurement output that can be written as a table of columns, see
+/// [`crate::export::writers`].
+pub trait Table {
+    /// The (name, values) columns of the table, all with the same number of
+    /// rows.
+    fn columns(&self) -> Vec<(&'static str, Column)>;
+}
+
+impl Table for LabelTable {
+    fn columns(&self) -> Vec<(&'static str, Column)> {
+        vec![
+            ("label", Column::UInt(self.label.clone())),
+            ("pixel_count", Column::UInt(self.pixel_count.clone())),
+            ("mean_a", Column::Float(self.mean_a.clone())),
+            ("std_a", Column::Float(self.std_a.clone())),
+            ("min_a", Column::Float(self.min_a.clone())),
+            ("max_a", Column::Float(self.max_a.clone())),
+            ("mean_b", Column::Float(self.mean_b.clone())),
+            ("std_b", Column::Float(self.std_b.clone())),
+            ("min_b", Column::Float(self.min_b.clone())),
+            ("max_b", Column::Float(self.max_b.clone())),
+            ("pearson", Column::Float(self.pearson.clone())),
+            ("manders_m1", Column::Float(self.manders_m1.clone())),
+            ("manders_m2", Column::Float(self.manders_m2.clone())),
+            ("mean_g", Column::Float(self.mean_g.clone())),
+            ("mean_s", Column::Float(self.mean_s.clone())),
+            ("tau_phase", Column::Float(self.tau_phase.clone())),
+            ("tau_modulation", Column::Float(self.tau_modulation.clone())),
+        ]
+    }
+}
+
+impl Table for [RegionProperties] {
+    fn columns(&self) -> Vec<(&'static str, Column)> {
+        let uint = |f: fn(&RegionProperties) -> usize| Column::UInt(self.iter().map(f).collect());
+        let float = |f: fn(&RegionProperties) -> f64| Column::Float(self.iter().map(f).collect());
+        vec![
+            ("label", uint(|p| p.label)),
+            ("pixel_count", uint(|p| p.pixel_count)),
+            ("area", float(|p| p.area)),
+            ("centroid_row", float(|p| p.centroid.0)),
+            ("centroid_col", float(|p| p.centroid.1)),
+            ("bbox_min_row", uint(|p| p.bbox.0)),
+            ("bbox_min_col", uint(|p| p.bbox.1)),
+            ("bbox_max_row", uint(|p| p.bbox.2)),
+            ("bbox_max_col", uint(|p| p.bbox.3)),
+            ("equivalent_diameter", float(|p| p.equivalent_diameter)),
+        ]
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{self, Write};
+
+use crate::export::table::{Column, Table};
+
+/// Write a measurement table as CSV.
+///
+/// # Description
+///
+/// This function writes a header row with the column names followed by one
+/// row per table row, with comma separated values. Missing values are written
+/// as `NaN`. The writer is not buffered, wrap files in a
+/// [`std::io::BufWriter`].
+///
+/// # Arguments
+///
+/// * `table`: The measurement table (_e.g._ the output of
+///    [`crate::measure::per_label_analysis`] or
+///    [`crate::segmentation::region_properties`]).
+/// * `writer`: The destination (_e.g._ a file).
+///
+/// # Returns
+///
+/// * `Ok(())`: If the table was written.
+/// * `Err(io::Error)`: If writing failed.
+pub fn write_csv<T, W>(table: &T, mut writer: W) -> io::Result<()>
+where
+    T: Table + ?Sized,
+    W: Write,
+{
+    let columns = table.columns();
+    let names: Vec<&str> = columns.iter().map(|(name, _)| *name).collect();
+    writeln!(writer, "{}", names.join(","))?;
+    let rows = columns.first().map_or(0, |(_, col)| col.len());
+    for r in 0..rows {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|(_, col)| match col {
+                Column::UInt(v) => v[r].to_string(),
+                Column::Float(v) => v[r].to_string(),
+            })
+            .collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+
+    writer.flush()
+}
+
+/// Write a measurement table as an uncompressed Parquet file (requires the
+/// `parquet` feature).
+///
+/// # Description
+///
+/// This function writes the table as a single row group with one required
+/// column per table column, where unsigned integer columns are stored as
+/// unsigned 64-bit integers and floating point columns as doubles. Missing
+/// values are stored as NaN.
+///
+/// # Arguments
+///
+/// * `table`: The measurement table (_e.g._ the output of
+///    [`crate::measure::per_label_analysis`] or
+///    [`crate::segmentation::region_properties`]).
+/// * `writer`: The destination (_e.g._ a file).
+///
+/// # Returns
+///
+/// * `Ok(())`: If the table was written.
+/// * `Err(io::Error)`: If writing or encoding failed.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<T, W>(table: &T, writer: W) -> io::Result<()>
+where
+    T: Table + ?Sized,
+    W: Write + Send,
+{
+    use std::sync::Arc;
+
+    use parquet::data_type::{DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    // build the schema from the column types
+    let columns = table.columns();
+    let fields: String = columns
+        .iter()
+        .map(|(name, col)| match col {
+            Column::UInt(_) => format!("REQUIRED INT64 {} (INTEGER(64, false)); ", name),
+            Column::Float(_) => format!("REQUIRED DOUBLE {}; ", name),
+        })
+        .collect();
+    let schema = Arc::new(parse_message_type(&format!(
+        "message table {{ {} }}",
+        fields
+    ))?);
+    let properties = Arc::new(WriterProperties::builder().build());
+
+    // write all columns in a single row group
+    let mut file = SerializedFileWriter::new(writer, schema, properties)?;
+    let mut row_group = file.next_row_group()?;
+    for (_, col) in &columns {
+        let Some(mut column_writer) = row_group.next_column()? else {
+            break;
+        };
+        match col {
+            Column::UInt(v) => {
+                let values: Vec<i64> = v.iter().map(|&x| x as i64).collect();
+                column_writer
+                    .typed::<Int64Type>()
+                    .write_batch(&values, None, None)?;
+            }
+            Column::Float(v) => {
+                column_writer
+                    .typed::<DoubleType>()
+                    .write_batch(v, None, None)?;
+            }
+        }
+        column_writer.close()?;
+    }
+    row_group.close()?;
+    file.close()?;
+
+    Ok(())
+}
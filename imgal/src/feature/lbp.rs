@@ -0,0 +1,190 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, ArrayView2, Zip};
+
+use crate::error::ImgalError;
+use crate::measure::profile::bilinear;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the local binary pattern (LBP) codes of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function compares each pixel with `points` neighbors sampled evenly on
+/// a circle of the given `radius` (bilinear interpolation, clamped edges). Each
+/// neighbor greater than or equal to the center pixel sets one bit of the
+/// pattern:
+///
+/// ```text
+/// LBP = Σ s(gₚ - g꜀) 2ᵖ
+/// ```
+///
+/// With `uniform` set, the rotation invariant uniform mapping is used instead:
+/// patterns with at most two circular 0/1 transitions are coded by their
+/// number of set bits (0 to `points`) and all other patterns are coded as
+/// `points + 1`, giving `points + 2` codes.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `radius`: The radius of the sampling circle in pixels, default = 1.0.
+/// * `points`: The number of circular sampling points, default = 8.
+/// * `uniform`: If `true`, use the rotation invariant uniform mapping, default
+///    = `true`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The LBP code of each pixel.
+/// * `Err(ImgalError)`: If `radius` is not positive or if `points` is not
+///    between 1 and 63.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TPAMI.2002.1017623>
+pub fn local_binary_pattern<T>(
+    data: ArrayView2<T>,
+    radius: Option<f64>,
+    points: Option<usize>,
+    uniform: Option<bool>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let radius = radius.unwrap_or(1.0);
+    let points = points.unwrap_or(8);
+    let uniform = uniform.unwrap_or(true);
+
+    // check if parameters are valid
+    if radius.is_nan() || radius <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "radius",
+            value: radius,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if !(1..=63).contains(&points) {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "points",
+            value: points as f64,
+            min: 1.0,
+            max: 63.0,
+        });
+    }
+
+    // sampling offsets on the circle, (row, col), rounded to avoid
+    // interpolating on-grid neighbors with floating point residuals
+    let round = |v: f64| (v * 1e5).round() / 1e5;
+    let offsets: Vec<(f64, f64)> = (0..points)
+        .map(|p| {
+            let theta = 2.0 * PI * p as f64 / points as f64;
+            (round(-radius * theta.sin()), round(radius * theta.cos()))
+        })
+        .collect();
+
+    // neighbors outside of the image are clamped to the nearest edge
+    let image = data.mapv(|v| v.to_f64());
+    let (max_r, max_c) = (image.nrows() as f64 - 1.0, image.ncols() as f64 - 1.0);
+    let mut codes = Array2::<usize>::zeros(data.dim());
+    Zip::indexed(&mut codes).par_for_each(|(r, c), code| {
+        let center = image[[r, c]];
+        let bits: Vec<bool> = offsets
+            .iter()
+            .map(|&(dr, dc)| {
+                let (sr, sc) = (
+                    (r as f64 + dr).clamp(0.0, max_r),
+                    (c as f64 + dc).clamp(0.0, max_c),
+                );
+                bilinear(image.view(), sr, sc).unwrap_or(center) >= center
+            })
+            .collect();
+        *code = if uniform {
+            let transitions = (0..points)
+                .filter(|&p| bits[p] != bits[(p + 1) % points])
+                .count();
+            if transitions <= 2 {
+                bits.iter().filter(|&&b| b).count()
+            } else {
+                points + 1
+            }
+        } else {
+            bits.iter()
+                .enumerate()
+                .filter(|(_, b)| **b)
+                .map(|(p, _)| 1usize << p)
+                .sum()
+        };
+    });
+
+    Ok(codes)
+}
+
+/// Compute normalized local binary pattern histograms per labeled region.
+///
+/// # Description
+///
+/// This function computes the normalized histogram of the LBP codes inside
+/// each labeled region, a texture descriptor of the region. Codes with label 0
+/// are treated as background and ignored when `labels` is given.
+///
+/// # Arguments
+///
+/// * `codes`: The LBP codes, see [`local_binary_pattern`].
+/// * `n_bins`: The number of histogram bins, `points + 2` for uniform codes or
+///    `2^points` otherwise.
+/// * `labels`: The region label image with the same shape as `codes`. If
+///    `None`, a single histogram of the whole image is computed.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The histograms with a shape of `(max_label + 1,
+///    n_bins)`, where row `i` is the histogram of label `i`. Row 0 is the
+///    whole image histogram if `labels` is `None` and all zeros otherwise.
+///    Each non-empty row sums to 1.0.
+/// * `Err(ImgalError)`: If the `codes` and `labels` shapes do not match or if
+///    a code is not less than `n_bins`.
+pub fn local_binary_pattern_histogram(
+    codes: ArrayView2<usize>,
+    n_bins: usize,
+    labels: Option<ArrayView2<usize>>,
+) -> Result<Array2<f64>, ImgalError> {
+    if let Some(l) = labels.as_ref()
+        && l.shape() != codes.shape()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: codes.shape().to_vec(),
+            shape_b: l.shape().to_vec(),
+        });
+    }
+    if codes.iter().any(|&c| c >= n_bins) {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid LBP code, all codes must be less than the number of bins.",
+        });
+    }
+
+    // accumulate the code counts of each region
+    let n_labels = labels
+        .as_ref()
+        .map(|l| l.iter().copied().max().unwrap_or(0) + 1)
+        .unwrap_or(1);
+    let mut hist = Array2::<f64>::zeros((n_labels, n_bins));
+    match labels.as_ref() {
+        Some(l) => Zip::from(&codes).and(l).for_each(|&c, &lbl| {
+            if lbl > 0 {
+                hist[[lbl, c]] += 1.0;
+            }
+        }),
+        None => codes.iter().for_each(|&c| hist[[0, c]] += 1.0),
+    }
+
+    // normalize each region histogram
+    hist.rows_mut().into_iter().for_each(|mut row| {
+        let total = row.sum();
+        if total > 0.0 {
+            row /= total;
+        }
+    });
+
+    Ok(hist)
+}
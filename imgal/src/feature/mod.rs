@@ -0,0 +1,6 @@
+//! Feature detection and analysis functions.
+pub mod lbp;
+pub use lbp::{local_binary_pattern, local_binary_pattern_histogram};
+pub mod structure_tensor;
+pub use structure_tensor::structure_tensor_2d;
+pub use structure_tensor::structure_tensor_3d;
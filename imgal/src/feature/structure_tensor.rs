@@ -0,0 +1,227 @@
+use ndarray::{Array2, Array3, Array4, ArrayD, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::gaussian::gaussian_nd_mut;
+use crate::filter::gradient::central_difference;
+use crate::traits::numeric::ToFloat64;
+
+// orientation, coherence and energy maps of a 2-dimensional image
+type StructureTensor2d = (Array2<f64>, Array2<f64>, Array2<f64>);
+
+// orientation vectors, coherence and energy maps of a 3-dimensional image
+type StructureTensor3d = (Array4<f64>, Array3<f64>, Array3<f64>);
+
+/// Compute the local orientation, coherence and energy of a 2-dimensional
+/// image with the structure tensor.
+///
+/// # Description
+///
+/// This function computes the structure tensor of each pixel, the Gaussian
+/// weighted average of the outer product of the image gradient:
+///
+/// ```text
+/// J = Gσ * | IcIc  IcIr |
+///          | IcIr  IrIr |
+/// ```
+///
+/// Where "Ic" and "Ir" are the column and row gradients and "Gσ" is a Gaussian
+/// window. From the eigenvalues λ₁ ≥ λ₂ of "J", the local structure is
+/// characterized by:
+///
+/// ```text
+/// orientation = ½ atan2(-2Jcr, Jrr - Jcc)
+/// coherence = (λ₁ - λ₂) / (λ₁ + λ₂)
+/// energy = λ₁ + λ₂
+/// ```
+///
+/// The orientation is the direction of the local structure (_e.g._ a fiber),
+/// perpendicular to the dominant gradient, in radians between -π/2 and π/2
+/// measured from the column axis towards the row axis. The coherence ranges
+/// from 0.0 (isotropic) to 1.0 (perfectly oriented).
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian integration window,
+///    default = 2.0.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>, Array2<f64>))`: The orientation, coherence
+///    and energy maps. The coherence is 0.0 where the energy is 0.0.
+/// * `Err(ImgalError)`: If `sigma` is not positive.
+pub fn structure_tensor_2d<T>(
+    data: ArrayView2<T>,
+    sigma: Option<f64>,
+) -> Result<StructureTensor2d, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let sigma = sigma.unwrap_or(2.0);
+    check_sigma(sigma)?;
+
+    // compute the smoothed gradient products
+    let image = data.mapv(|v| v.to_f64()).into_dyn();
+    let grads = central_difference(&image);
+    let (gr, gc) = (&grads[0], &grads[1]);
+    let jcc = smooth(gc * gc, sigma);
+    let jcr = smooth(gc * gr, sigma);
+    let jrr = smooth(gr * gr, sigma);
+
+    // compute orientation, coherence and energy of each pixel
+    let dim = data.raw_dim();
+    let mut orientation = Array2::<f64>::zeros(dim);
+    let mut coherence = Array2::<f64>::zeros(dim);
+    let mut energy = Array2::<f64>::zeros(dim);
+    Zip::from(orientation.view_mut().into_dyn())
+        .and(coherence.view_mut().into_dyn())
+        .and(energy.view_mut().into_dyn())
+        .and(&jcc)
+        .and(&jcr)
+        .and(&jrr)
+        .par_for_each(|o, c, e, &cc, &cr, &rr| {
+            let trace = cc + rr;
+            let diff = ((cc - rr).powi(2) + 4.0 * cr * cr).sqrt();
+            *o = 0.5 * (-2.0 * cr).atan2(rr - cc);
+            *e = trace;
+            *c = if trace > 0.0 { diff / trace } else { 0.0 };
+        });
+
+    Ok((orientation, coherence, energy))
+}
+
+/// Compute the local orientation, coherence and energy of a 3-dimensional
+/// image with the structure tensor.
+///
+/// # Description
+///
+/// This function computes the 3 x 3 structure tensor of each voxel (see
+/// [`structure_tensor_2d`]). The local orientation is the eigenvector of the
+/// smallest eigenvalue, the direction of least intensity change (_e.g._ along
+/// a fiber). With eigenvalues λ₁ ≥ λ₂ ≥ λ₃:
+///
+/// ```text
+/// coherence = (λ₁ - λ₃) / (λ₁ + λ₃)
+/// energy = λ₁ + λ₂ + λ₃
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian integration window,
+///    default = 2.0.
+///
+/// # Returns
+///
+/// * `Ok((Array4<f64>, Array3<f64>, Array3<f64>))`: The unit orientation
+///    vectors with a shape of `(pln, row, col, 3)`, in (pln, row, col)
+///    component order, and the coherence and energy maps.
+/// * `Err(ImgalError)`: If `sigma` is not positive.
+pub fn structure_tensor_3d<T>(
+    data: ArrayView3<T>,
+    sigma: Option<f64>,
+) -> Result<StructureTensor3d, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let sigma = sigma.unwrap_or(2.0);
+    check_sigma(sigma)?;
+
+    // compute the smoothed gradient products, upper triangle in row-major order
+    let image = data.mapv(|v| v.to_f64()).into_dyn();
+    let grads = central_difference(&image);
+    let mut products: Vec<ArrayD<f64>> = Vec::with_capacity(6);
+    for i in 0..3 {
+        for j in i..3 {
+            products.push(smooth(&grads[i] * &grads[j], sigma));
+        }
+    }
+
+    // eigen decompose the tensor of each voxel
+    let (p, r, c) = data.dim();
+    let mut orientation = Array4::<f64>::zeros((p, r, c, 3));
+    let mut coherence = Array3::<f64>::zeros((p, r, c));
+    let mut energy = Array3::<f64>::zeros((p, r, c));
+    Zip::indexed(orientation.lanes_mut(Axis(3)))
+        .and(&mut coherence)
+        .and(&mut energy)
+        .par_for_each(|idx, mut o, co, e| {
+            let t = |k: usize| products[k][[idx.0, idx.1, idx.2]];
+            let m = [[t(0), t(1), t(2)], [t(1), t(3), t(4)], [t(2), t(4), t(5)]];
+            let (vals, vecs) = jacobi_eigen_3x3(m);
+            // sort eigenvalue indices in descending order
+            let mut order = [0, 1, 2];
+            order.sort_by(|&a, &b| vals[b].total_cmp(&vals[a]));
+            let (l1, l3) = (vals[order[0]].max(0.0), vals[order[2]].max(0.0));
+            (0..3).for_each(|k| o[k] = vecs[k][order[2]]);
+            *e = vals.iter().map(|v| v.max(0.0)).sum();
+            *co = if l1 + l3 > 0.0 {
+                (l1 - l3) / (l1 + l3)
+            } else {
+                0.0
+            };
+        });
+
+    Ok((orientation, coherence, energy))
+}
+
+/// Check that the integration window sigma is positive.
+fn check_sigma(sigma: f64) -> Result<(), ImgalError> {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "sigma",
+            value: sigma,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    Ok(())
+}
+
+/// Smooth a gradient product with the Gaussian integration window.
+fn smooth(mut data: ArrayD<f64>, sigma: f64) -> ArrayD<f64> {
+    gaussian_nd_mut(&mut data, sigma);
+
+    data
+}
+
+/// Compute the eigenvalues and eigenvectors (as columns) of a symmetric 3 x 3
+/// matrix with the cyclic Jacobi method.
+fn jacobi_eigen_3x3(mut a: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..50 {
+        let off = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        if off < 1e-30 {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1e-300 {
+                continue;
+            }
+            // rotation angle that zeroes a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let t = if theta == 0.0 { 1.0 } else { t };
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut() {
+                let (akp, akq) = (row[p], row[q]);
+                row[p] = c * akp - s * akq;
+                row[q] = s * akp + c * akq;
+            }
+            let (ap, aq) = (a[p], a[q]);
+            a[p] = std::array::from_fn(|k| c * ap[k] - s * aq[k]);
+            a[q] = std::array::from_fn(|k| s * ap[k] + c * aq[k]);
+            for row in v.iter_mut() {
+                let (vp, vq) = (row[p], row[q]);
+                row[p] = c * vp - s * vq;
+                row[q] = s * vp + c * vq;
+            }
+        }
+    }
+
+    ([a[0][0], a[1][1], a[2][2]], v)
+}
@@ -0,0 +1,149 @@
+use ndarray::{Array2, ArrayView1, ArrayView2, Axis, Zip};
+use rustfft::num_complex::Complex;
+
+use crate::error::ImgalError;
+use crate::statistics::median;
+use crate::traits::numeric::ToFloat64;
+use crate::transform::fft::fft2;
+
+/// Orientations of fixed-pattern stripe noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StripeDirection {
+    /// Row stripes, each row has its own offset.
+    Horizontal,
+    /// Column stripes, each column has its own offset.
+    Vertical,
+    /// Both row and column stripes.
+    Both,
+}
+
+/// Stripe removal methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestripeMethod {
+    /// Subtract the deviation of each line median from a moving median of the
+    /// line medians over `window` lines.
+    Median { window: usize },
+    /// Suppress the Fourier coefficients of line offsets. Coefficients within
+    /// `width` bins of the stripe axis and above the `cutoff` frequency bin
+    /// along it are removed.
+    FftNotch { width: usize, cutoff: usize },
+}
+
+/// Remove row and/or column fixed-pattern noise from a 2-dimensional image.
+///
+/// # Description
+///
+/// This function removes stripe artifacts caused by per-line offsets, such as
+/// the column noise of sCMOS sensors or the line noise of resonant scanners.
+/// With the median method, the median of each line is compared to a moving
+/// median of neighboring line medians, so that large scale intensity gradients
+/// are preserved while line-to-line offsets are subtracted. With the FFT notch
+/// method, the line offsets are removed in the frequency domain, where they
+/// concentrate on a single axis.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `direction`: The stripe orientation, default = `StripeDirection::Both`.
+/// * `method`: The stripe removal method, default =
+///    `DestripeMethod::Median { window: 31 }`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The destriped image.
+/// * `Err(ImgalError)`: If the median `window` or the notch `width` is 0.
+pub fn destripe<T>(
+    data: ArrayView2<T>,
+    direction: Option<StripeDirection>,
+    method: Option<DestripeMethod>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let direction = direction.unwrap_or(StripeDirection::Both);
+    let method = method.unwrap_or(DestripeMethod::Median { window: 31 });
+
+    // check if parameters are valid
+    match method {
+        DestripeMethod::Median { window: 0 } => {
+            return Err(ImgalError::InvalidArrayParameterValueEqual {
+                param_name: "window",
+                value: 0,
+            });
+        }
+        DestripeMethod::FftNotch { width: 0, .. } => {
+            return Err(ImgalError::InvalidArrayParameterValueEqual {
+                param_name: "width",
+                value: 0,
+            });
+        }
+        _ => {}
+    }
+
+    // row stripes are constant along axis 1, column stripes along axis 0
+    let axes: &[usize] = match direction {
+        StripeDirection::Horizontal => &[1],
+        StripeDirection::Vertical => &[0],
+        StripeDirection::Both => &[1, 0],
+    };
+    let mut output = data.mapv(|v| v.to_f64());
+    for &axis in axes {
+        match method {
+            DestripeMethod::Median { window } => median_destripe(&mut output, axis, window),
+            DestripeMethod::FftNotch { width, cutoff } => {
+                notch_destripe(&mut output, axis, width, cutoff)
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Subtract line median offsets of stripes constant along `axis`.
+fn median_destripe(data: &mut Array2<f64>, axis: usize, window: usize) {
+    // median of each line and the moving median of the line medians
+    let profile: Vec<f64> = data
+        .lanes(Axis(axis))
+        .into_iter()
+        .map(|ln| median(&mut ln.to_vec()))
+        .collect();
+    let n = profile.len() as isize;
+    let half = (window / 2) as isize;
+    let trend: Vec<f64> = (0..n)
+        .map(|i| {
+            let mut w: Vec<f64> = (i - half..=i + half)
+                .map(|k| profile[k.clamp(0, n - 1) as usize])
+                .collect();
+            median(&mut w)
+        })
+        .collect();
+
+    // subtract the offset of each line
+    Zip::from(data.lanes_mut(Axis(axis)))
+        .and(&ArrayView1::from(&profile))
+        .and(&ArrayView1::from(&trend))
+        .par_for_each(|mut ln, &p, &t| ln -= p - t);
+}
+
+/// Remove the Fourier coefficients of stripes constant along `axis`.
+fn notch_destripe(data: &mut Array2<f64>, axis: usize, width: usize, cutoff: usize) {
+    let (rows, cols) = data.dim();
+    let mut buf = data.mapv(|v| Complex::new(v, 0.0));
+    fft2(&mut buf, false);
+
+    // stripes constant along an axis have no frequency content along it
+    let dist = |k: usize, n: usize| k.min(n - k);
+    buf.indexed_iter_mut().for_each(|((r, c), v)| {
+        let (along, across) = if axis == 1 {
+            (dist(c, cols), dist(r, rows))
+        } else {
+            (dist(r, rows), dist(c, cols))
+        };
+        if along < width && across > cutoff {
+            *v = Complex::new(0.0, 0.0);
+        }
+    });
+    fft2(&mut buf, true);
+    Zip::from(data).and(&buf).par_for_each(|d, b| *d = b.re);
+}
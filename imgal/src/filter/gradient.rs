@@ -0,0 +1,242 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array, Array2, ArrayView2, Axis, Dimension, Zip, s};
+use rustfft::num_complex::Complex;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+use crate::transform::fft::fft2;
+
+/// Compute the gradient of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the forward differences of an image along the rows
+/// and columns:
+///
+/// ```text
+/// ∂ᵣI(r, c) = I(r + 1, c) - I(r, c)
+/// ∂꜀I(r, c) = I(r, c + 1) - I(r, c)
+/// ```
+///
+/// The differences across the last row and column are 0.0 (_i.e._ Neumann
+/// boundaries), so that [`divergence`] is the negative adjoint of the
+/// gradient and `divergence(gradient(I))` equals [`laplacian`].
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+///
+/// # Returns
+///
+/// * `(Array2<f64>, Array2<f64>)`: The row and column gradient images.
+pub fn gradient<T>(data: ArrayView2<T>) -> (Array2<f64>, Array2<f64>)
+where
+    T: ToFloat64,
+{
+    let (rows, cols) = data.dim();
+    let mut grad_r = Array2::<f64>::zeros((rows, cols));
+    let mut grad_c = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut grad_r)
+        .and(&mut grad_c)
+        .par_for_each(|(r, c), gr, gc| {
+            let v = data[[r, c]].to_f64();
+            if r + 1 < rows {
+                *gr = data[[r + 1, c]].to_f64() - v;
+            }
+            if c + 1 < cols {
+                *gc = data[[r, c + 1]].to_f64() - v;
+            }
+        });
+
+    (grad_r, grad_c)
+}
+
+/// Compute the divergence of a 2-dimensional vector field.
+///
+/// # Description
+///
+/// This function computes the backward differences of a vector field (_e.g._
+/// a modified image gradient) along the rows and columns:
+///
+/// ```text
+/// div(r, c) = Fᵣ(r, c) - Fᵣ(r - 1, c) + F꜀(r, c) - F꜀(r, c - 1)
+/// ```
+///
+/// The field is 0.0 before the first and across the last row and column
+/// (_i.e._ Neumann boundaries), matching [`gradient`].
+///
+/// # Arguments
+///
+/// * `grad_row`: The row component of the vector field.
+/// * `grad_col`: The column component of the vector field.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The divergence image.
+/// * `Err(ImgalError)`: If `grad_row` and `grad_col` shapes do not match.
+pub fn divergence(
+    grad_row: ArrayView2<f64>,
+    grad_col: ArrayView2<f64>,
+) -> Result<Array2<f64>, ImgalError> {
+    if grad_row.shape() != grad_col.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: grad_row.shape().to_vec(),
+            shape_b: grad_col.shape().to_vec(),
+        });
+    }
+
+    let (rows, cols) = grad_row.dim();
+    // the field is 0.0 across the last row and column
+    let fr = |r: usize, c: usize| if r + 1 < rows { grad_row[[r, c]] } else { 0.0 };
+    let fc = |r: usize, c: usize| if c + 1 < cols { grad_col[[r, c]] } else { 0.0 };
+    let mut output = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        let mut v = fr(r, c) + fc(r, c);
+        if r > 0 {
+            v -= fr(r - 1, c);
+        }
+        if c > 0 {
+            v -= fc(r, c - 1);
+        }
+        *o = v;
+    });
+
+    Ok(output)
+}
+
+/// Compute the Laplacian of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the 5-point discrete Laplacian of an image:
+///
+/// ```text
+/// ∇²I(r, c) = Σₙ (I(n) - I(r, c))
+/// ```
+///
+/// Where "n" are the 4-connected neighbors of the pixel within the image
+/// (_i.e._ Neumann boundaries), so that the Laplacian sums to 0.0 and can be
+/// inverted by [`poisson_solve`].
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+///
+/// # Returns
+///
+/// * `Array2<f64>`: The Laplacian image.
+pub fn laplacian<T>(data: ArrayView2<T>) -> Array2<f64>
+where
+    T: ToFloat64,
+{
+    let (rows, cols) = data.dim();
+    let mut output = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        let v = data[[r, c]].to_f64();
+        let neighbors = [
+            (r > 0).then(|| (r - 1, c)),
+            (r + 1 < rows).then_some((r + 1, c)),
+            (c > 0).then(|| (r, c - 1)),
+            (c + 1 < cols).then_some((r, c + 1)),
+        ];
+        *o = neighbors
+            .iter()
+            .flatten()
+            .map(|&(nr, nc)| data[[nr, nc]].to_f64() - v)
+            .sum();
+    });
+
+    output
+}
+
+/// Solve the Poisson equation of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the image "u" whose Laplacian (see [`laplacian`])
+/// equals the right-hand side "f":
+///
+/// ```text
+/// ∇²u = f
+/// ```
+///
+/// With Neumann boundaries. The image is mirrored into a (2 * row, 2 * col)
+/// periodic image, which diagonalizes the Laplacian in the Fourier domain:
+///
+/// ```text
+/// û(k, l) = f̂(k, l) / (2cos(πk / row) + 2cos(πl / col) - 4)
+/// ```
+///
+/// The solution is unique up to a constant, set by `mean`. The right-hand side
+/// of a Neumann problem must sum to 0.0, otherwise its mean is ignored (_i.e._
+/// the least squares solution). Solving with the divergence of a modified
+/// gradient field (see [`divergence`]) reconstructs an image from its edited
+/// gradients, _e.g._ for gradient-domain background removal or seamless
+/// blending.
+///
+/// # Arguments
+///
+/// * `rhs`: The right-hand side, "f".
+/// * `mean`: The mean of the solution, default = 0.0.
+///
+/// # Returns
+///
+/// * `Array2<f64>`: The solution image, "u".
+pub fn poisson_solve(rhs: ArrayView2<f64>, mean: Option<f64>) -> Array2<f64> {
+    // set optional parameters if needed
+    let mean = mean.unwrap_or(0.0);
+
+    let (rows, cols) = rhs.dim();
+    if rows == 0 || cols == 0 {
+        return Array2::<f64>::zeros((rows, cols));
+    }
+
+    // mirror the right-hand side for Neumann boundaries
+    let mut buf = Array2::from_shape_fn((2 * rows, 2 * cols), |(r, c)| {
+        let r = if r < rows { r } else { 2 * rows - 1 - r };
+        let c = if c < cols { c } else { 2 * cols - 1 - c };
+        Complex::new(rhs[[r, c]], 0.0)
+    });
+    fft2(&mut buf, false);
+
+    // divide by the eigenvalues of the periodic Laplacian, dropping the mean
+    buf.indexed_iter_mut().for_each(|((k, l), v)| {
+        let eigen = 2.0 * (PI * k as f64 / rows as f64).cos()
+            + 2.0 * (PI * l as f64 / cols as f64).cos()
+            - 4.0;
+        *v = if k == 0 && l == 0 {
+            Complex::new(0.0, 0.0)
+        } else {
+            *v / eigen
+        };
+    });
+    fft2(&mut buf, true);
+
+    buf.slice(s![..rows, ..cols]).mapv(|v| v.re + mean)
+}
+
+/// Compute the central difference gradient along each axis of an
+/// n-dimensional image with clamped edges, in axis order.
+pub(crate) fn central_difference<D>(data: &Array<f64, D>) -> Vec<Array<f64, D>>
+where
+    D: Dimension,
+{
+    (0..data.ndim())
+        .map(|a| {
+            let mut grad = Array::<f64, D>::zeros(data.raw_dim());
+            Zip::from(grad.lanes_mut(Axis(a)))
+                .and(data.lanes(Axis(a)))
+                .par_for_each(|mut g, d| {
+                    let n = d.len();
+                    (0..n).for_each(|i| {
+                        let (i0, i1) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                        if i1 > i0 {
+                            g[i] = (d[i1] - d[i0]) / (i1 - i0) as f64;
+                        }
+                    });
+                });
+            grad
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+use ndarray::{Array2, ArrayView2, Ix2, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::mean;
+use crate::traits::numeric::ToFloat64;
+
+/// Smooth a 2-dimensional image with a guided filter.
+///
+/// # Description
+///
+/// This function smooths an input image "p" (_e.g._ a lifetime map) with the
+/// edges of a guidance image "I" (_e.g._ the intensity image). Within each
+/// (2 * `radius` + 1)² window "ω", the output is modeled as a linear transform
+/// of the guide, fitted to the input by regularized least squares:
+///
+/// ```text
+/// aₖ = cov_ω(I, p) / (var_ω(I) + ε)
+/// bₖ = mean_ω(p) - aₖ * mean_ω(I)
+/// q = mean(a) * I + mean(b)
+/// ```
+///
+/// Where the window means are computed with a mean filter (see
+/// [`crate::filter::mean`]), so the filter runs in constant time per pixel
+/// independently of `radius`. Where the guide is flat (var ≪ ε) the input is
+/// averaged, while edges of the guide (var ≫ ε) are preserved in the output.
+/// Windows are truncated at the image edges. Non-finite input pixels propagate
+/// to all windows that contain them and should be filled beforehand.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image to smooth, "p".
+/// * `guide`: The 2-dimensional guidance image, "I". The input image can be
+///    its own guide for edge-preserving smoothing.
+/// * `radius`: The window radius in pixels.
+/// * `epsilon`: The regularization "ε" (ε > 0), in squared guide units. Guide
+///    edges with a variance well above `epsilon` are preserved.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The smoothed image.
+/// * `Err(ImgalError)`: If `data` and `guide` shapes do not match. If
+///    `epsilon` is not positive or NaN.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TPAMI.2012.213>
+pub fn guided<T, U>(
+    data: ArrayView2<T>,
+    guide: ArrayView2<U>,
+    radius: usize,
+    epsilon: f64,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+    U: ToFloat64,
+{
+    // check if parameters are valid
+    if data.shape() != guide.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data.shape().to_vec(),
+            shape_b: guide.shape().to_vec(),
+        });
+    }
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "epsilon",
+            value: epsilon,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    let p = data.mapv(|v| v.to_f64());
+    let i = guide.mapv(|v| v.to_f64());
+    let box_mean = |arr: &Array2<f64>| {
+        mean(arr.view().into_dyn(), radius)
+            .into_dimensionality::<Ix2>()
+            .unwrap()
+    };
+
+    // window statistics of the guide and input
+    let mean_i = box_mean(&i);
+    let mean_p = box_mean(&p);
+    let corr_ii = box_mean(&(&i * &i));
+    let corr_ip = box_mean(&(&i * &p));
+
+    // linear coefficients of each window
+    let mut a = Array2::<f64>::zeros(p.dim());
+    let mut b = Array2::<f64>::zeros(p.dim());
+    Zip::from(&mut a)
+        .and(&mut b)
+        .and(&mean_i)
+        .and(&mean_p)
+        .and(&corr_ii)
+        .and(&corr_ip)
+        .par_for_each(|a, b, &mi, &mp, &cii, &cip| {
+            let var = cii - mi * mi;
+            let cov = cip - mi * mp;
+            *a = cov / (var + epsilon);
+            *b = mp - *a * mi;
+        });
+
+    // average the coefficients of all windows covering each pixel
+    let mean_a = box_mean(&a);
+    let mean_b = box_mean(&b);
+    let mut output = Array2::<f64>::zeros(p.dim());
+    Zip::from(&mut output)
+        .and(&mean_a)
+        .and(&mean_b)
+        .and(&i)
+        .par_for_each(|o, &ma, &mb, &iv| {
+            *o = ma * iv + mb;
+        });
+
+    Ok(output)
+}
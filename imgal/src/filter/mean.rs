@@ -0,0 +1,80 @@
+use ndarray::{ArrayD, ArrayViewD};
+use rayon::prelude::*;
+
+use crate::image::integral_image;
+use crate::traits::numeric::ToFloat64;
+
+/// Smooth an n-dimensional array with a mean (box) filter.
+///
+/// # Description
+///
+/// This function replaces each element of an n-dimensional array (_e.g._ a 2D
+/// image or 3D stack) by the mean of the (2 * `radius` + 1)ⁿ box centered on
+/// it. The box sums are computed from the integral image of the array (see
+/// [`crate::image::integral_image`]) with 2ⁿ lookups per element, so the
+/// filter runs in constant time per element independently of `radius`. Boxes
+/// are truncated at the array edges and averaged over the elements within the
+/// array only.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `radius`: The box radius in elements. If `radius` is 0, the array is
+///    returned unfiltered.
+///
+/// # Returns
+///
+/// * `ArrayD<f64>`: The smoothed array.
+pub fn mean<T>(data: ArrayViewD<T>, radius: usize) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    let integral = integral_image(data.view());
+    let shape = data.shape();
+    let strides: Vec<usize> = integral.strides().iter().map(|&s| s as usize).collect();
+    let table = integral.as_slice().unwrap();
+    let ndim = data.ndim();
+
+    // the output is in standard layout, element indices are recovered from
+    // the flat index
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    let out_strides: Vec<usize> = output.strides().iter().map(|&s| s as usize).collect();
+    let out_slice = output.as_slice_mut().unwrap();
+    out_slice.par_iter_mut().enumerate().for_each(|(i, o)| {
+        // truncate the box at the array edges
+        let bounds = |d: usize| {
+            let idx = (i / out_strides[d]) % shape[d];
+            let lo = idx.saturating_sub(radius);
+            let hi = (idx + radius + 1).min(shape[d]);
+            (lo, hi)
+        };
+        let count: usize = (0..ndim)
+            .map(|d| {
+                let (lo, hi) = bounds(d);
+                hi - lo
+            })
+            .product();
+
+        // inclusion-exclusion over the box corners of the integral image
+        let sum: f64 = (0..1usize << ndim)
+            .map(|corner| {
+                let mut sign = 1.0;
+                let offset: usize = (0..ndim)
+                    .map(|d| {
+                        let (lo, hi) = bounds(d);
+                        if corner & (1 << d) == 0 {
+                            hi * strides[d]
+                        } else {
+                            sign = -sign;
+                            lo * strides[d]
+                        }
+                    })
+                    .sum();
+                sign * table[offset]
+            })
+            .sum();
+        *o = sum / count as f64;
+    });
+
+    output
+}
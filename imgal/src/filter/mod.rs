@@ -1,3 +1,19 @@
 //! Filter functions.
 pub mod convolve;
 pub use convolve::{fft_convolve_1d, fft_deconvolve_1d};
+pub mod destripe;
+pub use destripe::{DestripeMethod, StripeDirection, destripe};
+pub mod gaussian;
+pub use gaussian::{gaussian_2d, gaussian_2d_into};
+pub mod gradient;
+pub use gradient::{divergence, gradient, laplacian, poisson_solve};
+pub mod guided;
+pub use guided::guided;
+pub mod kuwahara;
+pub use kuwahara::kuwahara;
+pub mod mean;
+pub use mean::mean;
+pub mod temporal;
+pub use temporal::{Baseline, TemporalFilter, delta_f_over_f, temporal};
+pub mod wavelet;
+pub use wavelet::{ShrinkageRule, wavelet_denoise};
@@ -0,0 +1,259 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Temporal smoothing filters, see [`temporal`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemporalFilter {
+    /// Mean of the `window` frames centered on each frame.
+    MovingAverage { window: usize },
+    /// Median of the `window` frames centered on each frame, which removes
+    /// single frame spikes without blurring steps.
+    MovingMedian { window: usize },
+    /// Causal exponential smoothing, `s(t) = α·x(t) + (1 - α)·s(t - 1)`, with
+    /// the smoothing factor `alpha` in (0.0, 1.0].
+    Exponential { alpha: f64 },
+}
+
+/// Baseline fluorescence (F₀) estimates, see [`delta_f_over_f`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    /// The mean of the frames in `start..end`, _e.g._ the frames before a
+    /// stimulus.
+    Frames { start: usize, end: usize },
+    /// The `percentile` (0.0 to 100.0) of the `window` frames centered on
+    /// each frame, a running baseline that follows slow photobleaching.
+    Percentile { percentile: f64, window: usize },
+}
+
+/// Smooth an n-dimensional stack along its time axis.
+///
+/// # Description
+///
+/// This function filters each lane (_i.e._ the time trace of each pixel) of
+/// an n-dimensional stack along `axis` with a moving average, a moving median
+/// or exponential smoothing. Lanes are processed in parallel. Moving windows
+/// are truncated at the start and end of the lanes, and an even `window` is
+/// extended by one frame to be centered.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional stack.
+/// * `filter`: The temporal filter.
+/// * `axis`: The time axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The smoothed stack, with the shape of `data`.
+/// * `Err(ImgalError)`: If the axis is out of bounds. If `window` is 0. If
+///    `alpha` is outside of (0.0, 1.0].
+pub fn temporal<T>(
+    data: ArrayViewD<T>,
+    filter: TemporalFilter,
+    axis: Option<usize>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= data.ndim() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: data.ndim(),
+        });
+    }
+    match filter {
+        TemporalFilter::MovingAverage { window } | TemporalFilter::MovingMedian { window } => {
+            check_window(window)?
+        }
+        TemporalFilter::Exponential { alpha } => {
+            if !(f64::MIN_POSITIVE..=1.0).contains(&alpha) {
+                return Err(ImgalError::InvalidParameterValueOutsideRange {
+                    param_name: "alpha",
+                    value: alpha,
+                    min: f64::MIN_POSITIVE,
+                    max: 1.0,
+                });
+            }
+        }
+    }
+
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    Zip::from(output.lanes_mut(Axis(a)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut out, ln| {
+            let trace: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            match filter {
+                TemporalFilter::MovingAverage { window } => {
+                    // running sum over the truncated window
+                    let prefix: Vec<f64> = std::iter::once(0.0)
+                        .chain(trace.iter().scan(0.0, |s, &v| {
+                            *s += v;
+                            Some(*s)
+                        }))
+                        .collect();
+                    out.iter_mut().enumerate().for_each(|(t, o)| {
+                        let (lo, hi) = window_bounds(t, window, trace.len());
+                        *o = (prefix[hi] - prefix[lo]) / (hi - lo) as f64;
+                    });
+                }
+                TemporalFilter::MovingMedian { window } => {
+                    out.iter_mut().enumerate().for_each(|(t, o)| {
+                        let (lo, hi) = window_bounds(t, window, trace.len());
+                        *o = percentile(&mut trace[lo..hi].to_vec(), 50.0);
+                    });
+                }
+                TemporalFilter::Exponential { alpha } => {
+                    let mut s = trace.first().copied().unwrap_or(0.0);
+                    out.iter_mut().zip(&trace).for_each(|(o, &v)| {
+                        s = alpha * v + (1.0 - alpha) * s;
+                        *o = s;
+                    });
+                }
+            }
+        });
+
+    Ok(output)
+}
+
+/// Compute the relative fluorescence change (ΔF/F₀) of an n-dimensional
+/// stack.
+///
+/// # Description
+///
+/// This function computes the relative fluorescence change of each lane
+/// (_i.e._ the time trace of each pixel or region) of an n-dimensional stack
+/// along `axis`, as used to report calcium indicator activity:
+///
+/// ```text
+/// ΔF/F₀(t) = (F(t) - F₀) / F₀
+/// ```
+///
+/// Where "F₀" is the baseline fluorescence, either the mean of a range of
+/// frames or a running percentile of the trace. Lanes are processed in
+/// parallel. Frames with a zero baseline are NaN. The background should be
+/// subtracted from `data` beforehand.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional stack.
+/// * `baseline`: The baseline (F₀) estimate.
+/// * `axis`: The time axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The ΔF/F₀ stack, with the shape of `data`.
+/// * `Err(ImgalError)`: If the axis is out of bounds. If the baseline frame
+///    range is empty or exceeds the axis length. If `window` is 0. If
+///    `percentile` is outside of 0.0 to 100.0.
+pub fn delta_f_over_f<T>(
+    data: ArrayViewD<T>,
+    baseline: Baseline,
+    axis: Option<usize>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= data.ndim() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: data.ndim(),
+        });
+    }
+    match baseline {
+        Baseline::Frames { start, end } => {
+            if end > data.len_of(Axis(a)) {
+                return Err(ImgalError::InvalidArrayParameterValueGreater {
+                    param_name: "end",
+                    value: data.len_of(Axis(a)),
+                });
+            }
+            if start >= end {
+                return Err(ImgalError::InvalidArrayParameterValueGreater {
+                    param_name: "start",
+                    value: end.saturating_sub(1),
+                });
+            }
+        }
+        Baseline::Percentile { percentile, window } => {
+            check_window(window)?;
+            if !(0.0..=100.0).contains(&percentile) {
+                return Err(ImgalError::InvalidParameterValueOutsideRange {
+                    param_name: "percentile",
+                    value: percentile,
+                    min: 0.0,
+                    max: 100.0,
+                });
+            }
+        }
+    }
+
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    Zip::from(output.lanes_mut(Axis(a)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut out, ln| {
+            let trace: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let f0: Vec<f64> = match baseline {
+                Baseline::Frames { start, end } => {
+                    let mean = trace[start..end].iter().sum::<f64>() / (end - start) as f64;
+                    vec![mean; trace.len()]
+                }
+                Baseline::Percentile {
+                    percentile: p,
+                    window,
+                } => (0..trace.len())
+                    .map(|t| {
+                        let (lo, hi) = window_bounds(t, window, trace.len());
+                        percentile(&mut trace[lo..hi].to_vec(), p)
+                    })
+                    .collect(),
+            };
+            out.iter_mut()
+                .zip(trace.iter().zip(&f0))
+                .for_each(|(o, (&f, &b))| {
+                    *o = if b != 0.0 { (f - b) / b } else { f64::NAN };
+                });
+        });
+
+    Ok(output)
+}
+
+/// Check that a moving window is not empty.
+fn check_window(window: usize) -> Result<(), ImgalError> {
+    if window == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "window",
+            value: 0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the (start, end) frame range of the window centered on frame `t`,
+/// truncated to the lane length.
+fn window_bounds(t: usize, window: usize, len: usize) -> (usize, usize) {
+    let half = window / 2;
+
+    (t.saturating_sub(half), (t + half + 1).min(len))
+}
+
+/// Percentile of a slice with linear interpolation, sorting it in place.
+fn percentile(data: &mut [f64], p: f64) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    data.sort_by(|a, b| a.total_cmp(b));
+    let pos = p / 100.0 * (data.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+
+    data[lo] + (data[hi] - data[lo]) * (pos - lo as f64)
+}
@@ -0,0 +1,422 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
+use rustfft::num_complex::Complex;
+
+use crate::error::{ImgalError, check_axis};
+use crate::fitting::linalg::solve;
+use crate::traits::numeric::ToFloat64;
+
+/// Estimate a lifetime by log-linear regression over the tail of a decay.
+///
+/// # Description
+///
+/// This function fits a monoexponential model to the tail of a 1-dimensional
+/// decay curve by weighted linear regression of the logarithm of the counts
+/// against time:
+///
+/// ```text
+/// ln I(t) = ln A - t/τ
+/// ```
+///
+/// Each bin is weighted by its counts, the inverse of the Poisson variance of
+/// `ln I`, and bins with no counts are ignored. The time `t` is measured from
+/// the `start` bin with a bin width of `period / n`. The fit is non-iterative
+/// and is useful as a fast lifetime estimate or as an initial guess for
+/// iterative fitters.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `start`: The first bin of the tail range, default = the peak bin.
+/// * `end`: The end bin (exclusive) of the tail range, default = `data.len()`.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The lifetime "τ" and the amplitude "A" at the `start`
+///    bin. If the tail range has less than 2 bins with counts or is not
+///    decaying, `(NaN, NaN)` is returned.
+/// * `Err(ImgalError)`: If `data` is empty. If `start >= end` or
+///    `end > data.len()`.
+pub fn tail_fit<T>(
+    data: &[T],
+    period: f64,
+    start: Option<usize>,
+    end: Option<usize>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    if data.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the decay curve must not be empty.",
+        });
+    }
+    let y: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    let (start, end) = tail_range(&y, start, end)?;
+
+    Ok(fit_tail(&y, period / y.len() as f64, start, end))
+}
+
+/// Estimate per-pixel lifetimes by log-linear regression over the tail of a
+/// 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function applies [`tail_fit`] to every decay curve of a 3-dimensional
+/// image in parallel. If `start` is `None`, the peak bin of each pixel is used.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `start`: The first bin of the tail range, default = the peak bin of each
+///    pixel.
+/// * `end`: The end bin (exclusive) of the tail range, default = the length of
+///    the decay axis.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>))`: The lifetime and amplitude images.
+///    Pixels that can not be fit are NaN.
+/// * `Err(ImgalError)`: If the axis is >= 3. If `start >= end` or `end` is
+///    greater than the length of the decay axis.
+pub fn tail_fit_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    start: Option<usize>,
+    end: Option<usize>,
+    axis: Option<usize>,
+) -> Result<(Array2<f64>, Array2<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    let n = data.len_of(Axis(a));
+    let end = end.unwrap_or(n);
+    if end > n {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "end",
+            value: n,
+        });
+    }
+    if let Some(s) = start
+        && s >= end
+    {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "start",
+            value: end - 1,
+        });
+    }
+
+    let dt = period / n as f64;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut taus = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut amps = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(&mut taus)
+        .and(&mut amps)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|t, am, ln| {
+            let y: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let s = start.unwrap_or_else(|| peak(&y[..end]));
+            (*t, *am) = fit_tail(&y, dt, s, end);
+        });
+
+    Ok((taus, amps))
+}
+
+/// Estimate multiexponential lifetimes of a decay with Prony's method.
+///
+/// # Description
+///
+/// This function estimates the lifetimes and amplitudes of a sum of
+/// exponentials with the least squares Prony method. The decay samples after
+/// `start` are modeled as:
+///
+/// ```text
+/// I(k) = Σᵢ Aᵢ × zᵢᵏ,  zᵢ = exp(-Δt/τᵢ)
+/// ```
+///
+/// The linear prediction coefficients of the samples are solved by least
+/// squares, the roots "zᵢ" of the resulting characteristic polynomial give the
+/// lifetimes and the amplitudes are solved by a second least squares fit. The
+/// bin width "Δt" is `period / n`. Prony's method is non-iterative but
+/// sensitive to noise, it is best used on the decay tail (after the IRF) and
+/// as an initial guess for iterative fitters.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `components`: The number of exponential components.
+/// * `start`: The first bin of the fitted range, default = the peak bin.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The lifetimes and amplitudes (at the `start`
+///    bin) of each component, sorted by increasing lifetime. Components with
+///    non-physical roots (_i.e._ not real or not between 0.0 and 1.0) have NaN
+///    lifetimes and amplitudes and are sorted last.
+/// * `Err(ImgalError)`: If `components` is 0. If there are less than
+///    `2 * components` samples after `start`.
+pub fn prony<T>(
+    data: &[T],
+    period: f64,
+    components: usize,
+    start: Option<usize>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    let y: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    let start = start.unwrap_or_else(|| peak(&y));
+    check_prony(y.len(), components, start)?;
+
+    Ok(fit_prony(&y[start..], period / y.len() as f64, components))
+}
+
+/// Estimate per-pixel multiexponential lifetimes of a 3-dimensional decay
+/// image with Prony's method.
+///
+/// # Description
+///
+/// This function applies [`prony`] to every decay curve of a 3-dimensional
+/// image in parallel. If `start` is `None`, the peak bin of each pixel is used
+/// and pixels with too few samples after the peak are NaN.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `components`: The number of exponential components.
+/// * `start`: The first bin of the fitted range, default = the peak bin of
+///    each pixel.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array3<f64>))`: The lifetime and amplitude images with
+///    shape `(rows, cols, components)`.
+/// * `Err(ImgalError)`: If the axis is >= 3. If `components` is 0. If there
+///    are less than `2 * components` samples after `start`.
+pub fn prony_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    components: usize,
+    start: Option<usize>,
+    axis: Option<usize>,
+) -> Result<(Array3<f64>, Array3<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    let n = data.len_of(Axis(a));
+    check_prony(n, components, start.unwrap_or(0))?;
+
+    let dt = period / n as f64;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut taus = Array3::<f64>::from_elem((shape[0], shape[1], components), f64::NAN);
+    let mut amps = Array3::<f64>::from_elem((shape[0], shape[1], components), f64::NAN);
+    Zip::from(taus.lanes_mut(Axis(2)))
+        .and(amps.lanes_mut(Axis(2)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut t, mut am, ln| {
+            let y: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let s = start.unwrap_or_else(|| peak(&y));
+            if n - s >= 2 * components {
+                let (ft, fa) = fit_prony(&y[s..], dt, components);
+                t.iter_mut().zip(ft).for_each(|(d, v)| *d = v);
+                am.iter_mut().zip(fa).for_each(|(d, v)| *d = v);
+            }
+        });
+
+    Ok((taus, amps))
+}
+
+/// Check that a decay of length `n` has enough samples for a Prony fit.
+fn check_prony(n: usize, components: usize, start: usize) -> Result<(), ImgalError> {
+    if components == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "components",
+            value: 0,
+        });
+    }
+    if n.saturating_sub(start) < 2 * components {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the decay must have at least 2 samples per component after the start bin.",
+        });
+    }
+
+    Ok(())
+}
+
+/// Fit the tail range of a decay curve by weighted log-linear regression.
+fn fit_tail(y: &[f64], dt: f64, start: usize, end: usize) -> (f64, f64) {
+    let (mut sw, mut st, mut sl, mut stt, mut stl) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    let mut count = 0;
+    y[start..end]
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v > 0.0)
+        .for_each(|(k, &v)| {
+            let t = k as f64 * dt;
+            let l = v.ln();
+            sw += v;
+            st += v * t;
+            sl += v * l;
+            stt += v * t * t;
+            stl += v * t * l;
+            count += 1;
+        });
+    let denom = sw * stt - st * st;
+    if count < 2 || denom <= 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let slope = (sw * stl - st * sl) / denom;
+    if slope >= 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let intercept = (sl - slope * st) / sw;
+
+    (-1.0 / slope, intercept.exp())
+}
+
+/// Fit a sum of exponentials to decay samples with the least squares Prony
+/// method.
+fn fit_prony(y: &[f64], dt: f64, p: usize) -> (Vec<f64>, Vec<f64>) {
+    let nan = (vec![f64::NAN; p], vec![f64::NAN; p]);
+
+    // solve the linear prediction coefficients, y[k + p] = Σᵢ cᵢ y[k + i]
+    let m = y.len() - p;
+    let mut ata = vec![vec![0.0; p]; p];
+    let mut atb = vec![0.0; p];
+    for k in 0..m {
+        let row = &y[k..k + p];
+        for (i, ri) in row.iter().enumerate() {
+            atb[i] += ri * y[k + p];
+            ata[i].iter_mut().zip(row).for_each(|(a, rj)| *a += ri * rj);
+        }
+    }
+    let Some(c) = solve(ata, atb) else {
+        return nan;
+    };
+
+    // find the roots of zᵖ - Σᵢ cᵢ zⁱ and convert valid roots to lifetimes
+    let roots = poly_roots(&c);
+    let valid: Vec<f64> = roots
+        .iter()
+        .filter(|z| z.im.abs() <= 1e-6 * z.norm().max(1.0) && z.re > 0.0 && z.re < 1.0)
+        .map(|z| z.re)
+        .collect();
+    if valid.is_empty() {
+        return nan;
+    }
+
+    // solve the amplitudes of the valid components, y[k] = Σᵢ Aᵢ zᵢᵏ
+    let q = valid.len();
+    let mut vtv = vec![vec![0.0; q]; q];
+    let mut vty = vec![0.0; q];
+    let mut powers = vec![1.0; q];
+    for &v in y {
+        for i in 0..q {
+            vty[i] += powers[i] * v;
+            for j in 0..q {
+                vtv[i][j] += powers[i] * powers[j];
+            }
+        }
+        powers.iter_mut().zip(&valid).for_each(|(pw, z)| *pw *= z);
+    }
+    let Some(amps) = solve(vtv, vty) else {
+        return nan;
+    };
+
+    // sort the components by lifetime, padding with NaN
+    let mut comps: Vec<(f64, f64)> = valid
+        .iter()
+        .zip(amps)
+        .map(|(z, a)| (-dt / z.ln(), a))
+        .collect();
+    comps.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut taus: Vec<f64> = comps.iter().map(|c| c.0).collect();
+    let mut amplitudes: Vec<f64> = comps.iter().map(|c| c.1).collect();
+    taus.resize(p, f64::NAN);
+    amplitudes.resize(p, f64::NAN);
+
+    (taus, amplitudes)
+}
+
+/// Index of the maximum value of a slice.
+pub(crate) fn peak(y: &[f64]) -> usize {
+    y.iter()
+        .enumerate()
+        .fold(
+            (0, f64::NEG_INFINITY),
+            |acc, (i, &v)| {
+                if v > acc.1 { (i, v) } else { acc }
+            },
+        )
+        .0
+}
+
+/// Find the complex roots of the monic polynomial `zᵖ - Σᵢ cᵢ zⁱ` with the
+/// Durand-Kerner method.
+fn poly_roots(c: &[f64]) -> Vec<Complex<f64>> {
+    let p = c.len();
+    let eval = |z: Complex<f64>| {
+        c.iter()
+            .rev()
+            .fold(Complex::new(1.0, 0.0), |acc, &ci| acc * z - ci)
+    };
+    let seed = Complex::new(0.4, 0.9);
+    let mut roots: Vec<Complex<f64>> = (0..p).map(|i| seed.powu(i as u32)).collect();
+    for _ in 0..500 {
+        let mut delta = 0.0_f64;
+        for i in 0..p {
+            let denom = (0..p)
+                .filter(|&j| j != i)
+                .fold(Complex::new(1.0, 0.0), |acc, j| acc * (roots[i] - roots[j]));
+            if denom.norm() == 0.0 {
+                continue;
+            }
+            let step = eval(roots[i]) / denom;
+            roots[i] -= step;
+            delta = delta.max(step.norm());
+        }
+        if delta < 1e-14 {
+            break;
+        }
+    }
+
+    roots
+}
+
+/// Find the tail range of a decay curve, defaulting to the peak bin and the
+/// curve length.
+fn tail_range(
+    y: &[f64],
+    start: Option<usize>,
+    end: Option<usize>,
+) -> Result<(usize, usize), ImgalError> {
+    let end = end.unwrap_or(y.len());
+    if end > y.len() {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "end",
+            value: y.len(),
+        });
+    }
+    let start = start.unwrap_or_else(|| peak(&y[..end]));
+    if start >= end {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "start",
+            value: end.saturating_sub(1),
+        });
+    }
+
+    Ok((start, end))
+}
@@ -0,0 +1,459 @@
+use ndarray::{Array2, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::fitting::exponential::peak;
+use crate::fitting::linalg::solve;
+use crate::traits::numeric::ToFloat64;
+
+// the Laguerre parameters searched when none is given
+const ALPHA_GRID: [f64; 12] = [
+    0.5, 0.6, 0.7, 0.75, 0.8, 0.85, 0.9, 0.93, 0.95, 0.97, 0.98, 0.99,
+];
+
+/// A decay fitted by Laguerre expansion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaguerreFit {
+    /// The expansion coefficients, one per basis function.
+    pub coefficients: Vec<f64>,
+    /// The Laguerre parameter α of the basis.
+    pub alpha: f64,
+    /// The fluorescence impulse response, Σⱼ cⱼ bⱼ(k), over the fitted bins.
+    pub impulse_response: Vec<f64>,
+    /// The average lifetime, the first moment of the impulse response.
+    pub mean_lifetime: f64,
+    /// The reduced χ² of the fit.
+    pub reduced_chi_square: f64,
+}
+
+/// Create a discrete Laguerre basis.
+///
+/// # Description
+///
+/// This function computes the orthonormal discrete Laguerre functions
+/// "bⱼ(k)" of orders 0 to `order - 1` with the recursion:
+///
+/// ```text
+/// b₀(k) = √(1 - α) · α^(k/2)
+/// bⱼ(0) = √α · bⱼ₋₁(0)
+/// bⱼ(k) = √α · bⱼ(k - 1) + √α · bⱼ₋₁(k) - bⱼ₋₁(k - 1)
+/// ```
+///
+/// Where "α" sets the time scale of the functions, which decay more slowly
+/// as "α" approaches 1.0.
+///
+/// # Arguments
+///
+/// * `n`: The number of samples of each function.
+/// * `order`: The number of basis functions, must be greater than 0.
+/// * `alpha`: The Laguerre parameter α, between 0.0 and 1.0 (exclusive).
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The basis with shape (order, n).
+/// * `Err(ImgalError)`: If `order` is 0. If `alpha` is not between 0.0 and
+///    1.0.
+pub fn laguerre_basis(n: usize, order: usize, alpha: f64) -> Result<Array2<f64>, ImgalError> {
+    check_basis(order, alpha)?;
+    let sa = alpha.sqrt();
+    let mut basis = Array2::<f64>::zeros((order, n));
+    for k in 0..n {
+        basis[[0, k]] = (1.0 - alpha).sqrt() * sa.powi(k as i32);
+    }
+    for j in 1..order {
+        for k in 0..n {
+            basis[[j, k]] = if k == 0 {
+                sa * basis[[j - 1, 0]]
+            } else {
+                sa * basis[[j, k - 1]] + sa * basis[[j - 1, k]] - basis[[j - 1, k - 1]]
+            };
+        }
+    }
+
+    Ok(basis)
+}
+
+/// Estimate the average lifetime of a decay curve by Laguerre expansion.
+///
+/// # Description
+///
+/// This function expands the fluorescence impulse response on a discrete
+/// Laguerre basis (see [`laguerre_basis`]), optionally convolved with an
+/// instrument response function (IRF):
+///
+/// ```text
+/// I(k) = IRF(k) ⊛ Σⱼ cⱼ bⱼ(k)
+/// ```
+///
+/// The coefficients "cⱼ" are the linear least squares solution, so unlike
+/// exponential fits there are no iterations or starting values. The average
+/// lifetime is the first moment of the impulse response "h", over the bin
+/// centers:
+///
+/// ```text
+/// τ = Σₖ tₖ h(k) / Σₖ h(k)
+/// ```
+///
+/// Which is the lifetime of a monoexponential decay and the intensity
+/// weighted lifetime, Σᵢ aᵢτᵢ² / Σᵢ aᵢτᵢ, of a multiexponential decay that
+/// has decayed within the period. Without an IRF, the decay is fitted from
+/// its peak bin.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `order`: The number of basis functions, must be greater than 0,
+///    default = 5.
+/// * `alpha`: The Laguerre parameter α, between 0.0 and 1.0 (exclusive). If
+///    `None`, the value of a fixed grid with the smallest squared residual is
+///    used.
+/// * `irf`: The instrument response function with the length of `data`. If
+///    `None`, the decay is fitted from the peak bin without convolution.
+///
+/// # Returns
+///
+/// * `Ok(LaguerreFit)`: The fitted expansion and average lifetime.
+/// * `Err(ImgalError)`: If `period` is not positive. If `order` is 0 or not
+///    smaller than the number of fitted bins. If `alpha` is not between 0.0
+///    and 1.0. If the length of `irf` does not match `data`.
+pub fn laguerre_fit<T>(
+    data: &[T],
+    period: f64,
+    order: Option<usize>,
+    alpha: Option<f64>,
+    irf: Option<&[f64]>,
+) -> Result<LaguerreFit, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let order = order.unwrap_or(5);
+
+    // check if parameters are valid
+    let y: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    let start = if irf.is_some() { 0 } else { peak(&y) };
+    check_decay(y.len(), period, order, alpha, irf, start)?;
+
+    let alpha = alpha.unwrap_or_else(|| select_alpha(&y, period, order, irf, start));
+    let model = LaguerreModel::new(y.len(), period, order, alpha, irf, start)?;
+
+    Ok(model.fit(&y))
+}
+
+/// Compute an average lifetime image of a 3-dimensional decay image by
+/// Laguerre expansion.
+///
+/// # Description
+///
+/// This function applies [`laguerre_fit`] to every decay curve of a
+/// 3-dimensional image in parallel. The basis and its least squares
+/// projection are computed once and shared by all pixels, so each pixel costs
+/// a single matrix-vector product. If `alpha` is `None` it is selected from
+/// the summed decay of the image, and without an IRF all pixels are fitted
+/// from the peak bin of the summed decay.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `order`: The number of basis functions, must be greater than 0,
+///    default = 5.
+/// * `alpha`: The Laguerre parameter α, between 0.0 and 1.0 (exclusive). If
+///    `None`, it is selected from the summed decay.
+/// * `irf`: The instrument response function with the length of the decay
+///    axis. If `None`, the decays are fitted from the peak bin without
+///    convolution.
+/// * `mask`: The 2-dimensional boolean mask of the pixels to fit. If `None`,
+///    all pixels are fitted.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The average lifetime image. Pixels without counts, or
+///    outside of `mask`, are NaN.
+/// * `Err(ImgalError)`: If the axis is >= 3. If the shape of `mask` does not
+///    match the image. If the parameters are invalid, see [`laguerre_fit`].
+pub fn laguerre_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    order: Option<usize>,
+    alpha: Option<f64>,
+    irf: Option<&[f64]>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let order = order.unwrap_or(5);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(m) = mask
+        && m.shape() != shape.as_slice()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape,
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    // build the shared model from the summed decay
+    let n = data.len_of(Axis(a));
+    let mut decay = vec![0.0; n];
+    data.lanes(Axis(a)).into_iter().for_each(|ln| {
+        decay.iter_mut().zip(ln).for_each(|(d, v)| *d += v.to_f64());
+    });
+    let start = if irf.is_some() { 0 } else { peak(&decay) };
+    check_decay(n, period, order, alpha, irf, start)?;
+    let alpha = alpha.unwrap_or_else(|| select_alpha(&decay, period, order, irf, start));
+    let model = LaguerreModel::new(n, period, order, alpha, irf, start)?;
+
+    // project each pixel in parallel
+    let mut taus = Array2::<f64>::from_elem((shape[0], shape[1]), f64::NAN);
+    Zip::indexed(&mut taus)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|(r, c), t, ln| {
+            if mask.is_some_and(|m| !m[[r, c]]) {
+                return;
+            }
+            let y: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            *t = model.mean_lifetime(&model.project(&y));
+        });
+
+    Ok(taus)
+}
+
+/// A Laguerre basis and its least squares projection over the fitted bins.
+struct LaguerreModel {
+    alpha: f64,
+    // the basis functions over the fitted bins
+    basis: Vec<Vec<f64>>,
+    // the (IRF convolved) basis over the fitted bins
+    design: Vec<Vec<f64>>,
+    // the least squares projection, (DᵀD)⁻¹Dᵀ
+    projection: Vec<Vec<f64>>,
+    start: usize,
+    dt: f64,
+}
+
+impl LaguerreModel {
+    /// Build the basis of the bins from `start` and its projection.
+    fn new(
+        n: usize,
+        period: f64,
+        order: usize,
+        alpha: f64,
+        irf: Option<&[f64]>,
+        start: usize,
+    ) -> Result<LaguerreModel, ImgalError> {
+        let len = n - start;
+        let basis: Vec<Vec<f64>> = laguerre_basis(len, order, alpha)?
+            .outer_iter()
+            .map(|b| b.to_vec())
+            .collect();
+        let design: Vec<Vec<f64>> = match irf {
+            Some(f) => {
+                let total: f64 = f.iter().sum();
+                let scale = if total > 0.0 { 1.0 / total } else { 1.0 };
+                basis
+                    .iter()
+                    .map(|b| {
+                        (0..len)
+                            .map(|k| (0..=k).map(|i| f[i] * b[k - i]).sum::<f64>() * scale)
+                            .collect()
+                    })
+                    .collect()
+            }
+            None => basis.clone(),
+        };
+
+        // invert the normal matrix column by column
+        let gram: Vec<Vec<f64>> = design
+            .iter()
+            .map(|di| {
+                design
+                    .iter()
+                    .map(|dj| di.iter().zip(dj).map(|(a, b)| a * b).sum())
+                    .collect()
+            })
+            .collect();
+        let mut inverse = vec![vec![0.0; order]; order];
+        for j in 0..order {
+            let mut e = vec![0.0; order];
+            e[j] = 1.0;
+            let col = solve(gram.clone(), e).ok_or(ImgalError::InvalidArrayGeneric {
+                msg: "Invalid array, the Laguerre basis is singular.",
+            })?;
+            (0..order).for_each(|i| inverse[i][j] = col[i]);
+        }
+        let projection = inverse
+            .iter()
+            .map(|row| {
+                (0..len)
+                    .map(|k| row.iter().zip(&design).map(|(g, d)| g * d[k]).sum())
+                    .collect()
+            })
+            .collect();
+
+        Ok(LaguerreModel {
+            alpha,
+            basis,
+            design,
+            projection,
+            start,
+            dt: period / n as f64,
+        })
+    }
+
+    /// Compute the expansion coefficients of a full length decay curve.
+    fn project(&self, y: &[f64]) -> Vec<f64> {
+        self.projection
+            .iter()
+            .map(|p| p.iter().zip(&y[self.start..]).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// Evaluate the impulse response of the expansion coefficients.
+    fn impulse_response(&self, coefficients: &[f64]) -> Vec<f64> {
+        let len = self.basis[0].len();
+        (0..len)
+            .map(|k| {
+                coefficients
+                    .iter()
+                    .zip(&self.basis)
+                    .map(|(c, b)| c * b[k])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Compute the first moment of the impulse response over the bin
+    /// centers.
+    fn mean_lifetime(&self, coefficients: &[f64]) -> f64 {
+        let h = self.impulse_response(coefficients);
+        let total: f64 = h.iter().sum();
+        if total <= 0.0 {
+            return f64::NAN;
+        }
+        let moment: f64 = h
+            .iter()
+            .enumerate()
+            .map(|(k, v)| (k as f64 + 0.5) * v)
+            .sum();
+
+        self.dt * moment / total
+    }
+
+    /// Fit a full length decay curve.
+    fn fit(&self, y: &[f64]) -> LaguerreFit {
+        let coefficients = self.project(y);
+        let fitted = &y[self.start..];
+        let chi: f64 = (0..fitted.len())
+            .map(|k| {
+                let m: f64 = coefficients
+                    .iter()
+                    .zip(&self.design)
+                    .map(|(c, d)| c * d[k])
+                    .sum();
+                (fitted[k] - m).powi(2) / fitted[k].max(1.0)
+            })
+            .sum();
+        let dof = fitted.len() - coefficients.len();
+
+        LaguerreFit {
+            alpha: self.alpha,
+            impulse_response: self.impulse_response(&coefficients),
+            mean_lifetime: self.mean_lifetime(&coefficients),
+            reduced_chi_square: chi / dof as f64,
+            coefficients,
+        }
+    }
+}
+
+/// Select the Laguerre parameter of the grid with the smallest squared
+/// residual.
+fn select_alpha(y: &[f64], period: f64, order: usize, irf: Option<&[f64]>, start: usize) -> f64 {
+    ALPHA_GRID
+        .iter()
+        .filter_map(|&alpha| {
+            let model = LaguerreModel::new(y.len(), period, order, alpha, irf, start).ok()?;
+            let c = model.project(y);
+            let sse: f64 = y[start..]
+                .iter()
+                .enumerate()
+                .map(|(k, v)| {
+                    let m: f64 = c.iter().zip(&model.design).map(|(c, d)| c * d[k]).sum();
+                    (v - m).powi(2)
+                })
+                .sum();
+            Some((alpha, sse))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(ALPHA_GRID[ALPHA_GRID.len() / 2], |(alpha, _)| alpha)
+}
+
+/// Check the order and Laguerre parameter of a basis.
+fn check_basis(order: usize, alpha: f64) -> Result<(), ImgalError> {
+    if order == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "order",
+            value: 0,
+        });
+    }
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "alpha",
+            value: alpha,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check the parameters of a Laguerre fit of `n` bins fitted from `start`.
+fn check_decay(
+    n: usize,
+    period: f64,
+    order: usize,
+    alpha: Option<f64>,
+    irf: Option<&[f64]>,
+    start: usize,
+) -> Result<(), ImgalError> {
+    check_basis(order, alpha.unwrap_or(ALPHA_GRID[0]))?;
+    if period.is_nan() || period <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "period",
+            value: period,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if let Some(f) = irf
+        && f.len() != n
+    {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: n,
+            b_arr_len: f.len(),
+        });
+    }
+    if n.saturating_sub(start) <= order {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "order",
+            value: n.saturating_sub(start).saturating_sub(1),
+        });
+    }
+
+    Ok(())
+}
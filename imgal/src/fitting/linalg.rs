@@ -0,0 +1,27 @@
+/// Solve a small dense linear system with Gaussian elimination and partial
+/// pivoting, returning `None` if the matrix is singular.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for k in 0..n {
+        let pivot = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))?;
+        if a[pivot][k].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+        let (top, bottom) = a.split_at_mut(k + 1);
+        let row_k = &top[k];
+        for (i, row) in bottom.iter_mut().enumerate() {
+            let f = row[k] / row_k[k];
+            row.iter_mut().zip(row_k).for_each(|(r, v)| *r -= f * v);
+            b[k + 1 + i] -= f * b[k];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let s: f64 = (k + 1..n).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - s) / a[k][k];
+    }
+
+    Some(x)
+}
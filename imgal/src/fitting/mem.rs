@@ -0,0 +1,630 @@
+use ndarray::{Array2, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::fitting::exponential::peak;
+use crate::fitting::linalg::solve;
+use crate::traits::numeric::ToFloat64;
+
+// the amplitude weighted mean lifetime, intensity weighted mean lifetime and
+// log-lifetime width images
+type MemImageOutput = (Array2<f64>, Array2<f64>, Array2<f64>);
+
+/// Maximum entropy method parameters, see [`mem_lifetime_distribution`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemOptions {
+    /// The (min, max) range of the lifetime grid. If `None`, the range spans
+    /// from the bin width to the period, default = `None`.
+    pub lifetime_range: Option<(f64, f64)>,
+    /// The number of logarithmically spaced lifetimes of the grid, must be
+    /// greater than 1, default = 32.
+    pub n_lifetimes: usize,
+    /// The relative entropy weight. A larger weight gives smoother, broader
+    /// distributions. If `None`, the weight is chosen by the discrepancy
+    /// principle so the reduced χ² of the fit is close to 1.0, default =
+    /// `None`.
+    pub regularization: Option<f64>,
+    /// The maximum number of Newton iterations per solve, must be greater
+    /// than 0, default = 100.
+    pub max_iterations: usize,
+    /// The first fitted bin. If `None`, the peak bin is used without an IRF
+    /// and the first bin with an IRF, default = `None`.
+    pub start: Option<usize>,
+}
+
+impl Default for MemOptions {
+    fn default() -> Self {
+        MemOptions {
+            lifetime_range: None,
+            n_lifetimes: 32,
+            regularization: None,
+            max_iterations: 100,
+            start: None,
+        }
+    }
+}
+
+impl MemOptions {
+    /// Validate the maximum entropy method parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If all parameters are valid.
+    /// * `Err(ImgalError)`: If `n_lifetimes` is < 2 or `max_iterations` is 0.
+    ///    If the lifetime range is not positive and increasing. If
+    ///    `regularization` is not positive.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        if self.n_lifetimes < 2 {
+            return Err(ImgalError::InvalidArrayParameterValueLess {
+                param_name: "n_lifetimes",
+                value: 2,
+            });
+        }
+        if self.max_iterations == 0 {
+            return Err(ImgalError::InvalidArrayParameterValueEqual {
+                param_name: "max_iterations",
+                value: 0,
+            });
+        }
+        if let Some((lo, hi)) = self.lifetime_range
+            && (lo.is_nan() || lo <= 0.0 || hi.is_nan() || hi <= lo)
+        {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "lifetime_range",
+                value: if lo > 0.0 { hi } else { lo },
+                min: if lo > 0.0 { lo } else { 0.0 },
+                max: f64::INFINITY,
+            });
+        }
+        if let Some(r) = self.regularization
+            && (r.is_nan() || r <= 0.0)
+        {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "regularization",
+                value: r,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A lifetime distribution recovered by the maximum entropy method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifetimeDistribution {
+    /// The logarithmically spaced lifetime grid.
+    pub lifetimes: Vec<f64>,
+    /// The amplitude (pre-exponential factor) of each lifetime of the grid.
+    pub amplitudes: Vec<f64>,
+    /// The amplitude weighted mean lifetime, Σᵢ aᵢτᵢ / Σᵢ aᵢ.
+    pub mean_lifetime: f64,
+    /// The intensity weighted mean lifetime, Σᵢ aᵢτᵢ² / Σᵢ aᵢτᵢ.
+    pub intensity_weighted_lifetime: f64,
+    /// The amplitude weighted standard deviation of ln τ, a width of the
+    /// distribution independent of the lifetime scale.
+    pub log_width: f64,
+    /// The relative entropy weight of the solution.
+    pub regularization: f64,
+    /// The reduced χ² of the fit.
+    pub reduced_chi_square: f64,
+}
+
+/// Recover a quasi-continuous lifetime distribution from a decay curve with
+/// the maximum entropy method (MEM).
+///
+/// # Description
+///
+/// This function models a decay as a nonnegative distribution of amplitudes
+/// "aⱼ" over a logarithmically spaced lifetime grid "τⱼ", optionally
+/// convolved with an instrument response function (IRF):
+///
+/// ```text
+/// I(t) = IRF(t) ⊛ Σⱼ aⱼ exp(-t/τⱼ)
+/// ```
+///
+/// The distribution maximizes the Shannon-Jaynes entropy relative to a flat
+/// default model "m" while fitting the data, by minimizing:
+///
+/// ```text
+/// Q(a) = χ²(a) / 2 - α Σⱼ (aⱼ - m - aⱼ ln(aⱼ / m))
+/// ```
+///
+/// Where "χ²" is weighted by the Poisson variance of the counts. Each
+/// minimization uses positivity preserving Newton iterations. Without an
+/// explicit weight, "α" is halved from a smooth solution, warm starting each
+/// minimization, until the reduced χ² reaches 1.0 (discrepancy principle),
+/// the smoothest distribution consistent with the noise. Unlike fits of 1 to 3 discrete exponentials, the recovered
+/// distribution shows broad or multimodal lifetime populations. The bin width
+/// is `period / n`.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `irf`: The instrument response function with the length of `data`. If
+///    `None`, the decay is fitted from the start bin without convolution.
+/// * `options`: The MEM parameters, default = `MemOptions::default()`.
+///
+/// # Returns
+///
+/// * `Ok(LifetimeDistribution)`: The lifetime distribution and its summary
+///    statistics. If the fitted range has no counts, the amplitudes are 0.0
+///    and the summaries are NaN.
+/// * `Err(ImgalError)`: If `data` is empty. If the lengths of `data` and
+///    `irf` do not match. If `period` is not positive. If the start bin is
+///    out of range. If the options are invalid.
+pub fn mem_lifetime_distribution<T>(
+    data: &[T],
+    period: f64,
+    irf: Option<&[f64]>,
+    options: Option<MemOptions>,
+) -> Result<LifetimeDistribution, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let options = options.unwrap_or_default();
+
+    // check if parameters are valid
+    if data.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the decay curve must not be empty.",
+        });
+    }
+    let y: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    let model = MemModel::new(y.len(), period, irf, &options)?;
+    let start = options
+        .start
+        .unwrap_or_else(|| if irf.is_some() { 0 } else { peak(&y) });
+    check_start(start, y.len())?;
+
+    Ok(model.fit(&y[start..], start, &options))
+}
+
+/// Recover per-pixel lifetime distributions of a 3-dimensional decay image
+/// with the maximum entropy method and summarize them.
+///
+/// # Description
+///
+/// This function applies [`mem_lifetime_distribution`] to every decay curve
+/// of a 3-dimensional image in parallel and returns summary images of each
+/// distribution. MEM needs many counts per decay, bin the image (or use
+/// [`mem_roi_distribution`]) for low count data. The lifetime grid and IRF
+/// are shared by all pixels. Pixels without counts, or outside of `mask`,
+/// are NaN.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `irf`: The instrument response function with the length of the decay
+///    axis. If `None`, each decay is fitted from its start bin without
+///    convolution.
+/// * `options`: The MEM parameters, default = `MemOptions::default()`. If
+///    `start` is `None` and there is no IRF, the peak bin of each pixel is
+///    used.
+/// * `mask`: The 2-dimensional boolean mask of the pixels to fit. If `None`,
+///    all pixels are fitted.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>, Array2<f64>))`: The amplitude weighted
+///    mean lifetime, intensity weighted mean lifetime and log-lifetime width
+///    images.
+/// * `Err(ImgalError)`: If the axis is >= 3. If the length of `irf` does not
+///    match the decay axis. If the shape of `mask` does not match the image.
+///    If `period` is not positive. If the start bin is out of range. If the
+///    options are invalid.
+pub fn mem_lifetime_distribution_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    irf: Option<&[f64]>,
+    options: Option<MemOptions>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<MemImageOutput, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let options = options.unwrap_or_default();
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let n = data.len_of(Axis(a));
+    let model = MemModel::new(n, period, irf, &options)?;
+    if let Some(s) = options.start {
+        check_start(s, n)?;
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(m) = mask
+        && m.shape() != shape.as_slice()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape,
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    // fit each pixel in parallel
+    let mut tau_a = Array2::<f64>::from_elem((shape[0], shape[1]), f64::NAN);
+    let mut tau_i = Array2::<f64>::from_elem((shape[0], shape[1]), f64::NAN);
+    let mut width = Array2::<f64>::from_elem((shape[0], shape[1]), f64::NAN);
+    Zip::indexed(&mut tau_a)
+        .and(&mut tau_i)
+        .and(&mut width)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|(r, c), ta, ti, w, ln| {
+            if mask.is_some_and(|m| !m[[r, c]]) {
+                return;
+            }
+            let y: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let s = options
+                .start
+                .unwrap_or_else(|| if irf.is_some() { 0 } else { peak(&y) });
+            let dist = model.fit(&y[s..], s, &options);
+            (*ta, *ti, *w) = (
+                dist.mean_lifetime,
+                dist.intensity_weighted_lifetime,
+                dist.log_width,
+            );
+        });
+
+    Ok((tau_a, tau_i, width))
+}
+
+/// Recover the lifetime distribution of a region of a 3-dimensional decay
+/// image with the maximum entropy method.
+///
+/// # Description
+///
+/// This function sums the decay curves of the pixels of `mask` (_e.g._ a
+/// cell or an organelle) into a single high count decay and recovers its
+/// lifetime distribution with [`mem_lifetime_distribution`].
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `mask`: The 2-dimensional boolean mask of the region.
+/// * `period`: The period (_i.e._ time interval).
+/// * `irf`: The instrument response function with the length of the decay
+///    axis. If `None`, the decay is fitted from the start bin without
+///    convolution.
+/// * `options`: The MEM parameters, default = `MemOptions::default()`.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(LifetimeDistribution)`: The lifetime distribution of the region.
+/// * `Err(ImgalError)`: If the axis is >= 3. If the shape of `mask` does not
+///    match the image. See [`mem_lifetime_distribution`] for the other
+///    errors.
+pub fn mem_roi_distribution<T>(
+    data: ArrayView3<T>,
+    mask: ArrayView2<bool>,
+    period: f64,
+    irf: Option<&[f64]>,
+    options: Option<MemOptions>,
+    axis: Option<usize>,
+) -> Result<LifetimeDistribution, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if mask.shape() != shape.as_slice() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape,
+            shape_b: mask.shape().to_vec(),
+        });
+    }
+
+    // sum the decays of the region
+    let mut decay = vec![0.0; data.len_of(Axis(a))];
+    Zip::from(data.lanes(Axis(a))).and(mask).for_each(|ln, &m| {
+        if m {
+            decay.iter_mut().zip(ln).for_each(|(d, v)| *d += v.to_f64());
+        }
+    });
+
+    mem_lifetime_distribution(&decay, period, irf, options)
+}
+
+/// The lifetime grid and decay basis shared by all fits.
+struct MemModel {
+    lifetimes: Vec<f64>,
+    // the basis decay of each lifetime over all bins
+    basis: Vec<Vec<f64>>,
+    // whether the basis is shifted to the start bin (no IRF)
+    shifted: bool,
+}
+
+impl MemModel {
+    /// Build the lifetime grid and the (IRF convolved) basis decays.
+    fn new(
+        n: usize,
+        period: f64,
+        irf: Option<&[f64]>,
+        options: &MemOptions,
+    ) -> Result<MemModel, ImgalError> {
+        options.validate()?;
+        if period.is_nan() || period <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "period",
+                value: period,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+        if let Some(f) = irf
+            && f.len() != n
+        {
+            return Err(ImgalError::MismatchedArrayLengths {
+                a_arr_len: n,
+                b_arr_len: f.len(),
+            });
+        }
+        let dt = period / n as f64;
+        let (lo, hi) = options.lifetime_range.unwrap_or((dt, period));
+        let m = options.n_lifetimes;
+        let lifetimes: Vec<f64> = (0..m)
+            .map(|j| lo * (hi / lo).powf(j as f64 / (m - 1) as f64))
+            .collect();
+        let basis = lifetimes
+            .iter()
+            .map(|tau| {
+                let e: Vec<f64> = (0..n).map(|k| (-(k as f64) * dt / tau).exp()).collect();
+                match irf {
+                    Some(f) => {
+                        let total: f64 = f.iter().sum();
+                        let scale = if total > 0.0 { 1.0 / total } else { 1.0 };
+                        (0..n)
+                            .map(|k| (0..=k).map(|i| f[i] * e[k - i]).sum::<f64>() * scale)
+                            .collect()
+                    }
+                    None => e,
+                }
+            })
+            .collect();
+
+        Ok(MemModel {
+            lifetimes,
+            basis,
+            shifted: irf.is_none(),
+        })
+    }
+
+    /// Fit the decay samples from the `start` bin.
+    fn fit(&self, y: &[f64], start: usize, options: &MemOptions) -> LifetimeDistribution {
+        let m = self.lifetimes.len();
+        let nan = LifetimeDistribution {
+            lifetimes: self.lifetimes.clone(),
+            amplitudes: vec![0.0; m],
+            mean_lifetime: f64::NAN,
+            intensity_weighted_lifetime: f64::NAN,
+            log_width: f64::NAN,
+            regularization: f64::NAN,
+            reduced_chi_square: f64::NAN,
+        };
+        let total: f64 = y.iter().sum();
+        if total <= 0.0 || y.is_empty() {
+            return nan;
+        }
+
+        // the basis over the fitted bins and the weighted normal equations
+        let offset = if self.shifted { 0 } else { start };
+        let k: Vec<&[f64]> = self
+            .basis
+            .iter()
+            .map(|b| &b[offset..offset + y.len()])
+            .collect();
+        let w: Vec<f64> = y.iter().map(|v| 1.0 / v.max(1.0)).collect();
+        let ktwk: Vec<Vec<f64>> = (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| (0..y.len()).map(|t| k[i][t] * w[t] * k[j][t]).sum())
+                    .collect()
+            })
+            .collect();
+        let ktwy: Vec<f64> = (0..m)
+            .map(|i| (0..y.len()).map(|t| k[i][t] * w[t] * y[t]).sum())
+            .collect();
+
+        // the flat default model and the natural entropy weight scale, where
+        // the data and entropy curvatures balance
+        let colsum: f64 = k.iter().map(|b| b.iter().sum::<f64>()).sum();
+        let model = total / colsum;
+        let scale = model * (0..m).map(|i| ktwk[i][i]).sum::<f64>() / m as f64;
+
+        let problem = Problem {
+            ktwk,
+            ktwy,
+            y,
+            k,
+            w,
+            model,
+        };
+        // decrease the weight from a smooth solution, warm starting each
+        // solve, until the requested weight or the noise level is reached
+        let iterations = options.max_iterations;
+        let dof = (y.len() as f64 - 1.0).max(1.0);
+        let target = options.regularization.unwrap_or(1e-8);
+        let mut a = vec![model; m];
+        let mut r = 1e4_f64.max(target);
+        let mut chi = problem.newton(r * scale, &mut a, iterations);
+        while r > target && (options.regularization.is_some() || chi / dof > 1.0) {
+            r = (r / 2.0).max(target);
+            chi = problem.newton(r * scale, &mut a, iterations);
+        }
+        let alpha = r;
+
+        // summarize the distribution
+        let sa: f64 = a.iter().sum();
+        let sat: f64 = a.iter().zip(&self.lifetimes).map(|(ai, t)| ai * t).sum();
+        let satt: f64 = a
+            .iter()
+            .zip(&self.lifetimes)
+            .map(|(ai, t)| ai * t * t)
+            .sum();
+        let mean_ln: f64 = a
+            .iter()
+            .zip(&self.lifetimes)
+            .map(|(ai, t)| ai * t.ln())
+            .sum::<f64>()
+            / sa;
+        let var_ln: f64 = a
+            .iter()
+            .zip(&self.lifetimes)
+            .map(|(ai, t)| ai * (t.ln() - mean_ln).powi(2))
+            .sum::<f64>()
+            / sa;
+
+        LifetimeDistribution {
+            lifetimes: self.lifetimes.clone(),
+            amplitudes: a,
+            mean_lifetime: sat / sa,
+            intensity_weighted_lifetime: satt / sat,
+            log_width: var_ln.sqrt(),
+            regularization: alpha,
+            reduced_chi_square: chi / dof,
+        }
+    }
+}
+
+/// The weighted normal equations of a decay fit.
+struct Problem<'a> {
+    ktwk: Vec<Vec<f64>>,
+    ktwy: Vec<f64>,
+    y: &'a [f64],
+    k: Vec<&'a [f64]>,
+    w: Vec<f64>,
+    // the flat default model amplitude
+    model: f64,
+}
+
+impl Problem<'_> {
+    /// Minimize the MEM objective with positivity preserving,
+    /// Levenberg-Marquardt damped Newton iterations, starting from and
+    /// updating `a`, and return the χ² of the solution.
+    fn newton(&self, alpha: f64, a: &mut Vec<f64>, max_iterations: usize) -> f64 {
+        let m = self.ktwy.len();
+        let floor = self.model * 1e-12;
+        let mut q = self.objective(alpha, a);
+        let mut damping = 1e-3;
+        for _ in 0..max_iterations {
+            // gradient and Hessian of Q
+            let grad: Vec<f64> = (0..m)
+                .map(|i| {
+                    let data: f64 = self.ktwk[i]
+                        .iter()
+                        .zip(a.iter())
+                        .map(|(h, aj)| h * aj)
+                        .sum();
+                    data - self.ktwy[i] + alpha * (a[i] / self.model).ln()
+                })
+                .collect();
+            let mut hess = self.ktwk.clone();
+            hess.iter_mut()
+                .enumerate()
+                .for_each(|(i, row)| row[i] += alpha / a[i]);
+
+            // increase the damping until a step decreases Q, each amplitude
+            // shrinks at most 10 fold per step
+            let mut accepted = None;
+            while damping < 1e12 {
+                let mut damped = hess.clone();
+                damped
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, row)| row[i] *= 1.0 + damping);
+                if let Some(step) = solve(damped, grad.iter().map(|g| -g).collect()) {
+                    let s = step
+                        .iter()
+                        .zip(a.iter())
+                        .filter(|(d, _)| **d < 0.0)
+                        .map(|(d, ai)| 0.9 * ai / -d)
+                        .fold(1.0_f64, f64::min);
+                    let next: Vec<f64> = a
+                        .iter()
+                        .zip(&step)
+                        .map(|(ai, d)| (ai + s * d).max(floor))
+                        .collect();
+                    let qn = self.objective(alpha, &next);
+                    if qn <= q {
+                        accepted = Some((next, qn));
+                        damping = (damping / 3.0).max(1e-12);
+                        break;
+                    }
+                }
+                damping *= 4.0;
+            }
+            let Some((next, qn)) = accepted else {
+                break;
+            };
+            let converged = q - qn <= 1e-12 * q.abs().max(1.0);
+            (*a, q) = (next, qn);
+            if converged {
+                break;
+            }
+        }
+
+        self.chi_square(a)
+    }
+
+    /// Compute the MEM objective Q of amplitudes, up to a constant.
+    fn objective(&self, alpha: f64, a: &[f64]) -> f64 {
+        let quad: f64 = (0..a.len())
+            .map(|i| {
+                let h: f64 = self.ktwk[i].iter().zip(a).map(|(h, aj)| h * aj).sum();
+                a[i] * (0.5 * h - self.ktwy[i])
+            })
+            .sum();
+        let entropy: f64 = a
+            .iter()
+            .map(|ai| ai * (ai / self.model).ln() - ai + self.model)
+            .sum();
+
+        quad + alpha * entropy
+    }
+
+    /// Compute the weighted χ² of amplitudes.
+    fn chi_square(&self, a: &[f64]) -> f64 {
+        (0..self.y.len())
+            .map(|t| {
+                let fit: f64 = self.k.iter().zip(a).map(|(b, ai)| b[t] * ai).sum();
+                self.w[t] * (self.y[t] - fit).powi(2)
+            })
+            .sum()
+    }
+}
+
+/// Check that the start bin leaves at least 2 bins to fit.
+fn check_start(start: usize, n: usize) -> Result<(), ImgalError> {
+    if start + 2 > n {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "start",
+            value: n.saturating_sub(2),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+//! Curve fitting and fit quality functions.
+pub mod exponential;
+pub use exponential::{prony, prony_image, tail_fit, tail_fit_image};
+pub mod gaussian;
+pub use gaussian::{GaussianFit, gaussian_2d, gaussian_3d};
+pub mod laguerre;
+pub use laguerre::{LaguerreFit, laguerre_basis, laguerre_fit, laguerre_image};
+pub(crate) mod linalg;
+pub mod mem;
+pub use mem::{
+    LifetimeDistribution, MemOptions, mem_lifetime_distribution, mem_lifetime_distribution_image,
+    mem_roi_distribution,
+};
+pub mod quality;
+pub use quality::{
+    chi_square, durbin_watson, reduced_chi_square, reduced_chi_square_image, weighted_residuals,
+    weighted_residuals_image,
+};
+pub mod selection;
+pub use selection::{ModelCriterion, select_model, select_model_image};
@@ -0,0 +1,257 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the chi-square statistic of a model fit to a decay curve.
+///
+/// # Description
+///
+/// This function computes the chi-square statistic between measured photon
+/// counts and the evaluated model curve, using the Poisson (Neyman) variance
+/// of the data:
+///
+/// ```text
+/// χ² = Σₖ (Dₖ - Mₖ)² / max(Dₖ, 1)
+/// ```
+///
+/// Where "D" is the data and "M" is the model.
+///
+/// # Arguments
+///
+/// * `data`: The measured 1-dimensional decay curve.
+/// * `model`: The evaluated model curve, the same length as `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The chi-square statistic.
+/// * `Err(ImgalError)`: If `data` and `model` lengths do not match.
+pub fn chi_square<T>(data: &[T], model: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    Ok(weighted_residuals(data, model)?.iter().map(|r| r * r).sum())
+}
+
+/// Compute the Durbin-Watson statistic of fit residuals.
+///
+/// # Description
+///
+/// This function computes the Durbin-Watson statistic, a test for serial
+/// correlation of consecutive residuals:
+///
+/// ```text
+/// d = Σₖ (rₖ - rₖ₋₁)² / Σₖ rₖ²
+/// ```
+///
+/// Uncorrelated residuals of a good fit give `d ≈ 2.0`, while systematic
+/// deviations of an underfit model (_e.g._ a monoexponential model fit to a
+/// biexponential decay) give `d < 2.0`.
+///
+/// # Arguments
+///
+/// * `residuals`: The (weighted) residuals of a fit.
+///
+/// # Returns
+///
+/// * `f64`: The Durbin-Watson statistic, between 0.0 and 4.0. If the residuals
+///    are empty or all 0.0, NaN is returned.
+pub fn durbin_watson(residuals: &[f64]) -> f64 {
+    let ss: f64 = residuals.iter().map(|r| r * r).sum();
+    if ss == 0.0 {
+        return f64::NAN;
+    }
+    let diff: f64 = residuals.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+
+    diff / ss
+}
+
+/// Compute the reduced chi-square statistic of a model fit to a decay curve.
+///
+/// # Description
+///
+/// This function computes the chi-square statistic (see [`chi_square`])
+/// normalized by the degrees of freedom of the fit:
+///
+/// ```text
+/// χ²ᵣ = χ² / (n - p)
+/// ```
+///
+/// Where "n" is the number of data points and "p" is the number of free model
+/// parameters. A good fit gives `χ²ᵣ ≈ 1.0`, comparing the reduced chi-square
+/// of nested models (_e.g._ mono- and biexponential) guides model selection.
+///
+/// # Arguments
+///
+/// * `data`: The measured 1-dimensional decay curve.
+/// * `model`: The evaluated model curve, the same length as `data`.
+/// * `n_params`: The number of free model parameters.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The reduced chi-square statistic.
+/// * `Err(ImgalError)`: If `data` and `model` lengths do not match. If
+///    `n_params` is not less than the length of `data`.
+pub fn reduced_chi_square<T>(data: &[T], model: &[f64], n_params: usize) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_dof(data.len(), n_params)?;
+
+    Ok(chi_square(data, model)? / (data.len() - n_params) as f64)
+}
+
+/// Compute the per-pixel reduced chi-square image of a model fit to a
+/// 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function computes the reduced chi-square statistic (see
+/// [`reduced_chi_square`]) of every pixel in parallel.
+///
+/// # Arguments
+///
+/// * `data`: The measured 3-dimensional decay image.
+/// * `model`: The evaluated model image, the same shape as `data`.
+/// * `n_params`: The number of free model parameters.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The reduced chi-square image.
+/// * `Err(ImgalError)`: If the axis is >= 3. If `data` and `model` shapes do
+///    not match. If `n_params` is not less than the length of the decay axis.
+pub fn reduced_chi_square_image<T>(
+    data: ArrayView3<T>,
+    model: ArrayView3<f64>,
+    n_params: usize,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let a = axis.unwrap_or(2);
+    let residuals = weighted_residuals_image(data, model, Some(a))?;
+    let n = residuals.len_of(Axis(a));
+    check_dof(n, n_params)?;
+
+    let mut shape = residuals.shape().to_vec();
+    shape.remove(a);
+    let mut chi = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(&mut chi)
+        .and(residuals.lanes(Axis(a)))
+        .par_for_each(|c, ln| {
+            *c = ln.iter().map(|r| r * r).sum::<f64>() / (n - n_params) as f64;
+        });
+
+    Ok(chi)
+}
+
+/// Compute the weighted residuals of a model fit to a decay curve.
+///
+/// # Description
+///
+/// This function computes the residuals between measured photon counts and the
+/// evaluated model curve, weighted by the Poisson (Neyman) standard deviation
+/// of the data:
+///
+/// ```text
+/// rₖ = (Dₖ - Mₖ) / √max(Dₖ, 1)
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The measured 1-dimensional decay curve.
+/// * `model`: The evaluated model curve, the same length as `data`.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The weighted residuals.
+/// * `Err(ImgalError)`: If `data` and `model` lengths do not match.
+pub fn weighted_residuals<T>(data: &[T], model: &[f64]) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    if data.len() != model.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.len(),
+            b_arr_len: model.len(),
+        });
+    }
+
+    Ok(data
+        .iter()
+        .zip(model)
+        .map(|(d, &m)| residual(d.to_f64(), m))
+        .collect())
+}
+
+/// Compute the weighted residuals image of a model fit to a 3-dimensional
+/// decay image.
+///
+/// # Description
+///
+/// This function computes the weighted residuals (see [`weighted_residuals`])
+/// of every pixel and time bin.
+///
+/// # Arguments
+///
+/// * `data`: The measured 3-dimensional decay image.
+/// * `model`: The evaluated model image, the same shape as `data`.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The weighted residuals image, the same shape as `data`.
+/// * `Err(ImgalError)`: If the axis is >= 3. If `data` and `model` shapes do
+///    not match.
+pub fn weighted_residuals_image<T>(
+    data: ArrayView3<T>,
+    model: ArrayView3<f64>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if data.shape() != model.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data.shape().to_vec(),
+            shape_b: model.shape().to_vec(),
+        });
+    }
+
+    let mut residuals = Array3::<f64>::zeros(data.dim());
+    Zip::from(&mut residuals)
+        .and(&data)
+        .and(&model)
+        .par_for_each(|r, d, &m| *r = residual(d.to_f64(), m));
+
+    Ok(residuals)
+}
+
+/// Check that a fit has at least one degree of freedom.
+fn check_dof(n: usize, n_params: usize) -> Result<(), ImgalError> {
+    if n_params >= n {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "n_params",
+            value: n.saturating_sub(1),
+        });
+    }
+
+    Ok(())
+}
+
+/// Poisson weighted residual of a single data point.
+fn residual(d: f64, m: f64) -> f64 {
+    (d - m) / d.max(1.0).sqrt()
+}
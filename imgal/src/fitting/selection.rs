@@ -0,0 +1,251 @@
+use ndarray::{Array2, ArrayView3, Axis, Zip};
+
+use crate::distribution::f_cdf;
+use crate::error::ImgalError;
+use crate::fitting::{chi_square, prony};
+use crate::traits::numeric::ToFloat64;
+
+/// Model selection criteria for choosing the number of exponential components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModelCriterion {
+    /// Akaike information criterion, `χ² + 2k`.
+    Aic,
+    /// Bayesian information criterion, `χ² + k ln(n)`.
+    Bic,
+    /// Sequential F-test of nested models with a significance level.
+    FTest { alpha: f64 },
+}
+
+/// Select the number of exponential components of a decay curve.
+///
+/// # Description
+///
+/// This function fits 1 to `max_components` exponential components to a
+/// 1-dimensional decay curve (or the summed decay of a region of interest)
+/// with [`prony`] and selects the preferred model order. Each model with "k"
+/// free parameters (2 per component) is scored by its chi-square statistic
+/// "χ²" (see [`chi_square`]) over the "n" fitted bins:
+///
+/// * [`ModelCriterion::Aic`]: the order with the lowest `χ² + 2k`.
+/// * [`ModelCriterion::Bic`]: the order with the lowest `χ² + k ln(n)`.
+/// * [`ModelCriterion::FTest`]: starting from 1 component, an extra component
+///    is accepted while the F-test of the nested models is significant:
+///
+/// ```text
+/// F = ((χ²ₚ - χ²ₚ₊₁) / (kₚ₊₁ - kₚ)) / (χ²ₚ₊₁ / (n - kₚ₊₁))
+/// ```
+///
+/// Orders whose fit has non-physical components (see [`prony`]) are skipped,
+/// which guards against over-fitting noisy decays.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `criterion`: The model selection criterion.
+/// * `max_components`: The maximum number of exponential components,
+///    default = 3.
+/// * `start`: The first bin of the fitted range, default = the peak bin.
+///
+/// # Returns
+///
+/// * `Ok(usize)`: The selected number of components. If no order could be
+///    fit, 0 is returned.
+/// * `Err(ImgalError)`: If `max_components` is 0. If the significance level
+///    is not between 0.0 and 1.0. If there are less than
+///    `2 * max_components` samples after `start`.
+pub fn select_model<T>(
+    data: &[T],
+    period: f64,
+    criterion: ModelCriterion,
+    max_components: Option<usize>,
+    start: Option<usize>,
+) -> Result<usize, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let max_components = max_components.unwrap_or(3);
+    check_parameters(criterion, max_components)?;
+
+    let y: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    prony(&y, period, max_components, start)?;
+
+    Ok(select(&y, period, criterion, max_components, start))
+}
+
+/// Select the number of exponential components of each pixel of a
+/// 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function applies [`select_model`] to every decay curve of a
+/// 3-dimensional image in parallel, returning a model order map. Pixels that
+/// can not be fit (_e.g._ with too few samples after the peak bin) are 0.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional decay image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `criterion`: The model selection criterion.
+/// * `max_components`: The maximum number of exponential components,
+///    default = 3.
+/// * `start`: The first bin of the fitted range, default = the peak bin of
+///    each pixel.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The selected number of components of each pixel.
+/// * `Err(ImgalError)`: If the axis is >= 3. If `max_components` is 0. If the
+///    significance level is not between 0.0 and 1.0.
+pub fn select_model_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    criterion: ModelCriterion,
+    max_components: Option<usize>,
+    start: Option<usize>,
+    axis: Option<usize>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let max_components = max_components.unwrap_or(3);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    check_parameters(criterion, max_components)?;
+
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut orders = Array2::<usize>::zeros((shape[0], shape[1]));
+    Zip::from(&mut orders)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|o, ln| {
+            let y: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            *o = select(&y, period, criterion, max_components, start);
+        });
+
+    Ok(orders)
+}
+
+/// Check that the model selection parameters are valid.
+fn check_parameters(criterion: ModelCriterion, max_components: usize) -> Result<(), ImgalError> {
+    if max_components == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "max_components",
+            value: 0,
+        });
+    }
+    if let ModelCriterion::FTest { alpha } = criterion
+        && (alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0)
+    {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "alpha",
+            value: alpha,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Fit each model order of a decay curve and select the preferred order.
+fn select(
+    y: &[f64],
+    period: f64,
+    criterion: ModelCriterion,
+    max_components: usize,
+    start: Option<usize>,
+) -> usize {
+    let dt = period / y.len() as f64;
+
+    // fit each model order and compute its chi-square statistic
+    let fits: Vec<Option<(f64, f64)>> = (1..=max_components)
+        .map(|p| {
+            let (taus, amps) = prony(y, period, p, start).ok()?;
+            if taus.iter().any(|t| t.is_nan()) {
+                return None;
+            }
+            let s = start.unwrap_or_else(|| peak(y));
+            let model: Vec<f64> = (0..y.len() - s)
+                .map(|k| {
+                    taus.iter()
+                        .zip(&amps)
+                        .map(|(t, a)| a * (-(k as f64) * dt / t).exp())
+                        .sum()
+                })
+                .collect();
+            let chi = chi_square(&y[s..], &model).ok()?;
+            Some((chi, (y.len() - s) as f64))
+        })
+        .collect();
+
+    match criterion {
+        ModelCriterion::Aic | ModelCriterion::Bic => {
+            fits.iter()
+                .enumerate()
+                .filter_map(|(i, f)| {
+                    let (chi, n) = (*f)?;
+                    let k = 2.0 * (i + 1) as f64;
+                    let penalty = if criterion == ModelCriterion::Aic {
+                        2.0 * k
+                    } else {
+                        k * n.ln()
+                    };
+                    Some((i + 1, chi + penalty))
+                })
+                .fold((0, f64::INFINITY), |acc, (p, score)| {
+                    if score < acc.1 { (p, score) } else { acc }
+                })
+                .0
+        }
+        ModelCriterion::FTest { alpha } => {
+            let Some(first) = fits.iter().position(|f| f.is_some()) else {
+                return 0;
+            };
+            let mut order = first + 1;
+            let (mut chi_p, _) = fits[first].unwrap();
+            for (i, f) in fits.iter().enumerate().skip(first + 1) {
+                let Some((chi_q, n)) = *f else {
+                    continue;
+                };
+                let (k_p, k_q) = (2.0 * order as f64, 2.0 * (i + 1) as f64);
+                let dof = n - k_q;
+                if dof <= 0.0 || chi_q >= chi_p {
+                    break;
+                }
+                let f_stat = ((chi_p - chi_q) / (k_q - k_p)) / (chi_q.max(f64::MIN_POSITIVE) / dof);
+                let p_value = 1.0 - f_cdf(f_stat, k_q - k_p, dof).unwrap_or(0.0);
+                if p_value >= alpha {
+                    break;
+                }
+                order = i + 1;
+                chi_p = chi_q;
+            }
+
+            order
+        }
+    }
+}
+
+/// Index of the maximum value of a slice.
+fn peak(y: &[f64]) -> usize {
+    y.iter()
+        .enumerate()
+        .fold(
+            (0, f64::NEG_INFINITY),
+            |acc, (i, &v)| {
+                if v > acc.1 { (i, v) } else { acc }
+            },
+        )
+        .0
+}
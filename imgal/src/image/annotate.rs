@@ -0,0 +1,485 @@
+use ndarray::{ArrayView2, ArrayViewMut3};
+
+use crate::error::ImgalError;
+use crate::image::render::label_color;
+use crate::segmentation::{Connectivity, label_boundaries};
+
+/// Image corners for placing annotations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    /// The top left corner, at row and col 0.
+    TopLeft,
+    /// The top right corner.
+    TopRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+/// Scale bar drawing options, see [`draw_scale_bar`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleBarOptions {
+    /// The image corner of the scale bar, default = `Corner::BottomRight`.
+    pub corner: Corner,
+    /// The RGB color of the bar and label, default = `[255, 255, 255]`.
+    pub color: [u8; 3],
+    /// The bar thickness in pixels, must be greater than 0, default = 4.
+    pub thickness: usize,
+    /// The distance between the bar and the image edges in pixels, default =
+    /// 8.
+    pub margin: usize,
+    /// The label text drawn next to the bar (_e.g._ "10 µm"). If `None`, no
+    /// label is drawn, default = `None`.
+    pub label: Option<String>,
+    /// The integer font scale of the label, must be greater than 0, default =
+    /// 1.
+    pub font_scale: usize,
+}
+
+impl Default for ScaleBarOptions {
+    fn default() -> Self {
+        ScaleBarOptions {
+            corner: Corner::BottomRight,
+            color: [255, 255, 255],
+            thickness: 4,
+            margin: 8,
+            label: None,
+            font_scale: 1,
+        }
+    }
+}
+
+impl ScaleBarOptions {
+    /// Validate the scale bar parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If all parameters are valid.
+    /// * `Err(ImgalError)`: If `thickness` or `font_scale` is 0.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        for (name, value) in [
+            ("thickness", self.thickness),
+            ("font_scale", self.font_scale),
+        ] {
+            if value == 0 {
+                return Err(ImgalError::InvalidArrayParameterValueEqual {
+                    param_name: name,
+                    value: 0,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Burn a scale bar into an RGB image.
+///
+/// # Description
+///
+/// This function draws a horizontal bar of `length` physical units, _i.e._
+/// `length / pixel_size` pixels, in a corner of an RGB image (_e.g._ the
+/// output of [`crate::image::render::apply_colormap`]), with an optional text
+/// label centered above the bar (below for the top corners).
+///
+/// # Arguments
+///
+/// * `rgb`: The 3-dimensional (row, col, ch) RGB image, modified in place.
+/// * `pixel_size`: The physical pixel size (_e.g._ µm per pixel).
+/// * `length`: The physical length of the scale bar, in the unit of
+///    `pixel_size`.
+/// * `options`: The scale bar options, default = `ScaleBarOptions::default()`.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the scale bar was drawn.
+/// * `Err(ImgalError)`: If `rgb` does not have 3 channels. If `pixel_size` or
+///    `length` is not positive. If the scale bar does not fit in the image.
+///    If the options are invalid.
+pub fn draw_scale_bar(
+    mut rgb: ArrayViewMut3<u8>,
+    pixel_size: f64,
+    length: f64,
+    options: Option<ScaleBarOptions>,
+) -> Result<(), ImgalError> {
+    // set optional parameters if needed
+    let options = options.unwrap_or_default();
+
+    // check if parameters are valid
+    check_rgb(&rgb)?;
+    options.validate()?;
+    for (name, value) in [("pixel_size", pixel_size), ("length", length)] {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: name,
+                value,
+                min: f64::MIN_POSITIVE,
+                max: f64::INFINITY,
+            });
+        }
+    }
+    let (rows, cols, _) = rgb.dim();
+    let width = ((length / pixel_size).round() as usize).max(1);
+    let margin = options.margin;
+    if width + 2 * margin > cols || options.thickness + 2 * margin > rows {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "length",
+            value: length,
+            min: 0.0,
+            max: cols.saturating_sub(2 * margin) as f64 * pixel_size,
+        });
+    }
+
+    // draw the bar in its corner
+    let top = matches!(options.corner, Corner::TopLeft | Corner::TopRight);
+    let left = matches!(options.corner, Corner::TopLeft | Corner::BottomLeft);
+    let r0 = if top {
+        margin
+    } else {
+        rows - margin - options.thickness
+    };
+    let c0 = if left { margin } else { cols - margin - width };
+    for r in r0..r0 + options.thickness {
+        for c in c0..c0 + width {
+            set_pixel(&mut rgb, r as isize, c as isize, options.color);
+        }
+    }
+
+    // draw the label centered on the bar, clipped at the image edges
+    if let Some(label) = &options.label {
+        let s = options.font_scale;
+        let (h, w) = text_size(label, Some(s));
+        let gap = 2 * s;
+        let tr = if top {
+            (r0 + options.thickness + gap) as isize
+        } else {
+            r0 as isize - (gap + h) as isize
+        };
+        let tc = (c0 + width / 2) as isize - (w / 2) as isize;
+        draw_glyphs(&mut rgb, label, tr, tc, options.color, s);
+    }
+
+    Ok(())
+}
+
+/// Burn the outlines of the regions of a label image into an RGB image.
+///
+/// # Description
+///
+/// This function colors the boundary pixels of each labeled region (see
+/// [`crate::segmentation::label_boundaries`]), _e.g._ to check a
+/// segmentation on an exported image. Without a color, each region is
+/// outlined with its distinct label color (see
+/// [`crate::image::render::overlay_labels`]).
+///
+/// # Arguments
+///
+/// * `rgb`: The 3-dimensional (row, col, ch) RGB image, modified in place.
+/// * `labels`: The 2-dimensional label image with the spatial shape of `rgb`,
+///    where 0 is background.
+/// * `color`: The RGB color of the outlines. If `None`, each region uses its
+///    label color.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the outlines were drawn.
+/// * `Err(ImgalError)`: If `rgb` does not have 3 channels. If the spatial
+///    shapes of `rgb` and `labels` do not match.
+pub fn draw_outlines(
+    mut rgb: ArrayViewMut3<u8>,
+    labels: ArrayView2<usize>,
+    color: Option<[u8; 3]>,
+) -> Result<(), ImgalError> {
+    // check if parameters are valid
+    check_rgb(&rgb)?;
+    if rgb.shape()[..2] != *labels.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: rgb.shape()[..2].to_vec(),
+            shape_b: labels.shape().to_vec(),
+        });
+    }
+
+    let boundaries = label_boundaries(labels, Some(Connectivity::Four));
+    boundaries
+        .indexed_iter()
+        .filter(|&(_, &b)| b)
+        .for_each(|((r, c), _)| {
+            let col = color.unwrap_or_else(|| label_color(labels[[r, c]]));
+            set_pixel(&mut rgb, r as isize, c as isize, col);
+        });
+
+    Ok(())
+}
+
+/// Burn a polyline (_e.g._ a ROI outline or a line profile path) into an RGB
+/// image.
+///
+/// # Description
+///
+/// This function draws 1 pixel wide line segments between consecutive
+/// vertices, sampled at unit (pixel) spacing. Pixels outside of the image are
+/// clipped.
+///
+/// # Arguments
+///
+/// * `rgb`: The 3-dimensional (row, col, ch) RGB image, modified in place.
+/// * `vertices`: The (row, col) vertices of the polyline, in pixels.
+/// * `color`: The RGB color of the polyline.
+/// * `closed`: Whether to connect the last vertex to the first (_i.e._ a
+///    polygon).
+///
+/// # Returns
+///
+/// * `Ok(())`: If the polyline was drawn.
+/// * `Err(ImgalError)`: If `rgb` does not have 3 channels.
+pub fn draw_polyline(
+    mut rgb: ArrayViewMut3<u8>,
+    vertices: &[(f64, f64)],
+    color: [u8; 3],
+    closed: bool,
+) -> Result<(), ImgalError> {
+    // check if parameters are valid
+    check_rgb(&rgb)?;
+
+    let n = vertices.len();
+    let segments = if closed && n > 2 {
+        n
+    } else {
+        n.saturating_sub(1)
+    };
+    if n == 1 {
+        let (r, c) = vertices[0];
+        set_pixel(&mut rgb, r.round() as isize, c.round() as isize, color);
+    }
+    (0..segments).for_each(|i| {
+        let (r0, c0) = vertices[i];
+        let (r1, c1) = vertices[(i + 1) % n];
+        let steps = (r1 - r0).abs().max((c1 - c0).abs()).ceil().max(1.0) as usize;
+        (0..=steps).for_each(|k| {
+            let t = k as f64 / steps as f64;
+            let r = (r0 + t * (r1 - r0)).round() as isize;
+            let c = (c0 + t * (c1 - c0)).round() as isize;
+            set_pixel(&mut rgb, r, c, color);
+        });
+    });
+
+    Ok(())
+}
+
+/// Burn a text label into an RGB image.
+///
+/// # Description
+///
+/// This function draws text with an embedded 5x7 pixel bitmap font, scaled
+/// by an integer factor. The font covers digits, letters, "µ", the space and
+/// common punctuation (`. , : - + = / % ( ) _ ! ? ' #`), other characters
+/// are drawn as "?". Lines are separated by "\n" and text outside of the
+/// image is clipped.
+///
+/// # Arguments
+///
+/// * `rgb`: The 3-dimensional (row, col, ch) RGB image, modified in place.
+/// * `text`: The text to draw.
+/// * `position`: The (row, col) position of the top left corner of the text.
+/// * `color`: The RGB color of the text.
+/// * `scale`: The integer font scale, a scale of "s" draws each font pixel as
+///    an s x s block, default = 1.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the text was drawn.
+/// * `Err(ImgalError)`: If `rgb` does not have 3 channels. If `scale` is 0.
+pub fn draw_text(
+    mut rgb: ArrayViewMut3<u8>,
+    text: &str,
+    position: (usize, usize),
+    color: [u8; 3],
+    scale: Option<usize>,
+) -> Result<(), ImgalError> {
+    // set optional parameters if needed
+    let scale = scale.unwrap_or(1);
+
+    // check if parameters are valid
+    check_rgb(&rgb)?;
+    if scale == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "scale",
+            value: 0,
+        });
+    }
+
+    draw_glyphs(
+        &mut rgb,
+        text,
+        position.0 as isize,
+        position.1 as isize,
+        color,
+        scale,
+    );
+
+    Ok(())
+}
+
+/// Compute the size of a text label drawn with [`draw_text`].
+///
+/// # Arguments
+///
+/// * `text`: The text to measure.
+/// * `scale`: The integer font scale, default = 1.
+///
+/// # Returns
+///
+/// * `(usize, usize)`: The (height, width) of the text in pixels.
+pub fn text_size(text: &str, scale: Option<usize>) -> (usize, usize) {
+    let s = scale.unwrap_or(1);
+    let lines: Vec<usize> = text.split('\n').map(|ln| ln.chars().count()).collect();
+    let longest = lines.iter().copied().max().unwrap_or(0);
+    let width = (longest * (GLYPH_WIDTH + 1)).saturating_sub(1) * s;
+    let height = (lines.len() * (GLYPH_HEIGHT + 2) - 2) * s;
+
+    (height, width)
+}
+
+// the glyph size of the embedded bitmap font
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/// Check that an image has 3 (RGB) channels.
+fn check_rgb(rgb: &ArrayViewMut3<u8>) -> Result<(), ImgalError> {
+    if rgb.shape()[2] != 3 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid RGB image, the image must have a (row, col, ch) shape with 3 channels.",
+        });
+    }
+
+    Ok(())
+}
+
+/// Draw text at a (row, col) position that may be outside of the image.
+fn draw_glyphs(
+    rgb: &mut ArrayViewMut3<u8>,
+    text: &str,
+    row: isize,
+    col: isize,
+    color: [u8; 3],
+    scale: usize,
+) {
+    let s = scale as isize;
+    for (i, line) in text.split('\n').enumerate() {
+        let r0 = row + (i * (GLYPH_HEIGHT + 2)) as isize * s;
+        for (j, ch) in line.chars().enumerate() {
+            let c0 = col + (j * (GLYPH_WIDTH + 1)) as isize * s;
+            for (gr, bits) in glyph(ch).iter().enumerate() {
+                for gc in 0..GLYPH_WIDTH {
+                    if bits >> (GLYPH_WIDTH - 1 - gc) & 1 == 0 {
+                        continue;
+                    }
+                    for dr in 0..s {
+                        for dc in 0..s {
+                            let r = r0 + gr as isize * s + dr;
+                            let c = c0 + gc as isize * s + dc;
+                            set_pixel(rgb, r, c, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Set the color of a pixel, pixels outside of the image are ignored.
+fn set_pixel(rgb: &mut ArrayViewMut3<u8>, r: isize, c: isize, color: [u8; 3]) {
+    let (rows, cols, _) = rgb.dim();
+    if r < 0 || c < 0 || r as usize >= rows || c as usize >= cols {
+        return;
+    }
+    (0..3).for_each(|k| rgb[[r as usize, c as usize, k]] = color[k]);
+}
+
+/// Get the 5x7 bitmap of a character, one row per byte with the leftmost
+/// pixel in the highest of the 5 low bits.
+fn glyph(ch: char) -> [u8; 7] {
+    match ch {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        'a' => [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F],
+        'b' => [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E],
+        'c' => [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E],
+        'd' => [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F],
+        'e' => [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E],
+        'f' => [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08],
+        'g' => [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E],
+        'h' => [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11],
+        'i' => [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E],
+        'j' => [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C],
+        'k' => [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12],
+        'l' => [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'm' => [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11],
+        'n' => [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11],
+        'o' => [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E],
+        'p' => [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10],
+        'q' => [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01],
+        'r' => [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10],
+        's' => [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E],
+        't' => [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06],
+        'u' => [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D],
+        'v' => [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'w' => [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A],
+        'x' => [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11],
+        'y' => [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E],
+        'z' => [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F],
+        'µ' => [0x00, 0x00, 0x11, 0x11, 0x13, 0x1D, 0x10],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        '?' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+        '\'' => [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        ' ' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        _ => glyph('?'),
+    }
+}
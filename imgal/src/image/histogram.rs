@@ -1,6 +1,7 @@
-use ndarray::ArrayViewD;
+use ndarray::{ArrayViewD, Zip};
 
-use crate::statistics::min_max;
+use crate::error::ImgalError;
+use crate::statistics::{bin_index, max, min_max};
 use crate::traits::numeric::ToFloat64;
 
 /// Compute the image histogram from an n-dimensional array.
@@ -8,19 +9,24 @@ use crate::traits::numeric::ToFloat64;
 /// # Description
 ///
 /// This function computes an image (_i.e._ frequency) histogram for the values
-/// in the input n-dimensional array.
+/// in the input n-dimensional array. The bins are evenly spaced between the
+/// `range` minimum and maximum, where the last bin includes the maximum value.
+/// Values outside of the range are not counted.
 ///
 /// # Arguments
 ///
 /// * `data`: The input n-dimensional array to construct the histogram from.
 /// * `bins`: The number of bins to use for the histogram, default = 256.
+/// * `range`: The (min, max) range of the histogram. If `None`, the minimum
+///    and maximum values of `data` are used. Set the range explicitly for
+///    histograms that are comparable across images.
 ///
 /// # Returns
 ///
 /// * `Vec<i64>`: The histogram of the input n-dimensional array of size `bins`.
 ///    Each element represents the count of values falling into the
 ///    corresponding bin.
-pub fn histogram<T>(data: ArrayViewD<T>, bins: Option<usize>) -> Vec<i64>
+pub fn histogram<T>(data: ArrayViewD<T>, bins: Option<usize>, range: Option<(f64, f64)>) -> Vec<i64>
 where
     T: ToFloat64,
 {
@@ -31,17 +37,178 @@ where
         return vec![0; 1];
     }
 
-    // get min and max values
-    let (min, max) = min_max(data.view());
-
     // construct histogram
+    let (min, max) = range.unwrap_or_else(|| data_range(&data));
     let mut hist = vec![0; bins];
-    let bin_width: f64 = (max.to_f64() - min.to_f64()) / bins as f64;
     data.iter().for_each(|&v| {
-        let bin_index: usize = ((v.to_f64() - min.to_f64()) / bin_width) as usize;
-        let bin_index = bin_index.min(bins - 1);
-        hist[bin_index] += 1;
+        if let Some(i) = bin_index(v.to_f64(), (min, max), bins) {
+            hist[i] += 1;
+        }
     });
 
     hist
 }
+
+/// Compute the bin edges of an evenly spaced histogram.
+///
+/// # Description
+///
+/// This function computes the `bins + 1` evenly spaced bin edges between the
+/// `range` minimum and maximum, matching the bins used by [`histogram`] and
+/// [`histogram_weighted`].
+///
+/// # Arguments
+///
+/// * `bins`: The number of histogram bins.
+/// * `range`: The (min, max) range of the histogram.
+///
+/// # Returns
+///
+/// * `Vec<f64>`: The bin edges, where bin `i` spans edges `i` and `i + 1`.
+pub fn histogram_bin_edges(bins: usize, range: (f64, f64)) -> Vec<f64> {
+    let (min, max) = range;
+    let width = (max - min) / bins as f64;
+
+    (0..=bins)
+        .map(|i| {
+            if i == bins {
+                max
+            } else {
+                min + i as f64 * width
+            }
+        })
+        .collect()
+}
+
+/// Compute a weighted and/or density normalized image histogram from an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function computes an image histogram (see [`histogram`]) where each
+/// value optionally contributes its weight instead of 1 to its bin. If
+/// `density` is `true`, the histogram is normalized such that its integral over
+/// the range is 1.0 (_i.e._ a probability density):
+///
+/// ```text
+/// pᵢ = cᵢ / (Σ cⱼ * Δ)
+/// ```
+///
+/// Where "cᵢ" is the (weighted) count of bin "i" and "Δ" is the bin width.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array to construct the histogram from.
+/// * `bins`: The number of bins to use for the histogram, default = 256.
+/// * `range`: The (min, max) range of the histogram. If `None`, the minimum
+///    and maximum values of `data` are used.
+/// * `weights`: An optional n-dimensional array of weights, one per value.
+///    Must have the same shape as `data`.
+/// * `density`: If `true`, the histogram is normalized to a probability
+///    density, default = `false`.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The histogram of size `bins` and the
+///    `bins + 1` bin edges.
+/// * `Err(ImgalError)`: If `bins` is 0. If the `weights` and `data` shapes do
+///    not match. If the range minimum is not less than the range maximum.
+pub fn histogram_weighted<T>(
+    data: ArrayViewD<T>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+    weights: Option<ArrayViewD<f64>>,
+    density: Option<bool>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+    let density = density.unwrap_or(false);
+
+    // check parameters are valid
+    if bins == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "bins",
+            value: 0,
+        });
+    }
+    if let Some(w) = weights.as_ref()
+        && w.shape() != data.shape()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data.shape().to_vec(),
+            shape_b: w.shape().to_vec(),
+        });
+    }
+    let (min, max) = range.unwrap_or_else(|| data_range(&data));
+    if range.is_some() && (min.is_nan() || max.is_nan() || min >= max) {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "range",
+            value: min,
+            min: f64::NEG_INFINITY,
+            max,
+        });
+    }
+
+    // construct the (weighted) histogram
+    let mut hist = vec![0.0; bins];
+    match weights {
+        Some(w) => Zip::from(&data).and(&w).for_each(|&v, &wv| {
+            if let Some(i) = bin_index(v.to_f64(), (min, max), bins) {
+                hist[i] += wv;
+            }
+        }),
+        None => data.iter().for_each(|&v| {
+            if let Some(i) = bin_index(v.to_f64(), (min, max), bins) {
+                hist[i] += 1.0;
+            }
+        }),
+    }
+
+    // normalize to a probability density
+    if density {
+        let total: f64 = hist.iter().sum();
+        let width = (max - min) / bins as f64;
+        let norm = total * width;
+        if norm > 0.0 {
+            hist.iter_mut().for_each(|h| *h /= norm);
+        }
+    }
+
+    Ok((hist, histogram_bin_edges(bins, (min, max))))
+}
+
+/// Compute the exact-count histogram of an unsigned 16-bit n-dimensional array.
+///
+/// # Description
+///
+/// This function computes an exact-count histogram with one bin per integer
+/// value, from 0 to the maximum value of the input array. Each value is used
+/// directly as its bin index, avoiding any floating point binning.
+///
+/// # Arguments
+///
+/// * `data`: The input unsigned 16-bit n-dimensional array.
+///
+/// # Returns
+///
+/// * `Vec<i64>`: The histogram of size `max + 1`, where element `i` is the
+///    count of values equal to `i`.
+pub fn histogram_u16(data: ArrayViewD<u16>) -> Vec<i64> {
+    let mut hist = vec![0; max(data.view()) as usize + 1];
+    data.iter().for_each(|&v| hist[v as usize] += 1);
+
+    hist
+}
+
+/// Find the (min, max) value range of an n-dimensional array.
+fn data_range<T>(data: &ArrayViewD<T>) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let (min, max) = min_max(data.view());
+
+    (min.to_f64(), max.to_f64())
+}
@@ -0,0 +1,169 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, IxDyn, Slice, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the integral image (summed-area table) of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the cumulative sum of the input array along every
+/// axis:
+///
+/// ```text
+/// S(x₁, ..., xₙ) = Σ I(y₁, ..., yₙ) for all yᵢ < xᵢ
+/// ```
+///
+/// The integral image has one more element than the input along each axis,
+/// with a leading zero row (plane) on every axis, so that the sum of any
+/// box of the input is computed from its 2ⁿ corners without bounds checks
+/// (see [`box_sum`]). Sliding-window sums, means and variances (with
+/// [`integral_image_squared`]) are then computed in constant time per
+/// window, independently of the window size.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array (_e.g._ a 2D image or 3D stack).
+///
+/// # Returns
+///
+/// * `ArrayD<f64>`: The integral image, with a shape of the input shape + 1
+///    on every axis.
+pub fn integral_image<T>(data: ArrayViewD<T>) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    cumulative_sum(data, |v| v.to_f64())
+}
+
+/// Compute the squared integral image of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the integral image (see [`integral_image`]) of the
+/// squared input values. Together with the integral image, the variance of
+/// any box of "N" elements is computed in constant time:
+///
+/// ```text
+/// Var = (S₂ - S² / N) / N
+/// ```
+///
+/// Where "S" and "S₂" are the box sums (see [`box_sum`]) of the integral and
+/// squared integral images respectively.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array (_e.g._ a 2D image or 3D stack).
+///
+/// # Returns
+///
+/// * `ArrayD<f64>`: The squared integral image, with a shape of the input
+///    shape + 1 on every axis.
+pub fn integral_image_squared<T>(data: ArrayViewD<T>) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    cumulative_sum(data, |v| {
+        let v = v.to_f64();
+        v * v
+    })
+}
+
+/// Compute the sum of a box of an array from its integral image.
+///
+/// # Description
+///
+/// This function computes the sum of the input elements within the half-open
+/// box [`start`, `end`) on every axis by inclusion-exclusion of the 2ⁿ box
+/// corners of the integral image (see [`integral_image`]).
+///
+/// # Arguments
+///
+/// * `integral`: The integral image, as returned by [`integral_image`] or
+///    [`integral_image_squared`].
+/// * `start`: The first index of the box on each axis (inclusive).
+/// * `end`: The last index of the box on each axis (exclusive).
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The sum of the box. Empty boxes sum to 0.0.
+/// * `Err(ImgalError)`: If the lengths of `start` or `end` do not match the
+///    number of dimensions of `integral`. If `start` is greater than `end` or
+///    `end` is outside of the input array on any axis.
+pub fn box_sum(
+    integral: ArrayViewD<f64>,
+    start: &[usize],
+    end: &[usize],
+) -> Result<f64, ImgalError> {
+    // check if parameters are valid
+    let ndim = integral.ndim();
+    for len in [start.len(), end.len()] {
+        if len != ndim {
+            return Err(ImgalError::MismatchedArrayLengths {
+                a_arr_len: ndim,
+                b_arr_len: len,
+            });
+        }
+    }
+    if start
+        .iter()
+        .zip(end)
+        .zip(integral.shape())
+        .any(|((&s, &e), &n)| s > e || e >= n)
+    {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid box, the box must be within the array and start must not exceed end.",
+        });
+    }
+
+    // inclusion-exclusion over the box corners, the sign flips with each
+    // start corner coordinate
+    let mut index = vec![0; ndim];
+    let sum = (0..1usize << ndim)
+        .map(|corner| {
+            let mut sign = 1.0;
+            for (d, i) in index.iter_mut().enumerate() {
+                if corner & (1 << d) == 0 {
+                    *i = end[d];
+                } else {
+                    *i = start[d];
+                    sign = -sign;
+                }
+            }
+            sign * integral[IxDyn(&index)]
+        })
+        .sum();
+
+    Ok(sum)
+}
+
+/// Compute the cumulative sum of mapped values along every axis, with a
+/// leading zero on every axis.
+fn cumulative_sum<T, F>(data: ArrayViewD<T>, map: F) -> ArrayD<f64>
+where
+    T: ToFloat64,
+    F: Fn(&T) -> f64,
+{
+    let shape: Vec<usize> = data.shape().iter().map(|&n| n + 1).collect();
+    let mut output = ArrayD::<f64>::zeros(IxDyn(&shape));
+
+    // copy the mapped values behind the leading zeros
+    let mut inner = output.view_mut();
+    for d in 0..data.ndim() {
+        inner.slice_axis_inplace(Axis(d), Slice::from(1..));
+    }
+    inner.zip_mut_with(&data, |o, v| *o = map(v));
+
+    // accumulate along each axis in turn
+    for d in 0..output.ndim() {
+        Zip::from(output.lanes_mut(Axis(d))).par_for_each(|mut ln| {
+            let mut total = 0.0;
+            ln.iter_mut().for_each(|v| {
+                total += *v;
+                *v = total;
+            });
+        });
+    }
+
+    output
+}
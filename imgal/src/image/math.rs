@@ -0,0 +1,270 @@
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::{FromFloat64, ToFloat64};
+
+/// Elementwise arithmetic and comparison operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathOp {
+    /// a + b
+    Add,
+    /// a - b
+    Subtract,
+    /// a * b
+    Multiply,
+    /// a / b
+    Divide,
+    /// The smaller of a and b.
+    Min,
+    /// The larger of a and b.
+    Max,
+}
+
+impl MathOp {
+    /// Apply the operation to two values.
+    pub fn apply(&self, a: f64, b: f64) -> f64 {
+        match self {
+            MathOp::Add => a + b,
+            MathOp::Subtract => a - b,
+            MathOp::Multiply => a * b,
+            MathOp::Divide => a / b,
+            MathOp::Min => a.min(b),
+            MathOp::Max => a.max(b),
+        }
+    }
+}
+
+/// Apply an elementwise operation between two n-dimensional images.
+///
+/// # Description
+///
+/// This function computes `op` between each element of `a` and the matching
+/// element of `b` in floating point and converts the result back to the type
+/// of `a`. Integer results are rounded to the nearest integer and saturated at
+/// the type range (_e.g._ subtracting a brighter background from a `u16`
+/// image clips at 0 instead of wrapping around), NaN results are 0. Image `b`
+/// is broadcast to the shape of `a` with NumPy rules, so a (ch) vector or a
+/// (row, col, 1) image applies to every channel of a (row, col, ch) image.
+///
+/// # Arguments
+///
+/// * `a`: The first n-dimensional image.
+/// * `b`: The second n-dimensional image, broadcastable to the shape of `a`.
+/// * `op`: The elementwise operation.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: The result image, with the shape and type of `a`.
+/// * `Err(ImgalError)`: If `b` can not be broadcast to the shape of `a`.
+pub fn binary<T, U>(a: ArrayViewD<T>, b: ArrayViewD<U>, op: MathOp) -> Result<ArrayD<T>, ImgalError>
+where
+    T: FromFloat64,
+    U: ToFloat64,
+{
+    let mut output = a.to_owned();
+    binary_mut(output.view_mut(), b, op)?;
+
+    Ok(output)
+}
+
+/// Apply an elementwise operation between two n-dimensional images in place.
+///
+/// # Description
+///
+/// This function computes `op` between each element of `a` and the matching
+/// element of `b` like [`binary`] and writes the result into `a`.
+///
+/// # Arguments
+///
+/// * `a`: The first n-dimensional image, overwritten with the result.
+/// * `b`: The second n-dimensional image, broadcastable to the shape of `a`.
+/// * `op`: The elementwise operation.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the operation was applied.
+/// * `Err(ImgalError)`: If `b` can not be broadcast to the shape of `a`.
+pub fn binary_mut<T, U>(
+    mut a: ArrayViewMutD<T>,
+    b: ArrayViewD<U>,
+    op: MathOp,
+) -> Result<(), ImgalError>
+where
+    T: FromFloat64,
+    U: ToFloat64,
+{
+    let b = b
+        .broadcast(a.raw_dim())
+        .ok_or(ImgalError::MismatchedArrayShapes {
+            shape_a: a.shape().to_vec(),
+            shape_b: b.shape().to_vec(),
+        })?;
+    Zip::from(&mut a).and(&b).par_for_each(|a, &b| {
+        *a = T::from_f64(op.apply(a.to_f64(), b.to_f64()));
+    });
+
+    Ok(())
+}
+
+/// Apply an elementwise operation between an n-dimensional image and a
+/// scalar.
+///
+/// # Description
+///
+/// This function computes `op` between each element of `data` and `value`,
+/// with the type conversion of [`binary`].
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+/// * `value`: The scalar value, the second operand of `op`.
+/// * `op`: The elementwise operation.
+///
+/// # Returns
+///
+/// * `ArrayD<T>`: The result image, with the shape and type of `data`.
+pub fn scalar<T>(data: ArrayViewD<T>, value: f64, op: MathOp) -> ArrayD<T>
+where
+    T: FromFloat64,
+{
+    let mut output = data.to_owned();
+    scalar_mut(output.view_mut(), value, op);
+
+    output
+}
+
+/// Apply an elementwise operation between an n-dimensional image and a scalar
+/// in place.
+///
+/// # Description
+///
+/// This function computes `op` between each element of `data` and `value`
+/// like [`scalar`] and writes the result into `data`.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image, overwritten with the result.
+/// * `value`: The scalar value, the second operand of `op`.
+/// * `op`: The elementwise operation.
+pub fn scalar_mut<T>(mut data: ArrayViewMutD<T>, value: f64, op: MathOp)
+where
+    T: FromFloat64,
+{
+    data.par_mapv_inplace(|v| T::from_f64(op.apply(v.to_f64(), value)));
+}
+
+/// Compute the absolute value of an n-dimensional image.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+///
+/// # Returns
+///
+/// * `ArrayD<T>`: The absolute value image, with the shape and type of
+///    `data`. The absolute value of a signed integer minimum saturates at the
+///    type maximum.
+pub fn abs<T>(data: ArrayViewD<T>) -> ArrayD<T>
+where
+    T: FromFloat64,
+{
+    let mut output = data.to_owned();
+    abs_mut(output.view_mut());
+
+    output
+}
+
+/// Compute the absolute value of an n-dimensional image in place.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image, overwritten with its absolute value.
+pub fn abs_mut<T>(mut data: ArrayViewMutD<T>)
+where
+    T: FromFloat64,
+{
+    data.par_mapv_inplace(|v| T::from_f64(v.to_f64().abs()));
+}
+
+/// Clip the values of an n-dimensional image to a range.
+///
+/// # Description
+///
+/// This function replaces the values below `min` by `min` and the values above
+/// `max` by `max`. The bounds are converted to the type of `data` (_i.e._
+/// rounded and saturated for integer types). NaN values are unchanged.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+/// * `min`: The lower bound. If `None`, the values are not clipped from
+///    below.
+/// * `max`: The upper bound. If `None`, the values are not clipped from
+///    above.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: The clipped image, with the shape and type of `data`.
+/// * `Err(ImgalError)`: If `min` is greater than `max`.
+pub fn clip<T>(
+    data: ArrayViewD<T>,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> Result<ArrayD<T>, ImgalError>
+where
+    T: FromFloat64,
+{
+    let mut output = data.to_owned();
+    clip_mut(output.view_mut(), min, max)?;
+
+    Ok(output)
+}
+
+/// Clip the values of an n-dimensional image to a range in place.
+///
+/// # Description
+///
+/// This function clips the values of `data` like [`clip`] and writes the
+/// result into `data`.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image, overwritten with the clipped values.
+/// * `min`: The lower bound. If `None`, the values are not clipped from
+///    below.
+/// * `max`: The upper bound. If `None`, the values are not clipped from
+///    above.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the image was clipped.
+/// * `Err(ImgalError)`: If `min` is greater than `max`.
+pub fn clip_mut<T>(
+    mut data: ArrayViewMutD<T>,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> Result<(), ImgalError>
+where
+    T: FromFloat64,
+{
+    if let (Some(lo), Some(hi)) = (min, max)
+        && lo > hi
+    {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "min",
+            value: lo,
+            min: f64::NEG_INFINITY,
+            max: hi,
+        });
+    }
+
+    let lo = min.map(T::from_f64);
+    let hi = max.map(T::from_f64);
+    data.par_mapv_inplace(|v| match (lo, hi) {
+        (Some(lo), _) if v < lo => lo,
+        (_, Some(hi)) if v > hi => hi,
+        _ => v,
+    });
+
+    Ok(())
+}
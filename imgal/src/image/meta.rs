@@ -0,0 +1,195 @@
+use crate::error::ImgalError;
+
+/// Semantic labels of image axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisLabel {
+    /// The horizontal spatial axis.
+    X,
+    /// The vertical spatial axis.
+    Y,
+    /// The depth spatial axis.
+    Z,
+    /// The channel axis.
+    Channel,
+    /// The time (frame) axis.
+    Time,
+    /// The lifetime (decay or micro-time) axis of time-resolved data.
+    Lifetime,
+}
+
+impl AxisLabel {
+    /// Get the axis label of a single character axis code (case insensitive):
+    /// "x", "y", "z", "c" (channel), "t" (time) or "l" (lifetime).
+    pub fn from_char(code: char) -> Option<Self> {
+        match code.to_ascii_lowercase() {
+            'x' => Some(AxisLabel::X),
+            'y' => Some(AxisLabel::Y),
+            'z' => Some(AxisLabel::Z),
+            'c' => Some(AxisLabel::Channel),
+            't' => Some(AxisLabel::Time),
+            'l' => Some(AxisLabel::Lifetime),
+            _ => None,
+        }
+    }
+}
+
+/// Axis labels and pixel sizes of an n-dimensional image.
+///
+/// # Description
+///
+/// `ImageMeta` describes what each axis of an image is, so the axis of an
+/// operation can be looked up by its label instead of a positional index:
+///
+/// ```
+/// use imgal::image::{AxisLabel, ImageMeta};
+///
+/// let meta = ImageMeta::from_order("yxl")
+///     .unwrap()
+///     .with_pixel_size(AxisLabel::X, 0.2)
+///     .unwrap();
+/// assert_eq!(meta.index(AxisLabel::Lifetime), Some(2));
+/// assert_eq!(meta.pixel_size(AxisLabel::X), Some(0.2));
+/// ```
+///
+/// The resolved axis index can then be passed to the `axis` parameter of
+/// functions such as [`crate::phasor::time_domain::image`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageMeta {
+    axes: Vec<AxisLabel>,
+    pixel_sizes: Vec<Option<f64>>,
+}
+
+impl ImageMeta {
+    /// Create image metadata from axis labels, in axis order.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes`: The label of each image axis.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImageMeta)`: The image metadata without pixel sizes.
+    /// * `Err(ImgalError)`: If an axis label is used more than once.
+    pub fn new(axes: &[AxisLabel]) -> Result<Self, ImgalError> {
+        if axes.iter().enumerate().any(|(i, a)| axes[..i].contains(a)) {
+            return Err(ImgalError::InvalidArrayGeneric {
+                msg: "Invalid image metadata, each axis label can only be used once.",
+            });
+        }
+
+        Ok(Self {
+            axes: axes.to_vec(),
+            pixel_sizes: vec![None; axes.len()],
+        })
+    }
+
+    /// Create image metadata from an axis order string (_e.g._ `"tyx"`), see
+    /// [`AxisLabel::from_char`] for the axis codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `order`: One axis code per image axis, in axis order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImageMeta)`: The image metadata without pixel sizes.
+    /// * `Err(ImgalError)`: If an axis code is unknown or used more than once.
+    pub fn from_order(order: &str) -> Result<Self, ImgalError> {
+        let axes = order
+            .chars()
+            .map(AxisLabel::from_char)
+            .collect::<Option<Vec<AxisLabel>>>()
+            .ok_or(ImgalError::InvalidArrayGeneric {
+                msg: "Invalid axis order, supported axis codes are \"x\", \"y\", \"z\", \"c\", \"t\", and \"l\".",
+            })?;
+
+        Self::new(&axes)
+    }
+
+    /// Set the pixel size (physical size of one sample) along an axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `label`: The axis label.
+    /// * `size`: The pixel size, in any unit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImageMeta)`: The image metadata with the pixel size set.
+    /// * `Err(ImgalError)`: If the axis label is not present. If `size` is not
+    ///    positive.
+    pub fn with_pixel_size(mut self, label: AxisLabel, size: f64) -> Result<Self, ImgalError> {
+        let idx = self.index(label).ok_or(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid image metadata, the axis label is not present.",
+        })?;
+        if size.is_nan() || size <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "size",
+                value: size,
+                min: f64::MIN_POSITIVE,
+                max: f64::INFINITY,
+            });
+        }
+        self.pixel_sizes[idx] = Some(size);
+
+        Ok(self)
+    }
+
+    /// The axis labels, in axis order.
+    pub fn axes(&self) -> &[AxisLabel] {
+        &self.axes
+    }
+
+    /// The number of axes.
+    pub fn ndim(&self) -> usize {
+        self.axes.len()
+    }
+
+    /// The index of an axis label, if present.
+    pub fn index(&self, label: AxisLabel) -> Option<usize> {
+        self.axes.iter().position(|&a| a == label)
+    }
+
+    /// The pixel size along an axis, if the axis is present and its size set.
+    pub fn pixel_size(&self, label: AxisLabel) -> Option<f64> {
+        self.index(label).and_then(|i| self.pixel_sizes[i])
+    }
+
+    /// The pixel sizes in axis order, 1.0 for axes without a pixel size (_e.g._
+    /// as the `spacing` of [`crate::image::distance_transform`]).
+    pub fn spacing(&self) -> Vec<f64> {
+        self.pixel_sizes.iter().map(|s| s.unwrap_or(1.0)).collect()
+    }
+
+    /// The decay axis of time-resolved data, the lifetime axis if present,
+    /// otherwise the time axis.
+    pub fn decay_axis(&self) -> Option<usize> {
+        self.index(AxisLabel::Lifetime)
+            .or_else(|| self.index(AxisLabel::Time))
+    }
+
+    /// Resolve the index of an axis label for an image of a given shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `label`: The axis label.
+    /// * `shape`: The shape of the image the metadata describes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The axis index of `label`.
+    /// * `Err(ImgalError)`: If the number of axes does not match the number of
+    ///    dimensions of `shape`. If the axis label is not present.
+    pub fn resolve(&self, label: AxisLabel, shape: &[usize]) -> Result<usize, ImgalError> {
+        if shape.len() != self.ndim() {
+            return Err(ImgalError::MismatchedArrayLengths {
+                a_arr_len: shape.len(),
+                b_arr_len: self.ndim(),
+            });
+        }
+
+        self.index(label).ok_or(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid image metadata, the axis label is not present.",
+        })
+    }
+}
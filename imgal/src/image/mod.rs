@@ -1,3 +1,27 @@
 //! Image functions.
+pub mod annotate;
+pub mod distance;
+pub use distance::distance_transform;
 pub mod histogram;
 pub use histogram::histogram;
+pub use histogram::histogram_bin_edges;
+pub use histogram::histogram_u16;
+pub use histogram::histogram_weighted;
+pub mod integral;
+pub use integral::{box_sum, integral_image, integral_image_squared};
+pub mod math;
+pub use math::MathOp;
+pub mod meta;
+pub use meta::{AxisLabel, ImageMeta};
+pub mod montage;
+pub use montage::{montage, stack_from_slices};
+pub mod pad;
+pub use pad::{PadMode, crop, crop_center, pad};
+pub mod ratio;
+pub use ratio::{RatioCalibration, ratio, ratio_to_concentration};
+pub mod render;
+pub mod saturation;
+pub use saturation::{saturation_counts, saturation_mask, saturation_region_counts};
+pub mod stabilize;
+pub use stabilize::anscombe;
+pub use stabilize::anscombe_inverse;
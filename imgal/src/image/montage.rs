@@ -0,0 +1,106 @@
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis, s};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Tile a stack of 2-dimensional images into a single montage image.
+///
+/// # Description
+///
+/// This function arranges the images of a `(pln, row, col)` stack on a grid in
+/// row-major order. Tiles are separated by `spacing` pixels of `border_value`,
+/// which also fills the outer border and any empty grid cells, making tile
+/// boundaries visible in the exported image.
+///
+/// # Arguments
+///
+/// * `stack`: The input 3-dimensional stack, with images along axis 0.
+/// * `columns`: The number of grid columns, default = ⌈√pln⌉.
+/// * `spacing`: The border width in pixels between and around tiles,
+///    default = 0.
+/// * `border_value`: The intensity value of the borders and empty cells,
+///    default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Array2<T>)`: The montage image.
+/// * `Err(ImgalError)`: If the stack is empty or if `columns` is 0.
+pub fn montage<T>(
+    stack: ArrayView3<T>,
+    columns: Option<usize>,
+    spacing: Option<usize>,
+    border_value: Option<T>,
+) -> Result<Array2<T>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let (n, rows, cols) = stack.dim();
+    if n == 0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid stack, the stack must contain at least one image.",
+        });
+    }
+
+    // set optional parameters if needed
+    let columns = columns.unwrap_or((n as f64).sqrt().ceil() as usize);
+    let spacing = spacing.unwrap_or(0);
+    let border_value = border_value.unwrap_or_default();
+    if columns == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "columns",
+            value: 0,
+        });
+    }
+
+    // copy each image into its grid cell
+    let grid_rows = n.div_ceil(columns);
+    let mut output = Array2::<T>::from_elem(
+        (
+            grid_rows * (rows + spacing) + spacing,
+            columns * (cols + spacing) + spacing,
+        ),
+        border_value,
+    );
+    stack.axis_iter(Axis(0)).enumerate().for_each(|(i, img)| {
+        let r0 = spacing + (i / columns) * (rows + spacing);
+        let c0 = spacing + (i % columns) * (cols + spacing);
+        output
+            .slice_mut(s![r0..r0 + rows, c0..c0 + cols])
+            .assign(&img);
+    });
+
+    Ok(output)
+}
+
+/// Assemble a 3-dimensional stack from 2-dimensional slices.
+///
+/// # Arguments
+///
+/// * `slices`: The 2-dimensional slices, all with the same shape.
+///
+/// # Returns
+///
+/// * `Ok(Array3<T>)`: The stack with a shape of `(slices.len(), row, col)`.
+/// * `Err(ImgalError)`: If `slices` is empty or if the slice shapes do not
+///    match.
+pub fn stack_from_slices<T>(slices: &[ArrayView2<T>]) -> Result<Array3<T>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let first = slices.first().ok_or(ImgalError::InvalidArrayGeneric {
+        msg: "Invalid slices, at least one slice is required.",
+    })?;
+    if let Some(s) = slices.iter().find(|s| s.shape() != first.shape()) {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: first.shape().to_vec(),
+            shape_b: s.shape().to_vec(),
+        });
+    }
+    let mut output = Array3::<T>::default((slices.len(), first.nrows(), first.ncols()));
+    output
+        .axis_iter_mut(Axis(0))
+        .zip(slices)
+        .for_each(|(mut o, s)| o.assign(s));
+
+    Ok(output)
+}
@@ -0,0 +1,201 @@
+use ndarray::{ArrayD, ArrayViewD, IxDyn, Slice};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Padding modes for array padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadMode {
+    /// Pad with a constant value.
+    Constant,
+    /// Pad with the reflection of the array, excluding the edge value
+    /// (_e.g._ `d c b | a b c d | c b a`).
+    Reflect,
+    /// Pad with the edge value of the array.
+    Edge,
+    /// Pad with the periodic repetition of the array.
+    Wrap,
+}
+
+/// Pad an n-dimensional array.
+///
+/// # Description
+///
+/// This function pads each axis of an n-dimensional array by the given number
+/// of elements before and after the data.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `pad_width`: The number of elements padded `(before, after)` each axis.
+/// * `mode`: The padding mode.
+/// * `constant`: The padding value for `PadMode::Constant`, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: The padded array.
+/// * `Err(ImgalError)`: If the length of `pad_width` does not match the number
+///    of dimensions or if a non-constant mode pads an empty axis.
+pub fn pad<T>(
+    data: ArrayViewD<T>,
+    pad_width: &[(usize, usize)],
+    mode: PadMode,
+    constant: Option<T>,
+) -> Result<ArrayD<T>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let constant = constant.unwrap_or_default();
+
+    // check if parameters are valid
+    if pad_width.len() != data.ndim() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.ndim(),
+            b_arr_len: pad_width.len(),
+        });
+    }
+    if mode != PadMode::Constant
+        && data
+            .shape()
+            .iter()
+            .zip(pad_width)
+            .any(|(&n, &(b, a))| n == 0 && b + a > 0)
+    {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid padding, only constant padding can pad an empty axis.",
+        });
+    }
+
+    // map each output index to a source index
+    let shape: Vec<usize> = data
+        .shape()
+        .iter()
+        .zip(pad_width)
+        .map(|(&n, &(b, a))| n + b + a)
+        .collect();
+    let output = ArrayD::from_shape_fn(IxDyn(&shape), |idx| {
+        let mut src = Vec::with_capacity(pad_width.len());
+        for (ax, &(before, _)) in pad_width.iter().enumerate() {
+            let n = data.shape()[ax] as isize;
+            let i = idx[ax] as isize - before as isize;
+            match source_index(i, n, mode) {
+                Some(s) => src.push(s),
+                None => return constant,
+            }
+        }
+        data[IxDyn(&src)]
+    });
+
+    Ok(output)
+}
+
+/// Crop a region of an n-dimensional array.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `start`: The start index of the region along each axis.
+/// * `shape`: The shape of the region.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: The cropped region.
+/// * `Err(ImgalError)`: If the lengths of `start` or `shape` do not match the
+///    number of dimensions or if the region extends outside the array.
+pub fn crop<T>(
+    data: ArrayViewD<T>,
+    start: &[usize],
+    shape: &[usize],
+) -> Result<ArrayD<T>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if parameters are valid
+    for len in [start.len(), shape.len()] {
+        if len != data.ndim() {
+            return Err(ImgalError::MismatchedArrayLengths {
+                a_arr_len: data.ndim(),
+                b_arr_len: len,
+            });
+        }
+    }
+    if start
+        .iter()
+        .zip(shape)
+        .zip(data.shape())
+        .any(|((&s, &l), &n)| s + l > n)
+    {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid crop region, the region extends outside the array.",
+        });
+    }
+
+    Ok(data
+        .slice_each_axis(|ad| {
+            let ax = ad.axis.index();
+            Slice::from(start[ax]..start[ax] + shape[ax])
+        })
+        .to_owned())
+}
+
+/// Crop the center region of an n-dimensional array.
+///
+/// # Description
+///
+/// This function crops a region of the given shape centered on the array. If
+/// the difference between the array and region lengths is odd, the extra
+/// element is removed from the end of the axis.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `shape`: The shape of the region.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: The cropped center region.
+/// * `Err(ImgalError)`: If the length of `shape` does not match the number of
+///    dimensions or if the region is larger than the array.
+pub fn crop_center<T>(data: ArrayViewD<T>, shape: &[usize]) -> Result<ArrayD<T>, ImgalError>
+where
+    T: ToFloat64,
+{
+    if shape.len() != data.ndim() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.ndim(),
+            b_arr_len: shape.len(),
+        });
+    }
+    let start: Vec<usize> = data
+        .shape()
+        .iter()
+        .zip(shape)
+        .map(|(&n, &l)| n.saturating_sub(l) / 2)
+        .collect();
+
+    crop(data, &start, shape)
+}
+
+/// Map a padded axis index to a source axis index, `None` for constant padding.
+fn source_index(i: isize, n: isize, mode: PadMode) -> Option<usize> {
+    if (0..n).contains(&i) {
+        return Some(i as usize);
+    }
+    let s = match mode {
+        PadMode::Constant => return None,
+        PadMode::Edge => i.clamp(0, n - 1),
+        PadMode::Wrap => i.rem_euclid(n),
+        PadMode::Reflect => {
+            if n == 1 {
+                0
+            } else {
+                let period = 2 * (n - 1);
+                let r = i.rem_euclid(period);
+                if r < n { r } else { period - r }
+            }
+        }
+    };
+
+    Some(s as usize)
+}
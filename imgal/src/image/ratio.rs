@@ -0,0 +1,194 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+// the ratio (or concentration) image and its uncertainty
+type RatioOutput = (ArrayD<f64>, ArrayD<f64>);
+
+/// The calibration constants of a ratiometric indicator, see
+/// [`ratio_to_concentration`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatioCalibration {
+    /// The dissociation constant of the indicator, in the concentration unit.
+    pub kd: f64,
+    /// The ratio of the analyte free indicator.
+    pub r_min: f64,
+    /// The ratio of the analyte saturated indicator.
+    pub r_max: f64,
+    /// The ratio of the free to bound intensity of the denominator channel.
+    pub beta: f64,
+}
+
+/// Compute the ratio image of two channels with its propagated uncertainty.
+///
+/// # Description
+///
+/// This function computes the background subtracted ratio of two channels of
+/// a ratiometric indicator (_e.g._ Fura-2 340/380 nm excitation or a pH
+/// sensor):
+///
+/// ```text
+/// R = (A - bg_A) / (B - bg_B)
+/// σ_R = R · √(A / (A - bg_A)² + B / (B - bg_B)²)
+/// ```
+///
+/// Where "σ_R" is the Poisson (shot noise) uncertainty of the ratio, assuming
+/// the channels are in photon counts. Pixels where either background
+/// subtracted channel is at or below `threshold` are masked with NaN, since
+/// ratios of dim pixels are dominated by noise.
+///
+/// # Arguments
+///
+/// * `channel_a`: The numerator channel.
+/// * `channel_b`: The denominator channel, with the same shape as
+///    `channel_a`.
+/// * `background_a`: The background of the numerator, default = 0.0.
+/// * `background_b`: The background of the denominator, default = 0.0.
+/// * `threshold`: The minimum background subtracted intensity of both
+///    channels, default = 0.0.
+/// * `clamp`: The (min, max) range the ratio is clamped to. If `None`, the
+///    ratio is not clamped.
+///
+/// # Returns
+///
+/// * `Ok((ArrayD<f64>, ArrayD<f64>))`: The ratio image and its uncertainty,
+///    NaN in masked pixels.
+/// * `Err(ImgalError)`: If the channel shapes do not match. If the `clamp`
+///    minimum is greater than its maximum.
+pub fn ratio<T>(
+    channel_a: ArrayViewD<T>,
+    channel_b: ArrayViewD<T>,
+    background_a: Option<f64>,
+    background_b: Option<f64>,
+    threshold: Option<f64>,
+    clamp: Option<(f64, f64)>,
+) -> Result<RatioOutput, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bg_a = background_a.unwrap_or(0.0);
+    let bg_b = background_b.unwrap_or(0.0);
+    let threshold = threshold.unwrap_or(0.0);
+
+    // check if parameters are valid
+    if channel_a.shape() != channel_b.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: channel_a.shape().to_vec(),
+            shape_b: channel_b.shape().to_vec(),
+        });
+    }
+    if let Some((lo, hi)) = clamp
+        && lo > hi
+    {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "clamp",
+            value: lo,
+            min: f64::NEG_INFINITY,
+            max: hi,
+        });
+    }
+
+    let mut ratio = ArrayD::<f64>::zeros(channel_a.raw_dim());
+    let mut sigma = ArrayD::<f64>::zeros(channel_a.raw_dim());
+    Zip::from(&mut ratio)
+        .and(&mut sigma)
+        .and(&channel_a)
+        .and(&channel_b)
+        .par_for_each(|r, s, a, b| {
+            let (a, b) = (a.to_f64(), b.to_f64());
+            let (na, nb) = (a - bg_a, b - bg_b);
+            if na.is_nan() || nb.is_nan() || na <= threshold || nb <= threshold {
+                *r = f64::NAN;
+                *s = f64::NAN;
+                return;
+            }
+            let v = na / nb;
+            *s = v * (a.max(0.0) / (na * na) + b.max(0.0) / (nb * nb)).sqrt();
+            *r = match clamp {
+                Some((lo, hi)) => v.clamp(lo, hi),
+                None => v,
+            };
+        });
+
+    Ok((ratio, sigma))
+}
+
+/// Convert a ratio image into an analyte concentration image.
+///
+/// # Description
+///
+/// This function calibrates a ratio image (see [`ratio`]) with the
+/// Grynkiewicz equation:
+///
+/// ```text
+/// c = Kd · β · (R - R_min) / (R_max - R)
+/// σ_c = Kd · β · (R_max - R_min) / (R_max - R)² · σ_R
+/// ```
+///
+/// Ratios outside of the (R_min, R_max) calibration range have no finite
+/// concentration and are NaN.
+///
+/// # Arguments
+///
+/// * `ratio`: The ratio image.
+/// * `uncertainty`: The ratio uncertainty image, with the same shape as
+///    `ratio`.
+/// * `calibration`: The indicator calibration constants.
+///
+/// # Returns
+///
+/// * `Ok((ArrayD<f64>, ArrayD<f64>))`: The concentration image and its
+///    uncertainty.
+/// * `Err(ImgalError)`: If the image shapes do not match. If `r_min` is not
+///    less than `r_max`.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/S0021-9258(19)83641-4>
+pub fn ratio_to_concentration(
+    ratio: ArrayViewD<f64>,
+    uncertainty: ArrayViewD<f64>,
+    calibration: RatioCalibration,
+) -> Result<RatioOutput, ImgalError> {
+    // check if parameters are valid
+    if ratio.shape() != uncertainty.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: ratio.shape().to_vec(),
+            shape_b: uncertainty.shape().to_vec(),
+        });
+    }
+    let RatioCalibration {
+        kd,
+        r_min,
+        r_max,
+        beta,
+    } = calibration;
+    if r_min.is_nan() || r_max.is_nan() || r_min >= r_max {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "r_min",
+            value: r_min,
+            min: f64::NEG_INFINITY,
+            max: r_max,
+        });
+    }
+
+    let mut conc = ArrayD::<f64>::zeros(ratio.raw_dim());
+    let mut sigma = ArrayD::<f64>::zeros(ratio.raw_dim());
+    Zip::from(&mut conc)
+        .and(&mut sigma)
+        .and(&ratio)
+        .and(&uncertainty)
+        .par_for_each(|c, s, &r, &u| {
+            if r < r_min || r >= r_max {
+                *c = f64::NAN;
+                *s = f64::NAN;
+                return;
+            }
+            *c = kd * beta * (r - r_min) / (r_max - r);
+            *s = kd * beta * (r_max - r_min) / (r_max - r).powi(2) * u;
+        });
+
+    Ok((conc, sigma))
+}
@@ -0,0 +1,501 @@
+use ndarray::{Array2, Array3, ArrayView2, ArrayViewMut1, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::statistics::max;
+use crate::traits::numeric::ToFloat64;
+
+// perceptual colormap anchors, sampled at 9 evenly spaced points
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 44, 122],
+    [59, 82, 139],
+    [44, 114, 142],
+    [33, 145, 140],
+    [40, 174, 128],
+    [94, 201, 98],
+    [173, 220, 48],
+    [253, 231, 37],
+];
+const MAGMA: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 135, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+const INFERNO: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [31, 12, 72],
+    [85, 15, 109],
+    [136, 34, 106],
+    [186, 54, 85],
+    [227, 89, 51],
+    [249, 142, 9],
+    [248, 201, 50],
+    [252, 255, 164],
+];
+const PLASMA: [[u8; 3]; 9] = [
+    [13, 8, 135],
+    [76, 2, 161],
+    [126, 3, 168],
+    [169, 35, 149],
+    [204, 71, 120],
+    [230, 108, 92],
+    [248, 149, 64],
+    [253, 197, 39],
+    [240, 249, 33],
+];
+const CIVIDIS: [[u8; 3]; 9] = [
+    [0, 34, 78],
+    [18, 53, 112],
+    [59, 73, 108],
+    [87, 93, 109],
+    [112, 113, 115],
+    [138, 134, 120],
+    [165, 156, 116],
+    [195, 179, 105],
+    [254, 232, 56],
+];
+// Turbo colormap anchors, sampled at 17 evenly spaced points of its
+// polynomial approximation
+const TURBO: [[u8; 3]; 17] = [
+    [35, 23, 27],
+    [73, 62, 175],
+    [68, 106, 238],
+    [50, 149, 247],
+    [38, 189, 225],
+    [41, 221, 187],
+    [64, 243, 146],
+    [102, 253, 109],
+    [150, 250, 80],
+    [198, 235, 59],
+    [238, 208, 45],
+    [255, 171, 36],
+    [255, 128, 29],
+    [238, 84, 21],
+    [201, 45, 12],
+    [161, 18, 2],
+    [144, 13, 0],
+];
+
+/// Built-in colormaps for pseudocolor rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    /// Perceptually uniform blue-green-yellow colormap.
+    Viridis,
+    /// Perceptually uniform black-purple-white colormap.
+    Magma,
+    /// Perceptually uniform black-red-yellow colormap.
+    Inferno,
+    /// Perceptually uniform blue-red-yellow colormap.
+    Plasma,
+    /// Perceptually uniform, color vision deficiency friendly, blue-yellow
+    /// colormap.
+    Cividis,
+    /// Smooth rainbow-like blue-green-red colormap with a high dynamic range.
+    Turbo,
+    /// Classic FLIM hue ramp from blue (low values) to red (high values).
+    Hsv,
+}
+
+impl Colormap {
+    /// Find a built-in colormap by its lowercase name (_e.g._ "viridis").
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        match name.to_lowercase().as_str() {
+            "viridis" => Some(Colormap::Viridis),
+            "magma" => Some(Colormap::Magma),
+            "inferno" => Some(Colormap::Inferno),
+            "plasma" => Some(Colormap::Plasma),
+            "cividis" => Some(Colormap::Cividis),
+            "turbo" => Some(Colormap::Turbo),
+            "hsv" => Some(Colormap::Hsv),
+            _ => None,
+        }
+    }
+
+    /// Map a normalized value, between 0.0 and 1.0, to an RGB color. Values
+    /// outside of the range are clamped.
+    pub fn map(&self, value: f64) -> [u8; 3] {
+        let v = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+        match self {
+            Colormap::Viridis => interpolate_anchors(&VIRIDIS, v),
+            Colormap::Magma => interpolate_anchors(&MAGMA, v),
+            Colormap::Inferno => interpolate_anchors(&INFERNO, v),
+            Colormap::Plasma => interpolate_anchors(&PLASMA, v),
+            Colormap::Cividis => interpolate_anchors(&CIVIDIS, v),
+            Colormap::Turbo => interpolate_anchors(&TURBO, v),
+            Colormap::Hsv => hsv_to_rgb((1.0 - v) * 240.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// Render a lifetime map as an intensity weighted pseudocolor RGB image.
+///
+/// # Description
+///
+/// This function renders the standard FLIM display, an HSV-style composite
+/// where the color (hue) encodes the lifetime and the brightness (value)
+/// encodes the intensity:
+///
+/// ```text
+/// RGB = C((τ - τₘᵢₙ) / (τₘₐₓ - τₘᵢₙ)) * I / Iₘₐₓ
+/// ```
+///
+/// Where "C" is the colormap. Pixels with a NaN lifetime are rendered black.
+///
+/// # Arguments
+///
+/// * `tau_map`: The 2-dimensional lifetime image.
+/// * `intensity`: The 2-dimensional intensity image (_e.g._ photon counts).
+///    Must have the same shape as `tau_map`.
+/// * `colormap`: The colormap used to encode the lifetime, default =
+///    `Colormap::Hsv`.
+/// * `tau_range`: The (min, max) lifetime range mapped to the colormap. If
+///    `None`, the minimum and maximum finite lifetimes of `tau_map` are used.
+///
+/// # Returns
+///
+/// * `Ok(Array3<u8>)`: The 3-dimensional (row, col, ch) RGB image.
+/// * `Err(ImgalError)`: If the shapes of `tau_map` and `intensity` do not
+///    match.
+pub fn lifetime_rgb<T>(
+    tau_map: ArrayView2<f64>,
+    intensity: ArrayView2<T>,
+    colormap: Option<Colormap>,
+    tau_range: Option<(f64, f64)>,
+) -> Result<Array3<u8>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check input image shapes match
+    let shape = tau_map.dim();
+    if intensity.dim() != shape {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![shape.0, shape.1],
+            shape_b: intensity.shape().to_vec(),
+        });
+    }
+
+    // set optional parameters if needed
+    let cmap = colormap.unwrap_or(Colormap::Hsv);
+    let (tau_min, tau_max) = tau_range.unwrap_or_else(|| {
+        tau_map
+            .iter()
+            .filter(|t| t.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(mn, mx), &t| {
+                (mn.min(t), mx.max(t))
+            })
+    });
+    let tau_width = tau_max - tau_min;
+    let inten_max = max(intensity.into_dyn()).to_f64();
+
+    // compose hue (lifetime) and value (intensity) per pixel
+    let mut rgb = Array3::<u8>::zeros((shape.0, shape.1, 3));
+    Zip::from(rgb.lanes_mut(Axis(2)))
+        .and(tau_map)
+        .and(intensity)
+        .par_for_each(|mut ln, &t, &i| {
+            if t.is_nan() || inten_max <= 0.0 {
+                return;
+            }
+            let norm_t = if tau_width > 0.0 {
+                (t - tau_min) / tau_width
+            } else {
+                0.0
+            };
+            let value = (i.to_f64() / inten_max).clamp(0.0, 1.0);
+            let color = cmap.map(norm_t);
+            ln.iter_mut().zip(color.iter()).for_each(|(v, &c)| {
+                *v = (c as f64 * value).round() as u8;
+            });
+        });
+
+    Ok(rgb)
+}
+
+/// Render a 2-dimensional image as a pseudocolor RGB image.
+///
+/// # Description
+///
+/// This function maps each pixel value to a color of a colormap (_e.g._ to
+/// export a lifetime or ratio map as an 8-bit RGB image):
+///
+/// ```text
+/// RGB = C((x - min) / (max - min))
+/// ```
+///
+/// Where "C" is the colormap. Values outside of the range are clamped and NaN
+/// pixels are rendered black.
+///
+/// # Arguments
+///
+/// * `image`: The 2-dimensional image.
+/// * `colormap`: The colormap, default = `Colormap::Viridis`.
+/// * `range`: The (min, max) value range mapped to the colormap. If `None`,
+///    the minimum and maximum finite values of `image` are used.
+///
+/// # Returns
+///
+/// * `Ok(Array3<u8>)`: The 3-dimensional (row, col, ch) RGB image.
+/// * `Err(ImgalError)`: If the max of `range` is not greater than its min.
+pub fn apply_colormap<T>(
+    image: ArrayView2<T>,
+    colormap: Option<Colormap>,
+    range: Option<(f64, f64)>,
+) -> Result<Array3<u8>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let cmap = colormap.unwrap_or(Colormap::Viridis);
+
+    let norm = normalize(image, range)?;
+    let mut rgb = Array3::<u8>::zeros((norm.nrows(), norm.ncols(), 3));
+    Zip::from(rgb.lanes_mut(Axis(2)))
+        .and(&norm)
+        .par_for_each(|mut ln, &v| {
+            if !v.is_nan() {
+                ln.iter_mut().zip(cmap.map(v)).for_each(|(o, c)| *o = c);
+            }
+        });
+
+    Ok(rgb)
+}
+
+/// Overlay a boolean mask on a 2-dimensional grayscale image.
+///
+/// # Description
+///
+/// This function renders `image` in grayscale and blends the mask pixels with
+/// a solid color:
+///
+/// ```text
+/// RGB = (1 - α) · gray + α · color
+/// ```
+///
+/// Where "α" is the opacity. NaN pixels are rendered black.
+///
+/// # Arguments
+///
+/// * `image`: The 2-dimensional grayscale image.
+/// * `mask`: The 2-dimensional boolean mask, with the shape of `image`.
+/// * `color`: The RGB color of the mask, default = `[255, 0, 0]` (red).
+/// * `alpha`: The opacity of the mask, between 0.0 and 1.0, default = 0.5.
+/// * `range`: The (min, max) value range mapped from black to white. If
+///    `None`, the minimum and maximum finite values of `image` are used.
+///
+/// # Returns
+///
+/// * `Ok(Array3<u8>)`: The 3-dimensional (row, col, ch) RGB image.
+/// * `Err(ImgalError)`: If the shapes of `image` and `mask` do not match. If
+///    `alpha` is outside of 0.0 to 1.0. If the max of `range` is not greater
+///    than its min.
+pub fn overlay_mask<T>(
+    image: ArrayView2<T>,
+    mask: ArrayView2<bool>,
+    color: Option<[u8; 3]>,
+    alpha: Option<f64>,
+    range: Option<(f64, f64)>,
+) -> Result<Array3<u8>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let color = color.unwrap_or([255, 0, 0]);
+    let alpha = alpha.unwrap_or(0.5);
+
+    // check if parameters are valid
+    if image.shape() != mask.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: image.shape().to_vec(),
+            shape_b: mask.shape().to_vec(),
+        });
+    }
+    check_alpha(alpha)?;
+
+    let norm = normalize(image, range)?;
+    let mut rgb = Array3::<u8>::zeros((norm.nrows(), norm.ncols(), 3));
+    Zip::from(rgb.lanes_mut(Axis(2)))
+        .and(&norm)
+        .and(mask)
+        .par_for_each(|ln, &v, &m| {
+            let a = if m { alpha } else { 0.0 };
+            blend(ln, v, color, a);
+        });
+
+    Ok(rgb)
+}
+
+/// Overlay a label image on a 2-dimensional grayscale image.
+///
+/// # Description
+///
+/// This function renders `image` in grayscale and blends each labeled region
+/// with a distinct color, so segmentations can be checked visually. Colors
+/// are spread around the hue circle by the golden angle, so consecutive
+/// labels have well separated colors. NaN pixels are rendered black.
+///
+/// # Arguments
+///
+/// * `image`: The 2-dimensional grayscale image.
+/// * `labels`: The 2-dimensional label image, with the shape of `image`,
+///    where 0 is background.
+/// * `alpha`: The opacity of the labels, between 0.0 and 1.0, default = 0.5.
+/// * `range`: The (min, max) value range mapped from black to white. If
+///    `None`, the minimum and maximum finite values of `image` are used.
+///
+/// # Returns
+///
+/// * `Ok(Array3<u8>)`: The 3-dimensional (row, col, ch) RGB image.
+/// * `Err(ImgalError)`: If the shapes of `image` and `labels` do not match.
+///    If `alpha` is outside of 0.0 to 1.0. If the max of `range` is not
+///    greater than its min.
+pub fn overlay_labels<T>(
+    image: ArrayView2<T>,
+    labels: ArrayView2<usize>,
+    alpha: Option<f64>,
+    range: Option<(f64, f64)>,
+) -> Result<Array3<u8>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let alpha = alpha.unwrap_or(0.5);
+
+    // check if parameters are valid
+    if image.shape() != labels.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: image.shape().to_vec(),
+            shape_b: labels.shape().to_vec(),
+        });
+    }
+    check_alpha(alpha)?;
+
+    let norm = normalize(image, range)?;
+    let mut rgb = Array3::<u8>::zeros((norm.nrows(), norm.ncols(), 3));
+    Zip::from(rgb.lanes_mut(Axis(2)))
+        .and(&norm)
+        .and(labels)
+        .par_for_each(|ln, &v, &l| {
+            if l == 0 {
+                blend(ln, v, [0, 0, 0], 0.0);
+            } else {
+                blend(ln, v, label_color(l), alpha);
+            }
+        });
+
+    Ok(rgb)
+}
+
+/// Compute the distinct color of a label, with hues spread by the golden
+/// angle.
+pub(crate) fn label_color(label: usize) -> [u8; 3] {
+    hsv_to_rgb((label as f64 * 137.507_764) % 360.0, 0.9, 1.0)
+}
+
+/// Blend a normalized gray value with a color into an RGB lane, NaN values
+/// are left black.
+fn blend(mut lane: ArrayViewMut1<u8>, value: f64, color: [u8; 3], alpha: f64) {
+    if value.is_nan() {
+        return;
+    }
+    let gray = value.clamp(0.0, 1.0) * 255.0;
+    lane.iter_mut().zip(color).for_each(|(o, c)| {
+        *o = ((1.0 - alpha) * gray + alpha * c as f64).round() as u8;
+    });
+}
+
+/// Check that an opacity is between 0.0 and 1.0.
+fn check_alpha(alpha: f64) -> Result<(), ImgalError> {
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "alpha",
+            value: alpha,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Normalize a 2-dimensional image to 0.0 to 1.0 within a value range, NaN
+/// values are kept.
+fn normalize<T>(image: ArrayView2<T>, range: Option<(f64, f64)>) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let image = image.mapv(|v| v.to_f64());
+    let (lo, hi) = match range {
+        Some((lo, hi)) => {
+            if hi.is_nan() || hi <= lo {
+                return Err(ImgalError::InvalidParameterValueOutsideRange {
+                    param_name: "range",
+                    value: hi,
+                    min: lo,
+                    max: f64::INFINITY,
+                });
+            }
+            (lo, hi)
+        }
+        None => image
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            }),
+    };
+    let width = hi - lo;
+
+    Ok(image.mapv(|v| {
+        if width > 0.0 {
+            (v - lo) / width
+        } else {
+            0.0 * v
+        }
+    }))
+}
+
+/// Convert an HSV color, with hue in degrees, to an 8-bit RGB color.
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> [u8; 3] {
+    let c = value * saturation;
+    let h = (hue % 360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as usize {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    [
+        ((r + m) * 255.0).round() as u8,
+        ((g + m) * 255.0).round() as u8,
+        ((b + m) * 255.0).round() as u8,
+    ]
+}
+
+/// Linearly interpolate evenly spaced colormap anchors at a normalized value.
+fn interpolate_anchors(anchors: &[[u8; 3]], value: f64) -> [u8; 3] {
+    let pos = value * (anchors.len() - 1) as f64;
+    let lo = (pos.floor() as usize).min(anchors.len() - 2);
+    let frac = pos - lo as f64;
+    let mut color = [0_u8; 3];
+    color.iter_mut().enumerate().for_each(|(k, c)| {
+        let a = anchors[lo][k] as f64;
+        let b = anchors[lo + 1][k] as f64;
+        *c = (a + (b - a) * frac).round() as u8;
+    });
+
+    color
+}
@@ -0,0 +1,136 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::FromFloat64;
+
+/// Create a boolean mask of the saturated pixels of an n-dimensional image.
+///
+/// # Description
+///
+/// This function flags the pixels at or above a saturation level. Saturated
+/// pixels hold clipped intensities, which silently bias downstream analyses
+/// (_e.g._ phasor coordinates or colocalization coefficients) and should be
+/// excluded or reported.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+/// * `value`: The saturation level, default = the maximum value of the image
+///    type (_e.g._ 65535 for `u16`). Set the level explicitly for detectors
+///    that saturate below the type maximum (_e.g._ 4095 for a 12-bit camera
+///    stored as `u16`).
+///
+/// # Returns
+///
+/// * `ArrayD<bool>`: The saturation mask, where `true` pixels are saturated.
+pub fn saturation_mask<T>(data: ArrayViewD<T>, value: Option<f64>) -> ArrayD<bool>
+where
+    T: FromFloat64,
+{
+    // set optional parameters if needed
+    let value = value.unwrap_or(T::MAX_F64);
+
+    let mut mask = ArrayD::<bool>::default(data.raw_dim());
+    Zip::from(&mut mask)
+        .and(&data)
+        .par_for_each(|m, &v| *m = v.to_f64() >= value);
+
+    mask
+}
+
+/// Count the saturated pixels of each frame of an n-dimensional image.
+///
+/// # Description
+///
+/// This function counts the pixels at or above a saturation level (see
+/// [`saturation_mask`]) in each frame along `axis` (_e.g._ each time point of
+/// a time series or each time bin of a decay image).
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+/// * `value`: The saturation level, default = the maximum value of the image
+///    type.
+/// * `axis`: The frame axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<usize>)`: The number of saturated pixels of each frame.
+/// * `Err(ImgalError)`: If `axis` is >= the number of dimensions of `data`.
+pub fn saturation_counts<T>(
+    data: ArrayViewD<T>,
+    value: Option<f64>,
+    axis: Option<usize>,
+) -> Result<Vec<usize>, ImgalError>
+where
+    T: FromFloat64,
+{
+    // set optional parameters if needed
+    let value = value.unwrap_or(T::MAX_F64);
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= data.ndim() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: data.ndim(),
+        });
+    }
+
+    Ok(data
+        .axis_iter(Axis(a))
+        .map(|frame| frame.iter().filter(|v| v.to_f64() >= value).count())
+        .collect())
+}
+
+/// Count the saturated pixels of each region of a labeled n-dimensional
+/// image.
+///
+/// # Description
+///
+/// This function counts the pixels at or above a saturation level (see
+/// [`saturation_mask`]) within each region of a label image (_e.g._ from
+/// [`crate::segmentation::label`]), so that regions with clipped intensities
+/// can be flagged or excluded.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image.
+/// * `labels`: The label image, with the same shape as `data`.
+/// * `value`: The saturation level, default = the maximum value of the image
+///    type.
+///
+/// # Returns
+///
+/// * `Ok(Vec<usize>)`: The number of saturated pixels of each label, indexed
+///    by label from 0 (_i.e._ the background) to the largest label.
+/// * `Err(ImgalError)`: If `data` and `labels` shapes do not match.
+pub fn saturation_region_counts<T>(
+    data: ArrayViewD<T>,
+    labels: ArrayViewD<usize>,
+    value: Option<f64>,
+) -> Result<Vec<usize>, ImgalError>
+where
+    T: FromFloat64,
+{
+    // set optional parameters if needed
+    let value = value.unwrap_or(T::MAX_F64);
+
+    // check if parameters are valid
+    if data.shape() != labels.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data.shape().to_vec(),
+            shape_b: labels.shape().to_vec(),
+        });
+    }
+
+    let n = labels.iter().max().map_or(0, |&l| l + 1);
+    let mut counts = vec![0; n];
+    Zip::from(&data).and(&labels).for_each(|&v, &l| {
+        if v.to_f64() >= value {
+            counts[l] += 1;
+        }
+    });
+
+    Ok(counts)
+}
@@ -1,4 +1,4 @@
-use crate::statistics::sum;
+use crate::statistics::{Summation, compensated_sum};
 use crate::traits::numeric::ToFloat64;
 
 /// Integrate a curve with the midpoint rule.
@@ -12,18 +12,22 @@ use crate::traits::numeric::ToFloat64;
 /// ∫f(x) dx ≈ Δx * [f(x₁) + f(x₂) + ... + f(xₙ)]
 /// ```
 ///
+/// The values are accumulated as 64-bit floats with the selected summation
+/// algorithm (see [`compensated_sum`]).
+///
 /// # Arguments
 ///
 /// * `x`: The 1-dimensional array to integrate.
 /// * `delta_x`: The width between data points, default = 1.0.
+/// * `summation`: The summation algorithm, default = `Summation::Naive`.
 ///
 /// # Returns
 ///
 /// * `f64`: The computed integral.
 #[inline]
-pub fn midpoint<T>(x: &[T], delta_x: Option<f64>) -> f64
+pub fn midpoint<T>(x: &[T], delta_x: Option<f64>, summation: Option<Summation>) -> f64
 where
     T: ToFloat64,
 {
-    delta_x.unwrap_or(1.0) * sum(x).to_f64()
+    delta_x.unwrap_or(1.0) * compensated_sum(x, summation)
 }
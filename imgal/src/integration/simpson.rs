@@ -1,4 +1,5 @@
 use crate::error::ImgalError;
+use crate::statistics::{Summation, compensated_sum};
 use crate::traits::numeric::ToFloat64;
 
 /// Integrate a curve with Simpson's 1/3 rule and the trapezoid rule.
@@ -25,11 +26,13 @@ use crate::traits::numeric::ToFloat64;
 ///
 /// * `x`: The 1-dimensional data to integrate.
 /// * `delta_x`: The width between data points, default = 1.0.
+/// * `summation`: The summation algorithm of the weighted values, default =
+///    `Summation::Naive`.
 ///
 /// # Returns
 ///
 /// * `f64`: The computed integral.
-pub fn composite_simpson<T>(x: &[T], delta_x: Option<f64>) -> f64
+pub fn composite_simpson<T>(x: &[T], delta_x: Option<f64>, summation: Option<Summation>) -> f64
 where
     T: ToFloat64,
 {
@@ -39,10 +42,10 @@ where
     let n: usize = x.len() - 1;
     // check for even number of subintervals
     if n % 2 == 0 {
-        simpson(x, delta_x).unwrap()
+        simpson(x, delta_x, summation).unwrap()
     } else {
         // compute the even subintervals with Simpson's rule
-        let integral: f64 = simpson(&x[..n], delta_x).unwrap();
+        let integral: f64 = simpson(&x[..n], delta_x, summation).unwrap();
         // compute the last subinterval with a trapizoid
         let trap: f64 = (d_x / 2.0) * (x[n - 1] + x[n]).to_f64();
         integral + trap
@@ -60,18 +63,26 @@ where
 /// ∫(f(x)dx) ≈ (Δx/3) * [f(x₀) + 4f(x₁) + 2f(x₂) + 4f(x₃) + ... + 2f(xₙ₋₂) + 4f(xₙ₋₁) + f(xₙ)]
 /// ```
 ///
-/// Where "n" is the number of evenly spaced points in the data.
+/// Where "n" is the number of evenly spaced points in the data. The weighted
+/// values are accumulated as 64-bit floats with the selected summation
+/// algorithm (see [`compensated_sum`]).
 ///
 /// # Arguments
 ///
 /// * `x`: The 1-dimensional data to integrate with an even number of subintervals.
 /// * `delta_x`: The width between data points, default = 1.0.
+/// * `summation`: The summation algorithm of the weighted values, default =
+///    `Summation::Naive`.
 ///
 /// # Returns
 ///
 /// * `Ok(f64)`: The computed integral.
 /// * `Err(ImgalError)`: If the number of subintervals is odd.
-pub fn simpson<T>(x: &[T], delta_x: Option<f64>) -> Result<f64, ImgalError>
+pub fn simpson<T>(
+    x: &[T],
+    delta_x: Option<f64>,
+    summation: Option<Summation>,
+) -> Result<f64, ImgalError>
 where
     T: ToFloat64,
 {
@@ -82,13 +93,14 @@ where
     // check for even number of subintervals
     if n % 2 == 0 {
         // compute integal with Simpson's rule
-        let mut coef: f64;
-        let mut integral: f64 = (x[0] + x[n]).to_f64();
-        for i in 1..n {
-            coef = if i % 2 == 1 { 4.0 } else { 2.0 };
-            integral += coef * x[i].to_f64();
+        let mut terms: Vec<f64> = Vec::with_capacity(n + 1);
+        terms.push(x[0].to_f64());
+        terms.push(x[n].to_f64());
+        for (i, v) in x.iter().enumerate().take(n).skip(1) {
+            let coef = if i % 2 == 1 { 4.0 } else { 2.0 };
+            terms.push(coef * v.to_f64());
         }
-        Ok((d_x / 3.0) * integral)
+        Ok((d_x / 3.0) * compensated_sum(&terms, summation))
     } else {
         return Err(ImgalError::InvalidArrayGeneric {
             msg: "An odd number of subintervals is not allowed in Simpson's 1/3 rule integration.",
@@ -231,3 +231,124 @@ pub fn weighted_sphere(
 
     Ok(kernel)
 }
+
+/// Create a 2-dimensional square kernel with a Gaussian weighted circle
+/// neighborhood.
+///
+/// # Description
+///
+/// This function creates a square kernel representing a Gaussian weighted
+/// circle of the specified radius (_i.e._ the neighborhood). Points within the
+/// radius are assigned a weight using the Gaussian function of their Euclidean
+/// distance from the center point, while points outside are set to 0.0:
+///
+/// ```text
+/// w(d) = w₀ * exp(-d² / 2σ²)
+/// ```
+///
+/// # Arguments
+///
+/// * `circle_radius`: The radius of the circle in pixels. Must be greater than
+///    0.
+/// * `sigma`: The standard deviation of the Gaussian function, in pixels.
+/// * `initial_value`: The maximum weight value at the center of the kernel,
+///    default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: A 2-dimensional square array with side lengths of
+///    "radius * 2 + 1" with a Gaussian weighted circular neighborhood.
+/// * `Err(ImgalError)`: If circle radius is <= 0.
+pub fn gaussian_circle(
+    circle_radius: usize,
+    sigma: f64,
+    initial_value: Option<f64>,
+) -> Result<Array2<f64>, ImgalError> {
+    // check if circle_radius parameter is valid
+    if circle_radius == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "circle_radius",
+            value: 0,
+        });
+    }
+
+    // set circle parameters and create weighted kernel
+    let dim = circle_radius * 2 + 1;
+    let center = circle_radius as f64;
+    let iv = initial_value.unwrap_or(1.0);
+    let two_sigma_sqr = 2.0 * sigma.powi(2);
+    let mut kernel = Array2::<f64>::zeros((dim, dim));
+
+    // iterate through each position and calculate euclidean distance and weights
+    kernel.indexed_iter_mut().for_each(|((row, col), v)| {
+        let x = col as f64;
+        let y = row as f64;
+        let dist_sqr = (x - center).powi(2) + (y - center).powi(2);
+        if dist_sqr.sqrt() <= center {
+            *v = iv * (-dist_sqr / two_sigma_sqr).exp();
+        }
+    });
+
+    Ok(kernel)
+}
+
+/// Create a 3-dimensional cube kernel with a Gaussian weighted sphere
+/// neighborhood.
+///
+/// # Description
+///
+/// This function creates a cube kernel representing a Gaussian weighted sphere
+/// of the specified radius (_i.e._ the neighborhood). Points within the radius
+/// are assigned a weight using the Gaussian function of their Euclidean
+/// distance from the center point, while points outside are set to 0.0:
+///
+/// ```text
+/// w(d) = w₀ * exp(-d² / 2σ²)
+/// ```
+///
+/// # Arguments
+///
+/// * `sphere_radius`: The radius of the sphere in voxels. Must be greater than
+///    0.
+/// * `sigma`: The standard deviation of the Gaussian function, in voxels.
+/// * `initial_value`: The maximum weight value at the center of the kernel,
+///    default = 1.0.
+///
+/// # Returns
+///
+/// * `OK(Array3<f64>)`: A 3-dimensional cube array with side lengths of
+///    "radius * 2 + 1" with a Gaussian weighted spherical neighborhood.
+/// * `Err(ImgalError)`: If the sphere radius is <= 0.
+pub fn gaussian_sphere(
+    sphere_radius: usize,
+    sigma: f64,
+    initial_value: Option<f64>,
+) -> Result<Array3<f64>, ImgalError> {
+    // check if the sphere_radius parameter is valid
+    if sphere_radius == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "sphere_radius",
+            value: 0,
+        });
+    }
+
+    // set sphere parameters and create a weighted kernel
+    let dim = sphere_radius * 2 + 1;
+    let center = sphere_radius as f64;
+    let iv = initial_value.unwrap_or(1.0);
+    let two_sigma_sqr = 2.0 * sigma.powi(2);
+    let mut kernel = Array3::<f64>::zeros((dim, dim, dim));
+
+    // iterate through each position and calculate euclidean distance and weights
+    kernel.indexed_iter_mut().for_each(|((pln, row, col), v)| {
+        let x = col as f64;
+        let y = row as f64;
+        let z = pln as f64;
+        let dist_sqr = (x - center).powi(2) + (y - center).powi(2) + (z - center).powi(2);
+        if dist_sqr.sqrt() <= center {
+            *v = iv * (-dist_sqr / two_sigma_sqr).exp();
+        }
+    });
+
+    Ok(kernel)
+}
@@ -13,15 +13,29 @@
 //!
 //! This crate is still under active development and it's API is not stable.
 pub mod colocalization;
+#[cfg(feature = "data")]
+pub mod data;
 pub mod distribution;
 pub mod error;
+pub mod export;
+pub mod feature;
 pub mod filter;
+pub mod fitting;
 pub mod image;
 pub mod integration;
 pub mod kernel;
+pub mod localization;
+pub mod measure;
+pub mod motion;
+pub mod ops;
 pub mod parameter;
 pub mod phasor;
+pub mod pipeline;
+pub mod registration;
+pub mod segmentation;
 pub mod simulation;
 pub mod statistics;
 pub mod threshold;
+pub mod tracking;
 pub mod traits;
+pub mod transform;
@@ -0,0 +1,106 @@
+use crate::error::ImgalError;
+use crate::localization::{Localization, render};
+use crate::registration::phase_correlation;
+
+// the drift corrected localizations and the (row, col) drift of each frame
+type DriftOutput = (Vec<Localization>, Vec<(f64, f64)>);
+
+/// Correct the sample drift of localizations by cross-correlation.
+///
+/// # Description
+///
+/// This function splits the localizations (see
+/// [`crate::localization::localize`]) into segments of `segment` consecutive
+/// frames, renders each segment into a Gaussian super-resolved image (see
+/// [`crate::localization::render`]) and measures its translation from the
+/// first segment with phase correlation (see
+/// [`crate::registration::phase_correlation`]). The drift of each frame is
+/// linearly interpolated between the segment centers (and held constant
+/// before the first and after the last center) and subtracted from its
+/// localizations. Segments need enough localizations of the same structures
+/// to correlate, drifts larger than half the image size are not recovered.
+///
+/// # Arguments
+///
+/// * `locs`: The localizations.
+/// * `shape`: The (row, col) shape of the camera frames.
+/// * `segment`: The number of frames per segment.
+/// * `magnification`: The number of rendered pixels per camera pixel, which
+///    sets the drift precision, default = 5.0.
+///
+/// # Returns
+///
+/// * `Ok((Vec<Localization>, Vec<(f64, f64)>))`: The drift corrected
+///    localizations and the (row, col) drift of each frame, in camera pixels,
+///    from frame 0 to the last frame.
+/// * `Err(ImgalError)`: If `segment` is 0. If `magnification` is not positive.
+pub fn drift_correct(
+    locs: &[Localization],
+    shape: (usize, usize),
+    segment: usize,
+    magnification: Option<f64>,
+) -> Result<DriftOutput, ImgalError> {
+    // set optional parameters if needed
+    let mag = magnification.unwrap_or(5.0);
+
+    // check if parameters are valid
+    if segment == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "segment",
+            value: 0,
+        });
+    }
+    let Some(n_frames) = locs.iter().map(|l| l.frame + 1).max() else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    // measure the translation of each segment from the first segment
+    let n_segments = n_frames.div_ceil(segment);
+    let images = (0..n_segments)
+        .map(|s| {
+            let seg: Vec<Localization> = locs
+                .iter()
+                .filter(|l| l.frame / segment == s)
+                .copied()
+                .collect();
+            render(&seg, shape, Some(mag), Some(1.0 / mag))
+        })
+        .collect::<Result<Vec<_>, ImgalError>>()?;
+    let knots = images
+        .iter()
+        .enumerate()
+        .map(|(s, img)| {
+            let (dr, dc) = phase_correlation(images[0].view(), img.view())?;
+            let center =
+                (s * segment) as f64 + (segment.min(n_frames - s * segment) - 1) as f64 / 2.0;
+            Ok((center, (-dr / mag, -dc / mag)))
+        })
+        .collect::<Result<Vec<(f64, (f64, f64))>, ImgalError>>()?;
+
+    // interpolate the drift of each frame and subtract it
+    let drift: Vec<(f64, f64)> = (0..n_frames)
+        .map(|f| {
+            let f = f as f64;
+            let next = knots.partition_point(|k| k.0 < f);
+            match (next.checked_sub(1).map(|p| knots[p]), knots.get(next)) {
+                (Some(a), Some(b)) => {
+                    let t = (f - a.0) / (b.0 - a.0);
+                    (a.1.0 + (b.1.0 - a.1.0) * t, a.1.1 + (b.1.1 - a.1.1) * t)
+                }
+                (Some(a), None) => a.1,
+                (None, Some(b)) => b.1,
+                (None, None) => (0.0, 0.0),
+            }
+        })
+        .collect();
+    let corrected = locs
+        .iter()
+        .map(|l| Localization {
+            row: l.row - drift[l.frame].0,
+            col: l.col - drift[l.frame].1,
+            ..*l
+        })
+        .collect();
+
+    Ok((corrected, drift))
+}
@@ -0,0 +1,204 @@
+use std::f64::consts::PI;
+
+use ndarray::{ArrayView2, ArrayView3, Axis, Slice};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::fitting::gaussian::fit_gaussian;
+use crate::localization::phasor::{phasor_position, phasor_sigma};
+use crate::measure::psf::detect_peaks;
+use crate::traits::numeric::ToFloat64;
+
+/// Sub-pixel spot localization methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalizationMethod {
+    /// Least squares fit of a 2-dimensional Gaussian with a constant offset
+    /// (see [`crate::fitting::gaussian_2d`]).
+    Gaussian,
+    /// Phase of the first Fourier coefficients of the spot neighborhood
+    /// (see [`crate::localization::phasor_position`]), non-iterative and about
+    /// 100 times faster than `Gaussian`.
+    Phasor,
+}
+
+/// A single emitter localization, see [`localize`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Localization {
+    /// The frame index of the localization.
+    pub frame: usize,
+    /// The sub-pixel row position, in pixels.
+    pub row: f64,
+    /// The sub-pixel column position, in pixels.
+    pub col: f64,
+    /// The integrated intensity of the spot above the background.
+    pub intensity: f64,
+    /// The background level around the spot.
+    pub background: f64,
+    /// The width (standard deviation) of the spot, in pixels.
+    pub sigma: f64,
+}
+
+/// Localize blinking emitters in a 3-dimensional time-lapse.
+///
+/// # Description
+///
+/// This function detects the spots of each frame of a (t, row, col)
+/// single-molecule localization microscopy (SMLM) acquisition (_e.g._ PALM or
+/// STORM) as local intensity maxima above `threshold` that are the brightest
+/// pixel within `radius` pixels, and localizes each spot with sub-pixel
+/// precision from its (2 * radius + 1)² neighborhood. Frames are processed in
+/// parallel. Spots whose neighborhood crosses the image border and spots
+/// whose fit fails, whose center leaves the neighborhood or whose width
+/// exceeds `radius` are skipped. The localizations can then be corrected for
+/// drift (see [`crate::localization::drift_correct`]) and rendered into a
+/// super-resolved image (see [`crate::localization::render`]).
+///
+/// # Arguments
+///
+/// * `data`: The (t, row, col) time-lapse of blinking emitters.
+/// * `radius`: The radius of the detection and fit neighborhood in pixels,
+///    which should be about 3 times the spot width.
+/// * `threshold`: The minimum peak intensity of a spot, default = the mean
+///    plus 3 standard deviations of each frame.
+/// * `method`: The localization method, default =
+///    `LocalizationMethod::Gaussian`.
+/// * `axis`: The frame (time) axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Localization>)`: The localizations, sorted by frame.
+/// * `Err(ImgalError)`: If axis is >= 3. If `radius` is 0.
+pub fn localize<T>(
+    data: ArrayView3<T>,
+    radius: usize,
+    threshold: Option<f64>,
+    method: Option<LocalizationMethod>,
+    axis: Option<usize>,
+) -> Result<Vec<Localization>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let method = method.unwrap_or(LocalizationMethod::Gaussian);
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if radius == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "radius",
+            value: 0,
+        });
+    }
+
+    let frames: Vec<ArrayView2<T>> = data.axis_iter(Axis(a)).collect();
+    let locs = frames
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(|(t, frame)| {
+            let threshold = threshold.unwrap_or_else(|| frame_threshold(frame));
+            let frame = frame.into_dyn();
+            detect_peaks(&frame, radius, threshold)
+                .into_iter()
+                .filter_map(move |peak| {
+                    let window = frame.slice_each_axis(|ax| {
+                        let c = peak[ax.axis.index()];
+                        Slice::from(c - radius..=c + radius)
+                    });
+                    let spot = match method {
+                        LocalizationMethod::Gaussian => {
+                            gaussian_spot(window.into_dimensionality().ok()?, radius)?
+                        }
+                        LocalizationMethod::Phasor => {
+                            phasor_spot(window.into_dimensionality().ok()?)?
+                        }
+                    };
+                    Some(Localization {
+                        frame: t,
+                        row: (peak[0] - radius) as f64 + spot.0,
+                        col: (peak[1] - radius) as f64 + spot.1,
+                        intensity: spot.2,
+                        background: spot.3,
+                        sigma: spot.4,
+                    })
+                })
+                .collect::<Vec<Localization>>()
+        })
+        .collect();
+
+    Ok(locs)
+}
+
+/// Compute the default detection threshold of a frame, the mean plus 3
+/// standard deviations.
+fn frame_threshold<T>(frame: ArrayView2<T>) -> f64
+where
+    T: ToFloat64,
+{
+    let n = frame.len() as f64;
+    let mean = frame.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let var = frame
+        .iter()
+        .map(|v| (v.to_f64() - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    mean + 3.0 * var.sqrt()
+}
+
+/// Localize a spot window with a Gaussian fit, returning the (row, col,
+/// intensity, background, sigma) of valid fits.
+fn gaussian_spot<T>(window: ArrayView2<T>, radius: usize) -> Option<(f64, f64, f64, f64, f64)>
+where
+    T: ToFloat64,
+{
+    let fit = fit_gaussian(window.into_dyn(), 200).ok()?;
+    let side = (2 * radius) as f64;
+    let (sr, sc) = (fit.sigma[0], fit.sigma[1]);
+    let valid = fit.amplitude > 0.0
+        && fit.sigma.iter().all(|&s| s > 0.0 && s < radius as f64)
+        && fit.center.iter().all(|&c| (0.0..=side).contains(&c));
+
+    valid.then(|| {
+        (
+            fit.center[0],
+            fit.center[1],
+            2.0 * PI * fit.amplitude * sr * sc,
+            fit.offset,
+            (sr * sc).sqrt(),
+        )
+    })
+}
+
+/// Localize a spot window with its first Fourier coefficients, returning the
+/// (row, col, intensity, background, sigma) of spots above the background.
+fn phasor_spot<T>(window: ArrayView2<T>) -> Option<(f64, f64, f64, f64, f64)>
+where
+    T: ToFloat64,
+{
+    // the background is the mean of the window border
+    let (rows, cols) = window.dim();
+    let border: Vec<f64> = window
+        .indexed_iter()
+        .filter(|&((r, c), _)| r == 0 || c == 0 || r + 1 == rows || c + 1 == cols)
+        .map(|(_, v)| v.to_f64())
+        .collect();
+    let background = border.iter().sum::<f64>() / border.len() as f64;
+    let intensity: f64 = window.iter().map(|v| v.to_f64() - background).sum();
+    let (row, col) = phasor_position(window);
+
+    (intensity > 0.0).then(|| {
+        (
+            row,
+            col,
+            intensity,
+            background,
+            phasor_sigma(window, background),
+        )
+    })
+}
@@ -0,0 +1,9 @@
+//! Single-molecule localization microscopy functions.
+pub mod drift;
+pub use drift::drift_correct;
+pub mod localize;
+pub use localize::{Localization, LocalizationMethod, localize};
+pub mod phasor;
+pub use phasor::phasor_position;
+pub mod render;
+pub use render::render;
@@ -0,0 +1,95 @@
+use std::f64::consts::PI;
+
+use ndarray::{ArrayView2, Axis};
+
+use crate::traits::numeric::ToFloat64;
+
+/// Localize a spot with the phase of the first Fourier coefficients of its
+/// region of interest.
+///
+/// # Description
+///
+/// This function computes the first Fourier coefficient of a region of
+/// interest (ROI) of a single spot along each axis:
+///
+/// ```text
+/// F꜀ = Σᵣ Σ꜀ I(r, c) · exp(-2πi · c / W)
+/// c₀ = W · (-arg(F꜀) mod 2π) / 2π
+/// ```
+///
+/// Where "W" is the ROI width (and likewise for the rows with the ROI
+/// height). The phase of a shifted spot is proportional to its shift, so the
+/// spot center is recovered without iterations, about 100 times faster than a
+/// Gaussian fit with a similar precision. A constant background has no first
+/// Fourier coefficient and does not bias the position. The ROI should be
+/// centered on the spot and span a few spot widths.
+///
+/// # Arguments
+///
+/// * `roi`: The 2-dimensional region of interest of the spot.
+///
+/// # Returns
+///
+/// * `(f64, f64)`: The sub-pixel (row, col) center of the spot within the
+///    ROI. If the ROI has a single row or column, the center of that axis is
+///    0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1063/1.5005899>
+pub fn phasor_position<T>(roi: ArrayView2<T>) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    (axis_position(roi, Axis(0)), axis_position(roi, Axis(1)))
+}
+
+/// Estimate the width of a spot from the magnitude of the first Fourier
+/// coefficients of its background subtracted region of interest.
+pub(crate) fn phasor_sigma<T>(roi: ArrayView2<T>, background: f64) -> f64
+where
+    T: ToFloat64,
+{
+    let total: f64 = roi.iter().map(|v| v.to_f64() - background).sum();
+    let sigmas: Vec<f64> = [Axis(0), Axis(1)]
+        .iter()
+        .map(|&ax| {
+            let w = roi.len_of(ax) as f64;
+            let (re, im) = first_coefficient(roi, ax);
+            // a Gaussian of width σ has |F₁| / F₀ = exp(-2π²σ² / W²)
+            let ratio = (re * re + im * im).sqrt() / total;
+            w / (PI * 2.0_f64.sqrt()) * (-ratio.ln()).max(0.0).sqrt()
+        })
+        .collect();
+
+    (sigmas[0] * sigmas[1]).sqrt()
+}
+
+/// Compute the sub-pixel center of a spot along one axis.
+fn axis_position<T>(roi: ArrayView2<T>, axis: Axis) -> f64
+where
+    T: ToFloat64,
+{
+    let w = roi.len_of(axis);
+    if w < 2 {
+        return 0.0;
+    }
+    let (re, im) = first_coefficient(roi, axis);
+
+    (-im.atan2(re)).rem_euclid(2.0 * PI) * w as f64 / (2.0 * PI)
+}
+
+/// Compute the (real, imaginary) first Fourier coefficient along one axis.
+fn first_coefficient<T>(roi: ArrayView2<T>, axis: Axis) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let w = roi.len_of(axis) as f64;
+    roi.axis_iter(axis)
+        .enumerate()
+        .fold((0.0, 0.0), |(re, im), (i, lane)| {
+            let s: f64 = lane.iter().map(|v| v.to_f64()).sum();
+            let phase = 2.0 * PI * i as f64 / w;
+            (re + s * phase.cos(), im - s * phase.sin())
+        })
+}
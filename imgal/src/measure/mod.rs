@@ -0,0 +1,17 @@
+//! Measurement functions.
+pub mod anisotropy;
+pub mod kymograph;
+pub mod mtf;
+pub mod per_label;
+pub mod profile;
+pub mod psf;
+pub mod qc;
+pub mod transients;
+pub use anisotropy::anisotropy;
+pub use kymograph::kymograph;
+pub use mtf::{EdgeResponse, esf_lsf_mtf};
+pub use per_label::{LabelTable, per_label_analysis};
+pub use profile::{Interpolation, profile_line};
+pub use psf::{PsfBead, PsfReport, fit_psf};
+pub use qc::{StackQc, qc_outliers, stack_qc};
+pub use transients::{Transient, detect_transients};
@@ -0,0 +1,354 @@
+use ndarray::{ArrayView2, ArrayView3};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+// number of rows accumulated sequentially per parallel block
+const ROW_BLOCK: usize = 16;
+
+/// Per-label measurements of a label image, see [`per_label_analysis`].
+///
+/// # Description
+///
+/// Each field is a column of the table, with one value per label present in
+/// the label image, sorted by label. Columns that need a missing input (_e.g._
+/// the colocalization columns without a second channel) are NaN.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelTable {
+    /// The region label.
+    pub label: Vec<usize>,
+    /// The number of pixels in the region.
+    pub pixel_count: Vec<usize>,
+    /// The mean intensity of channel A.
+    pub mean_a: Vec<f64>,
+    /// The intensity standard deviation of channel A.
+    pub std_a: Vec<f64>,
+    /// The minimum intensity of channel A.
+    pub min_a: Vec<f64>,
+    /// The maximum intensity of channel A.
+    pub max_a: Vec<f64>,
+    /// The mean intensity of channel B.
+    pub mean_b: Vec<f64>,
+    /// The intensity standard deviation of channel B.
+    pub std_b: Vec<f64>,
+    /// The minimum intensity of channel B.
+    pub min_b: Vec<f64>,
+    /// The maximum intensity of channel B.
+    pub max_b: Vec<f64>,
+    /// The Pearson correlation coefficient between channels A and B.
+    pub pearson: Vec<f64>,
+    /// The Manders' M1 coefficient, the fraction of channel A intensity where
+    /// channel B is above its threshold.
+    pub manders_m1: Vec<f64>,
+    /// The Manders' M2 coefficient, the fraction of channel B intensity where
+    /// channel A is above its threshold.
+    pub manders_m2: Vec<f64>,
+    /// The mean phasor G coordinate.
+    pub mean_g: Vec<f64>,
+    /// The mean phasor S coordinate.
+    pub mean_s: Vec<f64>,
+    /// The apparent phase lifetime (τφ) of the mean phasor coordinates.
+    pub tau_phase: Vec<f64>,
+    /// The apparent modulation lifetime (τM) of the mean phasor coordinates.
+    pub tau_modulation: Vec<f64>,
+}
+
+impl LabelTable {
+    /// The number of labels.
+    pub fn len(&self) -> usize {
+        self.label.len()
+    }
+
+    /// Whether the table has no labels.
+    pub fn is_empty(&self) -> bool {
+        self.label.is_empty()
+    }
+}
+
+/// Measure the intensity, colocalization and lifetime of each region of a
+/// 2-dimensional label image.
+///
+/// # Description
+///
+/// This function computes, for each labeled region (_e.g._ a cell), the
+/// intensity statistics of two channels, their Pearson correlation and
+/// thresholded Manders' coefficients (see
+/// [`crate::colocalization::pearson`] and
+/// [`crate::colocalization::manders`]), the mean (G, S) coordinates of a
+/// phasor image and the apparent lifetimes of the mean coordinates (see
+/// [`crate::phasor::plot::apparent_lifetimes`]). All statistics are
+/// accumulated in a single parallel pass over fixed blocks of rows that are
+/// merged in row order, so the table is bit-identical for any number of
+/// threads. Pixels with NaN phasor coordinates are excluded from the phasor columns.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `channel_a`: The first intensity channel, with the shape of `labels`.
+/// * `channel_b`: The second intensity channel, with the shape of `labels`.
+/// * `phasor`: The 3-dimensional phasor image, where G and S are channels 0
+///    and 1 respectively, with the spatial shape of `labels`.
+/// * `omega`: The angular frequency of the apparent lifetimes. If `None`, the
+///    lifetime columns are NaN.
+/// * `thresholds`: The (A, B) intensity thresholds of the Manders'
+///    coefficients, default = (0.0, 0.0).
+/// * `axis`: The channel axis of `phasor`, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(LabelTable)`: The per-label measurements.
+/// * `Err(ImgalError)`: If axis is >= 3. If the shape of an input does not
+///    match `labels`. If `phasor` has fewer than 2 channels.
+pub fn per_label_analysis<T>(
+    labels: ArrayView2<usize>,
+    channel_a: Option<ArrayView2<T>>,
+    channel_b: Option<ArrayView2<T>>,
+    phasor: Option<ArrayView3<f64>>,
+    omega: Option<f64>,
+    thresholds: Option<(f64, f64)>,
+    axis: Option<usize>,
+) -> Result<LabelTable, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let (ta, tb) = thresholds.unwrap_or((0.0, 0.0));
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let shapes = [
+        channel_a.as_ref().map(|ch| ch.shape()),
+        channel_b.as_ref().map(|ch| ch.shape()),
+    ];
+    for shape in shapes.into_iter().flatten() {
+        if shape != labels.shape() {
+            return Err(ImgalError::MismatchedArrayShapes {
+                shape_a: labels.shape().to_vec(),
+                shape_b: shape.to_vec(),
+            });
+        }
+    }
+    let phasor = phasor.map(|p| {
+        p.permuted_axes(match a {
+            0 => [1, 2, 0],
+            1 => [0, 2, 1],
+            _ => [0, 1, 2],
+        })
+    });
+    if let Some(p) = &phasor {
+        if p.shape()[..2] != *labels.shape() {
+            return Err(ImgalError::MismatchedArrayShapes {
+                shape_a: labels.shape().to_vec(),
+                shape_b: p.shape()[..2].to_vec(),
+            });
+        }
+        if p.shape()[2] < 2 {
+            return Err(ImgalError::InvalidArrayParameterValueLess {
+                param_name: "phasor channels",
+                value: 2,
+            });
+        }
+    }
+
+    // accumulate the per-label sums of fixed row blocks in parallel, then merge
+    // the blocks in order so the result does not depend on the thread count
+    let n = labels.iter().copied().max().unwrap_or(0);
+    let (rows, cols) = labels.dim();
+    let blocks: Vec<Vec<Accumulator>> = (0..rows.div_ceil(ROW_BLOCK))
+        .into_par_iter()
+        .map(|b| {
+            let mut acc = vec![Accumulator::default(); n + 1];
+            for r in b * ROW_BLOCK..rows.min((b + 1) * ROW_BLOCK) {
+                for c in 0..cols {
+                    let l = labels[[r, c]];
+                    if l == 0 {
+                        continue;
+                    }
+                    let va = channel_a.as_ref().map(|ch| ch[[r, c]].to_f64());
+                    let vb = channel_b.as_ref().map(|ch| ch[[r, c]].to_f64());
+                    let gs = phasor.as_ref().map(|p| (p[[r, c, 0]], p[[r, c, 1]]));
+                    acc[l].add(va, vb, gs, ta, tb);
+                }
+            }
+            acc
+        })
+        .collect();
+    let mut sums = vec![Accumulator::default(); n + 1];
+    blocks.iter().for_each(|y| {
+        sums.iter_mut().zip(y).for_each(|(a, b)| a.merge(b));
+    });
+
+    // compute the columns of each present label
+    let mut table = LabelTable {
+        label: Vec::new(),
+        pixel_count: Vec::new(),
+        mean_a: Vec::new(),
+        std_a: Vec::new(),
+        min_a: Vec::new(),
+        max_a: Vec::new(),
+        mean_b: Vec::new(),
+        std_b: Vec::new(),
+        min_b: Vec::new(),
+        max_b: Vec::new(),
+        pearson: Vec::new(),
+        manders_m1: Vec::new(),
+        manders_m2: Vec::new(),
+        mean_g: Vec::new(),
+        mean_s: Vec::new(),
+        tau_phase: Vec::new(),
+        tau_modulation: Vec::new(),
+    };
+    let nan_if = |ok: bool, v: f64| if ok { v } else { f64::NAN };
+    for (l, s) in sums.iter().enumerate().skip(1).filter(|(_, s)| s.n > 0) {
+        let n = s.n as f64;
+        let (has_a, has_b) = (channel_a.is_some(), channel_b.is_some());
+        let (ma, mb) = (s.sum_a / n, s.sum_b / n);
+        let (va, vb) = (
+            (s.sum_aa / n - ma * ma).max(0.0),
+            (s.sum_bb / n - mb * mb).max(0.0),
+        );
+        table.label.push(l);
+        table.pixel_count.push(s.n);
+        table.mean_a.push(nan_if(has_a, ma));
+        table.std_a.push(nan_if(has_a, va.sqrt()));
+        table.min_a.push(nan_if(has_a, s.min_a));
+        table.max_a.push(nan_if(has_a, s.max_a));
+        table.mean_b.push(nan_if(has_b, mb));
+        table.std_b.push(nan_if(has_b, vb.sqrt()));
+        table.min_b.push(nan_if(has_b, s.min_b));
+        table.max_b.push(nan_if(has_b, s.max_b));
+        let both = has_a && has_b;
+        let cov = s.sum_ab / n - ma * mb;
+        table.pearson.push(nan_if(both, cov / (va * vb).sqrt()));
+        let ratio = |x: f64, y: f64| if y > 0.0 { x / y } else { 0.0 };
+        table
+            .manders_m1
+            .push(nan_if(both, ratio(s.coloc_a, s.above_a)));
+        table
+            .manders_m2
+            .push(nan_if(both, ratio(s.coloc_b, s.above_b)));
+        let np = s.n_phasor as f64;
+        let (g, p) = (s.sum_g / np, s.sum_s / np);
+        table.mean_g.push(g);
+        table.mean_s.push(p);
+        let w = omega.unwrap_or(f64::NAN);
+        table.tau_phase.push(p / (w * g));
+        table
+            .tau_modulation
+            .push((1.0 / (g * g + p * p) - 1.0).sqrt() / w);
+    }
+
+    Ok(table)
+}
+
+/// Running sums of the pixels of a label.
+#[derive(Debug, Clone, Copy)]
+struct Accumulator {
+    n: usize,
+    sum_a: f64,
+    sum_aa: f64,
+    min_a: f64,
+    max_a: f64,
+    sum_b: f64,
+    sum_bb: f64,
+    min_b: f64,
+    max_b: f64,
+    sum_ab: f64,
+    above_a: f64,
+    coloc_a: f64,
+    above_b: f64,
+    coloc_b: f64,
+    n_phasor: usize,
+    sum_g: f64,
+    sum_s: f64,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Accumulator {
+            n: 0,
+            sum_a: 0.0,
+            sum_aa: 0.0,
+            min_a: f64::INFINITY,
+            max_a: f64::NEG_INFINITY,
+            sum_b: 0.0,
+            sum_bb: 0.0,
+            min_b: f64::INFINITY,
+            max_b: f64::NEG_INFINITY,
+            sum_ab: 0.0,
+            above_a: 0.0,
+            coloc_a: 0.0,
+            above_b: 0.0,
+            coloc_b: 0.0,
+            n_phasor: 0,
+            sum_g: 0.0,
+            sum_s: 0.0,
+        }
+    }
+}
+
+impl Accumulator {
+    /// Add a pixel, missing inputs are `None`.
+    fn add(&mut self, a: Option<f64>, b: Option<f64>, gs: Option<(f64, f64)>, ta: f64, tb: f64) {
+        self.n += 1;
+        let a = a.unwrap_or(0.0);
+        let b = b.unwrap_or(0.0);
+        self.sum_a += a;
+        self.sum_aa += a * a;
+        self.min_a = self.min_a.min(a);
+        self.max_a = self.max_a.max(a);
+        self.sum_b += b;
+        self.sum_bb += b * b;
+        self.min_b = self.min_b.min(b);
+        self.max_b = self.max_b.max(b);
+        self.sum_ab += a * b;
+        if a > ta {
+            self.above_a += a;
+            if b > tb {
+                self.coloc_a += a;
+            }
+        }
+        if b > tb {
+            self.above_b += b;
+            if a > ta {
+                self.coloc_b += b;
+            }
+        }
+        if let Some((g, s)) = gs
+            && !g.is_nan()
+            && !s.is_nan()
+        {
+            self.n_phasor += 1;
+            self.sum_g += g;
+            self.sum_s += s;
+        }
+    }
+
+    /// Merge the sums of another accumulator.
+    fn merge(&mut self, other: &Accumulator) {
+        self.n += other.n;
+        self.sum_a += other.sum_a;
+        self.sum_aa += other.sum_aa;
+        self.min_a = self.min_a.min(other.min_a);
+        self.max_a = self.max_a.max(other.max_a);
+        self.sum_b += other.sum_b;
+        self.sum_bb += other.sum_bb;
+        self.min_b = self.min_b.min(other.min_b);
+        self.max_b = self.max_b.max(other.max_b);
+        self.sum_ab += other.sum_ab;
+        self.above_a += other.above_a;
+        self.coloc_a += other.coloc_a;
+        self.above_b += other.above_b;
+        self.coloc_b += other.coloc_b;
+        self.n_phasor += other.n_phasor;
+        self.sum_g += other.sum_g;
+        self.sum_s += other.sum_s;
+    }
+}
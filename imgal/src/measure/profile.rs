@@ -0,0 +1,258 @@
+use ndarray::{Array1, ArrayView2, ArrayViewD, IxDyn};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Interpolation methods for sampling images at fractional positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// The value of the nearest pixel.
+    Nearest,
+    /// Linear interpolation of the 2ⁿ surrounding pixels (_i.e._ bilinear or
+    /// trilinear).
+    Linear,
+    /// Cubic convolution (Keys, a = -0.5) of the 4ⁿ surrounding pixels.
+    Cubic,
+}
+
+/// Sample an intensity profile along a line of a 2 or 3-dimensional image.
+///
+/// # Description
+///
+/// This function samples an image along the straight line from `start` to
+/// `end` at `round(length) + 1` evenly spaced points (_i.e._ about one sample
+/// per pixel, including both end points), like the "plot profile" tool of
+/// ImageJ. With a `width` greater than 1, each sample is the mean of points
+/// spaced one pixel apart perpendicular to the line: `width` points for
+/// 2-dimensional images and a `width` x `width` square for 3-dimensional
+/// images. Points outside of the image are ignored, samples without any point
+/// in the image are NaN. Profiles across edges or sub-resolution lines are the
+/// edge and line spread functions used to measure resolution.
+///
+/// # Arguments
+///
+/// * `data`: The input 2 or 3-dimensional image.
+/// * `start`: The start point of the line, one coordinate per axis.
+/// * `end`: The end point of the line, one coordinate per axis.
+/// * `width`: The line width in pixels, default = 1.
+/// * `interpolation`: The interpolation method, default =
+///    `Interpolation::Linear`.
+///
+/// # Returns
+///
+/// * `Ok((Array1<f64>, Array1<f64>))`: The distance of each sample from
+///    `start` and the profile values.
+/// * `Err(ImgalError)`: If `data` is not 2 or 3-dimensional. If the lengths
+///    of `start` or `end` do not match the number of dimensions. If `width` is
+///    0.
+pub fn profile_line<T>(
+    data: ArrayViewD<T>,
+    start: &[f64],
+    end: &[f64],
+    width: Option<usize>,
+    interpolation: Option<Interpolation>,
+) -> Result<(Array1<f64>, Array1<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let width = width.unwrap_or(1);
+    let interpolation = interpolation.unwrap_or(Interpolation::Linear);
+
+    // check if parameters are valid
+    let ndim = data.ndim();
+    if ndim != 2 && ndim != 3 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The input image must be 2 or 3-dimensional.",
+        });
+    }
+    if start.len() != ndim {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: start.len(),
+            b_arr_len: ndim,
+        });
+    }
+    if end.len() != ndim {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: end.len(),
+            b_arr_len: ndim,
+        });
+    }
+    if width == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "width",
+            value: 0,
+        });
+    }
+
+    // compute the line direction and the perpendicular offsets
+    let delta: Vec<f64> = start.iter().zip(end).map(|(s, e)| e - s).collect();
+    let length = delta.iter().map(|d| d * d).sum::<f64>().sqrt();
+    let offsets = perpendicular_offsets(&delta, length, width);
+    let n = length.round() as usize + 1;
+    let step = if n > 1 { length / (n - 1) as f64 } else { 0.0 };
+
+    let distances = Array1::from_shape_fn(n, |i| i as f64 * step);
+    let values = distances.mapv(|d| {
+        let t = if length > 0.0 { d / length } else { 0.0 };
+        let (sum, count) = offsets
+            .iter()
+            .filter_map(|o| {
+                let point: Vec<f64> = (0..ndim).map(|k| start[k] + delta[k] * t + o[k]).collect();
+                interpolate(&data, &point, interpolation)
+            })
+            .fold((0.0, 0usize), |(s, c), v| (s + v, c + 1));
+        if count > 0 {
+            sum / count as f64
+        } else {
+            f64::NAN
+        }
+    });
+
+    Ok((distances, values))
+}
+
+/// Compute the offsets of a line's width, one pixel apart perpendicular to the
+/// line.
+fn perpendicular_offsets(delta: &[f64], length: f64, width: usize) -> Vec<Vec<f64>> {
+    let half = (width - 1) as f64 / 2.0;
+    let u: Vec<f64> = if length > 0.0 {
+        delta.iter().map(|d| d / length).collect()
+    } else {
+        vec![0.0; delta.len()]
+    };
+    if u.len() == 2 {
+        return (0..width)
+            .map(|k| {
+                let o = k as f64 - half;
+                vec![-u[1] * o, u[0] * o]
+            })
+            .collect();
+    }
+
+    // two unit vectors perpendicular to the 3-dimensional line
+    let a = if u[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let mut e1 = cross(&u, &a);
+    let norm = e1.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        e1.iter_mut().for_each(|v| *v /= norm);
+    }
+    let e2 = cross(&u, &e1);
+    (0..width * width)
+        .map(|k| {
+            let (o1, o2) = ((k / width) as f64 - half, (k % width) as f64 - half);
+            (0..3).map(|i| e1[i] * o1 + e2[i] * o2).collect()
+        })
+        .collect()
+}
+
+/// Cross product of two 3-dimensional vectors.
+fn cross(a: &[f64], b: &[f64]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Sample a 2-dimensional image at a fractional position with bilinear
+/// interpolation, returning `None` outside of the image.
+pub(crate) fn bilinear<T>(image: ArrayView2<T>, r: f64, c: f64) -> Option<f64>
+where
+    T: ToFloat64,
+{
+    let (rows, cols) = image.dim();
+    if rows == 0 || cols == 0 || r < 0.0 || c < 0.0 {
+        return None;
+    }
+    if r > (rows - 1) as f64 || c > (cols - 1) as f64 {
+        return None;
+    }
+    let (r0, c0) = (r.floor() as usize, c.floor() as usize);
+    let (r1, c1) = ((r0 + 1).min(rows - 1), (c0 + 1).min(cols - 1));
+    let (fr, fc) = (r - r0 as f64, c - c0 as f64);
+    let top = image[[r0, c0]].to_f64() * (1.0 - fc) + image[[r0, c1]].to_f64() * fc;
+    let bottom = image[[r1, c0]].to_f64() * (1.0 - fc) + image[[r1, c1]].to_f64() * fc;
+
+    Some(top * (1.0 - fr) + bottom * fr)
+}
+
+/// Sample an n-dimensional image at a fractional position, returning `None`
+/// outside of the image.
+fn interpolate<T>(data: &ArrayViewD<T>, point: &[f64], interpolation: Interpolation) -> Option<f64>
+where
+    T: ToFloat64,
+{
+    let shape = data.shape();
+    if point
+        .iter()
+        .zip(shape)
+        .any(|(&p, &len)| len == 0 || !(0.0..=(len - 1) as f64).contains(&p))
+    {
+        return None;
+    }
+
+    // the (index, weight) pairs of each axis
+    let taps: Vec<Vec<(usize, f64)>> = point
+        .iter()
+        .zip(shape)
+        .map(|(&p, &len)| {
+            let last = len as isize - 1;
+            let clamp = |i: isize| i.clamp(0, last) as usize;
+            let base = p.floor();
+            let f = p - base;
+            let b = base as isize;
+            match interpolation {
+                Interpolation::Nearest => vec![(clamp(p.round() as isize), 1.0)],
+                Interpolation::Linear => vec![(clamp(b), 1.0 - f), (clamp(b + 1), f)],
+                Interpolation::Cubic => (-1..=2)
+                    .map(|k| (clamp(b + k), cubic_weight(f - k as f64)))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    // sum the weighted values of every combination of taps
+    let mut value = 0.0;
+    let mut idx = vec![0; taps.len()];
+    let mut counter = vec![0; taps.len()];
+    loop {
+        let mut weight = 1.0;
+        for (k, &c) in counter.iter().enumerate() {
+            idx[k] = taps[k][c].0;
+            weight *= taps[k][c].1;
+        }
+        value += weight * data[IxDyn(&idx)].to_f64();
+        // advance the tap counter
+        let mut k = 0;
+        while k < taps.len() {
+            counter[k] += 1;
+            if counter[k] < taps[k].len() {
+                break;
+            }
+            counter[k] = 0;
+            k += 1;
+        }
+        if k == taps.len() {
+            break;
+        }
+    }
+
+    Some(value)
+}
+
+/// Keys cubic convolution kernel with a = -0.5.
+fn cubic_weight(x: f64) -> f64 {
+    let x = x.abs();
+    if x <= 1.0 {
+        1.5 * x.powi(3) - 2.5 * x.powi(2) + 1.0
+    } else if x < 2.0 {
+        -0.5 * x.powi(3) + 2.5 * x.powi(2) - 4.0 * x + 2.0
+    } else {
+        0.0
+    }
+}
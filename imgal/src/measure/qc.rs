@@ -0,0 +1,157 @@
+use ndarray::{ArrayView2, ArrayView3, Axis};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::statistics::{FocusMetric, estimate_snr, median};
+use crate::traits::numeric::FromFloat64;
+
+/// Per-frame quality control metrics of a stack, see [`stack_qc`].
+///
+/// # Description
+///
+/// Each field is a column of the table, with one value per frame in the order
+/// of the frame axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackQc {
+    /// The mean intensity of each frame.
+    pub mean: Vec<f64>,
+    /// The median intensity of each frame.
+    pub median: Vec<f64>,
+    /// The fraction (0.0 to 1.0) of saturated pixels of each frame.
+    pub saturation: Vec<f64>,
+    /// The focus score of each frame.
+    pub focus: Vec<f64>,
+    /// The estimated linear signal-to-noise ratio of each frame.
+    pub snr: Vec<f64>,
+}
+
+impl StackQc {
+    /// The number of frames.
+    pub fn len(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Whether the table has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.mean.is_empty()
+    }
+}
+
+/// Compute per-frame quality control metrics of a 3-dimensional stack.
+///
+/// # Description
+///
+/// This function computes, for each frame of a (t, row, col) stack, the mean
+/// and median intensity, the fraction of saturated pixels (see
+/// [`crate::image::saturation_mask`]), a focus score (see
+/// [`crate::statistics::focus_curve`]) and the Poisson signal-to-noise ratio
+/// (see [`crate::statistics::estimate_snr`]). Frames are processed in
+/// parallel. Frames that are outliers in any column (see [`qc_outliers`]) can
+/// then be rejected automatically before batch analyses.
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional stack.
+/// * `saturation`: The saturation level, default = the maximum value of the
+///    image type.
+/// * `focus`: The focus metric, default = `FocusMetric::NormalizedVariance`.
+/// * `gain`: The detector gain in counts per photon, used by the SNR estimate,
+///    default = 1.0.
+/// * `axis`: The frame axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(StackQc)`: The per-frame quality control metrics.
+/// * `Err(ImgalError)`: If axis is >= 3. If the frames are empty. If `gain` is
+///    not positive.
+pub fn stack_qc<T>(
+    data: ArrayView3<T>,
+    saturation: Option<f64>,
+    focus: Option<FocusMetric>,
+    gain: Option<f64>,
+    axis: Option<usize>,
+) -> Result<StackQc, ImgalError>
+where
+    T: FromFloat64,
+{
+    // set optional parameters if needed
+    let saturation = saturation.unwrap_or(T::MAX_F64);
+    let focus = focus.unwrap_or(FocusMetric::NormalizedVariance);
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+
+    let frames: Vec<ArrayView2<T>> = data.axis_iter(Axis(a)).collect();
+    let rows = frames
+        .into_par_iter()
+        .map(|f| {
+            let mut values: Vec<f64> = f.iter().map(|v| v.to_f64()).collect();
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let saturated = values.iter().filter(|&&v| v >= saturation).count() as f64 / n;
+            let snr = estimate_snr(f.into_dyn(), None, None, gain)?;
+            Ok((mean, median(&mut values), saturated, focus.compute(f), snr))
+        })
+        .collect::<Result<Vec<_>, ImgalError>>()?;
+
+    Ok(StackQc {
+        mean: rows.iter().map(|r| r.0).collect(),
+        median: rows.iter().map(|r| r.1).collect(),
+        saturation: rows.iter().map(|r| r.2).collect(),
+        focus: rows.iter().map(|r| r.3).collect(),
+        snr: rows.iter().map(|r| r.4).collect(),
+    })
+}
+
+/// Find the outlier frames of a quality control metric.
+///
+/// # Description
+///
+/// This function flags the values whose robust z-score exceeds a threshold:
+///
+/// ```text
+/// z = |x - median(x)| / (1.4826 * MAD(x))
+/// ```
+///
+/// Where "MAD" is the median absolute deviation, which is insensitive to the
+/// outliers themselves (_e.g._ a few defocused or saturated frames). Apply it
+/// to a column of [`StackQc`] to select frames to reject.
+///
+/// # Arguments
+///
+/// * `values`: The metric values, one per frame.
+/// * `threshold`: The robust z-score threshold, default = 3.5.
+///
+/// # Returns
+///
+/// * `Vec<usize>`: The indices of the outlier frames. If the MAD is 0.0, only
+///    values different from the median are outliers.
+pub fn qc_outliers(values: &[f64], threshold: Option<f64>) -> Vec<usize> {
+    // set optional parameters if needed
+    let threshold = threshold.unwrap_or(3.5);
+
+    let mut sorted = values.to_vec();
+    let center = median(&mut sorted);
+    let mut deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    let scale = 1.4826 * median(&mut deviations);
+
+    values
+        .iter()
+        .enumerate()
+        .filter(|&(_, v)| {
+            let d = (v - center).abs();
+            if scale > 0.0 {
+                d / scale > threshold
+            } else {
+                d > 0.0
+            }
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
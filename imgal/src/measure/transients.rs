@@ -0,0 +1,164 @@
+use ndarray::{ArrayView1, ArrayView2, Axis};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::statistics::median;
+use crate::traits::numeric::ToFloat64;
+
+/// A single transient event of an intensity trace, see
+/// [`detect_transients`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transient {
+    /// The index of the trace of the event.
+    pub trace: usize,
+    /// The first frame above the threshold.
+    pub start: usize,
+    /// The frame of the event maximum.
+    pub peak: usize,
+    /// The first frame below the threshold after the event (exclusive end).
+    pub end: usize,
+    /// The peak value above the trace baseline.
+    pub amplitude: f64,
+    /// The number of frames above the threshold.
+    pub duration: usize,
+    /// The 10% to 90% rise time of the event, in frames.
+    pub rise_time: f64,
+}
+
+/// Detect transient events in intensity traces.
+///
+/// # Description
+///
+/// This function detects transients (_e.g._ calcium or voltage indicator
+/// spikes, typically after [`crate::filter::delta_f_over_f`]) in each row of
+/// a 2-dimensional (trace, t) array of per-pixel or per-region time traces.
+/// The baseline of each trace is its median and the noise is the scaled
+/// median absolute deviation (MAD), which are both robust to sparse events:
+///
+/// ```text
+/// σ = 1.4826 · median(|F(t) - median(F)|)
+/// ```
+///
+/// An event is a run of at least `min_duration` consecutive frames above the
+/// baseline plus `threshold` noise standard deviations. The amplitude is the
+/// event maximum above the baseline and the rise time is measured between the
+/// 10% and 90% amplitude crossings before the peak, interpolated linearly
+/// between frames. Traces are processed in parallel.
+///
+/// # Arguments
+///
+/// * `traces`: The 2-dimensional (trace, t) array of time traces.
+/// * `threshold`: The detection threshold in noise standard deviations above
+///    the baseline, default = 3.0.
+/// * `min_duration`: The minimum number of frames above the threshold,
+///    default = 1.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Transient>)`: The events, sorted by trace and start frame.
+/// * `Err(ImgalError)`: If `threshold` is not positive. If `min_duration` is
+///    0.
+pub fn detect_transients<T>(
+    traces: ArrayView2<T>,
+    threshold: Option<f64>,
+    min_duration: Option<usize>,
+) -> Result<Vec<Transient>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let threshold = threshold.unwrap_or(3.0);
+    let min_duration = min_duration.unwrap_or(1);
+
+    // check if parameters are valid
+    if !threshold.is_finite() || threshold <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "threshold",
+            value: threshold,
+            min: f64::MIN_POSITIVE,
+            max: f64::INFINITY,
+        });
+    }
+    if min_duration == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "min_duration",
+            value: 0,
+        });
+    }
+
+    let rows: Vec<ArrayView1<T>> = traces.axis_iter(Axis(0)).collect();
+    let events = rows
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, row)| {
+            let trace: Vec<f64> = row.iter().map(|v| v.to_f64()).collect();
+            trace_transients(&trace, threshold, min_duration)
+                .into_iter()
+                .map(move |e| Transient { trace: i, ..e })
+        })
+        .collect();
+
+    Ok(events)
+}
+
+/// Detect the transients of a single trace, with the trace index set to 0.
+fn trace_transients(trace: &[f64], threshold: f64, min_duration: usize) -> Vec<Transient> {
+    if trace.is_empty() {
+        return Vec::new();
+    }
+    let baseline = median(&mut trace.to_vec());
+    let mut dev: Vec<f64> = trace.iter().map(|v| (v - baseline).abs()).collect();
+    let sigma = 1.4826 * median(&mut dev);
+    let level = baseline + threshold * sigma;
+
+    let mut events: Vec<Transient> = Vec::new();
+    let mut t = 0;
+    while t < trace.len() {
+        if trace[t] <= level {
+            t += 1;
+            continue;
+        }
+        let start = t;
+        while t < trace.len() && trace[t] > level {
+            t += 1;
+        }
+        if t - start < min_duration {
+            continue;
+        }
+        let peak = (start..t)
+            .max_by(|&a, &b| trace[a].total_cmp(&trace[b]))
+            .unwrap();
+        let amplitude = trace[peak] - baseline;
+
+        // the rising edge may extend below the threshold, down to the end of
+        // the previous event
+        let lo = events.last().map_or(0, |e| e.end);
+        let t10 = crossing(trace, peak, lo, baseline + 0.1 * amplitude);
+        let t90 = crossing(trace, peak, lo, baseline + 0.9 * amplitude);
+        events.push(Transient {
+            trace: 0,
+            start,
+            peak,
+            end: t,
+            amplitude,
+            duration: t - start,
+            rise_time: t90 - t10,
+        });
+    }
+
+    events
+}
+
+/// Find the last fractional frame before `peak` (and not before `lo`) where
+/// the trace rises through `value`.
+fn crossing(trace: &[f64], peak: usize, lo: usize, value: f64) -> f64 {
+    let mut k = peak;
+    while k > lo && trace[k] >= value {
+        k -= 1;
+    }
+    if trace[k] >= value {
+        return k as f64;
+    }
+
+    k as f64 + (value - trace[k]) / (trace[k + 1] - trace[k])
+}
@@ -0,0 +1,6 @@
+//! Motion estimation functions.
+pub mod optical_flow;
+pub use optical_flow::FlowMethod;
+pub use optical_flow::farneback;
+pub use optical_flow::lucas_kanade;
+pub use optical_flow::optical_flow_3d;
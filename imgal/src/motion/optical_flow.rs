@@ -0,0 +1,425 @@
+use ndarray::{Array2, Array3, Array4, ArrayView2, ArrayView3, Axis, Zip, s};
+
+use crate::error::ImgalError;
+use crate::filter::gaussian::{gaussian_2d_mut, gaussian_kernel};
+use crate::filter::gradient::central_difference;
+use crate::fitting::linalg::solve;
+use crate::traits::numeric::ToFloat64;
+
+// a flow field with a shape of (row, col, 2) and its magnitude map
+type FlowField = (Array3<f64>, Array2<f64>);
+
+/// Optical flow methods available for time-lapse flow estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowMethod {
+    /// Dense Lucas-Kanade with the window radius and minimum eigenvalue.
+    LucasKanade {
+        window_radius: usize,
+        min_eigenvalue: f64,
+    },
+    /// Farnebäck polynomial expansion with the window radius, polynomial
+    /// neighborhood radius and number of iterations.
+    Farneback {
+        window_radius: usize,
+        poly_radius: usize,
+        iterations: usize,
+    },
+}
+
+/// Compute the dense optical flow between two frames with the Lucas-Kanade
+/// method.
+///
+/// # Description
+///
+/// This function estimates the displacement of every pixel between two frames
+/// by assuming brightness constancy and a constant displacement within a
+/// Gaussian weighted window around each pixel. For each pixel the
+/// displacement `d = (dr, dc)` solves the least squares system:
+///
+/// ```text
+/// | Σw Ic²   Σw IcIr | | dc |     | Σw IcIt |
+/// | Σw IcIr  Σw Ir²  | | dr | = - | Σw IrIt |
+/// ```
+///
+/// Where "Ir" and "Ic" are the spatial row and column gradients and "It" is the
+/// temporal difference. Pixels where the smallest eigenvalue of the system is
+/// below `min_eigenvalue` (_e.g._ flat regions, the aperture problem) are
+/// assigned zero flow. The estimate is reliable for displacements smaller than
+/// the window radius (typically a few pixels).
+///
+/// # Arguments
+///
+/// * `prev`: The first (earlier) 2-dimensional frame.
+/// * `next`: The second (later) 2-dimensional frame. Must have the same shape
+///    as `prev`.
+/// * `window_radius`: The radius of the Gaussian weighted window, default = 3.
+/// * `min_eigenvalue`: The minimum eigenvalue of the Gaussian averaged gradient
+///    structure tensor for a reliable estimate, default = 1e-6.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array2<f64>))`: The flow field with a shape of
+///    `(row, col, 2)`, where `[.., .., 0]` is the row displacement and
+///    `[.., .., 1]` is the column displacement, and the flow magnitude map.
+/// * `Err(ImgalError)`: If the frame shapes do not match. If `window_radius` is
+///    0.
+pub fn lucas_kanade<T>(
+    prev: ArrayView2<T>,
+    next: ArrayView2<T>,
+    window_radius: Option<usize>,
+    min_eigenvalue: Option<f64>,
+) -> Result<FlowField, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let window_radius = window_radius.unwrap_or(3);
+    let min_eigenvalue = min_eigenvalue.unwrap_or(1e-6);
+    check_frames(prev.shape(), next.shape(), window_radius)?;
+
+    // compute spatial and temporal gradients
+    let prev = prev.mapv(|v| v.to_f64());
+    let next = next.mapv(|v| v.to_f64());
+    let avg = (&prev + &next) * 0.5;
+    let grads = central_difference(&avg);
+    let (gr, gc) = (&grads[0], &grads[1]);
+    let gt = &next - &prev;
+
+    // sum the gradient products over each window
+    let sigma = window_sigma(window_radius);
+    let scc = smooth(&(gc * gc), sigma);
+    let scr = smooth(&(gc * gr), sigma);
+    let srr = smooth(&(gr * gr), sigma);
+    let sct = smooth(&(gc * &gt), sigma);
+    let srt = smooth(&(gr * &gt), sigma);
+
+    // solve the 2 x 2 system at each pixel
+    let mut flow = Array3::<f64>::zeros((prev.nrows(), prev.ncols(), 2));
+    Zip::from(flow.lanes_mut(Axis(2)))
+        .and(&scc)
+        .and(&scr)
+        .and(&srr)
+        .and(&sct)
+        .and(&srt)
+        .par_for_each(|mut f, &cc, &cr, &rr, &ct, &rt| {
+            let tr = cc + rr;
+            let det = cc * rr - cr * cr;
+            let lambda_min = 0.5 * (tr - (tr * tr - 4.0 * det).max(0.0).sqrt());
+            if lambda_min >= min_eigenvalue && det != 0.0 {
+                f[1] = (-rr * ct + cr * rt) / det;
+                f[0] = (cr * ct - cc * rt) / det;
+            }
+        });
+    let magnitude = flow_magnitude(&flow);
+
+    Ok((flow, magnitude))
+}
+
+/// Compute the dense optical flow between two frames with the Farnebäck
+/// polynomial expansion method.
+///
+/// # Description
+///
+/// This function approximates the neighborhood of every pixel in each frame
+/// with a quadratic polynomial, `f(x) = xᵀAx + bᵀx + c`, fitted by Gaussian
+/// weighted least squares. A displacement `d` of the signal changes the
+/// linear coefficients as `b₂ = b₁ - 2Ad`, which is solved for `d` in the
+/// least squares sense over a Gaussian weighted window around each pixel. The
+/// estimate is refined over several iterations, where the second frame's
+/// coefficients are sampled at the previously estimated displacement. This
+/// implementation operates on a single scale, and is reliable for
+/// displacements up to a few pixels.
+///
+/// # Arguments
+///
+/// * `prev`: The first (earlier) 2-dimensional frame.
+/// * `next`: The second (later) 2-dimensional frame. Must have the same shape
+///    as `prev`.
+/// * `window_radius`: The radius of the Gaussian weighted displacement window,
+///    default = 5.
+/// * `poly_radius`: The radius of the polynomial expansion neighborhood,
+///    default = 3.
+/// * `iterations`: The number of refinement iterations, default = 3.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array2<f64>))`: The flow field with a shape of
+///    `(row, col, 2)`, where `[.., .., 0]` is the row displacement and
+///    `[.., .., 1]` is the column displacement, and the flow magnitude map.
+/// * `Err(ImgalError)`: If the frame shapes do not match. If `window_radius` or
+///    `poly_radius` is 0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/3-540-45103-X_50>
+pub fn farneback<T>(
+    prev: ArrayView2<T>,
+    next: ArrayView2<T>,
+    window_radius: Option<usize>,
+    poly_radius: Option<usize>,
+    iterations: Option<usize>,
+) -> Result<FlowField, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let window_radius = window_radius.unwrap_or(5);
+    let poly_radius = poly_radius.unwrap_or(3);
+    let iterations = iterations.unwrap_or(3).max(1);
+    check_frames(prev.shape(), next.shape(), window_radius)?;
+    if poly_radius == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "poly_radius",
+            value: 0,
+        });
+    }
+
+    // expand both frames into polynomial coefficients
+    let prev = prev.mapv(|v| v.to_f64());
+    let next = next.mapv(|v| v.to_f64());
+    let coefs_1 = polynomial_expansion(&prev, poly_radius);
+    let coefs_2 = polynomial_expansion(&next, poly_radius);
+
+    // iteratively estimate the displacement
+    let (rows, cols) = prev.dim();
+    let sigma = window_sigma(window_radius);
+    let mut flow = Array3::<f64>::zeros((rows, cols, 2));
+    for _ in 0..iterations {
+        // per pixel normal equations, (g_rr, g_rc, g_cc, h_r, h_c)
+        let mut terms = Array3::<f64>::zeros((5, rows, cols));
+        Zip::indexed(terms.lanes_mut(Axis(0))).par_for_each(|(r, c), mut t| {
+            // sample the second frame at the rounded displacement
+            let dr = flow[[r, c, 0]].round();
+            let dc = flow[[r, c, 1]].round();
+            let r2 = (r as f64 + dr).clamp(0.0, (rows - 1) as f64) as usize;
+            let c2 = (c as f64 + dc).clamp(0.0, (cols - 1) as f64) as usize;
+            let (a1, b1) = coef_matrix(&coefs_1, r, c);
+            let (a2, b2) = coef_matrix(&coefs_2, r2, c2);
+            let a = [
+                [(a1[0][0] + a2[0][0]) * 0.5, (a1[0][1] + a2[0][1]) * 0.5],
+                [(a1[1][0] + a2[1][0]) * 0.5, (a1[1][1] + a2[1][1]) * 0.5],
+            ];
+            let db = [
+                -0.5 * (b2[0] - b1[0]) + a[0][0] * dr + a[0][1] * dc,
+                -0.5 * (b2[1] - b1[1]) + a[1][0] * dr + a[1][1] * dc,
+            ];
+            // AᵀA and AᵀΔb
+            t[0] = a[0][0] * a[0][0] + a[1][0] * a[1][0];
+            t[1] = a[0][0] * a[0][1] + a[1][0] * a[1][1];
+            t[2] = a[0][1] * a[0][1] + a[1][1] * a[1][1];
+            t[3] = a[0][0] * db[0] + a[1][0] * db[1];
+            t[4] = a[0][1] * db[0] + a[1][1] * db[1];
+        });
+
+        // sum the normal equations over each window and solve
+        let sums: Vec<Array2<f64>> = terms
+            .axis_iter(Axis(0))
+            .map(|t| smooth(&t.to_owned(), sigma))
+            .collect();
+        Zip::from(flow.lanes_mut(Axis(2)))
+            .and(&sums[0])
+            .and(&sums[1])
+            .and(&sums[2])
+            .and(&sums[3])
+            .and(&sums[4])
+            .par_for_each(|mut f, &grr, &grc, &gcc, &hr, &hc| {
+                let det = grr * gcc - grc * grc;
+                if det.abs() > f64::EPSILON {
+                    f[0] = (gcc * hr - grc * hc) / det;
+                    f[1] = (grr * hc - grc * hr) / det;
+                } else {
+                    f[0] = 0.0;
+                    f[1] = 0.0;
+                }
+            });
+    }
+    let magnitude = flow_magnitude(&flow);
+
+    Ok((flow, magnitude))
+}
+
+/// Compute the dense optical flow between consecutive frames of a time-lapse.
+///
+/// # Description
+///
+/// This function computes the dense optical flow (see [`lucas_kanade`] and
+/// [`farneback`]) between each pair of consecutive 2-dimensional frames of a
+/// 3-dimensional time-lapse.
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional time-lapse.
+/// * `method`: The optical flow method.
+/// * `axis`: The time axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok((Array4<f64>, Array3<f64>))`: The flow fields with a shape of
+///    `(t - 1, row, col, 2)` and the flow magnitude maps with a shape of
+///    `(t - 1, row, col)`, where index `i` is the flow from frame `i` to frame
+///    `i + 1`.
+/// * `Err(ImgalError)`: If the axis is >= 3. If there are less than 2 frames. If
+///    the method parameters are invalid.
+pub fn optical_flow_3d<T>(
+    data: ArrayView3<T>,
+    method: FlowMethod,
+    axis: Option<usize>,
+) -> Result<(Array4<f64>, Array3<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let axis = axis.unwrap_or(0);
+
+    // check if axis parameter is valid
+    if axis >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: 3,
+        });
+    }
+    let n_frames = data.len_of(Axis(axis));
+    if n_frames < 2 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "frames",
+            value: 2,
+        });
+    }
+
+    // compute the flow between each consecutive frame pair
+    let frame = data.index_axis(Axis(axis), 0);
+    let (rows, cols) = frame.dim();
+    let mut flows = Array4::<f64>::zeros((n_frames - 1, rows, cols, 2));
+    let mut magnitudes = Array3::<f64>::zeros((n_frames - 1, rows, cols));
+    for i in 0..n_frames - 1 {
+        let prev = data.index_axis(Axis(axis), i);
+        let next = data.index_axis(Axis(axis), i + 1);
+        let (flow, magnitude) = match method {
+            FlowMethod::LucasKanade {
+                window_radius,
+                min_eigenvalue,
+            } => lucas_kanade(prev, next, Some(window_radius), Some(min_eigenvalue))?,
+            FlowMethod::Farneback {
+                window_radius,
+                poly_radius,
+                iterations,
+            } => farneback(
+                prev,
+                next,
+                Some(window_radius),
+                Some(poly_radius),
+                Some(iterations),
+            )?,
+        };
+        flows.slice_mut(s![i, .., .., ..]).assign(&flow);
+        magnitudes.slice_mut(s![i, .., ..]).assign(&magnitude);
+    }
+
+    Ok((flows, magnitudes))
+}
+
+/// Check that the frame shapes match and the window radius is valid.
+fn check_frames(
+    shape_a: &[usize],
+    shape_b: &[usize],
+    window_radius: usize,
+) -> Result<(), ImgalError> {
+    if shape_a != shape_b {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape_a.to_vec(),
+            shape_b: shape_b.to_vec(),
+        });
+    }
+    if window_radius == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "window_radius",
+            value: 0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Unpack the quadratic (A) and linear (b) coefficients of a pixel, in
+/// (row, col) order.
+fn coef_matrix(coefs: &Array3<f64>, r: usize, c: usize) -> ([[f64; 2]; 2], [f64; 2]) {
+    // coefficient order: 1, r, c, r², c², rc
+    let a = [
+        [coefs[[3, r, c]], coefs[[5, r, c]] * 0.5],
+        [coefs[[5, r, c]] * 0.5, coefs[[4, r, c]]],
+    ];
+    let b = [coefs[[1, r, c]], coefs[[2, r, c]]];
+
+    (a, b)
+}
+
+/// Compute the flow magnitude of a (row, col, 2) flow field.
+fn flow_magnitude(flow: &Array3<f64>) -> Array2<f64> {
+    flow.map_axis(Axis(2), |f| f[0].hypot(f[1]))
+}
+
+/// Fit a Gaussian weighted quadratic polynomial to the neighborhood of each
+/// pixel, returning the (1, r, c, r², c², rc) coefficients with a shape of
+/// (6, row, col).
+fn polynomial_expansion(data: &Array2<f64>, radius: usize) -> Array3<f64> {
+    // build the weighted least squares filters, (BᵀWB)⁻¹BᵀW, by solving the
+    // normal equations for each neighborhood offset
+    let weights = gaussian_kernel(window_sigma(radius), radius);
+    let r = radius as isize;
+    let mut offsets: Vec<(isize, isize, f64)> = Vec::new();
+    for dr in -r..=r {
+        for dc in -r..=r {
+            let w = weights[(dr + r) as usize] * weights[(dc + r) as usize];
+            offsets.push((dr, dc, w));
+        }
+    }
+    let basis = |dr: isize, dc: isize| {
+        let (y, x) = (dr as f64, dc as f64);
+        [1.0, y, x, y * y, x * x, y * x]
+    };
+    let mut g = [[0.0; 6]; 6];
+    offsets.iter().for_each(|&(dr, dc, w)| {
+        let b = basis(dr, dc);
+        (0..6).for_each(|i| (0..6).for_each(|j| g[i][j] += w * b[i] * b[j]));
+    });
+    let g: Vec<Vec<f64>> = g.iter().map(|row| row.to_vec()).collect();
+    let filters: Vec<[f64; 6]> = offsets
+        .iter()
+        .map(|&(dr, dc, w)| {
+            let b = basis(dr, dc).map(|v| v * w).to_vec();
+            let f = solve(g.clone(), b).unwrap_or_else(|| vec![0.0; 6]);
+            std::array::from_fn(|i| f[i])
+        })
+        .collect();
+
+    // apply the filters with clamped edges
+    let (rows, cols) = data.dim();
+    let mut coefs = Array3::<f64>::zeros((6, rows, cols));
+    Zip::indexed(coefs.lanes_mut(Axis(0))).par_for_each(|(row, col), mut k| {
+        offsets
+            .iter()
+            .zip(filters.iter())
+            .for_each(|(&(dr, dc, _), f)| {
+                let rr = (row as isize + dr).clamp(0, rows as isize - 1) as usize;
+                let cc = (col as isize + dc).clamp(0, cols as isize - 1) as usize;
+                let v = data[[rr, cc]];
+                (0..6).for_each(|i| k[i] += f[i] * v);
+            });
+    });
+
+    coefs
+}
+
+/// Compute the Gaussian window sigma of a window radius, sigma = radius / 2.
+fn window_sigma(radius: usize) -> f64 {
+    (radius as f64 / 2.0).max(0.5)
+}
+
+/// Average a map over the Gaussian window of each pixel.
+fn smooth(data: &Array2<f64>, sigma: f64) -> Array2<f64> {
+    let mut out = data.clone();
+    let mut buffer = Array2::<f64>::zeros(data.dim());
+    gaussian_2d_mut(out.view_mut(), &mut buffer, sigma);
+
+    out
+}
@@ -0,0 +1,317 @@
+use ndarray::{ArrayView2, ArrayViewD, Ix2};
+
+use crate::error::ImgalError;
+use crate::filter::gaussian_2d;
+use crate::image::anscombe;
+use crate::ops::op::{Op, OpInfo, OpParameter, OpValue, ValueType};
+use crate::segmentation::{Connectivity, label};
+use crate::statistics::brenner_gradient;
+use crate::threshold::{manual_mask, otsu_mask, otsu_value};
+
+/// Create the metadata of a required, unconstrained op parameter.
+const fn param(
+    name: &'static str,
+    description: &'static str,
+    value_type: ValueType,
+) -> OpParameter {
+    OpParameter {
+        name,
+        description,
+        value_type,
+        required: true,
+        min: None,
+        max: None,
+    }
+}
+
+/// Create the metadata of an optional, constrained op parameter.
+const fn optional(
+    name: &'static str,
+    description: &'static str,
+    value_type: ValueType,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> OpParameter {
+    OpParameter {
+        name,
+        description,
+        value_type,
+        required: false,
+        min,
+        max,
+    }
+}
+
+const IMAGE_2D: OpParameter = param("data", "The input 2-dimensional image.", ValueType::Image);
+const IMAGE: OpParameter = param("data", "An n-dimensional image.", ValueType::Image);
+const MASK: OpParameter = param("mask", "A boolean mask.", ValueType::Mask);
+const BINS: OpParameter = optional(
+    "bins",
+    "The number of histogram bins, default = 256.",
+    ValueType::Int,
+    Some(2.0),
+    None,
+);
+
+/// All builtin `imgal` ops.
+pub(crate) fn builtin_ops() -> Vec<Box<dyn Op>> {
+    vec![
+        Box::new(AnscombeOp),
+        Box::new(BrennerGradientOp),
+        Box::new(Gaussian2dOp),
+        Box::new(LabelOp),
+        Box::new(ManualMaskOp),
+        Box::new(OtsuMaskOp),
+        Box::new(OtsuValueOp),
+    ]
+}
+
+/// Get a validated image input.
+fn image(value: &Option<OpValue>) -> ArrayViewD<'_, f64> {
+    match value {
+        Some(OpValue::Image(arr)) => arr.view(),
+        _ => unreachable!(),
+    }
+}
+
+/// Get a validated image input as a 2-dimensional view.
+fn image_2d(value: &Option<OpValue>) -> Result<ArrayView2<'_, f64>, ImgalError> {
+    image(value)
+        .into_dimensionality::<Ix2>()
+        .map_err(|_| ImgalError::InvalidArrayGeneric {
+            msg: "Invalid op inputs, the image must be 2-dimensional.",
+        })
+}
+
+/// Get a validated optional float input.
+fn float(value: &Option<OpValue>) -> Option<f64> {
+    match value {
+        Some(OpValue::Float(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Get a validated optional integer input.
+fn int(value: &Option<OpValue>) -> Option<usize> {
+    match value {
+        Some(OpValue::Int(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+/// The [`crate::image::anscombe`] op.
+struct AnscombeOp;
+
+impl Op for AnscombeOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "image.anscombe",
+            description: "Stabilize the variance of Poisson distributed data with the Anscombe transform.",
+            inputs: &[IMAGE],
+            outputs: &[param("output", "The transformed image.", ValueType::Image)],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        Ok(vec![OpValue::Image(anscombe(image(&inputs[0])))])
+    }
+}
+
+/// The [`crate::statistics::brenner_gradient`] op.
+struct BrennerGradientOp;
+
+impl Op for BrennerGradientOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "statistics.brenner_gradient",
+            description: "Compute the Brenner gradient focus measure of a 2-dimensional image.",
+            inputs: &[IMAGE_2D],
+            outputs: &[param("focus", "The focus measure.", ValueType::Float)],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        Ok(vec![OpValue::Float(brenner_gradient(image_2d(
+            &inputs[0],
+        )?))])
+    }
+}
+
+/// The [`crate::filter::gaussian_2d`] op.
+struct Gaussian2dOp;
+
+impl Op for Gaussian2dOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "filter.gaussian_2d",
+            description: "Smooth a 2-dimensional image with a Gaussian filter.",
+            inputs: &[
+                IMAGE_2D,
+                OpParameter {
+                    min: Some(0.0),
+                    ..param(
+                        "sigma",
+                        "The standard deviation of the Gaussian kernel in pixels.",
+                        ValueType::Float,
+                    )
+                },
+            ],
+            outputs: &[param("output", "The smoothed image.", ValueType::Image)],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let sigma = float(&inputs[1]).unwrap();
+        let output = gaussian_2d(image_2d(&inputs[0])?, sigma)?;
+
+        Ok(vec![OpValue::Image(output.into_dyn())])
+    }
+}
+
+/// The [`crate::segmentation::label`] op.
+struct LabelOp;
+
+impl Op for LabelOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "segmentation.label",
+            description: "Label the connected components of a 2-dimensional boolean mask.",
+            inputs: &[
+                MASK,
+                optional(
+                    "connectivity",
+                    "The pixel connectivity, 4 or 8, default = 8.",
+                    ValueType::Int,
+                    Some(4.0),
+                    Some(8.0),
+                ),
+            ],
+            outputs: &[
+                param("labels", "The label image.", ValueType::Labels),
+                param("count", "The number of labels.", ValueType::Int),
+            ],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let Some(OpValue::Mask(mask)) = &inputs[0] else {
+            unreachable!()
+        };
+        let mask = mask.view().into_dimensionality::<Ix2>().map_err(|_| {
+            ImgalError::InvalidArrayGeneric {
+                msg: "Invalid op inputs, the mask must be 2-dimensional.",
+            }
+        })?;
+        let connectivity = match int(&inputs[1]) {
+            None | Some(8) => Connectivity::Eight,
+            Some(4) => Connectivity::Four,
+            Some(_) => {
+                return Err(ImgalError::InvalidArrayGeneric {
+                    msg: "Invalid op inputs, the connectivity must be 4 or 8.",
+                });
+            }
+        };
+        let (labels, count) = label(mask, Some(connectivity));
+
+        Ok(vec![
+            OpValue::Labels(labels.into_dyn()),
+            OpValue::Int(count),
+        ])
+    }
+}
+
+/// The [`crate::threshold::manual_mask`] op.
+struct ManualMaskOp;
+
+impl Op for ManualMaskOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "threshold.manual_mask",
+            description: "Create a boolean mask from a threshold value.",
+            inputs: &[
+                IMAGE,
+                param(
+                    "threshold",
+                    "The image pixel threshold value.",
+                    ValueType::Float,
+                ),
+            ],
+            outputs: &[param(
+                "mask",
+                "The mask of pixels greater than the threshold.",
+                ValueType::Mask,
+            )],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let threshold = float(&inputs[1]).unwrap();
+
+        Ok(vec![OpValue::Mask(manual_mask(
+            image(&inputs[0]),
+            threshold,
+        ))])
+    }
+}
+
+/// The [`crate::threshold::otsu_mask`] op.
+struct OtsuMaskOp;
+
+impl Op for OtsuMaskOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "threshold.otsu_mask",
+            description: "Create a boolean mask with Otsu's threshold.",
+            inputs: &[IMAGE, BINS],
+            outputs: &[param(
+                "mask",
+                "The mask of pixels greater than the threshold.",
+                ValueType::Mask,
+            )],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let mask = otsu_mask(image(&inputs[0]), int(&inputs[1]))?;
+
+        Ok(vec![OpValue::Mask(mask)])
+    }
+}
+
+/// The [`crate::threshold::otsu_value`] op.
+struct OtsuValueOp;
+
+impl Op for OtsuValueOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "threshold.otsu_value",
+            description: "Compute Otsu's threshold value of an n-dimensional image.",
+            inputs: &[IMAGE, BINS],
+            outputs: &[param(
+                "threshold",
+                "Otsu's threshold value.",
+                ValueType::Float,
+            )],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let threshold = otsu_value(image(&inputs[0]), int(&inputs[1]))?;
+
+        Ok(vec![OpValue::Float(threshold)])
+    }
+}
@@ -0,0 +1,6 @@
+//! Op registry for dynamic algorithm lookup and invocation.
+mod builtin;
+pub mod op;
+pub use op::{Op, OpInfo, OpParameter, OpValue, ValueType};
+pub mod registry;
+pub use registry::OpRegistry;
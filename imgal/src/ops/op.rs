@@ -0,0 +1,145 @@
+use ndarray::ArrayD;
+
+use crate::error::ImgalError;
+
+/// The value type of an op input or output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// An n-dimensional floating point image.
+    Image,
+    /// An n-dimensional boolean mask.
+    Mask,
+    /// An n-dimensional label image.
+    Labels,
+    /// A floating point scalar.
+    Float,
+    /// An unsigned integer scalar.
+    Int,
+}
+
+/// A dynamically typed op input or output value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpValue {
+    Image(ArrayD<f64>),
+    Mask(ArrayD<bool>),
+    Labels(ArrayD<usize>),
+    Float(f64),
+    Int(usize),
+}
+
+impl OpValue {
+    /// The value type of this value.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            OpValue::Image(_) => ValueType::Image,
+            OpValue::Mask(_) => ValueType::Mask,
+            OpValue::Labels(_) => ValueType::Labels,
+            OpValue::Float(_) => ValueType::Float,
+            OpValue::Int(_) => ValueType::Int,
+        }
+    }
+}
+
+/// The metadata of an op input or output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpParameter {
+    /// The parameter name.
+    pub name: &'static str,
+    /// A short description of the parameter.
+    pub description: &'static str,
+    /// The value type of the parameter.
+    pub value_type: ValueType,
+    /// If `false`, the parameter may be omitted and the op uses its default.
+    pub required: bool,
+    /// The inclusive minimum of a scalar parameter, if constrained.
+    pub min: Option<f64>,
+    /// The inclusive maximum of a scalar parameter, if constrained.
+    pub max: Option<f64>,
+}
+
+/// The metadata of an op.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpInfo {
+    /// The unique op name, namespaced by module (_e.g._ `"filter.gaussian_2d"`).
+    pub name: &'static str,
+    /// A short description of the op.
+    pub description: &'static str,
+    /// The op inputs, in positional order.
+    pub inputs: &'static [OpParameter],
+    /// The op outputs, in positional order.
+    pub outputs: &'static [OpParameter],
+}
+
+/// An algorithm that can be looked up and invoked dynamically.
+///
+/// # Description
+///
+/// An op describes its inputs and outputs with an [`OpInfo`] and computes its
+/// outputs from a list of [`OpValue`] inputs. Ops are collected in an
+/// [`crate::ops::OpRegistry`], which lets bindings enumerate the available
+/// algorithms and build user interfaces from their metadata.
+pub trait Op: Send + Sync {
+    /// The metadata of the op.
+    fn info(&self) -> &OpInfo;
+
+    /// Compute the op outputs from validated inputs.
+    ///
+    /// The inputs match the op's input metadata in length, order and type,
+    /// with `None` for omitted optional inputs.
+    fn compute(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError>;
+
+    /// Validate the inputs against the op metadata and run the op.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs`: The op inputs in positional order, with `None` for omitted
+    ///    optional inputs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<OpValue>)`: The op outputs in positional order.
+    /// * `Err(ImgalError)`: If the number of inputs does not match the op
+    ///    metadata. If a required input is missing, has the wrong value type
+    ///    or a scalar input is outside of its constraints. If the op fails.
+    fn run(&self, inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        let params = self.info().inputs;
+        if inputs.len() != params.len() {
+            return Err(ImgalError::MismatchedArrayLengths {
+                a_arr_len: inputs.len(),
+                b_arr_len: params.len(),
+            });
+        }
+        for (input, param) in inputs.iter().zip(params) {
+            let Some(value) = input else {
+                if param.required {
+                    return Err(ImgalError::InvalidArrayGeneric {
+                        msg: "Invalid op inputs, a required input is missing.",
+                    });
+                }
+                continue;
+            };
+            if value.value_type() != param.value_type {
+                return Err(ImgalError::InvalidArrayGeneric {
+                    msg: "Invalid op inputs, an input has the wrong value type.",
+                });
+            }
+            let scalar = match value {
+                OpValue::Float(v) => *v,
+                OpValue::Int(v) => *v as f64,
+                _ => continue,
+            };
+            let min = param.min.unwrap_or(f64::NEG_INFINITY);
+            let max = param.max.unwrap_or(f64::INFINITY);
+            if scalar.is_nan() || scalar < min || scalar > max {
+                return Err(ImgalError::InvalidParameterValueOutsideRange {
+                    param_name: param.name,
+                    value: scalar,
+                    min,
+                    max,
+                });
+            }
+        }
+
+        self.compute(inputs)
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use crate::ops::builtin::builtin_ops;
+use crate::ops::op::Op;
+
+/// A collection of ops that can be enumerated and looked up by name.
+///
+/// # Description
+///
+/// The registry maps unique op names to [`Op`] implementations. The builtin
+/// `imgal` ops are available with [`OpRegistry::with_builtins`]:
+///
+/// ```
+/// use ndarray::Array2;
+/// use imgal::ops::{OpRegistry, OpValue};
+///
+/// let registry = OpRegistry::with_builtins();
+/// let op = registry.get("threshold.manual_mask").unwrap();
+/// let data = Array2::<f64>::from_elem((4, 4), 2.0).into_dyn();
+/// let outputs = op
+///     .run(&[Some(OpValue::Image(data)), Some(OpValue::Float(1.0))])
+///     .unwrap();
+/// assert!(matches!(&outputs[0], OpValue::Mask(m) if m.iter().all(|&v| v)));
+/// ```
+#[derive(Default)]
+pub struct OpRegistry {
+    ops: BTreeMap<&'static str, Box<dyn Op>>,
+}
+
+impl OpRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with all builtin `imgal` ops.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        builtin_ops()
+            .into_iter()
+            .for_each(|op| registry.register(op));
+
+        registry
+    }
+
+    /// Register an op, replacing any op with the same name.
+    pub fn register(&mut self, op: Box<dyn Op>) {
+        self.ops.insert(op.info().name, op);
+    }
+
+    /// Look up an op by name.
+    pub fn get(&self, name: &str) -> Option<&dyn Op> {
+        self.ops.get(name).map(|op| op.as_ref())
+    }
+
+    /// Iterate over the registered ops, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Op> {
+        self.ops.values().map(|op| op.as_ref())
+    }
+
+    /// The number of registered ops.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if no ops are registered.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
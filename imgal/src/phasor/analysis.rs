@@ -0,0 +1,125 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::parameter::omega;
+use crate::phasor::time_domain::validate_harmonic;
+use crate::traits::numeric::ToFloat64;
+
+// the multi-exponentiality index and apparent lifetime images
+type HarmonicConsistency = (Array2<f64>, Array3<f64>);
+
+/// Test the consistency of the apparent lifetimes of a 3-dimensional decay
+/// image across harmonics.
+///
+/// # Description
+///
+/// This function computes the apparent phase (τφ) and modulation (τM)
+/// lifetimes of each pixel at each harmonic "n":
+///
+/// ```text
+/// τφ(n) = Sₙ / (nωGₙ)
+/// τM(n) = √(1 / (Gₙ² + Sₙ²) - 1) / (nω)
+/// ```
+///
+/// All apparent lifetimes of a monoexponential decay are equal, while for
+/// multi-exponential decays they differ and decrease with the harmonic. The
+/// multi-exponentiality index of each pixel is the coefficient of variation
+/// of its apparent lifetimes:
+///
+/// ```text
+/// index = std(τ) / mean(τ)
+/// ```
+///
+/// An index close to 0.0 marks pixels where a single lifetime, and simple
+/// phasor interpretation, is valid, while larger values call for a
+/// multi-component analysis (_e.g._ [`crate::phasor::unmixing`]). Pixels
+/// without photons, or with non-finite apparent lifetimes, are NaN.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonics`: The harmonics to compare, default = [1, 2]. Each harmonic
+///    must be greater than 0 and can not exceed the Nyquist limit.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array3<f64>))`: The multi-exponentiality index image
+///    and the apparent lifetime image, where τφ(n) and τM(n) of the i-th
+///    harmonic are channels 2i and 2i + 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If `harmonics` is empty. If a
+///    harmonic is 0 or aliased (_i.e._ above the Nyquist limit).
+pub fn harmonic_consistency<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    harmonics: Option<&[u32]>,
+    axis: Option<usize>,
+) -> Result<HarmonicConsistency, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let harmonics = harmonics.unwrap_or(&[1, 2]);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if harmonics.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the harmonics must not be empty.",
+        });
+    }
+    let n = data.len_of(Axis(a));
+    for &h in harmonics {
+        validate_harmonic(h, n)?;
+    }
+
+    // load the waveform tables of each harmonic
+    let w = omega(period);
+    let dt = period / n as f64;
+    let tables: Vec<(Vec<f64>, Vec<f64>)> = harmonics
+        .iter()
+        .map(|&h| {
+            let theta = |i: usize| h as f64 * w * dt * i as f64;
+            (
+                (0..n).map(|i| theta(i).cos()).collect(),
+                (0..n).map(|i| theta(i).sin()).collect(),
+            )
+        })
+        .collect();
+
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut index = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut taus = Array3::<f64>::zeros((shape[0], shape[1], 2 * harmonics.len()));
+    Zip::from(data.lanes(Axis(a)))
+        .and(&mut index)
+        .and(taus.lanes_mut(Axis(2)))
+        .par_for_each(|ln, idx, mut t| {
+            let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let photons: f64 = values.iter().sum();
+            for (k, (&h, (cos, sin))) in harmonics.iter().zip(&tables).enumerate() {
+                let g = values.iter().zip(cos).map(|(x, c)| x * c).sum::<f64>() / photons;
+                let s = values.iter().zip(sin).map(|(x, c)| x * c).sum::<f64>() / photons;
+                let hw = h as f64 * w;
+                t[2 * k] = s / (hw * g);
+                t[2 * k + 1] = (1.0 / (g * g + s * s) - 1.0).sqrt() / hw;
+            }
+            *idx = if t.iter().all(|v| v.is_finite()) {
+                let m = t.len() as f64;
+                let mean = t.sum() / m;
+                let var = t.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / m;
+                var.sqrt() / mean
+            } else {
+                f64::NAN
+            };
+        });
+
+    Ok((index, taus))
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+
+use crate::error::ImgalError;
+use crate::phasor::calibration;
+use crate::phasor::time_domain::{PhasorGate, Waveform, compute_image_waveform, validate_harmonic};
+use crate::traits::numeric::ToFloat64;
+
+// the G/S image with a shape of (row, col, ch) and the validity mask
+type BatchOutput = (Array3<f64>, Array2<bool>);
+
+/// Shared phasor settings of a batch of decay datasets.
+///
+/// # Description
+///
+/// The settings are validated once by [`process`] and applied to every
+/// dataset of the batch. Optional settings use the defaults of
+/// [`crate::phasor::time_domain::gated_image`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhasorSettings {
+    /// The period (_i.e._ time interval).
+    pub period: f64,
+    /// The harmonic value, default = 1.
+    pub harmonic: Option<u32>,
+    /// The decay or lifetime axis, default = 2.
+    pub axis: Option<usize>,
+    /// The (modulation, phase) calibration applied to the phasor images (see
+    /// [`crate::phasor::calibration::modulation_and_phase`]). If `None`, the
+    /// phasor images are not calibrated.
+    pub calibration: Option<(f64, f64)>,
+    /// The minimum total photon count of a pixel's decay. If `None`, pixels
+    /// are not gated by photon count.
+    pub min_photons: Option<f64>,
+    /// The minimum histogram quality (0.0 to 1.0) of a pixel's decay. If
+    /// `None`, pixels are not gated by histogram quality.
+    pub min_quality: Option<f64>,
+    /// The G and S value of pixels that fail a gate, default = NaN.
+    pub fill: Option<f64>,
+}
+
+impl PhasorSettings {
+    /// Create settings with a period and default values for all other
+    /// settings.
+    pub fn new(period: f64) -> Self {
+        Self {
+            period,
+            harmonic: None,
+            axis: None,
+            calibration: None,
+            min_photons: None,
+            min_quality: None,
+            fill: None,
+        }
+    }
+}
+
+/// Compute the calibrated and gated phasor images of a batch of decay
+/// datasets.
+///
+/// # Description
+///
+/// This function lazily processes a sequence of 3-dimensional decay images
+/// (_e.g._ the files of an acquisition session) with shared settings: each
+/// dataset's phasor image is computed with photon count and histogram quality
+/// gating (see [`crate::phasor::time_domain::gated_image`]) and calibrated
+/// (see [`crate::phasor::calibration::image_mut`]) in place. The settings are
+/// validated once, and the waveform tables are computed once per decay length
+/// and reused by all datasets with the same number of time bins. Each dataset
+/// is processed in parallel on the shared thread pool as the iterator is
+/// advanced, so only one dataset's output is held at a time.
+///
+/// # Arguments
+///
+/// * `datasets`: The decay images, I(t), to process.
+/// * `settings`: The shared phasor settings.
+///
+/// # Returns
+///
+/// * `Ok(impl Iterator<Item = Result<(Array3<f64>, Array2<bool>), ImgalError>>)`:
+///    An iterator over the phasor image and validity mask of each dataset,
+///    in the order of `datasets`. The phasor image is a 3D (row, col, ch)
+///    image, where G and S are indexed at 0 and 1 respectively on the
+///    _channel_ axis. A dataset fails if the harmonic is aliased for its
+///    number of time bins.
+/// * `Err(ImgalError)`: If the period is not positive. If axis is >= 3. If
+///    the harmonic is 0. If the photon count or quality gates are invalid.
+pub fn process<'a, T, I>(
+    datasets: I,
+    settings: PhasorSettings,
+) -> Result<impl Iterator<Item = Result<BatchOutput, ImgalError>>, ImgalError>
+where
+    T: ToFloat64 + 'a,
+    I: IntoIterator<Item = ArrayView3<'a, T>>,
+{
+    // set optional parameters if needed
+    let h = settings.harmonic.unwrap_or(1);
+    let a = settings.axis.unwrap_or(2);
+    let gate = PhasorGate {
+        min_photons: settings.min_photons,
+        min_quality: settings.min_quality,
+        fill: settings.fill.unwrap_or(f64::NAN),
+    };
+
+    // check if parameters are valid
+    if settings.period.is_nan() || settings.period <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "period",
+            value: settings.period,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if h == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "harmonic",
+            value: 0,
+        });
+    }
+    gate.validate()?;
+
+    // cache the waveform tables of each decay length
+    let mut tables: HashMap<usize, Waveform> = HashMap::new();
+    Ok(datasets.into_iter().map(move |data| {
+        let n = data.len_of(Axis(a));
+        validate_harmonic(h, n)?;
+        let waveform = tables
+            .entry(n)
+            .or_insert_with(|| Waveform::new(settings.period, h as f64, n));
+        let (mut gs, valid) = compute_image_waveform(data, waveform, None, a, &gate);
+        if let Some((modulation, phase)) = settings.calibration {
+            calibration::image_mut(gs.view_mut(), modulation, phase, Some(2));
+        }
+
+        Ok((gs, valid))
+    }))
+}
+
+/// Compute the calibrated and gated phasor images of a batch of decay
+/// datasets and pass each result to a callback.
+///
+/// # Description
+///
+/// This function processes a sequence of decay images like [`process`] and
+/// calls `callback` with the index and result of each dataset as soon as it
+/// is computed (_e.g._ to save or summarize each result without keeping the
+/// batch in memory). A failed dataset does not stop the batch.
+///
+/// # Arguments
+///
+/// * `datasets`: The decay images, I(t), to process.
+/// * `settings`: The shared phasor settings.
+/// * `callback`: The function called with the index of each dataset and its
+///    phasor image and validity mask, or its error.
+///
+/// # Returns
+///
+/// * `Ok(usize)`: The number of processed datasets.
+/// * `Err(ImgalError)`: If the period is not positive. If axis is >= 3. If
+///    the harmonic is 0. If the photon count or quality gates are invalid.
+pub fn process_with<'a, T, I, F>(
+    datasets: I,
+    settings: PhasorSettings,
+    mut callback: F,
+) -> Result<usize, ImgalError>
+where
+    T: ToFloat64 + 'a,
+    I: IntoIterator<Item = ArrayView3<'a, T>>,
+    F: FnMut(usize, Result<BatchOutput, ImgalError>),
+{
+    let mut count = 0;
+    process(datasets, settings)?
+        .enumerate()
+        .for_each(|(i, result)| {
+            callback(i, result);
+            count += 1;
+        });
+
+    Ok(count)
+}
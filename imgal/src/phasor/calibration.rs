@@ -1,9 +1,76 @@
-use ndarray::{Array3, ArrayView3, ArrayViewMut3, Axis, Zip};
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, ArrayViewMut3, Axis, Zip};
 use rayon::prelude::*;
 
+use crate::error::ImgalError;
 use crate::phasor::plot;
+use crate::statistics::median;
 use crate::traits::numeric::ToFloat64;
 
+/// The estimator of the center of a phasor coordinate distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CenterEstimator {
+    /// The arithmetic mean.
+    Mean,
+    /// The median, robust to up to half of the coordinates being outliers.
+    Median,
+    /// The mean after discarding the given fraction (0.0 to 0.5, exclusive)
+    /// of the smallest and of the largest values.
+    Trimmed(f64),
+}
+
+/// Parameters of [`robust_from_image`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RobustOptions {
+    /// The center estimator of the calibration coordinates, default =
+    /// `CenterEstimator::Median`.
+    pub estimator: CenterEstimator,
+    /// The rejection threshold in robust standard deviations of the distance
+    /// to the expected coordinates, must be positive, default = 3.0.
+    pub threshold: f64,
+    /// The maximum number of rejection iterations, default = 10.
+    pub max_iterations: usize,
+}
+
+impl Default for RobustOptions {
+    fn default() -> Self {
+        RobustOptions {
+            estimator: CenterEstimator::Median,
+            threshold: 3.0,
+            max_iterations: 10,
+        }
+    }
+}
+
+impl RobustOptions {
+    /// Check that the parameters are valid.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        check_estimator(self.estimator)?;
+        if self.threshold.is_nan() || self.threshold <= 0.0 {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "threshold",
+                value: self.threshold,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A robust calibration of a phasor image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustCalibration {
+    /// The modulation calibration value, M.
+    pub modulation: f64,
+    /// The phase calibration value, φ.
+    pub phase: f64,
+    /// The pixels used by the final estimate.
+    pub inliers: Array2<bool>,
+    /// The number of rejection iterations performed.
+    pub iterations: usize,
+}
+
 /// Calibrate a real and imaginary (G, S) coordinates.
 ///
 /// # Description
@@ -181,3 +248,401 @@ pub fn modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) -> (f64, f64)
 
     (d_mod, d_phs)
 }
+
+/// A likely calibration mistake found by [`diagnostics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationIssue {
+    /// The control lies off the universal circle, from a modulation error,
+    /// background or IRF contamination, or a control that is not
+    /// monoexponential.
+    OffCircle,
+    /// The control lies on the universal circle away from its expected
+    /// lifetime, from a phase error or a mismatch of the period (or bin
+    /// width) with the acquisition.
+    LifetimeMismatch,
+}
+
+/// The calibration diagnostics of a monoexponential control.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationDiagnostics {
+    /// The median (G, S) coordinates of the control.
+    pub center: (f64, f64),
+    /// The expected (G, S) coordinates of the control's lifetime.
+    pub expected: (f64, f64),
+    /// The distance from `center` to `expected`.
+    pub center_distance: f64,
+    /// The median signed distance of the coordinates to the universal
+    /// circle, positive outside of the circle.
+    pub circle_distance: f64,
+    /// The median distance of the coordinates to `center`.
+    pub spread: f64,
+    /// The apparent phase lifetime of `center`, τφ.
+    pub phase_lifetime: f64,
+    /// The apparent modulation lifetime of `center`, τM.
+    pub modulation_lifetime: f64,
+    /// The modulation correction that moves `center` to `expected`.
+    pub suggested_modulation: f64,
+    /// The phase correction that moves `center` to `expected`.
+    pub suggested_phase: f64,
+    /// The factor of the period that makes τφ the expected lifetime, if the
+    /// lifetime mismatch is a period mismatch.
+    pub suggested_period_scale: f64,
+    /// The likely calibration mistakes.
+    pub issues: Vec<CalibrationIssue>,
+}
+
+/// Find the modulation and phase calibration values from many measured
+/// coordinates.
+///
+/// # Description
+///
+/// This function estimates the center of the measured (G, S) coordinates of
+/// a monoexponential reference with `estimator`, coordinate by coordinate,
+/// and computes the calibration values of the center with
+/// [`modulation_and_phase`]. The median and trimmed mean are robust to a few
+/// saturated or background pixels that skew the mean. Non-finite coordinates
+/// are ignored.
+///
+/// # Arguments
+///
+/// * `g`: The measured real (G) values.
+/// * `s`: The measured imaginary (S) values, the same length as `g`.
+/// * `tau`: The lifetime, τ.
+/// * `omega`: The angular frequency, ω.
+/// * `estimator`: The center estimator, default = `CenterEstimator::Median`.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The modulation and phase calibration values, (M, φ).
+/// * `Err(ImgalError)`: If the lengths of `g` and `s` do not match. If there
+///    are no finite coordinates. If the trimmed fraction is not between 0.0
+///    and 0.5.
+pub fn estimate_modulation_and_phase(
+    g: &[f64],
+    s: &[f64],
+    tau: f64,
+    omega: f64,
+    estimator: Option<CenterEstimator>,
+) -> Result<(f64, f64), ImgalError> {
+    // set optional parameters if needed
+    let estimator = estimator.unwrap_or(CenterEstimator::Median);
+
+    // check if parameters are valid
+    if g.len() != s.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: g.len(),
+            b_arr_len: s.len(),
+        });
+    }
+    check_estimator(estimator)?;
+    let (gc, sc) = center(g, s, estimator)?;
+
+    Ok(modulation_and_phase(gc, sc, tau, omega))
+}
+
+/// Find the modulation and phase calibration values of a 3-dimensional
+/// phasor image with iterative outlier rejection.
+///
+/// # Description
+///
+/// This function calibrates a phasor image of a monoexponential reference
+/// (_e.g._ a dye of known lifetime) while rejecting pixels that do not
+/// behave like the reference. Starting from all finite pixels of `mask`, each
+/// iteration estimates the calibration values from the current pixels (see
+/// [`estimate_modulation_and_phase`]), calibrates them and measures the
+/// distance "d" of each pixel to the expected monoexponential coordinates of
+/// `tau`. Pixels with:
+///
+/// ```text
+/// d > threshold · 1.4826 · median(d)
+/// ```
+///
+/// Are rejected, and the iterations stop when the pixels do not change or
+/// after `max_iterations`.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `tau`: The lifetime of the reference, τ.
+/// * `omega`: The angular frequency, ω.
+/// * `mask`: The 2-dimensional boolean mask of the reference pixels. If
+///    `None`, all pixels are used.
+/// * `options`: The rejection parameters, default =
+///    `RobustOptions::default()`.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(RobustCalibration)`: The calibration values and the inlier pixels.
+/// * `Err(ImgalError)`: If the axis is >= 3. If the shape of `mask` does not
+///    match the image. If there are no finite pixels. If the options are
+///    invalid.
+pub fn robust_from_image<T>(
+    data: ArrayView3<T>,
+    tau: f64,
+    omega: f64,
+    mask: Option<ArrayView2<bool>>,
+    options: Option<RobustOptions>,
+    axis: Option<usize>,
+) -> Result<RobustCalibration, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let options = options.unwrap_or_default();
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    options.validate()?;
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(m) = mask
+        && m.shape() != shape.as_slice()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape,
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    // collect the finite reference coordinates
+    let mut g = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut s = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(&mut g)
+        .and(&mut s)
+        .and(data.lanes(Axis(a)))
+        .for_each(|g, s, ln| {
+            *g = ln[0].to_f64();
+            *s = ln[1].to_f64();
+        });
+    let mut inliers = Array2::<bool>::from_shape_fn((shape[0], shape[1]), |(r, c)| {
+        mask.is_none_or(|m| m[[r, c]]) && g[[r, c]].is_finite() && s[[r, c]].is_finite()
+    });
+
+    // estimate, calibrate and reject until the inliers are stable
+    let (g_ref, s_ref) = plot::monoexponential_coordinates(tau, omega);
+    let select = |inliers: &Array2<bool>, v: &Array2<f64>| -> Vec<f64> {
+        v.iter()
+            .zip(inliers)
+            .filter(|(_, i)| **i)
+            .map(|(v, _)| *v)
+            .collect()
+    };
+    let mut iterations = 0;
+    let (modulation, phase) = loop {
+        let (gi, si) = (select(&inliers, &g), select(&inliers, &s));
+        let (gc, sc) = center(&gi, &si, options.estimator)?;
+        let (m, p) = modulation_and_phase(gc, sc, tau, omega);
+        if iterations == options.max_iterations {
+            break (m, p);
+        }
+        let distance = Zip::from(&g).and(&s).map_collect(|&g, &s| {
+            let (gc, sc) = coordinates(g, s, m, p);
+            (gc - g_ref).hypot(sc - s_ref)
+        });
+        let mut d = select(&inliers, &distance);
+        let cutoff = (options.threshold * 1.4826 * median(&mut d)).max(f64::EPSILON);
+        let next = Zip::from(&inliers)
+            .and(&distance)
+            .map_collect(|&i, &d| i && d <= cutoff);
+        iterations += 1;
+        if next == inliers {
+            break (m, p);
+        }
+        inliers = next;
+    };
+
+    Ok(RobustCalibration {
+        modulation,
+        phase,
+        inliers,
+        iterations,
+    })
+}
+
+/// Check the calibration of a 3-dimensional phasor image of a
+/// monoexponential control.
+///
+/// # Description
+///
+/// This function compares the calibrated (G, S) coordinates of a control of
+/// known lifetime (_e.g._ a fluorescein or coumarin solution) with the
+/// universal circle and the expected monoexponential coordinates, to catch
+/// the most common calibration mistakes:
+///
+/// * [`CalibrationIssue::OffCircle`]: The median signed distance to the
+///    universal circle, `|(G - 0.5, S)| - 0.5`, exceeds `tolerance`.
+/// * [`CalibrationIssue::LifetimeMismatch`]: The control lies on the circle
+///    but its median coordinates are more than `tolerance` away from the
+///    expected coordinates. A single harmonic can not tell a phase error from
+///    a period mismatch, so corrections for both are suggested.
+///
+/// The suggested modulation and phase are applied on top of the current
+/// calibration (see [`image`]). The suggested period scale is the factor of
+/// the period (_e.g._ the bin width) that matches the apparent phase lifetime
+/// with `tau`:
+///
+/// ```text
+/// scale = τ / τφ
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional calibrated phasor image, where G and S are
+///    channels 0 and 1 respectively.
+/// * `tau`: The lifetime of the control, τ.
+/// * `omega`: The angular frequency, ω.
+/// * `mask`: The 2-dimensional boolean mask of the control pixels. If `None`,
+///    all pixels are used.
+/// * `tolerance`: The distance in phasor units beyond which an issue is
+///    reported, default = 0.02.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(CalibrationDiagnostics)`: The diagnostics of the control.
+/// * `Err(ImgalError)`: If the axis is >= 3. If the shape of `mask` does not
+///    match the image. If there are no finite pixels. If `tolerance` is not
+///    positive.
+pub fn diagnostics<T>(
+    data: ArrayView3<T>,
+    tau: f64,
+    omega: f64,
+    mask: Option<ArrayView2<bool>>,
+    tolerance: Option<f64>,
+    axis: Option<usize>,
+) -> Result<CalibrationDiagnostics, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let tolerance = tolerance.unwrap_or(0.02);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if tolerance.is_nan() || tolerance <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "tolerance",
+            value: tolerance,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(m) = mask
+        && m.shape() != shape.as_slice()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape,
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    // collect the finite control coordinates
+    let (g, s): (Vec<f64>, Vec<f64>) = Zip::indexed(data.lanes(Axis(a)))
+        .fold(Vec::new(), |mut acc, (r, c), ln| {
+            if mask.is_none_or(|m| m[[r, c]]) {
+                acc.push((ln[0].to_f64(), ln[1].to_f64()));
+            }
+            acc
+        })
+        .into_iter()
+        .filter(|(g, s)| g.is_finite() && s.is_finite())
+        .unzip();
+    let (gc, sc) = center(&g, &s, CenterEstimator::Median)?;
+
+    // compare the control with the universal circle and its lifetime
+    let expected = plot::monoexponential_coordinates(tau, omega);
+    let center_distance = (gc - expected.0).hypot(sc - expected.1);
+    let mut circle: Vec<f64> = g
+        .iter()
+        .zip(&s)
+        .map(|(g, s)| (g - 0.5).hypot(*s) - 0.5)
+        .collect();
+    let circle_distance = median(&mut circle);
+    let mut spread: Vec<f64> = g
+        .iter()
+        .zip(&s)
+        .map(|(g, s)| (g - gc).hypot(s - sc))
+        .collect();
+    let spread = median(&mut spread);
+    let phase_lifetime = sc / (omega * gc);
+    let modulation_lifetime = (1.0 / (gc * gc + sc * sc) - 1.0).sqrt() / omega;
+    let (suggested_modulation, suggested_phase) = modulation_and_phase(gc, sc, tau, omega);
+    let mut issues = Vec::new();
+    if circle_distance.abs() > tolerance {
+        issues.push(CalibrationIssue::OffCircle);
+    } else if center_distance > tolerance {
+        issues.push(CalibrationIssue::LifetimeMismatch);
+    }
+
+    Ok(CalibrationDiagnostics {
+        center: (gc, sc),
+        expected,
+        center_distance,
+        circle_distance,
+        spread,
+        phase_lifetime,
+        modulation_lifetime,
+        suggested_modulation,
+        suggested_phase,
+        suggested_period_scale: tau / phase_lifetime,
+        issues,
+    })
+}
+
+/// Estimate the center of the finite (G, S) coordinates.
+fn center(g: &[f64], s: &[f64], estimator: CenterEstimator) -> Result<(f64, f64), ImgalError> {
+    let (mut gv, mut sv): (Vec<f64>, Vec<f64>) = g
+        .iter()
+        .zip(s)
+        .filter(|(g, s)| g.is_finite() && s.is_finite())
+        .unzip();
+    if gv.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, there are no finite phasor coordinates.",
+        });
+    }
+    let estimate = |v: &mut [f64]| match estimator {
+        CenterEstimator::Mean => v.iter().sum::<f64>() / v.len() as f64,
+        CenterEstimator::Median => median(v),
+        CenterEstimator::Trimmed(f) => {
+            v.sort_by(|a, b| a.total_cmp(b));
+            let k = (f * v.len() as f64).floor() as usize;
+            let kept = &v[k..v.len() - k];
+            kept.iter().sum::<f64>() / kept.len() as f64
+        }
+    };
+
+    Ok((estimate(&mut gv), estimate(&mut sv)))
+}
+
+/// Check the trimmed fraction of a center estimator.
+fn check_estimator(estimator: CenterEstimator) -> Result<(), ImgalError> {
+    if let CenterEstimator::Trimmed(f) = estimator
+        && (f.is_nan() || !(0.0..0.5).contains(&f))
+    {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "trim",
+            value: f,
+            min: 0.0,
+            max: 0.5,
+        });
+    }
+
+    Ok(())
+}
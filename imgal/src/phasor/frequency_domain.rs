@@ -0,0 +1,230 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip, stack};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+// the G/S image with a shape of (row, col, ch), the DC image and the bad steps
+type PhaseStepsOutput = (Array3<f64>, Array2<f64>, Vec<usize>);
+
+/// Compute the real and imaginary (G, S) coordinates and the DC image of a
+/// phase-stepped homodyne image stack.
+///
+/// # Description
+///
+/// In camera-based frequency-domain FLIM, the detector gain is modulated at
+/// the excitation frequency and K images are acquired at phase steps φₖ of
+/// the gain modulation. Each pixel's intensity follows:
+///
+/// ```text
+/// Iₖ = DC + DC * M * cos(φₖ - φ) = a + b * cos(φₖ) + c * sin(φₖ)
+/// ```
+///
+/// This function fits "a", "b" and "c" per pixel by linear least squares,
+/// which is the discrete Fourier transform of the steps for equally spaced
+/// phase steps, and returns:
+///
+/// ```text
+/// DC = a
+/// G = M * cos(φ) = b / a
+/// S = M * sin(φ) = c / a
+/// ```
+///
+/// Bad steps (_e.g._ dropped or saturated frames, or excitation flicker) are
+/// detected with a leave-one-out test: each step is predicted from a fit of
+/// the other steps and its mean absolute prediction error relative to the DC
+/// image is computed. The step with the largest error is excluded while its
+/// error is greater than `tolerance` and at least 4 steps remain, then the
+/// remaining steps are fitted. Detection requires at least 4 steps. The
+/// coordinates are uncalibrated, see [`crate::phasor::calibration`].
+///
+/// # Arguments
+///
+/// * `data`: The phase-stepped image stack.
+/// * `phases`: The phase step angles in radians, default = 2πk/K (_i.e._ K
+///    equally spaced steps over one period).
+/// * `tolerance`: The maximum relative leave-one-out error of a good step,
+///    default = 0.1.
+/// * `axis`: The phase step axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array2<f64>, Vec<usize>))`: The real and imaginary
+///    coordinates as a 3D (row, col, ch) image, where G and S are indexed at 0
+///    and 1 respectively on the _channel_ axis, the DC image and the indices
+///    of the excluded bad steps.
+/// * `Err(ImgalError)`: If axis is >= 3. If there are less than 3 steps. If
+///    the length of `phases` does not match the
+///    number of steps. If the phase steps can not be fitted (_e.g._ repeated
+///    phases).
+pub fn from_phase_steps<T>(
+    data: ArrayView3<T>,
+    phases: Option<&[f64]>,
+    tolerance: Option<f64>,
+    axis: Option<usize>,
+) -> Result<PhaseStepsOutput, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let tolerance = tolerance.unwrap_or(0.1);
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    let k = data.len_of(Axis(a));
+    if k < 3 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "steps",
+            value: 3,
+        });
+    }
+    let phases: Vec<f64> = match phases {
+        Some(p) => {
+            if p.len() != k {
+                return Err(ImgalError::MismatchedArrayLengths {
+                    a_arr_len: p.len(),
+                    b_arr_len: k,
+                });
+            }
+            p.to_vec()
+        }
+        None => (0..k).map(|i| 2.0 * PI * i as f64 / k as f64).collect(),
+    };
+
+    // detect bad steps with leave-one-out predictions, removing the worst
+    // step until all remaining steps are consistent
+    let mut steps: Vec<usize> = (0..k).collect();
+    let mut bad_steps = Vec::new();
+    while steps.len() > 3 {
+        let errors = leave_one_out_errors(data.view(), a, &phases, &steps)?;
+        let (worst, error) =
+            errors
+                .iter()
+                .enumerate()
+                .fold(
+                    (0, f64::NEG_INFINITY),
+                    |acc, (i, &e)| {
+                        if e > acc.1 { (i, e) } else { acc }
+                    },
+                );
+        if error <= tolerance {
+            break;
+        }
+        bad_steps.push(steps.remove(worst));
+    }
+    bad_steps.sort();
+
+    // fit the good steps of each pixel
+    let p = fit_matrix(&phases, &steps).ok_or(ImgalError::InvalidArrayGeneric {
+        msg: "Invalid phase steps, the phase angles must not be degenerate.",
+    })?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut dc_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(data.lanes(Axis(a)))
+        .and(&mut g_arr)
+        .and(&mut s_arr)
+        .and(&mut dc_arr)
+        .par_for_each(|ln, g, s, d| {
+            let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let [dc, b, c] = apply_fit(&p, &values, &steps);
+            *g = b / dc;
+            *s = c / dc;
+            *d = dc;
+        });
+
+    // stack G and S arrays, (row, col, ch)
+    Ok((
+        stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap(),
+        dc_arr,
+        bad_steps,
+    ))
+}
+
+/// Compute the relative leave-one-out prediction error of each phase step of
+/// a subset of steps.
+fn leave_one_out_errors<T>(
+    data: ArrayView3<T>,
+    axis: usize,
+    phases: &[f64],
+    steps: &[usize],
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut errors = Vec::with_capacity(steps.len());
+    for (i, &step) in steps.iter().enumerate() {
+        let others: Vec<usize> = steps
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &s)| s)
+            .collect();
+        let p = fit_matrix(phases, &others).ok_or(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid phase steps, the phase angles must not be degenerate.",
+        })?;
+        let (cos, sin) = (phases[step].cos(), phases[step].sin());
+        let (residual, total) =
+            data.lanes(Axis(axis))
+                .into_iter()
+                .fold((0.0, 0.0), |(residual, total), ln| {
+                    let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+                    let [dc, b, c] = apply_fit(&p, &values, &others);
+                    let predicted = dc + b * cos + c * sin;
+                    (residual + (values[step] - predicted).abs(), total + dc)
+                });
+        errors.push(residual / total);
+    }
+
+    Ok(errors)
+}
+
+/// Compute the least squares matrix (XᵀX)⁻¹Xᵀ of a subset of phase steps,
+/// where each row of X is [1, cos(φₖ), sin(φₖ)].
+fn fit_matrix(phases: &[f64], steps: &[usize]) -> Option<[Vec<f64>; 3]> {
+    let rows: Vec<[f64; 3]> = steps
+        .iter()
+        .map(|&i| [1.0, phases[i].cos(), phases[i].sin()])
+        .collect();
+    let mut m = [[0.0; 3]; 3];
+    for r in &rows {
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] += r[i] * r[j];
+            }
+        }
+    }
+
+    // invert XᵀX with its adjugate
+    let cof = |i: usize, j: usize| {
+        let (i0, i1) = ((i + 1) % 3, (i + 2) % 3);
+        let (j0, j1) = ((j + 1) % 3, (j + 2) % 3);
+        m[i0][j0] * m[i1][j1] - m[i0][j1] * m[i1][j0]
+    };
+    let det = m[0][0] * cof(0, 0) + m[0][1] * cof(0, 1) + m[0][2] * cof(0, 2);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv: [[f64; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| cof(j, i) / det));
+
+    Some(std::array::from_fn(|i| {
+        rows.iter()
+            .map(|r| (0..3).map(|j| inv[i][j] * r[j]).sum())
+            .collect()
+    }))
+}
+
+/// Apply a least squares matrix to the values of a subset of phase steps of a
+/// pixel, returning the fitted [a, b, c] coefficients.
+fn apply_fit(p: &[Vec<f64>; 3], values: &[f64], steps: &[usize]) -> [f64; 3] {
+    std::array::from_fn(|i| p[i].iter().zip(steps).map(|(w, &j)| w * values[j]).sum())
+}
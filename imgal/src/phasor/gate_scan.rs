@@ -0,0 +1,224 @@
+use ndarray::{Array1, Array2, Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::fitting::linalg::solve;
+use crate::traits::numeric::ToFloat64;
+
+/// Reconstruct a 1-dimensional decay curve from a gate-scan acquisition.
+///
+/// # Description
+///
+/// Gated detectors (_e.g._ gated ICCD or SPAD cameras) integrate the
+/// fluorescence decay within a gate of width "w" opened at a series of delays
+/// "dⱼ". With overlapping gates, each gate value is the sum of the decay over
+/// several time bins:
+///
+/// ```text
+/// Gⱼ = Σᵢ Aⱼᵢ I(tᵢ)
+/// ```
+///
+/// Where "Aⱼᵢ" is the fraction of time bin "i" covered by gate "j", with gates
+/// wrapping around the period. This function deconvolves the gate overlap by
+/// regularized (ridge) least squares:
+///
+/// ```text
+/// I = (AᵀA + λ s 1)⁻¹ Aᵀ G
+/// ```
+///
+/// Where "s" is the mean diagonal value of AᵀA, so that `regularization` is
+/// relative to the gate overlap. The decay is reconstructed on `bins` time
+/// bins of width period / `bins` and can be used with the phasor functions of
+/// [`crate::phasor::time_domain`] with the same period.
+///
+/// # Arguments
+///
+/// * `data`: The gate values, one per delay.
+/// * `delays`: The gate opening delays, in the unit of `period`.
+/// * `width`: The gate width, in the unit of `period`.
+/// * `period`: The period (_i.e._ time interval).
+/// * `bins`: The number of time bins of the reconstructed decay, default = the
+///    number of gates.
+/// * `regularization`: The relative ridge regularization "λ", default = 1e-3.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The reconstructed decay curve, in counts per time bin.
+/// * `Err(ImgalError)`: If `data` and `delays` lengths do not match. If the
+///    gate or reconstruction parameters are invalid. If the gates can not be
+///    deconvolved (_i.e._ a singular system without regularization).
+pub fn decay<T>(
+    data: &[T],
+    delays: &[f64],
+    width: f64,
+    period: f64,
+    bins: Option<usize>,
+    regularization: Option<f64>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    if data.len() != delays.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.len(),
+            b_arr_len: delays.len(),
+        });
+    }
+    let r = reconstruction_matrix(delays, width, period, bins, regularization)?;
+
+    Ok(r.rows()
+        .into_iter()
+        .map(|row| row.iter().zip(data).map(|(w, v)| w * v.to_f64()).sum())
+        .collect())
+}
+
+/// Reconstruct a 3-dimensional decay image from a gate-scan image stack.
+///
+/// # Description
+///
+/// This function reconstructs each pixel's decay from its gate values, see
+/// [`decay`]. The deconvolution matrix is computed once and shared by all
+/// pixels.
+///
+/// # Arguments
+///
+/// * `data`: The gate-scan image stack.
+/// * `delays`: The gate opening delays, in the unit of `period`.
+/// * `width`: The gate width, in the unit of `period`.
+/// * `period`: The period (_i.e._ time interval).
+/// * `bins`: The number of time bins of the reconstructed decays, default =
+///    the number of gates.
+/// * `regularization`: The relative ridge regularization "λ", default = 1e-3.
+/// * `axis`: The gate axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The reconstructed decay image, with the decays along
+///    the last axis (_i.e._ (row, col, bin)).
+/// * `Err(ImgalError)`: If axis is >= 3. If the gate axis length and `delays`
+///    length do not match. If the gate or reconstruction parameters are
+///    invalid. If the gates can not be deconvolved.
+pub fn image<T>(
+    data: ArrayView3<T>,
+    delays: &[f64],
+    width: f64,
+    period: f64,
+    bins: Option<usize>,
+    regularization: Option<f64>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if data.len_of(Axis(a)) != delays.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.len_of(Axis(a)),
+            b_arr_len: delays.len(),
+        });
+    }
+    let r = reconstruction_matrix(delays, width, period, bins, regularization)?;
+
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut output = Array3::<f64>::zeros((shape[0], shape[1], r.nrows()));
+    Zip::from(output.lanes_mut(Axis(2)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut o, ln| {
+            let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            o.iter_mut().zip(r.rows()).for_each(|(o, row)| {
+                *o = row.iter().zip(&values).map(|(w, v)| w * v).sum();
+            });
+        });
+
+    Ok(output)
+}
+
+/// Compute the (bins, gates) deconvolution matrix (AᵀA + λ s 1)⁻¹ Aᵀ of a
+/// gate scan.
+fn reconstruction_matrix(
+    delays: &[f64],
+    width: f64,
+    period: f64,
+    bins: Option<usize>,
+    regularization: Option<f64>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(delays.len());
+    let lambda = regularization.unwrap_or(1e-3);
+
+    // check if parameters are valid
+    if delays.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the gate delays must not be empty.",
+        });
+    }
+    if bins == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "bins",
+            value: 0,
+        });
+    }
+    if period.is_nan() || period <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "period",
+            value: period,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if width.is_nan() || width <= 0.0 || width > period {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "width",
+            value: width,
+            min: 0.0,
+            max: period,
+        });
+    }
+    if lambda.is_nan() || lambda < 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "regularization",
+            value: lambda,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    // fraction of each time bin covered by each gate, gates wrap around the
+    // period
+    let dt = period / bins as f64;
+    let overlap = |start: f64, end: f64, i: usize| {
+        let (b0, b1) = (i as f64 * dt, (i + 1) as f64 * dt);
+        (end.min(b1) - start.max(b0)).max(0.0)
+    };
+    let gates = Array2::from_shape_fn((delays.len(), bins), |(j, i)| {
+        let start = delays[j].rem_euclid(period);
+        let end = start + width;
+        (overlap(start, end, i) + overlap(start - period, end - period, i)) / dt
+    });
+
+    // regularized normal equations
+    let at = gates.t();
+    let mut normal = at.dot(&gates);
+    let scale = normal.diag().sum() / bins as f64;
+    normal.diag_mut().mapv_inplace(|v| v + lambda * scale);
+    // solve the normal equations for each gate column of Aᵀ
+    let normal: Vec<Vec<f64>> = normal.rows().into_iter().map(|r| r.to_vec()).collect();
+    let mut recon = Array2::<f64>::zeros((bins, delays.len()));
+    for (j, col) in at.columns().into_iter().enumerate() {
+        let x = solve(normal.clone(), col.to_vec()).ok_or(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid gate scan, the gates can not be deconvolved, increase the regularization.",
+        })?;
+        recon.column_mut(j).assign(&Array1::from(x));
+    }
+
+    Ok(recon)
+}
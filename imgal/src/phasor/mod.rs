@@ -1,4 +1,15 @@
-//! Phasor compute, calibration, and plot functions.
+//! Phasor compute (time and frequency domain, multi-harmonic, batch), gate-scan
+//! decay reconstruction, analysis, calibration, denoise, plot, render,
+//! trajectory, and unmixing functions.
+pub mod analysis;
+pub mod batch;
 pub mod calibration;
+pub mod denoise;
+pub mod frequency_domain;
+pub mod gate_scan;
+pub mod multi_harmonic;
 pub mod plot;
+pub mod render;
 pub mod time_domain;
+pub mod trajectory;
+pub mod unmixing;
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 use std::f64;
 
-use ndarray::{Array2, ArrayView3, Axis, Zip};
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
 
 use crate::error::ImgalError;
 
@@ -57,6 +57,117 @@ pub fn phase(g: f64, s: f64) -> f64 {
     s.atan2(g)
 }
 
+/// Compute the apparent phase and modulation lifetimes of a 3-dimensional
+/// phasor image.
+///
+/// # Description
+///
+/// This function back-projects the (G, S) coordinates of each pixel to the
+/// apparent phase (τφ) and modulation (τM) lifetimes:
+///
+/// ```text
+/// τφ = S / (ωG)
+/// τM = √(1 / (G² + S²) - 1) / ω
+/// ```
+///
+/// Both lifetimes are equal for monoexponential decays. Pixels with NaN
+/// coordinates remain NaN.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `omega`: The angular frequency.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: A 3-dimensional array with the apparent lifetimes,
+///    where τφ and τM are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If the length of the channel axis is
+///    not 2.
+pub fn apparent_lifetimes(
+    data: ArrayView3<f64>,
+    omega: f64,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError> {
+    // check if axis parameter is valid
+    let a = axis.unwrap_or(2);
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    check_channel_axis(data.shape(), a)?;
+
+    // allocate new array of the same shape for the lifetime data
+    let mut tau_data = Array3::<f64>::zeros(data.dim());
+    let src_lanes = data.lanes(Axis(a));
+    let dst_lanes = tau_data.lanes_mut(Axis(a));
+    Zip::from(src_lanes)
+        .and(dst_lanes)
+        .par_for_each(|s_ln, mut d_ln| {
+            let (g, s) = (s_ln[0], s_ln[1]);
+            d_ln[0] = s / (omega * g);
+            d_ln[1] = (1.0 / (g * g + s * s) - 1.0).sqrt() / omega;
+        });
+
+    Ok(tau_data)
+}
+
+/// Compute the G and S coordinates of a 3-dimensional polar phasor image.
+///
+/// # Description
+///
+/// This function is the inverse of [`to_polar`]. It converts a 3-dimensional
+/// polar phasor image of phase (φ) and modulation (M) values back into G and S
+/// coordinates using:
+///
+/// ```text
+/// G = M * cos(φ)
+/// S = M * sin(φ)
+/// ```
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional polar phasor image, where phase and modulation
+///    are channels 0 and 1 respectively.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: A 3-dimensional array with the G and S coordinates,
+///    where G and S are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If the length of the channel axis is
+///    not 2.
+pub fn from_polar(data: ArrayView3<f64>, axis: Option<usize>) -> Result<Array3<f64>, ImgalError> {
+    // check if axis parameter is valid
+    let a = axis.unwrap_or(2);
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    check_channel_axis(data.shape(), a)?;
+
+    // allocate new array of the same shape for the G/S data
+    let mut gs_data = Array3::<f64>::zeros(data.dim());
+    let src_lanes = data.lanes(Axis(a));
+    let dst_lanes = gs_data.lanes_mut(Axis(a));
+    Zip::from(src_lanes)
+        .and(dst_lanes)
+        .par_for_each(|s_ln, mut d_ln| {
+            d_ln[0] = s_ln[1] * s_ln[0].cos();
+            d_ln[1] = s_ln[1] * s_ln[0].sin();
+        });
+
+    Ok(gs_data)
+}
+
 /// Compute the G and S coordinates for a monoexponential decay.
 ///
 /// # Description
@@ -88,6 +199,114 @@ pub fn monoexponential_coordinates(tau: f64, omega: f64) -> (f64, f64) {
     (g, s)
 }
 
+/// Compute the G and S coordinates of monoexponential decays on the universal
+/// semicircle.
+///
+/// # Description
+///
+/// This function computes the monoexponential (G, S) coordinates (see
+/// [`monoexponential_coordinates`]) for each lifetime in `taus`. The returned
+/// points are reference markers on the universal semicircle, useful as plot
+/// layers (_e.g._ lifetime ticks).
+///
+/// # Arguments
+///
+/// * `taus`: The lifetimes of the monoexponential decays.
+/// * `omega`: The angular frequency.
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Vec<f64>)`: The G and S coordinates of each lifetime in `taus`.
+pub fn semicircle_points(taus: &[f64], omega: f64) -> (Vec<f64>, Vec<f64>) {
+    taus.iter()
+        .map(|&t| monoexponential_coordinates(t, omega))
+        .unzip()
+}
+
+/// Compute the phase and modulation polar image of a 3-dimensional phasor image.
+///
+/// # Description
+///
+/// This function converts a 3-dimensional phasor image of G and S coordinates
+/// into a polar image of phase (φ) and modulation (M) values (see [`phase`]
+/// and [`modulation`]):
+///
+/// ```text
+/// φ = tan⁻¹(S / G)
+/// M = √(G² + S²)
+/// ```
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: A 3-dimensional array with the polar phasor values,
+///    where phase and modulation are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If the length of the channel axis is
+///    not 2.
+pub fn to_polar(data: ArrayView3<f64>, axis: Option<usize>) -> Result<Array3<f64>, ImgalError> {
+    // check if axis parameter is valid
+    let a = axis.unwrap_or(2);
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    check_channel_axis(data.shape(), a)?;
+
+    // allocate new array of the same shape for the polar data
+    let mut polar_data = Array3::<f64>::zeros(data.dim());
+    let src_lanes = data.lanes(Axis(a));
+    let dst_lanes = polar_data.lanes_mut(Axis(a));
+    Zip::from(src_lanes)
+        .and(dst_lanes)
+        .par_for_each(|s_ln, mut d_ln| {
+            d_ln[0] = phase(s_ln[0], s_ln[1]);
+            d_ln[1] = modulation(s_ln[0], s_ln[1]);
+        });
+
+    Ok(polar_data)
+}
+
+/// Compute the G and S coordinates of the universal semicircle.
+///
+/// # Description
+///
+/// This function samples the universal semicircle, the locus of all
+/// monoexponential decays in phasor space, with evenly spaced G coordinates:
+///
+/// ```text
+/// S = √(G - G²), 0 ≤ G ≤ 1
+/// ```
+///
+/// # Arguments
+///
+/// * `samples`: The number of points along the semicircle, default = 100.
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Vec<f64>)`: The G and S coordinates of the semicircle.
+pub fn universal_semicircle(samples: Option<usize>) -> (Vec<f64>, Vec<f64>) {
+    let n = samples.unwrap_or(100);
+    if n < 2 {
+        return (vec![0.0; n], vec![0.0; n]);
+    }
+    let step = 1.0 / (n - 1) as f64;
+    (0..n)
+        .map(|i| {
+            let g = i as f64 * step;
+            (g, (g - g * g).max(0.0).sqrt())
+        })
+        .unzip()
+}
+
 /// Map G and S coordinates back to the input phasor array as a boolean mask.
 ///
 /// # Description
@@ -163,3 +382,17 @@ pub fn map_mask(
     // return output
     Ok(map_arr)
 }
+
+/// Check that the channel axis of a phasor image holds exactly two channels.
+fn check_channel_axis(shape: &[usize], axis: usize) -> Result<(), ImgalError> {
+    if shape[axis] != 2 {
+        let mut exp_shape = shape.to_vec();
+        exp_shape[axis] = 2;
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: exp_shape,
+            shape_b: shape.to_vec(),
+        });
+    }
+
+    Ok(())
+}
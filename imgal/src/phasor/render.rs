@@ -0,0 +1,116 @@
+use ndarray::{Array3, ArrayView2, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::statistics::max;
+
+// default component colors: red, green and blue
+const DEFAULT_COLORS: [[u8; 3]; 3] = [[255, 0, 0], [0, 255, 0], [0, 0, 255]];
+
+/// Render two or three component fraction images into an RGB image.
+///
+/// # Description
+///
+/// This function maps component fraction images (_e.g._ from phasor unmixing)
+/// into a single 8-bit RGB image. Each component is assigned a color and each
+/// pixel's color is the fraction weighted sum of the component colors:
+///
+/// ```text
+/// RGB = I * Σ fᵢ * Cᵢ
+/// ```
+///
+/// Where "fᵢ" is the fraction (clamped between 0.0 and 1.0) and "Cᵢ" the color
+/// of component "i" and "I" is the optional normalized intensity (_e.g._ photon
+/// count) modulation.
+///
+/// # Arguments
+///
+/// * `fractions`: Two or three 2-dimensional component fraction images. All
+///    fraction images must have the same shape.
+/// * `colors`: The RGB color of each component. Must be the same length as
+///    `fractions`. If `None`, red, green and blue are used for the first,
+///    second and third components respectively.
+/// * `intensity`: An optional 2-dimensional intensity image (_e.g._ photon
+///    counts) used to modulate the brightness of each pixel. The intensity is
+///    normalized by its maximum value. Must have the same shape as the fraction
+///    images.
+///
+/// # Returns
+///
+/// * `Ok(Array3<u8>)`: The 3-dimensional (row, col, ch) RGB image.
+/// * `Err(ImgalError)`: If there are less than two or more than three fraction
+///    images. If the number of colors does not match the number of fraction
+///    images. If the fraction or intensity image shapes do not match.
+pub fn fraction_rgb(
+    fractions: &[ArrayView2<f64>],
+    colors: Option<&[[u8; 3]]>,
+    intensity: Option<ArrayView2<f64>>,
+) -> Result<Array3<u8>, ImgalError> {
+    // check the number of components
+    let n = fractions.len();
+    if n < 2 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "fractions",
+            value: 2,
+        });
+    }
+    if n > 3 {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "fractions",
+            value: 3,
+        });
+    }
+
+    // check the number of colors matches the number of components
+    let colors = colors.unwrap_or(&DEFAULT_COLORS[..n]);
+    if colors.len() != n {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: n,
+            b_arr_len: colors.len(),
+        });
+    }
+
+    // check all fraction and intensity images have the same shape
+    let shape = fractions[0].dim();
+    for f in fractions.iter().skip(1) {
+        if f.dim() != shape {
+            return Err(ImgalError::MismatchedArrayShapes {
+                shape_a: vec![shape.0, shape.1],
+                shape_b: f.shape().to_vec(),
+            });
+        }
+    }
+    if let Some(inten) = intensity
+        && inten.dim() != shape
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![shape.0, shape.1],
+            shape_b: inten.shape().to_vec(),
+        });
+    }
+
+    // find the intensity normalization factor
+    let inten_max = intensity.map(|i| max(i.into_dyn())).unwrap_or(1.0);
+
+    // blend the component colors per pixel
+    let mut rgb = Array3::<u8>::zeros((shape.0, shape.1, 3));
+    Zip::indexed(rgb.lanes_mut(Axis(2))).par_for_each(|(row, col), mut ln| {
+        let scale = match intensity {
+            Some(inten) if inten_max > 0.0 => (inten[[row, col]] / inten_max).clamp(0.0, 1.0),
+            Some(_) => 0.0,
+            None => 1.0,
+        };
+        let mut acc = [0.0_f64; 3];
+        fractions.iter().zip(colors.iter()).for_each(|(f, c)| {
+            let fv = f[[row, col]];
+            let fv = if fv.is_nan() { 0.0 } else { fv.clamp(0.0, 1.0) };
+            acc.iter_mut()
+                .zip(c.iter())
+                .for_each(|(a, &cv)| *a += fv * cv as f64);
+        });
+        ln.iter_mut().zip(acc.iter()).for_each(|(v, &a)| {
+            *v = (a * scale).round().clamp(0.0, 255.0) as u8;
+        });
+    });
+
+    Ok(rgb)
+}
@@ -1,29 +1,1331 @@
 use std::f64;
 
-use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis, Zip, stack};
+use ndarray::{
+    Array2, Array3, Array4, ArrayView2, ArrayView3, ArrayView4, ArrayViewMut2, ArrayViewMut3, Axis,
+    Ix2, Zip, s, stack,
+};
 
-use crate::error::ImgalError;
+use crate::error::{ImgalError, check_axis};
 use crate::integration::midpoint;
 use crate::parameter::omega;
+use crate::statistics::{Reduction, reduce_axis};
 use crate::traits::numeric::ToFloat64;
 
+/// Spatial binning schemes for decay images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialBinning {
+    /// Non-overlapping `factor` x `factor` blocks, reducing the image size.
+    Block { factor: usize },
+    /// Sliding square kernel of (2 * `radius` + 1)² pixels.
+    Square { radius: usize },
+    /// Sliding circular kernel of the pixels within `radius` pixels.
+    Circular { radius: usize },
+}
+
+/// Photon count and histogram quality gating of phasor image pixels.
+///
+/// Pixels whose decay fails a gate are set to the `fill` value instead of
+/// their phasor coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhasorGate {
+    /// The minimum total photon count of a pixel's decay. If `None`, pixels
+    /// are not gated by photon count.
+    pub min_photons: Option<f64>,
+    /// The minimum histogram quality (0.0 to 1.0) of a pixel's decay (see
+    /// [`histogram_quality`]). If `None`, pixels are not gated by histogram
+    /// quality.
+    pub min_quality: Option<f64>,
+    /// The G and S value of pixels that fail a gate, default = NaN.
+    pub fill: f64,
+}
+
+impl Default for PhasorGate {
+    fn default() -> Self {
+        PhasorGate {
+            min_photons: None,
+            min_quality: None,
+            fill: f64::NAN,
+        }
+    }
+}
+
+impl PhasorGate {
+    /// Validate the gating parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If all parameters are valid.
+    /// * `Err(ImgalError)`: If `min_photons` is negative or NaN. If
+    ///    `min_quality` is outside of 0.0 to 1.0.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        if let Some(p) = self.min_photons
+            && (p.is_nan() || p < 0.0)
+        {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "min_photons",
+                value: p,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
+        }
+        if let Some(q) = self.min_quality
+            && !(0.0..=1.0).contains(&q)
+        {
+            return Err(ImgalError::InvalidParameterValueOutsideRange {
+                param_name: "min_quality",
+                value: q,
+                min: 0.0,
+                max: 1.0,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Adaptively smooth the decays of a 3-dimensional decay image along the
+/// decay axis.
+///
+/// # Description
+///
+/// This function smooths each pixel's decay with a moving average whose width
+/// adapts to the local photon counts: each time bin after the decay peak is
+/// averaged over the smallest window of ±h bins (h ≤ `max_radius`) that holds
+/// at least `min_counts` photons. Sparse late-time bins are smoothed more than
+/// well populated bins near the peak. Bins up to the peak (_i.e._ the rising
+/// edge shaped by the IRF) are not smoothed, and windows do not extend over
+/// the peak or past the end of the decay. This improves the precision of
+/// phasor coordinates without spatial binning.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `min_counts`: The minimum photon count of a smoothing window, default =
+///    10.0.
+/// * `max_radius`: The maximum half-width of a smoothing window in time bins,
+///    default = 8.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The smoothed decay image.
+/// * `Err(ImgalError)`: If axis is >= 3. If `min_counts` is negative or NaN.
+pub fn adaptive_smooth<T>(
+    data: ArrayView3<T>,
+    min_counts: Option<f64>,
+    max_radius: Option<usize>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let min_counts = min_counts.unwrap_or(10.0);
+    let max_radius = max_radius.unwrap_or(8);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    check_axis(a, 3)?;
+    if min_counts.is_nan() || min_counts < 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "min_counts",
+            value: min_counts,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    let mut output = data.mapv(|v| v.to_f64());
+    Zip::from(output.lanes_mut(Axis(a)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut o, ln| {
+            let n = ln.len();
+            let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let peak = values
+                .iter()
+                .enumerate()
+                .fold(
+                    (0, f64::NEG_INFINITY),
+                    |acc, (i, &v)| {
+                        if v > acc.1 { (i, v) } else { acc }
+                    },
+                )
+                .0;
+
+            // cumulative sums to sum windows in O(1)
+            let mut prefix = vec![0.0; n + 1];
+            for i in 0..n {
+                prefix[i + 1] = prefix[i] + values[i];
+            }
+            for i in (peak + 1)..n {
+                for h in 0..=max_radius {
+                    let lo = i.saturating_sub(h).max(peak + 1);
+                    let hi = (i + h + 1).min(n);
+                    let sum = prefix[hi] - prefix[lo];
+                    if sum >= min_counts || h == max_radius {
+                        o[i] = sum / (hi - lo) as f64;
+                        break;
+                    }
+                }
+            }
+        });
+
+    Ok(output)
+}
+
+/// Spatially bin the decay histograms of a 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function sums the decay histograms of neighboring pixels, increasing
+/// the photon counts per decay before phasor computation or fitting of low
+/// photon data. Block binning sums non-overlapping `factor` x `factor` blocks
+/// (partial blocks at the image edges are kept) and conserves the total photon
+/// count of the image. Sliding binning (square or circular kernels) keeps the
+/// image size, each pixel being the sum of the decays within the kernel
+/// centered on it (truncated at the image edges), so each photon is counted
+/// in several pixels.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `binning`: The binning scheme.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The binned decay image, with the decay along `axis`.
+/// * `Err(ImgalError)`: If axis is >= 3. If the block `factor` is 0.
+pub fn bin_spatial<T>(
+    data: ArrayView3<T>,
+    binning: SpatialBinning,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if parameters are valid
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    if let SpatialBinning::Block { factor: 0 } = binning {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "factor",
+            value: 0,
+        });
+    }
+
+    // view the data as (row, col, t)
+    let order: [usize; 3] = match a {
+        0 => [1, 2, 0],
+        1 => [0, 2, 1],
+        _ => [0, 1, 2],
+    };
+    let view = data.permuted_axes(order);
+    let (rows, cols, n) = view.dim();
+
+    let binned = match binning {
+        SpatialBinning::Block { factor } => {
+            let mut binned =
+                Array3::<f64>::zeros((rows.div_ceil(factor), cols.div_ceil(factor), n));
+            Zip::indexed(binned.lanes_mut(Axis(2))).par_for_each(|(r, c), mut ln| {
+                for rr in (r * factor)..((r + 1) * factor).min(rows) {
+                    for cc in (c * factor)..((c + 1) * factor).min(cols) {
+                        ln.zip_mut_with(&view.slice(s![rr, cc, ..]), |b, v| *b += v.to_f64());
+                    }
+                }
+            });
+            binned
+        }
+        SpatialBinning::Square { radius } | SpatialBinning::Circular { radius } => {
+            // half width of the kernel span on each row offset
+            let radius = radius as isize;
+            let spans: Vec<(isize, isize)> = (-radius..=radius)
+                .map(|dr| match binning {
+                    SpatialBinning::Circular { .. } => {
+                        (dr, (((radius * radius - dr * dr) as f64).sqrt()) as isize)
+                    }
+                    _ => (dr, radius),
+                })
+                .collect();
+
+            // cumulative sums along the columns to sum row spans in O(1)
+            let mut prefix = Array3::<f64>::zeros((rows, cols + 1, n));
+            Zip::from(prefix.axis_iter_mut(Axis(0)))
+                .and(view.axis_iter(Axis(0)))
+                .par_for_each(|mut p, v| {
+                    for c in 0..cols {
+                        for t in 0..n {
+                            p[[c + 1, t]] = p[[c, t]] + v[[c, t]].to_f64();
+                        }
+                    }
+                });
+
+            let mut binned = Array3::<f64>::zeros((rows, cols, n));
+            Zip::indexed(binned.lanes_mut(Axis(2))).par_for_each(|(r, c), mut ln| {
+                for &(dr, half) in &spans {
+                    let rr = r as isize + dr;
+                    if rr < 0 || rr >= rows as isize {
+                        continue;
+                    }
+                    let c0 = (c as isize - half).max(0) as usize;
+                    let c1 = ((c as isize + half + 1) as usize).min(cols);
+                    let row = prefix.index_axis(Axis(0), rr as usize);
+                    for t in 0..n {
+                        ln[t] += row[[c1, t]] - row[[c0, t]];
+                    }
+                }
+            });
+            binned
+        }
+    };
+
+    // restore the decay axis position
+    let inverse: [usize; 3] = match a {
+        0 => [2, 0, 1],
+        1 => [0, 2, 1],
+        _ => [0, 1, 2],
+    };
+
+    Ok(binned
+        .permuted_axes(inverse)
+        .as_standard_layout()
+        .into_owned())
+}
+
+/// Compute the real and imaginary (G, S) coordinates of many 1-dimensional
+/// decay curves.
+///
+/// # Description
+///
+/// This function computes the phasor coordinates of each curve of a
+/// (n_curves, n_bins) matrix in parallel (_e.g._ the decays of thousands of
+/// ROIs or flow cytometry events), like [`real`] and [`imaginary`] for each
+/// curve, while loading the waveform tables once:
+///
+/// ```text
+/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// ```
+///
+/// Curves without photons are NaN.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 2-dimensional matrix of decay curves.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples along `axis`).
+/// * `axis`: The decay or lifetime axis, default = 1.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The (n_curves, 2) coordinates, where G and S are
+///    columns 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 2. If the harmonic is 0 or aliased
+///    (_i.e._ above the Nyquist limit).
+pub fn curves<T>(
+    data: ArrayView2<T>,
+    period: f64,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+    let a = axis.unwrap_or(1);
+
+    // check if parameters are valid
+    check_axis(a, 2)?;
+    let n = data.len_of(Axis(a));
+    validate_harmonic(h, n)?;
+
+    // compute the curves as a (n_curves, 1, n_bins) image
+    let view = if a == 1 { data } else { data.reversed_axes() };
+    let waveform = Waveform::new(period, h as f64, n);
+    let (gs, _) = compute_image_waveform(
+        view.insert_axis(Axis(1)),
+        &waveform,
+        None,
+        2,
+        &PhasorGate::default(),
+    );
+
+    Ok(gs.index_axis_move(Axis(1), 0))
+}
+
 /// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
 /// image.
 ///
 /// # Description
 ///
-/// The real (G) and imaginary (S) components are calculated using the normalized
-/// sine and cosine Fourier transforms:
+/// The real (G) and imaginary (S) components are calculated using the normalized
+/// sine and cosine Fourier transforms:
+///
+/// ```text
+/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// ```
+///
+/// Pixels can optionally be gated by their total photon count and histogram
+/// quality (see [`PhasorGate`]). Pixels that fail either gate are set to the
+/// gate's fill value, NaN by default.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples along `axis`).
+/// * `axis`: The decay or lifetime axis, default = 2.
+/// * `gate`: The photon count and histogram quality gates, default =
+///    `PhasorGate::default()` (_i.e._ no gating).
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (ch, row, col) image,
+///    where G and S are indexed at 0 and 1 respectively on the _channel_ axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If the harmonic is 0 or aliased
+///    (_i.e._ above the Nyquist limit). If the gate parameters are invalid.
+pub fn image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    gate: Option<PhasorGate>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    gated_image(data, period, mask, harmonic, axis, gate).map(|(gs, _)| gs)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image with photon count and histogram quality gating.
+///
+/// # Description
+///
+/// This function computes the phasor image like [`image`] while gating each
+/// pixel by its total photon count and histogram quality (see
+/// [`PhasorGate`]) in the same pass. Pixels that fail either gate are set to
+/// the gate's `fill` value and marked as invalid in the returned validity
+/// mask.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0 and are invalid.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples along `axis`).
+/// * `axis`: The decay or lifetime axis, default = 2.
+/// * `gate`: The photon count and histogram quality gates, default =
+///    `PhasorGate::default()` (_i.e._ no gating).
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array2<bool>))`: The real and imaginary coordinates as
+///    a 3D (row, col, ch) image, where G and S are indexed at 0 and 1
+///    respectively on the _channel_ axis, and the 2-dimensional validity mask
+///    where `true` pixels passed all gates.
+/// * `Err(ImgalError)`: If axis is >= 3. If the harmonic is 0 or aliased
+///    (_i.e._ above the Nyquist limit). If the gate parameters are invalid.
+pub fn gated_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    gate: Option<PhasorGate>,
+) -> Result<(Array3<f64>, Array2<bool>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis parameter is valid
+    check_axis(a, 3)?;
+
+    // drop specified axis and create a new G/S output array
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut gs_arr = Array3::<f64>::zeros((shape[0], shape[1], 2));
+    let valid = gated_image_into(
+        data,
+        period,
+        mask,
+        harmonic,
+        Some(a),
+        gate,
+        gs_arr.view_mut(),
+    )?;
+
+    Ok((gs_arr, valid))
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image into an existing output array.
+///
+/// # Description
+///
+/// This function computes the phasor image like [`image`] and writes the
+/// coordinates into `out` instead of allocating a new array, so an output
+/// buffer can be reused across images of the same shape.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples along `axis`).
+/// * `axis`: The decay or lifetime axis, default = 2.
+/// * `gate`: The photon count and histogram quality gates, default =
+///    `PhasorGate::default()` (_i.e._ no gating).
+/// * `out`: The 3D (row, col, ch) output array, where G and S are written to
+///    0 and 1 respectively on the _channel_ axis.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the coordinates were written to `out`.
+/// * `Err(ImgalError)`: If axis is >= 3. If the harmonic is 0 or aliased
+///    (_i.e._ above the Nyquist limit). If the gate parameters are invalid. If
+///    the shape of `out` does not match the image shape with 2 channels.
+pub fn image_into<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    gate: Option<PhasorGate>,
+    out: ArrayViewMut3<f64>,
+) -> Result<(), ImgalError>
+where
+    T: ToFloat64,
+{
+    gated_image_into(data, period, mask, harmonic, axis, gate, out).map(|_| ())
+}
+
+/// Compute the gated real and imaginary (G, S) coordinates of a 3-dimensional
+/// decay image into an existing output array.
+///
+/// # Description
+///
+/// This function computes the gated phasor image like [`gated_image`] and
+/// writes the coordinates into `out` instead of allocating a new array.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0 and are invalid.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples along `axis`).
+/// * `axis`: The decay or lifetime axis, default = 2.
+/// * `gate`: The photon count and histogram quality gates, default =
+///    `PhasorGate::default()` (_i.e._ no gating).
+/// * `out`: The 3D (row, col, ch) output array, where G and S are written to
+///    0 and 1 respectively on the _channel_ axis.
+///
+/// # Returns
+///
+/// * `Ok(Array2<bool>)`: The 2-dimensional validity mask, where `true` pixels
+///    passed all gates.
+/// * `Err(ImgalError)`: If axis is >= 3. If the harmonic is 0 or aliased
+///    (_i.e._ above the Nyquist limit). If the gate parameters are invalid. If
+///    the shape of `out` does not match the image shape with 2 channels.
+pub fn gated_image_into<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    gate: Option<PhasorGate>,
+    out: ArrayViewMut3<f64>,
+) -> Result<Array2<bool>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+    let a = axis.unwrap_or(2);
+    let gate = gate.unwrap_or_default();
+
+    // check if parameters are valid
+    check_axis(a, 3)?;
+    gate.validate()?;
+    let mut shape = data.shape().to_vec();
+    let n = shape.remove(a);
+    if out.shape() != [shape[0], shape[1], 2] {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![shape[0], shape[1], 2],
+            shape_b: out.shape().to_vec(),
+        });
+    }
+
+    // check if the harmonic is valid for the number of samples
+    validate_harmonic(h, n)?;
+
+    let waveform = Waveform::new(period, h as f64, n);
+    let mut valid = Array2::<bool>::default((shape[0], shape[1]));
+    compute_image_waveform_into(data, &waveform, mask, a, &gate, out, valid.view_mut());
+
+    Ok(valid)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image at multiple harmonics.
+///
+/// # Description
+///
+/// This function computes the phasor image like [`image`] for each harmonic
+/// "n" in `harmonics` into a single stacked image:
+///
+/// ```text
+/// Gₙ = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// Sₙ = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonics`: The harmonic values. Each harmonic must be greater than 0
+///    and can not exceed the Nyquist limit (_i.e._ half the number of samples
+///    along `axis`).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array4<f64>)`: The real and imaginary coordinates as a 4D
+///    (harmonic, row, col, ch) image, where the _harmonic_ axis follows the
+///    order of `harmonics` and G and S are indexed at 0 and 1 respectively on
+///    the _channel_ axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If `harmonics` is empty. If a
+///    harmonic is 0 or aliased (_i.e._ above the Nyquist limit).
+pub fn image_multiharmonic<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    harmonics: &[u32],
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array4<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    check_axis(a, 3)?;
+    if harmonics.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the harmonics must not be empty.",
+        });
+    }
+    let n = data.len_of(Axis(a));
+    for &h in harmonics {
+        validate_harmonic(h, n)?;
+    }
+
+    // compute each harmonic into its slice of the (harmonic, row, col, ch) output
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut gs_arr = Array4::<f64>::zeros((harmonics.len(), shape[0], shape[1], 2));
+    let mut valid_arr = Array2::<bool>::default((shape[0], shape[1]));
+    let gate = PhasorGate::default();
+    gs_arr
+        .outer_iter_mut()
+        .zip(harmonics)
+        .for_each(|(out, &h)| {
+            let waveform = Waveform::new(period, h as f64, n);
+            compute_image_waveform_into(data, &waveform, mask, a, &gate, out, valid_arr.view_mut());
+        });
+
+    Ok(gs_arr)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image sampled at non-uniform time points.
+///
+/// # Description
+///
+/// This function computes the phasor image like [`image`] for decays whose
+/// time bins are not uniform (_e.g._ TCSPC hardware with nonlinear time
+/// bins). The waveforms are evaluated at the given time points and each bin
+/// is weighted by its width, from its time point to the next one (or to
+/// `period` for the last bin):
+///
+/// ```text
+/// G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+/// S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+/// ```
+///
+/// With uniform time points, `tᵢ = i * period / n`, the result equals
+/// [`image`].
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `time_points`: The start time of each time bin, strictly increasing from
+///    0.0 and below `period`, with the length of the decay axis.
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed half the number of time bins.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D
+///    (row, col, ch) image, where G and S are indexed at 0 and 1 respectively
+///    on the _channel_ axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If the harmonic is 0 or aliased. If
+///    the length of `time_points` does not match the decay axis. If the time
+///    points are not strictly increasing within the period.
+pub fn image_with_time_points<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    time_points: &[f64],
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    check_axis(a, 3)?;
+    let n = data.len_of(Axis(a));
+    validate_harmonic(h, n)?;
+    if time_points.len() != n {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: n,
+            b_arr_len: time_points.len(),
+        });
+    }
+    let waveform = Waveform::from_time_points(period, h as f64, time_points)?;
+
+    Ok(compute_image_waveform(data, &waveform, mask, a, &PhasorGate::default()).0)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 4-dimensional decay
+/// volume.
+///
+/// # Description
+///
+/// This function computes the phasor coordinates of each voxel of a
+/// (z, row, col, bin) decay volume (_e.g._ a light-sheet FLIM volume), like
+/// [`image`] does for each z slice. Before phasor computation, the decays can
+/// be spatially binned within each slice (see [`bin_spatial`]) and summed over
+/// neighboring slices along z with a separate radius, so that volumes with a
+/// coarser axial than lateral sampling can be binned anisotropically.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 4-dimensional (z, row, col, bin) decay volume.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 3-dimensional (z, row, col) boolean mask, only voxels
+///    set to `true` are computed. Voxels outside of the mask are set to 0.0.
+///    The mask must match the binned volume shape.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    time bins).
+/// * `binning`: The spatial binning scheme applied within each z slice. If
+///    `None`, the slices are not binned.
+/// * `z_radius`: The number of slices summed above and below each slice
+///    (truncated at the volume edges), default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Array4<f64>)`: The real and imaginary coordinates as a 4D
+///    (z, row, col, ch) volume, where G and S are indexed at 0 and 1
+///    respectively on the _channel_ axis.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased (_i.e._ above the
+///    Nyquist limit). If the block `factor` of `binning` is 0. If the `mask`
+///    and binned volume shapes do not match.
+pub fn volume<T>(
+    data: ArrayView4<T>,
+    period: f64,
+    mask: Option<ArrayView3<bool>>,
+    harmonic: Option<u32>,
+    binning: Option<SpatialBinning>,
+    z_radius: Option<usize>,
+) -> Result<Array4<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+    let z_radius = z_radius.unwrap_or(0);
+
+    // check if the harmonic is valid for the number of samples
+    validate_harmonic(h, data.len_of(Axis(3)))?;
+
+    // bin each slice, then sum the neighboring slices along z
+    let binned: Option<Array4<f64>> = if binning.is_some() || z_radius > 0 {
+        let slices = data
+            .axis_iter(Axis(0))
+            .map(|sl| match binning {
+                Some(b) => bin_spatial(sl, b, Some(2)),
+                None => Ok(sl.mapv(|v| v.to_f64())),
+            })
+            .collect::<Result<Vec<Array3<f64>>, ImgalError>>()?;
+        let views: Vec<ArrayView3<f64>> = slices.iter().map(|sl| sl.view()).collect();
+        let stacked = stack(Axis(0), &views).unwrap();
+        if z_radius > 0 {
+            let depth = stacked.len_of(Axis(0));
+            let mut summed = Array4::<f64>::zeros(stacked.dim());
+            Zip::indexed(summed.axis_iter_mut(Axis(0))).par_for_each(|z, mut sl| {
+                let z0 = z.saturating_sub(z_radius);
+                let z1 = (z + z_radius + 1).min(depth);
+                for zz in z0..z1 {
+                    sl += &stacked.index_axis(Axis(0), zz);
+                }
+            });
+            Some(summed)
+        } else {
+            Some(stacked)
+        }
+    } else {
+        None
+    };
+
+    // check if the mask matches the (binned) volume
+    let (depth, rows, cols, _) = match &binned {
+        Some(b) => b.dim(),
+        None => data.dim(),
+    };
+    if let Some(m) = mask
+        && m.dim() != (depth, rows, cols)
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![depth, rows, cols],
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    // compute the phasor coordinates of each slice
+    let mut output = Array4::<f64>::zeros((depth, rows, cols, 2));
+    for z in 0..depth {
+        let m = mask.as_ref().map(|m| m.index_axis(Axis(0), z));
+        let (gs, _) = match &binned {
+            Some(b) => compute_image(
+                b.index_axis(Axis(0), z),
+                period,
+                m,
+                h as f64,
+                2,
+                &PhasorGate::default(),
+            ),
+            None => compute_image(
+                data.index_axis(Axis(0), z),
+                period,
+                m,
+                h as f64,
+                2,
+                &PhasorGate::default(),
+            ),
+        };
+        output.index_axis_mut(Axis(0), z).assign(&gs);
+    }
+
+    Ok(output)
+}
+
+/// Compute the histogram quality of a 1-dimensional decay curve.
+///
+/// # Description
+///
+/// This function computes the histogram quality of a decay curve as the
+/// fraction of time bins with counts greater than or equal to the bin
+/// threshold:
+///
+/// ```text
+/// Q = |{i : I(tᵢ) ≥ threshold}| / n
+/// ```
+///
+/// Sparse, low photon decays have many empty bins and a low quality value,
+/// while well populated decays have a quality value near 1.0.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 1-dimensional decay curve.
+/// * `threshold`: The minimum count of a populated time bin, default = 1.0.
+///
+/// # Returns
+///
+/// * `f64`: The histogram quality, ranging between 0.0 and 1.0. If `data` is
+///    empty 0.0 is returned.
+pub fn histogram_quality<T>(data: &[T], threshold: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    let data: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    CountThreshold {
+        threshold: threshold.unwrap_or(1.0),
+    }
+    .quality(&data)
+}
+
+/// A histogram quality measure of a 1-dimensional decay curve.
+///
+/// # Description
+///
+/// Implement this trait to gate pixels on a custom criterion with
+/// [`histogram_quality_image`]. The built-in measures are [`CountThreshold`],
+/// [`FisherInformation`] and [`SignalToBackground`], selectable at runtime
+/// with [`QualityMetric`].
+pub trait DecayQuality: Sync {
+    /// Compute the quality of a decay curve, higher values are better.
+    fn quality(&self, data: &[f64]) -> f64;
+}
+
+/// Histogram quality as the fraction of time bins with counts greater than or
+/// equal to a threshold (see [`histogram_quality`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CountThreshold {
+    pub threshold: f64,
+}
+
+impl DecayQuality for CountThreshold {
+    fn quality(&self, data: &[f64]) -> f64 {
+        if data.is_empty() {
+            return 0.0;
+        }
+
+        filled_fraction(data.iter(), data.len(), self.threshold)
+    }
+}
+
+/// Histogram quality as a Fisher information proxy of the decay shape.
+///
+/// # Description
+///
+/// The Fisher information of Poisson distributed counts with respect to a
+/// temporal shift of the decay is approximated with the finite differences
+/// of neighboring time bins:
+///
+/// ```text
+/// F = Σ 2 (I(tᵢ₊₁) - I(tᵢ))² / (I(tᵢ₊₁) + I(tᵢ))
+/// ```
+///
+/// Pairs of empty bins are skipped. The value grows with the photon count and
+/// the sharpness of the decay, a flat or empty decay has a value of 0.0.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FisherInformation;
+
+impl DecayQuality for FisherInformation {
+    fn quality(&self, data: &[f64]) -> f64 {
+        data.windows(2)
+            .filter(|w| w[0] + w[1] > 0.0)
+            .map(|w| 2.0 * (w[1] - w[0]).powi(2) / (w[1] + w[0]))
+            .sum()
+    }
+}
+
+/// Histogram quality as the signal to background photon ratio.
+///
+/// # Description
+///
+/// The background count per time bin "b" is estimated as the mean count of
+/// the lowest `fraction` of time bins (see [`estimated_background_image`]),
+/// at least one bin being used, and the ratio of signal to background photons
+/// is:
+///
+/// ```text
+/// SBR = (Σ I(tᵢ) - n b) / (n b)
+/// ```
+///
+/// A decay without background has an infinite ratio, unless it is empty
+/// (0.0).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalToBackground {
+    pub fraction: f64,
+}
+
+impl DecayQuality for SignalToBackground {
+    fn quality(&self, data: &[f64]) -> f64 {
+        let n = data.len();
+        if n == 0 {
+            return 0.0;
+        }
+        let k = ((self.fraction * n as f64).round() as usize).clamp(1, n);
+        let mut values = data.to_vec();
+        values.sort_by(|x, y| x.total_cmp(y));
+        let background = values[..k].iter().sum::<f64>() / k as f64 * n as f64;
+        let signal = values.iter().sum::<f64>() - background;
+        if background > 0.0 {
+            signal / background
+        } else if signal > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Histogram quality measures selectable at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QualityMetric {
+    /// Fraction of time bins with counts greater than or equal to a
+    /// threshold, see [`CountThreshold`].
+    CountThreshold { threshold: f64 },
+    /// Fisher information proxy of the decay shape, see
+    /// [`FisherInformation`].
+    FisherInformation,
+    /// Signal to background photon ratio with the fraction of lowest count
+    /// bins used as background, see [`SignalToBackground`].
+    SignalToBackground { fraction: f64 },
+}
+
+impl DecayQuality for QualityMetric {
+    fn quality(&self, data: &[f64]) -> f64 {
+        match *self {
+            QualityMetric::CountThreshold { threshold } => {
+                CountThreshold { threshold }.quality(data)
+            }
+            QualityMetric::FisherInformation => FisherInformation.quality(data),
+            QualityMetric::SignalToBackground { fraction } => {
+                SignalToBackground { fraction }.quality(data)
+            }
+        }
+    }
+}
+
+/// Compute the histogram quality image of a 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function computes the histogram quality of each pixel's decay with a
+/// quality measure, either one of the [`QualityMetric`] measures or a custom
+/// [`DecayQuality`] implementation. The resulting image can be thresholded to
+/// gate pixels before phasor analysis or fitting.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `metric`: The histogram quality measure.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The histogram quality of each pixel.
+/// * `Err(ImgalError)`: If axis is >= 3.
+pub fn histogram_quality_image<T, Q>(
+    data: ArrayView3<T>,
+    metric: &Q,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+    Q: DecayQuality,
+{
+    // check if parameters are valid
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut quality = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(&mut quality)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|q, ln| {
+            let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            *q = metric.quality(&values);
+        });
+
+    Ok(quality)
+}
+
+/// Compute the total photon count image of a 3-dimensional decay image.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The sum of each pixel's decay.
+/// * `Err(ImgalError)`: If axis is >= 3.
+pub fn total_counts_image<T>(
+    data: ArrayView3<T>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+
+    Ok(reduce_axis(data.into_dyn(), a, Reduction::Sum)?
+        .into_dimensionality::<Ix2>()
+        .expect("reducing a 3-dimensional array yields a 2-dimensional array"))
+}
+
+/// Compute the peak time bin image of a 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function finds the time bin index of the maximum count of each
+/// pixel's decay. A peak far from the expected IRF position flags pixels with
+/// corrupted or pile-up distorted decays. Ties resolve to the first bin.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The peak time bin index of each pixel.
+/// * `Err(ImgalError)`: If axis is >= 3.
+pub fn peak_bin_image<T>(
+    data: ArrayView3<T>,
+    axis: Option<usize>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut peaks = Array2::<usize>::zeros((shape[0], shape[1]));
+    Zip::from(&mut peaks)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|p, ln| {
+            *p = ln
+                .iter()
+                .enumerate()
+                .fold((0, f64::NEG_INFINITY), |acc, (i, v)| {
+                    let v = v.to_f64();
+                    if v > acc.1 { (i, v) } else { acc }
+                })
+                .0;
+        });
+
+    Ok(peaks)
+}
+
+/// Estimate the background count per time bin of a 3-dimensional decay image.
+///
+/// # Description
+///
+/// This function estimates the constant background (_e.g._ dark counts and
+/// ambient light) of each pixel's decay as the mean count of the lowest
+/// `fraction` of its time bins. These bins lie before the rise of the decay or
+/// at the end of its tail, where the fluorescence signal is negligible.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `fraction`: The fraction of lowest count bins averaged, default = 0.1.
+///    At least one bin is used.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The estimated background count per time bin of each
+///    pixel.
+/// * `Err(ImgalError)`: If axis is >= 3. If `fraction` is not in (0.0, 1.0].
+pub fn estimated_background_image<T>(
+    data: ArrayView3<T>,
+    fraction: Option<f64>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let fraction = fraction.unwrap_or(0.1);
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    if fraction.is_nan() || fraction <= 0.0 || fraction > 1.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "fraction",
+            value: fraction,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    let n = data.len_of(Axis(a));
+    let k = ((fraction * n as f64).round() as usize).clamp(1, n.max(1));
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut background = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(&mut background)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|b, ln| {
+            if n == 0 {
+                return;
+            }
+            let mut values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            values.sort_by(|x, y| x.total_cmp(y));
+            *b = values[..k].iter().sum::<f64>() / k as f64;
+        });
+
+    Ok(background)
+}
+
+/// Correct the pile-up distortion of a 3-dimensional TCSPC decay image.
+///
+/// # Description
+///
+/// At high count rates, TCSPC detects at most one photon per excitation
+/// cycle, so late photons are lost when an earlier photon was detected in the
+/// same cycle and decays are distorted towards shorter lifetimes. This
+/// function applies the Coates correction to each pixel's decay:
 ///
 /// ```text
-/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
-/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// N = Σ nᵢ * f / r
+/// nᵢ' = -N ln(1 - nᵢ / (N - Σⱼ₍ⱼ₌₀..ᵢ₋₁₎ nⱼ))
 /// ```
 ///
+/// Where "nᵢ" are the counts of time bin "i", "f" is the excitation
+/// repetition rate, "r" is the detected count rate and "N" is the number of
+/// excitation cycles of the pixel. The first time bin must be the earliest
+/// after excitation. Correct the decays before phasor analysis or fitting.
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `count_rate`: The detected photon count rate while acquiring a pixel, in
+///    the unit of `repetition_rate`.
+/// * `repetition_rate`: The excitation (laser) repetition rate.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The pile-up corrected decay image.
+/// * `Err(ImgalError)`: If axis is >= 3. If `repetition_rate` is not positive.
+///    If `count_rate` is not in (0.0, `repetition_rate`).
+///
+/// # Reference
+///
+/// <https://doi.org/10.1088/0022-3735/1/8/437>
+pub fn pileup_correct<T>(
+    data: ArrayView3<T>,
+    count_rate: f64,
+    repetition_rate: f64,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut output = data.mapv(|v| v.to_f64());
+    pileup_correct_mut(output.view_mut(), count_rate, repetition_rate, axis)?;
+
+    Ok(output)
+}
+
+/// Correct the pile-up distortion of a 3-dimensional TCSPC decay image in
+/// place.
+///
+/// # Description
+///
+/// This function applies the Coates pile-up correction to each pixel's decay,
+/// see [`pileup_correct`].
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `count_rate`: The detected photon count rate while acquiring a pixel, in
+///    the unit of `repetition_rate`.
+/// * `repetition_rate`: The excitation (laser) repetition rate.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the decays were corrected.
+/// * `Err(ImgalError)`: If axis is >= 3. If `repetition_rate` is not positive.
+///    If `count_rate` is not in (0.0, `repetition_rate`).
+pub fn pileup_correct_mut(
+    mut data: ArrayViewMut3<f64>,
+    count_rate: f64,
+    repetition_rate: f64,
+    axis: Option<usize>,
+) -> Result<(), ImgalError> {
+    // check if parameters are valid
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    if repetition_rate.is_nan() || repetition_rate <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "repetition_rate",
+            value: repetition_rate,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if count_rate.is_nan() || count_rate <= 0.0 || count_rate >= repetition_rate {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "count_rate",
+            value: count_rate,
+            min: 0.0,
+            max: repetition_rate,
+        });
+    }
+
+    // the number of excitation cycles per detected photon
+    let cycles_per_count = repetition_rate / count_rate;
+    Zip::from(data.lanes_mut(Axis(a))).par_for_each(|mut ln| {
+        let cycles = ln.sum() * cycles_per_count;
+        if cycles <= 0.0 {
+            return;
+        }
+        let mut detected = 0.0;
+        ln.iter_mut().for_each(|v| {
+            let n = *v;
+            *v = -cycles * (1.0 - n / (cycles - detected)).ln();
+            detected += n;
+        });
+    });
+
+    Ok(())
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image with a floating point harmonic.
+///
+/// # Description
+///
+/// This function is the floating point harmonic variant of [`image`] and is
+/// kept for compatibility. Fractional and aliased harmonic values are _not_
+/// validated.
+///
 /// # Arguments
 ///
 /// * `data`: I(t), the decay data image.
 /// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
 /// * `harmonic`: The harmonic value, default = 1.0.
 /// * `axis`: The decay or lifetime axis, default = 2.
 ///
@@ -32,7 +1334,8 @@ use crate::traits::numeric::ToFloat64;
 /// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (ch, row, col) image,
 ///    where G and S are indexed at 0 and 1 respectively on the _channel_ axis.
 /// * `Err(ImgalError)`: If axis is >= 3.
-pub fn image<T>(
+#[deprecated(since = "0.1.1", note = "use `image` with an integer harmonic")]
+pub fn image_float_harmonic<T>(
     data: ArrayView3<T>,
     period: f64,
     mask: Option<ArrayView2<bool>>,
@@ -47,101 +1350,9 @@ where
     let a = axis.unwrap_or(2);
 
     // check if axis parameter is valid
-    if a >= 3 {
-        return Err(ImgalError::InvalidAxis {
-            axis_idx: a,
-            dim_len: 3,
-        });
-    }
-
-    // initialize phasor parameters
-    let w = omega(period);
-    let n: usize = data.len_of(Axis(a));
-    let dt: f64 = period / n as f64;
-    let h_w_dt: f64 = h * w * dt;
-
-    // initialize buffers
-    let mut w_cos_buf: Vec<f64> = Vec::with_capacity(n);
-    let mut w_sin_buf: Vec<f64> = Vec::with_capacity(n);
-
-    // drop specified axis and create new G and S output arrays with new shape
-    let mut shape = data.shape().to_vec();
-    shape.remove(a);
-    let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
-    let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    check_axis(a, 3)?;
 
-    // load the waveform buffers
-    for i in 0..n {
-        w_cos_buf.push(f64::cos(h_w_dt * (i as f64)));
-        w_sin_buf.push(f64::sin(h_w_dt * (i as f64)));
-    }
-
-    // compute phasor coordinates per lane, optionally only in mask area
-    let lanes = data.lanes(Axis(a));
-    if let Some(msk) = mask {
-        Zip::from(lanes)
-            .and(msk)
-            .and(&mut g_arr)
-            .and(&mut s_arr)
-            .par_for_each(|ln, m, g, s| {
-                if *m {
-                    let mut iv = 0.0;
-                    let mut gv = 0.0;
-                    let mut sv = 0.0;
-                    ln.iter()
-                        .zip(w_cos_buf.iter())
-                        .zip(w_sin_buf.iter())
-                        .for_each(|((v, cosv), sinv)| {
-                            // midpoint integration
-                            let vf: f64 = (*v).to_f64();
-                            iv += vf;
-                            gv += vf * cosv;
-                            sv += vf * sinv;
-                        });
-                    // midpoint integration, multiply by data point width
-                    iv *= dt;
-                    gv *= dt;
-                    sv *= dt;
-                    // normalize G/S values and write to output arrays
-                    *g = gv / iv;
-                    *s = sv / iv;
-                } else {
-                    // if false on mask, set G/S output to zero
-                    *g = 0.0;
-                    *s = 0.0;
-                }
-            });
-    } else {
-        // compute phasor coordinates per lane in the entire array, no mask
-        Zip::from(&mut g_arr)
-            .and(&mut s_arr)
-            .and(lanes)
-            .par_for_each(|g, s, ln| {
-                let mut iv = 0.0;
-                let mut gv = 0.0;
-                let mut sv = 0.0;
-                ln.iter()
-                    .zip(w_cos_buf.iter())
-                    .zip(w_sin_buf.iter())
-                    .for_each(|((v, cosv), sinv)| {
-                        // midpoint integration
-                        let vf: f64 = (*v).to_f64();
-                        iv += vf;
-                        gv += vf * cosv;
-                        sv += vf * sinv;
-                    });
-                // midpoint integration, multiply by data point width
-                iv *= dt;
-                gv *= dt;
-                sv *= dt;
-                // normalize G/S values and write to output arrays
-                *g = gv / iv;
-                *s = sv / iv;
-            });
-    }
-
-    // stack G and S arrays, (row, col, ch)
-    Ok(stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap())
+    Ok(compute_image(data, period, mask, h, a, &PhasorGate::default()).0)
 }
 
 /// Compute the imaginary (S) component of a 1-dimensional decay curve.
@@ -161,30 +1372,91 @@ where
 ///
 /// * `data`: I(t), the 1-dimensonal decay curve.
 /// * `period`: The period (_i.e._ time interval).
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples in `data`).
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The imaginary component, S.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased (_i.e._ above the
+///    Nyquist limit).
+pub fn imaginary<T>(data: &[T], period: f64, harmonic: Option<u32>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+
+    // check if the harmonic is valid for the number of samples
+    validate_harmonic(h, data.len())?;
+
+    Ok(compute_imaginary(data, period, h as f64))
+}
+
+/// Compute the imaginary (S) component of a 1-dimensional decay curve with a
+/// floating point harmonic.
+///
+/// # Description
+///
+/// This function is the floating point harmonic variant of [`imaginary`] and
+/// is kept for compatibility. Fractional and aliased harmonic values are _not_
+/// validated.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 1-dimensonal decay curve.
+/// * `period`: The period (_i.e._ time interval).
 /// * `harmonic`: The harmonic value, default = 1.0.
 ///
 /// # Returns
 ///
 /// * `f64`: The imaginary component, S.
-pub fn imaginary<T>(data: &[T], period: f64, harmonic: Option<f64>) -> f64
+#[deprecated(since = "0.1.1", note = "use `imaginary` with an integer harmonic")]
+pub fn imaginary_float_harmonic<T>(data: &[T], period: f64, harmonic: Option<f64>) -> f64
 where
     T: ToFloat64,
 {
-    // set optional parameters if needed
-    let h: f64 = harmonic.unwrap_or(1.0);
-    let w: f64 = omega(period);
+    compute_imaginary(data, period, harmonic.unwrap_or(1.0))
+}
 
-    // integrate sine transform (imaginary)
-    let n: usize = data.len();
-    let dt: f64 = period / (n as f64);
-    let h_w_dt: f64 = h * w * dt;
-    let mut buf = Vec::with_capacity(n);
-    for i in 0..n {
-        buf.push(data[i].to_f64() * f64::sin(h_w_dt * (i as f64)));
-    }
-    let i_sin_integral: f64 = midpoint(&buf, Some(dt));
-    let i_integral: f64 = midpoint(data, Some(dt));
-    i_sin_integral / i_integral
+/// Compute the imaginary (S) component of a 1-dimensional decay curve sampled
+/// at non-uniform time points.
+///
+/// # Description
+///
+/// This function computes the imaginary component like [`imaginary`], with
+/// each time bin weighted by its width (see [`image_with_time_points`]):
+///
+/// ```text
+/// S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 1-dimensonal decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `time_points`: The start time of each time bin, strictly increasing from
+///    0.0 and below `period`, with the length of `data`.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed half the number of samples in `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The imaginary component, S.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased. If the length of
+///    `time_points` does not match `data`. If the time points are not strictly
+///    increasing within the period.
+pub fn imaginary_with_time_points<T>(
+    data: &[T],
+    period: f64,
+    time_points: &[f64],
+    harmonic: Option<u32>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_time_points(data, period, time_points, harmonic).map(|(_, s)| s)
 }
 
 /// Compute the real (G) component of a 1-dimensional decay curve.
@@ -204,28 +1476,397 @@ where
 ///
 /// * `data`: I(t), the 1-dimensional decay curve.
 /// * `period`: The period, (_i.e._ time interval).
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed the Nyquist limit (_i.e._ half the number of
+///    samples in `data`).
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The real component, G.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased (_i.e._ above the
+///    Nyquist limit).
+pub fn real<T>(data: &[T], period: f64, harmonic: Option<u32>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1);
+
+    // check if the harmonic is valid for the number of samples
+    validate_harmonic(h, data.len())?;
+
+    Ok(compute_real(data, period, h as f64))
+}
+
+/// Compute the real (G) component of a 1-dimensional decay curve with a
+/// floating point harmonic.
+///
+/// # Description
+///
+/// This function is the floating point harmonic variant of [`real`] and is
+/// kept for compatibility. Fractional and aliased harmonic values are _not_
+/// validated.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 1-dimensional decay curve.
+/// * `period`: The period, (_i.e._ time interval).
 /// * `harmonic`: The harmonic value, default = 1.0.
 ///
 /// # Returns
 ///
 /// * `f64`: The real component, G.
-pub fn real<T>(data: &[T], period: f64, harmonic: Option<f64>) -> f64
+#[deprecated(since = "0.1.1", note = "use `real` with an integer harmonic")]
+pub fn real_float_harmonic<T>(data: &[T], period: f64, harmonic: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    compute_real(data, period, harmonic.unwrap_or(1.0))
+}
+
+/// Compute the real (G) component of a 1-dimensional decay curve sampled at
+/// non-uniform time points.
+///
+/// # Description
+///
+/// This function computes the real component like [`real`], with each time
+/// bin weighted by its width (see [`image_with_time_points`]):
+///
+/// ```text
+/// G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 1-dimensional decay curve.
+/// * `period`: The period, (_i.e._ time interval).
+/// * `time_points`: The start time of each time bin, strictly increasing from
+///    0.0 and below `period`, with the length of `data`.
+/// * `harmonic`: The harmonic value, default = 1. The harmonic must be greater
+///    than 0 and can not exceed half the number of samples in `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The real component, G.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased. If the length of
+///    `time_points` does not match `data`. If the time points are not strictly
+///    increasing within the period.
+pub fn real_with_time_points<T>(
+    data: &[T],
+    period: f64,
+    time_points: &[f64],
+    harmonic: Option<u32>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    compute_time_points(data, period, time_points, harmonic).map(|(g, _)| g)
+}
+
+/// Validate a harmonic against the Nyquist limit.
+///
+/// # Description
+///
+/// This function checks that the harmonic is resolvable for a decay curve with
+/// the given number of samples. A decay sampled at `n` points per period can
+/// only represent harmonics up to the Nyquist limit:
+///
+/// ```text
+/// 1 ≤ h ≤ n / 2
+/// ```
+///
+/// Harmonics above this limit are aliased onto lower harmonics and produce
+/// misleading phasor coordinates.
+///
+/// # Arguments
+///
+/// * `harmonic`: The harmonic value to validate.
+/// * `samples`: The number of samples in the decay curve.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the harmonic is valid.
+/// * `Err(ImgalError)`: If the harmonic is 0 or above the Nyquist limit.
+pub fn validate_harmonic(harmonic: u32, samples: usize) -> Result<(), ImgalError> {
+    let nyquist = samples / 2;
+    if harmonic == 0 || harmonic as usize > nyquist {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "harmonic",
+            value: harmonic as f64,
+            min: 1.0,
+            max: nyquist as f64,
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the G and S coordinates and the validity mask of a 3-dimensional
+/// decay image.
+fn compute_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: f64,
+    axis: usize,
+    gate: &PhasorGate,
+) -> (Array3<f64>, Array2<bool>)
+where
+    T: ToFloat64,
+{
+    let waveform = Waveform::new(period, harmonic, data.len_of(Axis(axis)));
+
+    compute_image_waveform(data, &waveform, mask, axis, gate)
+}
+
+/// The cosine and sine waveform tables of a harmonic, sampled at the time
+/// bins of a decay.
+pub(crate) struct Waveform {
+    cos: Vec<f64>,
+    sin: Vec<f64>,
+    dt: f64,
+    // the width of each time bin, if the bins are not uniform
+    weights: Option<Vec<f64>>,
+}
+
+impl Waveform {
+    /// Load the waveform tables of a harmonic for decays of `n` time bins.
+    pub(crate) fn new(period: f64, harmonic: f64, n: usize) -> Self {
+        let dt = period / n as f64;
+        let h_w_dt = harmonic * omega(period) * dt;
+        Self {
+            cos: (0..n).map(|i| f64::cos(h_w_dt * i as f64)).collect(),
+            sin: (0..n).map(|i| f64::sin(h_w_dt * i as f64)).collect(),
+            dt,
+            weights: None,
+        }
+    }
+
+    /// Load the waveform tables of a harmonic for decays sampled at
+    /// non-uniform time points, each bin extending to the next time point.
+    pub(crate) fn from_time_points(
+        period: f64,
+        harmonic: f64,
+        time_points: &[f64],
+    ) -> Result<Self, ImgalError> {
+        let increasing = time_points.windows(2).all(|w| w[0] < w[1]);
+        if !increasing
+            || time_points.first().is_some_and(|&t| t.is_nan() || t < 0.0)
+            || time_points
+                .last()
+                .is_some_and(|&t| t.is_nan() || t >= period)
+        {
+            return Err(ImgalError::InvalidArrayGeneric {
+                msg: "Invalid array, the time points must be strictly increasing within the period.",
+            });
+        }
+        let h_w = harmonic * omega(period);
+        let weights = time_points
+            .iter()
+            .zip(time_points.iter().skip(1).chain([&period]))
+            .map(|(t, next)| next - t)
+            .collect();
+
+        Ok(Self {
+            cos: time_points.iter().map(|t| f64::cos(h_w * t)).collect(),
+            sin: time_points.iter().map(|t| f64::sin(h_w * t)).collect(),
+            dt: 1.0,
+            weights: Some(weights),
+        })
+    }
+}
+
+/// Compute the gated (G, S) coordinates of a decay image with precomputed
+/// waveform tables.
+pub(crate) fn compute_image_waveform<T>(
+    data: ArrayView3<T>,
+    waveform: &Waveform,
+    mask: Option<ArrayView2<bool>>,
+    axis: usize,
+    gate: &PhasorGate,
+) -> (Array3<f64>, Array2<bool>)
+where
+    T: ToFloat64,
+{
+    // drop specified axis and create new G/S and validity output arrays
+    let mut shape = data.shape().to_vec();
+    shape.remove(axis);
+    let mut gs_arr = Array3::<f64>::zeros((shape[0], shape[1], 2));
+    let mut valid_arr = Array2::<bool>::default((shape[0], shape[1]));
+    compute_image_waveform_into(
+        data,
+        waveform,
+        mask,
+        axis,
+        gate,
+        gs_arr.view_mut(),
+        valid_arr.view_mut(),
+    );
+
+    (gs_arr, valid_arr)
+}
+
+/// Compute the gated (G, S) coordinates of a decay image with precomputed
+/// waveform tables into (row, col, ch) and validity output arrays.
+fn compute_image_waveform_into<T>(
+    data: ArrayView3<T>,
+    waveform: &Waveform,
+    mask: Option<ArrayView2<bool>>,
+    axis: usize,
+    gate: &PhasorGate,
+    mut out: ArrayViewMut3<f64>,
+    mut valid: ArrayViewMut2<bool>,
+) where
+    T: ToFloat64,
+{
+    let n = waveform.cos.len();
+    let dt = waveform.dt;
+
+    // use an "all true" mask if no mask is given
+    let full_mask: Array2<bool>;
+    let msk: ArrayView2<bool> = match mask {
+        Some(m) => m.reborrow(),
+        None => {
+            full_mask = Array2::<bool>::from_elem(valid.dim(), true);
+            full_mask.view()
+        }
+    };
+
+    // compute phasor coordinates per lane, only in mask area
+    let lanes = data.lanes(Axis(axis));
+    Zip::from(lanes)
+        .and(msk)
+        .and(out.lanes_mut(Axis(2)))
+        .and(&mut valid)
+        .par_for_each(|ln, m, mut gs, v| {
+            if !*m {
+                // if false on mask, set G/S output to zero
+                gs.fill(0.0);
+                *v = false;
+                return;
+            }
+            let mut counts = 0.0;
+            let mut iv = 0.0;
+            let mut gv = 0.0;
+            let mut sv = 0.0;
+            ln.iter()
+                .zip(waveform.cos.iter())
+                .zip(waveform.sin.iter())
+                .enumerate()
+                .for_each(|(i, ((v, cosv), sinv))| {
+                    // midpoint integration, weighted by non-uniform bin widths
+                    let vf: f64 = (*v).to_f64();
+                    let w = waveform.weights.as_ref().map_or(1.0, |w| w[i]);
+                    counts += vf;
+                    iv += vf * w;
+                    gv += vf * w * cosv;
+                    sv += vf * w * sinv;
+                });
+            // gate the pixel by total photon count and histogram quality
+            let photons_ok = gate.min_photons.is_none_or(|p| counts >= p);
+            let quality_ok = photons_ok
+                && gate
+                    .min_quality
+                    .is_none_or(|q| filled_fraction(ln.iter(), n, 1.0) >= q);
+            if !(photons_ok && quality_ok) {
+                gs.fill(gate.fill);
+                *v = false;
+                return;
+            }
+            // midpoint integration, multiply by data point width
+            iv *= dt;
+            gv *= dt;
+            sv *= dt;
+            // normalize G/S values and write to the output lane
+            gs[0] = gv / iv;
+            gs[1] = sv / iv;
+            *v = true;
+        });
+}
+
+/// Compute the fraction of time bins with counts at or above a threshold.
+fn filled_fraction<'a, T>(values: impl Iterator<Item = &'a T>, n: usize, threshold: f64) -> f64
+where
+    T: ToFloat64 + 'a,
+{
+    let filled = values.filter(|v| v.to_f64() >= threshold).count();
+    filled as f64 / n as f64
+}
+
+/// Compute the G and S components of a 1-dimensional decay curve sampled at
+/// non-uniform time points.
+fn compute_time_points<T>(
+    data: &[T],
+    period: f64,
+    time_points: &[f64],
+    harmonic: Option<u32>,
+) -> Result<(f64, f64), ImgalError>
 where
     T: ToFloat64,
 {
     // set optional parameters if needed
-    let h: f64 = harmonic.unwrap_or(1.0);
+    let h = harmonic.unwrap_or(1);
+
+    // check if parameters are valid
+    validate_harmonic(h, data.len())?;
+    if time_points.len() != data.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.len(),
+            b_arr_len: time_points.len(),
+        });
+    }
+    let waveform = Waveform::from_time_points(period, h as f64, time_points)?;
+
+    // integrate the cosine and sine transforms with the bin widths
+    let weights = waveform.weights.unwrap_or_default();
+    let (iv, gv, sv) = data
+        .iter()
+        .zip(&weights)
+        .zip(waveform.cos.iter().zip(&waveform.sin))
+        .fold((0.0, 0.0, 0.0), |(iv, gv, sv), ((v, w), (c, s))| {
+            let vw = v.to_f64() * w;
+            (iv + vw, gv + vw * c, sv + vw * s)
+        });
+
+    Ok((gv / iv, sv / iv))
+}
+
+/// Compute the S component of a 1-dimensional decay curve.
+fn compute_imaginary<T>(data: &[T], period: f64, harmonic: f64) -> f64
+where
+    T: ToFloat64,
+{
+    let w: f64 = omega(period);
+
+    // integrate sine transform (imaginary)
+    let n: usize = data.len();
+    let dt: f64 = period / (n as f64);
+    let h_w_dt: f64 = harmonic * w * dt;
+    let mut buf = Vec::with_capacity(n);
+    for i in 0..n {
+        buf.push(data[i].to_f64() * f64::sin(h_w_dt * (i as f64)));
+    }
+    let i_sin_integral: f64 = midpoint(&buf, Some(dt), None);
+    let i_integral: f64 = midpoint(data, Some(dt), None);
+    i_sin_integral / i_integral
+}
+
+/// Compute the G component of a 1-dimensional decay curve.
+fn compute_real<T>(data: &[T], period: f64, harmonic: f64) -> f64
+where
+    T: ToFloat64,
+{
     let w: f64 = omega(period);
 
     // integrate cosine transform (real)
     let n: usize = data.len();
     let dt: f64 = period / (n as f64);
-    let h_w_dt: f64 = h * w * dt;
+    let h_w_dt: f64 = harmonic * w * dt;
     let mut buf = Vec::with_capacity(n);
     for i in 0..n {
         buf.push(data[i].to_f64() * f64::cos(h_w_dt * (i as f64)));
     }
-    let i_cos_integral: f64 = midpoint(&buf, Some(dt));
-    let i_integral: f64 = midpoint(data, Some(dt));
+    let i_cos_integral: f64 = midpoint(&buf, Some(dt), None);
+    let i_integral: f64 = midpoint(data, Some(dt), None);
     i_cos_integral / i_integral
 }
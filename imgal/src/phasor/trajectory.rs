@@ -0,0 +1,241 @@
+use ndarray::{Array3, Array4, ArrayView2, ArrayView4, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::phasor::{plot, time_domain};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the real and imaginary (G, S) coordinates of each frame in a
+/// 4-dimensional time-lapse decay stack.
+///
+/// # Description
+///
+/// This function computes the phasor image (see [`time_domain::image`]) of each
+/// frame in a time-lapse decay stack with the shape (t, row, col, bin). The
+/// per-frame phasor images are stacked along the first axis.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the 4-dimensional (t, row, col, bin) time-lapse decay stack.
+/// * `period`: The period (_i.e._ time interval).
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are computed. Pixels outside of the mask are set to 0.0.
+/// * `harmonic`: The harmonic value, default = 1.
+///
+/// # Returns
+///
+/// * `Ok(Array4<f64>)`: The per-frame real and imaginary coordinates as a 4D
+///    (t, row, col, ch) image, where G and S are indexed at 0 and 1
+///    respectively on the _channel_ axis.
+/// * `Err(ImgalError)`: If the harmonic is 0 or aliased (_i.e._ above the
+///    Nyquist limit).
+pub fn image_series<T>(
+    data: ArrayView4<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<u32>,
+) -> Result<Array4<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let (t, row, col, _) = data.dim();
+    let mut gs_series = Array4::<f64>::zeros((t, row, col, 2));
+    for (frame, mut gs_frame) in data
+        .axis_iter(Axis(0))
+        .zip(gs_series.axis_iter_mut(Axis(0)))
+    {
+        let gs = time_domain::image(frame, period, mask, harmonic, Some(2), None)?;
+        gs_frame.assign(&gs);
+    }
+
+    Ok(gs_series)
+}
+
+/// Compute the mean ROI phasor trajectory of a phasor time series.
+///
+/// # Description
+///
+/// This function computes the mean G and S coordinates of the pixels within a
+/// region of interest (ROI) for each frame of a 4-dimensional (t, row, col, ch)
+/// phasor time series. NaN pixels (_e.g._ gated pixels) are ignored.
+///
+/// # Arguments
+///
+/// * `data`: The 4-dimensional (t, row, col, ch) phasor time series, where G
+///    and S are channels 0 and 1 respectively.
+/// * `roi`: The 2-dimensional boolean ROI mask, where `true` pixels are in the
+///    ROI. The ROI shape must match the (row, col) shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The mean G and S coordinates of the ROI for
+///    each frame. Frames with no valid pixels in the ROI are NaN.
+/// * `Err(ImgalError)`: If the ROI shape does not match the (row, col) shape
+///    of `data`.
+pub fn roi_trajectory(
+    data: ArrayView4<f64>,
+    roi: ArrayView2<bool>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError> {
+    // check ROI shape matches the frame shape
+    let (_, row, col, _) = data.dim();
+    if roi.dim() != (row, col) {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![row, col],
+            shape_b: roi.shape().to_vec(),
+        });
+    }
+
+    // average the G and S coordinates of each frame in the ROI
+    let (g_traj, s_traj) = data
+        .axis_iter(Axis(0))
+        .map(|frame| {
+            let mut g_sum = 0.0;
+            let mut s_sum = 0.0;
+            let mut n = 0;
+            Zip::from(frame.lanes(Axis(2))).and(roi).for_each(|ln, &r| {
+                if r && !ln[0].is_nan() && !ln[1].is_nan() {
+                    g_sum += ln[0];
+                    s_sum += ln[1];
+                    n += 1;
+                }
+            });
+            if n == 0 {
+                (f64::NAN, f64::NAN)
+            } else {
+                (g_sum / n as f64, s_sum / n as f64)
+            }
+        })
+        .unzip();
+
+    Ok((g_traj, s_traj))
+}
+
+/// Compute the displacement, direction, and velocity statistics of a phasor
+/// trajectory.
+///
+/// # Description
+///
+/// This function computes statistics of a phasor trajectory, a sequence of
+/// (G, S) coordinates over time. The net displacement is the distance between
+/// the first and last points, the direction is the angle of the net
+/// displacement vector and the path length is the sum of the distances between
+/// consecutive points:
+///
+/// ```text
+/// D = √((Gₙ - G₀)² + (Sₙ - S₀)²)
+/// θ = tan⁻¹((Sₙ - S₀) / (Gₙ - G₀))
+/// L = Σ √((Gᵢ₊₁ - Gᵢ)² + (Sᵢ₊₁ - Sᵢ)²)
+/// v = L / (n * Δt)
+/// ```
+///
+/// Where "n" is the number of steps and "Δt" is the frame interval.
+///
+/// # Arguments
+///
+/// * `g`: The G coordinates of the trajectory. Must be the same length as `s`.
+/// * `s`: The S coordinates of the trajectory. Must be the same length as `g`.
+/// * `frame_interval`: The time between frames, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64, f64, f64))`: The net displacement, direction (radians),
+///    path length and mean velocity of the trajectory. A trajectory with less
+///    than two points has statistics of 0.0.
+/// * `Err(ImgalError)`: If the `g` and `s` array lengths do not match.
+pub fn statistics(
+    g: &[f64],
+    s: &[f64],
+    frame_interval: Option<f64>,
+) -> Result<(f64, f64, f64, f64), ImgalError> {
+    // check g and s array lengths
+    let gl = g.len();
+    let sl = s.len();
+    if gl != sl {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: gl,
+            b_arr_len: sl,
+        });
+    }
+
+    Ok(trajectory_statistics(
+        g.iter().copied().zip(s.iter().copied()),
+        gl,
+        frame_interval.unwrap_or(1.0),
+    ))
+}
+
+/// Compute the per-pixel trajectory statistics of a phasor time series.
+///
+/// # Description
+///
+/// This function computes the trajectory statistics (see [`statistics`]) of
+/// each pixel in a 4-dimensional (t, row, col, ch) phasor time series.
+///
+/// # Arguments
+///
+/// * `data`: The 4-dimensional (t, row, col, ch) phasor time series, where G
+///    and S are channels 0 and 1 respectively.
+/// * `frame_interval`: The time between frames, default = 1.0.
+///
+/// # Returns
+///
+/// * `Array3<f64>`: A 3-dimensional (row, col, ch) array with the net
+///    displacement, direction, path length and mean velocity at channels 0, 1,
+///    2 and 3 respectively.
+pub fn statistics_image(data: ArrayView4<f64>, frame_interval: Option<f64>) -> Array3<f64> {
+    let dt = frame_interval.unwrap_or(1.0);
+    let (t, row, col, _) = data.dim();
+    let mut stats = Array3::<f64>::zeros((row, col, 4));
+
+    // each (row, col) position is a lane of G and S values over time
+    let g_data = data.index_axis(Axis(3), 0);
+    let s_data = data.index_axis(Axis(3), 1);
+    Zip::from(stats.lanes_mut(Axis(2)))
+        .and(g_data.lanes(Axis(0)))
+        .and(s_data.lanes(Axis(0)))
+        .par_for_each(|mut st, g_ln, s_ln| {
+            let (d, theta, l, v) =
+                trajectory_statistics(g_ln.iter().copied().zip(s_ln.iter().copied()), t, dt);
+            st[0] = d;
+            st[1] = theta;
+            st[2] = l;
+            st[3] = v;
+        });
+
+    stats
+}
+
+/// Compute trajectory statistics from an iterator of (G, S) points.
+fn trajectory_statistics(
+    points: impl Iterator<Item = (f64, f64)>,
+    n: usize,
+    frame_interval: f64,
+) -> (f64, f64, f64, f64) {
+    if n < 2 {
+        return (0.0, 0.0, 0.0, 0.0);
+    }
+
+    // walk the trajectory and accumulate the path length
+    let mut first = (0.0, 0.0);
+    let mut prev = (0.0, 0.0);
+    let mut path = 0.0;
+    points.enumerate().for_each(|(i, p)| {
+        if i == 0 {
+            first = p;
+        } else {
+            path += plot::modulation(p.0 - prev.0, p.1 - prev.1);
+        }
+        prev = p;
+    });
+
+    // net displacement vector between the first and last points
+    let dg = prev.0 - first.0;
+    let ds = prev.1 - first.1;
+    let velocity = path / ((n - 1) as f64 * frame_interval);
+
+    (
+        plot::modulation(dg, ds),
+        plot::phase(dg, ds),
+        path,
+        velocity,
+    )
+}
@@ -0,0 +1,223 @@
+use ndarray::{Array3, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::error::{ImgalError, check_axis};
+use crate::phasor::plot::monoexponential_coordinates;
+
+/// The result of a global two-component phasor analysis, see [`fit_line`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineFit {
+    /// The photon-weighted centroid (G, S) of the phasor cloud.
+    pub centroid: [f64; 2],
+    /// The unit direction (G, S) of the fitted line.
+    pub direction: [f64; 2],
+    /// The lifetimes of the two components, shortest first.
+    pub taus: [f64; 2],
+    /// The (G, S) coordinates of the two components on the universal
+    /// semicircle, in the order of `taus`.
+    pub points: [[f64; 2]; 2],
+    /// The (row, col, ch) fraction image of the two components, in the order
+    /// of `taus`, see [`fractions`].
+    pub fractions: Array3<f64>,
+}
+
+/// Find the two component lifetimes and fraction images of a phasor image.
+///
+/// # Description
+///
+/// This function performs a graphical global analysis of a 3-dimensional
+/// phasor image whose pixels are mixtures of the same two monoexponential
+/// components. The phasors of such mixtures lie on the line segment between
+/// the two component phasors on the universal semicircle. The line is fitted
+/// through the phasor cloud by weighted total least squares (_i.e._ the
+/// principal axis of the weighted (G, S) covariance matrix through the
+/// weighted centroid) and intersected with the universal semicircle:
+///
+/// ```text
+/// (G - 0.5)² + S² = 0.25
+/// ```
+///
+/// The intersections are the component phasors, whose lifetimes are
+/// τ = S / (ωG). The fraction images of the components are then computed for
+/// every pixel (see [`fractions`]).
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `omega`: The angular frequency.
+/// * `weights`: An optional 2-dimensional weight image (_e.g._ photon counts).
+///    If `None`, all pixels have the same weight.
+/// * `mask`: An optional 2-dimensional boolean mask, only pixels set to `true`
+///    are used to fit the line.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(LineFit)`: The fitted line, component lifetimes and fraction images.
+/// * `Err(ImgalError)`: If axis is >= 3. If the `weights` or `mask` shapes do
+///    not match the image. If less than 2 pixels have finite coordinates and a
+///    positive weight. If the fitted line does not intersect the universal
+///    semicircle.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1529/biophysj.107.120154>
+pub fn fit_line(
+    data: ArrayView3<f64>,
+    omega: f64,
+    weights: Option<ArrayView2<f64>>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<LineFit, ImgalError> {
+    // check if parameters are valid
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    for s in [
+        weights.map(|w| w.shape().to_vec()),
+        mask.map(|m| m.shape().to_vec()),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if s != shape {
+            return Err(ImgalError::MismatchedArrayShapes {
+                shape_a: shape.clone(),
+                shape_b: s,
+            });
+        }
+    }
+
+    // collect the weighted phasors used by the fit
+    let points: Vec<(f64, f64, f64)> = data
+        .lanes(Axis(a))
+        .into_iter()
+        .zip(ndarray::indices((shape[0], shape[1])))
+        .filter_map(|(ln, (r, c))| {
+            let w = weights.map_or(1.0, |w| w[[r, c]]);
+            let m = mask.is_none_or(|m| m[[r, c]]);
+            (m && w > 0.0 && ln[0].is_finite() && ln[1].is_finite()).then_some((ln[0], ln[1], w))
+        })
+        .collect();
+    if points.len() < 2 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid phasor image, less than 2 pixels can be fitted.",
+        });
+    }
+
+    // weighted centroid and covariance, the line follows the principal axis
+    let total: f64 = points.iter().map(|p| p.2).sum();
+    let cg = points.iter().map(|p| p.2 * p.0).sum::<f64>() / total;
+    let cs = points.iter().map(|p| p.2 * p.1).sum::<f64>() / total;
+    let (mut c_gg, mut c_ss, mut c_gs) = (0.0, 0.0, 0.0);
+    points.iter().for_each(|&(g, s, w)| {
+        c_gg += w * (g - cg) * (g - cg);
+        c_ss += w * (s - cs) * (s - cs);
+        c_gs += w * (g - cg) * (s - cs);
+    });
+    let theta = 0.5 * (2.0 * c_gs).atan2(c_gg - c_ss);
+    let (ug, us) = (theta.cos(), theta.sin());
+
+    // intersect the line with the universal semicircle
+    let (dg, ds) = (cg - 0.5, cs);
+    let b = dg * ug + ds * us;
+    let disc = b * b - (dg * dg + ds * ds - 0.25);
+    if disc < 0.0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid phasor image, the fitted line does not intersect the universal semicircle.",
+        });
+    }
+    let mut circle: Vec<[f64; 2]> = [-b - disc.sqrt(), -b + disc.sqrt()]
+        .iter()
+        .map(|t| [cg + t * ug, cs + t * us])
+        .collect();
+    // the component closest to G = 1 has the shortest lifetime
+    circle.sort_by(|p, q| q[0].total_cmp(&p[0]));
+    let taus = [
+        circle[0][1] / (omega * circle[0][0]),
+        circle[1][1] / (omega * circle[1][0]),
+    ];
+    let points = [circle[0], circle[1]];
+    let fractions = fraction_image(data, points, a);
+
+    Ok(LineFit {
+        centroid: [cg, cs],
+        direction: [ug, us],
+        taus,
+        points,
+        fractions,
+    })
+}
+
+/// Compute the fraction images of two known components of a phasor image.
+///
+/// # Description
+///
+/// This function projects each pixel's phasor onto the line through the
+/// phasors of two monoexponential components (see
+/// [`crate::phasor::plot::monoexponential_coordinates`]) and computes the
+/// fraction of each component:
+///
+/// ```text
+/// f₂ = ((P - P₁) · (P₂ - P₁)) / |P₂ - P₁|²
+/// f₁ = 1 - f₂
+/// ```
+///
+/// Fractions are the fractional intensities of the components and are not
+/// clamped, pixels outside of the segment between the components have
+/// fractions below 0.0 or above 1.0. Pixels with NaN coordinates remain NaN.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `taus`: The lifetimes of the two components.
+/// * `omega`: The angular frequency.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The (row, col, ch) fraction image, where the fractions
+///    of the components of `taus` are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If both lifetimes are equal.
+pub fn fractions(
+    data: ArrayView3<f64>,
+    taus: [f64; 2],
+    omega: f64,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError> {
+    // check if parameters are valid
+    let a = axis.unwrap_or(2);
+    check_axis(a, 3)?;
+    if taus[0] == taus[1] {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid lifetimes, the component lifetimes must be different.",
+        });
+    }
+
+    let points = taus.map(|t| {
+        let (g, s) = monoexponential_coordinates(t, omega);
+        [g, s]
+    });
+
+    Ok(fraction_image(data, points, a))
+}
+
+/// Project each pixel's phasor onto the segment between two component phasors.
+fn fraction_image(data: ArrayView3<f64>, points: [[f64; 2]; 2], axis: usize) -> Array3<f64> {
+    let mut shape = data.shape().to_vec();
+    shape.remove(axis);
+    let (vg, vs) = (points[1][0] - points[0][0], points[1][1] - points[0][1]);
+    let norm = vg * vg + vs * vs;
+    let mut output = Array3::<f64>::zeros((shape[0], shape[1], 2));
+    Zip::from(output.lanes_mut(Axis(2)))
+        .and(data.lanes(Axis(axis)))
+        .par_for_each(|mut o, ln| {
+            let f2 = ((ln[0] - points[0][0]) * vg + (ln[1] - points[0][1]) * vs) / norm;
+            o[0] = 1.0 - f2;
+            o[1] = f2;
+        });
+
+    output
+}
@@ -0,0 +1,263 @@
+use ndarray::{Array2, ArrayView2, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::gaussian::gaussian_2d_mut;
+use crate::segmentation::{Connectivity, label};
+use crate::threshold::otsu_value;
+use crate::traits::numeric::ToFloat64;
+
+/// A processing step of a [`Pipeline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// Gaussian smoothing with a standard deviation in pixels, see
+    /// [`crate::filter::gaussian_2d`].
+    Gaussian { sigma: f64 },
+    /// Threshold an image into a mask, values greater than the threshold are
+    /// `true`.
+    Threshold { threshold: f64 },
+    /// Threshold an image into a mask with Otsu's threshold computed over a
+    /// number of histogram bins, see [`crate::threshold::otsu_value`].
+    ThresholdOtsu { bins: usize },
+    /// Label the connected components of a mask, see
+    /// [`crate::segmentation::label`].
+    Label { connectivity: Connectivity },
+}
+
+/// The output of a [`Pipeline`], depending on its last step.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineOutput {
+    /// A floating point image.
+    Image(Array2<f64>),
+    /// A boolean mask.
+    Mask(Array2<bool>),
+    /// A label image and the number of labels.
+    Labels(Array2<usize>, usize),
+}
+
+/// A chain of 2-dimensional image processing steps.
+///
+/// # Description
+///
+/// A pipeline is built by chaining steps and executed on an image with
+/// [`Pipeline::run`]:
+///
+/// ```
+/// use ndarray::Array2;
+/// use imgal::pipeline::{Pipeline, PipelineOutput};
+///
+/// let data = Array2::<u16>::zeros((64, 64));
+/// let output = Pipeline::new()
+///     .gaussian(1.0)
+///     .threshold_otsu()
+///     .label()
+///     .run(data.view())
+///     .unwrap();
+/// assert!(matches!(output, PipelineOutput::Labels(_, _)));
+/// ```
+///
+/// The steps are validated before any computation. The pipeline avoids
+/// intermediate allocations: the input is converted to floating point once,
+/// all Gaussian steps share a single scratch buffer, consecutive Gaussian
+/// steps are fused into one pass (σ = √(σ₁² + σ₂²)) and a threshold directly
+/// applied to the input is computed in a single parallel pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+/// The intermediate data of a running pipeline.
+enum State<'a, T> {
+    Input(ArrayView2<'a, T>),
+    Image(Array2<f64>),
+    Mask(Array2<bool>),
+    Labels(Array2<usize>, usize),
+}
+
+impl Pipeline {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step to the pipeline.
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Append a Gaussian smoothing step.
+    pub fn gaussian(self, sigma: f64) -> Self {
+        self.step(Step::Gaussian { sigma })
+    }
+
+    /// Append a manual threshold step.
+    pub fn threshold(self, threshold: f64) -> Self {
+        self.step(Step::Threshold { threshold })
+    }
+
+    /// Append an Otsu threshold step with 256 histogram bins.
+    pub fn threshold_otsu(self) -> Self {
+        self.step(Step::ThresholdOtsu { bins: 256 })
+    }
+
+    /// Append an 8-connected component labeling step.
+    pub fn label(self) -> Self {
+        self.step(Step::Label {
+            connectivity: Connectivity::Eight,
+        })
+    }
+
+    /// The steps of the pipeline.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Run the pipeline on a 2-dimensional image.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: The input 2-dimensional image.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PipelineOutput)`: The output of the last step. An empty pipeline
+    ///    returns the input as a floating point image.
+    /// * `Err(ImgalError)`: If a step does not accept the output of the
+    ///    previous step (_e.g._ labeling an image that is not a mask). If a
+    ///    step parameter is invalid.
+    pub fn run<T>(&self, data: ArrayView2<T>) -> Result<PipelineOutput, ImgalError>
+    where
+        T: ToFloat64,
+    {
+        self.run_with_progress(data, |_, _| {})
+    }
+
+    /// Run the pipeline on a 2-dimensional image and report its progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: The input 2-dimensional image.
+    /// * `progress`: A callback called with the number of completed steps and
+    ///    the total number of steps after each step.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PipelineOutput)`: The output of the last step.
+    /// * `Err(ImgalError)`: See [`Pipeline::run`].
+    pub fn run_with_progress<T, F>(
+        &self,
+        data: ArrayView2<T>,
+        mut progress: F,
+    ) -> Result<PipelineOutput, ImgalError>
+    where
+        T: ToFloat64,
+        F: FnMut(usize, usize),
+    {
+        self.validate()?;
+
+        let n = self.steps.len();
+        let mut state = State::Input(data);
+        let mut buffer: Option<Array2<f64>> = None;
+        let mut i = 0;
+        while i < n {
+            state = match (self.steps[i], state) {
+                (Step::Gaussian { sigma }, s) => {
+                    // fuse consecutive Gaussian steps into one pass
+                    let mut var = sigma * sigma;
+                    while let Some(Step::Gaussian { sigma }) = self.steps.get(i + 1) {
+                        var += sigma * sigma;
+                        i += 1;
+                        progress(i, n);
+                    }
+                    let mut image = match s {
+                        State::Input(d) => d.mapv(|v| v.to_f64()),
+                        State::Image(img) => img,
+                        _ => unreachable!(),
+                    };
+                    let buf = buffer.get_or_insert_with(|| Array2::zeros(image.dim()));
+                    gaussian_2d_mut(image.view_mut(), buf, var.sqrt());
+                    State::Image(image)
+                }
+                (Step::Threshold { threshold }, State::Input(d)) => {
+                    State::Mask(threshold_view(d, threshold))
+                }
+                (Step::Threshold { threshold }, State::Image(img)) => {
+                    State::Mask(threshold_view(img.view(), threshold))
+                }
+                (Step::ThresholdOtsu { bins }, State::Input(d)) => {
+                    let t = otsu_value(d.into_dyn(), Some(bins))?;
+                    State::Mask(threshold_view(d, t))
+                }
+                (Step::ThresholdOtsu { bins }, State::Image(img)) => {
+                    let t = otsu_value(img.view().into_dyn(), Some(bins))?;
+                    State::Mask(threshold_view(img.view(), t))
+                }
+                (Step::Label { connectivity }, State::Mask(m)) => {
+                    let (labels, count) = label(m.view(), Some(connectivity));
+                    State::Labels(labels, count)
+                }
+                _ => unreachable!(),
+            };
+            i += 1;
+            progress(i, n);
+        }
+
+        Ok(match state {
+            State::Input(d) => PipelineOutput::Image(d.mapv(|v| v.to_f64())),
+            State::Image(img) => PipelineOutput::Image(img),
+            State::Mask(m) => PipelineOutput::Mask(m),
+            State::Labels(l, count) => PipelineOutput::Labels(l, count),
+        })
+    }
+
+    /// Check that each step accepts the output of the previous step.
+    fn validate(&self) -> Result<(), ImgalError> {
+        // 0 = image, 1 = mask, 2 = labels
+        let mut kind = 0;
+        for step in &self.steps {
+            kind = match (step, kind) {
+                (Step::Gaussian { sigma }, 0) => {
+                    if sigma.is_nan() || *sigma < 0.0 {
+                        return Err(ImgalError::InvalidParameterValueOutsideRange {
+                            param_name: "sigma",
+                            value: *sigma,
+                            min: 0.0,
+                            max: f64::INFINITY,
+                        });
+                    }
+                    0
+                }
+                (Step::Threshold { .. } | Step::ThresholdOtsu { .. }, 0) => 1,
+                (Step::Label { .. }, 1) => 2,
+                (
+                    Step::Gaussian { .. } | Step::Threshold { .. } | Step::ThresholdOtsu { .. },
+                    _,
+                ) => {
+                    return Err(ImgalError::InvalidArrayGeneric {
+                        msg: "Invalid pipeline, filter and threshold steps require an image.",
+                    });
+                }
+                (Step::Label { .. }, _) => {
+                    return Err(ImgalError::InvalidArrayGeneric {
+                        msg: "Invalid pipeline, a label step requires a mask.",
+                    });
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// Threshold an image into a mask in a single parallel pass.
+fn threshold_view<T>(data: ArrayView2<T>, threshold: f64) -> Array2<bool>
+where
+    T: ToFloat64,
+{
+    let mut mask = Array2::<bool>::default(data.dim());
+    Zip::from(&mut mask)
+        .and(data)
+        .par_for_each(|m, v| *m = v.to_f64() > threshold);
+
+    mask
+}
@@ -0,0 +1,5 @@
+//! Chainable image processing pipeline.
+pub mod builder;
+pub use builder::{Pipeline, PipelineOutput, Step};
+pub mod nuclei;
+pub use nuclei::{NucleiCount, NucleiOptions, count_nuclei};
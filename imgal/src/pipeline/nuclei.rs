@@ -0,0 +1,218 @@
+use ndarray::{Array2, ArrayView2, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::gaussian_2d;
+use crate::image::distance::check_spacing;
+use crate::image::distance_transform;
+use crate::segmentation::{RegionProperties, region_properties, watershed};
+use crate::threshold::otsu_value;
+use crate::traits::numeric::ToFloat64;
+
+/// Nucleus counting parameters, see [`count_nuclei`].
+///
+/// The defaults suit nuclei of about 10 to 40 pixels in diameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NucleiOptions {
+    /// The standard deviation in pixels of the Gaussian background estimate
+    /// subtracted from the image, 0.0 disables the background subtraction,
+    /// default = 25.0.
+    pub background_sigma: f64,
+    /// The standard deviation in pixels of the Gaussian smoothing applied
+    /// before thresholding, 0.0 disables the smoothing, default = 1.0.
+    pub smooth_sigma: f64,
+    /// The foreground threshold of the background subtracted image. If
+    /// `None`, Otsu's threshold is used, default = `None`.
+    pub threshold: Option<f64>,
+    /// The minimum distance in pixels between the seeds of touching nuclei,
+    /// default = 5.
+    pub min_separation: usize,
+    /// The minimum area in pixels of a nucleus, smaller regions are removed,
+    /// default = 20.
+    pub min_area: usize,
+    /// The (row, col) pixel size of the region measurements, default =
+    /// `[1.0, 1.0]` (pixels).
+    pub spacing: [f64; 2],
+}
+
+impl Default for NucleiOptions {
+    fn default() -> Self {
+        NucleiOptions {
+            background_sigma: 25.0,
+            smooth_sigma: 1.0,
+            threshold: None,
+            min_separation: 5,
+            min_area: 20,
+            spacing: [1.0, 1.0],
+        }
+    }
+}
+
+impl NucleiOptions {
+    /// Validate the nucleus counting parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If all parameters are valid.
+    /// * `Err(ImgalError)`: If `background_sigma` or `smooth_sigma` are < 0.0.
+    ///    If `min_separation` is 0. If a pixel size is not positive.
+    pub fn validate(&self) -> Result<(), ImgalError> {
+        for (name, sigma) in [
+            ("background_sigma", self.background_sigma),
+            ("smooth_sigma", self.smooth_sigma),
+        ] {
+            if sigma.is_nan() || sigma < 0.0 {
+                return Err(ImgalError::InvalidParameterValueOutsideRange {
+                    param_name: name,
+                    value: sigma,
+                    min: 0.0,
+                    max: f64::INFINITY,
+                });
+            }
+        }
+        if self.min_separation == 0 {
+            return Err(ImgalError::InvalidArrayParameterValueEqual {
+                param_name: "min_separation",
+                value: 0,
+            });
+        }
+        check_spacing(&self.spacing)
+    }
+}
+
+/// The result of [`count_nuclei`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NucleiCount {
+    /// The number of nuclei.
+    pub count: usize,
+    /// The nucleus label image, with consecutive labels from 1 and 0 as
+    /// background.
+    pub labels: Array2<usize>,
+    /// The measurements of each nucleus, sorted by label.
+    pub properties: Vec<RegionProperties>,
+}
+
+/// Count and measure the nuclei (or colonies) of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function chains the steps of a classic nucleus counting workflow with
+/// sensible defaults:
+///
+/// 1. Subtract a smooth background, a wide Gaussian blur of the image (see
+///    [`crate::filter::gaussian_2d`]), clamped at 0.0.
+/// 2. Smooth the image and threshold it into a foreground mask, with Otsu's
+///    threshold by default (see [`crate::threshold::otsu_value`]).
+/// 3. Split touching nuclei with a marker-controlled watershed (see
+///    [`crate::segmentation::watershed`]) of the negated distance transform
+///    of the mask (see [`crate::image::distance_transform`]), seeded at its
+///    maxima at least `min_separation` pixels apart.
+/// 4. Remove the regions smaller than `min_area` pixels and measure the
+///    remaining nuclei (see [`crate::segmentation::region_properties`]).
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image of bright nuclei.
+/// * `options`: The nucleus counting parameters, default =
+///    `NucleiOptions::default()`.
+///
+/// # Returns
+///
+/// * `Ok(NucleiCount)`: The number of nuclei, their label image and their
+///    measurements.
+/// * `Err(ImgalError)`: If the options are invalid (see
+///    [`NucleiOptions::validate`]). If the image is empty.
+pub fn count_nuclei<T>(
+    data: ArrayView2<T>,
+    options: Option<NucleiOptions>,
+) -> Result<NucleiCount, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let options = options.unwrap_or_default();
+
+    // check if parameters are valid
+    options.validate()?;
+
+    // subtract the background and smooth the image
+    let mut image = data.mapv(|v| v.to_f64());
+    if options.background_sigma > 0.0 {
+        let background = gaussian_2d(image.view(), options.background_sigma)?;
+        Zip::from(&mut image)
+            .and(&background)
+            .par_for_each(|v, &b| *v = (*v - b).max(0.0));
+    }
+    if options.smooth_sigma > 0.0 {
+        image = gaussian_2d(image.view(), options.smooth_sigma)?;
+    }
+
+    // threshold the foreground
+    let threshold = match options.threshold {
+        Some(t) => t,
+        None => otsu_value(image.view().into_dyn(), None)?,
+    };
+    let mask = image.mapv(|v| v > threshold);
+
+    // seed the watershed at the distance transform maxima
+    let distance = distance_transform(mask.view(), None)?;
+    let distance = gaussian_2d(distance.view(), 1.0)?;
+    let markers = distance_maxima(&distance, &mask, options.min_separation);
+    let surface = distance.mapv(|d| -d);
+    let labels = watershed(surface.view(), markers.view(), Some(mask.view()), None)?;
+
+    // remove small regions and relabel consecutively
+    let n = labels.iter().copied().max().unwrap_or(0);
+    let mut areas = vec![0usize; n + 1];
+    labels.iter().for_each(|&l| areas[l] += 1);
+    let mut relabel = vec![0usize; n + 1];
+    let mut count = 0;
+    (1..=n).for_each(|l| {
+        if areas[l] > 0 && areas[l] >= options.min_area {
+            count += 1;
+            relabel[l] = count;
+        }
+    });
+    let labels = labels.mapv(|l| relabel[l]);
+    let properties = region_properties(labels.view(), Some(options.spacing))?;
+
+    Ok(NucleiCount {
+        count,
+        labels,
+        properties,
+    })
+}
+
+/// Label the foreground pixels that are the maximum of the distance map
+/// within `radius` pixels, ties are broken in raster order.
+fn distance_maxima(distance: &Array2<f64>, mask: &Array2<bool>, radius: usize) -> Array2<usize> {
+    let (rows, cols) = distance.dim();
+    let r2 = (radius * radius) as isize;
+    let mut markers = Array2::<usize>::zeros((rows, cols));
+    let mut count = 0;
+    for r in 0..rows {
+        for c in 0..cols {
+            if !mask[[r, c]] {
+                continue;
+            }
+            let v = distance[[r, c]];
+            let (r0, r1) = (r.saturating_sub(radius), (r + radius + 1).min(rows));
+            let (c0, c1) = (c.saturating_sub(radius), (c + radius + 1).min(cols));
+            let is_max = (r0..r1).all(|nr| {
+                (c0..c1).all(|nc| {
+                    let (dr, dc) = (nr as isize - r as isize, nc as isize - c as isize);
+                    if dr * dr + dc * dc > r2 || !mask[[nr, nc]] {
+                        return true;
+                    }
+                    let n = distance[[nr, nc]];
+                    n < v || (n == v && (nr, nc) >= (r, c))
+                })
+            });
+            if is_max {
+                count += 1;
+                markers[[r, c]] = count;
+            }
+        }
+    }
+
+    markers
+}
@@ -0,0 +1,76 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::registration::{phase_correlation, translate};
+use crate::traits::numeric::ToFloat64;
+
+/// Align the channels of a multi-channel 2-dimensional image.
+///
+/// # Description
+///
+/// This function corrects lateral chromatic shifts between channels, which
+/// otherwise bias colocalization measurements. The translation of each
+/// channel relative to the reference channel is estimated with
+/// [`phase_correlation`] and the channel is resampled with [`translate`].
+/// Only translations are corrected.
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional multi-channel image.
+/// * `reference`: The index of the reference channel, default = 0.
+/// * `axis`: The channel axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array2<f64>))`: The aligned image with the same shape
+///    as `data`, and the `(row, col)` shift applied to each channel with a
+///    shape of `(channels, 2)`. Pixels shifted in from outside the image are
+///    0.0.
+/// * `Err(ImgalError)`: If `axis` is out of bounds or if `reference` is not a
+///    valid channel index.
+pub fn align_channels<T>(
+    data: ArrayView3<T>,
+    reference: Option<usize>,
+    axis: Option<usize>,
+) -> Result<(Array3<f64>, Array2<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let reference = reference.unwrap_or(0);
+    let axis = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if axis >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: 3,
+        });
+    }
+    let n_channels = data.len_of(Axis(axis));
+    if reference >= n_channels {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "reference",
+            value: n_channels.saturating_sub(1),
+        });
+    }
+
+    // estimate and apply the shift of each channel
+    let ref_channel = data.index_axis(Axis(axis), reference);
+    let mut output = Array3::<f64>::zeros(data.raw_dim());
+    let mut shifts = Array2::<f64>::zeros((n_channels, 2));
+    for ((ch, mut out), mut shift) in data
+        .axis_iter(Axis(axis))
+        .zip(output.axis_iter_mut(Axis(axis)))
+        .zip(shifts.rows_mut())
+    {
+        let (dr, dc) = phase_correlation(ref_channel.view(), ch.view())?;
+        Zip::from(&mut out)
+            .and(&translate(ch, (dr, dc), None))
+            .for_each(|o, &t| *o = t);
+        shift[0] = dr;
+        shift[1] = dc;
+    }
+
+    Ok((output, shifts))
+}
@@ -0,0 +1,251 @@
+use ndarray::{Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::fitting::linalg::solve;
+use crate::localization::{Localization, localize};
+use crate::measure::profile::bilinear;
+use crate::traits::numeric::ToFloat64;
+
+/// A polynomial chromatic aberration model, see [`chromatic_correction`].
+///
+/// # Description
+///
+/// Each channel maps the (row, col) coordinates of the reference channel to
+/// its own coordinates with a polynomial of the reference coordinates, with
+/// the terms in the order `1, r, c, r², r·c, c², ...` up to `order`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChromaticCorrection {
+    /// The polynomial order, 1 is an affine transform.
+    pub order: usize,
+    /// The index of the reference channel.
+    pub reference: usize,
+    /// The (row, col) polynomial coefficients of each channel.
+    pub coefficients: Vec<(Vec<f64>, Vec<f64>)>,
+    /// The root mean square registration error of the paired beads of each
+    /// channel, in pixels.
+    pub residual: Vec<f64>,
+    /// The number of paired beads of each channel.
+    pub n_beads: Vec<usize>,
+}
+
+/// Measure the chromatic aberration of a multi-channel bead image.
+///
+/// # Description
+///
+/// This function localizes the beads of each channel of a multi-color bead
+/// image (see [`crate::localization::localize`]), pairs each reference bead
+/// with the nearest bead of every other channel within `max_distance` pixels,
+/// and fits a polynomial mapping from the reference to the channel
+/// coordinates by least squares. Unlike a single translation (see
+/// [`crate::registration::align_channels`]), an affine or higher order
+/// polynomial also corrects the magnification differences and field
+/// dependent shifts of chromatic aberrations. The model is applied to
+/// experimental images with [`apply_chromatic_correction`].
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional multi-channel bead image.
+/// * `radius`: The bead detection and fit radius in pixels (see
+///    [`crate::localization::localize`]).
+/// * `order`: The polynomial order, default = 1 (affine).
+/// * `reference`: The index of the reference channel, default = 0.
+/// * `max_distance`: The maximum distance between paired beads in pixels,
+///    default = `radius`.
+/// * `axis`: The channel axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(ChromaticCorrection)`: The chromatic aberration model.
+/// * `Err(ImgalError)`: If axis is >= 3. If `radius` or `order` is 0. If
+///    `reference` is not a valid channel index. If a channel has fewer paired
+///    beads than polynomial terms.
+pub fn chromatic_correction<T>(
+    data: ArrayView3<T>,
+    radius: usize,
+    order: Option<usize>,
+    reference: Option<usize>,
+    max_distance: Option<f64>,
+    axis: Option<usize>,
+) -> Result<ChromaticCorrection, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let order = order.unwrap_or(1);
+    let reference = reference.unwrap_or(0);
+    let max_distance = max_distance.unwrap_or(radius as f64);
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if order == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "order",
+            value: 0,
+        });
+    }
+    let n_channels = data.len_of(Axis(a));
+    if reference >= n_channels {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "reference",
+            value: n_channels.saturating_sub(1),
+        });
+    }
+
+    // localize the beads of each channel, one channel per "frame"
+    let locs = localize(data, radius, None, None, Some(a))?;
+    let channel =
+        |ch: usize| -> Vec<&Localization> { locs.iter().filter(|l| l.frame == ch).collect() };
+    let ref_beads = channel(reference);
+    let n_terms = (order + 1) * (order + 2) / 2;
+
+    let mut coefficients = Vec::with_capacity(n_channels);
+    let mut residual = Vec::with_capacity(n_channels);
+    let mut n_beads = Vec::with_capacity(n_channels);
+    for ch in 0..n_channels {
+        // pair each reference bead with the nearest channel bead
+        let beads = channel(ch);
+        let pairs: Vec<((f64, f64), (f64, f64))> = ref_beads
+            .iter()
+            .filter_map(|r| {
+                beads
+                    .iter()
+                    .map(|b| ((b.row - r.row).hypot(b.col - r.col), b))
+                    .filter(|(d, _)| *d <= max_distance)
+                    .min_by(|x, y| x.0.total_cmp(&y.0))
+                    .map(|(_, b)| ((r.row, r.col), (b.row, b.col)))
+            })
+            .collect();
+        if pairs.len() < n_terms {
+            return Err(ImgalError::InvalidArrayParameterValueLess {
+                param_name: "paired beads",
+                value: n_terms,
+            });
+        }
+
+        // fit the row and column polynomials
+        let terms: Vec<Vec<f64>> = pairs
+            .iter()
+            .map(|&((r, c), _)| polynomial_terms(r, c, order))
+            .collect();
+        let fit = |target: &dyn Fn(usize) -> f64| -> Result<Vec<f64>, ImgalError> {
+            let mut ata = vec![vec![0.0; n_terms]; n_terms];
+            let mut atb = vec![0.0; n_terms];
+            terms.iter().enumerate().for_each(|(k, t)| {
+                for i in 0..n_terms {
+                    atb[i] += t[i] * target(k);
+                    for j in 0..n_terms {
+                        ata[i][j] += t[i] * t[j];
+                    }
+                }
+            });
+            solve(ata, atb).ok_or(ImgalError::InvalidArrayGeneric {
+                msg: "The bead positions do not constrain the polynomial model.",
+            })
+        };
+        let row_coefs = fit(&|k| pairs[k].1.0)?;
+        let col_coefs = fit(&|k| pairs[k].1.1)?;
+        let sse: f64 = pairs
+            .iter()
+            .zip(&terms)
+            .map(|(&(_, (br, bc)), t)| {
+                let (mr, mc) = (dot(&row_coefs, t), dot(&col_coefs, t));
+                (mr - br).powi(2) + (mc - bc).powi(2)
+            })
+            .sum();
+        residual.push((sse / pairs.len() as f64).sqrt());
+        n_beads.push(pairs.len());
+        coefficients.push((row_coefs, col_coefs));
+    }
+
+    Ok(ChromaticCorrection {
+        order,
+        reference,
+        coefficients,
+        residual,
+        n_beads,
+    })
+}
+
+/// Correct the chromatic aberration of a multi-channel image.
+///
+/// # Description
+///
+/// This function resamples each channel of a multi-channel image onto the
+/// reference channel coordinates with a chromatic aberration model (see
+/// [`chromatic_correction`]) and bilinear interpolation:
+///
+/// ```text
+/// output[ch, r, c] = data[ch, Pᵣ(r, c), P꜀(r, c)]
+/// ```
+///
+/// Where "P" is the polynomial mapping of the channel. Pixels mapped from
+/// outside the image are 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional multi-channel image.
+/// * `correction`: The chromatic aberration model.
+/// * `axis`: The channel axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The corrected image, with the shape of `data`.
+/// * `Err(ImgalError)`: If axis is >= 3. If the number of channels does not
+///    match the model.
+pub fn apply_chromatic_correction<T>(
+    data: ArrayView3<T>,
+    correction: &ChromaticCorrection,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(0);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if data.len_of(Axis(a)) != correction.coefficients.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: data.len_of(Axis(a)),
+            b_arr_len: correction.coefficients.len(),
+        });
+    }
+
+    let mut output = Array3::<f64>::zeros(data.raw_dim());
+    output
+        .axis_iter_mut(Axis(a))
+        .zip(data.axis_iter(Axis(a)))
+        .zip(&correction.coefficients)
+        .for_each(|((mut out, ch), (row_coefs, col_coefs))| {
+            Zip::indexed(&mut out).par_for_each(|(r, c), o| {
+                let t = polynomial_terms(r as f64, c as f64, correction.order);
+                *o = bilinear(ch, dot(row_coefs, &t), dot(col_coefs, &t)).unwrap_or(0.0);
+            });
+        });
+
+    Ok(output)
+}
+
+/// Compute the polynomial terms `1, r, c, r², r·c, c², ...` of a position.
+fn polynomial_terms(r: f64, c: f64, order: usize) -> Vec<f64> {
+    (0..=order)
+        .flat_map(|d| (0..=d).map(move |k| r.powi((d - k) as i32) * c.powi(k as i32)))
+        .collect()
+}
+
+/// Dot product of two slices.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
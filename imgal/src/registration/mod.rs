@@ -0,0 +1,9 @@
+//! Image registration functions.
+pub mod align;
+pub use align::align_channels;
+pub mod chromatic;
+pub use chromatic::{ChromaticCorrection, apply_chromatic_correction, chromatic_correction};
+pub mod phase_correlation;
+pub use phase_correlation::{phase_correlation, translate};
+pub mod stitch;
+pub use stitch::{BlendMode, stitch};
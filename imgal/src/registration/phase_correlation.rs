@@ -0,0 +1,156 @@
+use ndarray::{Array2, ArrayView2, Zip};
+use rustfft::num_complex::Complex;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+use crate::transform::fft::fft2;
+
+/// Estimate the translation between two 2-dimensional images with phase
+/// correlation.
+///
+/// # Description
+///
+/// This function computes the normalized cross-power spectrum of the two
+/// images:
+///
+/// ```text
+/// R = F(ref) F(mov)* / |F(ref) F(mov)*|
+/// ```
+///
+/// The inverse Fourier transform of "R" peaks at the translation between the
+/// images. The peak position is refined to subpixel precision by fitting a
+/// parabola through the peak and its neighbors along each axis. Translations
+/// are periodic, so shifts larger than half the image size are not recovered.
+///
+/// # Arguments
+///
+/// * `reference`: The reference 2-dimensional image.
+/// * `moving`: The moving 2-dimensional image, with the same shape as
+///    `reference`.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The `(row, col)` shift to apply to `moving` (see
+///    [`translate`]) to align it with `reference`.
+/// * `Err(ImgalError)`: If the image shapes do not match or the images are
+///    empty.
+pub fn phase_correlation<T>(
+    reference: ArrayView2<T>,
+    moving: ArrayView2<T>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the images are valid
+    if reference.shape() != moving.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: reference.shape().to_vec(),
+            shape_b: moving.shape().to_vec(),
+        });
+    }
+    if reference.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the images must not be empty.",
+        });
+    }
+
+    // compute the normalized cross-power spectrum
+    let (rows, cols) = reference.dim();
+    let mut fr = reference.mapv(|v| Complex::new(v.to_f64(), 0.0));
+    let mut fm = moving.mapv(|v| Complex::new(v.to_f64(), 0.0));
+    fft2(&mut fr, false);
+    fft2(&mut fm, false);
+    Zip::from(&mut fr).and(&fm).par_for_each(|r, m| {
+        let p = *r * m.conj();
+        let norm = p.norm();
+        *r = if norm > 0.0 {
+            p / norm
+        } else {
+            Complex::new(0.0, 0.0)
+        };
+    });
+    fft2(&mut fr, true);
+    let corr = fr.mapv(|v| v.re);
+
+    // find the correlation peak
+    let ((pr, pc), _) = corr
+        .indexed_iter()
+        .fold(((0, 0), f64::NEG_INFINITY), |acc, (idx, &v)| {
+            if v > acc.1 { (idx, v) } else { acc }
+        });
+
+    // refine the peak with a parabola fit along each axis
+    let refine = |prev: f64, peak: f64, next: f64| {
+        let denom = prev - 2.0 * peak + next;
+        if denom.abs() > f64::EPSILON {
+            (0.5 * (prev - next) / denom).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        }
+    };
+    let dr = refine(
+        corr[[(pr + rows - 1) % rows, pc]],
+        corr[[pr, pc]],
+        corr[[(pr + 1) % rows, pc]],
+    );
+    let dc = refine(
+        corr[[pr, (pc + cols - 1) % cols]],
+        corr[[pr, pc]],
+        corr[[pr, (pc + 1) % cols]],
+    );
+
+    // wrap the peak position to signed shifts
+    let wrap = |p: usize, n: usize| {
+        if p > n / 2 {
+            p as f64 - n as f64
+        } else {
+            p as f64
+        }
+    };
+
+    Ok((wrap(pr, rows) + dr, wrap(pc, cols) + dc))
+}
+
+/// Translate a 2-dimensional image by a subpixel shift.
+///
+/// # Description
+///
+/// This function shifts an image with bilinear interpolation, such that
+/// `output[r, c] = data[r - shift.0, c - shift.1]`. Pixels mapped from outside
+/// the image are set to `fill`.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `shift`: The `(row, col)` shift in pixels.
+/// * `fill`: The value of pixels mapped from outside the image, default = 0.0.
+///
+/// # Returns
+///
+/// * `Array2<f64>`: The translated image.
+pub fn translate<T>(data: ArrayView2<T>, shift: (f64, f64), fill: Option<f64>) -> Array2<f64>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let fill = fill.unwrap_or(0.0);
+
+    let (rows, cols) = data.dim();
+    let mut output = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        let sr = r as f64 - shift.0;
+        let sc = c as f64 - shift.1;
+        if sr < 0.0 || sc < 0.0 || sr > (rows - 1) as f64 || sc > (cols - 1) as f64 {
+            *o = fill;
+            return;
+        }
+        let (r0, c0) = (sr.floor() as usize, sc.floor() as usize);
+        let (r1, c1) = ((r0 + 1).min(rows - 1), (c0 + 1).min(cols - 1));
+        let (fr, fc) = (sr - r0 as f64, sc - c0 as f64);
+        let top = data[[r0, c0]].to_f64() * (1.0 - fc) + data[[r0, c1]].to_f64() * fc;
+        let bottom = data[[r1, c0]].to_f64() * (1.0 - fc) + data[[r1, c1]].to_f64() * fc;
+        *o = top * (1.0 - fr) + bottom * fr;
+    });
+
+    output
+}
@@ -0,0 +1,292 @@
+use ndarray::{Array2, ArrayView2, Zip, s};
+
+use crate::error::ImgalError;
+use crate::fitting::linalg::solve;
+use crate::registration::phase_correlation;
+use crate::traits::numeric::ToFloat64;
+
+// the 8-connected integer steps of the offset refinement
+const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Blending modes for fusing overlapping tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Average overlapping pixels with equal weights.
+    Linear,
+    /// Weight each pixel by its distance to the tile border, hiding seams.
+    Feather,
+}
+
+/// Stitch overlapping 2-dimensional tiles into a mosaic.
+///
+/// # Description
+///
+/// This function refines the nominal tile positions of a tiled acquisition and
+/// fuses the tiles into a single image:
+///
+/// 1. The offset of each pair of overlapping tiles is measured by
+///    [`phase_correlation`] of their nominal overlap regions, then refined by
+///    climbing the normalized cross-correlation of the tile overlap to its
+///    local maximum with subpixel precision. Corrections larger than
+///    `max_shift` are rejected and the nominal offset is kept.
+/// 2. The tile positions are globally optimized by least squares over all
+///    pairwise offsets, with the first tile fixed.
+/// 3. The tiles are placed at their rounded positions and blended.
+///
+/// # Arguments
+///
+/// * `tiles`: The 2-dimensional tiles.
+/// * `positions`: The nominal `(row, col)` position of the top left corner of
+///    each tile in pixels.
+/// * `blend`: The blending mode, default = `BlendMode::Feather`.
+/// * `max_shift`: The largest accepted correction of a pairwise offset in
+///    pixels, default = no limit.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>))`: The fused mosaic and the optimized tile
+///    positions with a shape of `(tiles, 2)`, relative to the mosaic origin.
+///    Mosaic pixels not covered by any tile are 0.0.
+/// * `Err(ImgalError)`: If `tiles` is empty or if the lengths of `tiles` and
+///    `positions` do not match.
+pub fn stitch<T>(
+    tiles: &[ArrayView2<T>],
+    positions: &[(f64, f64)],
+    blend: Option<BlendMode>,
+    max_shift: Option<f64>,
+) -> Result<(Array2<f64>, Array2<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let blend = blend.unwrap_or(BlendMode::Feather);
+    let max_shift = max_shift.unwrap_or(f64::INFINITY);
+
+    // check if parameters are valid
+    if tiles.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid tiles, at least one tile is required.",
+        });
+    }
+    if tiles.len() != positions.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: tiles.len(),
+            b_arr_len: positions.len(),
+        });
+    }
+
+    // measure the offset of each overlapping tile pair
+    let n = tiles.len();
+    let nominal: Vec<(isize, isize)> = positions
+        .iter()
+        .map(|&(r, c)| (r.round() as isize, c.round() as isize))
+        .collect();
+    let mut pairs: Vec<(usize, usize, f64, f64)> = Vec::new();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (pi, pj) = (nominal[i], nominal[j]);
+            let (hi, wi) = tiles[i].dim();
+            let (hj, wj) = tiles[j].dim();
+            let r0 = pi.0.max(pj.0);
+            let r1 = (pi.0 + hi as isize).min(pj.0 + hj as isize);
+            let c0 = pi.1.max(pj.1);
+            let c1 = (pi.1 + wi as isize).min(pj.1 + wj as isize);
+            if r1 - r0 < 2 || c1 - c0 < 2 {
+                continue;
+            }
+            let region = |p: (isize, isize)| {
+                s![
+                    (r0 - p.0) as usize..(r1 - p.0) as usize,
+                    (c0 - p.1) as usize..(c1 - p.1) as usize
+                ]
+            };
+            let (dr, dc) =
+                phase_correlation(tiles[i].slice(region(pi)), tiles[j].slice(region(pj)))?;
+
+            // refine the offset by climbing the normalized cross-correlation
+            // of the true tile overlap from the phase correlation estimate
+            let mut best = (
+                pj.0 - pi.0 + dr.round() as isize,
+                pj.1 - pi.1 + dc.round() as isize,
+            );
+            let mut best_ncc = ncc(&tiles[i], &tiles[j], best);
+            loop {
+                let (step, step_ncc) = NEIGHBORS
+                    .iter()
+                    .map(|&(sr, sc)| {
+                        let o = (best.0 + sr, best.1 + sc);
+                        (o, ncc(&tiles[i], &tiles[j], o))
+                    })
+                    .fold((best, best_ncc), |acc, v| if v.1 > acc.1 { v } else { acc });
+                if step == best {
+                    break;
+                }
+                (best, best_ncc) = (step, step_ncc);
+            }
+            let refine = |prev: f64, next: f64| {
+                let denom = prev - 2.0 * best_ncc + next;
+                if prev.is_finite() && next.is_finite() && denom.abs() > f64::EPSILON {
+                    (0.5 * (prev - next) / denom).clamp(-0.5, 0.5)
+                } else {
+                    0.0
+                }
+            };
+            let dr = best.0 as f64
+                + refine(
+                    ncc(&tiles[i], &tiles[j], (best.0 - 1, best.1)),
+                    ncc(&tiles[i], &tiles[j], (best.0 + 1, best.1)),
+                )
+                - (pj.0 - pi.0) as f64;
+            let dc = best.1 as f64
+                + refine(
+                    ncc(&tiles[i], &tiles[j], (best.0, best.1 - 1)),
+                    ncc(&tiles[i], &tiles[j], (best.0, best.1 + 1)),
+                )
+                - (pj.1 - pi.1) as f64;
+            let (dr, dc) = if !best_ncc.is_finite() || dr.hypot(dc) > max_shift {
+                (0.0, 0.0)
+            } else {
+                (dr, dc)
+            };
+            pairs.push((i, j, (pj.0 - pi.0) as f64 + dr, (pj.1 - pi.1) as f64 + dc));
+        }
+    }
+
+    // globally optimize the positions, tiles without overlaps keep their
+    // nominal positions
+    let rows = solve_positions(n, &pairs, positions, |p| p.2, |p| p.0);
+    let cols = solve_positions(n, &pairs, positions, |p| p.3, |p| p.1);
+    let min_r = rows.iter().copied().fold(f64::INFINITY, f64::min);
+    let min_c = cols.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut optimized = Array2::<f64>::zeros((n, 2));
+    optimized
+        .rows_mut()
+        .into_iter()
+        .enumerate()
+        .for_each(|(k, mut row)| {
+            row[0] = rows[k] - min_r;
+            row[1] = cols[k] - min_c;
+        });
+
+    // fuse the tiles at their rounded positions
+    let origins: Vec<(usize, usize)> = optimized
+        .rows()
+        .into_iter()
+        .map(|p| (p[0].round() as usize, p[1].round() as usize))
+        .collect();
+    let height = tiles
+        .iter()
+        .zip(&origins)
+        .map(|(t, o)| o.0 + t.nrows())
+        .max()
+        .unwrap_or(0);
+    let width = tiles
+        .iter()
+        .zip(&origins)
+        .map(|(t, o)| o.1 + t.ncols())
+        .max()
+        .unwrap_or(0);
+    let mut sum = Array2::<f64>::zeros((height, width));
+    let mut weights = Array2::<f64>::zeros((height, width));
+    for (tile, &(r0, c0)) in tiles.iter().zip(&origins) {
+        let (h, w) = tile.dim();
+        tile.indexed_iter().for_each(|((r, c), v)| {
+            let weight = match blend {
+                BlendMode::Linear => 1.0,
+                BlendMode::Feather => (r.min(h - 1 - r).min(c).min(w - 1 - c) + 1) as f64,
+            };
+            sum[[r0 + r, c0 + c]] += weight * v.to_f64();
+            weights[[r0 + r, c0 + c]] += weight;
+        });
+    }
+    sum.zip_mut_with(&weights, |s, &w| {
+        if w > 0.0 {
+            *s /= w;
+        }
+    });
+
+    Ok((sum, optimized))
+}
+
+/// Compute the normalized cross-correlation of the overlap of two tiles, with
+/// tile `b` placed at `offset` relative to tile `a`.
+fn ncc<T>(a: &ArrayView2<T>, b: &ArrayView2<T>, offset: (isize, isize)) -> f64
+where
+    T: ToFloat64,
+{
+    let (ha, wa) = (a.nrows() as isize, a.ncols() as isize);
+    let (hb, wb) = (b.nrows() as isize, b.ncols() as isize);
+    let (r0, r1) = (offset.0.max(0), (offset.0 + hb).min(ha));
+    let (c0, c1) = (offset.1.max(0), (offset.1 + wb).min(wa));
+    if r1 - r0 < 4 || c1 - c0 < 4 {
+        return f64::NEG_INFINITY;
+    }
+    let va = a.slice(s![r0..r1, c0..c1]);
+    let vb = b.slice(s![
+        r0 - offset.0..r1 - offset.0,
+        c0 - offset.1..c1 - offset.1
+    ]);
+    let n = va.len() as f64;
+    let mean_a = va.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let mean_b = vb.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    Zip::from(&va).and(&vb).for_each(|x, y| {
+        let (dx, dy) = (x.to_f64() - mean_a, y.to_f64() - mean_b);
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    });
+
+    if var_a > 0.0 && var_b > 0.0 {
+        cov / (var_a * var_b).sqrt()
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+/// Solve the least squares positions along one axis with the first tile fixed.
+fn solve_positions(
+    n: usize,
+    pairs: &[(usize, usize, f64, f64)],
+    positions: &[(f64, f64)],
+    offset: impl Fn(&(usize, usize, f64, f64)) -> f64,
+    nominal: impl Fn(&(f64, f64)) -> f64,
+) -> Vec<f64> {
+    // build the normal equations of the pairwise offset constraints, with a
+    // weak prior towards the nominal positions to anchor unconnected tiles
+    let prior = 1e-6;
+    let mut a = vec![vec![0.0; n]; n];
+    let mut b = vec![0.0; n];
+    for (k, p) in positions.iter().enumerate() {
+        a[k][k] += prior;
+        b[k] += prior * (nominal(p) - nominal(&positions[0]));
+    }
+    a[0][0] += 1e6;
+    for p in pairs {
+        let (i, j, d) = (p.0, p.1, offset(p));
+        a[i][i] += 1.0;
+        a[j][j] += 1.0;
+        a[i][j] -= 1.0;
+        a[j][i] -= 1.0;
+        b[i] -= d;
+        b[j] += d;
+    }
+
+    // solve the normal equations, the system is symmetric positive definite so
+    // it is only singular for non-finite offsets, then keep the nominal layout
+    solve(a, b).unwrap_or_else(|| {
+        positions
+            .iter()
+            .map(|p| nominal(p) - nominal(&positions[0]))
+            .collect()
+    })
+}
@@ -0,0 +1,217 @@
+use ndarray::{Array2, ArrayView3, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+// the label map and the class centroids in feature units
+type KMeansOutput = (Array2<usize>, Vec<Vec<f64>>);
+
+/// Cluster the per-pixel feature vectors of a 2-dimensional image into `k`
+/// classes.
+///
+/// # Description
+///
+/// This function segments an image by k-means clustering of the feature
+/// vector of each pixel, assembled as a 3-dimensional stack of feature
+/// channels (_e.g._ intensity, phasor G and S coordinates and texture
+/// features). The features are standardized to zero mean and unit variance
+/// by default, so features with large ranges do not dominate the Euclidean
+/// distance. Centroids are initialized with k-means++ and refined with
+/// Lloyd's algorithm until no pixel changes class or `max_iterations` is
+/// reached, with pixels assigned in parallel. Pixels with a NaN feature are
+/// not clustered, which allows masking regions out before clustering.
+///
+/// # Arguments
+///
+/// * `features`: The 3-dimensional stack of feature channels.
+/// * `k`: The number of classes.
+/// * `max_iterations`: The maximum number of Lloyd iterations, default = 100.
+/// * `standardize`: Whether to standardize each feature before clustering,
+///    default = true.
+/// * `seed`: Pseudorandom number generator seed of the k-means++
+///    initialization. If `None`, a random seed is drawn.
+/// * `axis`: The feature axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok((Array2<usize>, Vec<Vec<f64>>))`: The label map, with the classes
+///    labeled from 1 to `k` in order of increasing first feature centroid and
+///    0 for pixels with a NaN feature, and the centroid of each class in the
+///    original feature units.
+/// * `Err(ImgalError)`: If axis is >= 3. If `k` is 0 or greater than the
+///    number of valid pixels.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIT.1982.1056489>
+pub fn kmeans_features<T>(
+    features: ArrayView3<T>,
+    k: usize,
+    max_iterations: Option<usize>,
+    standardize: Option<bool>,
+    seed: Option<u64>,
+    axis: Option<usize>,
+) -> Result<KMeansOutput, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let max_iterations = max_iterations.unwrap_or(100);
+    let standardize = standardize.unwrap_or(true);
+    let a = axis.unwrap_or(2);
+
+    // check if parameters are valid
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        });
+    }
+    if k == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "k",
+            value: 0,
+        });
+    }
+
+    // collect the feature vectors of the valid pixels in (row, col) order
+    let features = features.permuted_axes(match a {
+        0 => [1, 2, 0],
+        1 => [0, 2, 1],
+        _ => [0, 1, 2],
+    });
+    let (rows, cols, n_features) = features.dim();
+    let mut pixels: Vec<(usize, usize)> = Vec::new();
+    let mut points: Vec<Vec<f64>> = Vec::new();
+    features
+        .lanes(Axis(2))
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, ln)| {
+            let v: Vec<f64> = ln.iter().map(|x| x.to_f64()).collect();
+            if v.iter().all(|x| !x.is_nan()) {
+                pixels.push((i / cols, i % cols));
+                points.push(v);
+            }
+        });
+    if k > points.len() {
+        return Err(ImgalError::InvalidArrayParameterValueGreater {
+            param_name: "k",
+            value: points.len(),
+        });
+    }
+
+    // standardize each feature
+    let n = points.len() as f64;
+    let (mean, scale): (Vec<f64>, Vec<f64>) = (0..n_features)
+        .map(|f| {
+            if !standardize {
+                return (0.0, 1.0);
+            }
+            let m = points.iter().map(|p| p[f]).sum::<f64>() / n;
+            let sd = (points.iter().map(|p| (p[f] - m).powi(2)).sum::<f64>() / n).sqrt();
+            (m, if sd > 0.0 { sd } else { 1.0 })
+        })
+        .unzip();
+    points.iter_mut().for_each(|p| {
+        p.iter_mut()
+            .enumerate()
+            .for_each(|(f, v)| *v = (*v - mean[f]) / scale[f]);
+    });
+
+    // k-means++ initialization, each new centroid is drawn with a probability
+    // proportional to its squared distance to the nearest centroid
+    let s = seed.unwrap_or_else(|| rand::rng().next_u64());
+    let mut rng = StdRng::seed_from_u64(s);
+    let mut centroids: Vec<Vec<f64>> = vec![points[rng.random_range(0..points.len())].clone()];
+    let mut nearest: Vec<f64> = points
+        .par_iter()
+        .map(|p| sq_dist(p, &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f64 = nearest.iter().sum();
+        let next = if total > 0.0 {
+            let mut target = rng.random::<f64>() * total;
+            nearest
+                .iter()
+                .position(|&d| {
+                    target -= d;
+                    target < 0.0
+                })
+                .unwrap_or(points.len() - 1)
+        } else {
+            rng.random_range(0..points.len())
+        };
+        centroids.push(points[next].clone());
+        let c = centroids.last().unwrap();
+        nearest
+            .par_iter_mut()
+            .zip(&points)
+            .for_each(|(d, p)| *d = d.min(sq_dist(p, c)));
+    }
+
+    // Lloyd iterations
+    let mut assignment = vec![usize::MAX; points.len()];
+    for iter in 0..=max_iterations {
+        let next: Vec<usize> = points
+            .par_iter()
+            .map(|p| {
+                (0..k)
+                    .min_by(|&i, &j| {
+                        sq_dist(p, &centroids[i]).total_cmp(&sq_dist(p, &centroids[j]))
+                    })
+                    .unwrap()
+            })
+            .collect();
+        let converged = next == assignment;
+        assignment = next;
+        if converged || iter == max_iterations {
+            break;
+        }
+        let mut sums = vec![vec![0.0; n_features]; k];
+        let mut counts = vec![0usize; k];
+        points.iter().zip(&assignment).for_each(|(p, &c)| {
+            counts[c] += 1;
+            sums[c].iter_mut().zip(p).for_each(|(s, v)| *s += v);
+        });
+        // empty classes keep their previous centroid
+        centroids
+            .iter_mut()
+            .zip(sums.into_iter().zip(counts))
+            .filter(|(_, (_, count))| *count > 0)
+            .for_each(|(c, (s, count))| {
+                *c = s.into_iter().map(|v| v / count as f64).collect();
+            });
+    }
+
+    // sort the classes by their first feature and restore the feature units
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&i, &j| centroids[i][0].total_cmp(&centroids[j][0]));
+    let mut rank = vec![0; k];
+    order.iter().enumerate().for_each(|(r, &c)| rank[c] = r);
+    let centroids = order
+        .iter()
+        .map(|&c| {
+            centroids[c]
+                .iter()
+                .enumerate()
+                .map(|(f, v)| v * scale[f] + mean[f])
+                .collect()
+        })
+        .collect();
+    let mut labels = Array2::<usize>::zeros((rows, cols));
+    pixels
+        .iter()
+        .zip(&assignment)
+        .for_each(|(&(r, c), &l)| labels[[r, c]] = rank[l] + 1);
+
+    Ok((labels, centroids))
+}
+
+/// Squared Euclidean distance between two feature vectors.
+fn sq_dist(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
@@ -0,0 +1,105 @@
+use ndarray::{Array2, ArrayView2};
+
+/// Pixel connectivity of connected component labeling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Pixels sharing an edge are connected.
+    Four,
+    /// Pixels sharing an edge or a corner are connected.
+    Eight,
+}
+
+/// Label the connected components of a 2-dimensional boolean mask.
+///
+/// # Description
+///
+/// This function labels each connected region of `true` pixels with a unique
+/// integer with a two pass union-find algorithm. Labels are consecutive,
+/// starting at 1 in raster (row-major) order of each region's first pixel,
+/// while background (`false`) pixels are 0.
+///
+/// # Arguments
+///
+/// * `mask`: The 2-dimensional boolean mask.
+/// * `connectivity`: The pixel connectivity, default = `Connectivity::Eight`.
+///
+/// # Returns
+///
+/// * `(Array2<usize>, usize)`: The label image and the number of labels.
+pub fn label(mask: ArrayView2<bool>, connectivity: Option<Connectivity>) -> (Array2<usize>, usize) {
+    // set optional parameters if needed
+    let connectivity = connectivity.unwrap_or(Connectivity::Eight);
+
+    let (rows, cols) = mask.dim();
+    let mut labels = Array2::<usize>::zeros((rows, cols));
+    let mut parent: Vec<usize> = vec![0];
+
+    // first pass, assign provisional labels and record equivalences
+    for r in 0..rows {
+        for c in 0..cols {
+            if !mask[[r, c]] {
+                continue;
+            }
+            let mut neighbors = [0usize; 4];
+            if c > 0 {
+                neighbors[0] = labels[[r, c - 1]];
+            }
+            if r > 0 {
+                neighbors[1] = labels[[r - 1, c]];
+                if connectivity == Connectivity::Eight {
+                    if c > 0 {
+                        neighbors[2] = labels[[r - 1, c - 1]];
+                    }
+                    if c + 1 < cols {
+                        neighbors[3] = labels[[r - 1, c + 1]];
+                    }
+                }
+            }
+            let current = match neighbors.iter().filter(|&&n| n > 0).min() {
+                Some(&n) => n,
+                None => {
+                    parent.push(parent.len());
+                    parent.len() - 1
+                }
+            };
+            for &n in neighbors.iter().filter(|&&n| n > 0) {
+                union(&mut parent, current, n);
+            }
+            labels[[r, c]] = current;
+        }
+    }
+
+    // second pass, resolve equivalences to consecutive labels
+    let mut relabel = vec![0; parent.len()];
+    let mut count = 0;
+    labels.iter_mut().filter(|l| **l > 0).for_each(|l| {
+        let root = find(&mut parent, *l);
+        if relabel[root] == 0 {
+            count += 1;
+            relabel[root] = count;
+        }
+        *l = relabel[root];
+    });
+
+    (labels, count)
+}
+
+/// Find the root label of a union-find forest with path halving.
+pub(crate) fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+
+    x
+}
+
+/// Merge two label trees, keeping the smaller root label.
+pub(crate) fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra < rb {
+        parent[rb] = ra;
+    } else if rb < ra {
+        parent[ra] = rb;
+    }
+}
@@ -0,0 +1,20 @@
+//! Image segmentation functions.
+pub mod felzenszwalb;
+pub use felzenszwalb::felzenszwalb;
+pub mod kmeans;
+pub use kmeans::kmeans_features;
+pub mod label;
+pub use label::{Connectivity, label};
+pub mod postprocess;
+pub use postprocess::{
+    expand_labels, label_adjacency, label_boundaries, merge_adjacent, merge_labels,
+    relabel_sequential, shrink_labels,
+};
+pub mod propagate;
+pub use propagate::propagate_labels;
+pub mod random_walker;
+pub use random_walker::random_walker;
+pub mod region;
+pub use region::{RegionProperties, region_properties};
+pub mod watershed;
+pub use watershed::watershed;
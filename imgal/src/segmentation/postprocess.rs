@@ -0,0 +1,307 @@
+use ndarray::{Array2, ArrayView2, Zip};
+
+use crate::error::ImgalError;
+use crate::segmentation::label::{find, union};
+use crate::segmentation::{Connectivity, RegionProperties, region_properties};
+
+/// Relabel a 2-dimensional label image with consecutive labels.
+///
+/// # Description
+///
+/// This function maps the labels present in a label image (_e.g._ after
+/// removing or merging regions) to consecutive labels starting at 1, keeping
+/// their relative order, while background pixels stay 0.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+///
+/// # Returns
+///
+/// * `(Array2<usize>, usize)`: The relabeled image and the number of labels.
+pub fn relabel_sequential(labels: ArrayView2<usize>) -> (Array2<usize>, usize) {
+    let n = labels.iter().copied().max().unwrap_or(0);
+    let mut present = vec![false; n + 1];
+    labels.iter().for_each(|&l| present[l] = true);
+    let mut relabel = vec![0; n + 1];
+    let mut count = 0;
+    (1..=n).filter(|&l| present[l]).for_each(|l| {
+        count += 1;
+        relabel[l] = count;
+    });
+
+    (labels.mapv(|l| relabel[l]), count)
+}
+
+/// Merge labels of a 2-dimensional label image.
+///
+/// # Description
+///
+/// This function merges each (a, b) pair of labels into a single region.
+/// Pairs are transitive, so chains of pairs merge into one region, which
+/// takes the smallest label of the chain. Labels are not renumbered, see
+/// [`relabel_sequential`].
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `pairs`: The (a, b) label pairs to merge. Pairs with the background
+///    label 0 or labels absent from `labels` are ignored.
+///
+/// # Returns
+///
+/// * `Array2<usize>`: The merged label image.
+pub fn merge_labels(labels: ArrayView2<usize>, pairs: &[(usize, usize)]) -> Array2<usize> {
+    let n = labels.iter().copied().max().unwrap_or(0);
+    let mut parent: Vec<usize> = (0..=n).collect();
+    pairs
+        .iter()
+        .filter(|&&(a, b)| a > 0 && b > 0 && a <= n && b <= n)
+        .for_each(|&(a, b)| union(&mut parent, a, b));
+    let roots: Vec<usize> = (0..=n).map(|l| find(&mut parent, l)).collect();
+
+    labels.mapv(|l| roots[l])
+}
+
+/// Merge the adjacent regions of a 2-dimensional label image that satisfy a
+/// predicate.
+///
+/// # Description
+///
+/// This function finds the pairs of labels that touch (see
+/// [`label_adjacency`]), measures their regions (see
+/// [`crate::segmentation::region_properties`]) and merges the pairs for which
+/// `predicate` is true (see [`merge_labels`]), _e.g._ to merge the fragments
+/// of an over-segmented cell whose areas are below a size limit. The
+/// predicate is evaluated on the regions before any merge.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `predicate`: The merge predicate of two adjacent regions.
+/// * `connectivity`: The pixel connectivity of adjacency, default =
+///    `Connectivity::Eight`.
+/// * `spacing`: The (row, col) pixel size of the region measurements,
+///    default = `[1.0, 1.0]` (pixels).
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The merged label image.
+/// * `Err(ImgalError)`: If a pixel size is not positive.
+pub fn merge_adjacent<F>(
+    labels: ArrayView2<usize>,
+    mut predicate: F,
+    connectivity: Option<Connectivity>,
+    spacing: Option<[f64; 2]>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    F: FnMut(&RegionProperties, &RegionProperties) -> bool,
+{
+    let props = region_properties(labels, spacing)?;
+    let n = labels.iter().copied().max().unwrap_or(0);
+    let mut index = vec![usize::MAX; n + 1];
+    props
+        .iter()
+        .enumerate()
+        .for_each(|(i, p)| index[p.label] = i);
+    let pairs: Vec<(usize, usize)> = label_adjacency(labels, connectivity)
+        .into_iter()
+        .filter(|&(a, b)| predicate(&props[index[a]], &props[index[b]]))
+        .collect();
+
+    Ok(merge_labels(labels, &pairs))
+}
+
+/// Find the pairs of touching labels of a 2-dimensional label image.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `connectivity`: The pixel connectivity, default = `Connectivity::Eight`.
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize)>`: The sorted (a, b) pairs of adjacent labels, with
+///    a < b.
+pub fn label_adjacency(
+    labels: ArrayView2<usize>,
+    connectivity: Option<Connectivity>,
+) -> Vec<(usize, usize)> {
+    // set optional parameters if needed
+    let connectivity = connectivity.unwrap_or(Connectivity::Eight);
+
+    // scan the forward neighbors of each pixel
+    let (rows, cols) = labels.dim();
+    let forward: &[(usize, isize)] = match connectivity {
+        Connectivity::Four => &[(0, 1), (1, 0)],
+        Connectivity::Eight => &[(0, 1), (1, -1), (1, 0), (1, 1)],
+    };
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    labels.indexed_iter().for_each(|((r, c), &l)| {
+        if l == 0 {
+            return;
+        }
+        for &(dr, dc) in forward {
+            let (nr, Some(nc)) = (r + dr, c.checked_add_signed(dc)) else {
+                continue;
+            };
+            if nr >= rows || nc >= cols {
+                continue;
+            }
+            let m = labels[[nr, nc]];
+            if m > 0 && m != l {
+                pairs.push((l.min(m), l.max(m)));
+            }
+        }
+    });
+    pairs.sort_unstable();
+    pairs.dedup();
+
+    pairs
+}
+
+/// Extract the boundaries of the regions of a 2-dimensional label image.
+///
+/// # Description
+///
+/// This function marks the labeled pixels that have a neighbor with a
+/// different label, including the background, as boundary pixels. Boundaries
+/// are inside of their regions and the image edges are not boundaries.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `connectivity`: The pixel connectivity, default = `Connectivity::Eight`.
+///
+/// # Returns
+///
+/// * `Array2<bool>`: The boundary mask.
+pub fn label_boundaries(
+    labels: ArrayView2<usize>,
+    connectivity: Option<Connectivity>,
+) -> Array2<bool> {
+    // set optional parameters if needed
+    let connectivity = connectivity.unwrap_or(Connectivity::Eight);
+
+    let (rows, cols) = labels.dim();
+    let offsets: &[(isize, isize)] = match connectivity {
+        Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        Connectivity::Eight => &[
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ],
+    };
+    let mut mask = Array2::<bool>::default((rows, cols));
+    Zip::indexed(&mut mask).par_for_each(|(r, c), m| {
+        let l = labels[[r, c]];
+        *m = l > 0
+            && offsets.iter().any(|&(dr, dc)| {
+                match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
+                    (Some(nr), Some(nc)) if nr < rows && nc < cols => labels[[nr, nc]] != l,
+                    _ => false,
+                }
+            });
+    });
+
+    mask
+}
+
+/// Expand the regions of a 2-dimensional label image into the background.
+///
+/// # Description
+///
+/// This function assigns each background pixel within `distance` pixels
+/// (Euclidean) of a region to its nearest region, so expanding regions stop
+/// where they meet (a Voronoi partition limited to `distance`) and never
+/// overwrite each other. Ties are assigned to the first region in raster
+/// order. To expand nuclei into cells bounded by a membrane channel, see
+/// [`crate::segmentation::propagate_labels`].
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `distance`: The expansion distance in pixels.
+///
+/// # Returns
+///
+/// * `Array2<usize>`: The expanded label image.
+pub fn expand_labels(labels: ArrayView2<usize>, distance: usize) -> Array2<usize> {
+    let (rows, cols) = labels.dim();
+    let offsets = disk_offsets(distance);
+    let mut output = labels.to_owned();
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        if *o != 0 {
+            return;
+        }
+        *o = offsets
+            .iter()
+            .find_map(|&(dr, dc)| {
+                let nr = r.checked_add_signed(dr).filter(|&v| v < rows)?;
+                let nc = c.checked_add_signed(dc).filter(|&v| v < cols)?;
+                let l = labels[[nr, nc]];
+                (l > 0).then_some(l)
+            })
+            .unwrap_or(0);
+    });
+
+    output
+}
+
+/// Shrink the regions of a 2-dimensional label image.
+///
+/// # Description
+///
+/// This function removes the labeled pixels within `distance` pixels
+/// (Euclidean) of a pixel with a different label, including the background,
+/// so touching regions separate and small regions may vanish. Pixels beyond
+/// the image edges are not considered different.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `distance`: The shrinking distance in pixels.
+///
+/// # Returns
+///
+/// * `Array2<usize>`: The shrunk label image.
+pub fn shrink_labels(labels: ArrayView2<usize>, distance: usize) -> Array2<usize> {
+    let (rows, cols) = labels.dim();
+    let offsets = disk_offsets(distance);
+    let mut output = labels.to_owned();
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        let l = *o;
+        if l == 0 {
+            return;
+        }
+        let boundary = offsets.iter().any(|&(dr, dc)| {
+            match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
+                (Some(nr), Some(nc)) if nr < rows && nc < cols => labels[[nr, nc]] != l,
+                _ => false,
+            }
+        });
+        if boundary {
+            *o = 0;
+        }
+    });
+
+    output
+}
+
+/// Compute the offsets within a disk of `radius` pixels, sorted by distance
+/// and then in raster order.
+fn disk_offsets(radius: usize) -> Vec<(isize, isize)> {
+    let r = radius as isize;
+    let mut offsets: Vec<(isize, isize)> = (-r..=r)
+        .flat_map(|dr| (-r..=r).map(move |dc| (dr, dc)))
+        .filter(|&(dr, dc)| dr * dr + dc * dc <= r * r)
+        .collect();
+    offsets.sort_by_key(|&(dr, dc)| dr * dr + dc * dc);
+
+    offsets
+}
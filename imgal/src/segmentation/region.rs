@@ -0,0 +1,99 @@
+use std::f64::consts::PI;
+
+use ndarray::ArrayView2;
+
+use crate::error::ImgalError;
+use crate::image::distance::check_spacing;
+
+/// Measurements of a labeled region.
+///
+/// Physical measurements are reported in the unit of the pixel size (_e.g._
+/// µm and µm²), or in pixels if no pixel size is given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionProperties {
+    /// The region label.
+    pub label: usize,
+    /// The number of pixels in the region.
+    pub pixel_count: usize,
+    /// The physical area of the region.
+    pub area: f64,
+    /// The physical (row, col) centroid of the region.
+    pub centroid: (f64, f64),
+    /// The pixel bounding box of the region as (min row, min col, max row,
+    /// max col), where the max bounds are exclusive.
+    pub bbox: (usize, usize, usize, usize),
+    /// The diameter of a circle with the same physical area as the region.
+    pub equivalent_diameter: f64,
+}
+
+/// Measure the labeled regions of a 2-dimensional label image.
+///
+/// # Description
+///
+/// This function measures the area, centroid, bounding box and equivalent
+/// diameter of each labeled region (see [`crate::segmentation::label`]). With
+/// a pixel size, areas and lengths are reported in physical units:
+///
+/// ```text
+/// area = n · s_row · s_col
+/// d = √(4 · area / π)
+/// ```
+///
+/// Where "n" is the number of pixels in the region.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image, where 0 is background.
+/// * `spacing`: The (row, col) pixel size, default = `[1.0, 1.0]` (pixels).
+///
+/// # Returns
+///
+/// * `Ok(Vec<RegionProperties>)`: The properties of each region present in
+///    `labels`, sorted by label.
+/// * `Err(ImgalError)`: If a pixel size is not positive.
+pub fn region_properties(
+    labels: ArrayView2<usize>,
+    spacing: Option<[f64; 2]>,
+) -> Result<Vec<RegionProperties>, ImgalError> {
+    // set optional parameters if needed
+    let spacing = spacing.unwrap_or([1.0, 1.0]);
+
+    // check if parameters are valid
+    check_spacing(&spacing)?;
+
+    // accumulate pixel counts, coordinate sums and bounds per label
+    let n = labels.iter().max().map_or(0, |&m| m + 1);
+    let mut counts = vec![0usize; n];
+    let mut sums = vec![(0.0, 0.0); n];
+    let mut bounds = vec![(usize::MAX, usize::MAX, 0, 0); n];
+    labels
+        .indexed_iter()
+        .filter(|(_, l)| **l > 0)
+        .for_each(|((r, c), &l)| {
+            counts[l] += 1;
+            sums[l].0 += r as f64;
+            sums[l].1 += c as f64;
+            let b = &mut bounds[l];
+            *b = (b.0.min(r), b.1.min(c), b.2.max(r + 1), b.3.max(c + 1));
+        });
+
+    let pixel_area = spacing[0] * spacing[1];
+    Ok((1..n)
+        .filter(|&l| counts[l] > 0)
+        .map(|l| {
+            let count = counts[l] as f64;
+            let area = count * pixel_area;
+            RegionProperties {
+                label: l,
+                pixel_count: counts[l],
+                area,
+                centroid: (
+                    sums[l].0 / count * spacing[0],
+                    sums[l].1 / count * spacing[1],
+                ),
+                bbox: bounds[l],
+                equivalent_diameter: (4.0 * area / PI).sqrt(),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use ndarray::{Array2, ArrayView2};
+
+use crate::error::ImgalError;
+use crate::segmentation::Connectivity;
+use crate::traits::numeric::ToFloat64;
+
+/// Segment a 2-dimensional image by marker-controlled watershed.
+///
+/// # Description
+///
+/// This function floods the `surface` from the labeled `markers` in order of
+/// increasing surface value (priority flood), so each pixel receives the
+/// label of the marker whose basin reaches it first. Pixels of equal value are
+/// flooded in the order they are reached. A typical use separates touching
+/// objects by flooding the negated distance transform of their mask (see
+/// [`crate::image::distance_transform`]) from its maxima. Pixels outside of
+/// `mask` are not flooded and stay 0.
+///
+/// # Arguments
+///
+/// * `surface`: The 2-dimensional surface to flood.
+/// * `markers`: The 2-dimensional marker image with the same shape as
+///    `surface`, where 0 is unlabeled.
+/// * `mask`: The 2-dimensional boolean mask of the pixels to flood, with the
+///    same shape as `surface`. If `None`, all pixels are flooded.
+/// * `connectivity`: The pixel connectivity, default = `Connectivity::Eight`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The label image. Pixels unreachable from a marker
+///    are 0.
+/// * `Err(ImgalError)`: If the shapes of `surface`, `markers` and `mask` do
+///    not match.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/0165-1684(94)90060-4>
+pub fn watershed<T>(
+    surface: ArrayView2<T>,
+    markers: ArrayView2<usize>,
+    mask: Option<ArrayView2<bool>>,
+    connectivity: Option<Connectivity>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let connectivity = connectivity.unwrap_or(Connectivity::Eight);
+
+    // check if parameters are valid
+    if surface.shape() != markers.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: surface.shape().to_vec(),
+            shape_b: markers.shape().to_vec(),
+        });
+    }
+    if let Some(m) = mask
+        && m.shape() != surface.shape()
+    {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: surface.shape().to_vec(),
+            shape_b: m.shape().to_vec(),
+        });
+    }
+
+    let (rows, cols) = surface.dim();
+    let inside = |r: usize, c: usize| mask.is_none_or(|m| m[[r, c]]);
+    let offsets: &[(isize, isize)] = match connectivity {
+        Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        Connectivity::Eight => &[
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ],
+    };
+
+    // seed the queue with the marker pixels
+    let mut labels = Array2::<usize>::zeros((rows, cols));
+    let mut queue = BinaryHeap::new();
+    let mut order = 0;
+    markers.indexed_iter().for_each(|((r, c), &m)| {
+        if m > 0 && inside(r, c) {
+            labels[[r, c]] = m;
+            queue.push(Pixel {
+                value: surface[[r, c]].to_f64(),
+                order,
+                r,
+                c,
+            });
+            order += 1;
+        }
+    });
+
+    // flood the lowest pixel first, its unlabeled neighbors join its basin
+    while let Some(p) = queue.pop() {
+        let l = labels[[p.r, p.c]];
+        for &(dr, dc) in offsets {
+            let (Some(nr), Some(nc)) = (p.r.checked_add_signed(dr), p.c.checked_add_signed(dc))
+            else {
+                continue;
+            };
+            if nr >= rows || nc >= cols || labels[[nr, nc]] != 0 || !inside(nr, nc) {
+                continue;
+            }
+            labels[[nr, nc]] = l;
+            queue.push(Pixel {
+                value: surface[[nr, nc]].to_f64(),
+                order,
+                r: nr,
+                c: nc,
+            });
+            order += 1;
+        }
+    }
+
+    Ok(labels)
+}
+
+/// A queued pixel, ordered by lowest value and then earliest insertion.
+pub(crate) struct Pixel {
+    pub(crate) value: f64,
+    pub(crate) order: usize,
+    pub(crate) r: usize,
+    pub(crate) c: usize,
+}
+
+impl Ord for Pixel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed for the max-heap
+        other
+            .value
+            .total_cmp(&self.value)
+            .then(other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for Pixel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Pixel {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pixel {}
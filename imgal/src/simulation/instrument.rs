@@ -1,6 +1,124 @@
 use std::f64::consts::LN_2;
 
+use rustfft::{FftPlanner, num_complex::Complex, num_traits::Zero};
+
 use crate::distribution::gaussian;
+use crate::error::ImgalError;
+use crate::simulation::decay::ideal_exponential_1d;
+use crate::traits::numeric::ToFloat64;
+
+/// Estimate the instrument response function (IRF) from a measured
+/// monoexponential reference decay.
+///
+/// # Description
+///
+/// This function recovers an effective IRF from the decay of a reference
+/// sample with a known monoexponential lifetime (_e.g._ fluorescein or
+/// coumarin 6), for setups without a directly measured IRF. The measured decay
+/// `D` is the convolution of the IRF with the ideal decay `E` of lifetime
+/// `tau`, so the IRF is recovered by Tikhonov regularized deconvolution in the
+/// frequency domain:
+///
+/// ```text
+/// IRF = F⁻¹[ F(D) F(E)* / (|F(E)|² + λ max|F(E)|²) ]
+/// ```
+///
+/// Where "λ" is the `regularization` weight, damping the amplification of
+/// noise at frequencies where the ideal decay has little power. Negative
+/// values of the recovered IRF are clipped to 0.0 and the IRF is normalized to
+/// sum to 1.0.
+///
+/// # Arguments
+///
+/// * `data`: The measured 1-dimensional reference decay.
+/// * `period`: The period (_i.e._ time interval).
+/// * `tau`: The known lifetime of the reference decay.
+/// * `regularization`: The relative Tikhonov regularization weight, default =
+///    1e-3.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The normalized IRF with the same length as `data`, usable
+///    with [`crate::simulation::decay::irf_exponential_1d`].
+/// * `Err(ImgalError)`: If `data` is empty. If `tau` is not positive or
+///    `regularization` is negative.
+pub fn estimate_irf<T>(
+    data: &[T],
+    period: f64,
+    tau: f64,
+    regularization: Option<f64>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let lambda = regularization.unwrap_or(1e-3);
+
+    // check if parameters are valid
+    if data.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the reference decay must not be empty.",
+        });
+    }
+    if tau.is_nan() || tau <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "tau",
+            value: tau,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    if lambda.is_nan() || lambda < 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "regularization",
+            value: lambda,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    // create the ideal decay with a unit sum, the deconvolution kernel
+    let n = data.len();
+    let ideal = ideal_exponential_1d(n, period, &[tau], &[1.0], 1.0)?;
+
+    // zero pad both signals to avoid circular wrap around
+    let fft_size = (2 * n - 1).next_power_of_two();
+    let mut d_buf = vec![Complex::zero(); fft_size];
+    let mut e_buf = vec![Complex::zero(); fft_size];
+    d_buf
+        .iter_mut()
+        .zip(data)
+        .for_each(|(b, v)| *b = Complex::new(v.to_f64(), 0.0));
+    e_buf
+        .iter_mut()
+        .zip(&ideal)
+        .for_each(|(b, &v)| *b = Complex::new(v, 0.0));
+    let mut planner = FftPlanner::new();
+    planner.plan_fft_forward(fft_size).process(&mut d_buf);
+    planner.plan_fft_forward(fft_size).process(&mut e_buf);
+
+    // regularized division in the frequency domain
+    let max_power = e_buf.iter().map(|v| v.norm_sqr()).fold(0.0, f64::max);
+    let damping = lambda * max_power;
+    d_buf.iter_mut().zip(&e_buf).for_each(|(d, e)| {
+        let denom = e.norm_sqr() + damping;
+        *d = if denom > 0.0 {
+            *d * e.conj() / denom
+        } else {
+            Complex::zero()
+        };
+    });
+    planner.plan_fft_inverse(fft_size).process(&mut d_buf);
+
+    // clip negative values, trim and normalize the IRF
+    let mut irf: Vec<f64> = d_buf[..n].iter().map(|v| v.re.max(0.0)).collect();
+    let total: f64 = irf.iter().sum();
+    if total > 0.0 {
+        irf.iter_mut().for_each(|v| *v /= total);
+    }
+
+    Ok(irf)
+}
 
 /// Simulate a 1-dimensional Gaussian instrument response function (IRF).
 ///
@@ -1,9 +1,10 @@
-use ndarray::{Array3, ArrayView1, ArrayView3, ArrayViewMut3, Axis, Zip};
+use ndarray::{
+    Array2, Array3, ArrayView2, ArrayView3, ArrayViewMut2, ArrayViewMut3, Axis, RemoveAxis, Zip,
+};
 use rand::SeedableRng;
 use rand::prelude::*;
 use rand::rngs::StdRng;
-use rand_distr::{Distribution, Poisson};
-use rayon::prelude::*;
+use rand_distr::{Distribution, Normal, Poisson};
 
 use crate::error::ImgalError;
 use crate::traits::numeric::ToFloat64;
@@ -22,9 +23,8 @@ use crate::traits::numeric::ToFloat64;
 ///
 /// * `data`: The input 1-dimensional array.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value for
+///    reproducible noise. If `None`, a random seed is drawn.
 ///
 /// # Returns
 ///
@@ -34,18 +34,12 @@ where
     T: ToFloat64,
 {
     // set optional parameters if needed
-    let s = seed.unwrap_or(0);
+    let s = seed.unwrap_or_else(|| rand::rng().next_u64());
     let mut rng = StdRng::seed_from_u64(s);
 
     let mut n_data = vec![0.0; data.len()];
     n_data.iter_mut().zip(data.iter()).for_each(|(n, &d)| {
-        if d.to_f64() > 0.0 {
-            let l: f64 = d.to_f64() * scale;
-            let p = Poisson::new(l).unwrap();
-            *n = p.sample(&mut rng);
-        } else {
-            *n = 0.0;
-        }
+        *n = sample_poisson(d.to_f64(), scale, &mut rng);
     });
 
     n_data
@@ -65,26 +59,181 @@ where
 ///
 /// * `data`: The input 1-dimensional array view to mutate.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value for
+///    reproducible noise. If `None`, a random seed is drawn.
 pub fn poisson_1d_mut(data: &mut [f64], scale: f64, seed: Option<u64>) {
     // set optional parameters if needed
-    let s = seed.unwrap_or(0);
+    let s = seed.unwrap_or_else(|| rand::rng().next_u64());
     let mut rng = StdRng::seed_from_u64(s);
 
     // mutate the 1d data array
     data.iter_mut().for_each(|x| {
-        if *x > 0.0 {
-            let l = *x * scale;
-            let p = Poisson::new(l).unwrap();
-            *x = p.sample(&mut rng);
-        } else {
-            *x = 0.0;
-        }
+        *x = sample_poisson(*x, scale, &mut rng);
     });
 }
 
+/// Simulate Poisson noise on a 2-dimensional array.
+///
+/// # Description
+///
+/// This function applies Poisson noise (_i.e._ shot noise) on a 2-dimensional
+/// image. An element-wise lambda value (scaled by the `scale` parameter) is
+/// used to simulate Poisson noise with variable signal strength. Each row
+/// draws from its own random stream derived from the master `seed` and the row
+/// index (see [`lane_seed`]), the output is reproducible for a given `seed`.
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional array.
+/// * `scale`: The scale factor.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
+///
+/// # Returns
+///
+/// * `Array2<f64>`: A 2-dimensional array of the input data with Poisson noise
+///    applied.
+pub fn poisson_2d<T>(data: ArrayView2<T>, scale: f64, seed: Option<u64>) -> Array2<f64>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let master = seed.unwrap_or_else(|| rand::rng().next_u64());
+
+    // apply and store Poisson noise data, one random stream per row
+    let mut n_data = Array2::<f64>::zeros(data.dim());
+    Zip::indexed(data.rows())
+        .and(n_data.rows_mut())
+        .par_for_each(|i, s_row, d_row| {
+            let mut rng = StdRng::seed_from_u64(lane_seed(master, i));
+            Zip::from(s_row).and(d_row).for_each(|s, d| {
+                *d = sample_poisson((*s).to_f64(), scale, &mut rng);
+            });
+        });
+
+    n_data
+}
+
+/// Simulate Poisson noise on a 2-dimensional array.
+///
+/// # Description
+///
+/// This function applies Poisson noise (_i.e._ shot noise) on a 2-dimensional
+/// image, see [`poisson_2d`]. The same `seed` produces identical noise.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional array to mutate.
+/// * `scale`: The scale factor.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
+pub fn poisson_2d_mut(mut data: ArrayViewMut2<f64>, scale: f64, seed: Option<u64>) {
+    // set optional parameters if needed
+    let master = seed.unwrap_or_else(|| rand::rng().next_u64());
+
+    // apply noise to each row, one random stream per row
+    Zip::indexed(data.rows_mut()).par_for_each(|i, row| {
+        let mut rng = StdRng::seed_from_u64(lane_seed(master, i));
+        row.into_iter().for_each(|x| {
+            *x = sample_poisson(*x, scale, &mut rng);
+        });
+    });
+}
+
+/// Simulate additive Gaussian noise on a 2-dimensional array.
+///
+/// # Description
+///
+/// This function adds zero mean Gaussian noise (_e.g._ detector read noise)
+/// with standard deviation `sigma` to each element of a 2-dimensional image.
+/// Each row draws from its own random stream derived from the master `seed`
+/// and the row index (see [`lane_seed`]).
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional array.
+/// * `sigma`: The standard deviation of the noise. Must be finite and
+///    non-negative.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: A 2-dimensional array of the input data with Gaussian
+///    noise applied.
+/// * `Err(ImgalError)`: If `sigma` is negative or not finite.
+pub fn gaussian_2d<T>(
+    data: ArrayView2<T>,
+    sigma: f64,
+    seed: Option<u64>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut n_data = data.mapv(|v| v.to_f64());
+    gaussian_2d_mut(n_data.view_mut(), sigma, seed)?;
+
+    Ok(n_data)
+}
+
+/// Simulate additive Gaussian noise on a 2-dimensional array.
+///
+/// # Description
+///
+/// This function adds zero mean Gaussian noise with standard deviation `sigma`
+/// to each element of a 2-dimensional image, see [`gaussian_2d`]. The same
+/// `seed` produces identical noise.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional array to mutate.
+/// * `sigma`: The standard deviation of the noise. Must be finite and
+///    non-negative.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the noise was applied.
+/// * `Err(ImgalError)`: If `sigma` is negative or not finite.
+pub fn gaussian_2d_mut(
+    mut data: ArrayViewMut2<f64>,
+    sigma: f64,
+    seed: Option<u64>,
+) -> Result<(), ImgalError> {
+    // check if sigma parameter is valid
+    if !sigma.is_finite() || sigma < 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "sigma",
+            value: sigma,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    // set optional parameters if needed
+    let master = seed.unwrap_or_else(|| rand::rng().next_u64());
+
+    // add noise to each row, one random stream per row
+    let normal = Normal::new(0.0, sigma).unwrap();
+    Zip::indexed(data.rows_mut()).par_for_each(|i, row| {
+        let mut rng = StdRng::seed_from_u64(lane_seed(master, i));
+        row.into_iter().for_each(|x| {
+            *x += normal.sample(&mut rng);
+        });
+    });
+
+    Ok(())
+}
+
 /// Simulate Poisson noise on a 3-dimensional array.
 ///
 /// # Description
@@ -93,15 +242,21 @@ pub fn poisson_1d_mut(data: &mut [f64], scale: f64, seed: Option<u64>) {
 /// array of data. An element-wise lambda value (scaled by the `scale` parameter)
 /// is used to simulate Poisson noise with variable signal strength.
 ///
+/// Each 1-dimensional lane along `axis` draws from its own random stream,
+/// seeded from the master `seed` and the row-major index of the lane (see
+/// [`lane_seed`]). The noise is therefore independent between lanes, and the
+/// output for a given `seed` is reproducible regardless of how the lanes are
+/// scheduled across threads. [`poisson_3d_mut`] produces identical noise for
+/// the same `seed` and `axis`.
+///
 /// This function creates a new array and does not mutate the input array.
 ///
 /// # Arguments
 ///
 /// * `data`: The input 3-dimensional array.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
 /// * `axis`: The signal data axis, default = 2.
 ///
 /// # Returns
@@ -120,6 +275,7 @@ where
 {
     // set optional parameters if needed
     let a = axis.unwrap_or(2);
+    let master = seed.unwrap_or_else(|| rand::rng().next_u64());
 
     // check if axis parameter is valid
     if a >= 3 {
@@ -133,42 +289,16 @@ where
     let shape = data.dim();
     let mut n_data = Array3::<f64>::zeros(shape);
 
-    // apply and store Poisson noise data in new array
-    let src_lanes = data.lanes(Axis(a));
-    let dst_lanes = n_data.lanes_mut(Axis(a));
-    if let Some(s) = seed {
-        // apply noise with one seed, homogenous noise
-        Zip::from(src_lanes)
-            .and(dst_lanes)
-            .par_for_each(|s_ln, d_ln| {
-                let mut rng = StdRng::seed_from_u64(s);
-                Zip::from(s_ln).and(d_ln).for_each(|s, d| {
-                    if (*s).to_f64() > 0.0 {
-                        let l = (*s).to_f64() * scale;
-                        let p = Poisson::new(l).unwrap();
-                        *d = p.sample(&mut rng);
-                    } else {
-                        *d = 0.0;
-                    }
-                });
+    // apply and store Poisson noise data, one random stream per lane
+    let lane_cols = data.raw_dim().remove_axis(Axis(a))[1];
+    Zip::indexed(data.lanes(Axis(a)))
+        .and(n_data.lanes_mut(Axis(a)))
+        .par_for_each(|(i, j), s_ln, d_ln| {
+            let mut rng = StdRng::seed_from_u64(lane_seed(master, i * lane_cols + j));
+            Zip::from(s_ln).and(d_ln).for_each(|s, d| {
+                *d = sample_poisson((*s).to_f64(), scale, &mut rng);
             });
-    } else {
-        // apply noise with variable seeds, hetergenous noise
-        Zip::from(src_lanes)
-            .and(dst_lanes)
-            .par_for_each(|s_ln, d_ln| {
-                let mut rng = rand::rng();
-                Zip::from(s_ln).and(d_ln).for_each(|s, d| {
-                    if (*s).to_f64() > 0.0 {
-                        let l = (*s).to_f64() * scale;
-                        let p = Poisson::new(l).unwrap();
-                        *d = p.sample(&mut rng);
-                    } else {
-                        *d = 0.0
-                    }
-                });
-            });
-    }
+        });
 
     Ok(n_data)
 }
@@ -181,15 +311,17 @@ where
 /// array of data. An element-wise lambda value (scaled by the `scale` parameter)
 /// is used to simulate Poisson noise with variable signal strength.
 ///
+/// Each lane along `axis` draws from its own random stream derived from the
+/// master `seed` and the lane index, see [`poisson_3d`].
+///
 /// This function mutates the input array and does not create a new array.
 ///
 /// # Arguments
 ///
 /// * `data`: The input 3-dimensional array to mutate.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+/// * `seed`: Pseudorandom number generator master seed. Set the `seed` value
+///    for reproducible noise. If `None`, a random master seed is drawn.
 /// * `axis`: The signal data axis, default = 2.
 pub fn poisson_3d_mut(
     mut data: ArrayViewMut3<f64>,
@@ -199,34 +331,56 @@ pub fn poisson_3d_mut(
 ) {
     // set optional parameters if needed
     let a = axis.unwrap_or(2);
+    let master = seed.unwrap_or_else(|| rand::rng().next_u64());
 
-    // apply noise to each lane
-    let lanes = data.lanes_mut(Axis(a));
-    if let Some(s) = seed {
-        // apply noise with one seed, homogeneous noise
-        lanes.into_iter().par_bridge().for_each(|mut ln| {
-            if let Some(l) = ln.as_slice_mut() {
-                poisson_1d_mut(l, scale, Some(s));
-            } else {
-                let mut l = ln.to_vec();
-                poisson_1d_mut(&mut l, scale, Some(s));
-                let l = ArrayView1::from(&l);
-                ln.assign(&l);
-            }
+    // apply noise to each lane, one random stream per lane
+    let lane_cols = data.raw_dim().remove_axis(Axis(a))[1];
+    Zip::indexed(data.lanes_mut(Axis(a))).par_for_each(|(i, j), ln| {
+        let mut rng = StdRng::seed_from_u64(lane_seed(master, i * lane_cols + j));
+        ln.into_iter().for_each(|x| {
+            *x = sample_poisson(*x, scale, &mut rng);
         });
+    });
+}
+
+/// Derive the random stream seed of a lane from a master seed.
+///
+/// # Description
+///
+/// This function derives an independent per-lane seed by mixing the master
+/// seed and the lane index with the SplitMix64 finalizer. Seeds of adjacent
+/// lanes are decorrelated, and depend only on the master seed and lane index
+/// (_i.e._ not on thread scheduling).
+///
+/// # Arguments
+///
+/// * `master`: The master seed.
+/// * `lane`: The row-major index of the lane.
+///
+/// # Returns
+///
+/// * `u64`: The seed of the lane's random stream.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1145/2714064.2660195>
+pub fn lane_seed(master: u64, lane: usize) -> u64 {
+    let mut z = master.wrapping_add(
+        (lane as u64)
+            .wrapping_add(1)
+            .wrapping_mul(0x9E3779B97F4A7C15),
+    );
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+    z ^ (z >> 31)
+}
+
+/// Sample a Poisson distributed value with lambda `value * scale`.
+fn sample_poisson(value: f64, scale: f64, rng: &mut StdRng) -> f64 {
+    if value > 0.0 {
+        Poisson::new(value * scale).unwrap().sample(rng)
     } else {
-        // apply noise with variable seeds, hetergeneous noise
-        lanes.into_iter().par_bridge().for_each(|mut ln| {
-            let mut rng = rand::rng();
-            let s = rng.next_u64();
-            if let Some(l) = ln.as_slice_mut() {
-                poisson_1d_mut(l, scale, Some(s));
-            } else {
-                let mut l = ln.to_vec();
-                poisson_1d_mut(&mut l, scale, Some(s));
-                let l = ArrayView1::from(&l);
-                ln.assign(&l);
-            }
-        });
+        0.0
     }
 }
@@ -0,0 +1,273 @@
+use ndarray::{Array2, ArrayView2, ArrayView3, Axis};
+use rayon::prelude::*;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Focus quality metrics available for focus curves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FocusMetric {
+    /// Brenner gradient.
+    Brenner,
+    /// Tenengrad (Sobel gradient energy) with a squared gradient threshold.
+    Tenengrad { threshold: f64 },
+    /// Normalized variance.
+    NormalizedVariance,
+    /// High frequency power spectrum fraction with a normalized frequency
+    /// cutoff.
+    Spectral { cutoff: f64 },
+}
+
+impl FocusMetric {
+    /// Compute the focus score of a 2-dimensional image.
+    pub(crate) fn compute<T>(&self, data: ArrayView2<T>) -> f64
+    where
+        T: ToFloat64,
+    {
+        match *self {
+            FocusMetric::Brenner => brenner_gradient(data),
+            FocusMetric::Tenengrad { threshold } => tenengrad(data, Some(threshold)),
+            FocusMetric::NormalizedVariance => normalized_variance(data),
+            FocusMetric::Spectral { cutoff } => spectral_focus(data, Some(cutoff)),
+        }
+    }
+}
+
+/// Compute the Brenner gradient focus score of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the Brenner gradient, the mean squared difference
+/// between pixels two columns apart:
+///
+/// ```text
+/// F = 1/N Σ (I(r, c + 2) - I(r, c))²
+/// ```
+///
+/// Where "N" is the number of pixel pairs. Sharper images have larger scores.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+///
+/// # Returns
+///
+/// * `f64`: The Brenner gradient focus score. If the image has less than 3
+///    columns, 0.0 is returned.
+pub fn brenner_gradient<T>(data: ArrayView2<T>) -> f64
+where
+    T: ToFloat64,
+{
+    let (rows, cols) = data.dim();
+    if cols < 3 {
+        return 0.0;
+    }
+    let sum: f64 = data
+        .rows()
+        .into_iter()
+        .map(|row| {
+            (0..cols - 2)
+                .map(|c| (row[c + 2].to_f64() - row[c].to_f64()).powi(2))
+                .sum::<f64>()
+        })
+        .sum();
+
+    sum / (rows * (cols - 2)) as f64
+}
+
+/// Compute the Tenengrad focus score of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the Tenengrad score, the mean squared Sobel gradient
+/// magnitude of the image:
+///
+/// ```text
+/// F = 1/N Σ (Gₓ² + Gᵧ²),  for Gₓ² + Gᵧ² > T
+/// ```
+///
+/// Where "Gₓ" and "Gᵧ" are the horizontal and vertical Sobel gradients of each
+/// interior pixel, "N" is the number of interior pixels and "T" is a squared
+/// gradient threshold that suppresses noise.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `threshold`: The squared gradient magnitude threshold, gradients at or
+///    below the threshold are ignored, default = 0.0.
+///
+/// # Returns
+///
+/// * `f64`: The Tenengrad focus score. If the image has less than 3 rows or
+///    columns, 0.0 is returned.
+pub fn tenengrad<T>(data: ArrayView2<T>, threshold: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let threshold = threshold.unwrap_or(0.0);
+
+    let (rows, cols) = data.dim();
+    if rows < 3 || cols < 3 {
+        return 0.0;
+    }
+    let v = |r: usize, c: usize| data[[r, c]].to_f64();
+    let sum: f64 = (1..rows - 1)
+        .map(|r| {
+            (1..cols - 1)
+                .map(|c| {
+                    let gx = (v(r - 1, c + 1) + 2.0 * v(r, c + 1) + v(r + 1, c + 1))
+                        - (v(r - 1, c - 1) + 2.0 * v(r, c - 1) + v(r + 1, c - 1));
+                    let gy = (v(r + 1, c - 1) + 2.0 * v(r + 1, c) + v(r + 1, c + 1))
+                        - (v(r - 1, c - 1) + 2.0 * v(r - 1, c) + v(r - 1, c + 1));
+                    let g2 = gx * gx + gy * gy;
+                    if g2 > threshold { g2 } else { 0.0 }
+                })
+                .sum::<f64>()
+        })
+        .sum();
+
+    sum / ((rows - 2) * (cols - 2)) as f64
+}
+
+/// Compute the normalized variance focus score of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the image variance normalized by the mean intensity,
+/// compensating for differences in illumination between frames:
+///
+/// ```text
+/// F = 1/(Nμ) Σ (I(r, c) - μ)²
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+///
+/// # Returns
+///
+/// * `f64`: The normalized variance focus score. If the image is empty or has
+///    a mean of 0.0, 0.0 is returned.
+pub fn normalized_variance<T>(data: ArrayView2<T>) -> f64
+where
+    T: ToFloat64,
+{
+    let n = data.len() as f64;
+    let mean = data.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+    if data.is_empty() || mean == 0.0 {
+        return 0.0;
+    }
+    let var = data
+        .iter()
+        .map(|v| (v.to_f64() - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    var / mean
+}
+
+/// Compute the power spectrum focus score of a 2-dimensional image.
+///
+/// # Description
+///
+/// This function computes the fraction of the (mean subtracted) image power
+/// spectrum above a radial frequency cutoff. Defocus acts as a low-pass
+/// filter, so sharper images have more high frequency power. Radial
+/// frequencies are normalized to the Nyquist frequency, where 1.0 is the
+/// Nyquist frequency along each axis.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `cutoff`: The normalized radial frequency cutoff, default = 0.2.
+///
+/// # Returns
+///
+/// * `f64`: The high frequency power fraction, between 0.0 and 1.0. If the
+///    image has no power (_i.e._ is constant), 0.0 is returned.
+pub fn spectral_focus<T>(data: ArrayView2<T>, cutoff: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let cutoff = cutoff.unwrap_or(0.2);
+
+    let (rows, cols) = data.dim();
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    // compute the 2-dimensional FFT of the mean subtracted image
+    let mean = data.iter().map(|v| v.to_f64()).sum::<f64>() / data.len() as f64;
+    let mut buf: Array2<Complex<f64>> = data.mapv(|v| Complex::new(v.to_f64() - mean, 0.0));
+    let mut planner = FftPlanner::new();
+    let fft_rows = planner.plan_fft_forward(cols);
+    let fft_cols = planner.plan_fft_forward(rows);
+    buf.rows_mut().into_iter().for_each(|mut row| {
+        let mut line = row.to_vec();
+        fft_rows.process(&mut line);
+        row.iter_mut().zip(line).for_each(|(r, l)| *r = l);
+    });
+    buf.columns_mut().into_iter().for_each(|mut col| {
+        let mut line = col.to_vec();
+        fft_cols.process(&mut line);
+        col.iter_mut().zip(line).for_each(|(c, l)| *c = l);
+    });
+
+    // sum the power above the radial frequency cutoff
+    let freq = |k: usize, n: usize| k.min(n - k) as f64 / n as f64 / 0.5;
+    let mut total = 0.0;
+    let mut high = 0.0;
+    buf.indexed_iter().for_each(|((r, c), v)| {
+        let p = v.norm_sqr();
+        total += p;
+        if freq(r, rows).hypot(freq(c, cols)) > cutoff {
+            high += p;
+        }
+    });
+
+    if total > 0.0 { high / total } else { 0.0 }
+}
+
+/// Compute the focus scores of each frame in a 3-dimensional stack.
+///
+/// # Description
+///
+/// This function computes a focus score for each 2-dimensional frame of a
+/// stack in parallel (_e.g._ an autofocus curve over a z-stack, or to reject
+/// out-of-focus frames of a time series).
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional stack.
+/// * `metric`: The focus metric.
+/// * `axis`: The frame axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The focus score of each frame.
+/// * `Err(ImgalError)`: If the axis is >= 3.
+pub fn focus_curve<T>(
+    data: ArrayView3<T>,
+    metric: FocusMetric,
+    axis: Option<usize>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let axis = axis.unwrap_or(0);
+
+    // check if axis parameter is valid
+    if axis >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: 3,
+        });
+    }
+
+    let frames: Vec<ArrayView2<T>> = data.axis_iter(Axis(axis)).collect();
+    Ok(frames.into_par_iter().map(|f| metric.compute(f)).collect())
+}
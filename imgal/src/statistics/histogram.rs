@@ -0,0 +1,132 @@
+use ndarray::Array2;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Find the bin index of a value in an evenly spaced histogram.
+///
+/// # Description
+///
+/// This function finds the bin of a value in a histogram with `bins` evenly
+/// spaced bins between the `range` minimum and maximum. The last bin includes
+/// the maximum value. If the range has no width, all values equal to the
+/// range are assigned to the first bin.
+///
+/// # Arguments
+///
+/// * `value`: The value to bin.
+/// * `range`: The (min, max) range of the histogram.
+/// * `bins`: The number of histogram bins. Must be greater than 0.
+///
+/// # Returns
+///
+/// * `Option<usize>`: The bin index of the value, or `None` if the value is
+///    outside of the range or NaN.
+#[inline]
+pub fn bin_index(value: f64, range: (f64, f64), bins: usize) -> Option<usize> {
+    let (min, max) = range;
+    if bins == 0 || value.is_nan() || value < min || value > max {
+        return None;
+    }
+    let width = (max - min) / bins as f64;
+    if width <= 0.0 {
+        return Some(0);
+    }
+
+    Some((((value - min) / width) as usize).min(bins - 1))
+}
+
+/// Compute the 2-dimensional histogram of paired values.
+///
+/// # Description
+///
+/// This function computes a 2-dimensional histogram of paired `(x, y)` values
+/// (_e.g._ phasor coordinates or the pixel intensities of two channels). Each
+/// axis is divided into evenly spaced bins (see [`bin_index`]), pairs with
+/// either value outside of its range are not counted.
+///
+/// # Arguments
+///
+/// * `x`: The first values of each pair. Must be the same length as `y`.
+/// * `y`: The second values of each pair. Must be the same length as `x`.
+/// * `bins`: The number of (x, y) bins, default = (256, 256).
+/// * `ranges`: The ((x_min, x_max), (y_min, y_max)) ranges of the histogram.
+///    If `None`, the minimum and maximum values of `x` and `y` are used.
+/// * `weights`: Optional weights, one per pair. Must be the same length as `x`.
+///    If `None`, each pair has a weight of 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional (x_bins, y_bins) histogram, where
+///    element `[i, j]` is the (weighted) count of pairs in x bin `i` and y bin
+///    `j`.
+/// * `Err(ImgalError)`: If the `x`, `y` and `weights` lengths do not match. If
+///    either number of bins is 0.
+pub fn histogram2d<T>(
+    x: &[T],
+    y: &[T],
+    bins: Option<(usize, usize)>,
+    ranges: Option<((f64, f64), (f64, f64))>,
+    weights: Option<&[f64]>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check input lengths match
+    if x.len() != y.len() {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: x.len(),
+            b_arr_len: y.len(),
+        });
+    }
+    if let Some(w) = weights
+        && w.len() != x.len()
+    {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: x.len(),
+            b_arr_len: w.len(),
+        });
+    }
+
+    // set optional parameters if needed
+    let (x_bins, y_bins) = bins.unwrap_or((256, 256));
+    if x_bins == 0 || y_bins == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "bins",
+            value: 0,
+        });
+    }
+    let (x_range, y_range) = ranges.unwrap_or_else(|| (value_range(x), value_range(y)));
+
+    // accumulate the (weighted) pair counts
+    let mut hist = Array2::<f64>::zeros((x_bins, y_bins));
+    x.iter()
+        .zip(y.iter())
+        .enumerate()
+        .for_each(|(k, (&xv, &yv))| {
+            if let (Some(i), Some(j)) = (
+                bin_index(xv.to_f64(), x_range, x_bins),
+                bin_index(yv.to_f64(), y_range, y_bins),
+            ) {
+                hist[[i, j]] += weights.map(|w| w[k]).unwrap_or(1.0);
+            }
+        });
+
+    Ok(hist)
+}
+
+/// Find the finite (min, max) range of a slice of values.
+fn value_range<T>(data: &[T]) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let range = data
+        .iter()
+        .map(|v| v.to_f64())
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(mn, mx), v| {
+            (mn.min(v), mx.max(v))
+        });
+
+    if range.0 > range.1 { (0.0, 0.0) } else { range }
+}
@@ -12,7 +12,8 @@ use crate::traits::numeric::ToFloat64;
 /// coefficient between two datasets. This implementation uses a weighted merge
 /// sort to count discordant pairs (inversions), and applies tie corrections for
 /// both variables to compute the final Tau-b coefficient. Here the weighted
-/// observations contribute unequally to the final correlation coefficient.
+/// observations contribute unequally to the final correlation coefficient,
+/// where each pair of observations `(i, j)` has the weight `wᵢ * wⱼ`.
 ///
 /// The weighted Kendall's Tau-b is calculated using:
 ///
@@ -23,9 +24,16 @@ use crate::traits::numeric::ToFloat64;
 /// Where:
 /// - `C` = number of weighted concordant pairs
 /// - `D` = number of weighted discordant pairs
-/// - `n₀` = total weighted pairs = `(Σwᵢ)² - Σwᵢ²`
-/// - `n₁` = weighted tie correction for first variable
-/// - `n₂` = weighted tie correction for second variable
+/// - `n₀` = total weighted pairs = `((Σwᵢ)² - Σwᵢ²) / 2`
+/// - `n₁` = weighted pairs tied in the first variable
+/// - `n₂` = weighted pairs tied in the second variable
+///
+/// Pairs tied in either variable are neither concordant nor discordant. Pairs
+/// tied in both variables are counted in both `n₁` and `n₂`, so that
+/// `C - D = n₀ - n₁ - n₂ + n₃ - 2D` where `n₃` is the number of weighted pairs
+/// tied in both variables. All tie terms are computed in linear time from the
+/// sorted data, the total cost of this function is _O(n log n)_ even with
+/// heavily tied data (_e.g._ 8-bit images).
 ///
 /// # Arguments
 ///
@@ -40,13 +48,49 @@ use crate::traits::numeric::ToFloat64;
 ///
 /// * `OK(f64)`: The weighted Kendall's Tau-b correlation coefficient, ranging
 ///    between -1.0 (negative correlation), 0.0 (no correlation) and 1.0
-///    (positive correlation).
+///    (positive correlation). If either variable is entirely tied, 0.0 is
+///    returned.
 /// * `Err(ImgalError)`: If input array lengths do not match.
 pub fn weighted_kendall_tau_b<T>(
     data_a: &[T],
     data_b: &[T],
     weights: &[f64],
 ) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    weighted_kendall_tau_b_with_ties(data_a, data_b, weights).map(|(tau, _, _, _)| tau)
+}
+
+/// Compute the weighted Kendall's Tau-b rank correlation coefficient and its
+/// tie correction terms.
+///
+/// # Description
+///
+/// This function computes the weighted Kendall's Tau-b rank correlation
+/// coefficient (see [`weighted_kendall_tau_b`]) and also returns the weighted
+/// tie correction terms used to compute it.
+///
+/// # Arguments
+///
+/// * `data_a`: The first dataset for correlation analysis. Must be the same
+///    length as `data_b`.
+/// * `data_b`: The second dataset for correlation analysis. Must be the same
+///    length as `data_a`.
+/// * `weights`: The associated weights for each observation pait. Must be the
+///    same length as both input datasets.
+///
+/// # Returns
+///
+/// * `OK((f64, f64, f64, f64))`: The weighted Kendall's Tau-b correlation
+///    coefficient and the weighted number of pairs tied in the first variable
+///    (`n₁`), the second variable (`n₂`) and both variables (`n₃`).
+/// * `Err(ImgalError)`: If input array lengths do not match.
+pub fn weighted_kendall_tau_b_with_ties<T>(
+    data_a: &[T],
+    data_b: &[T],
+    weights: &[f64],
+) -> Result<(f64, f64, f64, f64), ImgalError>
 where
     T: ToFloat64,
 {
@@ -61,103 +105,95 @@ where
 
     // can not compute a tau for less than 2 elements
     if dl < 2 {
-        return Ok(0.0);
+        return Ok((0.0, 0.0, 0.0, 0.0));
     }
 
-    // rank the data and create paired data
-    let (a_ranks, a_tie_corr) = rank_with_weights(data_a, weights);
-    let (b_ranks, b_tie_corr) = rank_with_weights(data_b, weights);
-    let mut rank_pairs: Vec<(i32, i32, usize)> = a_ranks
-        .iter()
-        .zip(b_ranks.iter())
-        .enumerate()
-        .map(|(i, (&a, &b))| (a, b, i))
-        .collect();
-    rank_pairs.sort_by_key(|&(a, _, _)| a);
+    // sort the observations by "a" then "b", pairs tied in "a" are now in "b"
+    // order and are not counted as inversions
+    let mut indices: Vec<usize> = (0..dl).collect();
+    indices.sort_unstable_by(|&i, &j| {
+        cmp_values(data_a[i], data_a[j]).then(cmp_values(data_b[i], data_b[j]))
+    });
 
-    // extract b ranks in "a" sorted order and associated weights
-    let mut b_sorted: Vec<i32> = Vec::with_capacity(dl);
+    // extract b values in "a" sorted order and associated weights
+    let mut b_sorted: Vec<T> = Vec::with_capacity(dl);
     let mut w_sorted: Vec<f64> = Vec::with_capacity(dl);
-    rank_pairs.iter().for_each(|&(_, b, i)| {
-        b_sorted.push(b);
+    indices.iter().for_each(|&i| {
+        b_sorted.push(data_b[i]);
         w_sorted.push(weights[i]);
     });
 
-    // count weighted inversions (i.e. swaps)
-    let swaps = weighted_merge_sort_mut(&mut b_sorted, &mut w_sorted).unwrap();
+    // find the weighted tie terms for "a" and joint "a" and "b" ties
+    let a_ties = weighted_tied_pairs(&indices, weights, |i, j| {
+        cmp_values(data_a[i], data_a[j]) == Ordering::Equal
+    });
+    let ab_ties = weighted_tied_pairs(&indices, weights, |i, j| {
+        cmp_values(data_a[i], data_a[j]) == Ordering::Equal
+            && cmp_values(data_b[i], data_b[j]) == Ordering::Equal
+    });
+
+    // count weighted inversions (i.e. discordant pairs), this sorts "b"
+    let swaps = weighted_merge_sort_mut(&mut b_sorted, &mut w_sorted)?;
+
+    // find the weighted tie term for "b" from the sorted "b" values
+    let b_order: Vec<usize> = (0..dl).collect();
+    let b_ties = weighted_tied_pairs(&b_order, &w_sorted, |i, j| {
+        cmp_values(b_sorted[i], b_sorted[j]) == Ordering::Equal
+    });
 
     // calculate total possible weighted pairs
     let total_w: f64 = weights.iter().sum();
     let sum_w_sqr: f64 = weights.iter().map(|w| w.powi(2)).sum();
-    let total_w_pairs = total_w.powi(2) - sum_w_sqr;
+    let total_w_pairs = (total_w.powi(2) - sum_w_sqr) / 2.0;
 
-    // calculate tau-b with tie corrections, discordant pairs and swaps are the same
-    let c_pairs = (total_w_pairs / 2.0) - swaps;
-    let numer = c_pairs - swaps;
+    // calculate tau-b with tie corrections
+    let numer = total_w_pairs - a_ties - b_ties + ab_ties - 2.0 * swaps;
     // denom will become 0 or NaN if the total weighted pairs and tie correction
     // are close, this happens when one of the inputs has the same value in the
     // all or most of the array
-    let denom = ((total_w_pairs - a_tie_corr) * (total_w_pairs - b_tie_corr)).sqrt();
-    if denom != 0.0 && !denom.is_nan() {
-        let tau = numer / denom;
+    let denom = ((total_w_pairs - a_ties) * (total_w_pairs - b_ties)).sqrt();
+    let tau = if denom != 0.0 && !denom.is_nan() {
         // clamp tau to meaningful range of -1.0 and 1.0
-        if tau >= 1.0 {
-            Ok(1.0)
-        } else if tau <= -1.0 {
-            Ok(-1.0)
-        } else {
-            Ok(tau)
-        }
+        (numer / denom).clamp(-1.0, 1.0)
     } else {
-        Ok(0.0)
-    }
+        0.0
+    };
+
+    Ok((tau, a_ties, b_ties, ab_ties))
 }
 
-/// Rank data and associated weights with a Kendall Tau-b tie correction
-fn rank_with_weights<T>(data: &[T], weights: &[f64]) -> (Vec<i32>, f64)
+/// Compare two values, treating incomparable values (_i.e._ NaN) as equal.
+#[inline]
+fn cmp_values<T>(a: T, b: T) -> Ordering
 where
     T: ToFloat64,
 {
-    // create indicies sorted by values
-    let dl = data.len();
-    let mut indices: Vec<usize> = (0..dl).collect();
-    indices.sort_by(|&a, &b| data[a].partial_cmp(&data[b]).unwrap_or(Ordering::Equal));
-
-    // set up rank parameters
-    let mut ranks: Vec<i32> = vec![0; dl];
-    let mut tie_corr = 0.0;
-    let mut cur_rank = 1;
-    let mut i = 0;
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
 
-    while i < dl {
-        let cur_val = data[indices[i]];
-        let mut j = i;
-        let mut tied_indices: Vec<usize> = Vec::new();
-        // find all values tied with current value
-        while j < dl && data[indices[j]].partial_cmp(&cur_val) == Some(Ordering::Equal) {
-            tied_indices.push(indices[j]);
-            j += 1;
-        }
-        // assign average rank to all tied values
-        let group_size = (j - i) as i32;
-        let avg_rank = cur_rank + (group_size - 1) / 2;
-        tied_indices.iter().for_each(|&ti| {
-            ranks[ti] = avg_rank;
-        });
-        // add tie corrections
-        if group_size > 1 {
-            let mut tie_group_corr = 0.0;
-            for k in 0..tied_indices.len() {
-                for l in (k + 1)..tied_indices.len() {
-                    tie_group_corr += weights[tied_indices[k]] * weights[tied_indices[l]];
-                }
-            }
-            // factor of 2 for symmetric pairs
-            tie_corr += 2.0 * tie_group_corr
+/// Sum the weights of tied pairs within runs of tied, sorted observations.
+///
+/// For a run of tied observations the weighted number of tied pairs is
+/// `((Σwᵢ)² - Σwᵢ²) / 2`, computed in a single pass.
+fn weighted_tied_pairs<F>(order: &[usize], weights: &[f64], is_tied: F) -> f64
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut ties = 0.0;
+    let mut run_w = weights[order[0]];
+    let mut run_w_sqr = run_w.powi(2);
+    order.windows(2).for_each(|pair| {
+        let w = weights[pair[1]];
+        if is_tied(pair[0], pair[1]) {
+            run_w += w;
+            run_w_sqr += w.powi(2);
+        } else {
+            ties += (run_w.powi(2) - run_w_sqr) / 2.0;
+            run_w = w;
+            run_w_sqr = w.powi(2);
         }
-        cur_rank += group_size;
-        i = j;
-    }
+    });
+    ties += (run_w.powi(2) - run_w_sqr) / 2.0;
 
-    (ranks, tie_corr)
+    ties
 }
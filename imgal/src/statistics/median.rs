@@ -0,0 +1,27 @@
+/// Compute the median of a slice of values.
+///
+/// # Description
+///
+/// This function computes the median of a slice by sorting it in place with a
+/// total ordering. For an even number of values the median is the mean of the
+/// two middle values. NaN values are sorted after all other values.
+///
+/// # Arguments
+///
+/// * `data`: The input values, sorted in place.
+///
+/// # Returns
+///
+/// * `f64`: The median of the values. If `data` is empty, NaN.
+pub fn median(data: &mut [f64]) -> f64 {
+    if data.is_empty() {
+        return f64::NAN;
+    }
+    data.sort_by(|a, b| a.total_cmp(b));
+    let mid = data.len() / 2;
+    if data.len().is_multiple_of(2) {
+        (data[mid - 1] + data[mid]) / 2.0
+    } else {
+        data[mid]
+    }
+}
@@ -1,13 +1,54 @@
 //! Statistics functions.
+pub mod focus;
+pub use focus::FocusMetric;
+pub use focus::brenner_gradient;
+pub use focus::focus_curve;
+pub use focus::normalized_variance;
+pub use focus::spectral_focus;
+pub use focus::tenengrad;
+pub mod histogram;
+pub use histogram::bin_index;
+pub use histogram::histogram2d;
 pub mod kendall_tau;
 pub use kendall_tau::weighted_kendall_tau_b;
+pub use kendall_tau::weighted_kendall_tau_b_with_ties;
+pub mod median;
+pub use median::median;
 pub mod min_max;
 pub use min_max::max;
 pub use min_max::min;
 pub use min_max::min_max;
+pub mod photon_transfer;
+pub use photon_transfer::adu_to_photons;
+pub use photon_transfer::photon_transfer_curve;
+pub mod pvalue;
+pub use pvalue::PAdjustMethod;
+pub use pvalue::Tail;
+pub use pvalue::p_adjust;
+pub use pvalue::z_to_p;
+pub mod rank;
+pub use rank::RankMethod;
+pub use rank::rank;
+pub use rank::weighted_inversions;
+pub use rank::weighted_merge_sort;
+pub use rank::weighted_merge_sort_mut;
+pub mod reduce;
+pub use reduce::Reduction;
+pub use reduce::reduce_axis;
 pub mod sample;
 pub use sample::effective_sample_size;
+pub use sample::effective_sample_size_axis;
+pub mod snr;
+pub use snr::estimate_snr;
 pub mod sum;
+pub use sum::Summation;
+pub use sum::compensated_sum;
+pub use sum::par_sum;
 pub use sum::sum;
-pub mod sort;
-pub use sort::weighted_merge_sort_mut;
+pub mod weighted;
+pub use weighted::weighted_correlation;
+pub use weighted::weighted_correlation_axis;
+pub use weighted::weighted_mean;
+pub use weighted::weighted_mean_axis;
+pub use weighted::weighted_variance;
+pub use weighted::weighted_variance_axis;
@@ -0,0 +1,151 @@
+use ndarray::{ArrayD, ArrayView3, ArrayViewD};
+
+use crate::error::ImgalError;
+use crate::statistics::{Reduction, reduce_axis};
+use crate::traits::numeric::ToFloat64;
+
+/// Estimate the detector gain, read noise and offset from a stack of repeated
+/// frames.
+///
+/// # Description
+///
+/// This function estimates the detector parameters of a camera with a
+/// photon transfer curve, the linear relationship between the temporal mean
+/// and variance of each pixel across repeated frames of a static scene:
+///
+/// ```text
+/// σ²(x) = g(μ(x) - o) + σᵣ²
+/// ```
+///
+/// Where "g" is the gain in counts (ADU) per photoelectron, "o" is the offset
+/// (ADU) and "σᵣ" is the read noise (ADU). The gain is the slope of an ordinary
+/// least squares fit of variance against mean over all pixels, so the frames
+/// should cover a range of intensities. The intercept of the fit alone can not
+/// separate the offset from the read noise. If `dark_frames` are given, the
+/// offset and read noise are the mean and temporal standard deviation of the
+/// dark frames. Otherwise, the read noise is assumed negligible (_i.e._ 0.0)
+/// and the offset is the intensity at which the fitted variance is zero.
+///
+/// # Arguments
+///
+/// * `frames`: The stack of repeated frames. Must have at least 2 frames.
+/// * `axis`: The frame axis, default = 0.
+/// * `dark_frames`: An optional stack of repeated frames acquired without
+///    light, with the frames along the same `axis`. Must have at least 2
+///    frames.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64, f64))`: The gain (ADU per photoelectron), read noise
+///    (photoelectrons) and offset (ADU).
+/// * `Err(ImgalError)`: If the axis is out of bounds. If there are less than 2
+///    frames. If the variance does not increase with the mean (_i.e._ the gain
+///    is not positive).
+pub fn photon_transfer_curve<T>(
+    frames: ArrayView3<T>,
+    axis: Option<usize>,
+    dark_frames: Option<ArrayView3<T>>,
+) -> Result<(f64, f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let axis = axis.unwrap_or(0);
+
+    // compute the temporal mean and variance of each pixel
+    let (means, vars) = temporal_mean_variance(frames.into_dyn(), axis)?;
+
+    // fit the variance against the mean with ordinary least squares
+    let n = means.len() as f64;
+    let mean_x = means.iter().sum::<f64>() / n;
+    let mean_y = vars.iter().sum::<f64>() / n;
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    means.iter().zip(vars.iter()).for_each(|(&x, &y)| {
+        sxx += (x - mean_x).powi(2);
+        sxy += (x - mean_x) * (y - mean_y);
+    });
+    let gain = sxy / sxx;
+    if gain.is_nan() || gain <= 0.0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The pixel variance does not increase with the pixel mean, the gain can not be estimated.",
+        });
+    }
+    let intercept = mean_y - gain * mean_x;
+
+    // separate the offset and read noise
+    match dark_frames {
+        Some(d) => {
+            let (dark_means, dark_vars) = temporal_mean_variance(d.into_dyn(), axis)?;
+            let offset = dark_means.mean().unwrap_or(0.0);
+            let read_noise = dark_vars.mean().unwrap_or(0.0).sqrt() / gain;
+            Ok((gain, read_noise, offset))
+        }
+        None => Ok((gain, 0.0, -intercept / gain)),
+    }
+}
+
+/// Convert an n-dimensional array of raw detector counts into photon units.
+///
+/// # Description
+///
+/// This function converts raw detector counts (ADU) into photoelectrons with
+/// the detector gain and offset (see [`photon_transfer_curve`]):
+///
+/// ```text
+/// p = (x - o) / g
+/// ```
+///
+/// Negative results are clamped to 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array of raw detector counts.
+/// * `gain`: The detector gain in ADU per photoelectron. Must be positive.
+/// * `offset`: The detector offset in ADU.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The n-dimensional array in photoelectrons.
+/// * `Err(ImgalError)`: If `gain` is not positive.
+pub fn adu_to_photons<T>(
+    data: ArrayViewD<T>,
+    gain: f64,
+    offset: f64,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    if gain.is_nan() || gain <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "gain",
+            value: gain,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    Ok(data.mapv(|v| ((v.to_f64() - offset) / gain).max(0.0)))
+}
+
+/// Compute the temporal mean and unbiased variance of each pixel.
+fn temporal_mean_variance<T>(
+    frames: ArrayViewD<T>,
+    axis: usize,
+) -> Result<(ArrayD<f64>, ArrayD<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    if axis < frames.ndim() && frames.shape()[axis] < 2 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "frames",
+            value: 2,
+        });
+    }
+    let means = reduce_axis(frames.view(), axis, Reduction::Mean)?;
+    let std = reduce_axis(frames.view(), axis, Reduction::Std)?;
+    let n = frames.shape()[axis] as f64;
+    let vars = std.mapv(|s| s * s * n / (n - 1.0));
+
+    Ok((means, vars))
+}
@@ -0,0 +1,134 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::distribution::normal_cdf;
+use crate::error::ImgalError;
+
+/// The tail of a hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tail {
+    /// Two-sided test, `p = 2Φ(-|z|)`.
+    Two,
+    /// One-sided test for large values, `p = Φ(-z)`.
+    Upper,
+    /// One-sided test for small values, `p = Φ(z)`.
+    Lower,
+}
+
+/// Multiple comparison correction methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PAdjustMethod {
+    /// Bonferroni correction, controls the family-wise error rate.
+    Bonferroni,
+    /// Benjamini-Hochberg procedure, controls the false discovery rate.
+    BenjaminiHochberg,
+}
+
+/// Convert a z-score map into a p-value map.
+///
+/// # Description
+///
+/// This function converts each _z-score_ of an n-dimensional map (_e.g._ the
+/// SACA colocalization z-score image) into the p-value of a standard normal
+/// test statistic, with the standard normal cumulative distribution "Φ" (see
+/// [`crate::distribution::normal_cdf`]).
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional _z-score_ map.
+/// * `tail`: The tail of the test, default = `Tail::Two`.
+///
+/// # Returns
+///
+/// * `ArrayD<f64>`: The p-values, with the same shape as `data`. NaN
+///    _z-scores_ have NaN p-values.
+pub fn z_to_p(data: ArrayViewD<f64>, tail: Option<Tail>) -> ArrayD<f64> {
+    // set optional parameters if needed
+    let tail = tail.unwrap_or(Tail::Two);
+
+    let mut p = ArrayD::<f64>::zeros(data.dim());
+    Zip::from(&mut p).and(data).par_for_each(|p, &z| {
+        *p = match tail {
+            Tail::Two => (2.0 * normal_cdf(-z.abs())).clamp(0.0, 1.0),
+            Tail::Upper => normal_cdf(-z),
+            Tail::Lower => normal_cdf(z),
+        };
+    });
+
+    p
+}
+
+/// Adjust a p-value map for multiple comparisons.
+///
+/// # Description
+///
+/// This function adjusts the p-values of an n-dimensional map (_e.g._ one
+/// p-value per pixel) for the number of tests "m", the number of non-NaN
+/// p-values. The Bonferroni correction controls the family-wise error rate:
+///
+/// ```text
+/// p'ᵢ = min(m · pᵢ, 1)
+/// ```
+///
+/// The Benjamini-Hochberg procedure controls the false discovery rate, with
+/// the p-values sorted in ascending order:
+///
+/// ```text
+/// p'₍ᵢ₎ = min(min_{j ≥ i} (m / j) · p₍ⱼ₎, 1)
+/// ```
+///
+/// Tests with an adjusted p-value less than or equal to the significance level
+/// "α" are significant.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional p-value map.
+/// * `method`: The correction method, default =
+///    `PAdjustMethod::BenjaminiHochberg`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The adjusted p-values, with the same shape as `data`.
+///    NaN p-values stay NaN and are not counted as tests.
+/// * `Err(ImgalError)`: If a p-value is less than 0.0 or greater than 1.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1111/j.2517-6161.1995.tb02031.x>
+pub fn p_adjust(
+    data: ArrayViewD<f64>,
+    method: Option<PAdjustMethod>,
+) -> Result<ArrayD<f64>, ImgalError> {
+    // set optional parameters if needed
+    let method = method.unwrap_or(PAdjustMethod::BenjaminiHochberg);
+
+    // check if parameters are valid
+    if let Some(&p) = data.iter().find(|p| **p < 0.0 || **p > 1.0) {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "p",
+            value: p,
+            min: 0.0,
+            max: 1.0,
+        });
+    }
+
+    let m = data.iter().filter(|p| !p.is_nan()).count() as f64;
+    match method {
+        PAdjustMethod::Bonferroni => Ok(data.mapv(|p| (p * m).clamp(0.0, 1.0))),
+        PAdjustMethod::BenjaminiHochberg => {
+            // sort the tested p-values, then take the running minimum from the
+            // largest p-value down
+            let values: Vec<f64> = data.iter().copied().collect();
+            let mut order: Vec<usize> =
+                (0..values.len()).filter(|&i| !values[i].is_nan()).collect();
+            order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+            let mut adjusted = values.clone();
+            let mut running = 1.0_f64;
+            order.iter().enumerate().rev().for_each(|(k, &i)| {
+                running = running.min(values[i] * m / (k + 1) as f64);
+                adjusted[i] = running;
+            });
+
+            Ok(ArrayD::from_shape_vec(data.raw_dim(), adjusted).unwrap())
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::cmp::Ordering;
+
+use ndarray::{ArrayD, ArrayViewD};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Tie handling methods for ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankMethod {
+    /// Tied values get the average of the ranks they span (_i.e._ fractional
+    /// ranks), as used by Spearman's correlation and the Mann-Whitney U test.
+    Average,
+    /// Tied values get the same rank and ranks have no gaps.
+    Dense,
+    /// Tied values get the lowest of the ranks they span.
+    Min,
+    /// Each value gets a distinct rank, tied values are ranked in order of
+    /// appearance.
+    Ordinal,
+}
+
+/// Rank the values of an n-dimensional array.
+///
+/// # Description
+///
+/// This function ranks the values of an n-dimensional array in ascending order,
+/// starting at 1. Values are compared in logical (row-major) order and NaN
+/// values are ranked after all other values. Tied values are ranked according
+/// to `method`, for example the values `[10, 20, 20, 30]` are ranked:
+///
+/// ```text
+/// Average: [1.0, 2.5, 2.5, 4.0]
+/// Dense:   [1.0, 2.0, 2.0, 3.0]
+/// Min:     [1.0, 2.0, 2.0, 4.0]
+/// Ordinal: [1.0, 2.0, 3.0, 4.0]
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `method`: The tie handling method, default = `RankMethod::Average`.
+///
+/// # Returns
+///
+/// * `ArrayD<f64>`: The ranks, with the same shape as `data`.
+pub fn rank<T>(data: ArrayViewD<T>, method: Option<RankMethod>) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let method = method.unwrap_or(RankMethod::Average);
+
+    // sort the value indices, a stable sort keeps ties in order of appearance
+    let values: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+
+    // assign ranks to each run of tied values
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    let mut dense = 0.0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]].total_cmp(&values[order[start]]).is_eq() {
+            end += 1;
+        }
+        dense += 1.0;
+        order[start..end].iter().enumerate().for_each(|(k, &i)| {
+            ranks[i] = match method {
+                RankMethod::Average => (start + end + 1) as f64 / 2.0,
+                RankMethod::Dense => dense,
+                RankMethod::Min => (start + 1) as f64,
+                RankMethod::Ordinal => (start + k + 1) as f64,
+            };
+        });
+        start = end;
+    }
+
+    ArrayD::from_shape_vec(data.raw_dim(), ranks).unwrap()
+}
+
+/// Count the weighted inversions of an n-dimensional array.
+///
+/// # Description
+///
+/// This function counts the weighted inversions (_i.e._ pairs of values out of
+/// ascending order) of an n-dimensional array in logical (row-major) order,
+/// where each inversion `(i, j)` has the weight `wᵢ * wⱼ`. The inputs are not
+/// mutated, see [`weighted_merge_sort_mut`].
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `weights`: The weights of each value, the same shape as `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted inversion count.
+/// * `Err(ImgalError)`: If the shapes of `data` and `weights` do not match.
+pub fn weighted_inversions<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    if data.shape() != weights.shape() {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: data.shape().to_vec(),
+            shape_b: weights.shape().to_vec(),
+        });
+    }
+    let mut d: Vec<T> = data.iter().copied().collect();
+    let mut w: Vec<f64> = weights.iter().copied().collect();
+
+    weighted_merge_sort_mut(&mut d, &mut w)
+}
+
+/// Sort 1-dimensional arrays of values and their associated weights without
+/// mutating the inputs.
+///
+/// # Description
+///
+/// This function sorts copies of the input arrays, see
+/// [`weighted_merge_sort_mut`].
+///
+/// # Arguments
+///
+/// * `data`: A 1-dimensional array/slice of numbers of the same length as
+///    `weights`.
+/// * `weights`: A 1-dimensional array/slice of weights of the same length as
+///    `data`.
+///
+/// # Returns
+///
+/// * `OK((Vec<T>, Vec<f64>, f64))`: The sorted data, the weights in sorted
+///    data order and the weighted inversion count.
+/// * `Err(ImgalError)`: If the data and weights array lengths do not match.
+pub fn weighted_merge_sort<T>(
+    data: &[T],
+    weights: &[f64],
+) -> Result<(Vec<T>, Vec<f64>, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut d = data.to_vec();
+    let mut w = weights.to_vec();
+    let swaps = weighted_merge_sort_mut(&mut d, &mut w)?;
+
+    Ok((d, w, swaps))
+}
+
+/// Sort 1-dimensional arrays of values and their associated weights.
+///
+/// # Description
+///
+/// This function performs a bottom up merge sort on the input 1-dimensional
+/// data array along with it's associated weights. Both the `data` and `weights`
+/// arrays are _mutated_ during the sorting. The output of this function is a
+/// weighted inversion count.
+///
+/// # Arguments
+///
+/// * `data`: A 1-dimensional array/slice of numbers of the same length as
+///    `weights`.
+/// * `weights`: A 1-dimensional array/slice of weights of the same length as
+///    `data`.
+///
+/// # Returns
+///
+/// * `OK(f64)`: The number of swaps needed to sort the input array.
+/// * `Err(ImgalError)`: If the data and weights array lengths do not match.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn weighted_merge_sort_mut<T>(data: &mut [T], weights: &mut [f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // ensure input arrays are same length
+    let dl = data.len();
+    let wl = weights.len();
+    if dl != wl {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: dl,
+            b_arr_len: wl,
+        });
+    };
+
+    // counters for weighted inversions (i.e. swaps)
+    let mut swap = 0.0;
+    let mut swap_temp: f64;
+
+    // define step and cursors
+    let mut step: usize = 1;
+    let mut left: usize;
+    let mut right: usize;
+    let mut end: usize;
+    let mut k: usize;
+
+    // create working buffers
+    let mut data_buf = vec![T::default(); dl];
+    let mut weights_buf = vec![0.0; dl];
+    let mut cum_weights_buf = vec![0.0; dl];
+
+    // weighted bottom-up merge sort
+    while step < dl {
+        left = 0;
+        k = 0;
+        let mut cw_acc = weights[0];
+        cum_weights_buf[0] = weights[0];
+        cum_weights_buf
+            .iter_mut()
+            .zip(weights.iter())
+            .skip(1)
+            .for_each(|(cw, w)| {
+                *cw = cw_acc + w;
+                cw_acc = *cw;
+            });
+
+        loop {
+            right = left + step;
+            end = right + step;
+            if end > dl {
+                if right > dl {
+                    break;
+                }
+                end = dl;
+            }
+            let mut l = left;
+            let mut r = right;
+            while l < right && r < end {
+                match data[l].partial_cmp(&data[r]) {
+                    Some(Ordering::Greater) => {
+                        if l == 0 {
+                            swap_temp = weights[r] * cum_weights_buf[right - 1];
+                        } else {
+                            swap_temp =
+                                weights[r] * (cum_weights_buf[right - 1] - cum_weights_buf[l - 1]);
+                        }
+                        swap += swap_temp;
+                        data_buf[k] = data[r];
+                        weights_buf[k] = weights[r];
+                        k += 1;
+                        r += 1;
+                    }
+                    _ => {
+                        data_buf[k] = data[l];
+                        weights_buf[k] = weights[l];
+                        k += 1;
+                        l += 1;
+                    }
+                }
+            }
+            if l < right {
+                while l < right {
+                    data_buf[k] = data[l];
+                    weights_buf[k] = weights[l];
+                    k += 1;
+                    l += 1;
+                }
+            } else {
+                while r < end {
+                    data_buf[k] = data[r];
+                    weights_buf[k] = weights[r];
+                    k += 1;
+                    r += 1;
+                }
+            }
+            left = end;
+        }
+
+        // copy any unmerged tail, if array size is not a power of 2
+        if k < dl {
+            while k < dl {
+                data_buf[k] = data[k];
+                weights_buf[k] = weights[k];
+                k += 1;
+            }
+        }
+
+        // prepare for the next step, copy merged results back source
+        data.clone_from_slice(&data_buf);
+        weights.clone_from_slice(&weights_buf);
+
+        // double the run size, continue
+        step *= 2;
+    }
+
+    Ok(swap)
+}
@@ -0,0 +1,85 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, RemoveAxis, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Reductions available for per-axis array reductions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    /// Sum of the lane values.
+    Sum,
+    /// Arithmetic mean of the lane values.
+    Mean,
+    /// Maximum lane value.
+    Max,
+    /// Minimum lane value.
+    Min,
+    /// Population standard deviation of the lane values.
+    Std,
+}
+
+/// Reduce an n-dimensional array along an axis.
+///
+/// # Description
+///
+/// This function reduces each 1-dimensional lane along the given axis to a
+/// single value (_e.g._ a sum or maximum intensity projection) in parallel,
+/// producing an (n - 1)-dimensional array.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `axis`: The axis to reduce.
+/// * `reduction`: The reduction applied to each lane.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The reduced (n - 1)-dimensional array. Reductions of
+///    empty lanes are 0.0, except the mean and standard deviation which are
+///    NaN.
+/// * `Err(ImgalError)`: If the axis is out of bounds.
+pub fn reduce_axis<T>(
+    data: ArrayViewD<T>,
+    axis: usize,
+    reduction: Reduction,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if axis parameter is valid
+    if axis >= data.ndim() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: data.ndim(),
+        });
+    }
+
+    // reduce each lane in parallel
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim().remove_axis(Axis(axis)));
+    Zip::from(&mut output)
+        .and(data.lanes(Axis(axis)))
+        .par_for_each(|o, ln| {
+            let n = ln.len() as f64;
+            *o = match reduction {
+                Reduction::Sum => ln.iter().map(|v| v.to_f64()).sum(),
+                Reduction::Mean => ln.iter().map(|v| v.to_f64()).sum::<f64>() / n,
+                Reduction::Max => ln
+                    .iter()
+                    .map(|v| v.to_f64())
+                    .reduce(f64::max)
+                    .unwrap_or(0.0),
+                Reduction::Min => ln
+                    .iter()
+                    .map(|v| v.to_f64())
+                    .reduce(f64::min)
+                    .unwrap_or(0.0),
+                Reduction::Std => {
+                    let mean = ln.iter().map(|v| v.to_f64()).sum::<f64>() / n;
+                    let var = ln.iter().map(|v| (v.to_f64() - mean).powi(2)).sum::<f64>() / n;
+                    var.sqrt()
+                }
+            };
+        });
+
+    Ok(output)
+}
@@ -1,3 +1,7 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, RemoveAxis, Zip};
+
+use crate::error::ImgalError;
+
 /// Compute the effective sample size (ESS) of a weighted sample set.
 ///
 /// # Description
@@ -33,3 +37,47 @@ pub fn effective_sample_size(weights: &[f64]) -> f64 {
         sum_w.powi(2) / sum_sqr_w
     }
 }
+
+/// Compute the effective sample size (ESS) of weighted sample sets along an
+/// axis of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the effective sample size (ESS, see
+/// [`effective_sample_size`]) of each 1-dimensional lane of weights along the
+/// given axis.
+///
+/// # Arguments
+///
+/// * `weights`: The n-dimensional array of non-negative weights.
+/// * `axis`: The sample axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The effective number of independent samples of each
+///    lane, with the sample axis removed.
+/// * `Err(ImgalError)`: If the axis is out of bounds.
+pub fn effective_sample_size_axis(
+    weights: ArrayViewD<f64>,
+    axis: usize,
+) -> Result<ArrayD<f64>, ImgalError> {
+    // check if axis parameter is valid
+    if axis >= weights.ndim() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: weights.ndim(),
+        });
+    }
+
+    let mut ess = ArrayD::<f64>::zeros(weights.raw_dim().remove_axis(Axis(axis)));
+    Zip::from(&mut ess)
+        .and(weights.lanes(Axis(axis)))
+        .par_for_each(|e, ln| {
+            *e = match ln.as_slice() {
+                Some(w) => effective_sample_size(w),
+                None => effective_sample_size(&ln.to_vec()),
+            };
+        });
+
+    Ok(ess)
+}
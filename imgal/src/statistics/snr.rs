@@ -0,0 +1,138 @@
+use ndarray::ArrayViewD;
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Estimate the signal-to-noise ratio (SNR) of an image or image region.
+///
+/// # Description
+///
+/// This function estimates the linear signal-to-noise ratio of an
+/// n-dimensional image. If a background region is given, the noise is measured
+/// directly from the background:
+///
+/// ```text
+/// SNR = (μₛ - μ_b) / σ_b
+/// ```
+///
+/// Where "μₛ" is the mean of the signal region, "μ_b" is the mean of the
+/// background region and "σ_b" is the standard deviation of the background
+/// region. If no background region is given, the image is assumed to be
+/// limited by Poisson (_i.e._ shot) noise, where the variance equals the mean
+/// photon count:
+///
+/// ```text
+/// SNR = μₛ / √(g * μₛ) = √(μₛ / g)
+/// ```
+///
+/// Where "g" is the detector gain (counts per photon).
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `signal_mask`: An optional n-dimensional mask of the signal region, where
+///    `true` values are signal. Must have the same shape as `data`. If `None`,
+///    all elements not in the background region are used.
+/// * `background_mask`: An optional n-dimensional mask of the background
+///    region, where `true` values are background. Must have the same shape as
+///    `data`. If `None`, the Poisson noise model is used.
+/// * `gain`: The detector gain in counts per photon, used by the Poisson noise
+///    model, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The estimated linear signal-to-noise ratio. If the background
+///    has no variance, infinity is returned.
+/// * `Err(ImgalError)`: If the mask shapes do not match the image shape. If the
+///    signal or background region is empty. If `gain` is not positive.
+pub fn estimate_snr<T>(
+    data: ArrayViewD<T>,
+    signal_mask: Option<ArrayViewD<bool>>,
+    background_mask: Option<ArrayViewD<bool>>,
+    gain: Option<f64>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let gain = gain.unwrap_or(1.0);
+
+    // check parameters are valid
+    if let Some(m) = signal_mask.as_ref() {
+        check_shapes(data.shape(), m.shape())?;
+    }
+    if let Some(m) = background_mask.as_ref() {
+        check_shapes(data.shape(), m.shape())?;
+    }
+    if gain.is_nan() || gain <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "gain",
+            value: gain,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+
+    // split the image into signal and background samples
+    let mut signal: Vec<f64> = Vec::new();
+    let mut background: Vec<f64> = Vec::new();
+    let mut sig_iter = signal_mask.as_ref().map(|m| m.iter());
+    let mut bg_iter = background_mask.as_ref().map(|m| m.iter());
+    data.iter().for_each(|v| {
+        let is_bg = bg_iter.as_mut().is_some_and(|it| *it.next().unwrap());
+        let is_sig = match sig_iter.as_mut() {
+            Some(it) => *it.next().unwrap(),
+            None => !is_bg,
+        };
+        if is_sig {
+            signal.push(v.to_f64());
+        }
+        if is_bg {
+            background.push(v.to_f64());
+        }
+    });
+    if signal.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The signal region is empty.",
+        });
+    }
+    let mean_sig = signal.iter().sum::<f64>() / signal.len() as f64;
+
+    // Poisson noise model without a background region
+    if background_mask.is_none() {
+        return Ok((mean_sig.max(0.0) / gain).sqrt());
+    }
+
+    // measured noise model with a background region
+    if background.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The background region is empty.",
+        });
+    }
+    let n_bg = background.len() as f64;
+    let mean_bg = background.iter().sum::<f64>() / n_bg;
+    let std_bg = (background
+        .iter()
+        .map(|v| (v - mean_bg).powi(2))
+        .sum::<f64>()
+        / n_bg)
+        .sqrt();
+
+    if std_bg > 0.0 {
+        Ok((mean_sig - mean_bg) / std_bg)
+    } else {
+        Ok(f64::INFINITY)
+    }
+}
+
+/// Check that two array shapes match.
+fn check_shapes(shape_a: &[usize], shape_b: &[usize]) -> Result<(), ImgalError> {
+    if shape_a != shape_b {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape_a.to_vec(),
+            shape_b: shape_b.to_vec(),
+        });
+    }
+
+    Ok(())
+}
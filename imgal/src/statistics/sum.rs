@@ -1,10 +1,33 @@
+use rayon::prelude::*;
+
 use crate::traits::numeric::ToFloat64;
 
+// number of elements summed sequentially per block in "par_sum"
+const PAR_SUM_BLOCK: usize = 4096;
+// number of elements summed sequentially at the leaves of a pairwise sum
+const PAIRWISE_BLOCK: usize = 128;
+
+/// Summation algorithms for accumulating long sequences of numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Summation {
+    /// Sequential left to right accumulation, with an error growing linearly
+    /// with the number of values.
+    Naive,
+    /// Recursive pairwise (cascade) summation, with an error growing
+    /// logarithmically with the number of values.
+    Pairwise,
+    /// Kahan-Babuška (Neumaier) compensated summation, with an error
+    /// independent of the number of values.
+    Kahan,
+}
+
 /// Compute the sum of the slice of numbers.
 ///
 /// # Description
 ///
-/// Computes the sum of numbers in the input slice.
+/// Computes the sum of numbers in the input slice. The values are accumulated
+/// sequentially from left to right, so the result is always reproducible. Use
+/// [`compensated_sum`] to sum long sequences with pairwise or Kahan summation.
 ///
 /// # Arguments
 ///
@@ -36,3 +59,102 @@ where
 {
     data.iter().fold(T::default(), |acc, &v| acc + v)
 }
+
+/// Compute the sum of the slice of numbers with a selectable algorithm.
+///
+/// # Description
+///
+/// Computes the sum of numbers in the input slice, accumulated as 64-bit
+/// floats with the given summation algorithm. Accumulating as 64-bit floats
+/// avoids the overflow of integer types (_e.g._ summing a large `u16` image)
+/// and the compensated algorithms limit the loss of precision of long
+/// sequences.
+///
+/// # Arguments
+///
+/// * `data`: A slice of numbers.
+/// * `summation`: The summation algorithm, default = `Summation::Naive`.
+///
+/// # Returns
+///
+/// * `f64`: The sum.
+pub fn compensated_sum<T>(data: &[T], summation: Option<Summation>) -> f64
+where
+    T: ToFloat64,
+{
+    match summation.unwrap_or(Summation::Naive) {
+        Summation::Naive => data.iter().fold(0.0, |acc, &v| acc + v.to_f64()),
+        Summation::Pairwise => pairwise_f64(data),
+        Summation::Kahan => {
+            let mut total = 0.0;
+            let mut comp = 0.0;
+            data.iter().for_each(|&v| {
+                let v = v.to_f64();
+                let t = total + v;
+                // recover the low order bits lost by the larger operand
+                if total.abs() >= v.abs() {
+                    comp += (total - t) + v;
+                } else {
+                    comp += (v - t) + total;
+                }
+                total = t;
+            });
+            total + comp
+        }
+    }
+}
+
+/// Compute the sum of the slice of numbers in parallel.
+///
+/// # Description
+///
+/// Computes the sum of numbers in the input slice by splitting the slice into
+/// fixed size blocks of `PAR_SUM_BLOCK` elements. Each block is summed
+/// sequentially in parallel and the block sums are then combined with a
+/// pairwise (tree) reduction in block order. Because the block boundaries and
+/// the combination order only depend on the length of the input, the result
+/// is bit-identical between runs regardless of the number of threads in the
+/// rayon thread pool.
+///
+/// # Arguments
+///
+/// * `data`: A slice of numbers.
+///
+/// # Returns
+///
+/// * `T`: The sum.
+pub fn par_sum<T>(data: &[T]) -> T
+where
+    T: ToFloat64,
+{
+    let block_sums: Vec<T> = data.par_chunks(PAR_SUM_BLOCK).map(sum).collect();
+    pairwise(&block_sums)
+}
+
+/// Combine the partial sums with a pairwise reduction in a fixed order.
+fn pairwise<T>(data: &[T]) -> T
+where
+    T: ToFloat64,
+{
+    match data.len() {
+        0 => T::default(),
+        1 => data[0],
+        n => {
+            let (left, right) = data.split_at(n / 2);
+            pairwise(left) + pairwise(right)
+        }
+    }
+}
+
+/// Pairwise sum of the slice of numbers accumulated as 64-bit floats.
+fn pairwise_f64<T>(data: &[T]) -> f64
+where
+    T: ToFloat64,
+{
+    if data.len() <= PAIRWISE_BLOCK {
+        data.iter().fold(0.0, |acc, &v| acc + v.to_f64())
+    } else {
+        let (left, right) = data.split_at(data.len() / 2);
+        pairwise_f64(left) + pairwise_f64(right)
+    }
+}
@@ -0,0 +1,365 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, RemoveAxis, Zip};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the weighted mean of a sample set.
+///
+/// # Description
+///
+/// This function computes the weighted arithmetic mean of a sample set:
+///
+/// ```text
+/// μ = Σ wᵢxᵢ / Σ wᵢ
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The sample set. Must be the same length as `weights`.
+/// * `weights`: The non-negative weights of each sample. Must be the same
+///    length as `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted mean. If the sum of weights is 0.0, NaN is
+///    returned.
+/// * `Err(ImgalError)`: If the `data` and `weights` lengths do not match.
+pub fn weighted_mean<T>(data: &[T], weights: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_lengths(data.len(), weights.len())?;
+
+    Ok(mean(data, weights))
+}
+
+/// Compute the weighted mean of sample sets along an axis of an n-dimensional
+/// array.
+///
+/// # Description
+///
+/// This function computes the weighted mean (see [`weighted_mean`]) of each
+/// 1-dimensional lane of samples along the given axis.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional sample array. Must have the same shape as
+///    `weights`.
+/// * `weights`: The n-dimensional array of non-negative weights. Must have the
+///    same shape as `data`.
+/// * `axis`: The sample axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The weighted mean of each lane, with the sample axis
+///    removed.
+/// * `Err(ImgalError)`: If the `data` and `weights` shapes do not match. If the
+///    axis is out of bounds.
+pub fn weighted_mean_axis<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    axis: usize,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_shapes_and_axis(data.shape(), weights.shape(), axis)?;
+
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim().remove_axis(Axis(axis)));
+    Zip::from(&mut output)
+        .and(data.lanes(Axis(axis)))
+        .and(weights.lanes(Axis(axis)))
+        .par_for_each(|o, d_ln, w_ln| {
+            *o = mean(&d_ln.to_vec(), &w_ln.to_vec());
+        });
+
+    Ok(output)
+}
+
+/// Compute the weighted variance of a sample set.
+///
+/// # Description
+///
+/// This function computes the weighted variance of a sample set. The biased
+/// (population) variance is:
+///
+/// ```text
+/// σ² = Σ wᵢ(xᵢ - μ)² / V₁
+/// ```
+///
+/// The unbiased variance, treating the weights as reliability weights, is:
+///
+/// ```text
+/// s² = Σ wᵢ(xᵢ - μ)² / (V₁ - V₂ / V₁)
+/// ```
+///
+/// Where "V₁" is the sum of weights and "V₂" is the sum of squared weights.
+///
+/// # Arguments
+///
+/// * `data`: The sample set. Must be the same length as `weights`.
+/// * `weights`: The non-negative weights of each sample. Must be the same
+///    length as `data`.
+/// * `unbiased`: If `true`, the unbiased reliability weighted variance is
+///    computed, default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted variance. If the variance is undefined (_e.g._
+///    the sum of weights is 0.0), NaN is returned.
+/// * `Err(ImgalError)`: If the `data` and `weights` lengths do not match.
+pub fn weighted_variance<T>(
+    data: &[T],
+    weights: &[f64],
+    unbiased: Option<bool>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_lengths(data.len(), weights.len())?;
+
+    Ok(variance(data, weights, unbiased.unwrap_or(false)))
+}
+
+/// Compute the weighted variance of sample sets along an axis of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the weighted variance (see [`weighted_variance`]) of
+/// each 1-dimensional lane of samples along the given axis.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional sample array. Must have the same shape as
+///    `weights`.
+/// * `weights`: The n-dimensional array of non-negative weights. Must have the
+///    same shape as `data`.
+/// * `axis`: The sample axis.
+/// * `unbiased`: If `true`, the unbiased reliability weighted variance is
+///    computed, default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The weighted variance of each lane, with the sample
+///    axis removed.
+/// * `Err(ImgalError)`: If the `data` and `weights` shapes do not match. If the
+///    axis is out of bounds.
+pub fn weighted_variance_axis<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    axis: usize,
+    unbiased: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_shapes_and_axis(data.shape(), weights.shape(), axis)?;
+
+    // set optional parameters if needed
+    let unbiased = unbiased.unwrap_or(false);
+
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim().remove_axis(Axis(axis)));
+    Zip::from(&mut output)
+        .and(data.lanes(Axis(axis)))
+        .and(weights.lanes(Axis(axis)))
+        .par_for_each(|o, d_ln, w_ln| {
+            *o = variance(&d_ln.to_vec(), &w_ln.to_vec(), unbiased);
+        });
+
+    Ok(output)
+}
+
+/// Compute the weighted Pearson correlation coefficient of two sample sets.
+///
+/// # Description
+///
+/// This function computes the weighted Pearson correlation coefficient between
+/// two paired sample sets:
+///
+/// ```text
+/// r = Σ wᵢ(aᵢ - μₐ)(bᵢ - μ_b) / √(Σ wᵢ(aᵢ - μₐ)² * Σ wᵢ(bᵢ - μ_b)²)
+/// ```
+///
+/// Where "μₐ" and "μ_b" are the weighted means of each sample set.
+///
+/// # Arguments
+///
+/// * `data_a`: The first sample set. Must be the same length as `data_b` and
+///    `weights`.
+/// * `data_b`: The second sample set. Must be the same length as `data_a` and
+///    `weights`.
+/// * `weights`: The non-negative weights of each sample pair.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted correlation coefficient, ranging between -1.0 and
+///    1.0. If either sample set has no weighted variance, 0.0 is returned.
+/// * `Err(ImgalError)`: If the input lengths do not match.
+pub fn weighted_correlation<T>(
+    data_a: &[T],
+    data_b: &[T],
+    weights: &[f64],
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_lengths(data_a.len(), data_b.len())?;
+    check_lengths(data_a.len(), weights.len())?;
+
+    Ok(correlation(data_a, data_b, weights))
+}
+
+/// Compute the weighted Pearson correlation coefficients of paired sample sets
+/// along an axis of two n-dimensional arrays.
+///
+/// # Description
+///
+/// This function computes the weighted Pearson correlation coefficient (see
+/// [`weighted_correlation`]) of each pair of 1-dimensional lanes along the
+/// given axis.
+///
+/// # Arguments
+///
+/// * `data_a`: The first n-dimensional sample array.
+/// * `data_b`: The second n-dimensional sample array. Must have the same shape
+///    as `data_a`.
+/// * `weights`: The n-dimensional array of non-negative weights. Must have the
+///    same shape as `data_a`.
+/// * `axis`: The sample axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The weighted correlation coefficient of each lane pair,
+///    with the sample axis removed.
+/// * `Err(ImgalError)`: If the input shapes do not match. If the axis is out of
+///    bounds.
+pub fn weighted_correlation_axis<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    axis: usize,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    check_shapes_and_axis(data_a.shape(), data_b.shape(), axis)?;
+    check_shapes_and_axis(data_a.shape(), weights.shape(), axis)?;
+
+    let mut output = ArrayD::<f64>::zeros(data_a.raw_dim().remove_axis(Axis(axis)));
+    Zip::from(&mut output)
+        .and(data_a.lanes(Axis(axis)))
+        .and(data_b.lanes(Axis(axis)))
+        .and(weights.lanes(Axis(axis)))
+        .par_for_each(|o, a_ln, b_ln, w_ln| {
+            *o = correlation(&a_ln.to_vec(), &b_ln.to_vec(), &w_ln.to_vec());
+        });
+
+    Ok(output)
+}
+
+/// Check that two slice lengths match.
+fn check_lengths(a_len: usize, b_len: usize) -> Result<(), ImgalError> {
+    if a_len != b_len {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: a_len,
+            b_arr_len: b_len,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that two array shapes match and the axis is in bounds.
+fn check_shapes_and_axis(
+    shape_a: &[usize],
+    shape_b: &[usize],
+    axis: usize,
+) -> Result<(), ImgalError> {
+    if shape_a != shape_b {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape_a.to_vec(),
+            shape_b: shape_b.to_vec(),
+        });
+    }
+    if axis >= shape_a.len() {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: shape_a.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Weighted correlation of equal length slices.
+fn correlation<T>(data_a: &[T], data_b: &[T], weights: &[f64]) -> f64
+where
+    T: ToFloat64,
+{
+    let mean_a = mean(data_a, weights);
+    let mean_b = mean(data_b, weights);
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    data_a
+        .iter()
+        .zip(data_b.iter())
+        .zip(weights.iter())
+        .for_each(|((&a, &b), &w)| {
+            let da = a.to_f64() - mean_a;
+            let db = b.to_f64() - mean_b;
+            cov += w * da * db;
+            var_a += w * da * da;
+            var_b += w * db * db;
+        });
+    let denom = (var_a * var_b).sqrt();
+
+    if denom > 0.0 {
+        (cov / denom).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Weighted mean of equal length slices.
+fn mean<T>(data: &[T], weights: &[f64]) -> f64
+where
+    T: ToFloat64,
+{
+    let mut sum_w = 0.0;
+    let mut sum_wx = 0.0;
+    data.iter().zip(weights.iter()).for_each(|(&x, &w)| {
+        sum_w += w;
+        sum_wx += w * x.to_f64();
+    });
+
+    if sum_w == 0.0 {
+        f64::NAN
+    } else {
+        sum_wx / sum_w
+    }
+}
+
+/// Weighted variance of equal length slices.
+fn variance<T>(data: &[T], weights: &[f64], unbiased: bool) -> f64
+where
+    T: ToFloat64,
+{
+    let mu = mean(data, weights);
+    let mut v1 = 0.0;
+    let mut v2 = 0.0;
+    let mut sum_sqr_dev = 0.0;
+    data.iter().zip(weights.iter()).for_each(|(&x, &w)| {
+        v1 += w;
+        v2 += w * w;
+        sum_sqr_dev += w * (x.to_f64() - mu).powi(2);
+    });
+    let denom = if unbiased { v1 - v2 / v1 } else { v1 };
+
+    if denom > 0.0 {
+        sum_sqr_dev / denom
+    } else {
+        f64::NAN
+    }
+}
@@ -1,3 +1,5 @@
 //! Threshold functions.
 pub mod manual;
 pub use manual::manual_mask;
+pub mod otsu;
+pub use otsu::{otsu_mask, otsu_value};
@@ -0,0 +1,118 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::image::{histogram, histogram_bin_edges};
+use crate::statistics::min_max;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute Otsu's threshold value of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the threshold that separates the image histogram
+/// into a background and a foreground class with the maximum between class
+/// variance:
+///
+/// ```text
+/// σ²ᵦ(k) = ω₀(k) ω₁(k) (μ₀(k) - μ₁(k))²
+/// ```
+///
+/// Where "ω" and "μ" are the fractions and mean values of the classes below
+/// and above histogram bin "k". The threshold is the upper edge of the last
+/// background bin, so values greater than the threshold are foreground.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `bins`: The number of histogram bins, default = 256.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: Otsu's threshold value. If all values of `data` are equal,
+///    that value is returned.
+/// * `Err(ImgalError)`: If `data` is empty. If `bins` is less than 2.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TSMC.1979.4310076>
+pub fn otsu_value<T>(data: ArrayViewD<T>, bins: Option<usize>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+
+    // check if parameters are valid
+    if data.is_empty() {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "Invalid array, the input image must not be empty.",
+        });
+    }
+    if bins < 2 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "bins",
+            value: 2,
+        });
+    }
+
+    let (min, max) = min_max(data.view());
+    let (min, max) = (min.to_f64(), max.to_f64());
+    if min == max {
+        return Ok(min);
+    }
+    let hist = histogram(data, Some(bins), Some((min, max)));
+    let edges = histogram_bin_edges(bins, (min, max));
+
+    // maximize the between class variance over the bin index
+    let total: f64 = hist.iter().map(|&c| c as f64).sum();
+    let centers: Vec<f64> = edges.windows(2).map(|e| (e[0] + e[1]) / 2.0).collect();
+    let sum_all: f64 = hist.iter().zip(&centers).map(|(&c, x)| c as f64 * x).sum();
+    let mut w_0 = 0.0;
+    let mut sum_0 = 0.0;
+    let mut best = (0, f64::NEG_INFINITY);
+    for k in 0..bins - 1 {
+        w_0 += hist[k] as f64;
+        sum_0 += hist[k] as f64 * centers[k];
+        let w_1 = total - w_0;
+        if w_0 == 0.0 || w_1 == 0.0 {
+            continue;
+        }
+        let diff = sum_0 / w_0 - (sum_all - sum_0) / w_1;
+        let var = w_0 * w_1 * diff * diff;
+        if var > best.1 {
+            best = (k, var);
+        }
+    }
+
+    Ok(edges[best.0 + 1])
+}
+
+/// Create a boolean mask with Otsu's threshold.
+///
+/// # Description
+///
+/// This function computes Otsu's threshold (see [`otsu_value`]) and creates a
+/// threshold mask (see [`crate::threshold::manual_mask`]) of the input image.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `bins`: The number of histogram bins, default = 256.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<bool>)`: A boolean array of the same shape as the input image
+///    with pixels that are greater than the threshold value set as `true`.
+/// * `Err(ImgalError)`: If `data` is empty. If `bins` is less than 2.
+pub fn otsu_mask<T>(data: ArrayViewD<T>, bins: Option<usize>) -> Result<ArrayD<bool>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let threshold = otsu_value(data.view(), bins)?;
+    let mut mask = ArrayD::<bool>::default(data.dim());
+    Zip::from(data).and(&mut mask).par_for_each(|ip, mp| {
+        *mp = ip.to_f64() > threshold;
+    });
+
+    Ok(mask)
+}
@@ -0,0 +1,267 @@
+use ndarray::{Array2, ArrayView2};
+
+use crate::error::ImgalError;
+
+/// Frame-to-frame linking methods available for particle tracking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMethod {
+    /// Greedy nearest-neighbor linking, shortest distances first.
+    NearestNeighbor,
+    /// Globally optimal linear assignment problem (LAP) linking.
+    Lap,
+}
+
+/// Link per-frame particle detections into trajectories.
+///
+/// # Description
+///
+/// This function links the particle detections of consecutive frames into
+/// trajectories (_i.e._ single-particle tracking). For each frame, the
+/// detections are linked to the last positions of the active tracks, where
+/// the cost of a link is the squared Euclidean distance. Links longer than
+/// `max_distance` are not allowed. Unlinked detections start new tracks.
+///
+/// With [`LinkMethod::Lap`], the links of each frame minimize the total cost
+/// by solving a linear assignment problem, where not linking a track or a
+/// detection costs `max_distance²`. With [`LinkMethod::NearestNeighbor`], the
+/// shortest available links are chosen greedily.
+///
+/// Gap closing allows a track to skip up to `max_gap` frames where its
+/// particle was not detected (_e.g._ blinking or out-of-focus particles),
+/// tracks not linked for more than `max_gap` frames are closed.
+///
+/// # Arguments
+///
+/// * `detections`: The detections of each frame, as 2-dimensional arrays with
+///    a shape of `(n_detections, n_dims)`, where each row holds the
+///    coordinates of a detection. All frames must have the same number of
+///    dimensions.
+/// * `max_distance`: The maximum link distance. Must be positive.
+/// * `max_gap`: The maximum number of skipped frames, default = 0.
+/// * `method`: The linking method, default = [`LinkMethod::Lap`].
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The track table with a shape of
+///    `(n_detections, 2 + n_dims)`, where each row is
+///    `(track_id, frame, coordinates...)`, sorted by track and frame.
+/// * `Err(ImgalError)`: If the number of detection dimensions do not match
+///    between frames. If `max_distance` is not positive.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1038/nmeth.1237>
+pub fn link_particles(
+    detections: &[ArrayView2<f64>],
+    max_distance: f64,
+    max_gap: Option<usize>,
+    method: Option<LinkMethod>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let max_gap = max_gap.unwrap_or(0);
+    let method = method.unwrap_or(LinkMethod::Lap);
+
+    // check parameters are valid
+    if max_distance.is_nan() || max_distance <= 0.0 {
+        return Err(ImgalError::InvalidParameterValueOutsideRange {
+            param_name: "max_distance",
+            value: max_distance,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    let n_dims = detections.first().map(|d| d.ncols()).unwrap_or(0);
+    if let Some(d) = detections.iter().find(|d| d.ncols() != n_dims) {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: n_dims,
+            b_arr_len: d.ncols(),
+        });
+    }
+
+    // link each frame to the active tracks, tracks are lists of (frame, detection)
+    let cutoff = max_distance * max_distance;
+    let mut tracks: Vec<Vec<(usize, usize)>> = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for (t, dets) in detections.iter().enumerate() {
+        // close tracks that exceeded the maximum gap
+        active.retain(|&k| t - tracks[k].last().unwrap().0 <= max_gap + 1);
+
+        // compute the link costs between active tracks and detections
+        let costs: Vec<Vec<f64>> = active
+            .iter()
+            .map(|&k| {
+                let &(f, i) = tracks[k].last().unwrap();
+                let last = detections[f].row(i);
+                dets.rows()
+                    .into_iter()
+                    .map(|d| {
+                        let d2: f64 = last
+                            .iter()
+                            .zip(d.iter())
+                            .map(|(a, b)| (a - b).powi(2))
+                            .sum();
+                        if d2 <= cutoff { d2 } else { f64::INFINITY }
+                    })
+                    .collect()
+            })
+            .collect();
+        let links = match method {
+            LinkMethod::NearestNeighbor => link_greedy(&costs, dets.nrows()),
+            LinkMethod::Lap => link_lap(&costs, dets.nrows(), cutoff),
+        };
+
+        // extend linked tracks and start new tracks
+        let mut linked = vec![false; dets.nrows()];
+        links.iter().for_each(|&(a, j)| {
+            tracks[active[a]].push((t, j));
+            linked[j] = true;
+        });
+        linked
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| !**l)
+            .for_each(|(j, _)| {
+                tracks.push(vec![(t, j)]);
+                active.push(tracks.len() - 1);
+            });
+    }
+
+    // build the track table
+    let n_rows: usize = tracks.iter().map(|tr| tr.len()).sum();
+    let mut table = Array2::<f64>::zeros((n_rows, 2 + n_dims));
+    tracks
+        .iter()
+        .enumerate()
+        .flat_map(|(k, tr)| tr.iter().map(move |&(f, i)| (k, f, i)))
+        .zip(table.rows_mut())
+        .for_each(|((k, f, i), mut row)| {
+            row[0] = k as f64;
+            row[1] = f as f64;
+            row.iter_mut()
+                .skip(2)
+                .zip(detections[f].row(i).iter())
+                .for_each(|(r, &v)| *r = v);
+        });
+
+    Ok(table)
+}
+
+/// Greedily link the lowest cost (track, detection) pairs.
+fn link_greedy(costs: &[Vec<f64>], n_dets: usize) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<(f64, usize, usize)> = costs
+        .iter()
+        .enumerate()
+        .flat_map(|(a, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, c)| c.is_finite())
+                .map(move |(j, &c)| (c, a, j))
+        })
+        .collect();
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let mut track_used = vec![false; costs.len()];
+    let mut det_used = vec![false; n_dets];
+    let mut links = Vec::new();
+    pairs.into_iter().for_each(|(_, a, j)| {
+        if !track_used[a] && !det_used[j] {
+            track_used[a] = true;
+            det_used[j] = true;
+            links.push((a, j));
+        }
+    });
+
+    links
+}
+
+/// Link (track, detection) pairs by solving the linear assignment problem with
+/// a no-link cost.
+fn link_lap(costs: &[Vec<f64>], n_dets: usize, no_link: f64) -> Vec<(usize, usize)> {
+    // build the (tracks + detections) square cost matrix, the top left block
+    // holds the link costs, the diagonal of the top right and bottom left
+    // blocks hold the no-link costs and the bottom right block is free
+    let n_tracks = costs.len();
+    let n = n_tracks + n_dets;
+    if n_tracks == 0 || n_dets == 0 {
+        return Vec::new();
+    }
+    let forbidden = 2.0 * no_link * (n + 1) as f64 + 1.0;
+    let mut matrix = vec![vec![forbidden; n]; n];
+    (0..n_tracks).for_each(|a| {
+        (0..n_dets).for_each(|j| {
+            if costs[a][j].is_finite() {
+                matrix[a][j] = costs[a][j];
+            }
+        });
+        matrix[a][n_dets + a] = no_link;
+    });
+    (0..n_dets).for_each(|j| {
+        matrix[n_tracks + j][j] = no_link;
+        (0..n_tracks).for_each(|a| matrix[n_tracks + j][n_dets + a] = 0.0);
+    });
+
+    hungarian(&matrix)
+        .into_iter()
+        .enumerate()
+        .filter(|&(a, j)| a < n_tracks && j < n_dets && costs[a][j].is_finite())
+        .collect()
+}
+
+/// Solve the square linear assignment problem with the Hungarian algorithm,
+/// returning the assigned column of each row.
+fn hungarian(matrix: &[Vec<f64>]) -> Vec<usize> {
+    // shortest augmenting path formulation with 1-based potentials
+    let n = matrix.len();
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    let mut p = vec![0_usize; n + 1];
+    let mut way = vec![0_usize; n + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if !used[j] {
+                    let cur = matrix[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut assignment = vec![0; n];
+    (1..=n).for_each(|j| assignment[p[j] - 1] = j - 1);
+
+    assignment
+}
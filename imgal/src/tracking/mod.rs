@@ -0,0 +1,4 @@
+//! Particle tracking functions.
+pub mod link;
+pub use link::LinkMethod;
+pub use link::link_particles;
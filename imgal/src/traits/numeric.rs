@@ -84,3 +84,105 @@ impl ToFloat64 for f64 {
         self
     }
 }
+
+pub trait FromFloat64: ToFloat64 {
+    /// The minimum value of the type, as f64.
+    const MIN_F64: f64;
+    /// The maximum value of the type, as f64.
+    const MAX_F64: f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+// unsigned from f64, rounded and saturated at the type range
+impl FromFloat64 for u8 {
+    const MIN_F64: f64 = u8::MIN as f64;
+    const MAX_F64: f64 = u8::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as u8
+    }
+}
+
+impl FromFloat64 for u16 {
+    const MIN_F64: f64 = u16::MIN as f64;
+    const MAX_F64: f64 = u16::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as u16
+    }
+}
+
+impl FromFloat64 for u32 {
+    const MIN_F64: f64 = u32::MIN as f64;
+    const MAX_F64: f64 = u32::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as u32
+    }
+}
+
+impl FromFloat64 for u64 {
+    const MIN_F64: f64 = u64::MIN as f64;
+    const MAX_F64: f64 = u64::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as u64
+    }
+}
+
+// signed from f64, rounded and saturated at the type range
+impl FromFloat64 for i8 {
+    const MIN_F64: f64 = i8::MIN as f64;
+    const MAX_F64: f64 = i8::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i8
+    }
+}
+
+impl FromFloat64 for i16 {
+    const MIN_F64: f64 = i16::MIN as f64;
+    const MAX_F64: f64 = i16::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i16
+    }
+}
+
+impl FromFloat64 for i32 {
+    const MIN_F64: f64 = i32::MIN as f64;
+    const MAX_F64: f64 = i32::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i32
+    }
+}
+
+impl FromFloat64 for i64 {
+    const MIN_F64: f64 = i64::MIN as f64;
+    const MAX_F64: f64 = i64::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i64
+    }
+}
+
+// f64 to float, f32 loses precision and saturates to infinity
+impl FromFloat64 for f32 {
+    const MIN_F64: f64 = f32::MIN as f64;
+    const MAX_F64: f64 = f32::MAX as f64;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl FromFloat64 for f64 {
+    const MIN_F64: f64 = f64::MIN;
+    const MAX_F64: f64 = f64::MAX;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
@@ -0,0 +1,223 @@
+use ndarray::{Array2, ArrayView2, Axis, s};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Orthogonal wavelets available for the discrete wavelet transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wavelet {
+    /// Haar wavelet (2 taps).
+    Haar,
+    /// Daubechies wavelet with 2 vanishing moments (4 taps).
+    Db2,
+    /// Daubechies wavelet with 4 vanishing moments (8 taps).
+    Db4,
+}
+
+impl Wavelet {
+    /// The low-pass (scaling) decomposition filter.
+    fn low_pass(&self) -> Vec<f64> {
+        match self {
+            Wavelet::Haar => vec![std::f64::consts::FRAC_1_SQRT_2; 2],
+            Wavelet::Db2 => {
+                let s3 = 3.0_f64.sqrt();
+                let norm = 4.0 * 2.0_f64.sqrt();
+                vec![
+                    (1.0 + s3) / norm,
+                    (3.0 + s3) / norm,
+                    (3.0 - s3) / norm,
+                    (1.0 - s3) / norm,
+                ]
+            }
+            Wavelet::Db4 => vec![
+                0.23037781330885523,
+                0.7148465705525415,
+                0.6308807679295904,
+                -0.02798376941698385,
+                -0.18703481171888114,
+                0.030841381835986965,
+                0.032883011666982945,
+                -0.010597401784997278,
+            ],
+        }
+    }
+
+    /// The low-pass and high-pass (quadrature mirror) decomposition filters.
+    fn filters(&self) -> (Vec<f64>, Vec<f64>) {
+        let h = self.low_pass();
+        let n = h.len();
+        let g = (0..n)
+            .map(|k| {
+                if k % 2 == 0 {
+                    h[n - 1 - k]
+                } else {
+                    -h[n - 1 - k]
+                }
+            })
+            .collect();
+
+        (h, g)
+    }
+}
+
+/// Compute the multilevel 2-dimensional discrete wavelet transform.
+///
+/// # Description
+///
+/// This function computes the separable 2-dimensional discrete wavelet
+/// transform (DWT) of an image with an orthogonal wavelet and periodic
+/// boundaries. Each level filters the rows and then the columns of the
+/// current approximation into four subbands, stored in place (_i.e._ the
+/// Mallat layout):
+///
+/// ```text
+/// | LL | LH |
+/// | HL | HH |
+/// ```
+///
+/// Where "LL" is the approximation (recursively transformed by the next
+/// level), "LH" holds the horizontal details (column high-pass), "HL" holds
+/// the vertical details (row high-pass) and "HH" holds the diagonal details.
+/// The transform is orthogonal, [`idwt2`] reconstructs the image exactly.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image. Both dimensions must be divisible
+///    by `2^levels`.
+/// * `wavelet`: The wavelet.
+/// * `levels`: The number of decomposition levels, default = 1.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The wavelet coefficients in the Mallat layout, with the
+///    same shape as `data`.
+/// * `Err(ImgalError)`: If `levels` is 0. If a dimension is not divisible by
+///    `2^levels`.
+pub fn dwt2<T>(
+    data: ArrayView2<T>,
+    wavelet: Wavelet,
+    levels: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let levels = levels.unwrap_or(1);
+    check_levels(data.dim(), levels)?;
+
+    let (h, g) = wavelet.filters();
+    let mut coefs = data.mapv(|v| v.to_f64());
+    let (mut rows, mut cols) = coefs.dim();
+    for _ in 0..levels {
+        let mut region = coefs.slice_mut(s![..rows, ..cols]);
+        for axis in [Axis(1), Axis(0)] {
+            region.lanes_mut(axis).into_iter().for_each(|mut ln| {
+                let out = forward_1d(&ln.to_vec(), &h, &g);
+                ln.iter_mut().zip(out).for_each(|(l, o)| *l = o);
+            });
+        }
+        rows /= 2;
+        cols /= 2;
+    }
+
+    Ok(coefs)
+}
+
+/// Compute the multilevel 2-dimensional inverse discrete wavelet transform.
+///
+/// # Description
+///
+/// This function reconstructs an image from its wavelet coefficients in the
+/// Mallat layout (see [`dwt2`]).
+///
+/// # Arguments
+///
+/// * `data`: The 2-dimensional wavelet coefficients. Both dimensions must be
+///    divisible by `2^levels`.
+/// * `wavelet`: The wavelet used for the forward transform.
+/// * `levels`: The number of decomposition levels, default = 1.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The reconstructed image.
+/// * `Err(ImgalError)`: If `levels` is 0. If a dimension is not divisible by
+///    `2^levels`.
+pub fn idwt2(
+    data: ArrayView2<f64>,
+    wavelet: Wavelet,
+    levels: Option<usize>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let levels = levels.unwrap_or(1);
+    check_levels(data.dim(), levels)?;
+
+    let (h, g) = wavelet.filters();
+    let mut image = data.to_owned();
+    let (rows, cols) = image.dim();
+    for l in (0..levels).rev() {
+        let (r, c) = (rows >> l, cols >> l);
+        let mut region = image.slice_mut(s![..r, ..c]);
+        for axis in [Axis(0), Axis(1)] {
+            region.lanes_mut(axis).into_iter().for_each(|mut ln| {
+                let out = inverse_1d(&ln.to_vec(), &h, &g);
+                ln.iter_mut().zip(out).for_each(|(l, o)| *l = o);
+            });
+        }
+    }
+
+    Ok(image)
+}
+
+/// Check that the image dimensions support the number of levels.
+fn check_levels(dim: (usize, usize), levels: usize) -> Result<(), ImgalError> {
+    if levels == 0 {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
+            param_name: "levels",
+            value: 0,
+        });
+    }
+    let factor = 1_usize << levels.min(usize::BITS as usize - 1);
+    if !dim.0.is_multiple_of(factor) || !dim.1.is_multiple_of(factor) || dim.0 == 0 || dim.1 == 0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The image dimensions must be divisible by 2^levels.",
+        });
+    }
+
+    Ok(())
+}
+
+/// Single level periodic forward DWT, returning (approximation, detail).
+fn forward_1d(x: &[f64], h: &[f64], g: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let half = n / 2;
+    let mut out = vec![0.0; n];
+    (0..half).for_each(|k| {
+        h.iter()
+            .zip(g.iter())
+            .enumerate()
+            .for_each(|(m, (&hm, &gm))| {
+                let v = x[(2 * k + m) % n];
+                out[k] += hm * v;
+                out[half + k] += gm * v;
+            });
+    });
+
+    out
+}
+
+/// Single level periodic inverse DWT of (approximation, detail).
+fn inverse_1d(c: &[f64], h: &[f64], g: &[f64]) -> Vec<f64> {
+    let n = c.len();
+    let half = n / 2;
+    let mut out = vec![0.0; n];
+    (0..half).for_each(|k| {
+        h.iter()
+            .zip(g.iter())
+            .enumerate()
+            .for_each(|(m, (&hm, &gm))| {
+                out[(2 * k + m) % n] += hm * c[k] + gm * c[half + k];
+            });
+    });
+
+    out
+}
@@ -0,0 +1,33 @@
+use ndarray::Array2;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+/// Compute the in-place 2-dimensional FFT, normalized when inverse.
+pub(crate) fn fft2(buf: &mut Array2<Complex<f64>>, inverse: bool) {
+    let (rows, cols) = buf.dim();
+    let mut planner = FftPlanner::new();
+    let (fft_rows, fft_cols) = if inverse {
+        (
+            planner.plan_fft_inverse(cols),
+            planner.plan_fft_inverse(rows),
+        )
+    } else {
+        (
+            planner.plan_fft_forward(cols),
+            planner.plan_fft_forward(rows),
+        )
+    };
+    buf.rows_mut().into_iter().for_each(|mut row| {
+        let mut line = row.to_vec();
+        fft_rows.process(&mut line);
+        row.iter_mut().zip(line).for_each(|(r, l)| *r = l);
+    });
+    buf.columns_mut().into_iter().for_each(|mut col| {
+        let mut line = col.to_vec();
+        fft_cols.process(&mut line);
+        col.iter_mut().zip(line).for_each(|(c, l)| *c = l);
+    });
+    if inverse {
+        let n = (rows * cols) as f64;
+        buf.mapv_inplace(|v| v / n);
+    }
+}
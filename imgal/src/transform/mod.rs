@@ -0,0 +1,6 @@
+//! Transform functions.
+pub mod dwt;
+pub(crate) mod fft;
+pub use dwt::Wavelet;
+pub use dwt::dwt2;
+pub use dwt::idwt2;
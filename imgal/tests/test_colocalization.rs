@@ -0,0 +1,244 @@
+use ndarray::{Array2, Array3, Axis};
+
+use imgal::colocalization::{self, BootstrapMetric, PairwiseMetric, SacaKernel, SacaOptions};
+
+// create a pair of positively correlated 2-dimensional images
+fn correlated_images() -> (Array2<f64>, Array2<f64>) {
+    let a = Array2::from_shape_fn((16, 16), |(r, c)| ((r * 7 + c * 13) % 17) as f64);
+    let b = a.mapv(|v| 2.0 * v + 1.0);
+
+    (a, b)
+}
+
+#[test]
+fn saca_options_validate() {
+    // default options are valid
+    assert!(SacaOptions::default().validate().is_ok());
+
+    // invalid step schedules are rejected
+    let opts = SacaOptions {
+        tl: 15,
+        ..Default::default()
+    };
+    assert!(opts.validate().is_err());
+    let opts = SacaOptions {
+        step_size: 1.0,
+        ..Default::default()
+    };
+    assert!(opts.validate().is_err());
+}
+
+#[test]
+fn saca_saca_2d_kernels() {
+    let (a, b) = correlated_images();
+
+    // each kernel detects the positive colocalization
+    for kernel in [
+        SacaKernel::Weighted,
+        SacaKernel::Circle,
+        SacaKernel::Gaussian,
+    ] {
+        let opts = SacaOptions {
+            tu: 6,
+            tl: 3,
+            kernel,
+            ..Default::default()
+        };
+        let z = colocalization::saca_2d(a.view(), b.view(), 0.0, 0.0, Some(opts)).unwrap();
+        assert!(z[[8, 8]] > 0.0);
+    }
+}
+
+#[test]
+fn saca_saca_2d_intermediate() {
+    let (a, b) = correlated_images();
+    let opts = SacaOptions {
+        tu: 6,
+        tl: 3,
+        ..Default::default()
+    };
+
+    // record the per-iteration snapshots
+    let (z, tau, sqrt_n) =
+        colocalization::saca_2d_intermediate(a.view(), b.view(), 0.0, 0.0, Some(opts)).unwrap();
+    let z_ref = colocalization::saca_2d(a.view(), b.view(), 0.0, 0.0, Some(opts)).unwrap();
+
+    // check the snapshot shapes and the z-score matches the plain run
+    assert_eq!(tau.shape(), [6, 16, 16]);
+    assert_eq!(sqrt_n.shape(), [6, 16, 16]);
+    assert_eq!(z, z_ref);
+
+    // the neighborhood grows with each scale
+    assert!(sqrt_n[[5, 8, 8]] > sqrt_n[[0, 8, 8]]);
+}
+
+#[test]
+fn saca_saca_2d_z_score() {
+    let (a, b) = correlated_images();
+    let b_noisy = Array2::from_shape_fn((16, 16), |(r, c)| {
+        b[[r, c]] + ((r * 3 + c * 5) % 11) as f64 * 4.0
+    });
+    let opts = SacaOptions {
+        tu: 1,
+        tl: 0,
+        ..Default::default()
+    };
+
+    // the reference implementation scales the z-score by 1.5, the asymptotic
+    // standard score of Kendall's Tau-b, z = 1.5 * τ * √n
+    let (z, tau, sqrt_n) =
+        colocalization::saca_2d_intermediate(a.view(), b_noisy.view(), 0.0, 0.0, Some(opts))
+            .unwrap();
+    assert_eq!(SacaOptions::default().z_scale, 1.5);
+    z.indexed_iter().for_each(|((r, c), &v)| {
+        assert!((v - 1.5 * tau[[0, r, c]] * sqrt_n[[0, r, c]]).abs() < 1e-12);
+    });
+    assert!((z[[8, 8]] - 0.353661998161318).abs() < 1e-12);
+
+    // the z-score scales linearly with "z_scale"
+    let opts = SacaOptions {
+        z_scale: 3.0,
+        ..opts
+    };
+    let z_double = colocalization::saca_2d(a.view(), b_noisy.view(), 0.0, 0.0, Some(opts)).unwrap();
+    assert!((z_double[[8, 8]] - 2.0 * z[[8, 8]]).abs() < 1e-12);
+}
+
+#[test]
+fn coefficients_pearson() {
+    let (a, b) = correlated_images();
+    let b_neg = b.mapv(|v| 100.0 - v);
+
+    // check perfectly correlated and anti-correlated images
+    let r = colocalization::pearson(a.view().into_dyn(), b.view().into_dyn()).unwrap();
+    let r_neg = colocalization::pearson(a.view().into_dyn(), b_neg.view().into_dyn()).unwrap();
+    assert!((r - 1.0).abs() < 1e-12);
+    assert!((r_neg + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn coefficients_costes_threshold() {
+    // uncorrelated background with a correlated bright region
+    let hash = |i: usize, k: usize| {
+        let mut x = (i as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ k as u64;
+        x ^= x >> 31;
+        x = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        (x >> 40) % 20
+    };
+    let a = Array2::from_shape_fn((32, 32), |(r, c)| {
+        if r < 16 {
+            hash(r * 32 + c, 1) as f64
+        } else {
+            40.0 + (r * 32 + c) as f64 % 60.0
+        }
+    });
+    let b = Array2::from_shape_fn((32, 32), |(r, c)| {
+        if r < 16 {
+            hash(r * 32 + c, 7) as f64
+        } else {
+            2.0 * a[[r, c]] + 5.0
+        }
+    });
+    let (ta, tb) =
+        colocalization::costes_threshold(a.view().into_dyn(), b.view().into_dyn()).unwrap();
+
+    // check the thresholds separate the correlated region from the background
+    assert!((19.0..=40.0).contains(&ta));
+    assert!((19.0..=85.0).contains(&tb));
+
+    // check anti-correlated images are rejected
+    let b_neg = a.mapv(|v| 100.0 - v);
+    assert!(
+        colocalization::costes_threshold(a.view().into_dyn(), b_neg.view().into_dyn()).is_err()
+    );
+}
+
+#[test]
+fn coefficients_manders() {
+    // channel "b" only overlaps the first half of channel "a"
+    let a = Array2::from_shape_fn((4, 4), |_| 2.0);
+    let b = Array2::from_shape_fn((4, 4), |(r, _)| if r < 2 { 1.0 } else { 0.0 });
+    let (m1, m2) =
+        colocalization::manders(a.view().into_dyn(), b.view().into_dyn(), None, None).unwrap();
+
+    assert_eq!(m1, 0.5);
+    assert_eq!(m2, 1.0);
+}
+
+#[test]
+fn pairwise_pairwise_matrix() {
+    // create a 3 channel image: "a", "a" correlated and "a" anti-correlated
+    let (a, b) = correlated_images();
+    let mut data = Array3::<f64>::zeros((3, 16, 16));
+    data.index_axis_mut(Axis(0), 0).assign(&a);
+    data.index_axis_mut(Axis(0), 1).assign(&b);
+    data.index_axis_mut(Axis(0), 2)
+        .assign(&a.mapv(|v| 100.0 - v));
+
+    let (m, maps) = colocalization::pairwise_matrix(
+        data.view().into_dyn(),
+        PairwiseMetric::Pearson,
+        None,
+        Some(true),
+    )
+    .unwrap();
+
+    // check the matrix is symmetric with the expected correlations
+    assert_eq!(m.shape(), [3, 3]);
+    assert!((m[[0, 1]] - 1.0).abs() < 1e-12);
+    assert!((m[[2, 0]] + 1.0).abs() < 1e-12);
+    assert_eq!(m[[1, 2]], m[[2, 1]]);
+
+    // one map per channel pair, with a mean equal to the coefficient
+    let maps = maps.unwrap();
+    assert_eq!(maps.len(), 3);
+    assert!((maps[1].mean().unwrap() + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn coefficients_icq() {
+    let (a, b) = correlated_images();
+    let q = colocalization::icq(a.view().into_dyn(), b.view().into_dyn()).unwrap();
+
+    // all pixels away from the mean have a positive product of differences
+    assert!(q > 0.4 && q <= 0.5);
+}
+
+#[test]
+fn bootstrap_bootstrap_ci() {
+    // create noisy, partially correlated images
+    let a = Array2::from_shape_fn((32, 32), |(r, c)| ((r * 31 + c * 17) % 23) as f64);
+    let b = Array2::from_shape_fn((32, 32), |(r, c)| a[[r, c]] + ((r * 7 + c * 3) % 11) as f64);
+    let (va, vb) = (a.view().into_dyn(), b.view().into_dyn());
+
+    // pixel and block bootstrap intervals contain the estimate
+    let metric = BootstrapMetric::Pearson;
+    let (r, lo, hi) =
+        colocalization::bootstrap_ci(va.view(), vb.view(), metric, Some(200), None, None, Some(7))
+            .unwrap();
+    assert!(lo <= r && r <= hi);
+    let (r_blk, lo_blk, hi_blk) = colocalization::bootstrap_ci(
+        va.view(),
+        vb.view(),
+        metric,
+        Some(200),
+        None,
+        Some(4),
+        Some(7),
+    )
+    .unwrap();
+    assert_eq!(r, r_blk);
+    assert!(lo_blk <= r_blk && r_blk <= hi_blk);
+
+    // seeded intervals are reproducible
+    let repeat =
+        colocalization::bootstrap_ci(va.view(), vb.view(), metric, Some(200), None, None, Some(7))
+            .unwrap();
+    assert_eq!((r, lo, hi), repeat);
+
+    // invalid confidence levels are rejected
+    assert!(
+        colocalization::bootstrap_ci(va.view(), vb.view(), metric, None, Some(1.0), None, None)
+            .is_err()
+    );
+}
@@ -0,0 +1,45 @@
+use imgal::data;
+use imgal::phasor::time_domain;
+use imgal::segmentation;
+use imgal::statistics::min_max;
+use imgal::threshold;
+
+#[test]
+fn data_cells() {
+    // get the cells image twice
+    let cells = data::cells();
+
+    // assert the image is reproducible and segments into several cells
+    assert_eq!(cells.dim(), (256, 256));
+    assert_eq!(cells, data::cells());
+    let mask = threshold::otsu_mask(cells.view().into_dyn(), None).unwrap();
+    let mask = mask.into_dimensionality().unwrap();
+    let (_, count) = segmentation::label(mask.view(), None);
+    assert!(count > 5);
+}
+
+#[test]
+fn data_beads() {
+    let beads = data::beads();
+
+    // assert the beads are bright spots on a dim background
+    let (min, max) = min_max(beads.view().into_dyn());
+    assert_eq!(beads.dim(), (128, 128));
+    assert!(min < 30);
+    assert!(max > 150);
+    assert_eq!(beads, data::beads());
+}
+
+#[test]
+fn data_flim_cube() {
+    let cube = data::flim_cube();
+
+    // assert the phasor of each half matches its lifetime
+    let gs = time_domain::image(cube.view(), 12.5, None, None, None, None).unwrap();
+    let omega = 2.0 * std::f64::consts::PI / 12.5;
+    for (col, tau) in [(8, 1.0), (56, 3.0)] {
+        let g = 1.0 / (1.0 + (omega * tau) * (omega * tau));
+        assert!((gs[[40, col, 0]] - g).abs() < 0.05);
+    }
+    assert_eq!(cube.dim(), (64, 64, 256));
+}
@@ -8,5 +8,37 @@ fn distribution_gaussian() {
 
     // assert a value and integrate the curve
     assert_eq!(gauss_arr[100], 0.004465507286912305);
-    assert_eq!(midpoint(&gauss_arr, None), 1.0000000000000007);
+    assert_eq!(midpoint(&gauss_arr, None, None), 1.0000000000000007);
+}
+
+#[test]
+fn distribution_f_cdf() {
+    // assert against the closed form for d1 = 2, 1 - (1 + 2x / d2)^(-d2 / 2)
+    for (x, d2) in [(0.5_f64, 4.0_f64), (3.0, 10.0), (12.0, 7.0)] {
+        let expected = 1.0 - (1.0 + 2.0 * x / d2).powf(-d2 / 2.0);
+        assert!((distribution::f_cdf(x, 2.0, d2).unwrap() - expected).abs() < 1e-12);
+    }
+
+    // assert the median of F(d, d) is 1.0 and the bounds
+    assert!((distribution::f_cdf(1.0, 5.0, 5.0).unwrap() - 0.5).abs() < 1e-12);
+    assert_eq!(distribution::f_cdf(-1.0, 3.0, 4.0).unwrap(), 0.0);
+    assert!(distribution::f_cdf(1.0, 0.0, 4.0).is_err());
+}
+
+#[test]
+fn distribution_normal_cdf() {
+    // check known values of the standard normal distribution
+    assert!((distribution::normal_cdf(0.0) - 0.5).abs() < 1e-7);
+    assert!((distribution::normal_cdf(1.959964) - 0.975).abs() < 1e-7);
+    assert!((distribution::normal_cdf(-3.0) - 0.0013498980316301).abs() < 1e-9);
+
+    // check the upper tail is accurate far from the mean
+    let tail = distribution::normal_cdf(-8.0);
+    assert!((tail - 6.22096057427178e-16).abs() / 6.22096057427178e-16 < 1e-6);
+
+    // check the inverse round trip
+    let z = distribution::inverse_normal_cdf(0.3).unwrap();
+    assert!((distribution::normal_cdf(z) - 0.3).abs() < 1e-7);
+    assert!(distribution::inverse_normal_cdf(1.5).is_err());
+    assert!(distribution::inverse_normal_cdf(f64::NAN).is_err());
 }
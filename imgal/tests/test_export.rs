@@ -0,0 +1,78 @@
+use std::fs::File;
+
+use ndarray::Array2;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+
+use imgal::export;
+use imgal::measure;
+use imgal::segmentation;
+
+/// Create a label image with two rectangular regions.
+fn labels() -> Array2<usize> {
+    Array2::from_shape_fn((6, 6), |(r, c)| {
+        if r < 2 && c < 3 {
+            1
+        } else if r >= 3 && c >= 4 {
+            2
+        } else {
+            0
+        }
+    })
+}
+
+#[test]
+fn export_write_csv() {
+    // region properties and a per-label table with NaN columns
+    let labels = labels();
+    let props = segmentation::region_properties(labels.view(), None).unwrap();
+    let mut buffer = Vec::new();
+    export::write_csv(props.as_slice(), &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // assert the header and rows
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("label,pixel_count,area,centroid_row"));
+    assert!(lines[1].starts_with("1,6,6,0.5,1,"));
+    assert!(lines[2].starts_with("2,6,6,4,4.5,"));
+
+    let intensity = Array2::from_elem((6, 6), 2.0);
+    let table = measure::per_label_analysis(
+        labels.view(),
+        Some(intensity.view()),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let mut buffer = Vec::new();
+    export::write_csv(&table, &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.lines().nth(1).unwrap().starts_with("1,6,2,0,2,2,NaN"));
+}
+
+#[test]
+fn export_write_parquet() {
+    // write the region properties to a temporary file
+    let labels = labels();
+    let props = segmentation::region_properties(labels.view(), None).unwrap();
+    let path = std::env::temp_dir().join("imgal_export_write_parquet.parquet");
+    export::write_parquet(props.as_slice(), File::create(&path).unwrap()).unwrap();
+
+    // assert the schema and row count
+    let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+    let metadata = reader.metadata().file_metadata();
+    assert_eq!(metadata.num_rows(), 2);
+    let names: Vec<&str> = metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.name())
+        .collect();
+    assert_eq!(names.len(), 10);
+    assert_eq!(names[0], "label");
+    assert_eq!(names[9], "equivalent_diameter");
+    std::fs::remove_file(&path).unwrap();
+}
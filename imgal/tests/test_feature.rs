@@ -0,0 +1,97 @@
+use ndarray::{Array2, Array3, array};
+
+use imgal::feature;
+
+#[test]
+fn feature_structure_tensor_2d_horizontal_stripes() {
+    // create horizontal stripes, intensity varies along rows only
+    let data = Array2::from_shape_fn((32, 32), |(r, _)| (r as f64 * 0.5).sin());
+
+    // compute the structure tensor
+    let (orientation, coherence, energy) = feature::structure_tensor_2d(data.view(), None).unwrap();
+
+    // assert structure along the column axis with full coherence
+    assert!(orientation[[16, 16]].abs() < 1e-6);
+    assert!((coherence[[16, 16]] - 1.0).abs() < 1e-6);
+    assert!(energy[[16, 16]] > 0.0);
+}
+
+#[test]
+fn feature_structure_tensor_2d_diagonal_stripes() {
+    // create stripes along the (1, 1) direction
+    let data = Array2::from_shape_fn((32, 32), |(r, c)| ((c as f64 - r as f64) * 0.4).sin());
+
+    // compute the structure tensor
+    let (orientation, coherence, _) = feature::structure_tensor_2d(data.view(), Some(1.5)).unwrap();
+
+    // assert a 45 degree orientation
+    assert!((orientation[[16, 16]] - std::f64::consts::FRAC_PI_4).abs() < 1e-6);
+    assert!(coherence[[16, 16]] > 0.99);
+}
+
+#[test]
+fn feature_structure_tensor_2d_invalid_sigma() {
+    let data = Array2::<f64>::zeros((8, 8));
+    assert!(feature::structure_tensor_2d(data.view(), Some(0.0)).is_err());
+}
+
+#[test]
+fn feature_structure_tensor_3d_fiber() {
+    // create fibers along the plane axis, intensity varies in rows and columns
+    let data = Array3::from_shape_fn((16, 16, 16), |(_, r, c)| {
+        (r as f64 * 0.6).sin() + (c as f64 * 0.6).cos()
+    });
+
+    // compute the structure tensor
+    let (orientation, coherence, energy) = feature::structure_tensor_3d(data.view(), None).unwrap();
+
+    // assert the orientation vector points along the plane axis
+    assert!((orientation[[8, 8, 8, 0]].abs() - 1.0).abs() < 1e-6);
+    assert!((coherence[[8, 8, 8]] - 1.0).abs() < 1e-6);
+    assert!(energy[[8, 8, 8]] > 0.0);
+}
+
+#[test]
+fn feature_local_binary_pattern_uniform() {
+    // create a flat image with a single bright and a single dark pixel
+    let mut data = Array2::<f64>::from_elem((5, 5), 1.0);
+    data[[1, 1]] = 5.0;
+    data[[3, 3]] = 0.0;
+
+    // compute uniform LBP codes
+    let codes = feature::local_binary_pattern(data.view(), None, None, None).unwrap();
+
+    // assert bright peak, dark pit and flat codes
+    assert_eq!(codes[[1, 1]], 0);
+    assert_eq!(codes[[3, 3]], 8);
+    assert_eq!(codes[[0, 4]], 8);
+}
+
+#[test]
+fn feature_local_binary_pattern_default_codes() {
+    // create a horizontal step edge, brightness increases down the rows
+    let data = array![[0.0, 0.0, 0.0], [0.5, 0.5, 0.5], [1.0, 1.0, 1.0]];
+
+    // compute non-uniform LBP codes
+    let codes = feature::local_binary_pattern(data.view(), None, Some(4), Some(false)).unwrap();
+
+    // assert the center pixel sets the right, bottom and left neighbor bits
+    assert_eq!(codes[[1, 1]], 0b1101);
+}
+
+#[test]
+fn feature_local_binary_pattern_histogram_regions() {
+    let codes = array![[0, 1], [1, 2]];
+    let labels = array![[1, 1], [2, 0]];
+
+    // compute histograms per region
+    let hist =
+        feature::local_binary_pattern_histogram(codes.view(), 3, Some(labels.view())).unwrap();
+
+    // assert region histograms
+    assert_eq!(hist.dim(), (3, 3));
+    assert_eq!(hist.row(0).to_vec(), vec![0.0, 0.0, 0.0]);
+    assert_eq!(hist.row(1).to_vec(), vec![0.5, 0.5, 0.0]);
+    assert_eq!(hist.row(2).to_vec(), vec![0.0, 1.0, 0.0]);
+    assert!(feature::local_binary_pattern_histogram(codes.view(), 2, None).is_err());
+}
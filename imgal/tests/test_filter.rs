@@ -1,6 +1,11 @@
-use imgal::filter;
-use imgal::simulation::{decay, instrument};
+use ndarray::{Array2, Array3, s};
+
+use imgal::filter::{
+    self, Baseline, DestripeMethod, ShrinkageRule, StripeDirection, TemporalFilter,
+};
+use imgal::simulation::{decay, instrument, noise};
 use imgal::statistics::sum;
+use imgal::transform::Wavelet;
 
 // simulated bioexponential decay parameters, unit is nanoseconds
 const SAMPLES: usize = 256;
@@ -60,3 +65,372 @@ fn filter_fft_deconvolve_1d() {
         1e-12
     ));
 }
+
+#[test]
+fn filter_wavelet_denoise() {
+    // create a piecewise constant image with Gaussian noise
+    let clean = Array2::from_shape_fn((60, 60), |(r, c)| {
+        if (20..40).contains(&r) && (20..40).contains(&c) {
+            100.0
+        } else {
+            20.0
+        }
+    });
+    let noisy = noise::gaussian_2d(clean.view(), 10.0, Some(3)).unwrap();
+    let mse = |a: &Array2<f64>| {
+        a.iter()
+            .zip(clean.iter())
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            / a.len() as f64
+    };
+
+    // denoise with both threshold rules
+    let bayes = filter::wavelet_denoise(noisy.view(), Wavelet::Db2, None, None, None).unwrap();
+    let visu = filter::wavelet_denoise(
+        noisy.view(),
+        Wavelet::Haar,
+        Some(2),
+        Some(ShrinkageRule::VisuShrink),
+        Some(10.0),
+    )
+    .unwrap();
+
+    // assert the shape is kept and the error is reduced
+    assert_eq!(bayes.shape(), &[60, 60]);
+    assert!(mse(&bayes) < 0.5 * mse(&noisy));
+    assert!(mse(&visu) < 0.5 * mse(&noisy));
+    assert!(filter::wavelet_denoise(noisy.view(), Wavelet::Haar, None, None, Some(-1.0)).is_err());
+}
+
+// create a smooth gradient image with column offsets
+fn striped_image() -> (Array2<f64>, Array2<f64>) {
+    let clean = Array2::from_shape_fn((64, 64), |(r, c)| 100.0 + r as f64 + 0.05 * c as f64);
+    let mut striped = clean.clone();
+    striped
+        .columns_mut()
+        .into_iter()
+        .enumerate()
+        .for_each(|(c, mut col)| col += if c % 3 == 0 { 8.0 } else { -4.0 });
+    (clean, striped)
+}
+
+#[test]
+fn filter_destripe_median() {
+    let (clean, striped) = striped_image();
+
+    // remove column stripes
+    let output = filter::destripe(striped.view(), Some(StripeDirection::Vertical), None).unwrap();
+
+    // assert the stripes are removed up to a constant offset
+    let diff = &output - &clean;
+    let center = diff.slice(s![.., 16..48]);
+    let mean = center.mean().unwrap();
+    assert!(center.iter().all(|d| (d - mean).abs() < 0.5));
+}
+
+#[test]
+fn filter_destripe_fft_notch() {
+    let (clean, striped) = striped_image();
+
+    // remove column stripes in the frequency domain
+    let method = DestripeMethod::FftNotch {
+        width: 1,
+        cutoff: 4,
+    };
+    let output = filter::destripe(
+        striped.view(),
+        Some(StripeDirection::Vertical),
+        Some(method),
+    )
+    .unwrap();
+
+    // assert the line-to-line stripe amplitude is reduced
+    let stripe = |img: &Array2<f64>| (img[[32, 30]] - img[[32, 31]]).abs();
+    assert!(stripe(&output) < 0.2 * stripe(&striped));
+    assert!((output.mean().unwrap() - clean.mean().unwrap()).abs() < 1.0);
+    assert!(
+        filter::destripe(
+            striped.view(),
+            None,
+            Some(DestripeMethod::Median { window: 0 })
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn filter_gaussian_2d() {
+    // create an image with a single bright pixel
+    let mut data = Array2::<u16>::zeros((21, 21));
+    data[[10, 10]] = 1000;
+
+    // smooth the image
+    let smooth = filter::gaussian_2d(data.view(), 1.5).unwrap();
+
+    // assert the total intensity is conserved and the peak is spread
+    // symmetrically
+    assert!(ensure_within_tolerance(smooth.sum(), 1000.0, 1e-9));
+    assert!(smooth[[10, 10]] < 1000.0);
+    assert!(ensure_within_tolerance(
+        smooth[[10, 8]],
+        smooth[[8, 10]],
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        smooth[[9, 10]],
+        smooth[[11, 10]],
+        1e-12
+    ));
+    assert_eq!(
+        filter::gaussian_2d(data.view(), 0.0).unwrap()[[10, 10]],
+        1000.0
+    );
+    assert!(filter::gaussian_2d(data.view(), -1.0).is_err());
+}
+
+#[test]
+fn filter_gaussian_2d_into() {
+    // create an image with a single bright pixel
+    let mut data = Array2::<u16>::zeros((21, 21));
+    data[[10, 10]] = 1000;
+
+    // smooth into an existing output array
+    let mut out = Array2::<f64>::from_elem((21, 21), 5.0);
+    filter::gaussian_2d_into(data.view(), 1.5, out.view_mut()).unwrap();
+
+    // assert the output matches the allocating filter
+    assert_eq!(out, filter::gaussian_2d(data.view(), 1.5).unwrap());
+    let mut bad = Array2::<f64>::zeros((21, 20));
+    assert!(filter::gaussian_2d_into(data.view(), 1.5, bad.view_mut()).is_err());
+}
+
+#[test]
+fn filter_gradient_domain() {
+    // create a smooth image with a ramp and a bump
+    let data = Array2::from_shape_fn((12, 15), |(r, c)| {
+        let (y, x) = (r as f64 - 6.0, c as f64 - 7.0);
+        0.5 * r as f64 + 10.0 * (-(x * x + y * y) / 8.0).exp()
+    });
+
+    // the forward gradient and divergence compose to the Laplacian
+    let (grad_r, grad_c) = filter::gradient(data.view());
+    assert!(ensure_within_tolerance(
+        grad_r[[0, 0]],
+        data[[1, 0]] - data[[0, 0]],
+        1e-12
+    ));
+    assert_eq!(grad_c[[3, 14]], 0.0);
+    let div = filter::divergence(grad_r.view(), grad_c.view()).unwrap();
+    let lap = filter::laplacian(data.view());
+    assert!(
+        div.iter()
+            .zip(lap.iter())
+            .all(|(a, b)| ensure_within_tolerance(*a, *b, 1e-12))
+    );
+    assert!(ensure_within_tolerance(lap.sum(), 0.0, 1e-9));
+
+    // the Poisson solver inverts the Laplacian up to the mean
+    let mean = data.mean().unwrap();
+    let solved = filter::poisson_solve(lap.view(), Some(mean));
+    assert!(
+        solved
+            .iter()
+            .zip(data.iter())
+            .all(|(a, b)| ensure_within_tolerance(*a, *b, 1e-9))
+    );
+    assert!(filter::divergence(grad_r.view(), grad_c.slice(s![.., ..3])).is_err());
+}
+
+#[test]
+fn filter_guided() {
+    // create a step edge guide and a noisy lifetime map with the same edge
+    let guide = Array2::from_shape_fn((20, 20), |(_, c)| if c < 10 { 100.0 } else { 1000.0 });
+    let data = Array2::from_shape_fn((20, 20), |(r, c)| {
+        let tau = if c < 10 { 1.0 } else { 3.0 };
+        tau + 0.2 * (((r * 7 + c * 13) % 5) as f64 - 2.0)
+    });
+    let smooth = filter::guided(data.view(), guide.view(), 2, 1e-2).unwrap();
+
+    // the edge is preserved while the noise is reduced on both sides
+    let spread = |arr: &Array2<f64>, cols: std::ops::Range<usize>| {
+        let region = arr.slice(s![.., cols]);
+        region.fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+            - region.fold(f64::INFINITY, |a, &b| a.min(b))
+    };
+    assert!(ensure_within_tolerance(smooth[[10, 9]], 1.0, 0.1));
+    assert!(ensure_within_tolerance(smooth[[10, 10]], 3.0, 0.1));
+    assert!(spread(&smooth, 0..10) < spread(&data, 0..10));
+    assert!(spread(&smooth, 10..20) < spread(&data, 10..20));
+
+    // a flat guide reduces to a mean filter
+    let flat = Array2::<f64>::ones((20, 20));
+    let smooth = filter::guided(data.view(), flat.view(), 2, 1e-2).unwrap();
+    let expected = filter::mean(data.view().into_dyn(), 2);
+    assert!(ensure_within_tolerance(
+        smooth[[5, 5]],
+        expected[[5, 5]],
+        1e-9
+    ));
+    assert!(filter::guided(data.view(), flat.view(), 2, 0.0).is_err());
+    assert!(filter::guided(data.view(), flat.slice(s![.., ..5]), 2, 1.0).is_err());
+}
+
+#[test]
+fn filter_kuwahara() {
+    // create a noisy step edge image
+    let data = Array2::from_shape_fn((20, 20), |(r, c)| {
+        let base = if c < 10 { 10.0 } else { 100.0 };
+        base + (((r * 7 + c * 13) % 5) as f64 - 2.0)
+    });
+
+    // the hard selection keeps the edge and reduces the noise
+    let smooth = filter::kuwahara(data.view(), 3, None, None).unwrap();
+    assert!(ensure_within_tolerance(smooth[[10, 9]], 10.0, 2.0));
+    assert!(ensure_within_tolerance(smooth[[10, 10]], 100.0, 2.0));
+    let noise = |arr: &Array2<f64>| arr.slice(s![5..15, 2..7]).std(0.0);
+    assert!(noise(&smooth) < noise(&data));
+
+    // the weighted sectors approach the hard selection for large sharpness
+    let blended = filter::kuwahara(data.view(), 3, Some(8), Some(8.0)).unwrap();
+    assert!(ensure_within_tolerance(blended[[10, 9]], 10.0, 2.0));
+    assert!(ensure_within_tolerance(blended[[10, 10]], 100.0, 2.0));
+    assert_eq!(
+        filter::kuwahara(data.view(), 0, None, None).unwrap()[[4, 4]],
+        data[[4, 4]]
+    );
+    assert!(filter::kuwahara(data.view(), 3, Some(1), None).is_err());
+    assert!(filter::kuwahara(data.view(), 3, None, Some(-1.0)).is_err());
+}
+
+#[test]
+fn filter_mean() {
+    // create a ramp image, the mean of a linear ramp is the center value
+    let data = Array2::from_shape_fn((9, 9), |(r, c)| (r * 9 + c) as u16);
+    let smooth = filter::mean(data.view().into_dyn(), 2);
+    assert!(ensure_within_tolerance(smooth[[4, 4]], 40.0, 1e-12));
+
+    // truncated boxes at the edges match a direct mean
+    let expected = data
+        .slice(s![0..3, 6..9])
+        .iter()
+        .map(|&v| v as f64)
+        .sum::<f64>()
+        / 9.0;
+    assert!(ensure_within_tolerance(smooth[[0, 8]], expected, 1e-12));
+    assert_eq!(filter::mean(data.view().into_dyn(), 0)[[3, 5]], 32.0);
+
+    // 3D boxes match a direct mean
+    let stack = Array3::from_shape_fn((5, 6, 7), |(z, r, c)| ((z * 7 + r * 3 + c) % 11) as f64);
+    let smooth = filter::mean(stack.view().into_dyn(), 1);
+    let expected = stack.slice(s![1..4, 2..5, 3..6]).mean().unwrap();
+    assert!(ensure_within_tolerance(smooth[[2, 3, 4]], expected, 1e-12));
+}
+
+#[test]
+fn filter_temporal() {
+    // a (row, t) stack of ramp traces with a spike in frame 4
+    let mut data = Array2::from_shape_fn((2, 8), |(r, t)| (r * 10 + t) as f64);
+    data[[0, 4]] = 100.0;
+    let data = data.into_dyn();
+
+    // moving average along the time axis, truncated at the ends
+    let avg = filter::temporal(
+        data.view(),
+        TemporalFilter::MovingAverage { window: 3 },
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(avg[[1, 0]], 10.5);
+    assert_eq!(avg[[1, 3]], 13.0);
+    assert_eq!(avg[[0, 3]], (2.0 + 3.0 + 100.0) / 3.0);
+
+    // the moving median removes the spike
+    let med = filter::temporal(
+        data.view(),
+        TemporalFilter::MovingMedian { window: 3 },
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(med[[0, 4]], 5.0);
+    assert_eq!(med[[1, 4]], 14.0);
+
+    // exponential smoothing
+    let exp = filter::temporal(
+        data.view(),
+        TemporalFilter::Exponential { alpha: 0.5 },
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(exp[[1, 0]], 10.0);
+    assert_eq!(exp[[1, 1]], 10.5);
+    assert_eq!(exp[[1, 2]], 11.25);
+
+    // invalid parameters
+    assert!(
+        filter::temporal(
+            data.view(),
+            TemporalFilter::MovingAverage { window: 0 },
+            None
+        )
+        .is_err()
+    );
+    assert!(
+        filter::temporal(
+            data.view(),
+            TemporalFilter::Exponential { alpha: 1.5 },
+            None
+        )
+        .is_err()
+    );
+    assert!(
+        filter::temporal(
+            data.view(),
+            TemporalFilter::Exponential { alpha: 0.5 },
+            Some(2)
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn filter_delta_f_over_f() {
+    // a (t, row, col) calcium transient on a baseline of 100
+    let data = Array3::from_shape_fn((20, 2, 2), |(t, r, _)| {
+        100.0 * (r + 1) as f64 + if (10..13).contains(&t) { 50.0 } else { 0.0 }
+    })
+    .into_dyn();
+
+    // baseline from the frames before the transient
+    let dff =
+        filter::delta_f_over_f(data.view(), Baseline::Frames { start: 0, end: 10 }, None).unwrap();
+    assert_eq!(dff[[0, 0, 0]], 0.0);
+    assert_eq!(dff[[11, 0, 0]], 0.5);
+    assert_eq!(dff[[11, 1, 1]], 0.25);
+
+    // a running percentile baseline ignores the transient
+    let dff = filter::delta_f_over_f(
+        data.view(),
+        Baseline::Percentile {
+            percentile: 10.0,
+            window: 9,
+        },
+        None,
+    )
+    .unwrap();
+    assert_eq!(dff[[11, 0, 1]], 0.5);
+    assert_eq!(dff[[5, 1, 0]], 0.0);
+
+    // invalid parameters
+    assert!(
+        filter::delta_f_over_f(data.view(), Baseline::Frames { start: 5, end: 5 }, None).is_err()
+    );
+    assert!(
+        filter::delta_f_over_f(data.view(), Baseline::Frames { start: 0, end: 21 }, None).is_err()
+    );
+    let invalid = Baseline::Percentile {
+        percentile: 101.0,
+        window: 9,
+    };
+    assert!(filter::delta_f_over_f(data.view(), invalid, None).is_err());
+}
@@ -0,0 +1,333 @@
+use ndarray::{Array2, Array3};
+
+use imgal::fitting::{self, ModelCriterion};
+use imgal::simulation::noise;
+
+// decay parameters
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+
+// create a multiexponential decay with a bin width of period / samples
+fn decay(taus: &[f64], amplitudes: &[f64]) -> Vec<f64> {
+    let dt = PERIOD / SAMPLES as f64;
+    (0..SAMPLES)
+        .map(|k| {
+            taus.iter()
+                .zip(amplitudes)
+                .map(|(t, a)| a * (-(k as f64) * dt / t).exp())
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn exponential_tail_fit() {
+    let d = decay(&[2.5], &[1000.0]);
+    let (tau, amp) = fitting::tail_fit(&d, PERIOD, None, None).unwrap();
+
+    // assert the lifetime and amplitude are recovered
+    assert!((tau - 2.5).abs() < 1e-9);
+    assert!((amp - 1000.0).abs() < 1e-6);
+
+    // assert invalid ranges
+    assert!(fitting::tail_fit(&d, PERIOD, Some(10), Some(10)).is_err());
+    assert!(fitting::tail_fit(&d, PERIOD, None, Some(SAMPLES + 1)).is_err());
+    assert!(fitting::tail_fit::<f64>(&[], PERIOD, None, None).is_err());
+}
+
+#[test]
+fn exponential_tail_fit_image() {
+    let d = decay(&[1.5], &[500.0]);
+    let data = Array3::from_shape_fn((3, 4, SAMPLES), |(r, c, k)| d[k] * (r + c + 1) as f64);
+    let (taus, amps) = fitting::tail_fit_image(data.view(), PERIOD, None, None, None).unwrap();
+
+    // assert the per-pixel lifetimes and amplitudes
+    assert_eq!(taus.shape(), [3, 4]);
+    assert!(taus.iter().all(|t| (t - 1.5).abs() < 1e-9));
+    assert!((amps[[2, 3]] - 3000.0).abs() < 1e-6);
+    assert!(fitting::tail_fit_image(data.view(), PERIOD, None, None, Some(3)).is_err());
+}
+
+#[test]
+fn exponential_prony() {
+    let d = decay(&[0.8, 3.2], &[700.0, 300.0]);
+    let (taus, amps) = fitting::prony(&d, PERIOD, 2, None).unwrap();
+
+    // assert both components are recovered, sorted by lifetime
+    assert!((taus[0] - 0.8).abs() < 1e-6);
+    assert!((taus[1] - 3.2).abs() < 1e-6);
+    assert!((amps[0] - 700.0).abs() < 1e-4);
+    assert!((amps[1] - 300.0).abs() < 1e-4);
+
+    // assert invalid parameters
+    assert!(fitting::prony(&d, PERIOD, 0, None).is_err());
+    assert!(fitting::prony(&d, PERIOD, 2, Some(SAMPLES - 3)).is_err());
+}
+
+#[test]
+fn exponential_prony_image() {
+    let d = decay(&[1.0, 4.0], &[200.0, 100.0]);
+    let data = Array3::from_shape_fn((SAMPLES, 2, 3), |(k, _, _)| d[k]);
+    let (taus, amps) = fitting::prony_image(data.view(), PERIOD, 2, None, Some(0)).unwrap();
+
+    // assert the per-pixel components
+    assert_eq!(taus.shape(), [2, 3, 2]);
+    assert!((taus[[1, 2, 0]] - 1.0).abs() < 1e-6);
+    assert!((taus[[1, 2, 1]] - 4.0).abs() < 1e-6);
+    assert!((amps[[0, 0, 1]] - 100.0).abs() < 1e-4);
+}
+
+#[test]
+fn laguerre_fit() {
+    // assert the basis is orthonormal over a long window
+    let b = fitting::laguerre_basis(512, 4, 0.8).unwrap();
+    let g = b.dot(&b.t());
+    for i in 0..4 {
+        for j in 0..4 {
+            let e = if i == j { 1.0 } else { 0.0 };
+            assert!((g[[i, j]] - e).abs() < 1e-9);
+        }
+    }
+
+    // assert the average lifetimes of mono and biexponential decays
+    let mono = fitting::laguerre_fit(&decay(&[2.0], &[1000.0]), PERIOD, None, None, None).unwrap();
+    assert!((mono.mean_lifetime - 2.0).abs() < 0.05);
+    assert!(mono.reduced_chi_square < 1e-3);
+    assert_eq!(mono.coefficients.len(), 5);
+    let bi = decay(&[0.5, 1.5], &[2000.0, 500.0]);
+    let fit = fitting::laguerre_fit(&bi, PERIOD, Some(6), None, None).unwrap();
+    assert!((fit.mean_lifetime - 1625.0 / 1750.0).abs() < 0.03);
+
+    // assert the deconvolved lifetime of an IRF convolved decay
+    let irf: Vec<f64> = (0..SAMPLES)
+        .map(|k| (-((k as f64 - 10.0) / 2.0).powi(2) / 2.0).exp())
+        .collect();
+    let total: f64 = irf.iter().sum();
+    let d = decay(&[2.0], &[1000.0]);
+    let conv: Vec<f64> = (0..SAMPLES)
+        .map(|k| (0..=k).map(|i| irf[i] * d[k - i]).sum::<f64>() / total)
+        .collect();
+    let fit = fitting::laguerre_fit(&conv, PERIOD, None, None, Some(&irf)).unwrap();
+    assert!((fit.mean_lifetime - 2.0).abs() < 0.05);
+
+    // assert the image fit shares the basis across pixels
+    let mut data = Array3::<f64>::zeros((2, 2, SAMPLES));
+    let d = decay(&[2.0], &[1000.0]);
+    data.lanes_mut(ndarray::Axis(2))
+        .into_iter()
+        .for_each(|mut ln| ln.assign(&ndarray::ArrayView1::from(&d[..])));
+    let mask = Array2::from_shape_vec((2, 2), vec![true, true, true, false]).unwrap();
+    let taus = fitting::laguerre_image(
+        data.view(),
+        PERIOD,
+        None,
+        None,
+        None,
+        Some(mask.view()),
+        None,
+    )
+    .unwrap();
+    assert!((taus[[0, 1]] - mono.mean_lifetime).abs() < 1e-9);
+    assert!(taus[[1, 1]].is_nan());
+
+    // assert invalid parameters
+    assert!(fitting::laguerre_basis(8, 0, 0.5).is_err());
+    assert!(fitting::laguerre_basis(8, 2, 1.0).is_err());
+    assert!(fitting::laguerre_fit(&d[..4], PERIOD, Some(4), None, None).is_err());
+    assert!(fitting::laguerre_fit(&d, PERIOD, None, None, Some(&[1.0])).is_err());
+}
+
+#[test]
+fn mem_lifetime_distribution() {
+    // noisy mono and biexponential decays
+    let mono = noise::poisson_1d(&decay(&[2.0], &[2500.0]), 1.0, Some(1));
+    let bi = noise::poisson_1d(&decay(&[1.0, 4.0], &[2000.0, 500.0]), 1.0, Some(2));
+    let dm = fitting::mem_lifetime_distribution(&mono, PERIOD, None, None).unwrap();
+    let db = fitting::mem_lifetime_distribution(&bi, PERIOD, None, None).unwrap();
+
+    // assert the recovered mean lifetimes and distribution widths
+    assert_eq!(dm.lifetimes.len(), 32);
+    assert!((dm.intensity_weighted_lifetime - 2.0).abs() < 0.2);
+    assert!((db.mean_lifetime - 1.6).abs() < 0.2);
+    assert!((db.intensity_weighted_lifetime - 2.5).abs() < 0.25);
+    assert!(dm.reduced_chi_square < 2.0 && db.reduced_chi_square < 2.0);
+    assert!(dm.amplitudes.iter().all(|&a| a > 0.0));
+
+    // assert a larger entropy weight broadens the distribution
+    let smooth = fitting::MemOptions {
+        regularization: Some(10.0),
+        ..Default::default()
+    };
+    let ds = fitting::mem_lifetime_distribution(&mono, PERIOD, None, Some(smooth)).unwrap();
+    assert!(ds.log_width > dm.log_width);
+
+    // assert the image and ROI distributions of uniform pixels
+    let mut data = Array3::<f64>::zeros((2, 1, SAMPLES));
+    data.outer_iter_mut().for_each(|mut row| {
+        row.row_mut(0).assign(&ndarray::ArrayView1::from(&mono[..]));
+    });
+    let mask = Array2::from_shape_vec((2, 1), vec![true, false]).unwrap();
+    let (mean, intensity, _) = fitting::mem_lifetime_distribution_image(
+        data.view(),
+        PERIOD,
+        None,
+        None,
+        Some(mask.view()),
+        None,
+    )
+    .unwrap();
+    assert!((intensity[[0, 0]] - dm.intensity_weighted_lifetime).abs() < 1e-9);
+    assert!(mean[[1, 0]].is_nan());
+    let roi =
+        fitting::mem_roi_distribution(data.view(), mask.view(), PERIOD, None, None, None).unwrap();
+    assert!((roi.intensity_weighted_lifetime - dm.intensity_weighted_lifetime).abs() < 1e-9);
+
+    // assert invalid parameters
+    let few = fitting::MemOptions {
+        n_lifetimes: 1,
+        ..Default::default()
+    };
+    assert!(fitting::mem_lifetime_distribution(&mono, PERIOD, None, Some(few)).is_err());
+    assert!(fitting::mem_lifetime_distribution(&mono, PERIOD, Some(&[1.0]), None).is_err());
+}
+
+#[test]
+fn quality_chi_square() {
+    let data = [4.0, 9.0, 0.0, 16.0];
+    let model = [2.0, 12.0, 1.0, 16.0];
+
+    // assert the Neyman weighted statistics
+    let r = fitting::weighted_residuals(&data, &model).unwrap();
+    assert_eq!(r, vec![1.0, -1.0, -1.0, 0.0]);
+    assert_eq!(fitting::chi_square(&data, &model).unwrap(), 3.0);
+    assert_eq!(fitting::reduced_chi_square(&data, &model, 1).unwrap(), 1.0);
+
+    // assert invalid parameters
+    assert!(fitting::chi_square(&data, &model[..3]).is_err());
+    assert!(fitting::reduced_chi_square(&data, &model, 4).is_err());
+}
+
+#[test]
+fn quality_model_selection() {
+    // fit a monoexponential model to a biexponential decay
+    let d = decay(&[0.5, 4.0], &[800.0, 200.0]);
+    let (tau, amp) = fitting::tail_fit(&d, PERIOD, None, None).unwrap();
+    let mono = decay(&[tau], &[amp]);
+    let bi = decay(&[0.5, 4.0], &[800.0, 200.0]);
+
+    // assert the biexponential model is a better fit
+    let chi_mono = fitting::reduced_chi_square(&d, &mono, 2).unwrap();
+    let chi_bi = fitting::reduced_chi_square(&d, &bi, 4).unwrap();
+    assert!(chi_mono > 1.0);
+    assert!(chi_bi < 1e-12);
+
+    // assert the underfit residuals are serially correlated
+    let r = fitting::weighted_residuals(&d, &mono).unwrap();
+    assert!(fitting::durbin_watson(&r) < 0.1);
+    assert!(fitting::durbin_watson(&[0.0; 4]).is_nan());
+    assert_eq!(fitting::durbin_watson(&[1.0, -1.0]), 2.0);
+}
+
+#[test]
+fn quality_images() {
+    let data = Array3::from_shape_fn((2, 3, 4), |(r, c, k)| (r + c + k) as f64 * 4.0);
+    let model = data.mapv(|v| v + 2.0);
+    let r = fitting::weighted_residuals_image(data.view(), model.view(), None).unwrap();
+    let chi = fitting::reduced_chi_square_image(data.view(), model.view(), 1, None).unwrap();
+
+    // assert the residuals and reduced chi-square of a pixel
+    assert_eq!(r.shape(), [2, 3, 4]);
+    assert_eq!(r[[0, 0, 1]], -1.0);
+    let expected =
+        fitting::reduced_chi_square(&[8.0, 12.0, 16.0, 20.0], &[10.0, 14.0, 18.0, 22.0], 1)
+            .unwrap();
+    assert!((chi[[1, 1]] - expected).abs() < 1e-12);
+    assert!(fitting::weighted_residuals_image(data.view(), model.view(), Some(3)).is_err());
+}
+
+#[test]
+fn selection_select_model() {
+    let mono = noise::poisson_1d(&decay(&[2.0], &[2000.0]), 1.0, Some(7));
+    let bi = noise::poisson_1d(&decay(&[0.4, 3.0], &[8000.0, 2000.0]), 1.0, Some(7));
+
+    // assert each criterion selects the simulated number of components
+    for criterion in [
+        ModelCriterion::Aic,
+        ModelCriterion::Bic,
+        ModelCriterion::FTest { alpha: 0.05 },
+    ] {
+        assert_eq!(
+            fitting::select_model(&mono, PERIOD, criterion, None, None).unwrap(),
+            1
+        );
+        assert_eq!(
+            fitting::select_model(&bi, PERIOD, criterion, None, None).unwrap(),
+            2
+        );
+    }
+
+    // assert invalid parameters
+    let f = ModelCriterion::FTest { alpha: 1.5 };
+    assert!(fitting::select_model(&mono, PERIOD, f, None, None).is_err());
+    assert!(fitting::select_model(&mono, PERIOD, ModelCriterion::Bic, Some(0), None).is_err());
+}
+
+#[test]
+fn selection_select_model_image() {
+    let d = noise::poisson_1d(&decay(&[0.4, 3.0], &[8000.0, 2000.0]), 1.0, Some(3));
+    let data = Array3::from_shape_fn((2, 2, SAMPLES), |(_, _, k)| d[k]);
+    let orders = fitting::select_model_image(
+        data.view(),
+        PERIOD,
+        ModelCriterion::Bic,
+        Some(2),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // assert the order map
+    assert_eq!(orders.shape(), [2, 2]);
+    assert!(orders.iter().all(|&o| o == 2));
+}
+
+#[test]
+fn gaussian_gaussian_2d() {
+    // an elongated spot with a background offset
+    let data = Array2::from_shape_fn((15, 17), |(r, c)| {
+        let d = (r as f64 - 7.3).powi(2) / 4.5 + (c as f64 - 8.6).powi(2) / 8.0;
+        20.0 + 300.0 * (-d).exp()
+    });
+    let fit = fitting::gaussian_2d(data.view(), None).unwrap();
+
+    // assert the fit parameters
+    assert!((fit.amplitude - 300.0).abs() < 1e-6);
+    assert!((fit.offset - 20.0).abs() < 1e-6);
+    assert!((fit.center[0] - 7.3).abs() < 1e-8);
+    assert!((fit.center[1] - 8.6).abs() < 1e-8);
+    assert!((fit.sigma[0] - 1.5).abs() < 1e-8);
+    assert!((fit.sigma[1] - 2.0).abs() < 1e-8);
+    assert!(fit.residual < 1e-12);
+
+    // a flat image has no peak
+    assert!(fitting::gaussian_2d(Array2::<u16>::ones((5, 5)).view(), None).is_err());
+}
+
+#[test]
+fn gaussian_gaussian_3d() {
+    // a noisy spot with an elongated axial width
+    let data = Array3::from_shape_fn((13, 11, 11), |(p, r, c)| {
+        let d = (p as f64 - 6.2).powi(2) / 18.0
+            + (r as f64 - 5.1).powi(2) / 2.0
+            + (c as f64 - 4.8).powi(2) / 2.0;
+        (5.0 + 2000.0 * (-d).exp()).round() as u16
+    });
+    let data = noise::poisson_3d(data.view(), 1.0, Some(42), None).unwrap();
+    let fit = fitting::gaussian_3d(data.view(), None).unwrap();
+
+    // assert the fit parameters
+    assert!((fit.center[0] - 6.2).abs() < 0.05);
+    assert!((fit.center[1] - 5.1).abs() < 0.05);
+    assert!((fit.sigma[0] - 3.0).abs() < 0.1);
+    assert!((fit.sigma[2] - 1.0).abs() < 0.05);
+}
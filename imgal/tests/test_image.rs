@@ -1,6 +1,9 @@
-use ndarray::{Array, Array2};
+use ndarray::{Array, Array2, Array3, array, s};
 
-use imgal::image;
+use imgal::image::annotate::{self, Corner, ScaleBarOptions};
+use imgal::image::render;
+use imgal::image::{self, AxisLabel, MathOp, PadMode, math};
+use imgal::phasor::time_domain;
 use imgal::statistics::min_max;
 
 #[test]
@@ -13,7 +16,7 @@ fn image_histogram() {
             ((i - 15) * 20 + j) as u16
         }
     });
-    let hist = image::histogram(data.view().into_dyn(), Some(20));
+    let hist = image::histogram(data.view().into_dyn(), Some(20), None);
 
     // wrap hist vector as an array for assert tests
     let arr = Array::from_vec(hist);
@@ -25,3 +28,604 @@ fn image_histogram() {
     assert_eq!(arr[10], 5);
     assert_eq!(arr.len(), 20);
 }
+
+#[test]
+fn image_histogram_range() {
+    // values outside of an explicit range are not counted
+    let data = Array::from_vec(vec![0.0, 1.0, 2.5, 5.0, 10.0, 11.0]);
+    let hist = image::histogram(data.view().into_dyn(), Some(5), Some((0.0, 10.0)));
+
+    assert_eq!(hist, vec![2, 1, 1, 0, 1]);
+}
+
+#[test]
+fn image_histogram_weighted() {
+    let data = Array::from_vec(vec![0.0, 1.0, 2.5, 5.0, 10.0, 11.0]);
+    let weights = Array::from_vec(vec![1.0, 2.0, 0.5, 1.0, 0.5, 3.0]);
+
+    // weighted counts and bin edges
+    let (hist, edges) = image::histogram_weighted(
+        data.view().into_dyn(),
+        Some(5),
+        Some((0.0, 10.0)),
+        Some(weights.view().into_dyn()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(hist, vec![3.0, 0.5, 1.0, 0.0, 0.5]);
+    assert_eq!(edges, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+
+    // density histogram integrates to 1
+    let (dens, _) = image::histogram_weighted(
+        data.view().into_dyn(),
+        Some(5),
+        Some((0.0, 10.0)),
+        None,
+        Some(true),
+    )
+    .unwrap();
+    assert!((dens.iter().sum::<f64>() * 2.0 - 1.0).abs() < 1e-12);
+
+    // invalid range
+    assert!(
+        image::histogram_weighted(data.view().into_dyn(), None, Some((1.0, 1.0)), None, None)
+            .is_err()
+    );
+}
+
+#[test]
+fn image_histogram_u16() {
+    let data = Array::from_vec(vec![0_u16, 3, 3, 7]);
+    let hist = image::histogram_u16(data.view().into_dyn());
+
+    assert_eq!(hist, vec![1, 0, 0, 2, 0, 0, 0, 1]);
+}
+
+#[test]
+fn image_render_lifetime_rgb() {
+    // create a lifetime ramp and an intensity ramp
+    let tau = Array2::from_shape_fn((4, 5), |(_, c)| 1.0 + c as f64);
+    let inten = Array2::from_shape_fn((4, 5), |(r, _)| (r * 10) as u16);
+    let mut tau_nan = tau.clone();
+    tau_nan[[3, 0]] = f64::NAN;
+
+    // render with the default hue ramp and an automatic lifetime range
+    let rgb = render::lifetime_rgb(tau_nan.view(), inten.view(), None, None).unwrap();
+
+    assert_eq!(rgb.dim(), (4, 5, 3));
+    assert_eq!(rgb.slice(s![3, 4, ..]).to_vec(), vec![255, 0, 0]);
+    assert_eq!(rgb.slice(s![3, 0, ..]).to_vec(), vec![0, 0, 0]);
+    assert_eq!(rgb.slice(s![0, 2, ..]).to_vec(), vec![0, 0, 0]);
+
+    // render with a perceptual colormap and a fixed lifetime range
+    let rgb = render::lifetime_rgb(
+        tau.view(),
+        inten.view(),
+        Some(render::Colormap::Viridis),
+        Some((1.0, 9.0)),
+    )
+    .unwrap();
+
+    assert_eq!(rgb.slice(s![3, 0, ..]).to_vec(), vec![68, 1, 84]);
+    assert_eq!(rgb.slice(s![3, 4, ..]).to_vec(), vec![33, 145, 140]);
+    assert_eq!(
+        render::Colormap::from_name("Magma"),
+        Some(render::Colormap::Magma)
+    );
+}
+
+#[test]
+fn image_render_apply_colormap() {
+    // a ramp from 0.0 to 8.0 with a NaN pixel
+    let mut data = Array2::from_shape_fn((2, 9), |(_, c)| c as f64);
+    data[[1, 4]] = f64::NAN;
+
+    // render with the default colormap and an automatic range
+    let rgb = render::apply_colormap(data.view(), None, None).unwrap();
+    assert_eq!(rgb.dim(), (2, 9, 3));
+    assert_eq!(rgb.slice(s![0, 0, ..]).to_vec(), vec![68, 1, 84]);
+    assert_eq!(rgb.slice(s![0, 8, ..]).to_vec(), vec![253, 231, 37]);
+    assert_eq!(rgb.slice(s![1, 4, ..]).to_vec(), vec![0, 0, 0]);
+
+    // a fixed range clamps the values outside of it
+    let rgb = render::apply_colormap(data.view(), Some(render::Colormap::Turbo), Some((0.0, 4.0)))
+        .unwrap();
+    assert_eq!(rgb.slice(s![0, 4, ..]).to_vec(), vec![144, 13, 0]);
+    assert_eq!(rgb.slice(s![0, 8, ..]).to_vec(), vec![144, 13, 0]);
+    assert!(render::apply_colormap(data.view(), None, Some((4.0, 4.0))).is_err());
+}
+
+#[test]
+fn image_render_overlays() {
+    // a gray ramp with a mask and two labels
+    let data = Array2::from_shape_fn((4, 4), |(_, c)| (c * 85) as u8);
+    let mask = Array2::from_shape_fn((4, 4), |(r, _)| r == 0);
+    let labels = Array2::from_shape_fn((4, 4), |(r, _)| match r {
+        1 => 1,
+        2 => 2,
+        _ => 0,
+    });
+
+    // masked pixels are blended with the color, others stay gray
+    let rgb =
+        render::overlay_mask(data.view(), mask.view(), Some([0, 255, 0]), Some(1.0), None).unwrap();
+    assert_eq!(rgb.slice(s![0, 3, ..]).to_vec(), vec![0, 255, 0]);
+    assert_eq!(rgb.slice(s![1, 3, ..]).to_vec(), vec![255, 255, 255]);
+    let rgb = render::overlay_mask(data.view(), mask.view(), None, None, None).unwrap();
+    assert_eq!(rgb.slice(s![0, 0, ..]).to_vec(), vec![128, 0, 0]);
+
+    // each label has a distinct color
+    let rgb = render::overlay_labels(data.view(), labels.view(), Some(1.0), None).unwrap();
+    assert_eq!(rgb.slice(s![3, 1, ..]).to_vec(), vec![85, 85, 85]);
+    assert_ne!(rgb.slice(s![1, 0, ..]), rgb.slice(s![2, 0, ..]));
+    assert_eq!(rgb.slice(s![1, 0, ..]), rgb.slice(s![1, 3, ..]));
+
+    // invalid parameters
+    let small = Array2::<bool>::default((2, 2));
+    assert!(render::overlay_mask(data.view(), small.view(), None, None, None).is_err());
+    assert!(render::overlay_labels(data.view(), labels.view(), Some(1.5), None).is_err());
+}
+
+#[test]
+fn image_annotate() {
+    // a black RGB image with a 10 µm scale bar at 0.5 µm per pixel
+    let mut rgb = Array3::<u8>::zeros((40, 60, 3));
+    annotate::draw_scale_bar(rgb.view_mut(), 0.5, 10.0, None).unwrap();
+    let bar: Vec<(usize, usize)> = rgb
+        .slice(s![.., .., 0])
+        .indexed_iter()
+        .filter(|&(_, &v)| v == 255)
+        .map(|(idx, _)| idx)
+        .collect();
+    assert_eq!(bar.len(), 4 * 20);
+    assert_eq!(bar.first(), Some(&(28, 32)));
+    assert_eq!(bar.last(), Some(&(31, 51)));
+
+    // a labeled scale bar in the top left corner
+    let mut rgb = Array3::<u8>::zeros((40, 60, 3));
+    let options = ScaleBarOptions {
+        corner: Corner::TopLeft,
+        color: [0, 255, 0],
+        label: Some("10 µm".to_string()),
+        ..Default::default()
+    };
+    annotate::draw_scale_bar(rgb.view_mut(), 0.5, 10.0, Some(options)).unwrap();
+    assert_eq!(rgb.slice(s![8, 8, ..]).to_vec(), vec![0, 255, 0]);
+    let label_pixels = rgb
+        .slice(s![14.., .., 1])
+        .iter()
+        .filter(|&&v| v == 255)
+        .count();
+    assert!(label_pixels > 20);
+
+    // text size and clipping at the image edges
+    assert_eq!(annotate::text_size("AB", None), (7, 11));
+    assert_eq!(annotate::text_size("A\nB", Some(2)), (32, 10));
+    let mut rgb = Array3::<u8>::zeros((10, 10, 3));
+    annotate::draw_text(rgb.view_mut(), "I", (0, 0), [255, 0, 0], None).unwrap();
+    assert_eq!(rgb.slice(s![0, 1..4, 0]).to_vec(), vec![255, 255, 255]);
+    assert_eq!(rgb[[1, 1, 0]], 0);
+    annotate::draw_text(rgb.view_mut(), "W", (8, 8), [255, 0, 0], Some(3)).unwrap();
+
+    // a closed square polyline and label outlines
+    let mut rgb = Array3::<u8>::zeros((10, 10, 3));
+    let square = [(1.0, 1.0), (1.0, 5.0), (5.0, 5.0), (5.0, 1.0)];
+    annotate::draw_polyline(rgb.view_mut(), &square, [0, 0, 255], true).unwrap();
+    let outline = rgb
+        .slice(s![.., .., 2])
+        .iter()
+        .filter(|&&v| v == 255)
+        .count();
+    assert_eq!(outline, 16);
+    let labels = Array2::from_shape_fn((10, 10), |(r, c)| usize::from(r >= 2 && c >= 2));
+    let mut rgb = Array3::<u8>::zeros((10, 10, 3));
+    annotate::draw_outlines(rgb.view_mut(), labels.view(), Some([255, 255, 0])).unwrap();
+    assert_eq!(rgb.slice(s![2, 5, ..]).to_vec(), vec![255, 255, 0]);
+    assert_eq!(rgb.slice(s![5, 5, ..]).to_vec(), vec![0, 0, 0]);
+
+    // invalid parameters
+    let mut rgb = Array3::<u8>::zeros((40, 60, 3));
+    assert!(annotate::draw_scale_bar(rgb.view_mut(), 0.5, 30.0, None).is_err());
+    assert!(annotate::draw_scale_bar(rgb.view_mut(), 0.0, 10.0, None).is_err());
+    assert!(annotate::draw_text(rgb.view_mut(), "A", (0, 0), [0, 0, 0], Some(0)).is_err());
+    let mut gray = Array3::<u8>::zeros((40, 60, 1));
+    assert!(annotate::draw_text(gray.view_mut(), "A", (0, 0), [0, 0, 0], None).is_err());
+}
+
+#[test]
+fn image_anscombe() {
+    // create Poisson count data
+    let data = Array::from_vec(vec![0_u16, 1, 5, 20, 100]).into_dyn();
+
+    // transform and invert
+    let a = image::anscombe(data.view());
+    let biased = image::anscombe_inverse(a.view(), Some(false));
+    let unbiased = image::anscombe_inverse(a.view(), None);
+
+    // assert forward values and algebraic round trip
+    assert!((a[0] - 2.0 * 0.375_f64.sqrt()).abs() < 1e-12);
+    assert!((a[4] - 2.0 * 100.375_f64.sqrt()).abs() < 1e-12);
+    data.iter()
+        .zip(biased.iter())
+        .for_each(|(&d, &b)| assert!((d as f64 - b).abs() < 1e-9));
+    assert!(unbiased.iter().all(|&v| v >= 0.0));
+    assert!((unbiased[4] - 100.0).abs() < 0.5);
+}
+
+#[test]
+fn image_pad_modes() {
+    let data = array![1.0, 2.0, 3.0].into_dyn();
+
+    // pad with each mode
+    let constant = image::pad(data.view(), &[(2, 1)], PadMode::Constant, Some(9.0)).unwrap();
+    let reflect = image::pad(data.view(), &[(2, 4)], PadMode::Reflect, None).unwrap();
+    let edge = image::pad(data.view(), &[(2, 1)], PadMode::Edge, None).unwrap();
+    let wrap = image::pad(data.view(), &[(2, 1)], PadMode::Wrap, None).unwrap();
+
+    // assert padded values
+    assert_eq!(
+        constant.into_raw_vec_and_offset().0,
+        vec![9.0, 9.0, 1.0, 2.0, 3.0, 9.0]
+    );
+    assert_eq!(
+        reflect.into_raw_vec_and_offset().0,
+        vec![3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0]
+    );
+    assert_eq!(
+        edge.into_raw_vec_and_offset().0,
+        vec![1.0, 1.0, 1.0, 2.0, 3.0, 3.0]
+    );
+    assert_eq!(
+        wrap.into_raw_vec_and_offset().0,
+        vec![2.0, 3.0, 1.0, 2.0, 3.0, 1.0]
+    );
+}
+
+#[test]
+fn image_pad_2d_and_invalid() {
+    let data = array![[1, 2], [3, 4]].into_dyn();
+
+    // pad rows only with edge values
+    let padded = image::pad(data.view(), &[(1, 0), (0, 0)], PadMode::Edge, None).unwrap();
+
+    // assert shape and values
+    assert_eq!(padded.shape(), &[3, 2]);
+    assert_eq!(padded[[0, 1]], 2);
+    assert!(image::pad(data.view(), &[(1, 1)], PadMode::Edge, None).is_err());
+}
+
+#[test]
+fn image_crop_and_crop_center() {
+    let data = Array2::from_shape_fn((5, 6), |(r, c)| (r * 6 + c) as f64).into_dyn();
+
+    // crop a region and the center
+    let region = image::crop(data.view(), &[1, 2], &[2, 3]).unwrap();
+    let center = image::crop_center(data.view(), &[3, 2]).unwrap();
+
+    // assert cropped values
+    assert_eq!(region.shape(), &[2, 3]);
+    assert_eq!(region[[0, 0]], 8.0);
+    assert_eq!(center.shape(), &[3, 2]);
+    assert_eq!(center[[0, 0]], 8.0);
+    assert!(image::crop(data.view(), &[4, 0], &[2, 1]).is_err());
+}
+
+#[test]
+fn image_montage_grid_and_borders() {
+    // create a stack of 3 constant 2 x 2 images
+    let stack = ndarray::Array3::from_shape_fn((3, 2, 2), |(p, _, _)| (p + 1) as u16);
+
+    // tile with 1 pixel borders
+    let m = image::montage(stack.view(), None, Some(1), Some(9)).unwrap();
+
+    // assert the grid shape, tiles, borders and empty cell
+    assert_eq!(m.dim(), (7, 7));
+    assert_eq!(m[[0, 0]], 9);
+    assert_eq!(m[[1, 1]], 1);
+    assert_eq!(m[[1, 4]], 2);
+    assert_eq!(m[[4, 1]], 3);
+    assert_eq!(m[[3, 1]], 9);
+    assert_eq!(m[[4, 4]], 9);
+    assert!(image::montage(stack.view(), Some(0), None, None).is_err());
+}
+
+#[test]
+fn image_stack_from_slices() {
+    let a = array![[1.0, 2.0], [3.0, 4.0]];
+    let b = array![[5.0, 6.0], [7.0, 8.0]];
+    let c = array![[1.0, 2.0, 3.0]];
+
+    // assemble the stack
+    let stack = image::stack_from_slices(&[a.view(), b.view()]).unwrap();
+
+    // assert shape and values
+    assert_eq!(stack.dim(), (2, 2, 2));
+    assert_eq!(stack[[1, 1, 0]], 7.0);
+    assert!(image::stack_from_slices(&[a.view(), c.view()]).is_err());
+    assert!(image::stack_from_slices::<f64>(&[]).is_err());
+}
+
+#[test]
+fn image_meta() {
+    // create metadata from labels and from an axis order string
+    let meta = image::ImageMeta::new(&[AxisLabel::Time, AxisLabel::Y, AxisLabel::X])
+        .unwrap()
+        .with_pixel_size(AxisLabel::Y, 0.5)
+        .unwrap();
+    let parsed = image::ImageMeta::from_order("TYX").unwrap();
+
+    // assert axes are resolved by label
+    assert_eq!(meta.axes(), parsed.axes());
+    assert_eq!(meta.index(AxisLabel::X), Some(2));
+    assert_eq!(meta.index(AxisLabel::Channel), None);
+    assert_eq!(meta.decay_axis(), Some(0));
+    assert_eq!(meta.pixel_size(AxisLabel::Y), Some(0.5));
+    assert_eq!(meta.pixel_size(AxisLabel::X), None);
+    assert_eq!(meta.resolve(AxisLabel::Time, &[8, 4, 4]), Ok(0));
+    assert!(meta.resolve(AxisLabel::Time, &[4, 4]).is_err());
+    assert!(meta.resolve(AxisLabel::Z, &[8, 4, 4]).is_err());
+    assert_eq!(
+        image::ImageMeta::from_order("yxtl").unwrap().decay_axis(),
+        Some(3)
+    );
+
+    // assert invalid metadata is rejected
+    assert!(image::ImageMeta::from_order("yxy").is_err());
+    assert!(image::ImageMeta::from_order("yxq").is_err());
+    assert!(meta.clone().with_pixel_size(AxisLabel::Z, 1.0).is_err());
+    assert!(meta.with_pixel_size(AxisLabel::X, 0.0).is_err());
+}
+
+#[test]
+fn image_meta_phasor_axis() {
+    // create the same decay image in (y, x, t) and (t, y, x) axis order
+    let yxt = Array::from_shape_fn((4, 5, 16), |(y, x, t)| {
+        ((-(t as f64) / (1.0 + (y + x) as f64)).exp() * 100.0) as u16
+    });
+    let tyx = yxt.view().permuted_axes([2, 0, 1]);
+
+    // resolve the decay axis from the metadata of each image
+    let yxt_axis = image::ImageMeta::from_order("yxt")
+        .unwrap()
+        .resolve(AxisLabel::Time, yxt.shape())
+        .unwrap();
+    let tyx_axis = image::ImageMeta::from_order("tyx")
+        .unwrap()
+        .resolve(AxisLabel::Time, tyx.shape())
+        .unwrap();
+    let a = time_domain::image(yxt.view(), 12.5, None, None, Some(yxt_axis), None).unwrap();
+    let b = time_domain::image(tyx, 12.5, None, None, Some(tyx_axis), None).unwrap();
+
+    // assert both axis orders give the same phasor
+    assert_eq!(a, b);
+}
+
+#[test]
+fn image_distance_transform() {
+    // create a mask with a few background pixels
+    let mut mask = Array2::<bool>::from_elem((9, 12), true);
+    for (r, c) in [(0, 0), (4, 7), (8, 2), (6, 11)] {
+        mask[[r, c]] = false;
+    }
+    let meta = image::ImageMeta::from_order("yx")
+        .unwrap()
+        .with_pixel_size(AxisLabel::Y, 0.5)
+        .unwrap()
+        .with_pixel_size(AxisLabel::X, 0.2)
+        .unwrap();
+    let spacing = meta.spacing();
+
+    // compute the anisotropic distance transform
+    let dist = image::distance_transform(mask.view(), Some([spacing[0], spacing[1]])).unwrap();
+
+    // assert the distances match a brute force search
+    let background: Vec<(usize, usize)> = mask
+        .indexed_iter()
+        .filter(|(_, m)| !**m)
+        .map(|(i, _)| i)
+        .collect();
+    for ((r, c), d) in dist.indexed_iter() {
+        let expected = background
+            .iter()
+            .map(|&(br, bc)| {
+                let dr = (r as f64 - br as f64) * 0.5;
+                let dc = (c as f64 - bc as f64) * 0.2;
+                (dr * dr + dc * dc).sqrt()
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!((d - expected).abs() < 1e-12);
+    }
+    let full = Array2::<bool>::from_elem((3, 3), true);
+    assert!(
+        image::distance_transform(full.view(), None)
+            .unwrap()
+            .iter()
+            .all(|d| d.is_infinite())
+    );
+    assert!(image::distance_transform(mask.view(), Some([1.0, 0.0])).is_err());
+}
+
+#[test]
+fn image_integral_image() {
+    // 2D integral image with a leading zero row and column
+    let data = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]].into_dyn();
+    let integral = image::integral_image(data.view());
+    assert_eq!(integral.shape(), &[3, 4]);
+    assert_eq!(integral[[1, 3]], 6.0);
+    assert_eq!(integral[[2, 3]], 21.0);
+
+    // box sums match direct sums
+    assert_eq!(
+        image::box_sum(integral.view(), &[0, 1], &[2, 3]).unwrap(),
+        16.0
+    );
+    assert_eq!(
+        image::box_sum(integral.view(), &[1, 1], &[1, 3]).unwrap(),
+        0.0
+    );
+    let squared = image::integral_image_squared(data.view());
+    assert_eq!(
+        image::box_sum(squared.view(), &[1, 0], &[2, 2]).unwrap(),
+        41.0
+    );
+
+    // 3D box sums match direct sums
+    let stack = Array3::from_shape_fn((4, 5, 6), |(z, r, c)| (z * 30 + r * 6 + c) as u16);
+    let integral = image::integral_image(stack.view().into_dyn());
+    let expected: f64 = stack
+        .slice(s![1..3, 2..5, 0..4])
+        .iter()
+        .map(|&v| v as f64)
+        .sum();
+    assert_eq!(
+        image::box_sum(integral.view(), &[1, 2, 0], &[3, 5, 4]).unwrap(),
+        expected
+    );
+
+    // invalid boxes
+    assert!(image::box_sum(integral.view(), &[0, 0], &[1, 1]).is_err());
+    assert!(image::box_sum(integral.view(), &[2, 0, 0], &[1, 1, 1]).is_err());
+    assert!(image::box_sum(integral.view(), &[0, 0, 0], &[5, 1, 1]).is_err());
+}
+
+#[test]
+fn image_math() {
+    // integer results saturate at the type range
+    let a = array![[10u16, 200], [65000, 3]].into_dyn();
+    let b = array![[20u16, 100], [1000, 3]].into_dyn();
+    let diff = math::binary(a.view(), b.view(), MathOp::Subtract).unwrap();
+    assert_eq!(diff, array![[0u16, 100], [64000, 0]].into_dyn());
+    let sum = math::binary(a.view(), b.view(), MathOp::Add).unwrap();
+    assert_eq!(sum[[1, 0]], u16::MAX);
+    let ratio = math::binary(a.view(), b.view(), MathOp::Divide).unwrap();
+    assert_eq!(ratio[[0, 0]], 1);
+    assert_eq!(
+        math::binary(a.view(), b.view(), MathOp::Max).unwrap(),
+        array![[20u16, 200], [65000, 3]].into_dyn()
+    );
+
+    // per channel values broadcast over a (row, col, ch) image
+    let mut rgb = Array::from_elem((2, 2, 3), 1.0_f32).into_dyn();
+    let gains = array![1.0, 2.0, 3.0].into_dyn();
+    math::binary_mut(rgb.view_mut(), gains.view(), MathOp::Multiply).unwrap();
+    assert_eq!(rgb[[1, 1, 2]], 3.0);
+    assert!(math::binary(rgb.view(), a.view(), MathOp::Add).is_err());
+
+    // scalar, absolute value and clip operations
+    let signed = array![-5i16, 0, 7, i16::MIN].into_dyn();
+    assert_eq!(
+        math::scalar(signed.view(), 2.5, MathOp::Multiply),
+        array![-13i16, 0, 18, i16::MIN].into_dyn()
+    );
+    assert_eq!(
+        math::abs(signed.view()),
+        array![5i16, 0, 7, i16::MAX].into_dyn()
+    );
+    let mut clipped = signed.clone();
+    math::clip_mut(clipped.view_mut(), Some(-1.0), Some(5.0)).unwrap();
+    assert_eq!(clipped, array![-1i16, 0, 5, -1].into_dyn());
+    assert!(math::clip(signed.view(), Some(1.0), Some(0.0)).is_err());
+}
+
+#[test]
+fn image_saturation() {
+    // a (frame, row, col) u16 stack with saturated pixels in the last frame
+    let mut data = Array::from_elem((3, 2, 2), 100u16).into_dyn();
+    data[[2, 0, 0]] = u16::MAX;
+    data[[2, 1, 1]] = u16::MAX;
+    data[[1, 0, 1]] = 4095;
+
+    // the default level is the type maximum
+    let mask = image::saturation_mask(data.view(), None);
+    assert_eq!(mask.iter().filter(|&&m| m).count(), 2);
+    assert!(mask[[2, 0, 0]]);
+    assert_eq!(
+        image::saturation_counts(data.view(), None, None).unwrap(),
+        vec![0, 0, 2]
+    );
+
+    // a 12-bit level flags more pixels
+    assert_eq!(
+        image::saturation_counts(data.view(), Some(4095.0), None).unwrap(),
+        vec![0, 1, 2]
+    );
+    assert!(image::saturation_counts(data.view(), None, Some(3)).is_err());
+
+    // per region counts
+    let labels = Array::from_shape_fn((3, 2, 2), |(_, _, c)| c + 1).into_dyn();
+    assert_eq!(
+        image::saturation_region_counts(data.view(), labels.view(), Some(4095.0)).unwrap(),
+        vec![0, 1, 2]
+    );
+    assert!(
+        image::saturation_region_counts(
+            data.view(),
+            labels.slice(s![.., .., ..1]).into_dyn(),
+            None
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn image_ratio() {
+    // a ratio of 2 with a dim and a negative masked pixel
+    let a = array![[210.0, 410.0], [10.0, 15.0]].into_dyn();
+    let b = array![[110.0, 210.0], [110.0, 5.0]].into_dyn();
+    let (ratio, sigma) =
+        image::ratio(a.view(), b.view(), Some(10.0), Some(10.0), Some(1.0), None).unwrap();
+    assert_eq!(ratio[[0, 0]], 2.0);
+    assert_eq!(ratio[[0, 1]], 2.0);
+    assert!(ratio[[1, 0]].is_nan() && sigma[[1, 0]].is_nan());
+    assert!(ratio[[1, 1]].is_nan());
+
+    // the Poisson uncertainty shrinks with the intensity
+    let expected = 2.0 * (210.0 / 200.0_f64.powi(2) + 110.0 / 100.0_f64.powi(2)).sqrt();
+    assert!((sigma[[0, 0]] - expected).abs() < 1e-12);
+    assert!(sigma[[0, 1]] < sigma[[0, 0]]);
+
+    // clamped ratios
+    let (clamped, _) = image::ratio(
+        a.view(),
+        b.view(),
+        Some(10.0),
+        Some(10.0),
+        None,
+        Some((0.0, 1.5)),
+    )
+    .unwrap();
+    assert_eq!(clamped[[0, 0]], 1.5);
+
+    // calibration to concentration, the ratio midway between the limits is
+    // the dissociation constant times beta
+    let calibration = image::RatioCalibration {
+        kd: 224.0,
+        r_min: 1.0,
+        r_max: 3.0,
+        beta: 2.0,
+    };
+    let (conc, conc_sigma) =
+        image::ratio_to_concentration(ratio.view(), sigma.view(), calibration).unwrap();
+    assert!((conc[[0, 0]] - 448.0).abs() < 1e-9);
+    assert!((conc_sigma[[0, 0]] - 448.0 * 2.0 * sigma[[0, 0]]).abs() < 1e-9);
+    assert!(conc[[1, 0]].is_nan());
+
+    // invalid parameters
+    assert!(
+        image::ratio(
+            a.view(),
+            b.slice(s![.., ..1]).into_dyn(),
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err()
+    );
+    assert!(image::ratio(a.view(), b.view(), None, None, None, Some((2.0, 1.0))).is_err());
+    let invalid = image::RatioCalibration {
+        r_min: 3.0,
+        ..calibration
+    };
+    assert!(image::ratio_to_concentration(ratio.view(), sigma.view(), invalid).is_err());
+}
@@ -1,5 +1,6 @@
 use imgal::distribution::gaussian;
 use imgal::integration;
+use imgal::statistics::Summation;
 
 // helper functions
 fn get_gaussian_distribution(bins: usize) -> Vec<f64> {
@@ -11,7 +12,7 @@ fn integration_composite_simpson() {
     let gauss_arr = get_gaussian_distribution(512);
 
     assert_eq!(
-        integration::composite_simpson(&gauss_arr, None),
+        integration::composite_simpson(&gauss_arr, None, None),
         0.9986155934120933
     );
 }
@@ -20,7 +21,10 @@ fn integration_composite_simpson() {
 fn integration_midpoint() {
     let gauss_arr = get_gaussian_distribution(512);
 
-    assert_eq!(integration::midpoint(&gauss_arr, None), 1.0000000000000009);
+    assert_eq!(
+        integration::midpoint(&gauss_arr, None, None),
+        1.0000000000000009
+    );
 }
 
 #[test]
@@ -28,7 +32,22 @@ fn integration_simpson() {
     let gauss_arr = get_gaussian_distribution(511);
 
     assert_eq!(
-        integration::simpson(&gauss_arr, None).unwrap(),
+        integration::simpson(&gauss_arr, None, None).unwrap(),
         0.9986128844345734
     );
 }
+
+#[test]
+fn integration_summation() {
+    let gauss_arr = get_gaussian_distribution(511);
+
+    // all summation algorithms agree on a well conditioned curve
+    for summation in [Summation::Naive, Summation::Pairwise, Summation::Kahan] {
+        assert!((integration::midpoint(&gauss_arr, None, Some(summation)) - 1.0).abs() < 1e-12);
+        assert!(
+            (integration::simpson(&gauss_arr, None, Some(summation)).unwrap() - 0.9986128844345732)
+                .abs()
+                < 1e-12
+        );
+    }
+}
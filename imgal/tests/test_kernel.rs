@@ -47,3 +47,25 @@ fn neighborhood_weighted_sphere() {
     assert_eq!(k[[2, 5, 1]], 0.2857142857142857);
     assert_eq!(k[[8, 9, 10]], 0.0);
 }
+
+#[test]
+fn neighborhood_gaussian_circle() {
+    // create a gaussian circle neighborhood kernel
+    let k = neighborhood::gaussian_circle(RADIUS, 2.0, None).unwrap();
+
+    assert_eq!(k.shape(), [11, 11]);
+    assert_eq!(k[[RADIUS, RADIUS]], 1.0);
+    assert!((k[[8, 1]] - (-25.0_f64 / 8.0).exp()).abs() < 1e-12);
+    assert_eq!(k[[2, 0]], 0.0);
+}
+
+#[test]
+fn neighborhood_gaussian_sphere() {
+    // create a gaussian sphere neighborhood kernel
+    let k = neighborhood::gaussian_sphere(RADIUS, 2.0, None).unwrap();
+
+    assert_eq!(k.shape(), [11, 11, 11]);
+    assert_eq!(k[[RADIUS, RADIUS, RADIUS]], 1.0);
+    assert!((k[[2, 5, 1]] - (-25.0_f64 / 8.0).exp()).abs() < 1e-12);
+    assert_eq!(k[[8, 9, 10]], 0.0);
+}
@@ -0,0 +1,152 @@
+use ndarray::{Array2, Array3};
+
+use imgal::localization::{self, Localization, LocalizationMethod};
+
+// acquisition parameters
+const FRAMES: usize = 40;
+const DRIFT: (f64, f64) = (0.02, -0.015);
+
+// create the true emitter positions on a grid
+fn emitters() -> Vec<(f64, f64)> {
+    (0..9)
+        .map(|e| {
+            let (i, j) = ((e / 3) as f64, (e % 3) as f64);
+            (8.0 + 9.0 * i + 0.1 * j, 8.0 + 9.0 * j + 0.07 * i)
+        })
+        .collect()
+}
+
+// create a time-lapse of drifting emitters, each off every third frame
+fn blinking_stack() -> Array3<f64> {
+    let pos = emitters();
+    Array3::from_shape_fn((FRAMES, 36, 36), |(t, r, c)| {
+        let (dr, dc) = (DRIFT.0 * t as f64, DRIFT.1 * t as f64);
+        10.0 + pos
+            .iter()
+            .enumerate()
+            .filter(|(e, _)| (t + e) % 3 != 0)
+            .map(|(_, &(er, ec))| {
+                let d = (r as f64 - er - dr).powi(2) + (c as f64 - ec - dc).powi(2);
+                200.0 * (-d / (2.0 * 1.2 * 1.2)).exp()
+            })
+            .sum::<f64>()
+    })
+}
+
+#[test]
+fn localize_localize() {
+    let locs = localization::localize(blinking_stack().view(), 4, Some(100.0), None, None).unwrap();
+
+    // assert the number of localizations and their sub-pixel positions
+    assert_eq!(
+        locs.len(),
+        (0..FRAMES)
+            .map(|t| (0..9).filter(|e| (t + e) % 3 != 0).count())
+            .sum()
+    );
+    let pos = emitters();
+    locs.iter().for_each(|l| {
+        let (dr, dc) = (DRIFT.0 * l.frame as f64, DRIFT.1 * l.frame as f64);
+        let d = pos
+            .iter()
+            .map(|&(er, ec)| (l.row - er - dr).hypot(l.col - ec - dc))
+            .fold(f64::INFINITY, f64::min);
+        assert!(d < 1e-3);
+        assert!((l.sigma - 1.2).abs() < 1e-3);
+        assert!((l.background - 10.0).abs() < 0.1);
+        assert!((l.intensity / (400.0 * std::f64::consts::PI * 1.44) - 1.0).abs() < 1e-3);
+    });
+
+    // invalid parameters
+    assert!(localization::localize(blinking_stack().view(), 0, None, None, None).is_err());
+    assert!(localization::localize(blinking_stack().view(), 4, None, None, Some(3)).is_err());
+}
+
+#[test]
+fn drift_drift_correct() {
+    let locs = localization::localize(blinking_stack().view(), 4, Some(100.0), None, None).unwrap();
+    let (corrected, drift) = localization::drift_correct(&locs, (36, 36), 8, None).unwrap();
+
+    // assert the drift of each segment center and the corrected positions,
+    // the drift is measured relative to the first segment center (frame 3.5)
+    assert_eq!(drift.len(), FRAMES);
+    assert_eq!(corrected.len(), locs.len());
+    for t in [3, 11, 19, 27, 35] {
+        let expected = (
+            DRIFT.0 * (t as f64 + 0.5 - 3.5),
+            DRIFT.1 * (t as f64 + 0.5 - 3.5),
+        );
+        assert!((drift[t].0 - expected.0).abs() < 0.05);
+        assert!((drift[t].1 - expected.1).abs() < 0.05);
+    }
+    let spread = |locs: &[Localization]| {
+        locs.iter()
+            .filter(|l| (l.row - 8.0).hypot(l.col - 8.0) < 2.0)
+            .map(|l| l.row)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), r| {
+                (lo.min(r), hi.max(r))
+            })
+    };
+    let (lo, hi) = spread(&locs);
+    assert!(hi - lo > 0.7);
+    let (lo, hi) = spread(&corrected);
+    assert!(hi - lo < 0.15);
+
+    // invalid parameters
+    assert!(localization::drift_correct(&locs, (36, 36), 0, None).is_err());
+}
+
+#[test]
+fn render_render() {
+    let locs = localization::localize(blinking_stack().view(), 4, Some(100.0), None, None).unwrap();
+
+    // a histogram and a Gaussian rendering keep the localization count
+    let hist = localization::render(&locs, (36, 36), None, None).unwrap();
+    assert_eq!(hist.dim(), (360, 360));
+    assert_eq!(hist.sum(), locs.len() as f64);
+    let (r, c) = (
+        ((locs[0].row + 0.5) * 10.0).floor() as usize,
+        ((locs[0].col + 0.5) * 10.0).floor() as usize,
+    );
+    assert!(hist[[r, c]] >= 1.0);
+    let image = localization::render(&locs, (36, 36), Some(4.0), Some(0.2)).unwrap();
+    assert_eq!(image.dim(), (144, 144));
+    assert!((image.sum() - locs.len() as f64).abs() < 1e-9);
+
+    // invalid parameters
+    assert!(localization::render(&locs, (36, 36), Some(0.0), None).is_err());
+    assert!(localization::render(&locs, (36, 36), None, Some(-1.0)).is_err());
+}
+
+#[test]
+fn phasor_phasor_position() {
+    // a spot on a constant background
+    let roi = Array2::from_shape_fn((9, 11), |(r, c)| {
+        let d = (r as f64 - 4.3).powi(2) + (c as f64 - 5.6).powi(2);
+        50.0 + 300.0 * (-d / (2.0 * 1.1 * 1.1)).exp()
+    });
+    let (r, c) = localization::phasor_position(roi.view());
+    assert!((r - 4.3).abs() < 0.02);
+    assert!((c - 5.6).abs() < 0.02);
+
+    // phasor localization of the time-lapse
+    let locs = localization::localize(
+        blinking_stack().view(),
+        4,
+        Some(100.0),
+        Some(LocalizationMethod::Phasor),
+        None,
+    )
+    .unwrap();
+    let pos = emitters();
+    assert_eq!(locs.len(), 240);
+    locs.iter().for_each(|l| {
+        let (dr, dc) = (DRIFT.0 * l.frame as f64, DRIFT.1 * l.frame as f64);
+        let d = pos
+            .iter()
+            .map(|&(er, ec)| (l.row - er - dr).hypot(l.col - ec - dc))
+            .fold(f64::INFINITY, f64::min);
+        assert!(d < 0.05);
+        assert!((l.sigma - 1.2).abs() < 0.1);
+    });
+}
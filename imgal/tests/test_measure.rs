@@ -0,0 +1,444 @@
+use ndarray::{Array2, Array3, array, s};
+
+use imgal::measure;
+use imgal::statistics::FocusMetric;
+
+#[test]
+fn measure_stack_qc() {
+    // a (t, row, col) stack with a textured image, a saturated frame and a
+    // flat (defocused) frame
+    let mut data = Array3::from_shape_fn((6, 16, 16), |(_, r, c)| {
+        if (r / 2 + c / 2) % 2 == 0 {
+            400u16
+        } else {
+            100
+        }
+    });
+    data.slice_mut(s![2, ..4, ..]).fill(u16::MAX);
+    data.slice_mut(s![4, .., ..]).fill(250);
+    let qc = measure::stack_qc(data.view(), None, None, None, None).unwrap();
+
+    // assert the per-frame columns
+    assert_eq!(qc.len(), 6);
+    assert_eq!(qc.mean[0], 250.0);
+    assert_eq!(qc.median[0], 250.0);
+    assert_eq!(qc.saturation[0], 0.0);
+    assert_eq!(qc.saturation[2], 0.25);
+    assert_eq!(qc.focus[4], 0.0);
+    assert!((qc.snr[0] - 250.0_f64.sqrt()).abs() < 1e-9);
+
+    // outlier frames of each column
+    assert_eq!(measure::qc_outliers(&qc.saturation, None), vec![2]);
+    assert_eq!(measure::qc_outliers(&qc.focus, None), vec![2, 4]);
+
+    // a 12-bit saturation level and invalid parameters
+    let qc = measure::stack_qc(
+        data.view(),
+        Some(400.0),
+        Some(FocusMetric::Brenner),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(qc.saturation[0], 0.5);
+    assert!(measure::stack_qc(data.view(), None, None, None, Some(3)).is_err());
+    assert!(measure::stack_qc(data.view(), None, None, Some(0.0), None).is_err());
+}
+
+#[test]
+fn measure_kymograph() {
+    // a (t, row, col) stack with a bright spot moving one column per frame
+    let mut data = Array3::<f32>::zeros((4, 9, 12));
+    for t in 0..4 {
+        data[[t, 4, 2 + t]] = 10.0;
+    }
+
+    // a horizontal path along the spot row
+    let kymo = measure::kymograph(data.view(), &[(4.0, 0.0), (4.0, 11.0)], None, None).unwrap();
+    assert_eq!(kymo.dim(), (4, 12));
+    for t in 0..4 {
+        assert_eq!(kymo[[t, 2 + t]], 10.0);
+        assert_eq!(kymo.row(t).sum(), 10.0);
+    }
+
+    // a wide path averages across the path and a bent path
+    let kymo = measure::kymograph(data.view(), &[(4.0, 0.0), (4.0, 11.0)], Some(3), None).unwrap();
+    assert!((kymo[[1, 3]] - 10.0 / 3.0).abs() < 1e-12);
+    let kymo = measure::kymograph(
+        data.view(),
+        &[(0.0, 2.0), (4.0, 2.0), (4.0, 6.0)],
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(kymo.dim(), (4, 9));
+    assert_eq!(kymo[[0, 4]], 10.0);
+    assert_eq!(kymo[[3, 7]], 10.0);
+
+    // invalid parameters
+    assert!(measure::kymograph(data.view(), &[(4.0, 0.0)], None, None).is_err());
+    assert!(measure::kymograph(data.view(), &[(4.0, 0.0), (4.0, 5.0)], Some(0), None).is_err());
+    assert!(measure::kymograph(data.view(), &[(4.0, 0.0), (4.0, 5.0)], None, Some(3)).is_err());
+}
+
+#[test]
+fn measure_profile_line() {
+    // a horizontal ramp image
+    let data = Array2::from_shape_fn((8, 10), |(_, c)| 2.0 * c as f64).into_dyn();
+
+    // a profile along the ramp
+    let (dist, prof) =
+        measure::profile_line(data.view(), &[3.0, 1.0], &[3.0, 9.0], None, None).unwrap();
+    assert_eq!(prof.len(), 9);
+    assert_eq!(dist[8], 8.0);
+    assert_eq!(prof[0], 2.0);
+    assert_eq!(prof[8], 18.0);
+
+    // fractional and wide profiles of each interpolation method
+    let (_, prof) = measure::profile_line(
+        data.view(),
+        &[2.0, 1.5],
+        &[2.0, 3.5],
+        Some(3),
+        Some(measure::Interpolation::Nearest),
+    )
+    .unwrap();
+    assert_eq!(prof.to_vec(), vec![4.0, 6.0, 8.0]);
+    for method in [
+        measure::Interpolation::Linear,
+        measure::Interpolation::Cubic,
+    ] {
+        let (_, prof) =
+            measure::profile_line(data.view(), &[2.0, 1.5], &[2.0, 3.5], Some(3), Some(method))
+                .unwrap();
+        prof.iter()
+            .zip([3.0, 5.0, 7.0])
+            .for_each(|(p, e)| assert!((p - e).abs() < 1e-12));
+    }
+
+    // a 3-dimensional profile along the planes and invalid parameters
+    let data = Array3::from_shape_fn((5, 6, 6), |(p, _, _)| p as f64).into_dyn();
+    let (_, prof) = measure::profile_line(
+        data.view(),
+        &[0.0, 2.0, 2.0],
+        &[4.0, 2.0, 2.0],
+        Some(3),
+        None,
+    )
+    .unwrap();
+    assert_eq!(prof.to_vec(), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert!(measure::profile_line(data.view(), &[0.0, 2.0], &[4.0, 2.0], None, None).is_err());
+    assert!(
+        measure::profile_line(
+            data.view(),
+            &[0.0, 2.0, 2.0],
+            &[4.0, 2.0, 2.0],
+            Some(0),
+            None
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn measure_esf_lsf_mtf() {
+    // a logistic edge tilted by ~5 degrees, with an MTF of πst / sinh(πst)
+    // where t = 2πf
+    let s = 0.8;
+    let tilt = 5.0_f64.to_radians().tan();
+    let data = Array2::from_shape_fn((48, 48), |(r, c)| {
+        let d = (c as f64 - 24.0 - tilt * (r as f64 - 24.0)) * tilt.atan().cos();
+        100.0 / (1.0 + (-d / s).exp())
+    });
+    let resp = measure::esf_lsf_mtf(data.view(), None).unwrap();
+
+    // assert the edge angle and response curves
+    assert!((resp.angle - 5.0).abs() < 0.1);
+    assert_eq!(resp.esf.len(), resp.distance.len());
+    assert!(resp.esf[0] < 1.0 && *resp.esf.last().unwrap() > 99.0);
+    assert_eq!(resp.mtf[0], 1.0);
+    for f in [0.1, 0.2, 0.3] {
+        let k = resp.frequency.iter().position(|&v| v >= f).unwrap();
+        let t = std::f64::consts::PI * s * 2.0 * std::f64::consts::PI * resp.frequency[k];
+        assert!((resp.mtf[k] - t / t.sinh()).abs() < 0.03);
+    }
+
+    // a horizontal edge and invalid parameters
+    let resp = measure::esf_lsf_mtf(data.t(), None).unwrap();
+    assert!((resp.angle - 5.0).abs() < 0.1);
+    assert!(measure::esf_lsf_mtf(data.view(), Some(0)).is_err());
+    assert!(measure::esf_lsf_mtf(Array2::<f64>::ones((8, 8)).view(), None).is_err());
+}
+
+#[test]
+fn measure_fit_psf() {
+    // a 2-dimensional image with round beads and an elongated bead
+    let beads = [
+        (12.3, 14.6, 1.5, 1.5),
+        (30.0, 40.2, 1.5, 1.5),
+        (40.5, 12.0, 1.2, 2.4),
+    ];
+    let data = Array2::from_shape_fn((56, 56), |(r, c)| {
+        10.0 + beads
+            .iter()
+            .map(|&(br, bc, sr, sc)| {
+                let d = (r as f64 - br).powi(2) / (2.0 * sr * sr)
+                    + (c as f64 - bc).powi(2) / (2.0 * sc * sc);
+                500.0 * (-d).exp()
+            })
+            .sum::<f64>()
+    });
+    let report =
+        measure::fit_psf(data.view().into_dyn(), 6, Some(100.0), Some(&[0.1, 0.1])).unwrap();
+
+    // assert the bead fits, the FWHM is 2.3548σ
+    assert_eq!(report.beads.len(), 3);
+    let bead = report
+        .beads
+        .iter()
+        .find(|b| (b.center[0] - 12.3).abs() < 1e-6)
+        .unwrap();
+    assert!((bead.center[1] - 14.6).abs() < 1e-6);
+    assert!((bead.fwhm[0] - 0.35322).abs() < 1e-4);
+    assert!((bead.amplitude - 500.0).abs() < 1e-3);
+    assert!((bead.offset - 10.0).abs() < 1e-3);
+    assert!(bead.asymmetry < 1e-6);
+    let bead = report
+        .beads
+        .iter()
+        .find(|b| (b.center[0] - 40.5).abs() < 1e-6)
+        .unwrap();
+    assert!((bead.asymmetry - 0.5).abs() < 1e-4);
+    assert!(report.asymmetry_mean > 0.16 && report.asymmetry_mean < 0.17);
+
+    // a 3-dimensional bead with an elongated axial PSF
+    let data = Array3::from_shape_fn((20, 20, 20), |(p, r, c)| {
+        let d = (p as f64 - 9.6).powi(2) / 18.0
+            + (r as f64 - 10.2).powi(2) / 2.0
+            + (c as f64 - 9.8).powi(2) / 2.0;
+        1000.0 * (-d).exp()
+    });
+    let report = measure::fit_psf(data.view().into_dyn(), 6, None, None).unwrap();
+    assert_eq!(report.beads.len(), 1);
+    assert!((report.fwhm_mean[0] - 3.0 * 2.35482).abs() < 1e-3);
+    assert!((report.fwhm_mean[2] - 2.35482).abs() < 1e-3);
+    assert_eq!(report.fwhm_std, vec![0.0; 3]);
+
+    // invalid parameters
+    assert!(measure::fit_psf(data.view().into_dyn(), 0, None, None).is_err());
+    assert!(measure::fit_psf(data.view().into_dyn(), 8, None, Some(&[1.0, 1.0])).is_err());
+}
+
+#[test]
+fn measure_anisotropy() {
+    // isotropic, fully parallel and background pixels
+    let par = array![[110.0, 410.0], [10.0, f64::NAN]].into_dyn();
+    let perp = array![[60.0, 10.0], [10.0, 50.0]].into_dyn();
+    let r = measure::anisotropy(
+        par.view(),
+        perp.view(),
+        Some(2.0),
+        Some(10.0),
+        Some(10.0),
+        None,
+    )
+    .unwrap();
+    assert!(r[[0, 0]].abs() < 1e-12);
+    assert!((r[[0, 1]] - 1.0).abs() < 1e-12);
+    assert!(r[[1, 0]].is_nan());
+    assert!(r[[1, 1]].is_nan());
+
+    // without a G-factor correction
+    let r = measure::anisotropy(par.view(), perp.view(), None, None, None, Some(100.0)).unwrap();
+    assert!((r[[0, 0]] - 50.0 / 230.0).abs() < 1e-12);
+    assert!(r[[1, 0]].is_nan());
+
+    // invalid parameters
+    assert!(measure::anisotropy(par.view(), perp.view(), Some(0.0), None, None, None).is_err());
+    assert!(
+        measure::anisotropy(
+            par.view(),
+            perp.slice(s![..1, ..]).into_dyn(),
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err()
+    );
+}
+
+// create a transient with a linear 10 frame rise and an exponential decay
+fn transient(t: usize, onset: usize, amplitude: f64) -> f64 {
+    if t < onset {
+        0.0
+    } else if t < onset + 10 {
+        amplitude * (t - onset) as f64 / 10.0
+    } else {
+        amplitude * (-((t - onset - 10) as f64) / 5.0).exp()
+    }
+}
+
+#[test]
+fn measure_detect_transients() {
+    // two traces on a noisy baseline, one and two events
+    let traces = Array2::from_shape_fn((2, 200), |(i, t)| {
+        let noise = 0.02 * (1.7 * t as f64 + i as f64).sin();
+        let signal = if i == 0 {
+            transient(t, 50, 1.0)
+        } else {
+            transient(t, 30, 0.5) + transient(t, 120, 2.0)
+        };
+        1.0 + noise + signal
+    });
+    let events = measure::detect_transients(traces.view(), None, Some(3)).unwrap();
+
+    // assert the events and their metrics
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events.iter().map(|e| e.trace).collect::<Vec<usize>>(),
+        vec![0, 1, 1]
+    );
+    let expected = [(50, 1.0), (30, 0.5), (120, 2.0)];
+    events
+        .iter()
+        .zip(expected)
+        .for_each(|(e, (onset, amplitude))| {
+            assert_eq!(e.peak, onset + 10);
+            assert!(e.start > onset && e.start < onset + 5);
+            assert!(e.end > e.peak);
+            assert_eq!(e.duration, e.end - e.start);
+            assert!((e.amplitude - amplitude).abs() < 0.05);
+            assert!((e.rise_time - 8.0).abs() < 0.5);
+        });
+
+    // a longer minimum duration rejects the short event
+    let events = measure::detect_transients(traces.view(), None, Some(25)).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].trace, 1);
+
+    // invalid parameters
+    assert!(measure::detect_transients(traces.view(), Some(0.0), None).is_err());
+    assert!(measure::detect_transients(traces.view(), None, Some(0)).is_err());
+}
+
+#[test]
+fn measure_per_label_analysis() {
+    // two regions, region 1 with correlated channels and region 2 with
+    // anti-correlated channels, on a constant phasor of a 2 ns lifetime
+    let labels = Array2::from_shape_fn((8, 8), |(r, c)| match (r, c) {
+        (_, 0..3) => 1,
+        (_, 5..8) => 2,
+        _ => 0,
+    });
+    let a = Array2::from_shape_fn((8, 8), |(r, _)| 10.0 + r as f64);
+    let b = Array2::from_shape_fn((8, 8), |(r, c)| {
+        if c < 4 {
+            2.0 * r as f64
+        } else {
+            20.0 - r as f64
+        }
+    });
+    let omega = 2.0 * std::f64::consts::PI / 12.5;
+    let tau = 2.0;
+    let g = 1.0 / (1.0 + (omega * tau).powi(2));
+    let phasor = Array3::from_shape_fn(
+        (8, 8, 2),
+        |(_, _, ch)| {
+            if ch == 0 { g } else { g * omega * tau }
+        },
+    );
+    let table = measure::per_label_analysis(
+        labels.view(),
+        Some(a.view()),
+        Some(b.view()),
+        Some(phasor.view()),
+        Some(omega),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // assert the columns
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.label, vec![1, 2]);
+    assert_eq!(table.pixel_count, vec![24, 24]);
+    assert!((table.mean_a[0] - 13.5).abs() < 1e-12);
+    assert_eq!((table.min_a[1], table.max_a[1]), (10.0, 17.0));
+    assert!((table.pearson[0] - 1.0).abs() < 1e-9);
+    assert!((table.pearson[1] + 1.0).abs() < 1e-9);
+    // channel B is 0 in the first row of region 1
+    assert!((table.manders_m1[0] - 98.0 / 108.0).abs() < 1e-12);
+    assert!((table.manders_m2[0] - 1.0).abs() < 1e-12);
+    assert!((table.mean_g[1] - g).abs() < 1e-12);
+    assert!((table.tau_phase[0] - tau).abs() < 1e-9);
+    assert!((table.tau_modulation[1] - tau).abs() < 1e-9);
+
+    // bit-identical tables for any thread count
+    let noisy_labels = Array2::from_shape_fn((97, 61), |(r, c)| (r * 7 + c * 3) % 5);
+    let noisy = Array2::from_shape_fn((97, 61), |(r, c)| ((r * c) as f64 * 0.13).sin() * 1e3);
+    let stds: Vec<Vec<f64>> = [1, 4]
+        .iter()
+        .map(|&t| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(t)
+                .build()
+                .unwrap()
+                .install(|| {
+                    measure::per_label_analysis(
+                        noisy_labels.view(),
+                        Some(noisy.view()),
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                    .unwrap()
+                    .std_a
+                })
+        })
+        .collect();
+    assert!(
+        stds[0]
+            .iter()
+            .zip(&stds[1])
+            .all(|(x, y)| x.to_bits() == y.to_bits())
+    );
+
+    // missing inputs produce NaN columns
+    let table =
+        measure::per_label_analysis(labels.view(), Some(a.view()), None, None, None, None, None)
+            .unwrap();
+    assert!((table.mean_a[0] - 13.5).abs() < 1e-12);
+    assert!(table.mean_b[0].is_nan());
+    assert!(table.pearson[0].is_nan());
+    assert!(table.mean_g[0].is_nan());
+
+    // invalid parameters
+    let small = Array2::<f64>::zeros((4, 4));
+    assert!(
+        measure::per_label_analysis(
+            labels.view(),
+            Some(small.view()),
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err()
+    );
+    assert!(
+        measure::per_label_analysis::<f64>(
+            labels.view(),
+            None,
+            None,
+            Some(phasor.view()),
+            None,
+            None,
+            Some(3)
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,59 @@
+use ndarray::{Array2, Axis, stack};
+
+use imgal::motion;
+
+// create a smooth blob image shifted by (dr, dc) pixels
+fn blob(dr: f64, dc: f64) -> Array2<f64> {
+    Array2::from_shape_fn((32, 32), |(r, c)| {
+        let y = r as f64 - 16.0 - dr;
+        let x = c as f64 - 16.0 - dc;
+        100.0 * (-(x * x + y * y) / (2.0 * 16.0)).exp()
+    })
+}
+
+#[test]
+fn motion_lucas_kanade() {
+    let prev = blob(0.0, 0.0);
+    let next = blob(0.5, 1.0);
+
+    // estimate the flow around the blob center
+    let (flow, mag) = motion::lucas_kanade(prev.view(), next.view(), None, None).unwrap();
+
+    // assert flow direction and magnitude
+    assert_eq!(flow.shape(), &[32, 32, 2]);
+    assert!((flow[[14, 14, 0]] - 0.5).abs() < 0.2);
+    assert!((flow[[14, 14, 1]] - 1.0).abs() < 0.2);
+    assert!((mag[[14, 14]] - 1.25_f64.sqrt()).abs() < 0.2);
+    assert!(motion::lucas_kanade(prev.view(), next.view(), Some(0), None).is_err());
+}
+
+#[test]
+fn motion_farneback() {
+    let prev = blob(0.0, 0.0);
+    let next = blob(-1.0, 1.0);
+
+    // estimate the flow around the blob center
+    let (flow, _) = motion::farneback(prev.view(), next.view(), None, None, None).unwrap();
+
+    // assert flow direction
+    assert!((flow[[16, 16, 0]] + 1.0).abs() < 0.25);
+    assert!((flow[[16, 16, 1]] - 1.0).abs() < 0.25);
+}
+
+#[test]
+fn motion_optical_flow_3d() {
+    let frames = stack![Axis(0), blob(0.0, 0.0), blob(0.0, 1.0), blob(0.0, 2.0)];
+    let method = motion::FlowMethod::LucasKanade {
+        window_radius: 3,
+        min_eigenvalue: 1e-6,
+    };
+
+    // estimate the flow between each frame pair
+    let (flows, mags) = motion::optical_flow_3d(frames.view(), method, None).unwrap();
+
+    // assert output shapes and flow direction
+    assert_eq!(flows.shape(), &[2, 32, 32, 2]);
+    assert_eq!(mags.shape(), &[2, 32, 32]);
+    assert!((flows[[1, 16, 14, 1]] - 1.0).abs() < 0.2);
+    assert!(motion::optical_flow_3d(frames.view(), method, Some(3)).is_err());
+}
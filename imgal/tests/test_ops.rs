@@ -0,0 +1,116 @@
+use ndarray::Array2;
+
+use imgal::error::ImgalError;
+use imgal::ops::{Op, OpInfo, OpRegistry, OpValue, ValueType};
+use imgal::threshold;
+
+#[test]
+fn ops_registry_builtins() {
+    let registry = OpRegistry::with_builtins();
+
+    // assert builtin ops are enumerable and have consistent metadata
+    let names: Vec<&str> = registry.iter().map(|op| op.info().name).collect();
+    assert_eq!(names.len(), registry.len());
+    assert!(names.contains(&"filter.gaussian_2d"));
+    assert!(names.contains(&"segmentation.label"));
+    assert!(names.windows(2).all(|w| w[0] < w[1]));
+    assert!(registry.get("not.an_op").is_none());
+    let info = registry.get("threshold.otsu_value").unwrap().info();
+    assert_eq!(info.inputs[0].value_type, ValueType::Image);
+    assert!(!info.inputs[1].required);
+    assert_eq!(info.outputs[0].value_type, ValueType::Float);
+}
+
+#[test]
+fn ops_run() {
+    let registry = OpRegistry::with_builtins();
+    let data = Array2::from_shape_fn((16, 16), |(r, c)| {
+        if (4..12).contains(&r) && (4..12).contains(&c) {
+            100.0
+        } else {
+            (r + c) as f64 % 5.0
+        }
+    })
+    .into_dyn();
+
+    // run an op with an omitted optional input
+    let outputs = registry
+        .get("threshold.otsu_value")
+        .unwrap()
+        .run(&[Some(OpValue::Image(data.clone())), None])
+        .unwrap();
+    let expected = threshold::otsu_value(data.view(), None).unwrap();
+    assert_eq!(outputs, vec![OpValue::Float(expected)]);
+
+    // chain two ops
+    let mask = registry
+        .get("threshold.otsu_mask")
+        .unwrap()
+        .run(&[Some(OpValue::Image(data.clone())), Some(OpValue::Int(64))])
+        .unwrap()
+        .remove(0);
+    let outputs = registry
+        .get("segmentation.label")
+        .unwrap()
+        .run(&[Some(mask), Some(OpValue::Int(4))])
+        .unwrap();
+    assert_eq!(outputs[1], OpValue::Int(1));
+}
+
+#[test]
+fn ops_run_invalid_inputs() {
+    let registry = OpRegistry::with_builtins();
+    let op = registry.get("filter.gaussian_2d").unwrap();
+    let data = OpValue::Image(Array2::<f64>::zeros((4, 4)).into_dyn());
+
+    // assert inputs are validated against the op metadata
+    assert!(matches!(
+        op.run(&[Some(data.clone())]),
+        Err(ImgalError::MismatchedArrayLengths { .. })
+    ));
+    assert!(op.run(&[Some(data.clone()), None]).is_err());
+    assert!(
+        op.run(&[Some(data.clone()), Some(OpValue::Int(1))])
+            .is_err()
+    );
+    assert!(matches!(
+        op.run(&[Some(data), Some(OpValue::Float(-1.0))]),
+        Err(ImgalError::InvalidParameterValueOutsideRange { .. })
+    ));
+    let data_3d = OpValue::Image(ndarray::Array3::<f64>::zeros((2, 4, 4)).into_dyn());
+    assert!(op.run(&[Some(data_3d), Some(OpValue::Float(1.0))]).is_err());
+}
+
+struct ConstantOp;
+
+impl Op for ConstantOp {
+    fn info(&self) -> &OpInfo {
+        const INFO: OpInfo = OpInfo {
+            name: "test.constant",
+            description: "Return a constant.",
+            inputs: &[],
+            outputs: &[],
+        };
+
+        &INFO
+    }
+
+    fn compute(&self, _inputs: &[Option<OpValue>]) -> Result<Vec<OpValue>, ImgalError> {
+        Ok(vec![OpValue::Float(2.0)])
+    }
+}
+
+#[test]
+fn ops_register_custom() {
+    // register a custom op in an empty registry
+    let mut registry = OpRegistry::new();
+    assert!(registry.is_empty());
+    registry.register(Box::new(ConstantOp));
+    registry.register(Box::new(ConstantOp));
+
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry.get("test.constant").unwrap().run(&[]).unwrap(),
+        vec![OpValue::Float(2.0)]
+    );
+}
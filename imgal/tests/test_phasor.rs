@@ -1,7 +1,17 @@
-use ndarray::{Array2, Axis, s};
+use ndarray::{Array2, Array3, Array4, Axis, s};
 
 use imgal::parameter::omega;
-use imgal::phasor::{calibration, plot, time_domain};
+use imgal::phasor::batch::{self, PhasorSettings};
+use imgal::phasor::calibration::{CalibrationIssue, CenterEstimator, RobustOptions};
+use imgal::phasor::denoise::PhasorFilter;
+use imgal::phasor::multi_harmonic::LifetimeEstimate;
+use imgal::phasor::time_domain::{
+    FisherInformation, PhasorGate, QualityMetric, SignalToBackground, SpatialBinning,
+};
+use imgal::phasor::{
+    analysis, calibration, denoise, frequency_domain, gate_scan, multi_harmonic, plot, render,
+    time_domain, trajectory, unmixing,
+};
 use imgal::simulation::{decay, noise};
 
 // simulated bioexponential decay parameters
@@ -47,6 +57,93 @@ fn get_circle_mask(shape: (usize, usize), center: (isize, isize), radius: isize)
     mask
 }
 
+// test the phasor::batch module
+#[test]
+fn batch_process() {
+    // get simulated data with two decay lengths and an aliased harmonic
+    let a = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let b =
+        decay::ideal_exponential_3d(64, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE).unwrap();
+    let c = Array3::<f64>::ones((4, 4, 4));
+    let settings = PhasorSettings {
+        harmonic: Some(3),
+        calibration: Some((MODULATION, PHASE)),
+        min_photons: Some(1000.0),
+        ..PhasorSettings::new(PERIOD)
+    };
+    let results: Vec<_> = batch::process([a.view(), b.view(), c.view()], settings)
+        .unwrap()
+        .collect();
+
+    // each dataset matches the single image functions
+    for (data, result) in [&a, &b].iter().zip(&results) {
+        let (gs, valid) = result.as_ref().unwrap();
+        let gate = PhasorGate {
+            min_photons: Some(1000.0),
+            ..Default::default()
+        };
+        let (expected, expected_valid) =
+            time_domain::gated_image(data.view(), PERIOD, None, Some(3), None, Some(gate)).unwrap();
+        let expected = calibration::image(expected.view(), MODULATION, PHASE, None);
+        assert_eq!(valid, expected_valid);
+        assert!(ensure_within_tolerance(
+            gs[[5, 5, 0]],
+            expected[[5, 5, 0]],
+            1e-12
+        ));
+        assert!(ensure_within_tolerance(
+            gs[[5, 5, 1]],
+            expected[[5, 5, 1]],
+            1e-12
+        ));
+    }
+    assert!(results[2].is_err());
+
+    // the callback receives every dataset and invalid settings fail early
+    let mut indices = Vec::new();
+    let count = batch::process_with([a.view(), c.view()], settings, |i, r| {
+        indices.push((i, r.is_ok()))
+    })
+    .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(indices, vec![(0, true), (1, false)]);
+    assert!(batch::process([a.view()], PhasorSettings::new(-1.0)).is_err());
+}
+
+// test the phasor::analysis module
+#[test]
+fn analysis_harmonic_consistency() {
+    // monoexponential and biexponential decays
+    let mono =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &[2.0], &[1.0], TOTAL_COUNTS, (2, 2)).unwrap();
+    let bi = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (2, 2))
+        .unwrap();
+    let (mono_idx, mono_taus) =
+        analysis::harmonic_consistency(mono.view(), PERIOD, None, None).unwrap();
+    let (bi_idx, bi_taus) =
+        analysis::harmonic_consistency(bi.view(), PERIOD, Some(&[1, 2, 3]), None).unwrap();
+
+    // assert the apparent lifetimes and the multi-exponentiality index
+    assert_eq!(mono_taus.dim(), (2, 2, 4));
+    assert_eq!(bi_taus.dim(), (2, 2, 6));
+    assert!(
+        mono_taus
+            .iter()
+            .all(|&t| ensure_within_tolerance(t, 2.0, 0.15))
+    );
+    assert!(mono_idx[[0, 0]] < 0.03);
+    assert!(bi_idx[[1, 1]] > 5.0 * mono_idx[[1, 1]]);
+    assert!(bi_taus[[0, 0, 4]] < bi_taus[[0, 0, 0]]);
+
+    // assert pixels without photons and invalid parameters
+    let empty = Array3::<f64>::zeros((1, 1, SAMPLES));
+    let (idx, _) = analysis::harmonic_consistency(empty.view(), PERIOD, None, None).unwrap();
+    assert!(idx[[0, 0]].is_nan());
+    assert!(analysis::harmonic_consistency(mono.view(), PERIOD, Some(&[]), None).is_err());
+    assert!(analysis::harmonic_consistency(mono.view(), PERIOD, Some(&[0]), None).is_err());
+}
+
 // test the phasor::calibration module
 #[test]
 fn calibration_coordinates() {
@@ -76,7 +173,7 @@ fn calibration_image() {
     .unwrap();
 
     // calculate the phasor image, (G, S)
-    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None).unwrap();
+    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None, None).unwrap();
 
     // calibrate the phasor image
     let cal_gs_arr = calibration::image(gs_arr.view(), MODULATION, PHASE, None);
@@ -105,7 +202,7 @@ fn calibration_image_mut() {
     .unwrap();
 
     // calculate the phasor image, (G, S)
-    let mut gs_arr = time_domain::image(sim_data.view(), PERIOD, None, None, None).unwrap();
+    let mut gs_arr = time_domain::image(sim_data.view(), PERIOD, None, None, None, None).unwrap();
 
     // calibrate the phasor image
     calibration::image_mut(gs_arr.view_mut(), MODULATION, PHASE, None);
@@ -127,7 +224,182 @@ fn calibration_modulation_and_phase() {
     assert_eq!(mod_phs, (1.4768757234403935, -1.1586655116823268));
 }
 
+#[test]
+fn calibration_robust() {
+    // a jittered reference with 4 garbage pixels
+    let w = omega(PERIOD);
+    let expected = calibration::modulation_and_phase(-0.055, 0.59, 1.1, w);
+    let mut data = Array3::<f64>::zeros((10, 10, 2));
+    data.indexed_iter_mut().for_each(|((r, c, ch), v)| {
+        let jitter = 0.002 * (((r * 7 + c * 3) % 5) as f64 - 2.0);
+        *v = if ch == 0 {
+            -0.055 + jitter
+        } else {
+            0.59 - jitter
+        };
+    });
+    for (r, c) in [(0, 0), (3, 4), (6, 1), (9, 9)] {
+        data[[r, c, 0]] = 0.9;
+        data[[r, c, 1]] = 0.05;
+    }
+    data[[5, 5, 0]] = f64::NAN;
+
+    // assert the robust estimators ignore the garbage pixels
+    let g: Vec<f64> = data.index_axis(Axis(2), 0).iter().copied().collect();
+    let s: Vec<f64> = data.index_axis(Axis(2), 1).iter().copied().collect();
+    let mean =
+        calibration::estimate_modulation_and_phase(&g, &s, 1.1, w, Some(CenterEstimator::Mean))
+            .unwrap();
+    let median = calibration::estimate_modulation_and_phase(&g, &s, 1.1, w, None).unwrap();
+    let trimmed = calibration::estimate_modulation_and_phase(
+        &g,
+        &s,
+        1.1,
+        w,
+        Some(CenterEstimator::Trimmed(0.1)),
+    )
+    .unwrap();
+    assert!((mean.1 - expected.1).abs() > 0.05);
+    assert!((median.1 - expected.1).abs() < 1e-2);
+    assert!((trimmed.1 - expected.1).abs() < 1e-2);
+
+    // assert the iterative rejection finds the garbage pixels
+    let cal = calibration::robust_from_image(data.view(), 1.1, w, None, None, None).unwrap();
+    assert!((cal.modulation - expected.0).abs() < 1e-2);
+    assert!((cal.phase - expected.1).abs() < 1e-2);
+    assert!(!cal.inliers[[0, 0]] && !cal.inliers[[9, 9]] && !cal.inliers[[5, 5]]);
+    assert!(cal.inliers.iter().filter(|&&i| i).count() >= 90);
+
+    // assert invalid parameters
+    let bad = RobustOptions {
+        estimator: CenterEstimator::Trimmed(0.5),
+        ..Default::default()
+    };
+    assert!(calibration::robust_from_image(data.view(), 1.1, w, None, Some(bad), None).is_err());
+    assert!(calibration::estimate_modulation_and_phase(&g, &s[1..], 1.1, w, None).is_err());
+    assert!(calibration::estimate_modulation_and_phase(&[f64::NAN], &[0.0], 1.1, w, None).is_err());
+}
+
+#[test]
+fn calibration_diagnostics() {
+    // jittered controls at a (G, S) point
+    let w = omega(PERIOD);
+    let control = |(g, s): (f64, f64)| {
+        Array3::from_shape_fn((8, 8, 2), |(r, c, ch)| {
+            let jitter = 0.002 * (((r * 7 + c * 3) % 5) as f64 - 2.0);
+            if ch == 0 { g + jitter } else { s - jitter }
+        })
+    };
+    let expected = plot::monoexponential_coordinates(1.1, w);
+
+    // assert a calibrated control has no issues
+    let d = calibration::diagnostics(control(expected).view(), 1.1, w, None, None, None).unwrap();
+    assert!(d.issues.is_empty());
+    assert!(d.center_distance < 1e-9 && d.circle_distance.abs() < 1e-3);
+    assert!((d.phase_lifetime - 1.1).abs() < 1e-9);
+    assert!((d.modulation_lifetime - 1.1).abs() < 1e-9);
+
+    // assert a lifetime mismatch on the circle suggests a period scale
+    let longer = plot::monoexponential_coordinates(1.5, w);
+    let d = calibration::diagnostics(control(longer).view(), 1.1, w, None, None, None).unwrap();
+    assert_eq!(d.issues, vec![CalibrationIssue::LifetimeMismatch]);
+    assert!((d.suggested_period_scale - 1.1 / 1.5).abs() < 1e-9);
+
+    // assert a modulation error is off the circle and its correction
+    let scaled = (expected.0 * 0.8, expected.1 * 0.8);
+    let data = control(scaled);
+    let d = calibration::diagnostics(data.view(), 1.1, w, None, None, None).unwrap();
+    assert_eq!(d.issues, vec![CalibrationIssue::OffCircle]);
+    assert!(d.circle_distance < 0.0);
+    let fixed = calibration::image(data.view(), d.suggested_modulation, d.suggested_phase, None);
+    let d = calibration::diagnostics(fixed.view(), 1.1, w, None, None, None).unwrap();
+    assert!(d.issues.is_empty());
+
+    // assert invalid parameters
+    assert!(calibration::diagnostics(data.view(), 1.1, w, None, Some(0.0), None).is_err());
+    let mask = Array2::<bool>::default((8, 8));
+    assert!(calibration::diagnostics(data.view(), 1.1, w, Some(mask.view()), None, None).is_err());
+}
+
+// test the phasor::denoise module
+// create a noisy constant phasor image with a NaN pixel
+fn noisy_phasor_image() -> Array3<f64> {
+    let mut data = Array3::from_shape_fn((16, 16, 2), |(r, c, ch)| {
+        let noise = if (r + c) % 2 == 0 { 0.05 } else { -0.05 };
+        if ch == 0 { 0.6 + noise } else { 0.3 - noise }
+    });
+    data[[4, 4, 0]] = f64::NAN;
+    data[[4, 4, 1]] = f64::NAN;
+    data
+}
+
+#[test]
+fn denoise_image_median() {
+    // add sparse outliers to the phasor image
+    let mut data = noisy_phasor_image();
+    data.indexed_iter_mut()
+        .filter(|((r, c, _), _)| (r * 16 + c) % 7 == 0)
+        .for_each(|(_, v)| *v += 0.4);
+
+    // median filter the phasor image
+    let output =
+        denoise::image(data.view(), PhasorFilter::Median { size: 3 }, Some(2), None).unwrap();
+
+    // assert the outlier is removed and the NaN pixel is preserved
+    assert!(data[[8, 12, 0]] > 0.9);
+    assert!((output[[8, 12, 0]] - 0.6).abs() <= 0.05 + 1e-12);
+    assert!(output[[4, 4, 0]].is_nan());
+    assert!(!output[[4, 5, 1]].is_nan());
+}
+
+#[test]
+fn denoise_image_gaussian() {
+    let data = noisy_phasor_image();
+
+    // Gaussian filter the phasor image
+    let output = denoise::image(
+        data.view(),
+        PhasorFilter::Gaussian { sigma: 1.5 },
+        None,
+        None,
+    )
+    .unwrap();
+
+    // assert the filtered coordinates are close to the noise free values
+    assert!((output[[8, 8, 0]] - 0.6).abs() < 0.01);
+    assert!((output[[8, 8, 1]] - 0.3).abs() < 0.01);
+    assert!(output[[4, 4, 1]].is_nan());
+    assert!(
+        denoise::image(
+            data.view(),
+            PhasorFilter::Gaussian { sigma: 0.0 },
+            None,
+            None
+        )
+        .is_err()
+    );
+}
+
 // test the phasor::plot module
+#[test]
+fn plot_apparent_lifetimes() {
+    // create a phasor image of a monoexponential decay
+    let w = omega(PERIOD);
+    let (g, s) = plot::monoexponential_coordinates(2.0, w);
+    let data = Array3::from_shape_fn((2, 2, 2), |(_, _, ch)| if ch == 0 { g } else { s });
+
+    // back-project to apparent lifetimes
+    let taus = plot::apparent_lifetimes(data.view(), w, None).unwrap();
+
+    // assert equal phase and modulation lifetimes
+    assert!(ensure_within_tolerance(taus[[0, 0, 0]], 2.0, 1e-12));
+    assert!(ensure_within_tolerance(taus[[1, 1, 1]], 2.0, 1e-12));
+
+    // a channel axis without exactly two channels is an error
+    let single_ch = Array3::<f64>::zeros((2, 2, 1));
+    assert!(plot::apparent_lifetimes(single_ch.view(), w, None).is_err());
+}
+
 #[test]
 fn plot_modulation() {
     let m = plot::modulation(0.71, 0.43);
@@ -168,7 +440,7 @@ fn plot_map_image() {
     noise::poisson_3d_mut(i.view_mut(), 0.3, None, None);
 
     // compute phasor array and select coordinates to map back
-    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None).unwrap();
+    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None, None).unwrap();
     let g_coords = gs_arr.slice(s![25..30, 25..30, 0]).flatten().to_vec();
     let s_coords = gs_arr.slice(s![25..30, 25..30, 1]).flatten().to_vec();
 
@@ -179,7 +451,393 @@ fn plot_map_image() {
     assert_eq!(mask[[28, 28]], true);
     assert_eq!(mask[[5, 5]], false);
 }
+
+#[test]
+fn plot_polar_round_trip() {
+    // get simulated data and the phasor image
+    let i = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None, None).unwrap();
+
+    // convert to polar coordinates and back
+    let polar_arr = plot::to_polar(gs_arr.view(), None).unwrap();
+    let gs_back = plot::from_polar(polar_arr.view(), None).unwrap();
+
+    assert_eq!(
+        polar_arr[[3, 3, 0]],
+        plot::phase(gs_arr[[3, 3, 0]], gs_arr[[3, 3, 1]])
+    );
+    assert_eq!(
+        polar_arr[[3, 3, 1]],
+        plot::modulation(gs_arr[[3, 3, 0]], gs_arr[[3, 3, 1]])
+    );
+    assert!(ensure_within_tolerance(
+        gs_back[[3, 3, 0]],
+        gs_arr[[3, 3, 0]],
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        gs_back[[3, 3, 1]],
+        gs_arr[[3, 3, 1]],
+        1e-12
+    ));
+    assert!(plot::to_polar(gs_arr.view(), Some(3)).is_err());
+
+    // a channel axis without exactly two channels is an error
+    let three_ch = Array3::<f64>::zeros((10, 10, 3));
+    assert!(plot::to_polar(three_ch.view(), None).is_err());
+    assert!(plot::from_polar(three_ch.view(), None).is_err());
+    assert!(plot::from_polar(polar_arr.view(), Some(0)).is_err());
+}
+
+#[test]
+fn plot_semicircle() {
+    // semicircle reference points lie on the universal semicircle
+    let w = omega(PERIOD);
+    let (g, s) = plot::semicircle_points(&[0.5, 1.1, 4.0], w);
+    assert_eq!((g[1], s[1]), plot::monoexponential_coordinates(1.1, w));
+    g.iter().zip(s.iter()).for_each(|(&g, &s)| {
+        assert!(ensure_within_tolerance(s, (g - g * g).sqrt(), 1e-12));
+    });
+
+    // sample the universal semicircle
+    let (g, s) = plot::universal_semicircle(Some(11));
+    assert_eq!(g.len(), 11);
+    assert_eq!(g[10], 1.0);
+    assert_eq!(s[5], 0.5);
+}
+
+// test the phasor::render module
+#[test]
+fn render_fraction_rgb() {
+    // create two complementary fraction images and an intensity ramp
+    let f1 = Array2::from_shape_fn((4, 4), |(_, c)| c as f64 / 3.0);
+    let f2 = f1.mapv(|v| 1.0 - v);
+    let inten = Array2::from_shape_fn((4, 4), |(r, _)| (r + 1) as f64);
+
+    // render without intensity modulation
+    let rgb = render::fraction_rgb(&[f1.view(), f2.view()], None, None).unwrap();
+    assert_eq!(rgb.dim(), (4, 4, 3));
+    assert_eq!(rgb.slice(s![0, 0, ..]).to_vec(), vec![0, 255, 0]);
+    assert_eq!(rgb.slice(s![0, 3, ..]).to_vec(), vec![255, 0, 0]);
+
+    // render with custom colors and intensity modulation
+    let colors = [[0, 255, 255], [255, 0, 255]];
+    let rgb =
+        render::fraction_rgb(&[f1.view(), f2.view()], Some(&colors), Some(inten.view())).unwrap();
+    assert_eq!(rgb.slice(s![3, 3, ..]).to_vec(), vec![0, 255, 255]);
+    assert_eq!(rgb.slice(s![1, 3, ..]).to_vec(), vec![0, 128, 128]);
+
+    // a single component is not allowed
+    assert!(render::fraction_rgb(&[f1.view()], None, None).is_err());
+}
+
 // test the phasor::time_domain module
+#[test]
+fn multi_harmonic_lifetime_image() {
+    // get simulated monoexponential data
+    let data =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &[2.0], &[1.0], TOTAL_COUNTS, (2, 2)).unwrap();
+
+    // compute the merged and single harmonic lifetimes
+    let (tau, var) = multi_harmonic::lifetime_image(data.view(), PERIOD, None, None, None).unwrap();
+    let (tau_1, var_1) =
+        multi_harmonic::lifetime_image(data.view(), PERIOD, Some(&[1]), None, None).unwrap();
+    let (tau_2, var_2) =
+        multi_harmonic::lifetime_image(data.view(), PERIOD, Some(&[2]), None, None).unwrap();
+    let (tau_m, _) = multi_harmonic::lifetime_image(
+        data.view(),
+        PERIOD,
+        None,
+        Some(LifetimeEstimate::Modulation),
+        None,
+    )
+    .unwrap();
+
+    // assert the merged lifetimes and variances
+    assert!(ensure_within_tolerance(tau[[0, 0]], 2.0, 1e-1));
+    assert!(ensure_within_tolerance(tau_m[[1, 1]], 2.0, 1e-1));
+    assert!(tau[[0, 0]] >= tau_1[[0, 0]].min(tau_2[[0, 0]]));
+    assert!(tau[[0, 0]] <= tau_1[[0, 0]].max(tau_2[[0, 0]]));
+    assert!(var[[0, 0]] < var_1[[0, 0]].min(var_2[[0, 0]]));
+    assert_eq!(
+        multi_harmonic::inverse_variance_mean(&[1.0, 3.0, f64::NAN], &[1.0, 1.0, 1.0]).unwrap(),
+        (2.0, 0.5)
+    );
+    assert!(multi_harmonic::inverse_variance_mean(&[1.0], &[]).is_err());
+    assert!(multi_harmonic::lifetime_image(data.view(), PERIOD, Some(&[]), None, None).is_err());
+    assert!(multi_harmonic::lifetime_image(data.view(), PERIOD, Some(&[200]), None, None).is_err());
+}
+
+#[test]
+fn time_domain_adaptive_smooth() {
+    // create a decay image with a sparse tail
+    let mut data = Array3::<f64>::zeros((2, 1, 8));
+    data.slice_mut(s![0, 0, ..])
+        .assign(&ndarray::arr1(&[0.0, 100.0, 50.0, 2.0, 0.0, 1.0, 0.0, 0.0]));
+
+    // smooth the decays with windows of at least 3 photons
+    let smoothed = time_domain::adaptive_smooth(data.view(), Some(3.0), Some(2), None).unwrap();
+
+    // assert the smoothed decays, the rising edge and peak are unchanged
+    let expected = [0.0, 100.0, 50.0, 52.0 / 3.0, 1.0, 0.6, 0.25, 1.0 / 3.0];
+    for (i, e) in expected.iter().enumerate() {
+        assert!(ensure_within_tolerance(smoothed[[0, 0, i]], *e, 1e-12));
+    }
+    assert!(smoothed.slice(s![1, 0, ..]).iter().all(|&v| v == 0.0));
+    assert!(time_domain::adaptive_smooth(data.view(), Some(-1.0), None, None).is_err());
+    assert!(time_domain::adaptive_smooth(data.view(), None, None, Some(3)).is_err());
+}
+
+#[test]
+fn time_domain_image_multiharmonic() {
+    // get simulated data and circle mask
+    let i = decay::gaussian_exponential_3d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF_CENTER,
+        IRF_WIDTH,
+        SHAPE,
+    )
+    .unwrap();
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+
+    // compute all harmonics in one pass
+    let harmonics = [1, 2, 5];
+    let gs =
+        time_domain::image_multiharmonic(i.view(), PERIOD, &harmonics, Some(mask.view()), None)
+            .unwrap();
+    assert_eq!(gs.shape(), [3, 10, 10, 2]);
+
+    // assert each harmonic matches the single harmonic image
+    for (k, &h) in harmonics.iter().enumerate() {
+        let single =
+            time_domain::image(i.view(), PERIOD, Some(mask.view()), Some(h), None, None).unwrap();
+        assert_eq!(gs.index_axis(Axis(0), k), single);
+    }
+
+    // a transposed decay axis gives the same coordinates
+    let transposed = i.view().permuted_axes([2, 0, 1]);
+    let gs_t = time_domain::image_multiharmonic(
+        transposed,
+        PERIOD,
+        &harmonics,
+        Some(mask.view()),
+        Some(0),
+    )
+    .unwrap();
+    assert_eq!(gs_t, gs);
+
+    // invalid parameters
+    assert!(time_domain::image_multiharmonic(i.view(), PERIOD, &[], None, None).is_err());
+    assert!(time_domain::image_multiharmonic(i.view(), PERIOD, &[1, 0], None, None).is_err());
+    assert!(time_domain::image_multiharmonic(i.view(), PERIOD, &[129], None, None).is_err());
+    assert!(time_domain::image_multiharmonic(i.view(), PERIOD, &[1], None, Some(3)).is_err());
+}
+
+#[test]
+fn time_domain_curves() {
+    // stack decay curves of different lifetimes
+    let taus = [0.5, 1.0, 2.0, 4.0];
+    let mut data = Array2::<f64>::zeros((5, SAMPLES));
+    for (i, &t) in taus.iter().enumerate() {
+        let d = decay::ideal_exponential_1d(SAMPLES, PERIOD, &[t], &[1.0], TOTAL_COUNTS).unwrap();
+        data.row_mut(i).assign(&ndarray::Array1::from(d));
+    }
+
+    // assert each curve matches the 1-dimensional coordinates
+    let gs = time_domain::curves(data.view(), PERIOD, Some(2), None).unwrap();
+    let gs_t = time_domain::curves(data.t(), PERIOD, Some(2), Some(0)).unwrap();
+    assert_eq!(gs.dim(), (5, 2));
+    gs.iter()
+        .zip(gs_t.iter())
+        .for_each(|(a, b)| assert!(a == b || (a.is_nan() && b.is_nan())));
+    for i in 0..taus.len() {
+        let row = data.row(i).to_vec();
+        let g = time_domain::real(&row, PERIOD, Some(2)).unwrap();
+        let s = time_domain::imaginary(&row, PERIOD, Some(2)).unwrap();
+        assert!(ensure_within_tolerance(gs[[i, 0]], g, 1e-12));
+        assert!(ensure_within_tolerance(gs[[i, 1]], s, 1e-12));
+    }
+    assert!(gs[[4, 0]].is_nan());
+
+    // assert invalid parameters
+    assert!(time_domain::curves(data.view(), PERIOD, None, Some(2)).is_err());
+    assert!(time_domain::curves(data.view(), PERIOD, Some(200), None).is_err());
+}
+
+#[test]
+fn time_domain_time_points() {
+    // a 2 ns decay sampled at nonlinear time points
+    let n = 1024;
+    let w = omega(PERIOD);
+    let times: Vec<f64> = (0..n)
+        .map(|i| PERIOD * (i as f64 / n as f64).powf(1.5))
+        .collect();
+    let data: Vec<f64> = times.iter().map(|t| (-t / 2.0).exp()).collect();
+    let g_exact = 1.0 / (1.0 + (w * 2.0).powi(2));
+    let s_exact = w * 2.0 / (1.0 + (w * 2.0).powi(2));
+
+    // assert the non-uniform integration recovers the coordinates
+    let g = time_domain::real_with_time_points(&data, PERIOD, &times, None).unwrap();
+    let s = time_domain::imaginary_with_time_points(&data, PERIOD, &times, None).unwrap();
+    assert!(ensure_within_tolerance(g, g_exact, 1e-2));
+    assert!(ensure_within_tolerance(s, s_exact, 1e-2));
+    assert!(!ensure_within_tolerance(
+        time_domain::real(&data, PERIOD, None).unwrap(),
+        g_exact,
+        5e-2
+    ));
+
+    // assert uniform time points match the uniform computation
+    let decays =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, 1.0, (2, 2)).unwrap();
+    let uniform: Vec<f64> = (0..SAMPLES)
+        .map(|i| i as f64 * PERIOD / SAMPLES as f64)
+        .collect();
+    let gs =
+        time_domain::image_with_time_points(decays.view(), PERIOD, &uniform, None, Some(2), None)
+            .unwrap();
+    let expected = time_domain::image(decays.view(), PERIOD, None, Some(2), None, None).unwrap();
+    gs.iter()
+        .zip(expected.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    // assert invalid time points
+    let mut bad = uniform.clone();
+    bad.swap(1, 2);
+    assert!(
+        time_domain::image_with_time_points(decays.view(), PERIOD, &bad, None, None, None).is_err()
+    );
+    assert!(
+        time_domain::image_with_time_points(decays.view(), PERIOD, &uniform[1..], None, None, None)
+            .is_err()
+    );
+    assert!(time_domain::real_with_time_points(&data, 1.0, &times, None).is_err());
+}
+
+#[test]
+fn time_domain_bin_spatial_block() {
+    // create a 5 x 5 decay image with 4 time bins of 1 count
+    let data = Array3::<u16>::ones((5, 5, 4));
+
+    // bin into 2 x 2 blocks
+    let binned =
+        time_domain::bin_spatial(data.view(), SpatialBinning::Block { factor: 2 }, None).unwrap();
+
+    // assert the shape, the block sums, and the conserved total count
+    assert_eq!(binned.dim(), (3, 3, 4));
+    assert_eq!(binned[[0, 0, 0]], 4.0);
+    assert_eq!(binned[[2, 2, 3]], 1.0);
+    assert_eq!(binned.sum(), 100.0);
+}
+
+#[test]
+fn time_domain_bin_spatial_sliding() {
+    // create a decay image with the decay on axis 0
+    let data = Array3::<f64>::ones((3, 5, 5));
+
+    // bin with square and circular sliding kernels
+    let square =
+        time_domain::bin_spatial(data.view(), SpatialBinning::Square { radius: 1 }, Some(0))
+            .unwrap();
+    let circular =
+        time_domain::bin_spatial(data.view(), SpatialBinning::Circular { radius: 1 }, Some(0))
+            .unwrap();
+
+    // assert the kernel sums and truncated edges
+    assert_eq!(square.dim(), (3, 5, 5));
+    assert_eq!(square[[1, 2, 2]], 9.0);
+    assert_eq!(square[[1, 0, 0]], 4.0);
+    assert_eq!(circular[[2, 2, 2]], 5.0);
+    assert_eq!(circular[[0, 0, 2]], 4.0);
+    assert!(
+        time_domain::bin_spatial(data.view(), SpatialBinning::Block { factor: 0 }, None).is_err()
+    );
+}
+
+#[test]
+fn time_domain_quality_maps() {
+    // create a 2 x 2 decay image with a background of 2 counts and a peak
+    let mut data = Array3::<u16>::from_elem((2, 2, 20), 2);
+    data[[0, 0, 5]] = 50;
+    data[[0, 0, 6]] = 30;
+    data[[1, 1, 12]] = 40;
+
+    // compute the quality maps
+    let totals = time_domain::total_counts_image(data.view(), None).unwrap();
+    let peaks = time_domain::peak_bin_image(data.view(), None).unwrap();
+    let background = time_domain::estimated_background_image(data.view(), None, None).unwrap();
+
+    // assert the quality maps
+    assert_eq!(totals[[0, 0]], 116.0);
+    assert_eq!(totals[[0, 1]], 40.0);
+    assert_eq!(peaks[[0, 0]], 5);
+    assert_eq!(peaks[[1, 1]], 12);
+    assert_eq!(peaks[[1, 0]], 0);
+    assert_eq!(background[[0, 0]], 2.0);
+    assert!(time_domain::total_counts_image(data.view(), Some(3)).is_err());
+    assert!(time_domain::estimated_background_image(data.view(), Some(0.0), None).is_err());
+}
+
+#[test]
+fn time_domain_volume() {
+    // get simulated data and stack it into a (z, row, col, bin) volume
+    let i = decay::gaussian_exponential_3d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF_CENTER,
+        IRF_WIDTH,
+        SHAPE,
+    )
+    .unwrap();
+    let vol = ndarray::stack(Axis(0), &[i.view(), i.view(), i.view()]).unwrap();
+
+    // compute phasors of the volume, unbinned and anisotropically binned
+    let gs = time_domain::image(i.view(), PERIOD, None, None, None, None).unwrap();
+    let gs_vol = time_domain::volume(vol.view(), PERIOD, None, None, None, None).unwrap();
+    let gs_binned = time_domain::volume(
+        vol.view(),
+        PERIOD,
+        None,
+        None,
+        Some(SpatialBinning::Block { factor: 2 }),
+        Some(1),
+    )
+    .unwrap();
+
+    // assert the volume shapes and coordinates
+    assert_eq!(gs_vol.shape(), &[3, SHAPE.0, SHAPE.1, 2]);
+    assert_eq!(gs_binned.shape(), &[3, SHAPE.0 / 2, SHAPE.1 / 2, 2]);
+    for z in 0..3 {
+        assert_eq!(gs_vol.index_axis(Axis(0), z), gs);
+        assert!(ensure_within_tolerance(
+            gs_binned[[z, 0, 0, 0]],
+            gs[[0, 0, 0]],
+            1e-12
+        ));
+    }
+    let mask = Array3::<bool>::from_elem((3, SHAPE.0, SHAPE.1), true);
+    assert!(
+        time_domain::volume(
+            vol.view(),
+            PERIOD,
+            Some(mask.view()),
+            None,
+            Some(SpatialBinning::Block { factor: 2 }),
+            None
+        )
+        .is_err()
+    );
+    assert!(time_domain::volume(vol.view(), PERIOD, None, Some(200), None, None).is_err());
+}
+
 #[test]
 fn time_domain_image() {
     // get simulated data
@@ -199,8 +857,9 @@ fn time_domain_image() {
     let mask = get_circle_mask((100, 100), (50, 50), 8);
 
     // compute phasors with and without a mask
-    let gs_no_mask = time_domain::image(i.view(), PERIOD, None, None, None).unwrap();
-    let gs_with_mask = time_domain::image(i.view(), PERIOD, Some(mask.view()), None, None).unwrap();
+    let gs_no_mask = time_domain::image(i.view(), PERIOD, None, None, None, None).unwrap();
+    let gs_with_mask =
+        time_domain::image(i.view(), PERIOD, Some(mask.view()), None, None, None).unwrap();
 
     // get views of each channel
     let g_no_mask_view = gs_no_mask.index_axis(Axis(2), 0);
@@ -247,10 +906,289 @@ fn time_domain_image() {
     ));
 }
 
+#[test]
+fn time_domain_gated_image() {
+    // get simulated data, with a dim region
+    let mut i =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+            .unwrap();
+    i.slice_mut(s![0..5, .., ..]).mapv_inplace(|v| v * 0.01);
+
+    // gate pixels by photon count
+    let gate = PhasorGate {
+        min_photons: Some(1000.0),
+        min_quality: None,
+        fill: -1.0,
+    };
+    let (gs_arr, valid) =
+        time_domain::gated_image(i.view(), PERIOD, None, None, None, Some(gate)).unwrap();
+
+    assert_eq!(valid[[2, 2]], false);
+    assert_eq!(valid[[7, 7]], true);
+    assert_eq!(gs_arr[[2, 2, 0]], -1.0);
+    assert!(ensure_within_tolerance(
+        gs_arr[[7, 7, 1]],
+        0.4102178630685894,
+        1e-12
+    ));
+
+    // gate pixels by histogram quality, default NaN fill
+    let gate = PhasorGate {
+        min_quality: Some(0.5),
+        ..Default::default()
+    };
+    let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None, Some(gate)).unwrap();
+
+    assert!(gs_arr[[2, 2, 0]].is_nan());
+    assert!(!gs_arr[[7, 7, 0]].is_nan());
+
+    // invalid gates
+    let gate = PhasorGate {
+        min_quality: Some(1.5),
+        ..Default::default()
+    };
+    assert!(time_domain::image(i.view(), PERIOD, None, None, None, Some(gate)).is_err());
+    let gate = PhasorGate {
+        min_photons: Some(-1.0),
+        ..Default::default()
+    };
+    assert!(time_domain::gated_image(i.view(), PERIOD, None, None, None, Some(gate)).is_err());
+}
+
+#[test]
+fn time_domain_image_into() {
+    // get simulated data
+    let i = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+
+    // write into a reused output array, it matches the allocated image
+    let mut out = Array3::<f64>::from_elem((10, 10, 2), 9.0);
+    time_domain::image_into(
+        i.view(),
+        PERIOD,
+        Some(mask.view()),
+        None,
+        None,
+        None,
+        out.view_mut(),
+    )
+    .unwrap();
+    let gs = time_domain::image(i.view(), PERIOD, Some(mask.view()), None, None, None).unwrap();
+    assert_eq!(out, gs);
+
+    // gated output returns the same validity mask
+    let gate = PhasorGate {
+        min_photons: Some(1.0),
+        ..Default::default()
+    };
+    let valid = time_domain::gated_image_into(
+        i.view(),
+        PERIOD,
+        None,
+        None,
+        None,
+        Some(gate),
+        out.view_mut(),
+    )
+    .unwrap();
+    let (gs, exp_valid) =
+        time_domain::gated_image(i.view(), PERIOD, None, None, None, Some(gate)).unwrap();
+    assert_eq!(out, gs);
+    assert_eq!(valid, exp_valid);
+
+    // a mismatched output shape is an error
+    let mut bad = Array3::<f64>::zeros((10, 9, 2));
+    assert!(
+        time_domain::image_into(i.view(), PERIOD, None, None, None, None, bad.view_mut()).is_err()
+    );
+}
+
+#[test]
+fn frequency_domain_from_phase_steps() {
+    // create a phase-stepped stack with DC = 100, M = 0.6 and φ = 0.5
+    let steps = 8;
+    let mut data = Array3::<f64>::zeros((steps, 4, 4));
+    for k in 0..steps {
+        let phi_k = 2.0 * std::f64::consts::PI * k as f64 / steps as f64;
+        data.index_axis_mut(Axis(0), k)
+            .fill(100.0 * (1.0 + 0.6 * (phi_k - 0.5).cos()));
+    }
+
+    // compute phasors with all good steps and with a corrupted step
+    let (gs, dc, bad) = frequency_domain::from_phase_steps(data.view(), None, None, None).unwrap();
+    data.index_axis_mut(Axis(0), 3).mapv_inplace(|v| v * 1.5);
+    let (gs_bad, _, bad_corrupted) =
+        frequency_domain::from_phase_steps(data.view(), None, None, None).unwrap();
+
+    // assert the coordinates and the detected bad steps
+    assert!(ensure_within_tolerance(
+        gs[[1, 2, 0]],
+        0.6 * 0.5_f64.cos(),
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        gs[[1, 2, 1]],
+        0.6 * 0.5_f64.sin(),
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(dc[[0, 0]], 100.0, 1e-12));
+    assert!(bad.is_empty());
+    assert_eq!(bad_corrupted, vec![3]);
+    assert!(ensure_within_tolerance(
+        gs_bad[[0, 0, 0]],
+        gs[[0, 0, 0]],
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        gs_bad[[0, 0, 1]],
+        gs[[0, 0, 1]],
+        1e-12
+    ));
+    assert!(
+        frequency_domain::from_phase_steps(data.view(), Some(&[0.0, 1.0]), None, None).is_err()
+    );
+    assert!(frequency_domain::from_phase_steps(data.view(), None, None, Some(3)).is_err());
+}
+
+#[test]
+fn gate_scan_decay() {
+    // simulate a decay and gates of 4 time bins opened every time bin
+    let bins = 16;
+    let dt = PERIOD / bins as f64;
+    let truth: Vec<f64> = (0..bins)
+        .map(|i| 1000.0 * (-(i as f64) * dt / 2.0).exp())
+        .collect();
+    let delays: Vec<f64> = (0..bins).map(|j| j as f64 * dt).collect();
+    let gates: Vec<f64> = (0..bins)
+        .map(|j| (j..j + 4).map(|i| truth[i % bins]).sum())
+        .collect();
+
+    // reconstruct the decay from the overlapping gates
+    let decay = gate_scan::decay(&gates, &delays, 4.0 * dt, PERIOD, None, Some(1e-6)).unwrap();
+    let g = time_domain::real(&decay, PERIOD, None).unwrap();
+    let s = time_domain::imaginary(&decay, PERIOD, None).unwrap();
+
+    // reconstruct a decay image from the gate-scan stack
+    let mut stack = Array3::<f64>::zeros((2, 3, bins));
+    stack
+        .lanes_mut(Axis(2))
+        .into_iter()
+        .for_each(|mut ln| ln.assign(&ndarray::arr1(&gates)));
+    let image = gate_scan::image(
+        stack.view(),
+        &delays,
+        4.0 * dt,
+        PERIOD,
+        None,
+        Some(1e-6),
+        None,
+    )
+    .unwrap();
+
+    // assert the reconstructed phasor coordinates and errors
+    assert!(ensure_within_tolerance(
+        g,
+        time_domain::real(&truth, PERIOD, None).unwrap(),
+        1e-4
+    ));
+    assert!(ensure_within_tolerance(
+        s,
+        time_domain::imaginary(&truth, PERIOD, None).unwrap(),
+        1e-4
+    ));
+    assert_eq!(image.shape(), &[2, 3, bins]);
+    assert!(ensure_within_tolerance(image[[1, 2, 5]], decay[5], 1e-9));
+    assert!(gate_scan::decay(&gates, &delays, 2.0 * PERIOD, PERIOD, None, None).is_err());
+    assert!(gate_scan::decay(&gates[..4], &delays, dt, PERIOD, None, None).is_err());
+}
+
+#[test]
+fn time_domain_pileup_correct() {
+    // simulate the first photon detection of 1e6 excitation cycles, with
+    // mean photon numbers per cycle "lambda" in each time bin
+    let cycles = 1e6;
+    let lambda: Vec<f64> = (0..16).map(|i| 0.05 * (-(i as f64) / 4.0).exp()).collect();
+    let mut data = Array3::<f64>::zeros((2, 2, 16));
+    let mut survival = 1.0;
+    for (i, l) in lambda.iter().enumerate() {
+        data.slice_mut(s![.., .., i])
+            .fill(cycles * survival * (1.0 - (-l).exp()));
+        survival *= (-l).exp();
+    }
+    data.slice_mut(s![1, 1, ..]).fill(0.0);
+    let count_rate = data.slice(s![0, 0, ..]).sum() / cycles;
+
+    // correct the pile-up, with and without a new array
+    let corrected = time_domain::pileup_correct(data.view(), count_rate, 1.0, None).unwrap();
+    time_domain::pileup_correct_mut(data.view_mut(), count_rate, 1.0, None).unwrap();
+
+    // assert the corrected decays
+    for (i, l) in lambda.iter().enumerate() {
+        assert!(ensure_within_tolerance(
+            corrected[[0, 1, i]],
+            cycles * l,
+            1e-6
+        ));
+    }
+    assert_eq!(corrected, data);
+    assert_eq!(corrected[[1, 1, 0]], 0.0);
+    assert!(time_domain::pileup_correct(data.view(), 2.0, 1.0, None).is_err());
+    assert!(time_domain::pileup_correct(data.view(), 0.1, 1.0, Some(3)).is_err());
+}
+
+#[test]
+fn time_domain_histogram_quality() {
+    let data = [0.0, 3.0, 1.0, 0.0, 5.0, 0.5, 2.0, 0.0];
+
+    assert_eq!(time_domain::histogram_quality(&data, None), 0.5);
+    assert_eq!(time_domain::histogram_quality(&data, Some(2.0)), 0.375);
+}
+
+#[test]
+fn time_domain_histogram_quality_image() {
+    // create a decay image with a sharp decay and a flat background pixel
+    let mut data = Array3::<f64>::from_elem((1, 2, 4), 2.0);
+    data.slice_mut(s![0, 0, ..])
+        .assign(&ndarray::arr1(&[10.0, 6.0, 4.0, 2.0]));
+
+    // compute the quality images
+    let count = time_domain::histogram_quality_image(
+        data.view(),
+        &QualityMetric::CountThreshold { threshold: 4.0 },
+        None,
+    )
+    .unwrap();
+    let fisher =
+        time_domain::histogram_quality_image(data.view(), &QualityMetric::FisherInformation, None)
+            .unwrap();
+    let sbr = time_domain::histogram_quality_image(
+        data.view(),
+        &SignalToBackground { fraction: 0.25 },
+        None,
+    )
+    .unwrap();
+
+    // assert the quality images
+    assert_eq!(count[[0, 0]], 0.75);
+    assert_eq!(count[[0, 1]], 0.0);
+    assert!(ensure_within_tolerance(
+        fisher[[0, 0]],
+        2.0 + 0.8 + 4.0 / 3.0,
+        1e-12
+    ));
+    assert_eq!(fisher[[0, 1]], 0.0);
+    assert_eq!(sbr[[0, 0]], 1.75);
+    assert_eq!(sbr[[0, 1]], 0.0);
+    assert!(
+        time_domain::histogram_quality_image(data.view(), &FisherInformation, Some(3)).is_err()
+    );
+}
+
 #[test]
 fn time_domain_imaginary() {
     let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
-    let s = time_domain::imaginary(&i, PERIOD, None);
+    let s = time_domain::imaginary(&i, PERIOD, None).unwrap();
 
     assert_eq!(s, 0.4102178630685894);
 }
@@ -258,7 +1196,102 @@ fn time_domain_imaginary() {
 #[test]
 fn time_domain_real() {
     let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
-    let g = time_domain::real(&i, PERIOD, None);
+    let g = time_domain::real(&i, PERIOD, None).unwrap();
 
     assert_eq!(g, 0.660137605034518);
 }
+
+#[test]
+fn time_domain_validate_harmonic() {
+    // harmonics within the Nyquist limit are valid
+    assert!(time_domain::validate_harmonic(1, SAMPLES).is_ok());
+    assert!(time_domain::validate_harmonic(128, SAMPLES).is_ok());
+
+    // zero and aliased harmonics are rejected
+    assert!(time_domain::validate_harmonic(0, SAMPLES).is_err());
+    assert!(time_domain::validate_harmonic(129, SAMPLES).is_err());
+
+    // aliased harmonics are rejected by the phasor functions
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    assert!(time_domain::real(&i, PERIOD, Some(200)).is_err());
+    assert!(time_domain::imaginary(&i, PERIOD, Some(0)).is_err());
+}
+
+// test the phasor::trajectory module
+#[test]
+fn trajectory_image_series() {
+    // create a time-lapse stack with a lifetime shift over 3 frames
+    let mut data = Array4::<f64>::zeros((3, 5, 5, SAMPLES));
+    [1.0, 2.0, 3.0].iter().enumerate().for_each(|(t, &tau)| {
+        let frame =
+            decay::ideal_exponential_3d(SAMPLES, PERIOD, &[tau], &[1.0], TOTAL_COUNTS, (5, 5))
+                .unwrap();
+        data.index_axis_mut(Axis(0), t).assign(&frame);
+    });
+
+    // compute the per-frame phasors and the ROI trajectory
+    let gs_series = trajectory::image_series(data.view(), PERIOD, None, None).unwrap();
+    let roi = Array2::<bool>::from_elem((5, 5), true);
+    let (g, s) = trajectory::roi_trajectory(gs_series.view(), roi.view()).unwrap();
+    let first =
+        time_domain::image(data.index_axis(Axis(0), 0), PERIOD, None, None, None, None).unwrap();
+
+    assert_eq!(gs_series.dim(), (3, 5, 5, 2));
+    assert!(ensure_within_tolerance(g[0], first[[2, 2, 0]], 1e-12));
+    assert!(ensure_within_tolerance(s[0], first[[2, 2, 1]], 1e-12));
+
+    // longer lifetimes move the phasor towards the origin
+    let stats = trajectory::statistics_image(gs_series.view(), Some(2.0));
+    let (d, _, l, v) = trajectory::statistics(&g, &s, Some(2.0)).unwrap();
+
+    assert!(g[2] < g[0]);
+    assert!(ensure_within_tolerance(stats[[1, 1, 0]], d, 1e-12));
+    assert!(ensure_within_tolerance(stats[[1, 1, 3]], l / 4.0, 1e-12));
+    assert!(ensure_within_tolerance(v, l / 4.0, 1e-12));
+}
+
+#[test]
+fn trajectory_statistics() {
+    // a straight path along G
+    let g = [0.2, 0.3, 0.5];
+    let s = [0.4, 0.4, 0.4];
+    let (d, theta, l, v) = trajectory::statistics(&g, &s, None).unwrap();
+
+    assert!(ensure_within_tolerance(d, 0.3, 1e-12));
+    assert_eq!(theta, 0.0);
+    assert!(ensure_within_tolerance(l, 0.3, 1e-12));
+    assert!(ensure_within_tolerance(v, 0.15, 1e-12));
+    assert!(trajectory::statistics(&g, &s[..2], None).is_err());
+}
+
+#[test]
+fn unmixing_fit_line() {
+    // create a phasor image of two component mixtures, with a NaN pixel
+    let w = omega(PERIOD);
+    let (p1, p2) = (
+        plot::monoexponential_coordinates(1.0, w),
+        plot::monoexponential_coordinates(4.0, w),
+    );
+    let mut data = Array3::<f64>::zeros((3, 3, 2));
+    for (i, mut ln) in data.lanes_mut(Axis(2)).into_iter().enumerate() {
+        let f = 0.1 * (i + 1) as f64;
+        ln[0] = f * p1.0 + (1.0 - f) * p2.0;
+        ln[1] = f * p1.1 + (1.0 - f) * p2.1;
+    }
+    data[[2, 2, 0]] = f64::NAN;
+
+    // fit the line and compute the fractions with the known lifetimes
+    let fit = unmixing::fit_line(data.view(), w, None, None, None).unwrap();
+    let fractions = unmixing::fractions(data.view(), [1.0, 4.0], w, None).unwrap();
+
+    // assert the component lifetimes and fractions
+    assert!(ensure_within_tolerance(fit.taus[0], 1.0, 1e-9));
+    assert!(ensure_within_tolerance(fit.taus[1], 4.0, 1e-9));
+    assert!(ensure_within_tolerance(fit.fractions[[1, 0, 0]], 0.4, 1e-9));
+    assert!(ensure_within_tolerance(fit.fractions[[1, 0, 1]], 0.6, 1e-9));
+    assert!(ensure_within_tolerance(fractions[[0, 2, 0]], 0.3, 1e-9));
+    assert!(fit.fractions[[2, 2, 0]].is_nan());
+    let mask = Array2::<bool>::from_elem((2, 2), true);
+    assert!(unmixing::fit_line(data.view(), w, None, Some(mask.view()), None).is_err());
+    assert!(unmixing::fractions(data.view(), [1.0, 1.0], w, None).is_err());
+}
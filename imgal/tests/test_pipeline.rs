@@ -0,0 +1,149 @@
+use ndarray::Array2;
+
+use imgal::filter;
+use imgal::pipeline::{self, NucleiOptions, Pipeline, PipelineOutput, Step};
+use imgal::segmentation::{self, Connectivity};
+
+// create an image with two separate bright squares on a dim background
+fn two_squares() -> Array2<u16> {
+    Array2::from_shape_fn((40, 40), |(r, c)| {
+        let bright = ((5..15).contains(&r) && (5..15).contains(&c))
+            || ((22..34).contains(&r) && (20..32).contains(&c));
+        let base = if bright { 180 } else { 15 };
+        base + ((r * 7 + c * 13) % 9) as u16
+    })
+}
+
+#[test]
+fn pipeline_run() {
+    let data = two_squares();
+
+    // run a smooth, threshold and label pipeline
+    let mut calls = Vec::new();
+    let output = Pipeline::new()
+        .gaussian(1.0)
+        .threshold_otsu()
+        .label()
+        .run_with_progress(data.view(), |i, n| calls.push((i, n)))
+        .unwrap();
+
+    // assert both squares are labeled and progress was reported per step
+    let PipelineOutput::Labels(labels, count) = output else {
+        panic!("expected a label output");
+    };
+    assert_eq!(count, 2);
+    assert_eq!(labels[[10, 10]], 1);
+    assert_eq!(labels[[28, 26]], 2);
+    assert_eq!(labels[[0, 39]], 0);
+    assert_eq!(calls, vec![(1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn pipeline_fused_gaussian() {
+    let data = two_squares();
+
+    // consecutive Gaussian steps are fused into one pass
+    let PipelineOutput::Image(fused) = Pipeline::new()
+        .gaussian(0.6)
+        .gaussian(0.8)
+        .run(data.view())
+        .unwrap()
+    else {
+        panic!("expected an image output");
+    };
+    let single = filter::gaussian_2d(data.view(), 1.0).unwrap();
+    assert!(fused.iter().zip(&single).all(|(a, b)| (a - b).abs() < 1e-9));
+
+    // a manual threshold and 4-connected labeling match the functions
+    let PipelineOutput::Labels(labels, count) = Pipeline::new()
+        .threshold(100.0)
+        .step(Step::Label {
+            connectivity: Connectivity::Four,
+        })
+        .run(data.view())
+        .unwrap()
+    else {
+        panic!("expected a label output");
+    };
+    let mask = data.mapv(|v| v > 100);
+    assert_eq!(
+        (labels, count),
+        segmentation::label(mask.view(), Some(Connectivity::Four))
+    );
+}
+
+#[test]
+fn pipeline_invalid_steps() {
+    let data = two_squares();
+
+    // assert invalid step orders and parameters are rejected before running
+    assert!(Pipeline::new().label().run(data.view()).is_err());
+    assert!(
+        Pipeline::new()
+            .threshold(10.0)
+            .gaussian(1.0)
+            .run(data.view())
+            .is_err()
+    );
+    assert!(Pipeline::new().gaussian(-1.0).run(data.view()).is_err());
+    assert_eq!(
+        Pipeline::new().run(data.view()).unwrap(),
+        PipelineOutput::Image(data.mapv(|v| v as f64))
+    );
+}
+
+// create an image of isolated and touching nuclei on a sloped background
+fn nuclei() -> Array2<f64> {
+    let centers = [
+        (15.0, 15.0),
+        (15.0, 60.0),
+        (60.0, 20.0),
+        (55.0, 55.0),
+        (55.0, 68.0),
+    ];
+    Array2::from_shape_fn((80, 90), |(r, c)| {
+        let inside = centers
+            .iter()
+            .any(|&(cr, cc)| (r as f64 - cr).hypot(c as f64 - cc) <= 7.5);
+        let background = 20.0 + 0.3 * r as f64 + 0.2 * c as f64;
+        let noise = ((r * 7 + c * 13) % 9) as f64;
+        background + noise + if inside { 150.0 } else { 0.0 }
+    })
+}
+
+#[test]
+fn pipeline_count_nuclei() {
+    let data = nuclei();
+    let result = pipeline::count_nuclei(data.view(), None).unwrap();
+
+    // the touching pair is split into two nuclei
+    assert_eq!(result.count, 5);
+    assert_eq!(result.properties.len(), 5);
+    let l = |r: usize, c: usize| result.labels[[r, c]];
+    assert_ne!(l(55, 55), 0);
+    assert_ne!(l(55, 68), 0);
+    assert_ne!(l(55, 55), l(55, 68));
+    assert_eq!(l(0, 0), 0);
+    result.properties.iter().for_each(|p| {
+        assert!((p.area - 177.0).abs() < 40.0);
+    });
+
+    // a large minimum area removes all nuclei
+    let options = NucleiOptions {
+        min_area: 1000,
+        ..NucleiOptions::default()
+    };
+    assert_eq!(
+        pipeline::count_nuclei(data.view(), Some(options))
+            .unwrap()
+            .count,
+        0
+    );
+
+    // invalid parameters
+    let options = NucleiOptions {
+        min_separation: 0,
+        ..NucleiOptions::default()
+    };
+    assert!(pipeline::count_nuclei(data.view(), Some(options)).is_err());
+}
@@ -0,0 +1,146 @@
+use ndarray::{Array2, Array3, s};
+
+use imgal::registration;
+
+// create a smooth test image with a few Gaussian spots
+fn spots(rows: usize, cols: usize, offset: (f64, f64)) -> Array2<f64> {
+    let centers = [(20.0, 18.0), (40.0, 44.0), (28.0, 40.0)];
+    Array2::from_shape_fn((rows, cols), |(r, c)| {
+        centers
+            .iter()
+            .map(|(cr, cc)| {
+                let dr = r as f64 - cr - offset.0;
+                let dc = c as f64 - cc - offset.1;
+                (-(dr * dr + dc * dc) / 18.0).exp()
+            })
+            .sum()
+    })
+}
+
+#[test]
+fn registration_phase_correlation_integer_shift() {
+    let reference = spots(64, 64, (0.0, 0.0));
+    let moving = spots(64, 64, (3.0, -5.0));
+
+    // estimate the shift
+    let (dr, dc) = registration::phase_correlation(reference.view(), moving.view()).unwrap();
+
+    // assert the shift aligns moving to reference
+    assert!((dr + 3.0).abs() < 0.05);
+    assert!((dc - 5.0).abs() < 0.05);
+}
+
+#[test]
+fn registration_phase_correlation_invalid_shapes() {
+    let a = Array2::<f64>::zeros((8, 8));
+    let b = Array2::<f64>::zeros((8, 9));
+    assert!(registration::phase_correlation(a.view(), b.view()).is_err());
+}
+
+#[test]
+fn registration_translate() {
+    let data = spots(32, 32, (0.0, 0.0));
+
+    // translate by an integer shift
+    let shifted = registration::translate(data.view(), (2.0, -1.0), None);
+
+    // assert shifted values and fill
+    assert!((shifted[[22, 17]] - data[[20, 18]]).abs() < 1e-12);
+    assert_eq!(shifted[[0, 5]], 0.0);
+    assert_eq!(shifted[[5, 31]], 0.0);
+}
+
+#[test]
+fn registration_align_channels() {
+    // create a 2 channel image with a shifted second channel
+    let mut data = Array3::<f64>::zeros((2, 64, 64));
+    data.slice_mut(s![0, .., ..])
+        .assign(&spots(64, 64, (0.0, 0.0)));
+    data.slice_mut(s![1, .., ..])
+        .assign(&spots(64, 64, (-2.0, 4.0)));
+
+    // align the channels
+    let (aligned, shifts) = registration::align_channels(data.view(), None, None).unwrap();
+
+    // assert the shifts and the aligned spot
+    assert!(shifts[[0, 0]].abs() < 1e-9 && shifts[[0, 1]].abs() < 1e-9);
+    assert!((shifts[[1, 0]] - 2.0).abs() < 0.05);
+    assert!((shifts[[1, 1]] + 4.0).abs() < 0.05);
+    assert!((aligned[[1, 20, 18]] - data[[0, 20, 18]]).abs() < 0.05);
+    assert!(registration::align_channels(data.view(), Some(2), None).is_err());
+}
+
+#[test]
+fn registration_stitch_refines_positions() {
+    // cut a 2 x 2 grid of overlapping tiles from a larger image
+    let image = spots(80, 80, (0.0, 0.0)) + spots(80, 80, (25.0, 22.0));
+    let origins = [(0, 0), (0, 30), (32, 0), (32, 30)];
+    let tiles: Vec<Array2<f64>> = origins
+        .iter()
+        .map(|&(r, c)| image.slice(s![r..r + 48, c..c + 48]).to_owned())
+        .collect();
+    let views: Vec<_> = tiles.iter().map(|t| t.view()).collect();
+
+    // stitch with perturbed nominal positions
+    let nominal = [(0.0, 0.0), (0.0, 32.0), (30.0, 0.0), (30.0, 32.0)];
+    let (mosaic, positions) = registration::stitch(&views, &nominal, None, Some(6.0)).unwrap();
+
+    // assert the refined positions and the fused mosaic
+    origins.iter().enumerate().for_each(|(k, &(r, c))| {
+        assert!((positions[[k, 0]] - r as f64).abs() < 0.25);
+        assert!((positions[[k, 1]] - c as f64).abs() < 0.25);
+    });
+    assert_eq!(mosaic.dim(), (80, 78));
+    assert!((mosaic[[50, 50]] - image[[50, 50]]).abs() < 1e-9);
+    assert!(registration::stitch(&views, &nominal[..2], None, None).is_err());
+}
+
+#[test]
+fn registration_chromatic_correction() {
+    // a two-color bead grid where the second channel is magnified, sheared
+    // and shifted
+    let map = |r: f64, c: f64| (0.8 + 1.01 * r + 0.005 * c, -0.5 - 0.004 * r + 0.99 * c);
+    let beads: Vec<(f64, f64)> = (0..25)
+        .map(|i| (8.0 + 11.0 * (i / 5) as f64, 8.0 + 11.0 * (i % 5) as f64))
+        .collect();
+    let data = Array3::from_shape_fn((2, 64, 64), |(ch, r, c)| {
+        beads
+            .iter()
+            .map(|&(br, bc)| {
+                let (br, bc) = if ch == 1 { map(br, bc) } else { (br, bc) };
+                let d = (r as f64 - br).powi(2) + (c as f64 - bc).powi(2);
+                5.0 + 400.0 * (-d / (2.0 * 1.3 * 1.3)).exp()
+            })
+            .sum::<f64>()
+    });
+    let model = registration::chromatic_correction(data.view(), 4, None, None, None, None).unwrap();
+
+    // assert the affine model of each channel
+    assert_eq!(model.n_beads, vec![25, 25]);
+    let (row, col) = &model.coefficients[1];
+    [0.8, 1.01, 0.005]
+        .iter()
+        .zip(row)
+        .chain([-0.5, -0.004, 0.99].iter().zip(col))
+        .for_each(|(e, v)| assert!((e - v).abs() < 1e-3));
+    assert!(model.residual[1] < 0.01);
+    assert!(model.residual[0] < 1e-12);
+
+    // bilinear resampling of a linear ramp is exact, so the corrected second
+    // channel is the ramp at the mapped positions
+    let ramp = Array3::from_shape_fn((2, 64, 64), |(_, r, c)| r as f64 + 2.0 * c as f64);
+    let corrected = registration::apply_chromatic_correction(ramp.view(), &model, None).unwrap();
+    assert_eq!(corrected.slice(s![0, .., ..]), ramp.slice(s![0, .., ..]));
+    for (r, c) in [(5, 5), (30, 40), (55, 50)] {
+        let (mr, mc) = map(r as f64, c as f64);
+        assert!((corrected[[1, r, c]] - (mr + 2.0 * mc)).abs() < 0.1);
+    }
+    assert_eq!(corrected[[1, 63, 0]], 0.0);
+
+    // a quadratic model needs more beads than an affine model
+    let model =
+        registration::chromatic_correction(data.view(), 4, Some(2), None, None, None).unwrap();
+    assert_eq!(model.coefficients[1].0.len(), 6);
+    assert!(registration::chromatic_correction(data.view(), 4, Some(6), None, None, None).is_err());
+    assert!(registration::chromatic_correction(data.view(), 4, None, Some(2), None, None).is_err());
+}
@@ -0,0 +1,293 @@
+use ndarray::{Array2, Array3, s};
+
+use imgal::segmentation::{self, Connectivity};
+
+#[test]
+fn segmentation_random_walker() {
+    // create a noisy two region image, left dark and right bright
+    let data = Array2::from_shape_fn((16, 16), |(r, c)| {
+        let base = if c < 8 { 20.0 } else { 80.0 };
+        base + ((r * 7 + c * 13) % 5) as f64
+    });
+    let mut seeds = Array2::<usize>::zeros((16, 16));
+    seeds[[8, 1]] = 1;
+    seeds[[8, 14]] = 2;
+
+    // segment the image
+    let (probs, labels) =
+        segmentation::random_walker(data.view(), seeds.view(), None, None, None).unwrap();
+
+    // assert probabilities sum to 1.0 and regions follow the edge
+    assert_eq!(probs.shape(), &[2, 16, 16]);
+    assert!((probs[[0, 3, 5]] + probs[[1, 3, 5]] - 1.0).abs() < 1e-4);
+    assert!(labels.column(7).iter().all(|&l| l == 1));
+    assert!(labels.column(8).iter().all(|&l| l == 2));
+    assert_eq!(probs[[0, 8, 1]], 1.0);
+    let no_seeds = Array2::<usize>::zeros((16, 16));
+    assert!(segmentation::random_walker(data.view(), no_seeds.view(), None, None, None).is_err());
+}
+
+#[test]
+fn segmentation_felzenszwalb() {
+    // create a four quadrant image
+    let data = Array2::from_shape_fn((20, 20), |(r, c)| match (r < 10, c < 10) {
+        (true, true) => 10_u16,
+        (true, false) => 100,
+        (false, true) => 200,
+        (false, false) => 250,
+    });
+
+    // segment the image
+    let labels = segmentation::felzenszwalb(data.view(), Some(0.5), Some(0.0), Some(5)).unwrap();
+
+    // assert one region per quadrant, labeled in raster order
+    assert_eq!(labels[[0, 0]], 1);
+    assert_eq!(labels[[0, 19]], 2);
+    assert_eq!(labels[[19, 0]], 3);
+    assert_eq!(labels[[19, 19]], 4);
+    assert_eq!(labels.iter().copied().max().unwrap(), 4);
+    assert!(segmentation::felzenszwalb(data.view(), Some(-1.0), None, None).is_err());
+}
+
+#[test]
+fn segmentation_label() {
+    // create a mask with two diagonally touching squares and a separate bar
+    let mut mask = Array2::<bool>::default((8, 8));
+    for (r, c) in [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (1, 1),
+        (2, 2),
+        (2, 3),
+        (3, 2),
+        (3, 3),
+    ] {
+        mask[[r, c]] = true;
+    }
+    for r in 0..8 {
+        mask[[r, 6]] = true;
+    }
+
+    // label the mask with 8 and 4 connectivity
+    let (labels_8, count_8) = segmentation::label(mask.view(), None);
+    let (labels_4, count_4) =
+        segmentation::label(mask.view(), Some(segmentation::Connectivity::Four));
+
+    // assert the diagonal squares only merge with 8 connectivity
+    assert_eq!(count_8, 2);
+    assert_eq!(count_4, 3);
+    assert_eq!(labels_8[[0, 0]], 1);
+    assert_eq!(labels_8[[3, 3]], 1);
+    assert_eq!(labels_8[[7, 6]], 2);
+    assert_eq!(labels_4[[0, 6]], 2);
+    assert_eq!(labels_4[[2, 2]], 3);
+    assert_eq!(labels_4[[5, 5]], 0);
+}
+
+#[test]
+fn segmentation_region_properties() {
+    // create a label image with two rectangular regions and a missing label
+    let mut labels = Array2::<usize>::zeros((10, 10));
+    labels.slice_mut(s![1..3, 2..6]).fill(1);
+    labels.slice_mut(s![5..9, 5..7]).fill(3);
+
+    // measure the regions in pixels and in physical units
+    let props = segmentation::region_properties(labels.view(), None).unwrap();
+    let props_um = segmentation::region_properties(labels.view(), Some([0.5, 0.25])).unwrap();
+
+    // assert the region measurements
+    assert_eq!(props.len(), 2);
+    assert_eq!(props[0].label, 1);
+    assert_eq!(props[0].pixel_count, 8);
+    assert_eq!(props[0].area, 8.0);
+    assert_eq!(props[0].centroid, (1.5, 3.5));
+    assert_eq!(props[0].bbox, (1, 2, 3, 6));
+    assert_eq!(props[1].label, 3);
+    assert_eq!(props[1].bbox, (5, 5, 9, 7));
+    assert_eq!(props_um[0].area, 1.0);
+    assert_eq!(props_um[0].centroid, (0.75, 0.875));
+    assert!((props_um[1].equivalent_diameter - (4.0 / std::f64::consts::PI).sqrt()).abs() < 1e-12);
+    assert!(segmentation::region_properties(labels.view(), Some([-1.0, 1.0])).is_err());
+}
+
+#[test]
+fn segmentation_watershed() {
+    // two basins separated by a ridge at column 5
+    let surface = Array2::from_shape_fn((6, 11), |(_, c)| -((c as f64 - 5.0).abs()));
+    let mut markers = Array2::<usize>::zeros((6, 11));
+    markers[[3, 0]] = 1;
+    markers[[3, 10]] = 2;
+    let labels = segmentation::watershed(surface.view(), markers.view(), None, None).unwrap();
+    assert!(labels.slice(s![.., ..5]).iter().all(|&l| l == 1));
+    assert!(labels.slice(s![.., 6..]).iter().all(|&l| l == 2));
+
+    // masked pixels are not flooded
+    let mask = Array2::from_shape_fn((6, 11), |(r, _)| r < 4);
+    let labels =
+        segmentation::watershed(surface.view(), markers.view(), Some(mask.view()), None).unwrap();
+    assert!(labels.slice(s![4.., ..]).iter().all(|&l| l == 0));
+    assert_eq!(labels[[0, 0]], 1);
+
+    // invalid parameters
+    let small = Array2::<usize>::zeros((6, 10));
+    assert!(segmentation::watershed(surface.view(), small.view(), None, None).is_err());
+}
+
+#[test]
+fn segmentation_kmeans_features() {
+    // a (row, col, feature) stack of three regions with distinct intensity and
+    // phasor-like coordinates on very different scales, and a masked pixel
+    let mut features = Array3::from_shape_fn((12, 12, 3), |(r, c, f)| {
+        let class = if c < 4 {
+            0
+        } else if r < 6 {
+            1
+        } else {
+            2
+        };
+        let jitter = ((r * 7 + c * 13 + f * 5) % 5) as f64 / 5.0 - 0.4;
+        match f {
+            0 => [100.0, 1000.0, 2000.0][class] + 50.0 * jitter,
+            1 => [0.8, 0.3, 0.6][class] + 0.02 * jitter,
+            _ => [0.2, 0.4, 0.1][class] + 0.02 * jitter,
+        }
+    });
+    features[[0, 0, 1]] = f64::NAN;
+
+    // assert each region is one class, ordered by the first feature
+    let (labels, centroids) =
+        segmentation::kmeans_features(features.view(), 3, None, None, Some(7), None).unwrap();
+    assert_eq!(labels[[0, 0]], 0);
+    assert!(labels.slice(s![1.., ..4]).iter().all(|&l| l == 1));
+    assert!(labels.slice(s![..6, 4..]).iter().all(|&l| l == 2));
+    assert!(labels.slice(s![6.., 4..]).iter().all(|&l| l == 3));
+    assert_eq!(centroids.len(), 3);
+    assert!((centroids[1][1] - 0.3).abs() < 0.02);
+
+    // the feature axis can be moved
+    let moved = features.view().permuted_axes([2, 0, 1]);
+    let (moved_labels, _) =
+        segmentation::kmeans_features(moved, 3, None, None, Some(7), Some(0)).unwrap();
+    assert_eq!(moved_labels, labels);
+
+    // invalid parameters
+    assert!(segmentation::kmeans_features(features.view(), 0, None, None, None, None).is_err());
+    assert!(segmentation::kmeans_features(features.view(), 200, None, None, None, None).is_err());
+    assert!(segmentation::kmeans_features(features.view(), 3, None, None, None, Some(3)).is_err());
+}
+
+#[test]
+fn segmentation_label_postprocess() {
+    // three touching regions with non-consecutive labels and a separate one
+    let mut labels = Array2::<usize>::zeros((8, 10));
+    labels.slice_mut(s![1..4, 1..3]).fill(3);
+    labels.slice_mut(s![1..4, 3..5]).fill(7);
+    labels.slice_mut(s![4..6, 1..5]).fill(9);
+    labels.slice_mut(s![1..3, 7..9]).fill(12);
+
+    // relabel sequentially in label order
+    let (seq, count) = segmentation::relabel_sequential(labels.view());
+    assert_eq!(count, 4);
+    assert_eq!(
+        (seq[[1, 1]], seq[[1, 3]], seq[[4, 1]], seq[[1, 7]]),
+        (1, 2, 3, 4)
+    );
+    assert_eq!(seq[[0, 0]], 0);
+
+    // adjacency and merging
+    assert_eq!(
+        segmentation::label_adjacency(labels.view(), None),
+        vec![(3, 7), (3, 9), (7, 9)]
+    );
+    let merged = segmentation::merge_labels(labels.view(), &[(7, 9), (0, 12)]);
+    assert_eq!(merged[[4, 1]], 7);
+    assert_eq!(merged[[1, 1]], 3);
+    assert_eq!(merged[[1, 7]], 12);
+    let merged = segmentation::merge_adjacent(
+        labels.view(),
+        |a, b| a.pixel_count + b.pixel_count <= 12,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(merged[[1, 3]], 3);
+    assert_eq!(merged[[4, 1]], 9);
+
+    // inner boundaries of each region
+    let boundaries = segmentation::label_boundaries(labels.view(), Some(Connectivity::Four));
+    assert!(boundaries[[1, 1]]);
+    assert!(boundaries[[2, 2]]);
+    assert!(!boundaries[[0, 0]]);
+    let wide = Array2::from_elem((5, 5), 1usize);
+    assert!(
+        !segmentation::label_boundaries(wide.view(), None)
+            .iter()
+            .any(|&b| b)
+    );
+
+    // expand without overlap and shrink
+    let expanded = segmentation::expand_labels(labels.view(), 2);
+    assert_eq!(expanded[[1, 6]], 12);
+    assert_eq!(expanded[[1, 5]], 7);
+    assert_eq!(expanded[[7, 2]], 9);
+    assert_eq!(expanded[[1, 3]], 7);
+    let shrunk = segmentation::shrink_labels(labels.view(), 1);
+    assert_eq!(shrunk[[1, 1]], 0);
+    assert_eq!(shrunk[[2, 2]], 0);
+    assert!(shrunk.iter().all(|&l| l == 0));
+    let big = Array2::from_shape_fn((9, 9), |(r, c)| {
+        usize::from((2..7).contains(&r) && (2..7).contains(&c))
+    });
+    let shrunk = segmentation::shrink_labels(big.view(), 1);
+    assert_eq!(shrunk.iter().filter(|&&l| l == 1).count(), 9);
+}
+
+#[test]
+fn segmentation_propagate_labels() {
+    // two nuclei seeds with a membrane between them, off the midpoint
+    let mut seeds = Array2::<usize>::zeros((20, 31));
+    seeds.slice_mut(s![9..11, 4..6]).fill(1);
+    seeds.slice_mut(s![9..11, 25..27]).fill(2);
+    let membrane = Array2::from_shape_fn((20, 31), |(_, c)| if c == 12 { 100.0 } else { 5.0 });
+
+    // the membrane stops the first cell, unlike a plain Voronoi expansion
+    let cells =
+        segmentation::propagate_labels(seeds.view(), membrane.view(), None, None, None).unwrap();
+    assert_eq!(cells[[10, 11]], 1);
+    assert_eq!(cells[[10, 14]], 2);
+    assert_eq!(cells[[0, 13]], 2);
+    assert_eq!(cells[[10, 5]], 1);
+    let voronoi = segmentation::expand_labels(seeds.view(), 40);
+    assert_eq!(voronoi[[10, 14]], 1);
+
+    // a strong regularization ignores the membrane, the distance and mask
+    // limit the expansion
+    let cells =
+        segmentation::propagate_labels(seeds.view(), membrane.view(), Some(1e6), None, None)
+            .unwrap();
+    assert_eq!(cells[[10, 14]], 1);
+    let cells =
+        segmentation::propagate_labels(seeds.view(), membrane.view(), None, Some(3.0), None)
+            .unwrap();
+    assert_eq!(cells[[10, 8]], 1);
+    assert_eq!(cells[[10, 9]], 0);
+    let mask = Array2::from_shape_fn((20, 31), |(r, _)| r < 15);
+    let cells = segmentation::propagate_labels(
+        seeds.view(),
+        membrane.view(),
+        None,
+        None,
+        Some(mask.view()),
+    )
+    .unwrap();
+    assert_eq!(cells[[16, 5]], 0);
+
+    // invalid parameters
+    assert!(
+        segmentation::propagate_labels(seeds.view(), membrane.view(), Some(-1.0), None, None)
+            .is_err()
+    );
+    let small = Array2::<f64>::zeros((20, 30));
+    assert!(segmentation::propagate_labels(seeds.view(), small.view(), None, None, None).is_err());
+}
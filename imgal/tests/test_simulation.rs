@@ -1,4 +1,4 @@
-use ndarray::s;
+use ndarray::{Array2, Array3, s};
 
 use imgal::integration::midpoint;
 use imgal::simulation::{decay, instrument, noise};
@@ -140,6 +140,33 @@ fn decay_irf_exponential_3d() {
 }
 
 // test the simulation::instrument module
+#[test]
+fn instrument_estimate_irf() {
+    // simulate a monoexponential reference decay with a known IRF
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH);
+    let i = decay::irf_exponential_1d(&irf, SAMPLES, PERIOD, &[2.0], &[1.0], TOTAL_COUNTS).unwrap();
+    let est = instrument::estimate_irf(&i, PERIOD, 2.0, Some(1e-6)).unwrap();
+
+    // check the recovered IRF is normalized and matches the simulated IRF
+    let irf_sum = sum(&irf);
+    let argmax = |v: &[f64]| {
+        v.iter()
+            .enumerate()
+            .fold((0, f64::MIN), |m, (k, &x)| if x > m.1 { (k, x) } else { m })
+            .0
+    };
+    assert_eq!(est.len(), SAMPLES);
+    assert!(ensure_within_tolerance(sum(&est), 1.0, 1e-9));
+    assert_eq!(argmax(&est), argmax(&irf));
+    est.iter()
+        .zip(irf.iter())
+        .for_each(|(e, r)| assert!(ensure_within_tolerance(*e, r / irf_sum, 1e-3)));
+
+    // check invalid parameters
+    assert!(instrument::estimate_irf(&i, PERIOD, 0.0, None).is_err());
+    assert!(instrument::estimate_irf::<f64>(&[], PERIOD, 2.0, None).is_err());
+}
+
 #[test]
 fn instrument_gaussian_irf_1d() {
     // simulate IRF data
@@ -150,7 +177,7 @@ fn instrument_gaussian_irf_1d() {
 
     // check the curve by integration and a point
     assert!(ensure_within_tolerance(
-        midpoint(&irf, Some(dt)),
+        midpoint(&irf, Some(dt), None),
         0.048828125,
         1e-12
     ));
@@ -230,3 +257,45 @@ fn noise_poisson_3d_mut() {
     assert_ne!(i_a, i_b);
     assert!(i_a.iter().all(|&x| x >= 0.0));
 }
+
+#[test]
+fn noise_poisson_3d_lane_streams() {
+    // create data with identical lanes
+    let data = Array3::<f64>::from_elem((4, 4, 64), 20.0);
+    let mut data_mut = data.clone();
+    let seed = Some(7);
+
+    // apply noise with new and mutating functions
+    let result = noise::poisson_3d(data.view(), 1.0, seed, None).unwrap();
+    noise::poisson_3d_mut(data_mut.view_mut(), 1.0, seed, None);
+
+    // assert both paths agree and lanes are independent
+    assert_eq!(result, data_mut);
+    assert_ne!(result.slice(s![0, 0, ..]), result.slice(s![0, 1, ..]));
+    assert_ne!(noise::lane_seed(7, 0), noise::lane_seed(7, 1));
+}
+
+#[test]
+fn noise_2d() {
+    // create a flat test image
+    let data = Array2::<u16>::from_elem((16, 16), 50);
+    let mut data_mut = data.mapv(|v| v as f64);
+    let seed = Some(42);
+
+    // apply Poisson noise and test if deterministic with seed
+    let result_a = noise::poisson_2d(data.view(), 1.0, seed);
+    let result_b = noise::poisson_2d(data.view(), 1.0, seed);
+    noise::poisson_2d_mut(data_mut.view_mut(), 1.0, seed);
+    assert_eq!(result_a.shape(), [16, 16]);
+    assert_eq!(result_a, result_b);
+    assert_eq!(result_a, data_mut);
+    assert_ne!(result_a, noise::poisson_2d(data.view(), 1.0, Some(30)));
+
+    // apply Gaussian noise and test the noise level
+    let gauss = noise::gaussian_2d(data.view(), 2.0, seed).unwrap();
+    let mean = gauss.mean().unwrap();
+    let std = gauss.std(0.0);
+    assert!(ensure_within_tolerance(mean, 50.0, 0.5));
+    assert!(ensure_within_tolerance(std, 2.0, 0.5));
+    assert!(noise::gaussian_2d(data.view(), -1.0, seed).is_err());
+}
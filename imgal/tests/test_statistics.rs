@@ -1,4 +1,7 @@
-use imgal::statistics;
+use ndarray::{Array2, Array3, s};
+
+use imgal::simulation::noise;
+use imgal::statistics::{self, PAdjustMethod, RankMethod, Tail};
 
 #[test]
 fn statistics_sum() {
@@ -11,6 +14,74 @@ fn statistics_sum() {
     assert_eq!(statistics::sum(&float_data), 51.86);
 }
 
+#[test]
+fn statistics_compensated_sum() {
+    // small values lost against a large magnitude by naive accumulation
+    let mut data = vec![1e16];
+    data.extend(std::iter::repeat_n(1.0, 1000));
+    data.push(-1e16);
+    let naive = statistics::compensated_sum(&data, Some(statistics::Summation::Naive));
+    let kahan = statistics::compensated_sum(&data, Some(statistics::Summation::Kahan));
+
+    // assert compensated results
+    assert_ne!(naive, 1000.0);
+    assert_eq!(kahan, 1000.0);
+
+    // the rounding error of pairwise summation grows slower than naive
+    let tenths = vec![0.1; 1_000_000];
+    let naive = statistics::compensated_sum(&tenths, Some(statistics::Summation::Naive));
+    let pairwise = statistics::compensated_sum(&tenths, Some(statistics::Summation::Pairwise));
+    assert!((pairwise - 100_000.0).abs() < (naive - 100_000.0).abs());
+
+    // u16 values are accumulated as f64 without overflow
+    let counts = vec![u16::MAX; 100_000];
+    assert_eq!(
+        statistics::compensated_sum(&counts, Some(statistics::Summation::Kahan)),
+        65535.0 * 100_000.0
+    );
+    assert_eq!(
+        statistics::compensated_sum(&counts, None),
+        65535.0 * 100_000.0
+    );
+}
+
+#[test]
+fn statistics_median() {
+    // odd and even number of values
+    let mut odd = vec![5.0, 1.0, 3.0];
+    let mut even = vec![4.0, 1.0, 3.0, 2.0];
+
+    // assert medians, the values are sorted in place
+    assert_eq!(statistics::median(&mut odd), 3.0);
+    assert_eq!(odd, [1.0, 3.0, 5.0]);
+    assert_eq!(statistics::median(&mut even), 2.5);
+    assert!(statistics::median(&mut []).is_nan());
+}
+
+#[test]
+fn statistics_par_sum() {
+    // values spanning several orders of magnitude over many blocks
+    let data: Vec<f64> = (0..100_003)
+        .map(|i| (i as f64 * 0.37).sin() * 10f64.powi(i % 7))
+        .collect();
+    let sums: Vec<f64> = [1, 3, 8]
+        .iter()
+        .map(|&t| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(t)
+                .build()
+                .unwrap()
+                .install(|| statistics::par_sum(&data))
+        })
+        .collect();
+
+    // assert bit-identical results for any thread count
+    assert!(sums.iter().all(|s| s.to_bits() == sums[0].to_bits()));
+    assert!((sums[0] - statistics::sum(&data)).abs() < 1e-6 * statistics::sum(&data).abs());
+    assert_eq!(statistics::par_sum(&[2, 5, 10, 23]), 40);
+    assert_eq!(statistics::par_sum::<f64>(&[]), 0.0);
+}
+
 #[test]
 fn statistics_weighted_merge_sort_mut() {
     // create data and associated weights
@@ -25,3 +96,375 @@ fn statistics_weighted_merge_sort_mut() {
     assert_eq!(w, [0.51, 0.32, 12.83, 9.25, 4.24]);
     assert_eq!(s, 47.64239999999998);
 }
+
+#[test]
+fn statistics_weighted_merge_sort() {
+    // create data and associated weights
+    let d = [3, 10, 87, 22, 5];
+    let w = [0.51, 12.83, 4.24, 9.25, 0.32];
+
+    // sort copies of the data and weights, the inputs are not mutated
+    let (sd, sw, s) = statistics::weighted_merge_sort(&d, &w).unwrap();
+    assert_eq!(sd, vec![3, 5, 10, 22, 87]);
+    assert_eq!(sw, vec![0.51, 0.32, 12.83, 9.25, 4.24]);
+    assert_eq!(s, 47.64239999999998);
+    assert_eq!(d, [3, 10, 87, 22, 5]);
+
+    // count the same inversions over a 2-dimensional view
+    let d_2d = Array2::from_shape_vec((1, 5), d.to_vec()).unwrap();
+    let w_2d = Array2::from_shape_vec((1, 5), w.to_vec()).unwrap();
+    assert_eq!(
+        statistics::weighted_inversions(d_2d.view().into_dyn(), w_2d.view().into_dyn()).unwrap(),
+        s
+    );
+    assert!(statistics::weighted_inversions(d_2d.view().into_dyn(), w_2d.t().into_dyn()).is_err());
+}
+
+#[test]
+fn statistics_rank() {
+    // create 2-dimensional data with ties and a NaN value
+    let data =
+        Array2::from_shape_vec((2, 3), vec![20.0, 10.0, f64::NAN, 30.0, 20.0, 10.0]).unwrap();
+    let rank = |method| {
+        statistics::rank(data.view().into_dyn(), Some(method))
+            .into_iter()
+            .collect::<Vec<f64>>()
+    };
+
+    // assert each tie handling method
+    assert_eq!(
+        rank(RankMethod::Average),
+        vec![3.5, 1.5, 6.0, 5.0, 3.5, 1.5]
+    );
+    assert_eq!(rank(RankMethod::Dense), vec![2.0, 1.0, 4.0, 3.0, 2.0, 1.0]);
+    assert_eq!(rank(RankMethod::Min), vec![3.0, 1.0, 6.0, 5.0, 3.0, 1.0]);
+    assert_eq!(
+        rank(RankMethod::Ordinal),
+        vec![3.0, 1.0, 6.0, 5.0, 4.0, 2.0]
+    );
+    assert_eq!(
+        statistics::rank(data.view().into_dyn(), None).shape(),
+        &[2, 3]
+    );
+}
+
+#[test]
+fn statistics_weighted_kendall_tau_b() {
+    // create perfectly correlated and anti-correlated data
+    let a = [1, 2, 3, 4, 5, 6];
+    let b_pos = [2, 4, 6, 8, 10, 12];
+    let b_neg = [12, 10, 8, 6, 4, 2];
+    let w = [1.0, 0.5, 2.0, 1.5, 1.0, 0.25];
+
+    // check tau is at the range limits
+    assert_eq!(
+        statistics::weighted_kendall_tau_b(&a, &b_pos, &w).unwrap(),
+        1.0
+    );
+    assert_eq!(
+        statistics::weighted_kendall_tau_b(&a, &b_neg, &w).unwrap(),
+        -1.0
+    );
+
+    // check a fully tied input has no correlation
+    let b_tied = [3, 3, 3, 3, 3, 3];
+    assert_eq!(
+        statistics::weighted_kendall_tau_b(&a, &b_tied, &w).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn statistics_weighted_kendall_tau_b_with_ties() {
+    // create heavily tied data, similar to a low bit depth image
+    let a: Vec<u8> = (0..200).map(|i: u32| ((i * 7) % 5) as u8).collect();
+    let b: Vec<u8> = (0..200).map(|i: u32| ((i * 3 + i / 7) % 4) as u8).collect();
+    let w: Vec<f64> = (0..200).map(|i| 0.1 + (i % 9) as f64 * 0.3).collect();
+
+    // compute the reference tau-b and tie terms with brute force
+    let mut numer = 0.0;
+    let mut total = 0.0;
+    let mut ties_a = 0.0;
+    let mut ties_b = 0.0;
+    let mut ties_ab = 0.0;
+    for i in 0..a.len() {
+        for j in (i + 1)..a.len() {
+            let pw = w[i] * w[j];
+            let da = a[i] as f64 - a[j] as f64;
+            let db = b[i] as f64 - b[j] as f64;
+            total += pw;
+            if da == 0.0 {
+                ties_a += pw;
+            }
+            if db == 0.0 {
+                ties_b += pw;
+            }
+            if da == 0.0 && db == 0.0 {
+                ties_ab += pw;
+            }
+            if da * db > 0.0 {
+                numer += pw;
+            } else if da * db < 0.0 {
+                numer -= pw;
+            }
+        }
+    }
+    let tau_ref = numer / ((total - ties_a) * (total - ties_b)).sqrt();
+
+    // compare the fast implementation to the reference
+    let (tau, ta, tb, tab) = statistics::weighted_kendall_tau_b_with_ties(&a, &b, &w).unwrap();
+    assert!((tau - tau_ref).abs() < 1e-9);
+    assert!((ta - ties_a).abs() < 1e-6);
+    assert!((tb - ties_b).abs() < 1e-6);
+    assert!((tab - ties_ab).abs() < 1e-6);
+}
+
+#[test]
+fn statistics_weighted_mean_variance() {
+    let d = [1.0, 2.0, 3.0, 4.0];
+    let w = [1.0, 1.0, 2.0, 0.0];
+
+    // check weighted mean and biased and unbiased variance
+    let mu = statistics::weighted_mean(&d, &w).unwrap();
+    assert_eq!(mu, 2.25);
+    let var = statistics::weighted_variance(&d, &w, None).unwrap();
+    assert_eq!(var, 0.6875);
+    let var_unbiased = statistics::weighted_variance(&d, &w, Some(true)).unwrap();
+    assert_eq!(var_unbiased, 0.6875 * 4.0 / (4.0 - 6.0 / 4.0));
+}
+
+#[test]
+fn statistics_weighted_axis() {
+    // create a (2, 4) array with a sample set on each row
+    let d = Array2::from_shape_vec((2, 4), vec![1.0, 2.0, 3.0, 4.0, 4.0, 3.0, 2.0, 1.0]).unwrap();
+    let w = Array2::from_shape_vec((2, 4), vec![1.0, 1.0, 2.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+
+    // check per-row statistics match the slice functions
+    let mu = statistics::weighted_mean_axis(d.view().into_dyn(), w.view().into_dyn(), 1).unwrap();
+    assert_eq!(mu.shape(), [2]);
+    assert_eq!(mu[[0]], 2.25);
+    assert_eq!(mu[[1]], 2.5);
+    let ess = statistics::effective_sample_size_axis(w.view().into_dyn(), 1).unwrap();
+    assert_eq!(ess[[1]], 4.0);
+    let r = statistics::weighted_correlation_axis(
+        d.view().into_dyn(),
+        d.view().into_dyn(),
+        w.view().into_dyn(),
+        1,
+    )
+    .unwrap();
+    assert!((r[[0]] - 1.0).abs() < 1e-12);
+
+    // check invalid axis
+    assert!(statistics::weighted_mean_axis(d.view().into_dyn(), w.view().into_dyn(), 2).is_err());
+}
+
+#[test]
+fn statistics_histogram2d() {
+    let x = [0.0, 0.1, 0.9, 1.0, 0.5];
+    let y = [0.0, 0.9, 0.9, 1.0, 2.0];
+
+    // count pairs in a 2x2 grid, the last pair is outside the y range
+    let h = statistics::histogram2d(&x, &y, Some((2, 2)), Some(((0.0, 1.0), (0.0, 1.0))), None)
+        .unwrap();
+    assert_eq!(
+        h,
+        Array2::from_shape_vec((2, 2), vec![1.0, 1.0, 0.0, 2.0]).unwrap()
+    );
+
+    // weighted counts
+    let w = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let h = statistics::histogram2d(
+        &x,
+        &y,
+        Some((2, 2)),
+        Some(((0.0, 1.0), (0.0, 1.0))),
+        Some(&w),
+    )
+    .unwrap();
+    assert_eq!(h[[1, 1]], 7.0);
+
+    // check the bin index edge cases
+    assert_eq!(statistics::bin_index(1.0, (0.0, 1.0), 4), Some(3));
+    assert_eq!(statistics::bin_index(f64::NAN, (0.0, 1.0), 4), None);
+}
+
+#[test]
+fn statistics_reduce_axis() {
+    // create a (2, 3) array
+    let data = Array2::from_shape_vec((2, 3), vec![1_u16, 2, 3, 4, 5, 6])
+        .unwrap()
+        .into_dyn();
+
+    // reduce along each axis
+    let sum = statistics::reduce_axis(data.view(), 0, statistics::Reduction::Sum).unwrap();
+    let mean = statistics::reduce_axis(data.view(), 1, statistics::Reduction::Mean).unwrap();
+    let max = statistics::reduce_axis(data.view(), 1, statistics::Reduction::Max).unwrap();
+    let min = statistics::reduce_axis(data.view(), 0, statistics::Reduction::Min).unwrap();
+    let std = statistics::reduce_axis(data.view(), 0, statistics::Reduction::Std).unwrap();
+
+    // assert reductions and invalid axis
+    assert_eq!(sum.shape(), &[3]);
+    assert_eq!(sum.as_slice().unwrap(), &[5.0, 7.0, 9.0]);
+    assert_eq!(mean.as_slice().unwrap(), &[2.0, 5.0]);
+    assert_eq!(max.as_slice().unwrap(), &[3.0, 6.0]);
+    assert_eq!(min.as_slice().unwrap(), &[1.0, 2.0, 3.0]);
+    assert_eq!(std.as_slice().unwrap(), &[1.5, 1.5, 1.5]);
+    assert!(statistics::reduce_axis(data.view(), 2, statistics::Reduction::Sum).is_err());
+}
+
+#[test]
+fn statistics_estimate_snr() {
+    // create an image with a bright signal and a noisy background
+    let data = Array2::from_shape_vec(
+        (2, 4),
+        vec![9.0, 11.0, 9.0, 11.0, 100.0, 100.0, 100.0, 100.0],
+    )
+    .unwrap()
+    .into_dyn();
+    let bg = Array2::from_shape_vec(
+        (2, 4),
+        vec![true, true, true, true, false, false, false, false],
+    )
+    .unwrap()
+    .into_dyn();
+
+    // estimate with background and Poisson models
+    let snr_bg = statistics::estimate_snr(data.view(), None, Some(bg.view()), None).unwrap();
+    let sig = bg.mapv(|b| !b);
+    let snr_poisson =
+        statistics::estimate_snr(data.view(), Some(sig.view()), None, Some(4.0)).unwrap();
+
+    // assert SNR values and invalid parameters
+    assert_eq!(snr_bg, 90.0);
+    assert_eq!(snr_poisson, 5.0);
+    assert!(statistics::estimate_snr(data.view(), None, None, Some(0.0)).is_err());
+    let empty = Array2::from_elem((2, 4), false).into_dyn();
+    assert!(statistics::estimate_snr(data.view(), None, Some(empty.view()), None).is_err());
+}
+
+#[test]
+fn statistics_photon_transfer_curve() {
+    // simulate frames of a static scene with gain 2.0 and offset 100.0
+    let photons = Array3::from_shape_fn((200, 16, 16), |(_, r, c)| 5.0 + (r * 16 + c) as f64);
+    let counts = noise::poisson_3d(photons.view(), 1.0, Some(1), Some(0)).unwrap();
+    let frames = counts.mapv(|p| 2.0 * p + 100.0);
+
+    // simulate dark frames with 3.0 ADU read noise
+    let dark = noise::gaussian_2d(Array2::from_elem((200, 256), 100.0).view(), 3.0, Some(2))
+        .unwrap()
+        .into_shape_with_order((200, 16, 16))
+        .unwrap();
+
+    // estimate detector parameters with and without dark frames
+    let (gain, read_noise, offset) =
+        statistics::photon_transfer_curve(frames.view(), None, Some(dark.view())).unwrap();
+    let (gain_nd, read_noise_nd, offset_nd) =
+        statistics::photon_transfer_curve(frames.view(), None, None).unwrap();
+
+    // assert estimates and conversion to photons
+    assert!((gain - 2.0).abs() < 0.15);
+    assert!((read_noise - 1.5).abs() < 0.15);
+    assert!((offset - 100.0).abs() < 0.1);
+    assert_eq!(gain, gain_nd);
+    assert_eq!(read_noise_nd, 0.0);
+    assert!((offset_nd - 100.0).abs() < 10.0);
+    let p = statistics::adu_to_photons(frames.view().into_dyn(), 2.0, 100.0).unwrap();
+    assert_eq!(p[[0, 0, 1]], counts[[0, 0, 1]]);
+    assert!(statistics::photon_transfer_curve(frames.slice(s![..1, .., ..]), None, None).is_err());
+}
+
+#[test]
+fn statistics_focus_metrics() {
+    // create a sharp pattern and a blurred (3 x 3 box mean) copy
+    let sharp = Array2::from_shape_fn((32, 32), |(r, c)| {
+        ((r / 2 + c / 2) % 2) as f64 * 100.0 + 10.0
+    });
+    let blurred = Array2::from_shape_fn((32, 32), |(r, c)| {
+        let mut sum = 0.0;
+        let mut n = 0.0;
+        for dr in r.saturating_sub(1)..(r + 2).min(32) {
+            for dc in c.saturating_sub(1)..(c + 2).min(32) {
+                sum += sharp[[dr, dc]];
+                n += 1.0;
+            }
+        }
+        sum / n
+    });
+
+    // assert the sharp image scores higher with every metric
+    assert!(
+        statistics::brenner_gradient(sharp.view()) > statistics::brenner_gradient(blurred.view())
+    );
+    assert!(
+        statistics::tenengrad(sharp.view(), None) > statistics::tenengrad(blurred.view(), None)
+    );
+    assert!(
+        statistics::normalized_variance(sharp.view())
+            > statistics::normalized_variance(blurred.view())
+    );
+    assert!(
+        statistics::spectral_focus(sharp.view(), None)
+            > statistics::spectral_focus(blurred.view(), None)
+    );
+
+    // assert a constant image has no focus and the focus curve order
+    let flat = Array2::from_elem((8, 8), 5.0);
+    assert_eq!(statistics::brenner_gradient(flat.view()), 0.0);
+    assert_eq!(statistics::spectral_focus(flat.view(), None), 0.0);
+    let stack = ndarray::stack![ndarray::Axis(0), blurred, sharp];
+    let curve =
+        statistics::focus_curve(stack.view(), statistics::FocusMetric::Brenner, None).unwrap();
+    assert_eq!(curve.len(), 2);
+    assert!(curve[1] > curve[0]);
+    assert!(
+        statistics::focus_curve(stack.view(), statistics::FocusMetric::Brenner, Some(3)).is_err()
+    );
+}
+
+#[test]
+fn statistics_z_to_p() {
+    // create a 2-dimensional z-score map
+    let z = Array2::from_shape_vec((2, 2), vec![0.0, 1.959964, -1.959964, f64::NAN]).unwrap();
+
+    // convert to two-sided and one-sided p-values
+    let two = statistics::z_to_p(z.view().into_dyn(), None);
+    let upper = statistics::z_to_p(z.view().into_dyn(), Some(Tail::Upper));
+    let lower = statistics::z_to_p(z.view().into_dyn(), Some(Tail::Lower));
+
+    // assert the p-values
+    assert!((two[[0, 0]] - 1.0).abs() < 1e-12);
+    assert!((two[[0, 1]] - 0.05).abs() < 1e-6);
+    assert!((two[[1, 0]] - 0.05).abs() < 1e-6);
+    assert!((upper[[0, 1]] - 0.025).abs() < 1e-6);
+    assert!((lower[[0, 1]] - 0.975).abs() < 1e-6);
+    assert!(two[[1, 1]].is_nan());
+}
+
+#[test]
+fn statistics_p_adjust() {
+    // create a 2-dimensional p-value map with an untested (NaN) pixel
+    let p = Array2::from_shape_vec((2, 3), vec![0.04, 0.005, f64::NAN, 0.2, 0.01, 0.04]).unwrap();
+
+    // adjust the p-values
+    let bh = statistics::p_adjust(p.view().into_dyn(), None).unwrap();
+    let bonf = statistics::p_adjust(p.view().into_dyn(), Some(PAdjustMethod::Bonferroni)).unwrap();
+
+    // assert the adjusted values, 5 tests
+    let expected_bh = [0.05, 0.025, f64::NAN, 0.2, 0.025, 0.05];
+    let expected_bonf = [0.2, 0.025, f64::NAN, 1.0, 0.05, 0.2];
+    for ((a, b), (e_a, e_b)) in bh
+        .iter()
+        .zip(bonf.iter())
+        .zip(expected_bh.iter().zip(expected_bonf.iter()))
+    {
+        if e_a.is_nan() {
+            assert!(a.is_nan() && b.is_nan());
+        } else {
+            assert!((a - e_a).abs() < 1e-12);
+            assert!((b - e_b).abs() < 1e-12);
+        }
+    }
+    let invalid = Array2::from_elem((2, 2), 1.5);
+    assert!(statistics::p_adjust(invalid.view().into_dyn(), None).is_err());
+}
@@ -0,0 +1,32 @@
+use ndarray::Array2;
+
+use imgal::threshold;
+
+#[test]
+fn threshold_otsu() {
+    // create a bimodal image, a dim background and a bright square
+    let data = Array2::from_shape_fn((32, 32), |(r, c)| {
+        let base = if (8..24).contains(&r) && (8..24).contains(&c) {
+            200_u16
+        } else {
+            20
+        };
+        base + ((r * 7 + c * 13) % 10) as u16
+    });
+
+    // compute Otsu's threshold and mask
+    let t = threshold::otsu_value(data.view().into_dyn(), None).unwrap();
+    let mask = threshold::otsu_mask(data.view().into_dyn(), None).unwrap();
+
+    // assert the threshold separates the two classes
+    assert!(t > 29.0 && t < 200.0);
+    assert_eq!(mask.iter().filter(|&&m| m).count(), 256);
+    assert!(mask[[16, 16]]);
+    assert!(!mask[[0, 0]]);
+    let flat = Array2::<f64>::from_elem((4, 4), 3.0);
+    assert_eq!(
+        threshold::otsu_value(flat.view().into_dyn(), None).unwrap(),
+        3.0
+    );
+    assert!(threshold::otsu_value(data.view().into_dyn(), Some(1)).is_err());
+}
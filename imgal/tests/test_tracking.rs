@@ -0,0 +1,45 @@
+use ndarray::{Array2, array};
+
+use imgal::tracking::{self, LinkMethod};
+
+#[test]
+fn tracking_link_particles() {
+    // create two particles in 1D, where greedy linking is suboptimal
+    let f0 = array![[0.0], [3.0]];
+    let f1 = array![[1.9], [5.5]];
+    let detections = [f0.view(), f1.view()];
+
+    // link with LAP and nearest neighbor
+    let lap = tracking::link_particles(&detections, 3.0, None, None).unwrap();
+    let nn = tracking::link_particles(&detections, 3.0, None, Some(LinkMethod::NearestNeighbor))
+        .unwrap();
+
+    // assert LAP links both particles, greedy splits a track
+    assert_eq!(lap.shape(), &[4, 3]);
+    assert_eq!(lap.row(0).to_vec(), vec![0.0, 0.0, 0.0]);
+    assert_eq!(lap.row(1).to_vec(), vec![0.0, 1.0, 1.9]);
+    assert_eq!(lap.row(3).to_vec(), vec![1.0, 1.0, 5.5]);
+    let n_tracks_nn = nn.column(0).iter().fold(0.0_f64, |a, &b| a.max(b)) + 1.0;
+    assert_eq!(n_tracks_nn, 3.0);
+}
+
+#[test]
+fn tracking_link_particles_gap_closing() {
+    // create a 2D particle missing in frame 1
+    let f0 = array![[10.0, 10.0]];
+    let f1 = Array2::<f64>::zeros((0, 2));
+    let f2 = array![[11.0, 10.5]];
+    let detections = [f0.view(), f1.view(), f2.view()];
+
+    // link without and with gap closing
+    let no_gap = tracking::link_particles(&detections, 2.0, None, None).unwrap();
+    let gap = tracking::link_particles(&detections, 2.0, Some(1), None).unwrap();
+
+    // assert tracks and invalid parameters
+    assert_eq!(no_gap[[1, 0]], 1.0);
+    assert_eq!(gap[[1, 0]], 0.0);
+    assert_eq!(gap[[1, 1]], 2.0);
+    assert!(tracking::link_particles(&detections, 0.0, None, None).is_err());
+    let bad = array![[1.0]];
+    assert!(tracking::link_particles(&[f0.view(), bad.view()], 2.0, None, None).is_err());
+}
@@ -0,0 +1,38 @@
+use ndarray::{Array2, array};
+
+use imgal::transform::{self, Wavelet};
+
+#[test]
+fn transform_dwt2_haar() {
+    // create a 2 x 2 image
+    let data = array![[1.0, 3.0], [5.0, 7.0]];
+
+    // transform with one Haar level
+    let coefs = transform::dwt2(data.view(), Wavelet::Haar, None).unwrap();
+
+    // assert approximation and diagonal detail
+    assert!((coefs[[0, 0]] - 8.0).abs() < 1e-12);
+    assert!(coefs[[1, 1]].abs() < 1e-12);
+    assert!((coefs[[0, 1]].abs() - 2.0).abs() < 1e-12);
+    assert!((coefs[[1, 0]].abs() - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn transform_dwt2_reconstruction() {
+    // create a test image
+    let data = Array2::from_shape_fn((16, 24), |(r, c)| ((r * 31 + c * 17) % 11) as f64);
+
+    // assert perfect reconstruction and energy preservation for each wavelet
+    for wavelet in [Wavelet::Haar, Wavelet::Db2, Wavelet::Db4] {
+        let coefs = transform::dwt2(data.view(), wavelet, Some(3)).unwrap();
+        let recon = transform::idwt2(coefs.view(), wavelet, Some(3)).unwrap();
+        let energy_a: f64 = data.iter().map(|v| v * v).sum();
+        let energy_b: f64 = coefs.iter().map(|v| v * v).sum();
+        assert!((energy_a - energy_b).abs() < 1e-8);
+        data.iter()
+            .zip(recon.iter())
+            .for_each(|(a, b)| assert!((a - b).abs() < 1e-9));
+    }
+    assert!(transform::dwt2(data.view(), Wavelet::Haar, Some(4)).is_err());
+    assert!(transform::dwt2(data.view(), Wavelet::Haar, Some(0)).is_err());
+}
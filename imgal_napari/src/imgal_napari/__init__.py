@@ -0,0 +1,20 @@
+"""napari widgets for imgal phasor, threshold and colocalization analysis.
+
+The widgets are a thin layer over the imgal Python bindings: every computation
+runs in Rust, and long-running functions release the GIL so the napari event
+loop stays responsive.
+"""
+
+from ._widgets import (
+    phasor_cursor_widget,
+    phasor_widget,
+    saca_widget,
+    threshold_widget,
+)
+
+__all__ = [
+    "phasor_cursor_widget",
+    "phasor_widget",
+    "saca_widget",
+    "threshold_widget",
+]
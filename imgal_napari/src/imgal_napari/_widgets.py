@@ -0,0 +1,229 @@
+"""napari widgets backed by the imgal Rust functions."""
+
+from typing import TYPE_CHECKING
+
+import imgal
+import numpy as np
+from magicgui import magic_factory
+from napari.qt.threading import FunctionWorker, thread_worker
+
+if TYPE_CHECKING:
+    import napari
+
+# phasor plot coordinates are scaled to pixels, the universal semicircle spans
+# 0 to PLOT_SCALE along the G axis
+PLOT_SCALE = 500.0
+PLOT_MARGIN = 50.0
+
+
+@magic_factory(
+    period={"min": 0.0, "max": 1e6, "step": 0.1},
+    harmonic={"min": 1},
+    axis={"min": 0, "max": 2},
+    min_photons={"min": 0.0, "max": 1e12},
+)
+def phasor_widget(
+    image: "napari.layers.Image",
+    period: float = 12.5,
+    harmonic: int = 1,
+    axis: int = 2,
+    min_photons: float = 0.0,
+) -> "list[napari.types.LayerDataTuple]":
+    """Compute the phasor image and plot of a 3-dimensional decay image.
+
+    The real (G) and imaginary (S) images are added as image layers and every
+    pixel's (G, S) coordinate is added as a points layer with the universal
+    semicircle, to the right of the image. Draw cursors on the phasor plot
+    with a shapes layer and gate the image with the "Phasor cursor" widget.
+
+    :param image: The 3-dimensional decay image layer.
+    :param period: The period (i.e. time interval).
+    :param harmonic: The harmonic value.
+    :param axis: The decay or lifetime axis.
+    :param min_photons: The minimum total photon count of a pixel's decay,
+        pixels below are excluded from the phasor plot.
+    :return: The G and S image layers, the phasor plot points layer and the
+        universal semicircle shapes layer.
+    """
+    data = np.asarray(image.data)
+    gs = imgal.phasor.time_domain.image(
+        data,
+        period,
+        harmonic=harmonic,
+        axis=axis,
+        min_photons=min_photons if min_photons > 0.0 else None,
+    )
+    g = gs[..., 0]
+    s = gs[..., 1]
+
+    # plot the phasor coordinates to the right of the image, S increases upwards
+    translate = (PLOT_SCALE, g.shape[1] + PLOT_MARGIN)
+    valid = np.isfinite(g) & np.isfinite(s)
+    points = np.column_stack([-s[valid], g[valid]])
+    sc_g, sc_s = imgal.phasor.plot.universal_semicircle(200)
+    semicircle = np.column_stack([-np.asarray(sc_s), np.asarray(sc_g)])
+    plot_kwargs = {"scale": (PLOT_SCALE, PLOT_SCALE), "translate": translate}
+
+    return [
+        (g, {"name": f"{image.name} G"}, "image"),
+        (s, {"name": f"{image.name} S"}, "image"),
+        (
+            points,
+            {
+                "name": f"{image.name} phasor",
+                "size": 0.004,
+                "border_width": 0.0,
+                "metadata": {"imgal_phasor": gs},
+                **plot_kwargs,
+            },
+            "points",
+        ),
+        (
+            [semicircle],
+            {
+                "name": "universal semicircle",
+                "shape_type": "path",
+                "edge_width": 0.004,
+                "edge_color": "white",
+                **plot_kwargs,
+            },
+            "shapes",
+        ),
+    ]
+
+
+@magic_factory
+def phasor_cursor_widget(
+    phasor: "napari.layers.Points",
+    cursors: "napari.layers.Shapes",
+) -> "napari.types.LabelsData":
+    """Gate a phasor image with cursors drawn on its phasor plot.
+
+    Each polygon, rectangle or ellipse of the "cursors" shapes layer selects
+    the phasor coordinates inside it, which are mapped back to the image with
+    "imgal.phasor.plot.map_mask". Pixels of the n-th cursor are labeled n.
+
+    :param phasor: The phasor plot points layer of the "Phasor" widget.
+    :param cursors: The shapes layer with the cursors drawn on the phasor plot.
+    :return: The cursor labels image.
+    """
+    gs = phasor.metadata["imgal_phasor"]
+    g = gs[..., 0].ravel()
+    s = gs[..., 1].ravel()
+    valid = np.isfinite(g) & np.isfinite(s)
+    labels = np.zeros(gs.shape[:2], dtype=np.uint16)
+    for i, (vertices, shape_type) in enumerate(
+        zip(cursors.data, cursors.shape_type), start=1
+    ):
+        # convert the cursor vertices from world to (G, S) coordinates
+        world = np.asarray(vertices)[:, -2:] * np.asarray(cursors.scale[-2:])
+        world += np.asarray(cursors.translate[-2:])
+        plot = (world - np.asarray(phasor.translate[-2:])) / PLOT_SCALE
+        cursor_g = plot[:, 1]
+        cursor_s = -plot[:, 0]
+        if shape_type == "ellipse":
+            inside = _inside_ellipse(g, s, cursor_g, cursor_s)
+        else:
+            inside = _inside_polygon(g, s, cursor_g, cursor_s)
+        inside &= valid
+        if not inside.any():
+            continue
+        mask = imgal.phasor.plot.map_mask(gs, list(g[inside]), list(s[inside]))
+        labels[mask & (labels == 0)] = i
+
+    return labels
+
+
+@magic_factory(threshold={"min": -1e12, "max": 1e12})
+def threshold_widget(
+    image: "napari.layers.Image",
+    threshold: float = 0.0,
+) -> "napari.types.LabelsData":
+    """Threshold an image into a binary mask.
+
+    :param image: The image layer.
+    :param threshold: The threshold value, pixels greater than the threshold
+        are set to 1.
+    :return: The threshold mask.
+    """
+    mask = imgal.threshold.manual_mask(np.asarray(image.data), threshold)
+
+    return mask.astype(np.uint8)
+
+
+@magic_factory(
+    threshold_a={"min": -1e12, "max": 1e12},
+    threshold_b={"min": -1e12, "max": 1e12},
+    alpha={"min": 0.0, "max": 1.0, "step": 0.01},
+)
+def saca_widget(
+    image_a: "napari.layers.Image",
+    image_b: "napari.layers.Image",
+    threshold_a: float = 0.0,
+    threshold_b: float = 0.0,
+    costes: bool = False,
+    alpha: float = 0.05,
+) -> "FunctionWorker[list[napari.types.LayerDataTuple]]":
+    """Compute SACA colocalization strength of two 2 or 3-dimensional images.
+
+    SACA runs in a background thread, the z-score image and the Bonferroni
+    corrected significance mask are added when it finishes.
+
+    :param image_a: The image layer of channel "A".
+    :param image_b: The image layer of channel "B", with the same shape and
+        dtype as "image_a".
+    :param threshold_a: The pixel intensity threshold of channel "A".
+    :param threshold_b: The pixel intensity threshold of channel "B".
+    :param costes: If true, the thresholds are estimated with Costes' method
+        instead.
+    :param alpha: The significance level of the significance mask.
+    :return: A worker adding the z-score and significance mask layers.
+    """
+    data_a = np.asarray(image_a.data)
+    data_b = np.asarray(image_b.data)
+
+    @thread_worker
+    def run() -> "list[napari.types.LayerDataTuple]":
+        t_a, t_b = threshold_a, threshold_b
+        if costes:
+            t_a, t_b = imgal.colocalization.costes_threshold(data_a, data_b)
+        if data_a.ndim == 2:
+            z = imgal.colocalization.saca_2d(data_a, data_b, t_a, t_b)
+        else:
+            z = imgal.colocalization.saca_3d(data_a, data_b, t_a, t_b)
+        mask = imgal.colocalization.saca_significance_mask(z, alpha)
+        return [
+            (z, {"name": "SACA z-score", "colormap": "bwr"}, "image"),
+            (mask.astype(np.uint8), {"name": "SACA significance"}, "labels"),
+        ]
+
+    return run()
+
+
+def _inside_polygon(
+    g: np.ndarray, s: np.ndarray, poly_g: np.ndarray, poly_s: np.ndarray
+) -> np.ndarray:
+    """Even-odd rule test of points inside a polygon."""
+    inside = np.zeros(g.shape, dtype=bool)
+    n = len(poly_g)
+    for k in range(n):
+        g0, s0 = poly_g[k], poly_s[k]
+        g1, s1 = poly_g[(k + 1) % n], poly_s[(k + 1) % n]
+        crosses = (s0 > s) != (s1 > s)
+        with np.errstate(divide="ignore", invalid="ignore"):
+            g_cross = g0 + (s - s0) * (g1 - g0) / (s1 - s0)
+        inside ^= crosses & (g < g_cross)
+
+    return inside
+
+
+def _inside_ellipse(
+    g: np.ndarray, s: np.ndarray, box_g: np.ndarray, box_s: np.ndarray
+) -> np.ndarray:
+    """Test of points inside an axis aligned ellipse from its bounding box."""
+    center_g = (box_g.min() + box_g.max()) / 2.0
+    center_s = (box_s.min() + box_s.max()) / 2.0
+    radius_g = max((box_g.max() - box_g.min()) / 2.0, np.finfo(float).eps)
+    radius_s = max((box_s.max() - box_s.min()) / 2.0, np.finfo(float).eps)
+
+    return ((g - center_g) / radius_g) ** 2 + ((s - center_s) / radius_s) ** 2 <= 1.0
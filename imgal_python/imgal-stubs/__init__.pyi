@@ -0,0 +1,24 @@
+# This file is generated by stub_gen, do not edit.
+from . import colocalization as colocalization
+from . import data as data
+from . import distribution as distribution
+from . import feature as feature
+from . import filter as filter
+from . import fitting as fitting
+from . import image as image
+from . import integration as integration
+from . import kernel as kernel
+from . import localization as localization
+from . import measure as measure
+from . import motion as motion
+from . import ops as ops
+from . import parameter as parameter
+from . import phasor as phasor
+from . import pipeline as pipeline
+from . import registration as registration
+from . import segmentation as segmentation
+from . import simulation as simulation
+from . import statistics as statistics
+from . import threshold as threshold
+from . import tracking as tracking
+from . import transform as transform
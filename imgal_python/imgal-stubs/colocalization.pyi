@@ -0,0 +1,317 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def bootstrap_ci(data_a: npt.ArrayLike, data_b: npt.ArrayLike, metric: str | None = None, threshold_a: float | None = None, threshold_b: float | None = None, n_resamples: int | None = None, confidence: float | None = None, block_size: int | None = None, seed: int | None = None) -> tuple[float, float, float]:
+    r"""
+    Compute a bootstrap confidence interval for a colocalization metric.
+
+    This function estimates a percentile bootstrap confidence interval for a
+    colocalization metric between two images. Each bootstrap replicate
+    resamples the pixels of the images with replacement and recomputes the
+    metric. Setting "block_size" resamples non-overlapping square (or cube)
+    blocks of pixels instead, preserving spatial correlation within each block.
+
+    :param data_a: The n-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: The n-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param metric: The colocalization metric, "pearson", "manders_m1",
+        "manders_m2" or "icq", default = "pearson".
+    :param threshold_a: Pixel intensity threshold value for image "A" used by
+        the Manders' metrics, default = 0.0.
+    :param threshold_b: Pixel intensity threshold value for image "B" used by
+        the Manders' metrics, default = 0.0.
+    :param n_resamples: The number of bootstrap replicates, default = 1000.
+    :param confidence: The confidence level, between 0.0 and 1.0,
+        default = 0.95.
+    :param block_size: The side length of the resampled blocks in pixels,
+        default = 1 (i.e. pixel resampling).
+    :param seed: Pseudorandom number generator seed for reproducible intervals.
+    :return: The metric of the input images and the lower and upper bounds of
+        the confidence interval.
+    """
+    ...
+
+def costes_threshold(data_a: npt.ArrayLike, data_b: npt.ArrayLike, mask: npt.NDArray[np.bool_] | None = None) -> tuple[float, float]:
+    r"""
+    Compute the Costes automatic thresholds of two images.
+
+    This function computes the Costes automatic thresholds, separating
+    correlated signal from uncorrelated background, for use with "manders". The
+    threshold of image "A" is lowered along the orthogonal regression line of
+    the pixel intensities until the Pearson correlation coefficient of the
+    pixels below either threshold drops to 0.0.
+
+    :param data_a: The n-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: The n-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param mask: A boolean mask with the same shape as the images, only pixels
+        inside the mask are used. If "None", all pixels are used.
+    :return: The thresholds of image "A" and "B".
+    """
+    ...
+
+def icq(data_a: npt.ArrayLike, data_b: npt.ArrayLike, mask: npt.NDArray[np.bool_] | None = None) -> float:
+    r"""
+    Compute the intensity correlation quotient (ICQ) between two images.
+
+    This function computes the intensity correlation quotient (ICQ) between two
+    n-dimensional images, the fraction of pixels with a positive product of the
+    differences from the mean minus 0.5.
+
+    :param data_a: The n-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: The n-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param mask: A boolean mask with the same shape as the images, only pixels
+        inside the mask are used. If "None", all pixels are used.
+    :return: The intensity correlation quotient, ranging between -0.5
+        (segregated staining), 0.0 (random staining) and 0.5 (dependent
+        staining).
+    """
+    ...
+
+def manders(data_a: npt.ArrayLike, data_b: npt.ArrayLike, threshold_a: float | None = None, threshold_b: float | None = None, mask: npt.NDArray[np.bool_] | None = None) -> tuple[float, float]:
+    r"""
+    Compute the Manders' colocalization coefficients between two images.
+
+    This function computes the thresholded Manders' colocalization coefficients
+    (M1 and M2) between two n-dimensional images. M1 is the fraction of image
+    "A"'s intensity in pixels where image "B" is above its threshold, and M2 is
+    the fraction of image "B"'s intensity in pixels where image "A" is above its
+    threshold.
+
+    :param data_a: The n-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: The n-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param threshold_a: Pixel intensity threshold value for image "A",
+        default = 0.
+    :param threshold_b: Pixel intensity threshold value for image "B",
+        default = 0.
+    :param mask: A boolean mask with the same shape as the images, only pixels
+        inside the mask are used. If "None", all pixels are used.
+    :return: The Manders' M1 and M2 coefficients.
+    """
+    ...
+
+def pairwise_matrix(data: npt.ArrayLike, metric: str | None = None, thresholds: list[float] | None = None, return_maps: bool | None = None) -> tuple[npt.NDArray[np.float64], list[npt.NDArray[np.float64]] | None]:
+    r"""
+    Compute a colocalization metric for every channel pair of a multichannel
+    image.
+
+    This function computes the chosen colocalization metric between every pair
+    of channels in a multichannel image and returns a (c, c) matrix of the
+    results. For the Manders' metric entry (i, j) is the fraction of channel
+    "i"'s intensity colocalized with channel "j". Optionally the per-pair maps
+    are returned for each pair (i, j) with i < j, ordered row by row.
+
+    :param data: The 3-dimensional (c, row, col) or 4-dimensional
+        (c, pln, row, col) multichannel image.
+    :param metric: The colocalization metric, "pearson", "manders" or "saca",
+        default = "pearson".
+    :param thresholds: The per-channel pixel intensity thresholds used by the
+        Manders' and SACA metrics, default = 0 for all channels.
+    :param return_maps: If true, the per-pair maps are returned,
+        default = False.
+    :return: The (c, c) pairwise metric matrix and the optional list of per-pair
+        maps.
+    """
+    ...
+
+def pearson(data_a: npt.ArrayLike, data_b: npt.ArrayLike, mask: npt.NDArray[np.bool_] | None = None) -> float:
+    r"""
+    Compute the Pearson correlation coefficient between two images.
+
+    This function computes the Pearson correlation coefficient (PCC) between
+    the pixel intensities of two n-dimensional images.
+
+    :param data_a: The n-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: The n-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param mask: A boolean mask with the same shape as the images, only pixels
+        inside the mask are used. If "None", all pixels are used.
+    :return: The Pearson correlation coefficient, ranging between -1.0
+        (anti-correlation), 0.0 (no correlation) and 1.0 (correlation).
+    """
+    ...
+
+def saca_2d(data_a: npt.ArrayLike, data_b: npt.ArrayLike, threshold_a: float, threshold_b: float, tu: int | None = None, tl: int | None = None, step_size: float | None = None, lambda_multiplier: float | None = None, z_scale: float | None = None, kernel: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute colocalization strength using 2-dimensional Spatially Adaptive
+    Colocalization Analysis (SACA)
+
+    This function computes a pixel-wise _z-score_ indicating colocalization and
+    anti-colocalization strength on 2-dimensional input images using the
+    Spatially Adaptive Colocalization Analysis (SACA) framework. Per pixel SACA
+    utilizes a propagation and separation strategy to adaptively expand a
+    weighted circular kernel that defines the pixel of consideration's
+    neighborhood. The pixels within the neighborhood are assigned weights based
+    on their distance from the center pixel (decreasing with distance), ranked
+    and their colocalization coefficient computed using Kendall's Tau-b rank
+    correlation.
+
+    :param data_a: The 2-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: Ihe 2-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        circular neighborhood.
+    :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        circular neighborhood.
+    :param tu: The total number of iterations (i.e. scales), default = 15.
+    :param tl: The iteration after which the stopping rule is applied, must be
+        less than "tu", default = 8.
+    :param step_size: The kernel radius growth factor per iteration, must be
+        greater than 1.0, default = 1.15.
+    :param lambda_multiplier: The stopping rule threshold multiplier, must be
+        greater than 0.0, default = 1.0.
+    :param z_scale: The z-score scaling, must be greater than 0.0,
+        default = 1.5.
+    :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+        default = "weighted".
+    :return: The pixel-wise _z-score_ indicating colocalization or
+        anti-colocalization by its sign and the degree or strength of the
+        relationship through its absolute values.
+    """
+    ...
+
+def saca_2d_intermediate(data_a: npt.ArrayLike, data_b: npt.ArrayLike, threshold_a: float, threshold_b: float, tu: int | None = None, tl: int | None = None, step_size: float | None = None, lambda_multiplier: float | None = None, z_scale: float | None = None, kernel: str | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute 2-dimensional SACA colocalization strength and record the
+    intermediate results of each iteration.
+
+    This function computes the pixel-wise z-score of "saca_2d" and also records
+    a snapshot of the per-pixel Kendall's Tau-b and square root of the effective
+    sample size ("√n") after each iteration (i.e. scale). The snapshots show how
+    the adaptive neighborhood of each pixel converges.
+
+    :param data_a: The 2-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: Ihe 2-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        neighborhood.
+    :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        neighborhood.
+    :param tu: The total number of iterations (i.e. scales), default = 15.
+    :param tl: The iteration after which the stopping rule is applied, must be
+        less than "tu", default = 8.
+    :param step_size: The kernel radius growth factor per iteration, must be
+        greater than 1.0, default = 1.15.
+    :param lambda_multiplier: The stopping rule threshold multiplier, must be
+        greater than 0.0, default = 1.0.
+    :param z_scale: The z-score scaling, must be greater than 0.0,
+        default = 1.5.
+    :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+        default = "weighted".
+    :return: The pixel-wise z-score, the per-iteration Kendall's Tau-b
+        snapshots and the per-iteration "√n" snapshots. The snapshots are
+        stacked along the first axis.
+    """
+    ...
+
+def saca_3d(data_a: npt.ArrayLike, data_b: npt.ArrayLike, threshold_a: float, threshold_b: float, tu: int | None = None, tl: int | None = None, step_size: float | None = None, lambda_multiplier: float | None = None, z_scale: float | None = None, kernel: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute colocalization strength using 3-dimensional Spatially Adaptive
+    Colocalization Analysis (SACA)
+
+    This function computes a pixel-wise _z-score_ indicating colocalization and
+    anti-colocalization strength on 3-dimensional input images using the
+    Spatially Adaptive Colocalization Analysis (SACA) framework. Per pixel SACA
+    utilizes a propagation and separation strategy to adaptively expand a
+    weighted spherical kernel that defines the pixel of consideration's
+    neighborhood. The pixels within the neighborhood are assigned weights based
+    on their distance from the center pixel (decreasing with distance), ranked
+    and their colocalization coefficient computed using Kendall's Tau-b rank
+    correlation.
+
+    :param data_a: The 3-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: Ihe 3-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        circular neighborhood.
+    :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        circular neighborhood.
+    :param tu: The total number of iterations (i.e. scales), default = 15.
+    :param tl: The iteration after which the stopping rule is applied, must be
+        less than "tu", default = 8.
+    :param step_size: The kernel radius growth factor per iteration, must be
+        greater than 1.0, default = 1.15.
+    :param lambda_multiplier: The stopping rule threshold multiplier, must be
+        greater than 0.0, default = 1.0.
+    :param z_scale: The z-score scaling, must be greater than 0.0,
+        default = 1.5.
+    :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+        default = "weighted".
+    :return: The pixel-wise _z-score_ indicating colocalization or
+        anti-colocalization by its sign and the degree or strength of the
+        relationship through its absolute values.
+    """
+    ...
+
+def saca_3d_intermediate(data_a: npt.ArrayLike, data_b: npt.ArrayLike, threshold_a: float, threshold_b: float, tu: int | None = None, tl: int | None = None, step_size: float | None = None, lambda_multiplier: float | None = None, z_scale: float | None = None, kernel: str | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute 3-dimensional SACA colocalization strength and record the
+    intermediate results of each iteration.
+
+    This function computes the pixel-wise z-score of "saca_3d" and also records
+    a snapshot of the per-pixel Kendall's Tau-b and square root of the effective
+    sample size ("√n") after each iteration (i.e. scale). The snapshots show how
+    the adaptive neighborhood of each pixel converges.
+
+    :param data_a: The 3-dimensional input image, "A". Image "A" must have the
+        same shape as image "B".
+    :param data_b: Ihe 3-dimensional input image, "B". Image "B" must have the
+        same shape as image "A".
+    :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        neighborhood.
+    :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+        below this value are given a weight of 0.0 if the pixel is in the
+        neighborhood.
+    :param tu: The total number of iterations (i.e. scales), default = 15.
+    :param tl: The iteration after which the stopping rule is applied, must be
+        less than "tu", default = 8.
+    :param step_size: The kernel radius growth factor per iteration, must be
+        greater than 1.0, default = 1.15.
+    :param lambda_multiplier: The stopping rule threshold multiplier, must be
+        greater than 0.0, default = 1.0.
+    :param z_scale: The z-score scaling, must be greater than 0.0,
+        default = 1.5.
+    :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+        default = "weighted".
+    :return: The pixel-wise z-score, the per-iteration Kendall's Tau-b
+        snapshots and the per-iteration "√n" snapshots. The snapshots are
+        stacked along the first axis.
+    """
+    ...
+
+def saca_significance_mask(data: npt.ArrayLike, alpha: float | None = None) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a significant pixel mask from a pixel-wise z-score array.
+
+    This function applies Bonferroni correction to adjust for multiple
+    comparisons and creates a boolean array representing the significant pixel
+    mask.
+
+    :param data: The pixel-wise z-score indicating colocalization or
+        anti-colocalization strength.
+    :param alpha: The significance level representing the maximum type I error
+        (i.e. positive error) allowed (default = 0.05).
+    :return: The significant pixel mask where "true" pixels represent
+        significant z-score values.
+    """
+    ...
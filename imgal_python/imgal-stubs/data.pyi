@@ -0,0 +1,46 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def beads() -> npt.NDArray[np.uint16]:
+    r"""
+    Create a synthetic fluorescent beads image.
+
+    This function generates a 2-dimensional (row, col) 128 x 128 image of 32
+    diffraction limited beads, modeled as Gaussian spots with a standard
+    deviation of 1.5 pixels, on a dim background with Poisson noise applied.
+    The image is identical across calls.
+
+    :return: The synthetic beads image.
+    """
+    ...
+
+def cells() -> npt.NDArray[np.uint16]:
+    r"""
+    Create a synthetic fluorescent cells image.
+
+    This function generates a 2-dimensional (row, col) 256 x 256 image of 24
+    elliptical cells with a bright center on a dim background, with Poisson
+    noise applied. Cells may touch or overlap. The image is identical across
+    calls.
+
+    :return: The synthetic cells image.
+    """
+    ...
+
+def flim_cube() -> npt.NDArray[np.float64]:
+    r"""
+    Create a synthetic fluorescence lifetime imaging (FLIM) decay image.
+
+    This function generates a 3-dimensional (row, col, time) 64 x 64 x 256
+    decay image over a 12.5 ns period with Poisson noise applied. The left half
+    of the image has a monoexponential lifetime of 1.0 ns and the right half a
+    monoexponential lifetime of 3.0 ns, with 2000 and 4000 total photon counts
+    per pixel in the top and bottom halves respectively. The decay axis is 2.
+    The image is identical across calls.
+
+    :return: The synthetic FLIM decay image.
+    """
+    ...
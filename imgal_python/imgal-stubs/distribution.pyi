@@ -0,0 +1,75 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def f_cdf(x: float, d1: float, d2: float) -> float:
+    r"""
+    Compute the cumulative distribution function of the F-distribution.
+
+    This function computes the probability that an F-distributed random
+    variable with "d1" and "d2" degrees of freedom is less than or equal to
+    "x", using the regularized incomplete beta function. The upper tail,
+    1 - F(x; d1, d2), is the p-value of an F-test.
+
+    :param x: The value of the F statistic.
+    :param d1: The numerator degrees of freedom.
+    :param d2: The denominator degrees of freedom.
+    :return: The cumulative probability between 0.0 and 1.0.
+    """
+    ...
+
+def gaussian(sigma: float, bins: int, range: float, center: float) -> npt.NDArray[np.float64]:
+    r"""
+    Generate a normalized Gaussian distribution over a specified range.
+
+    This function creates a discrete Gaussian distribution by sampling the continuous
+    Gaussian probability density function at evenly spaced points across a given range.
+    The resulting distribution is normalized so that all values sum to 1.0.
+    The function implements the Gaussian probability density function:
+
+    f(x) = exp(-((x - μ)² / (2σ²)))
+
+    where:
+    - `x` is the position along the range.
+    - `μ` is the center (mean).
+    - `σ` is the sigma (standard deviation).
+
+
+    :param sigma: The standard deviation of the Gaussian distribution (i.e. the width).
+    :param bins: The number of discrete points to sample the Gaussian distribution.
+    :param range: The total width of the sampling range.
+    :param center: The mean (center) of the Gaussian distribution (i.e. the peak).
+    :return: The normalized Gaussian distribution.
+    """
+    ...
+
+def inverse_normal_cdf(p: float) -> float:
+    r"""
+    Compute quantile of a probability using the inverse normal cumulative
+    distribution function.
+
+    The function calculates the quantile (z-score) corresponding to a given
+    cumulative probability "p" using Peter Acklam's rational approximation
+    algorithm. Acklam's algorithm has a relative error of less than 1.15e-9.
+
+    :param p: The probability value in the range of 0.0 to 1.0.
+    :reeturn: The quantile (z-score) corresponding to the given probability
+       "p".
+    """
+    ...
+
+def normal_cdf(z: float) -> float:
+    r"""
+    Compute the cumulative distribution function of the standard normal
+    distribution.
+
+    This function computes the probability that a standard normal random
+    variable is less than or equal to "z", Φ(z) = erfc(-z / √2) / 2, with a
+    fractional error of less than 1.2e-7.
+
+    :param z: The quantile (z-score).
+    :return: The cumulative probability between 0.0 and 1.0.
+    """
+    ...
@@ -0,0 +1,72 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def local_binary_pattern(data: npt.ArrayLike, radius: float | None = None, points: int | None = None, uniform: bool | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Compute the local binary pattern (LBP) codes of a 2-dimensional image.
+
+    This function compares each pixel with "points" neighbors sampled evenly on
+    a circle of the given radius. With "uniform" set, the rotation invariant
+    uniform mapping is used, giving "points + 2" codes.
+
+    :param data: The input 2-dimensional image.
+    :param radius: The radius of the sampling circle in pixels, default = 1.0.
+    :param points: The number of circular sampling points, default = 8.
+    :param uniform: If "True", use the rotation invariant uniform mapping,
+        default = True.
+    :return: The LBP code of each pixel.
+    """
+    ...
+
+def local_binary_pattern_histogram(codes: npt.NDArray[np.uintp], n_bins: int, labels: npt.NDArray[np.uintp] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute normalized local binary pattern histograms per labeled region.
+
+    :param codes: The LBP codes.
+    :param n_bins: The number of histogram bins, "points + 2" for uniform codes
+        or "2^points" otherwise.
+    :param labels: The region label image with the same shape as "codes", label
+        0 is ignored. If "None", a single histogram of the whole image is
+        computed.
+    :return: The histograms with a shape of (max_label + 1, n_bins), where row
+        "i" is the histogram of label "i".
+    """
+    ...
+
+def structure_tensor_2d(data: npt.ArrayLike, sigma: float | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the local orientation, coherence and energy of a 2-dimensional
+    image with the structure tensor.
+
+    This function computes the Gaussian weighted structure tensor of each pixel.
+    The orientation is the direction of the local structure in radians between
+    -pi/2 and pi/2, measured from the column axis towards the row axis. The
+    coherence ranges from 0.0 (isotropic) to 1.0 (perfectly oriented) and the
+    energy is the tensor trace.
+
+    :param data: The input 2-dimensional image.
+    :param sigma: The standard deviation of the Gaussian integration window,
+        default = 2.0.
+    :return: The orientation, coherence and energy maps.
+    """
+    ...
+
+def structure_tensor_3d(data: npt.ArrayLike, sigma: float | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the local orientation, coherence and energy of a 3-dimensional
+    image with the structure tensor.
+
+    This function computes the 3 x 3 structure tensor of each voxel. The local
+    orientation is the unit eigenvector of the smallest eigenvalue, the
+    direction of least intensity change (e.g. along a fiber).
+
+    :param data: The input 3-dimensional image.
+    :param sigma: The standard deviation of the Gaussian integration window,
+        default = 2.0.
+    :return: The orientation vectors with a shape of (pln, row, col, 3), and the
+        coherence and energy maps.
+    """
+    ...
@@ -0,0 +1,281 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def delta_f_over_f(data: npt.ArrayLike, baseline: str | None = None, start: int | None = None, end: int | None = None, percentile: float | None = None, window: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the relative fluorescence change (ΔF/F₀) of an n-dimensional
+    stack.
+
+    This function computes (F(t) - F₀) / F₀ of the time trace of each pixel of
+    an n-dimensional stack along "axis", where the baseline F₀ is either the
+    mean of the frames in "start..end" or a running percentile of the "window"
+    frames centered on each frame. Frames with a zero baseline are NaN.
+
+    :param data: The input n-dimensional stack, with the background
+        subtracted.
+    :param baseline: The baseline estimate, "frames" or "percentile",
+        default = "frames".
+    :param start: The first baseline frame of the "frames" baseline,
+        default = 0.
+    :param end: The end (exclusive) baseline frame of the "frames" baseline,
+        default = the number of frames.
+    :param percentile: The percentile (0.0 to 100.0) of the "percentile"
+        baseline, default = 10.0.
+    :param window: The window in frames of the "percentile" baseline,
+        default = 31.
+    :param axis: The time axis, default = 0.
+    :return: The ΔF/F₀ stack, with the shape of "data".
+    """
+    ...
+
+def destripe(data: npt.ArrayLike, direction: str | None = None, method: str | None = None, window: int | None = None, width: int | None = None, cutoff: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Remove row and/or column fixed-pattern noise from a 2-dimensional image.
+
+    This function removes stripe artifacts caused by per-line offsets, such as
+    the column noise of sCMOS sensors or the line noise of resonant scanners.
+    The "median" method subtracts the deviation of each line median from a
+    moving median of the line medians. The "fft" method removes the Fourier
+    coefficients of line offsets above a cutoff frequency.
+
+    :param data: The input 2-dimensional image.
+    :param direction: The stripe orientation, "horizontal", "vertical" or
+        "both", default = "both".
+    :param method: The stripe removal method, "median" or "fft", default =
+        "median".
+    :param window: The moving median window in lines of the "median" method,
+        default = 31.
+    :param width: The notch width in frequency bins of the "fft" method,
+        default = 1.
+    :param cutoff: The highest preserved frequency bin along the stripe profile
+        of the "fft" method, default = 2.
+    :return: The destriped image.
+    """
+    ...
+
+def divergence(grad_row: npt.NDArray[np.float64], grad_col: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the divergence of a 2-dimensional vector field.
+
+    This function computes the backward differences of a vector field (e.g. a
+    modified image gradient) along the rows and columns. The field is 0.0
+    before the first and across the last row and column (i.e. Neumann
+    boundaries), matching "gradient".
+
+    :param grad_row: The row component of the vector field.
+    :param grad_col: The column component of the vector field.
+    :return: The divergence image.
+    """
+    ...
+
+def fft_convolve_1d(a: list[float], b: list[float]) -> npt.NDArray[np.float64]:
+    r"""
+    Convolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
+
+    Compute the convolution of two discrete signals ("a" and "b") by transforming
+    them to the frequency domain, multiplying them, and then transforming the
+    result back into a signal. This function uses "same-length" trimming with the
+    first parameter "a". This means that the returned convolution's array length
+    will have the same length as "a".
+
+    :param a: The first input signal to FFT convolve. Returned convolution arrays
+        will be "same-length" trimmed to "a"'s length.
+    :param b: The second input signal to FFT convolve.
+    :return: The FFT convolved result of the same length as input signal "a".
+    """
+    ...
+
+def fft_deconvolve_1d(a: list[float], b: list[float], epsilon: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Deconvolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
+
+    Compute the deconvolution of two discrete signals (`a` and `b`) by transforming
+    them to the frequency domain, dividing them, and then transforming the result
+    back into a signal. This function uses "same-length" triming with the first
+    parameter "a". This means that the returned deconvolution's array length will
+    have the same length as "a".
+
+    :param a: The first input signal to FFT deconvolve. Returned deconvolution arrays
+        will be "same-length" trimmed to "a"'s length.
+    :param b: The second input signal to deconvolve.
+    :param epsilon: An epsilon value to prevent division by zero errors (default =
+        1e-8).
+    :return: The FFT deconvolved result of the same length as input signal "a".
+    """
+    ...
+
+def gaussian_2d(data: npt.ArrayLike, sigma: float, out: npt.NDArray[np.float64] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Smooth a 2-dimensional image with a Gaussian filter.
+
+    This function convolves a 2-dimensional image with a normalized Gaussian
+    kernel, truncated at 4 sigma. The kernel is separable, so rows and columns
+    are filtered in two 1-dimensional passes. Pixels outside of the image are
+    replaced by the nearest edge pixel.
+
+    :param data: The input 2-dimensional image.
+    :param sigma: The standard deviation of the Gaussian kernel in pixels. If
+        "sigma" is 0.0, the image is returned unfiltered.
+    :param out: An optional float64 output array of the same shape as "data" to
+        write the smoothed image into, default = None.
+    :return: The smoothed image. If "out" is given, "out" is returned.
+    """
+    ...
+
+def gradient(data: npt.ArrayLike) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the gradient of a 2-dimensional image.
+
+    This function computes the forward differences of an image along the rows
+    and columns. The differences across the last row and column are 0.0 (i.e.
+    Neumann boundaries), so that "divergence(*gradient(I))" equals
+    "laplacian(I)".
+
+    :param data: The input 2-dimensional image.
+    :return: The row and column gradient images.
+    """
+    ...
+
+def guided(data: npt.NDArray[np.float64], guide: npt.ArrayLike, radius: int, epsilon: float) -> npt.NDArray[np.float64]:
+    r"""
+    Smooth a 2-dimensional image with a guided filter.
+
+    This function smooths an input image "p" (e.g. a lifetime map) with the
+    edges of a guidance image "I" (e.g. the intensity image). Within each
+    (2 * radius + 1)² window, the output is modeled as a linear transform of the
+    guide, fitted to the input by regularized least squares:
+
+    a = cov(I, p) / (var(I) + ε)
+    b = mean(p) - a * mean(I)
+    q = mean(a) * I + mean(b)
+
+    Where the guide is flat (var ≪ ε) the input is averaged, while edges of the
+    guide (var ≫ ε) are preserved in the output. Windows are truncated at the
+    image edges. Non-finite input pixels propagate to all windows that contain
+    them and should be filled beforehand.
+
+    :param data: The input 2-dimensional image to smooth, "p".
+    :param guide: The 2-dimensional guidance image, "I". The input image can be
+        its own guide for edge-preserving smoothing.
+    :param radius: The window radius in pixels.
+    :param epsilon: The regularization "ε" (ε > 0), in squared guide units.
+        Guide edges with a variance well above "epsilon" are preserved.
+    :return: The smoothed image.
+    """
+    ...
+
+def kuwahara(data: npt.ArrayLike, radius: int, sectors: int | None = None, sharpness: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Smooth a 2-dimensional image with a generalized Kuwahara filter.
+
+    This function divides the circular window of "radius" pixels around each
+    pixel into "sectors" equal angular sectors, each including the center pixel,
+    and computes the mean and variance of every sector. By default the output is
+    the mean of the sector with the lowest variance (i.e. the classic Kuwahara
+    filter for 4 sectors). With a "sharpness" q, all sectors are blended with
+    weights that decrease with their variance:
+
+    wₖ = 1 / (1 + σₖ²)^(q / 2)
+
+    Which avoids the blocky artifacts of the hard selection. Windows are
+    truncated at the image edges.
+
+    :param data: The input 2-dimensional image.
+    :param radius: The window radius in pixels. If "radius" is 0, the image is
+        returned unfiltered.
+    :param sectors: The number of angular sectors, default = 4.
+    :param sharpness: The weighting exponent "q" of the sectors. If "None", the
+        sector with the lowest variance is selected.
+    :return: The smoothed image.
+    """
+    ...
+
+def laplacian(data: npt.ArrayLike) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the Laplacian of a 2-dimensional image.
+
+    This function computes the 5-point discrete Laplacian of an image, the sum
+    of the differences between each pixel and its 4-connected neighbors within
+    the image (i.e. Neumann boundaries).
+
+    :param data: The input 2-dimensional image.
+    :return: The Laplacian image.
+    """
+    ...
+
+def mean(data: npt.ArrayLike, radius: int) -> npt.NDArray[np.float64]:
+    r"""
+    Smooth an n-dimensional array with a mean (box) filter.
+
+    This function replaces each element of an n-dimensional array (e.g. a 2D
+    image or 3D stack) by the mean of the (2 * radius + 1)ⁿ box centered on it.
+    The box sums are computed from the integral image of the array, so the
+    filter runs in constant time per element independently of "radius". Boxes
+    are truncated at the array edges and averaged over the elements within the
+    array only.
+
+    :param data: The input n-dimensional array.
+    :param radius: The box radius in elements. If "radius" is 0, the array is
+        returned unfiltered.
+    :return: The smoothed array.
+    """
+    ...
+
+def poisson_solve(rhs: npt.NDArray[np.float64], mean: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Solve the Poisson equation of a 2-dimensional image.
+
+    This function computes the image "u" whose Laplacian equals the right-hand
+    side "f", ∇²u = f, with Neumann boundaries, using the FFT of the mirrored
+    image. The solution is unique up to a constant, set by "mean". Solving with
+    the divergence of a modified gradient field reconstructs an image from its
+    edited gradients, e.g. for gradient-domain background removal or seamless
+    blending.
+
+    :param rhs: The right-hand side, "f".
+    :param mean: The mean of the solution, default = 0.0.
+    :return: The solution image, "u".
+    """
+    ...
+
+def temporal(data: npt.ArrayLike, method: str | None = None, window: int | None = None, alpha: float | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Smooth an n-dimensional stack along its time axis.
+
+    This function filters the time trace of each pixel of an n-dimensional
+    stack along "axis" with a moving average, a moving median or causal
+    exponential smoothing, s(t) = α·x(t) + (1 - α)·s(t - 1). Moving windows are
+    centered and truncated at the start and end of the traces.
+
+    :param data: The input n-dimensional stack.
+    :param method: The temporal filter, "mean", "median" or "exponential",
+        default = "mean".
+    :param window: The moving window in frames of the "mean" and "median"
+        methods, default = 5.
+    :param alpha: The smoothing factor in (0.0, 1.0] of the "exponential"
+        method, default = 0.3.
+    :param axis: The time axis, default = 0.
+    :return: The smoothed stack, with the shape of "data".
+    """
+    ...
+
+def wavelet_denoise(data: npt.ArrayLike, wavelet: str | None = None, levels: int | None = None, rule: str | None = None, sigma: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Denoise a 2-dimensional image by wavelet shrinkage.
+
+    This function denoises an image by soft-thresholding its wavelet detail
+    coefficients. If the noise standard deviation is not given, it is estimated
+    from the finest diagonal subband.
+
+    :param data: The input 2-dimensional image.
+    :param wavelet: The wavelet, "haar", "db2" or "db4", default = "haar".
+    :param levels: The number of decomposition levels, default = 3.
+    :param rule: The threshold rule, "bayes" or "visu", default = "bayes".
+    :param sigma: The noise standard deviation. If "None", it is estimated from
+        the data.
+    :return: The denoised image.
+    """
+    ...
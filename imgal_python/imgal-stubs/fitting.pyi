@@ -0,0 +1,392 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def chi_square(data: list[float], model: list[float]) -> float:
+    r"""
+    Compute the chi-square statistic of a model fit to a decay curve.
+
+    This function computes the chi-square statistic between measured photon
+    counts and the evaluated model curve, using the Poisson (Neyman) variance
+    of the data:
+
+    χ² = Σₖ (Dₖ - Mₖ)² / max(Dₖ, 1)
+
+    :param data: The measured 1-dimensional decay curve.
+    :param model: The evaluated model curve, the same length as "data".
+    :return: The chi-square statistic.
+    """
+    ...
+
+def durbin_watson(residuals: list[float]) -> float:
+    r"""
+    Compute the Durbin-Watson statistic of fit residuals.
+
+    This function computes the Durbin-Watson statistic, a test for serial
+    correlation of consecutive residuals:
+
+    d = Σₖ (rₖ - rₖ₋₁)² / Σₖ rₖ²
+
+    :param residuals: The (weighted) residuals of a fit.
+    :return: The Durbin-Watson statistic, between 0.0 and 4.0, where 2.0
+        indicates uncorrelated residuals.
+    """
+    ...
+
+def gaussian_2d(data: npt.ArrayLike, max_iterations: int | None = None) -> dict[str, Any]:
+    r"""
+    Fit a 2-dimensional Gaussian to a single peak.
+
+    This function fits an axis-aligned Gaussian with a constant offset to an
+    image containing a single peak (e.g. a cropped spot or bead) by
+    Levenberg-Marquardt least squares:
+
+    f(r, c) = offset + A · exp(-(r - cᵣ)² / 2σᵣ² - (c - c꜀)² / 2σ꜀²)
+
+    :param data: The 2-dimensional image of the peak.
+    :param max_iterations: The maximum number of Levenberg-Marquardt
+        iterations, default = 200.
+    :return: A dictionary with the "amplitude", "offset", (row, col) "center"
+        and "sigma", and the sum of squared "residual".
+    """
+    ...
+
+def gaussian_3d(data: npt.ArrayLike, max_iterations: int | None = None) -> dict[str, Any]:
+    r"""
+    Fit a 3-dimensional Gaussian to a single peak.
+
+    This function fits an axis-aligned Gaussian with a constant offset to a
+    volume containing a single peak by Levenberg-Marquardt least squares:
+
+    f(p, r, c) = offset + A · exp(-Σₖ (xₖ - cₖ)² / 2σₖ²)
+
+    :param data: The 3-dimensional volume of the peak.
+    :param max_iterations: The maximum number of Levenberg-Marquardt
+        iterations, default = 200.
+    :return: A dictionary with the "amplitude", "offset", (pln, row, col)
+        "center" and "sigma", and the sum of squared "residual".
+    """
+    ...
+
+def laguerre_basis(n: int, order: int, alpha: float) -> npt.NDArray[np.float64]:
+    r"""
+    Create a discrete Laguerre basis.
+
+    This function computes the orthonormal discrete Laguerre functions of
+    orders 0 to "order - 1", which decay more slowly as "alpha" approaches
+    1.0.
+
+    :param n: The number of samples of each function.
+    :param order: The number of basis functions, must be greater than 0.
+    :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive).
+    :return: The basis with shape (order, n).
+    """
+    ...
+
+def laguerre_fit(data: list[float], period: float, order: int | None = None, alpha: float | None = None, irf: list[float] | None = None) -> dict[str, Any]:
+    r"""
+    Estimate the average lifetime of a decay curve by Laguerre expansion.
+
+    This function expands the fluorescence impulse response on a discrete
+    Laguerre basis, optionally convolved with an instrument response function
+    (IRF), by linear least squares, without iterations or starting values.
+    The average lifetime is the first moment of the impulse response "h":
+
+    τ = Σₖ tₖ h(k) / Σₖ h(k)
+
+    :param data: The 1-dimensional decay curve.
+    :param period: The period (i.e. time interval).
+    :param order: The number of basis functions, default = 5.
+    :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive). If
+        "None", the value of a fixed grid with the smallest squared residual is
+        used.
+    :param irf: The instrument response function with the length of "data". If
+        "None", the decay is fitted from the peak bin without convolution.
+    :return: A dictionary with the expansion "coefficients", the Laguerre
+        "alpha", the "impulse_response", the "mean_lifetime" and the
+        "reduced_chi_square" of the fit.
+    """
+    ...
+
+def laguerre_image(data: npt.ArrayLike, period: float, order: int | None = None, alpha: float | None = None, irf: list[float] | None = None, mask: npt.NDArray[np.bool_] | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute an average lifetime image of a 3-dimensional decay image by
+    Laguerre expansion.
+
+    The basis and its least squares projection are computed once and shared
+    by all pixels. If "alpha" is "None" it is selected from the summed decay
+    of the image, and without an IRF all pixels are fitted from the peak bin
+    of the summed decay.
+
+    :param data: The 3-dimensional decay image.
+    :param period: The period (i.e. time interval).
+    :param order: The number of basis functions, default = 5.
+    :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive). If
+        "None", it is selected from the summed decay.
+    :param irf: The instrument response function with the length of the decay
+        axis. If "None", the decays are fitted from the peak bin without
+        convolution.
+    :param mask: The 2-dimensional boolean mask of the pixels to fit. If
+        "None", all pixels are fitted.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The average lifetime image. Pixels without counts or outside of
+        "mask" are NaN.
+    """
+    ...
+
+def mem_lifetime_distribution(data: list[float], period: float, irf: list[float] | None = None, lifetime_range: tuple[float, float] | None = None, n_lifetimes: int | None = None, regularization: float | None = None, max_iterations: int | None = None, start: int | None = None) -> dict[str, Any]:
+    r"""
+    Recover a lifetime distribution from a decay curve with the maximum entropy
+    method.
+
+    This function models a decay as a nonnegative distribution of amplitudes
+    over a logarithmically spaced lifetime grid, optionally convolved with an
+    instrument response function (IRF), and maximizes the entropy of the
+    distribution relative to a flat model while fitting the data:
+
+    I(t) = IRF(t) ⊛ Σⱼ aⱼ exp(-t/τⱼ)
+
+    :param data: The 1-dimensional decay curve.
+    :param period: The period (i.e. time interval).
+    :param irf: The instrument response function with the length of "data". If
+        "None", the decay is fitted from the start bin without convolution.
+    :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+        the grid spans from the bin width to the period.
+    :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+    :param regularization: The relative entropy weight. If "None", the weight
+        is chosen so the reduced chi-square of the fit is close to 1.0.
+    :param max_iterations: The maximum number of Newton iterations per solve,
+        default = 100.
+    :param start: The first fitted bin. If "None", the peak bin is used
+        without an IRF and the first bin with an IRF.
+    :return: A dictionary with the "lifetimes" grid and their "amplitudes",
+        the amplitude weighted "mean_lifetime", the
+        "intensity_weighted_lifetime", the "log_width" of the distribution,
+        the "regularization" weight and the "reduced_chi_square" of the fit.
+    """
+    ...
+
+def mem_lifetime_distribution_image(data: npt.ArrayLike, period: float, irf: list[float] | None = None, lifetime_range: tuple[float, float] | None = None, n_lifetimes: int | None = None, regularization: float | None = None, max_iterations: int | None = None, start: int | None = None, mask: npt.NDArray[np.bool_] | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Recover the lifetime distribution of each pixel of a 3-dimensional decay
+    image with the maximum entropy method.
+
+    :param data: The 3-dimensional decay image.
+    :param period: The period (i.e. time interval).
+    :param irf: The instrument response function with the length of the decay
+        axis. If "None", each decay is fitted from its start bin without
+        convolution.
+    :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+        the grid spans from the bin width to the period.
+    :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+    :param regularization: The relative entropy weight. If "None", the weight
+        is chosen per pixel so the reduced chi-square is close to 1.0.
+    :param max_iterations: The maximum number of Newton iterations per solve,
+        default = 100.
+    :param start: The first fitted bin. If "None", the peak bin of each pixel
+        is used without an IRF and the first bin with an IRF.
+    :param mask: The 2-dimensional boolean mask of the pixels to fit. If
+        "None", all pixels are fitted.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The amplitude weighted mean lifetime, intensity weighted mean
+        lifetime and log-lifetime width images. Pixels without counts or
+        outside of "mask" are NaN.
+    """
+    ...
+
+def mem_roi_distribution(data: npt.ArrayLike, mask: npt.NDArray[np.bool_], period: float, irf: list[float] | None = None, lifetime_range: tuple[float, float] | None = None, n_lifetimes: int | None = None, regularization: float | None = None, max_iterations: int | None = None, start: int | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Recover the lifetime distribution of a region of a 3-dimensional decay
+    image with the maximum entropy method.
+
+    This function sums the decay curves of the pixels of "mask" into a single
+    high count decay and recovers its lifetime distribution.
+
+    :param data: The 3-dimensional decay image.
+    :param mask: The 2-dimensional boolean mask of the region.
+    :param period: The period (i.e. time interval).
+    :param irf: The instrument response function with the length of the decay
+        axis. If "None", the decay is fitted from the start bin without
+        convolution.
+    :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+        the grid spans from the bin width to the period.
+    :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+    :param regularization: The relative entropy weight. If "None", the weight
+        is chosen so the reduced chi-square of the fit is close to 1.0.
+    :param max_iterations: The maximum number of Newton iterations per solve,
+        default = 100.
+    :param start: The first fitted bin. If "None", the peak bin is used
+        without an IRF and the first bin with an IRF.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: A dictionary of the region's lifetime distribution, see
+        "mem_lifetime_distribution".
+    """
+    ...
+
+def prony(data: list[float], period: float, components: int, start: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Estimate multiexponential lifetimes of a decay with Prony's method.
+
+    This function estimates the lifetimes and amplitudes of a sum of
+    exponentials with the least squares Prony method, modeling the decay
+    samples after "start" as:
+
+    I(k) = Σᵢ Aᵢ × zᵢᵏ,  zᵢ = exp(-Δt/τᵢ)
+
+    :param data: The 1-dimensional decay curve.
+    :param period: The period (i.e. time interval).
+    :param components: The number of exponential components.
+    :param start: The first bin of the fitted range, default = the peak bin.
+    :return: The lifetimes and amplitudes (at the "start" bin) of each
+        component, sorted by increasing lifetime. Components with non-physical
+        roots are NaN.
+    """
+    ...
+
+def prony_image(data: npt.ArrayLike, period: float, components: int, start: int | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Estimate per-pixel multiexponential lifetimes of a 3-dimensional decay
+    image with Prony's method.
+
+    :param data: The 3-dimensional decay image.
+    :param period: The period (i.e. time interval).
+    :param components: The number of exponential components.
+    :param start: The first bin of the fitted range, default = the peak bin of
+        each pixel.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The lifetime and amplitude images with shape
+        (rows, cols, components).
+    """
+    ...
+
+def reduced_chi_square(data: list[float], model: list[float], n_params: int) -> float:
+    r"""
+    Compute the reduced chi-square statistic of a model fit to a decay curve.
+
+    χ²ᵣ = χ² / (n - p)
+
+    :param data: The measured 1-dimensional decay curve.
+    :param model: The evaluated model curve, the same length as "data".
+    :param n_params: The number of free model parameters.
+    :return: The reduced chi-square statistic.
+    """
+    ...
+
+def reduced_chi_square_image(data: npt.ArrayLike, model: npt.NDArray[np.float64], n_params: int, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the per-pixel reduced chi-square image of a model fit to a
+    3-dimensional decay image.
+
+    :param data: The measured 3-dimensional decay image.
+    :param model: The evaluated model image, the same shape as "data".
+    :param n_params: The number of free model parameters.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The reduced chi-square image.
+    """
+    ...
+
+def select_model(data: list[float], period: float, criterion: str | None = None, alpha: float | None = None, max_components: int | None = None, start: int | None = None) -> int:
+    r"""
+    Select the number of exponential components of a decay curve.
+
+    This function fits 1 to "max_components" exponential components to a
+    1-dimensional decay curve (or the summed decay of a region of interest)
+    with Prony's method and selects the preferred model order with the Akaike
+    (AIC) or Bayesian (BIC) information criterion or a sequential F-test of the
+    nested models.
+
+    :param data: The 1-dimensional decay curve.
+    :param period: The period (i.e. time interval).
+    :param criterion: The model selection criterion, "aic", "bic" or "ftest",
+        default = "bic".
+    :param alpha: The significance level of the "ftest" criterion,
+        default = 0.05.
+    :param max_components: The maximum number of exponential components,
+        default = 3.
+    :param start: The first bin of the fitted range, default = the peak bin.
+    :return: The selected number of components, 0 if no order could be fit.
+    """
+    ...
+
+def select_model_image(data: npt.ArrayLike, period: float, criterion: str | None = None, alpha: float | None = None, max_components: int | None = None, start: int | None = None, axis: int | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Select the number of exponential components of each pixel of a
+    3-dimensional decay image.
+
+    :param data: The 3-dimensional decay image.
+    :param period: The period (i.e. time interval).
+    :param criterion: The model selection criterion, "aic", "bic" or "ftest",
+        default = "bic".
+    :param alpha: The significance level of the "ftest" criterion,
+        default = 0.05.
+    :param max_components: The maximum number of exponential components,
+        default = 3.
+    :param start: The first bin of the fitted range, default = the peak bin of
+        each pixel.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The selected number of components of each pixel, 0 for pixels
+        that can not be fit.
+    """
+    ...
+
+def tail_fit(data: list[float], period: float, start: int | None = None, end: int | None = None) -> tuple[float, float]:
+    r"""
+    Estimate a lifetime by log-linear regression over the tail of a decay.
+
+    This function fits a monoexponential model to the tail of a 1-dimensional
+    decay curve by counts weighted linear regression of the logarithm of the
+    counts against time:
+
+    ln I(t) = ln A - t/τ
+
+    :param data: The 1-dimensional decay curve.
+    :param period: The period (i.e. time interval).
+    :param start: The first bin of the tail range, default = the peak bin.
+    :param end: The end bin (exclusive) of the tail range, default = the length
+        of "data".
+    :return: The lifetime and the amplitude at the "start" bin.
+    """
+    ...
+
+def tail_fit_image(data: npt.ArrayLike, period: float, start: int | None = None, end: int | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Estimate per-pixel lifetimes by log-linear regression over the tail of a
+    3-dimensional decay image.
+
+    :param data: The 3-dimensional decay image.
+    :param period: The period (i.e. time interval).
+    :param start: The first bin of the tail range, default = the peak bin of
+        each pixel.
+    :param end: The end bin (exclusive) of the tail range, default = the length
+        of the decay axis.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The lifetime and amplitude images, pixels that can not be fit are
+        NaN.
+    """
+    ...
+
+def weighted_residuals(data: list[float], model: list[float]) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the weighted residuals of a model fit to a decay curve.
+
+    rₖ = (Dₖ - Mₖ) / √max(Dₖ, 1)
+
+    :param data: The measured 1-dimensional decay curve.
+    :param model: The evaluated model curve, the same length as "data".
+    :return: The Poisson weighted residuals.
+    """
+    ...
+
+def weighted_residuals_image(data: npt.ArrayLike, model: npt.NDArray[np.float64], axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the weighted residuals image of a model fit to a 3-dimensional
+    decay image.
+
+    :param data: The measured 3-dimensional decay image.
+    :param model: The evaluated model image, the same shape as "data".
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The Poisson weighted residuals image.
+    """
+    ...
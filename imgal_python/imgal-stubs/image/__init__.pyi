@@ -0,0 +1,303 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+from . import annotate as annotate
+from . import math as math
+from . import render as render
+
+def anscombe(data: npt.ArrayLike) -> npt.NDArray[np.float64]:
+    r"""
+    Apply the Anscombe variance-stabilizing transform to an n-dimensional array.
+
+    This function applies the Anscombe transform to Poisson distributed data
+    (e.g. photon counts), converting it to approximately Gaussian distributed
+    data with unit variance:
+
+    A(x) = 2√(x + 3/8)
+
+    :param data: The input n-dimensional array of Poisson distributed values.
+    :return: The variance-stabilized n-dimensional array.
+    """
+    ...
+
+def anscombe_inverse(data: npt.NDArray[np.float64], unbiased: bool | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Apply the inverse Anscombe transform to an n-dimensional array.
+
+    This function maps Anscombe transformed data back to the Poisson domain.
+
+    :param data: The input n-dimensional array of Anscombe transformed values.
+    :param unbiased: If "True", the closed-form approximation of the exact
+        unbiased inverse is used, otherwise the algebraic inverse is used,
+        default = True.
+    :return: The n-dimensional array in the Poisson domain.
+    """
+    ...
+
+def box_sum(integral: npt.NDArray[np.float64], start: list[int], end: list[int]) -> float:
+    r"""
+    Compute the sum of a box of an array from its integral image.
+
+    :param integral: The integral image, as returned by "integral_image" or
+        "integral_image_squared".
+    :param start: The first index of the box on each axis (inclusive).
+    :param end: The last index of the box on each axis (exclusive).
+    :return: The sum of the box. Empty boxes sum to 0.0.
+    """
+    ...
+
+def crop(data: npt.ArrayLike, start: list[int], shape: list[int]) -> npt.NDArray[Any]:
+    r"""
+    Crop a region of an n-dimensional array.
+
+    :param data: The input n-dimensional array.
+    :param start: The start index of the region along each axis.
+    :param shape: The shape of the region.
+    :return: The cropped region with the same dtype as the input array.
+    """
+    ...
+
+def crop_center(data: npt.ArrayLike, shape: list[int]) -> npt.NDArray[Any]:
+    r"""
+    Crop the center region of an n-dimensional array.
+
+    If the difference between the array and region lengths is odd, the extra
+    element is removed from the end of the axis.
+
+    :param data: The input n-dimensional array.
+    :param shape: The shape of the region.
+    :return: The cropped center region with the same dtype as the input array.
+    """
+    ...
+
+def distance_transform(mask: npt.NDArray[np.bool_], spacing: tuple[float, float] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the Euclidean distance transform of a 2-dimensional boolean mask.
+
+    This function computes, for each "true" (foreground) pixel, the exact
+    Euclidean distance to the nearest "false" (background) pixel, while
+    background pixels are 0.0. Anisotropic pixel sizes are supported exactly.
+
+    :param mask: The 2-dimensional boolean mask.
+    :param spacing: The (row, col) pixel size, distances are reported in the
+        unit of the pixel size (e.g. µm), default = (1.0, 1.0) (pixels).
+    :return: The distance transform. If "mask" has no background pixels, all
+        distances are infinite.
+    """
+    ...
+
+def histogram(data: npt.ArrayLike, bins: int | None = None, range: tuple[float, float] | None = None) -> list[int]:
+    r"""
+    Compute the image histogram from an n-dimensional array.
+
+    This function computes an image (_i.e._ frequency) histogram for the values
+    in the input n-dimensional array.
+
+    :param data: The input n-dimensional array to construct the histogram from.
+    :param bins: The number of bins to use for the histogram, default = 256.
+    :param range: The (min, max) range of the histogram. If "None", the minimum
+        and maximum values of "data" are used.
+    :return: The histogram of the input n-dimensional array of size `bins`.
+        Each element represents the count of values falling into the
+        corresponding bin.
+    """
+    ...
+
+def histogram_u16(data: npt.NDArray[np.uint16]) -> list[int]:
+    r"""
+    Compute the exact-count histogram of an unsigned 16-bit n-dimensional array.
+
+    This function computes an exact-count histogram with one bin per integer
+    value, from 0 to the maximum value of the input array.
+
+    :param data: The input unsigned 16-bit n-dimensional array.
+    :return: The histogram of size "max + 1", where element "i" is the count of
+        values equal to "i".
+    """
+    ...
+
+def histogram_weighted(data: npt.ArrayLike, bins: int | None = None, range: tuple[float, float] | None = None, weights: npt.NDArray[np.float64] | None = None, density: bool | None = None) -> tuple[list[float], list[float]]:
+    r"""
+    Compute a weighted and/or density normalized image histogram from an
+    n-dimensional array.
+
+    This function computes an image histogram where each value optionally
+    contributes its weight instead of 1 to its bin. If "density" is true, the
+    histogram is normalized such that its integral over the range is 1.0.
+
+    :param data: The input n-dimensional array to construct the histogram from.
+    :param bins: The number of bins to use for the histogram, default = 256.
+    :param range: The (min, max) range of the histogram. If "None", the minimum
+        and maximum values of "data" are used.
+    :param weights: An optional n-dimensional array of weights, one per value.
+        Must have the same shape as "data".
+    :param density: If true, the histogram is normalized to a probability
+        density, default = False.
+    :return: The histogram of size "bins" and the "bins + 1" bin edges.
+    """
+    ...
+
+def integral_image(data: npt.ArrayLike) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the integral image (summed-area table) of an n-dimensional array.
+
+    This function computes the cumulative sum of the input array along every
+    axis. The integral image has one more element than the input along each
+    axis, with a leading zero row (plane) on every axis, so that the sum of any
+    box of the input is computed in constant time (see "box_sum").
+
+    :param data: The input n-dimensional array (e.g. a 2D image or 3D stack).
+    :return: The integral image, with a shape of the input shape + 1 on every
+        axis.
+    """
+    ...
+
+def integral_image_squared(data: npt.ArrayLike) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the squared integral image of an n-dimensional array.
+
+    This function computes the integral image (see "integral_image") of the
+    squared input values. Together with the integral image, the variance of any
+    box of "N" elements is computed in constant time:
+
+    Var = (S₂ - S² / N) / N
+
+    :param data: The input n-dimensional array (e.g. a 2D image or 3D stack).
+    :return: The squared integral image, with a shape of the input shape + 1 on
+        every axis.
+    """
+    ...
+
+def montage(stack: npt.ArrayLike, columns: int | None = None, spacing: int | None = None, border_value: float | None = None) -> npt.NDArray[Any]:
+    r"""
+    Tile a stack of 2-dimensional images into a single montage image.
+
+    This function arranges the images of a (pln, row, col) stack on a grid in
+    row-major order. Tiles are separated by "spacing" pixels of "border_value",
+    which also fills the outer border and any empty grid cells.
+
+    :param stack: The input 3-dimensional stack, with images along axis 0.
+    :param columns: The number of grid columns, default = ceil(sqrt(pln)).
+    :param spacing: The border width in pixels between and around tiles,
+        default = 0.
+    :param border_value: The intensity value of the borders and empty cells,
+        default = 0.
+    :return: The montage image with the same dtype as the input stack.
+    """
+    ...
+
+def pad(data: npt.ArrayLike, pad_width: list[tuple[int, int]], mode: str | None = None, constant: float | None = None) -> npt.NDArray[Any]:
+    r"""
+    Pad an n-dimensional array.
+
+    This function pads each axis of an n-dimensional array by the given number
+    of elements before and after the data. The padded array has the same dtype
+    as the input array.
+
+    :param data: The input n-dimensional array.
+    :param pad_width: The number of elements padded (before, after) each axis.
+    :param mode: The padding mode, "constant", "reflect", "edge" or "wrap",
+        default = "constant".
+    :param constant: The padding value for the "constant" mode, default = 0.
+    :return: The padded array.
+    """
+    ...
+
+def ratio(channel_a: npt.ArrayLike, channel_b: npt.ArrayLike, background_a: float | None = None, background_b: float | None = None, threshold: float | None = None, clamp: tuple[float, float] | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the ratio image of two channels with its propagated uncertainty.
+
+    This function computes the background subtracted ratio of two channels of
+    a ratiometric indicator (e.g. Fura-2 or a pH sensor) and its Poisson (shot
+    noise) uncertainty, assuming the channels are in photon counts. Pixels
+    where either background subtracted channel is at or below "threshold" are
+    NaN.
+
+    :param channel_a: The numerator channel.
+    :param channel_b: The denominator channel, with the same shape and dtype as
+        "channel_a".
+    :param background_a: The background of the numerator, default = 0.0.
+    :param background_b: The background of the denominator, default = 0.0.
+    :param threshold: The minimum background subtracted intensity of both
+        channels, default = 0.0.
+    :param clamp: The (min, max) range the ratio is clamped to. If "None", the
+        ratio is not clamped.
+    :return: The ratio image and its uncertainty.
+    """
+    ...
+
+def ratio_to_concentration(ratio: npt.NDArray[np.float64], uncertainty: npt.NDArray[np.float64], kd: float, r_min: float, r_max: float, beta: float | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Convert a ratio image into an analyte concentration image.
+
+    This function calibrates a ratio image with the Grynkiewicz equation,
+    c = Kd · β · (R - R_min) / (R_max - R), and propagates the ratio
+    uncertainty. Ratios outside of the (R_min, R_max) range are NaN.
+
+    :param ratio: The ratio image.
+    :param uncertainty: The ratio uncertainty image, with the same shape as
+        "ratio".
+    :param kd: The dissociation constant of the indicator.
+    :param r_min: The ratio of the analyte free indicator.
+    :param r_max: The ratio of the analyte saturated indicator.
+    :param beta: The ratio of the free to bound intensity of the denominator
+        channel, default = 1.0.
+    :return: The concentration image and its uncertainty.
+    """
+    ...
+
+def saturation_counts(data: npt.ArrayLike, value: float | None = None, axis: int | None = None) -> list[int]:
+    r"""
+    Count the saturated pixels of each frame of an n-dimensional image.
+
+    :param data: The n-dimensional image.
+    :param value: The saturation level, default = the maximum value of the
+        dtype.
+    :param axis: The frame axis, default = 0.
+    :return: The number of saturated pixels of each frame.
+    """
+    ...
+
+def saturation_mask(data: npt.ArrayLike, value: float | None = None) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a boolean mask of the saturated pixels of an n-dimensional image.
+
+    This function flags the pixels at or above a saturation level. Saturated
+    pixels hold clipped intensities, which silently bias downstream analyses
+    (e.g. phasor coordinates or colocalization coefficients) and should be
+    excluded or reported.
+
+    :param data: The n-dimensional image.
+    :param value: The saturation level, default = the maximum value of the
+        dtype (e.g. 65535 for uint16). Set the level explicitly for detectors
+        that saturate below the dtype maximum (e.g. 4095 for a 12-bit camera
+        stored as uint16).
+    :return: The saturation mask, where "True" pixels are saturated.
+    """
+    ...
+
+def saturation_region_counts(data: npt.ArrayLike, labels: npt.NDArray[np.uintp], value: float | None = None) -> list[int]:
+    r"""
+    Count the saturated pixels of each region of a labeled n-dimensional image.
+
+    :param data: The n-dimensional image.
+    :param labels: The label image, with the same shape as "data".
+    :param value: The saturation level, default = the maximum value of the
+        dtype.
+    :return: The number of saturated pixels of each label, indexed by label from
+        0 (i.e. the background) to the largest label.
+    """
+    ...
+
+def stack_from_slices(slices: npt.ArrayLike) -> npt.NDArray[Any]:
+    r"""
+    Assemble a 3-dimensional stack from 2-dimensional slices.
+
+    :param slices: A list of 2-dimensional slices, all with the same shape and
+        dtype.
+    :return: The stack with a shape of (len(slices), row, col).
+    """
+    ...
@@ -0,0 +1,96 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def draw_outlines(rgb: npt.NDArray[np.uint8], labels: npt.NDArray[np.uintp], color: tuple[int, int, int] | None = None) -> None:
+    r"""
+    Burn the outlines of the regions of a label image into an RGB image in
+    place.
+
+    This function colors the boundary pixels of each labeled region, e.g. to
+    check a segmentation on an exported image. Without a color, each region is
+    outlined with its distinct label color (see "overlay_labels").
+
+    :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+        place.
+    :param labels: The 2-dimensional label image with the spatial shape of
+        "rgb", where 0 is background.
+    :param color: The RGB color of the outlines. If None, each region uses its
+        label color.
+    """
+    ...
+
+def draw_polyline(rgb: npt.NDArray[np.uint8], vertices: list[tuple[float, float]], color: tuple[int, int, int], closed: bool | None = None) -> None:
+    r"""
+    Burn a polyline (e.g. a ROI outline or a line profile path) into an RGB
+    image in place.
+
+    This function draws 1 pixel wide line segments between consecutive
+    vertices. Pixels outside of the image are clipped.
+
+    :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+        place.
+    :param vertices: The (row, col) vertices of the polyline, in pixels.
+    :param color: The RGB color of the polyline.
+    :param closed: Whether to connect the last vertex to the first (i.e. a
+        polygon), default = False.
+    """
+    ...
+
+def draw_scale_bar(rgb: npt.NDArray[np.uint8], pixel_size: float, length: float, corner: str | None = None, color: tuple[int, int, int] | None = None, thickness: int | None = None, margin: int | None = None, label: str | None = None, font_scale: int | None = None) -> None:
+    r"""
+    Burn a scale bar into an RGB image in place.
+
+    This function draws a horizontal bar of "length" physical units, i.e.
+    "length / pixel_size" pixels, in a corner of an RGB image (e.g. the output
+    of "apply_colormap"), with an optional text label centered above the bar
+    (below for the top corners).
+
+    :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+        place.
+    :param pixel_size: The physical pixel size (e.g. µm per pixel).
+    :param length: The physical length of the scale bar, in the unit of
+        "pixel_size".
+    :param corner: The image corner of the scale bar, "top_left", "top_right",
+        "bottom_left" or "bottom_right", default = "bottom_right".
+    :param color: The RGB color of the bar and label, default =
+        (255, 255, 255).
+    :param thickness: The bar thickness in pixels, default = 4.
+    :param margin: The distance between the bar and the image edges in pixels,
+        default = 8.
+    :param label: The label text drawn next to the bar (e.g. "10 µm"). If
+        None, no label is drawn.
+    :param font_scale: The integer font scale of the label, default = 1.
+    """
+    ...
+
+def draw_text(rgb: npt.NDArray[np.uint8], text: str, position: tuple[int, int], color: tuple[int, int, int], scale: int | None = None) -> None:
+    r"""
+    Burn a text label into an RGB image in place.
+
+    This function draws text with an embedded 5x7 pixel bitmap font, scaled by
+    an integer factor. The font covers digits, letters, "µ", the space and
+    common punctuation, other characters are drawn as "?". Lines are separated
+    by newlines and text outside of the image is clipped.
+
+    :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+        place.
+    :param text: The text to draw.
+    :param position: The (row, col) position of the top left corner of the
+        text.
+    :param color: The RGB color of the text.
+    :param scale: The integer font scale, default = 1.
+    """
+    ...
+
+def text_size(text: str, scale: int | None = None) -> tuple[int, int]:
+    r"""
+    Compute the size of a text label drawn with "draw_text".
+
+    :param text: The text to measure.
+    :param scale: The integer font scale, default = 1.
+    :return: The (height, width) of the text in pixels.
+    """
+    ...
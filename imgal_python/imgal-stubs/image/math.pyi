@@ -0,0 +1,115 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def abs(data: npt.ArrayLike) -> npt.NDArray[Any]:
+    r"""
+    Compute the absolute value of an n-dimensional image.
+
+    :param data: The n-dimensional image.
+    :return: The absolute value image, with the shape and dtype of "data". The
+        absolute value of a signed integer minimum saturates at the dtype
+        maximum.
+    """
+    ...
+
+def abs_mut(data: npt.ArrayLike) -> None:
+    r"""
+    Compute the absolute value of an n-dimensional image in place.
+
+    :param data: The n-dimensional image, overwritten with its absolute value.
+    """
+    ...
+
+def binary(a: npt.ArrayLike, b: npt.ArrayLike, op: str) -> npt.NDArray[Any]:
+    r"""
+    Apply an elementwise operation between two n-dimensional images.
+
+    This function computes "op" between each element of "a" and the matching
+    element of "b" in floating point and converts the result back to the dtype
+    of "a". Integer results are rounded to the nearest integer and saturated at
+    the dtype range (e.g. subtracting a brighter background from a uint16 image
+    clips at 0 instead of wrapping around), NaN results are 0. Image "b" is
+    broadcast to the shape of "a" with NumPy rules.
+
+    :param a: The first n-dimensional image.
+    :param b: The second n-dimensional image, broadcastable to the shape of "a"
+        and with the same dtype as "a".
+    :param op: The elementwise operation, "add", "subtract", "multiply",
+        "divide", "min" or "max".
+    :return: The result image, with the shape and dtype of "a".
+    """
+    ...
+
+def binary_mut(a: npt.ArrayLike, b: npt.ArrayLike, op: str) -> None:
+    r"""
+    Apply an elementwise operation between two n-dimensional images in place.
+
+    This function computes "op" between each element of "a" and the matching
+    element of "b" like "binary" and writes the result into "a".
+
+    :param a: The first n-dimensional image, overwritten with the result.
+    :param b: The second n-dimensional image, broadcastable to the shape of "a"
+        and with the same dtype as "a".
+    :param op: The elementwise operation, "add", "subtract", "multiply",
+        "divide", "min" or "max".
+    """
+    ...
+
+def clip(data: npt.ArrayLike, min: float | None = None, max: float | None = None) -> npt.NDArray[Any]:
+    r"""
+    Clip the values of an n-dimensional image to a range.
+
+    This function replaces the values below "min" by "min" and the values above
+    "max" by "max". The bounds are converted to the dtype of "data" (i.e.
+    rounded and saturated for integer dtypes). NaN values are unchanged.
+
+    :param data: The n-dimensional image.
+    :param min: The lower bound. If "None", the values are not clipped from
+        below.
+    :param max: The upper bound. If "None", the values are not clipped from
+        above.
+    :return: The clipped image, with the shape and dtype of "data".
+    """
+    ...
+
+def clip_mut(data: npt.ArrayLike, min: float | None = None, max: float | None = None) -> None:
+    r"""
+    Clip the values of an n-dimensional image to a range in place.
+
+    :param data: The n-dimensional image, overwritten with the clipped values.
+    :param min: The lower bound. If "None", the values are not clipped from
+        below.
+    :param max: The upper bound. If "None", the values are not clipped from
+        above.
+    """
+    ...
+
+def scalar(data: npt.ArrayLike, value: float, op: str) -> npt.NDArray[Any]:
+    r"""
+    Apply an elementwise operation between an n-dimensional image and a scalar.
+
+    This function computes "op" between each element of "data" and "value",
+    with the dtype conversion of "binary".
+
+    :param data: The n-dimensional image.
+    :param value: The scalar value, the second operand of "op".
+    :param op: The elementwise operation, "add", "subtract", "multiply",
+        "divide", "min" or "max".
+    :return: The result image, with the shape and dtype of "data".
+    """
+    ...
+
+def scalar_mut(data: npt.ArrayLike, value: float, op: str) -> None:
+    r"""
+    Apply an elementwise operation between an n-dimensional image and a scalar
+    in place.
+
+    :param data: The n-dimensional image, overwritten with the result.
+    :param value: The scalar value, the second operand of "op".
+    :param op: The elementwise operation, "add", "subtract", "multiply",
+        "divide", "min" or "max".
+    """
+    ...
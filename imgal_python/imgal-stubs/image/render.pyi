@@ -0,0 +1,91 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def apply_colormap(data: npt.ArrayLike, colormap: str | None = None, range: tuple[float, float] | None = None) -> npt.NDArray[np.uint8]:
+    r"""
+    Render a 2-dimensional image as a pseudocolor RGB image.
+
+    This function maps each pixel value to a color of a colormap (e.g. to
+    export a lifetime or ratio map as an 8-bit RGB image):
+
+    RGB = C((x - min) / (max - min))
+
+    Where "C" is the colormap. Values outside of the range are clamped and NaN
+    pixels are rendered black.
+
+    :param data: The 2-dimensional image.
+    :param colormap: The colormap name, one of "viridis", "magma", "inferno",
+        "plasma", "cividis", "turbo" or "hsv", default = "viridis".
+    :param range: The (min, max) value range mapped to the colormap. If
+        "None", the minimum and maximum finite values of "data" are used.
+    :return: The 3-dimensional (row, col, ch) RGB image.
+    """
+    ...
+
+def lifetime_rgb(tau_map: npt.NDArray[np.float64], intensity: npt.ArrayLike, colormap: str | None = None, tau_range: tuple[float, float] | None = None) -> npt.NDArray[np.uint8]:
+    r"""
+    Render a lifetime map as an intensity weighted pseudocolor RGB image.
+
+    This function renders the standard FLIM display, an HSV-style composite
+    where the color (hue) encodes the lifetime and the brightness (value)
+    encodes the intensity:
+
+    RGB = C((τ - τₘᵢₙ) / (τₘₐₓ - τₘᵢₙ)) * I / Iₘₐₓ
+
+    Where "C" is the colormap. Pixels with a NaN lifetime are rendered black.
+
+    :param tau_map: The 2-dimensional lifetime image.
+    :param intensity: The 2-dimensional intensity image (e.g. photon counts).
+        Must have the same shape as "tau_map".
+    :param colormap: The colormap name used to encode the lifetime, one of
+        "viridis", "magma", "inferno", "plasma", "cividis", "turbo" or "hsv",
+        default = "hsv".
+    :param tau_range: The (min, max) lifetime range mapped to the colormap. If
+        "None", the minimum and maximum finite lifetimes of "tau_map" are used.
+    :return: The 3-dimensional (row, col, ch) RGB image.
+    """
+    ...
+
+def overlay_labels(data: npt.ArrayLike, labels: npt.NDArray[np.uintp], alpha: float | None = None, range: tuple[float, float] | None = None) -> npt.NDArray[np.uint8]:
+    r"""
+    Overlay a label image on a 2-dimensional grayscale image.
+
+    This function renders "data" in grayscale and blends each labeled region
+    with a distinct color, so segmentations can be checked visually. Colors
+    are spread around the hue circle by the golden angle. NaN pixels are
+    rendered black.
+
+    :param data: The 2-dimensional grayscale image.
+    :param labels: The 2-dimensional label image, with the shape of "data",
+        where 0 is background.
+    :param alpha: The opacity of the labels, between 0.0 and 1.0, default =
+        0.5.
+    :param range: The (min, max) value range mapped from black to white. If
+        "None", the minimum and maximum finite values of "data" are used.
+    :return: The 3-dimensional (row, col, ch) RGB image.
+    """
+    ...
+
+def overlay_mask(data: npt.ArrayLike, mask: npt.NDArray[np.bool_], color: tuple[int, int, int] | None = None, alpha: float | None = None, range: tuple[float, float] | None = None) -> npt.NDArray[np.uint8]:
+    r"""
+    Overlay a boolean mask on a 2-dimensional grayscale image.
+
+    This function renders "data" in grayscale and blends the mask pixels with
+    a solid color:
+
+    RGB = (1 - α) · gray + α · color
+
+    Where "α" is the opacity. NaN pixels are rendered black.
+
+    :param data: The 2-dimensional grayscale image.
+    :param mask: The 2-dimensional boolean mask, with the shape of "data".
+    :param color: The RGB color of the mask, default = (255, 0, 0) (red).
+    :param alpha: The opacity of the mask, between 0.0 and 1.0, default = 0.5.
+    :param range: The (min, max) value range mapped from black to white. If
+        "None", the minimum and maximum finite values of "data" are used.
+    :return: The 3-dimensional (row, col, ch) RGB image.
+    """
+    ...
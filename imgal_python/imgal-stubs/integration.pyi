@@ -0,0 +1,66 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def composite_simpson(x: list[float], delta_x: float | None = None, summation: str | None = None) -> float:
+    r"""
+    Integrate a curve with Simpson's 1/3 rule and the trapezoid rule.
+
+    Approximates the definite integral using Simpson's 1/3 rule and
+    the trapezoid rule (for odd number of subintervals) with pre-computed
+    x-values:
+
+    ∫(f(x)dx) ≈ (Δx/3) * [f(x₀) + 4f(x₁) + 2f(x₂) + 4f(x₃) + ... + 2f(xₙ₋₂) + 4f(xₙ₋₁) + f(xₙ)]
+
+    Where "n" is the number of evenly spaced points in the data. If there is an
+    odd number of subintervals, the final subinterval is integrated using the
+    trapezoid rule:
+
+    ∫(f(x)dx) ≈ (Δx/2) * [f(x₀) + f(x₁)]
+
+    :param x: The 1-dimensional data to integrate.
+    :param delta_x: The width between data points, defualt = 1.0.
+    :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+        default = "naive".
+    :return: The computed integral.
+    """
+    ...
+
+def midpoint(x: list[float], delta_x: float | None = None, summation: str | None = None) -> float:
+    r"""
+    Integrate a curve with the midpoint rule.
+
+    Approximates the definite integral using the midpoint rule
+    with pre-computed x-values:
+
+    ∫f(x) dx ≈ Δx * [f(x₁) + f(x₂) + ... + f(xₙ)]
+
+    :param x: The 1-dimensional data to integrate.
+    :param delta_x: The width between data points, default = 1.0.
+    :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+        default = "naive".
+    :return: The computed integral.
+    """
+    ...
+
+def simpson(x: list[float], delta_x: float | None = None, summation: str | None = None) -> float:
+    r"""
+    Integrate a curve with Simpson's 1/3 rule.
+
+    Approximates the definite integral using Simpson's 1/3 rule and
+    with pre-computed x-values:
+
+    ∫(f(x)dx) ≈ (Δx/3) * [f(x₀) + 4f(x₁) + 2f(x₂) + 4f(x₃) + ... + 2f(xₙ₋₂) + 4f(xₙ₋₁) + f(xₙ)]
+
+    Where "n" is the number of evenly spaced points in the data.
+
+    :param x: The 1-dimensional data to integrate with an even number of
+       subintervals.
+    :param delta_x: The width between data points, defualt = 1.0.
+    :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+        default = "naive".
+    :return: The computed integral.
+    """
+    ...
@@ -0,0 +1,3 @@
+# This file is generated by stub_gen, do not edit.
+
+from . import neighborhood as neighborhood
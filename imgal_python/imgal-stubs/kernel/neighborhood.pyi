@@ -0,0 +1,135 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def circle(radius: int) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a 2-dimensional square kernel with a circle neighborhood.
+
+    This function creates a square boolean kernel representing a filled circle
+    of the specified radius (i.e. the neighborhood). The circle is defined
+    using the Euclidean distance from the center point. Points within the
+    radius are set to "true", while points outside are set to "false".
+
+    :param radius: The radius of the circle in pixels. Must be greather than 0.
+    :return: A 2-dimensional square boolean array with side lengths
+        of "radius * 2 + 1" where "true" values represent points inside or on
+        the circle boundary of the specified radius.
+    """
+    ...
+
+def gaussian_circle(circle_radius: int, sigma: float, initial_value: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Create a 2-dimensional square kernel with a Gaussian weighted circle
+    neighborhood.
+
+    This function creates a square kernel representing a Gaussian weighted
+    circle of the specified radius (i.e. the neighborhood). Points within the
+    radius are assigned a weight using the Gaussian function of their
+    Euclidean distance from the center point, while points outside are set to
+    0.0:
+
+    w(d) = w₀ * exp(-d² / 2σ²)
+
+    :param circle_radius: The radius of the circle in pixels. Must be greater
+        than 0.
+    :param sigma: The standard deviation of the Gaussian function, in pixels.
+    :param initial_value: The maximum weight value at the center of the kernel,
+        default = 1.0.
+    :return: A 2-dimensional square array with side lengths of
+        "radius * 2 + 1" with a Gaussian weighted circular neighborhood.
+    """
+    ...
+
+def gaussian_sphere(sphere_radius: int, sigma: float, initial_value: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Create a 3-dimensional cube kernel with a Gaussian weighted sphere
+    neighborhood.
+
+    This function creates a cube kernel representing a Gaussian weighted
+    sphere of the specified radius (i.e. the neighborhood). Points within the
+    radius are assigned a weight using the Gaussian function of their
+    Euclidean distance from the center point, while points outside are set to
+    0.0:
+
+    w(d) = w₀ * exp(-d² / 2σ²)
+
+    :param sphere_radius: The radius of the sphere in voxels. Must be greater
+        than 0.
+    :param sigma: The standard deviation of the Gaussian function, in voxels.
+    :param initial_value: The maximum weight value at the center of the kernel,
+        default = 1.0.
+    :return: A 3-dimensional cube array with side lengths of
+        "radius * 2 + 1" with a Gaussian weighted spherical neighborhood.
+    """
+    ...
+
+def sphere(radius: int) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a 3-dimensional cube kernel with a sphere neighborhood.
+
+    This function creates a cube boolean kernel representing a filled sphere of
+    the specified radius (i.e the neighborhood). The sphere is defined using
+    the Euclidean distance from the center point. Points within the radius are
+    set to "true", while jpoints outside are set to "false".
+
+    :param radius: The radius of the sphere in voxels. Must be greater than 0.
+    :return: A 3-dimensional cube boolean array with side lengths
+        of "radius * 2 + 1" where "true" values represent points inside or on
+        the sphere boundary of the specified radius.
+    """
+    ...
+
+def weighted_circle(circle_radius: int, falloff_radius: float, initial_value: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Create a 2-dimensional square kernel with a weighted circle neighborhood.
+
+    This function creates a square kernel representing a weighted value
+    circle of the specified radius (i.e. the neighborhood). The circle is
+    defined using the Euclidean distance from the center point. Points within
+    the radius are valid weighted positions (i.e. a weight can be assigned but
+    is not guaranteed to be present), while points outside are not valid and
+    set to 0.0. The maximum weight value is located at the center of the circle,
+    defined by "initial_value", and decaying values towards the edge at the
+    "falloff_radius" rate.
+
+    :param circle_radius: The radius of the circle in pixels. Must be greater than
+        0.
+    :param falloff_radius: A scaling factor that determines how quickly weights
+        decay with distance. Larger values result in a slower falloff with a
+        broader circle. Small values result in a faster falloff with a tighter
+        circle.
+    :param initial_value: The maximum weight value at the center of the kernel,
+        default = 1.0.
+    :return: A 2-dimensional square array with side lengths
+        of "radius * 2 + 1" with a weighted circular neighborhood.
+    """
+    ...
+
+def weighted_sphere(sphere_radius: int, falloff_radius: float, initial_value: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Create a 3-dimensional cube kernel with a weighted sphere neighborhood.
+
+    This function creates a cube kernel representing a weighted value sphere of
+    the specified radius (i.e. the neighborhood). The sphere is defined using
+    the Euclidean distance from the center point. Points within the radius are
+    valid weighted positions (i.e. a weight can be assigned but is not
+    guaranteed to be present), while points outside are not valid and set to 0.0.
+    The maximum weight value is located at the center of the sphere, defined by
+    "initial_value", and decaying values towards the edge at the "falloff_radius"
+    rate.
+
+    :param sphere_radius: The radius of the sphere in voxels. Must be greater than
+        0.
+    :param falloff_radius: A scaling factor that determines how quickly weights
+        decay with distance. Larger values result in a slower falloff with a
+        broader sphere. Small values result in a faster falloff with a tighter
+        sphere.
+    :param initial_value: The maximum weight value at the center of the kernel,
+        default = 1.0.
+    :return: A 3-dimensional cube array with side lengths of
+        "radius * 2 + 1" with a weighted spherical neighborhood.
+    """
+    ...
@@ -0,0 +1,86 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def drift_correct(locs: dict[str, Any], shape: tuple[int, int], segment: int, magnification: float | None = None) -> tuple[dict[str, Any], list[tuple[float, float]]]:
+    r"""
+    Correct the sample drift of localizations by cross-correlation.
+
+    This function splits the localizations into segments of "segment"
+    consecutive frames, renders each segment into a super-resolved image and
+    measures its translation from the first segment with phase correlation.
+    The drift of each frame is linearly interpolated between the segment
+    centers and subtracted from its localizations.
+
+    :param locs: The localizations, a dictionary of columns (see "localize").
+    :param shape: The (row, col) shape of the camera frames.
+    :param segment: The number of frames per segment.
+    :param magnification: The number of rendered pixels per camera pixel,
+        which sets the drift precision, default = 5.0.
+    :return: A tuple of the drift corrected localizations and the (row, col)
+        drift of each frame in camera pixels.
+    """
+    ...
+
+def localize(data: npt.ArrayLike, radius: int, threshold: float | None = None, method: str | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Localize blinking emitters in a 3-dimensional time-lapse.
+
+    This function detects the spots of each frame of a (t, row, col)
+    single-molecule localization microscopy (SMLM) acquisition as local
+    intensity maxima above "threshold" that are the brightest pixel within
+    "radius" pixels, and localizes each spot with sub-pixel precision from its
+    (2 * radius + 1)² neighborhood.
+
+    :param data: The (t, row, col) time-lapse of blinking emitters.
+    :param radius: The radius of the detection and fit neighborhood in pixels,
+        which should be about 3 times the spot width.
+    :param threshold: The minimum peak intensity of a spot, default = the mean
+        plus 3 standard deviations of each frame.
+    :param method: The localization method, "gaussian" (least squares Gaussian
+        fit) or "phasor" (phase of the first Fourier coefficients, about 100
+        times faster), default = "gaussian".
+    :param axis: The frame (time) axis, default = 0.
+    :return: A dictionary of localization columns, "frame", "row", "col",
+        "intensity", "background" and "sigma", sorted by frame.
+    """
+    ...
+
+def phasor_position(roi: npt.ArrayLike) -> tuple[float, float]:
+    r"""
+    Localize a spot with the phase of the first Fourier coefficients of its
+    region of interest.
+
+    This function computes the first Fourier coefficient of a region of
+    interest (ROI) of a single spot along each axis. The phase of a shifted
+    spot is proportional to its shift, so the spot center is recovered without
+    iterations, about 100 times faster than a Gaussian fit with a similar
+    precision. A constant background does not bias the position.
+
+    :param roi: The 2-dimensional region of interest of the spot.
+    :return: The sub-pixel (row, col) center of the spot within the ROI.
+    """
+    ...
+
+def render(locs: dict[str, Any], shape: tuple[int, int], magnification: float | None = None, sigma: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Render localizations into a super-resolved 2-dimensional image.
+
+    This function renders localizations onto a grid "magnification" times
+    finer than the camera pixels. Without "sigma", each output pixel counts the
+    localizations within it (i.e. a 2-dimensional histogram). With "sigma",
+    each localization is drawn as a normalized Gaussian of standard deviation
+    "sigma" camera pixels (e.g. the localization precision).
+
+    :param locs: The localizations, a dictionary of columns (see "localize").
+    :param shape: The (row, col) shape of the camera frames.
+    :param magnification: The number of output pixels per camera pixel along
+        each axis, default = 10.0.
+    :param sigma: The Gaussian standard deviation in camera pixels. If None, a
+        histogram is rendered.
+    :return: The super-resolved image, with a shape of ceil(shape *
+        magnification).
+    """
+    ...
@@ -0,0 +1,204 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def anisotropy(i_parallel: npt.ArrayLike, i_perpendicular: npt.ArrayLike, g_factor: float | None = None, background_parallel: float | None = None, background_perpendicular: float | None = None, threshold: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the steady-state fluorescence anisotropy image of a pair of
+    polarized channels.
+
+    This function computes the per-pixel steady-state anisotropy
+    r = (I∥ - G·I⊥) / (I∥ + 2G·I⊥) of the background subtracted parallel and
+    perpendicular polarized emission channels. Pixels where the total intensity
+    I∥ + 2G·I⊥ is at or below "threshold", or where a channel is NaN, are NaN.
+
+    :param i_parallel: The parallel polarized channel.
+    :param i_perpendicular: The perpendicular polarized channel, with the same
+        shape and dtype as "i_parallel".
+    :param g_factor: The instrument G-factor, default = 1.0.
+    :param background_parallel: The background of the parallel channel,
+        default = 0.0.
+    :param background_perpendicular: The background of the perpendicular
+        channel, default = 0.0.
+    :param threshold: The minimum total intensity, default = 0.0.
+    :return: The anisotropy image.
+    """
+    ...
+
+def detect_transients(traces: npt.ArrayLike, threshold: float | None = None, min_duration: int | None = None) -> dict[str, Any]:
+    r"""
+    Detect transient events in intensity traces.
+
+    This function detects transients (e.g. calcium or voltage indicator spikes)
+    in each row of a 2-dimensional (trace, t) array of time traces. The baseline
+    of each trace is its median and the noise is the scaled median absolute
+    deviation. An event is a run of at least "min_duration" consecutive frames
+    above the baseline plus "threshold" noise standard deviations.
+
+    :param traces: The 2-dimensional (trace, t) array of time traces.
+    :param threshold: The detection threshold in noise standard deviations
+        above the baseline, default = 3.0.
+    :param min_duration: The minimum number of frames above the threshold,
+        default = 1.
+    :return: A dictionary of event columns, "trace", "start", "peak", "end"
+        (exclusive), "amplitude" (above the baseline), "duration" (in frames)
+        and "rise_time" (10% to 90%, in frames).
+    """
+    ...
+
+def esf_lsf_mtf(data: npt.ArrayLike, oversample: int | None = None) -> dict[str, Any]:
+    r"""
+    Estimate the edge, line spread and modulation transfer functions of a
+    slanted edge.
+
+    This function measures the resolution of an imaging system from a region of
+    interest containing a single straight edge slightly tilted from the pixel
+    grid, with the slanted-edge method of ISO 12233. The pixels are projected
+    onto the edge normal into an oversampled edge spread function (ESF), which
+    is differentiated into the line spread function (LSF). The modulation
+    transfer function (MTF) is the normalized magnitude of the Fourier
+    transform of the Hamming windowed LSF.
+
+    :param data: The 2-dimensional region of interest containing the edge.
+    :param oversample: The number of ESF bins per pixel, default = 4.
+    :return: A dictionary with the "distance" of each ESF and LSF sample from
+        the edge in pixels, the "esf", the "lsf", the "frequency" of each MTF
+        sample in cycles per pixel, the "mtf" and the edge "angle" in degrees.
+    """
+    ...
+
+def fit_psf(data: npt.ArrayLike, radius: int, threshold: float | None = None, spacing: list[float] | None = None) -> dict[str, Any]:
+    r"""
+    Measure the point spread function of a 2 or 3-dimensional bead image.
+
+    This function detects sub-resolution beads as local intensity maxima above
+    "threshold" that are the brightest pixel within "radius" pixels, and fits
+    each bead's neighborhood with an axis-aligned Gaussian. The full width at
+    half maximum (FWHM) along each axis measures the resolution of the
+    microscope, and the lateral asymmetry reveals astigmatism or coma.
+
+    :param data: The 2 or 3-dimensional bead image.
+    :param radius: The radius of the detection and fit neighborhood in pixels,
+        which should be about twice the expected FWHM.
+    :param threshold: The minimum peak intensity of a bead, default = the mean
+        plus 3 standard deviations of the image.
+    :param spacing: The pixel size of each axis, default = 1.0 (pixels).
+    :return: A dictionary with per-bead lists, "center" (in pixels), "fwhm"
+        (in the unit of the pixel size), "amplitude", "offset" and
+        "asymmetry", and the summary statistics "fwhm_mean", "fwhm_std" and
+        "asymmetry_mean".
+    """
+    ...
+
+def kymograph(data: npt.ArrayLike, path: list[tuple[float, float]], width: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Create a kymograph of a 3-dimensional stack along a polyline path.
+
+    This function samples each frame of a (t, row, col) stack (e.g. an
+    intensity or lifetime time series) along a polyline path, creating a
+    (t, distance) kymograph where moving objects appear as slanted lines whose
+    slope is their velocity. The path is sampled at unit (pixel) spacing with
+    bilinear interpolation, averaging "width" points perpendicular to the path.
+    Samples without any point in the image are NaN.
+
+    :param data: The input 3-dimensional stack.
+    :param path: The (row, col) vertices of the polyline path, in pixels.
+    :param width: The path width in pixels, default = 1.
+    :param axis: The frame (time) axis, default = 0.
+    :return: The kymograph with a shape of (t, distance).
+    """
+    ...
+
+def per_label_analysis(labels: npt.NDArray[np.uintp], channel_a: npt.NDArray[np.float64] | None = None, channel_b: npt.NDArray[np.float64] | None = None, phasor: npt.NDArray[np.float64] | None = None, omega: float | None = None, thresholds: tuple[float, float] | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Measure the intensity, colocalization and lifetime of each region of a
+    2-dimensional label image.
+
+    This function computes, for each labeled region (e.g. a cell), the
+    intensity statistics of two channels, their Pearson correlation and
+    thresholded Manders' coefficients, the mean (G, S) coordinates of a phasor
+    image and the apparent lifetimes of the mean coordinates, in a single
+    parallel pass over the image. Columns that need a missing input are NaN.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param channel_a: The first intensity channel, with the shape of "labels".
+    :param channel_b: The second intensity channel, with the shape of "labels".
+    :param phasor: The 3-dimensional phasor image, where G and S are channels
+        0 and 1 respectively, with the spatial shape of "labels".
+    :param omega: The angular frequency of the apparent lifetimes. If None,
+        the lifetime columns are NaN.
+    :param thresholds: The (A, B) intensity thresholds of the Manders'
+        coefficients, default = (0.0, 0.0).
+    :param axis: The channel axis of "phasor", default = 2.
+    :return: A dictionary of columns with one value per label, convertible to
+        a DataFrame (e.g. "pandas.DataFrame(table)").
+    """
+    ...
+
+def profile_line(data: npt.ArrayLike, start: list[float], end: list[float], width: int | None = None, interpolation: str | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Sample an intensity profile along a line of a 2 or 3-dimensional image.
+
+    This function samples an image along the straight line from "start" to
+    "end" at round(length) + 1 evenly spaced points (i.e. about one sample per
+    pixel, including both end points), like the "plot profile" tool of ImageJ.
+    With a "width" greater than 1, each sample is the mean of points spaced one
+    pixel apart perpendicular to the line. Samples without any point in the
+    image are NaN.
+
+    :param data: The input 2 or 3-dimensional image.
+    :param start: The start point of the line, one coordinate per axis.
+    :param end: The end point of the line, one coordinate per axis.
+    :param width: The line width in pixels, default = 1.
+    :param interpolation: The interpolation method, "nearest", "linear" or
+        "cubic", default = "linear".
+    :return: A tuple of the distance of each sample from "start" and the
+        profile values.
+    """
+    ...
+
+def qc_outliers(values: list[float], threshold: float | None = None) -> list[int]:
+    r"""
+    Find the outlier frames of a quality control metric.
+
+    This function flags the values whose robust z-score exceeds a threshold:
+
+    z = |x - median(x)| / (1.4826 * MAD(x))
+
+    Where "MAD" is the median absolute deviation, which is insensitive to the
+    outliers themselves (e.g. a few defocused or saturated frames).
+
+    :param values: The metric values, one per frame.
+    :param threshold: The robust z-score threshold, default = 3.5.
+    :return: The indices of the outlier frames. If the MAD is 0.0, only values
+        different from the median are outliers.
+    """
+    ...
+
+def stack_qc(data: npt.ArrayLike, saturation: float | None = None, metric: str | None = None, threshold: float | None = None, cutoff: float | None = None, gain: float | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Compute per-frame quality control metrics of a 3-dimensional stack.
+
+    This function computes, for each frame of a (t, row, col) stack, the mean
+    and median intensity, the fraction of saturated pixels, a focus score and
+    the Poisson signal-to-noise ratio. Frames that are outliers in any column
+    (see "qc_outliers") can then be rejected automatically before batch
+    analyses.
+
+    :param data: The input 3-dimensional stack.
+    :param saturation: The saturation level, default = the maximum value of the
+        dtype.
+    :param metric: The focus metric, "brenner", "tenengrad",
+        "normalized_variance" or "spectral", default = "normalized_variance".
+    :param threshold: The Tenengrad squared gradient threshold, default = 0.0.
+    :param cutoff: The spectral normalized radial frequency cutoff, default =
+        0.2.
+    :param gain: The detector gain in counts per photon, used by the SNR
+        estimate, default = 1.0.
+    :param axis: The frame axis, default = 0.
+    :return: A dictionary of per-frame columns, "mean", "median", "saturation"
+        (the saturated fraction), "focus" and "snr".
+    """
+    ...
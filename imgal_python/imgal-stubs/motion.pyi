@@ -0,0 +1,64 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def farneback(prev: npt.ArrayLike, next: npt.ArrayLike, window_radius: int | None = None, poly_radius: int | None = None, iterations: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the dense optical flow between two frames with the Farnebäck
+    polynomial expansion method.
+
+    :param prev: The first (earlier) 2-dimensional frame.
+    :param next: The second (later) 2-dimensional frame, with the same shape and
+        dtype as "prev".
+    :param window_radius: The radius of the Gaussian weighted displacement
+        window, default = 5.
+    :param poly_radius: The radius of the polynomial expansion neighborhood,
+        default = 3.
+    :param iterations: The number of refinement iterations, default = 3.
+    :return: The flow field with a shape of (row, col, 2), where [..., 0] is
+        the row displacement and [..., 1] is the column displacement, and the
+        flow magnitude map.
+    """
+    ...
+
+def lucas_kanade(prev: npt.ArrayLike, next: npt.ArrayLike, window_radius: int | None = None, min_eigenvalue: float | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the dense optical flow between two frames with the Lucas-Kanade
+    method.
+
+    This function estimates the displacement of every pixel between two frames
+    by assuming brightness constancy and a constant displacement within a
+    Gaussian weighted window around each pixel.
+
+    :param prev: The first (earlier) 2-dimensional frame.
+    :param next: The second (later) 2-dimensional frame, with the same shape and
+        dtype as "prev".
+    :param window_radius: The radius of the Gaussian weighted window,
+        default = 3.
+    :param min_eigenvalue: The minimum eigenvalue of the gradient structure
+        tensor for a reliable estimate, default = 1e-6.
+    :return: The flow field with a shape of (row, col, 2), where [..., 0] is
+        the row displacement and [..., 1] is the column displacement, and the
+        flow magnitude map.
+    """
+    ...
+
+def optical_flow_3d(data: npt.ArrayLike, method: str | None = None, window_radius: int | None = None, min_eigenvalue: float | None = None, poly_radius: int | None = None, iterations: int | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute the dense optical flow between consecutive frames of a time-lapse.
+
+    :param data: The input 3-dimensional time-lapse.
+    :param method: The optical flow method, "lucas_kanade" or "farneback",
+        default = "lucas_kanade".
+    :param window_radius: The radius of the Gaussian weighted window, default =
+        3 for "lucas_kanade" and 5 for "farneback".
+    :param min_eigenvalue: The Lucas-Kanade minimum eigenvalue, default = 1e-6.
+    :param poly_radius: The Farnebäck polynomial expansion radius, default = 3.
+    :param iterations: The Farnebäck refinement iterations, default = 3.
+    :param axis: The time axis, default = 0.
+    :return: The flow fields with a shape of (t - 1, row, col, 2) and the flow
+        magnitude maps with a shape of (t - 1, row, col).
+    """
+    ...
@@ -0,0 +1,45 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def info(name: str) -> dict[str, Any]:
+    r"""
+    Get the metadata of an op.
+
+    This function describes an op's inputs and outputs so that user interfaces
+    can be built from them. Each input and output is a dictionary with the
+    "name", "description", "type" ("image", "mask", "labels", "float" or
+    "int"), "required", "min" and "max" keys.
+
+    :param name: The op name.
+    :return: A dictionary with the op "name", "description", "inputs" and
+        "outputs".
+    """
+    ...
+
+def list_ops() -> list[str]:
+    r"""
+    List the names of all available ops.
+
+    Op names are namespaced by module, for example "filter.gaussian_2d".
+
+    :return: The sorted list of op names.
+    """
+    ...
+
+def run(name: str, inputs: dict[str, Any]) -> tuple[Any, ...]:
+    r"""
+    Run an op by name.
+
+    This function converts the inputs to the types described by the op
+    metadata (see "info"), validates them and runs the op. Images are
+    converted to float64.
+
+    :param name: The op name.
+    :param inputs: A dictionary of the op inputs by name. Optional inputs may
+        be omitted.
+    :return: A tuple of the op outputs, in the order of the op metadata.
+    """
+    ...
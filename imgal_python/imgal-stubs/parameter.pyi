@@ -0,0 +1,36 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def abbe_diffraction_limit(wavelength: float, na: float) -> float:
+    r"""
+    Compute the Abbe diffraction limit.
+
+    Compute Ernst Abbe's diffraction limit using:
+
+    d = wavelength / 2 * NA
+
+    Where "NA" is the numerical aperture of the objective.
+
+    :param wavelength: The wavelength of light.
+    :param na: The numerical aperture.
+    :return: Abbe's diffraction limit.
+    """
+    ...
+
+def omega(period: npt.ArrayLike) -> float:
+    r"""
+    Compute the angular frequency (omega) value.
+
+    Compute the angular frequency, omega (ω), using the following equation:
+
+    ω = 2π/T
+
+    Where "T" is the period.
+
+    :param period: The time period.
+    :return: The omega (ω) value.
+    """
+    ...
@@ -0,0 +1,14 @@
+# This file is generated by stub_gen, do not edit.
+
+from . import analysis as analysis
+from . import batch as batch
+from . import calibration as calibration
+from . import denoise as denoise
+from . import frequency_domain as frequency_domain
+from . import gate_scan as gate_scan
+from . import multi_harmonic as multi_harmonic
+from . import plot as plot
+from . import render as render
+from . import time_domain as time_domain
+from . import trajectory as trajectory
+from . import unmixing as unmixing
@@ -0,0 +1,31 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def harmonic_consistency(data: npt.ArrayLike, period: float, harmonics: list[int] | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Test the consistency of the apparent lifetimes of a 3-dimensional decay
+    image across harmonics.
+
+    This function computes the apparent phase (τφ) and modulation (τM)
+    lifetimes of each pixel at each harmonic. All apparent lifetimes of a
+    monoexponential decay are equal, so the multi-exponentiality index of each
+    pixel is the coefficient of variation of its apparent lifetimes:
+
+    index = std(τ) / mean(τ)
+
+    An index close to 0.0 marks pixels where simple phasor interpretation is
+    valid. Pixels without photons are NaN.
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param harmonics: The harmonics to compare, default = [1, 2]. Each
+        harmonic must be greater than 0 and can not exceed the Nyquist limit.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: A tuple of the multi-exponentiality index image and the apparent
+        lifetime image, where τφ(n) and τM(n) of the i-th harmonic are channels
+        2i and 2i + 1 respectively.
+    """
+    ...
@@ -0,0 +1,33 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def process(datasets: npt.ArrayLike, period: float, harmonic: int | None = None, axis: int | None = None, calibration: tuple[float, float] | None = None, min_photons: float | None = None, min_quality: float | None = None, fill: float | None = None) -> list[tuple[npt.NDArray[np.float64], npt.NDArray[np.bool_]]]:
+    r"""
+    Compute the calibrated and gated phasor images of a batch of decay
+    datasets.
+
+    This function processes a list of 3-dimensional decay images (e.g. the
+    files of an acquisition session) with shared settings: each dataset's
+    phasor image is computed with photon count and histogram quality gating
+    (see "time_domain.gated_image") and calibrated. The settings are validated
+    once, and the waveform tables are computed once per decay length and
+    reused by all datasets with the same number of time bins.
+
+    :param datasets: A list of decay images, I(t), all with the same dtype.
+    :param period: The period (i.e. time interval).
+    :param harmonic: The harmonic value, default = 1.
+    :param axis: The decay or lifetime axis, default = 2.
+    :param calibration: The (modulation, phase) calibration applied to the
+        phasor images. If "None", the phasor images are not calibrated.
+    :param min_photons: The minimum total photon count of a pixel's decay. If
+        "None", pixels are not gated by photon count.
+    :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+        decay. If "None", pixels are not gated by histogram quality.
+    :param fill: The G and S value of pixels that fail a gate, default = NaN.
+    :return: A list with the (row, col, ch) phasor image and the validity mask
+        of each dataset, in the order of "datasets".
+    """
+    ...
@@ -0,0 +1,192 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def coordinates(g: float, s: float, modulation: float, phase: float) -> tuple[float, float]:
+    r"""
+    Calibrate a real and imaginary (G, S) coordinates.
+
+    Calibrate the real and imaginary (e.g. G and S) coordinates by rotating
+    and scaling by phase (φ) and modulation (M) respectively using:
+
+    g = M * cos(φ)
+    s = M * sin(φ)
+    S' = G * s + S * g
+    G' = G * g - S * s
+
+    Where G' and S' are the calibrated real and imaginary values after rotation
+    and scaling.
+
+    :param g: The real component (G) to calibrate.
+    :param s: The imaginary component (S) to calibrate.
+    :param modulation: The modulation to scale the input (G, S) coordinates.
+    :param phase: The phase, φ angle, to rotate the input (G, S) coordinates.
+    :return: The calibrated coordinates, (G, S).
+    """
+    ...
+
+def diagnostics(data: npt.ArrayLike, tau: float, omega: float, mask: npt.NDArray[np.bool_] | None = None, tolerance: float | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Check the calibration of a 3-dimensional phasor image of a
+    monoexponential control.
+
+    This function compares the calibrated (G, S) coordinates of a control of
+    known lifetime with the universal circle and the expected monoexponential
+    coordinates. An "off_circle" issue is reported if the median signed
+    distance to the universal circle exceeds "tolerance", and a
+    "lifetime_mismatch" issue if the control lies on the circle more than
+    "tolerance" away from the expected coordinates, from a phase error or a
+    period (or bin width) mismatch.
+
+    :param data: The 3-dimensional calibrated phasor image, where G and S are
+        channels 0 and 1 respectively.
+    :param tau: The lifetime of the control, τ.
+    :param omega: The angular frequency, ω.
+    :param mask: The 2-dimensional boolean mask of the control pixels. If
+        "None", all pixels are used.
+    :param tolerance: The distance in phasor units beyond which an issue is
+        reported, default = 0.02.
+    :param axis: The channel axis, default = 2.
+    :return: A dictionary with the median "center" and "expected" (G, S)
+        coordinates, the "center_distance", the "circle_distance", the
+        "spread", the apparent "phase_lifetime" and "modulation_lifetime", the
+        "suggested_modulation", "suggested_phase" and "suggested_period_scale"
+        corrections and the list of "issues".
+    """
+    ...
+
+def estimate_modulation_and_phase(g: list[float], s: list[float], tau: float, omega: float, estimator: str | None = None, trim: float | None = None) -> tuple[float, float]:
+    r"""
+    Find the modulation and phase calibration values from many measured
+    coordinates.
+
+    This function estimates the center of the measured (G, S) coordinates of
+    a monoexponential reference, coordinate by coordinate, and computes the
+    calibration values of the center. The median and trimmed mean are robust
+    to a few saturated or background pixels that skew the mean. Non-finite
+    coordinates are ignored.
+
+    :param g: The measured real (G) values.
+    :param s: The measured imaginary (S) values, the same length as "g".
+    :param tau: The lifetime, τ.
+    :param omega: The angular frequency, ω.
+    :param estimator: The center estimator, "mean", "median" or "trimmed",
+        default = "median".
+    :param trim: The fraction of the smallest and of the largest values
+        discarded by the "trimmed" estimator, default = 0.1.
+    :return: The modulation and phase calibration values, (M, φ).
+    """
+    ...
+
+def image(data: npt.ArrayLike, modulation: float, phase: float, axis: int | None = None, out: npt.NDArray[np.float64] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
+    image.
+
+    # Description
+
+    This function calibrates an input 3-dimensional phasor image by rotating and
+    scaling G and S coordinates by phase (φ) and modulation (M) respectively using:
+
+    g = M * cos(φ)
+    s = M * sin(φ)
+    G' = G * g - S * s
+    S' = G * s + S * g
+
+    Where G' and S' are the calibrated real and imaginary values after rotation
+    and scaling.
+
+    This function creates a new array and does not mutate the input array.
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param modulation: The modulation to scale the input (G, S) coordinates.
+    :param phase: The phase, φ angle, to rotate the input (G, S) coordinates.
+    :param axis: The channel axis, default = 2.
+    :param out: An optional float64 output array with the same shape as "data"
+        to write the calibrated values into, instead of allocating a new array.
+        "out" must not be the same array as "data", use "image_mut" to
+        calibrate in place.
+    :return: A 3-dimensional array with the calibrated phasor values, where
+        calibrated G and S are channels 0 and 1 respectively. If "out" is
+        given, "out" is returned.
+    """
+    ...
+
+def image_mut(data: npt.NDArray[np.float64], modulation: float, phase: float, axis: int | None = None) -> None:
+    r"""
+    Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
+    image.
+
+    This function calibrates an input 3-dimensional phasor image by rotating and
+    scaling G and S coordinates by phase (φ) and modulation (M) respectively using:
+
+    g = M * cos(φ)
+    s = M * sin(φ)
+    G' = G * g - S * s
+    S' = G * s + S * g
+
+    Where G' and S' are the calibrated real and imaginary values after rotation
+    and scaling. This function mutates the input data and does not create a new
+    array.
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0 and 1
+        respectively.
+    :param modulation: The modulation to scale the input (G, S) coordinates.
+    :param phase: The phase, φ angle, to rotate the intput (G, S) coorindates.
+    :param axis: The channel axis, default = 2.
+    """
+    ...
+
+def modulation_and_phase(g: float, s: float, tau: float, omega: float) -> tuple[float, float]:
+    r"""
+    Find the modulation and phase calibration values.
+
+    This function calculates the modulation and phase calibration values from
+    theoretical monoexponential coordinates (computed from "tau" and
+    "omega") and measured coordinates. The output, (M, φ), are the
+    modulation and phase values to calibrate with.
+
+    :param g: The measured real (G) value.
+    :param s: The measured imaginary (S) value.
+    :param tau: The lifetime, τ.
+    :param omega: The angular frequency, ω.
+    :param axis: The channel axis, default = 2.
+    :return: The modulation and phase calibration values, (M, φ).
+    """
+    ...
+
+def robust_from_image(data: npt.ArrayLike, tau: float, omega: float, mask: npt.NDArray[np.bool_] | None = None, estimator: str | None = None, trim: float | None = None, threshold: float | None = None, max_iterations: int | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Find the modulation and phase calibration values of a 3-dimensional
+    phasor image with iterative outlier rejection.
+
+    This function calibrates a phasor image of a monoexponential reference
+    while rejecting pixels that do not behave like the reference. Each
+    iteration estimates the calibration values from the current pixels,
+    calibrates them and rejects the pixels whose distance "d" to the expected
+    monoexponential coordinates is:
+
+    d > threshold · 1.4826 · median(d)
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param tau: The lifetime of the reference, τ.
+    :param omega: The angular frequency, ω.
+    :param mask: The 2-dimensional boolean mask of the reference pixels. If
+        "None", all pixels are used.
+    :param estimator: The center estimator, "mean", "median" or "trimmed",
+        default = "median".
+    :param trim: The fraction of the smallest and of the largest values
+        discarded by the "trimmed" estimator, default = 0.1.
+    :param threshold: The rejection threshold in robust standard deviations,
+        default = 3.0.
+    :param max_iterations: The maximum number of rejection iterations,
+        default = 10.
+    :param axis: The channel axis, default = 2.
+    :return: A dictionary with the "modulation" and "phase" calibration
+        values, the boolean "inliers" image and the number of "iterations".
+    """
+    ...
@@ -0,0 +1,30 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def image(data: npt.NDArray[np.float64], filter: str | None = None, size: int | None = None, sigma: float | None = None, wavelet: str | None = None, levels: int | None = None, repeat: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Denoise the real and imaginary (G, S) coordinates of a 3-dimensional phasor
+    image.
+
+    This function spatially filters the G and S coordinates of a phasor image
+    independently, reducing the scatter of the phasor cloud without spatially
+    binning the photons of the decay data. Pixels with NaN coordinates are
+    excluded from the median and Gaussian neighborhoods and remain NaN.
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param filter: The spatial filter, "median", "gaussian" or "wavelet",
+        default = "median".
+    :param size: The median filter window size, default = 3.
+    :param sigma: The Gaussian filter standard deviation, default = 1.0.
+    :param wavelet: The wavelet of the wavelet filter, "haar", "db2" or "db4",
+        default = "haar".
+    :param levels: The decomposition levels of the wavelet filter, default = 3.
+    :param repeat: The number of times the filter is applied, default = 1.
+    :param axis: The channel axis, default = 2.
+    :return: The denoised phasor image.
+    """
+    ...
@@ -0,0 +1,34 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def from_phase_steps(data: npt.ArrayLike, phases: list[float] | None = None, tolerance: float | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64], list[int]]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates and the DC image of a
+    phase-stepped homodyne image stack.
+
+    This function fits each pixel's phase-stepped intensities (camera-based
+    frequency-domain FLIM) with:
+
+    Iₖ = a + b * cos(φₖ) + c * sin(φₖ)
+
+    by linear least squares and returns DC = a, G = b / a and S = c / a. Bad
+    steps (e.g. dropped or saturated frames) are detected with leave-one-out
+    predictions: the step with the largest relative prediction error is
+    excluded while its error is greater than "tolerance" and at least 4 steps
+    remain. The coordinates are uncalibrated.
+
+    :param data: The phase-stepped image stack.
+    :param phases: The phase step angles in radians, default = 2πk/K (i.e. K
+        equally spaced steps over one period).
+    :param tolerance: The maximum relative leave-one-out error of a good step,
+        default = 0.1.
+    :param axis: The phase step axis, default = 0.
+    :return: A tuple of the real and imaginary coordinates as a 3-dimensional
+        (row, col, ch) image, where G and S are indexed at 0 and 1 respectively
+        on the channel axis, the DC image and the indices of the excluded bad
+        steps.
+    """
+    ...
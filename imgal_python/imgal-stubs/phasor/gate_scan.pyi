@@ -0,0 +1,52 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def decay(data: list[float], delays: list[float], width: float, period: float, bins: int | None = None, regularization: float | None = None) -> list[float]:
+    r"""
+    Reconstruct a 1-dimensional decay curve from a gate-scan acquisition.
+
+    Gated detectors (e.g. gated ICCD or SPAD cameras) integrate the decay within
+    a gate of width "width" opened at each delay. This function deconvolves the
+    overlap of the gates by regularized (ridge) least squares:
+
+    I = (AᵀA + λ s 1)⁻¹ Aᵀ G
+
+    Where "A" holds the fraction of each time bin covered by each gate (gates
+    wrap around the period) and "s" is the mean diagonal value of AᵀA. The
+    decay can be used with the "time_domain" phasor functions with the same
+    period.
+
+    :param data: The gate values, one per delay.
+    :param delays: The gate opening delays, in the unit of "period".
+    :param width: The gate width, in the unit of "period".
+    :param period: The period.
+    :param bins: The number of time bins of the reconstructed decay, default =
+        the number of gates.
+    :param regularization: The relative ridge regularization "λ", default =
+        1e-3.
+    :return: The reconstructed decay curve, in counts per time bin.
+    """
+    ...
+
+def image(data: npt.ArrayLike, delays: list[float], width: float, period: float, bins: int | None = None, regularization: float | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Reconstruct a 3-dimensional decay image from a gate-scan image stack.
+
+    This function reconstructs each pixel's decay from its gate values, see
+    "decay".
+
+    :param data: The gate-scan image stack.
+    :param delays: The gate opening delays, in the unit of "period".
+    :param width: The gate width, in the unit of "period".
+    :param period: The period.
+    :param bins: The number of time bins of the reconstructed decays, default =
+        the number of gates.
+    :param regularization: The relative ridge regularization "λ", default =
+        1e-3.
+    :param axis: The gate axis, default = 2.
+    :return: The reconstructed (row, col, bin) decay image.
+    """
+    ...
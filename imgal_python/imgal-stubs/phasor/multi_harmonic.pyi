@@ -0,0 +1,47 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def inverse_variance_mean(estimates: list[float], variances: list[float]) -> tuple[float, float]:
+    r"""
+    Combine estimates with an inverse-variance weighted mean.
+
+    This function computes the minimum variance combination of independent
+    estimates of the same quantity:
+
+    x̄ = Σ (xᵢ / σᵢ²) / Σ (1 / σᵢ²)
+    σ̄² = 1 / Σ (1 / σᵢ²)
+
+    Estimates with a non-finite value or a non-finite or non-positive variance
+    are skipped.
+
+    :param estimates: The estimates, "xᵢ".
+    :param variances: The variance of each estimate, "σᵢ²".
+    :return: The weighted mean and its variance. If no estimate is valid,
+        (NaN, NaN) is returned.
+    """
+    ...
+
+def lifetime_image(data: npt.ArrayLike, period: float, harmonics: list[int] | None = None, estimate: str | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Compute variance-weighted lifetimes of a 3-dimensional decay image from
+    multiple harmonics.
+
+    This function computes the apparent lifetime of each pixel at each
+    harmonic and merges them into one estimate with an inverse-variance
+    weighted mean. The variances follow from the Poisson statistics of the
+    decay's photons, including the covariance of G and S at each harmonic.
+    Pixels without photons are NaN.
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param harmonics: The harmonics to merge, default = [1, 2]. Each harmonic
+        must be greater than 0 and can not exceed the Nyquist limit.
+    :param estimate: The apparent lifetime estimate, "phase" or "modulation",
+        default = "phase".
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: A tuple of the merged lifetime image and its variance image.
+    """
+    ...
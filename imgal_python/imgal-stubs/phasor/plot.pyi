@@ -0,0 +1,159 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def apparent_lifetimes(data: npt.NDArray[np.float64], omega: float, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the apparent phase and modulation lifetimes of a 3-dimensional
+    phasor image.
+
+    This function back-projects the (G, S) coordinates of each pixel to the
+    apparent phase and modulation lifetimes:
+
+    tau_phi = S / (omega * G)
+    tau_m = sqrt(1 / (G^2 + S^2) - 1) / omega
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param omega: The angular frequency.
+    :param axis: The channel axis, default = 2.
+    :return: A 3-dimensional array with the apparent lifetimes, where tau_phi
+        and tau_m are channels 0 and 1 respectively.
+    """
+    ...
+
+def from_polar(data: npt.NDArray[np.float64], axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the G and S coordinates of a 3-dimensional polar phasor image.
+
+    This function is the inverse of "to_polar". It converts a 3-dimensional
+    polar phasor image of phase (φ) and modulation (M) values back into G and S
+    coordinates using:
+
+    G = M * cos(φ)
+    S = M * sin(φ)
+
+    :param data: The 3-dimensional polar phasor image, where phase and
+        modulation are channels 0 and 1 respectively.
+    :param axis: The channel axis, default = 2.
+    :return: A 3-dimensional array with the G and S coordinates, where G and S
+        are channels 0 and 1 respectively.
+    """
+    ...
+
+def map_mask(data: npt.NDArray[np.float64], g_coords: list[float], s_coords: list[float], axis: int | None = None) -> npt.NDArray[np.bool_]:
+    r"""
+    Map G and S coordinates back to the input phasor array as a boolean mask.
+
+    This function maps the G and S coordinates back to the input G/S phasor
+    array and returns a 2-dimensional boolean mask where "true" indicates
+    G and S coordiantes presentin the "g_coords" and "s_coords" arrays.
+
+    :param data: The G/S 3-dimensional array.
+    :param g_coords: A 1-dimensional array of "g" coordinates in the "data" array.
+        The "g_coords" and "s_coords" array lengths must match.
+    :param s_coords: A 1-dimensional array of "s" coordiantes in the "data" array.
+    *   The "s_coords" and "g_coords" array lengths must match.
+    :param axis: The channel axis, default = 2.
+    :return: A 2-dimensional boolean mask where "true" pixels
+        represent values found in the "g_coords" and "s_coords" arrays.
+    """
+    ...
+
+def modulation(g: float, s: float) -> float:
+    r"""
+    Compute the modulation of phasor G and S coordinates.
+
+    This function calculates the modulation (M) of phasor G and S coordinates
+    using the pythagorean theorem to find the hypotenuse (i.e. the modulation):
+
+    M = √(G² + S²)
+
+    :param g: The real component, G.
+    :param s: The imaginary component, S.
+    :return: The modulation (M) of the (G, S) phasor coordinates.
+    """
+    ...
+
+def monoexponential_coordinates(tau: float, omega: float) -> tuple[float, float]:
+    r"""
+    Compute the G and S coordinates for a monoexponential decay.
+
+    This function computes the G and S coordinates for a monoexponential decay
+    given as:
+
+    G = 1 / 1 + (ωτ)²
+    S = ωτ / 1 + (ωτ)²
+
+    :param tau: The lifetime of a monoexponential.
+    :param omega: The angular frequency.
+    :return: The single component decay coordinates, (G, S).
+    """
+    ...
+
+def phase(g: float, s: float) -> float:
+    r"""
+    Compute the phase of phasor G and S coordinates.
+
+    This function calculates the phase or phi (φ) of phasor G and S coordinates
+    using:
+
+    φ = tan⁻¹(S / G)
+
+    This implementation uses atan2 and computes the four quadrant arctanget of
+    the phasor coordinates.
+
+    :param g: The real component, G.
+    :param s: The imaginary component, S.
+    :return: The phase (phi, φ) angle of the (G, S) phasor coordinates.
+    """
+    ...
+
+def semicircle_points(taus: list[float], omega: float) -> tuple[list[float], list[float]]:
+    r"""
+    Compute the G and S coordinates of monoexponential decays on the universal
+    semicircle.
+
+    This function computes the monoexponential (G, S) coordinates for each
+    lifetime in "taus". The returned points are reference markers on the
+    universal semicircle, useful as plot layers (e.g. lifetime ticks).
+
+    :param taus: The lifetimes of the monoexponential decays.
+    :param omega: The angular frequency.
+    :return: The G and S coordinates of each lifetime in "taus".
+    """
+    ...
+
+def to_polar(data: npt.NDArray[np.float64], axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the phase and modulation polar image of a 3-dimensional phasor image.
+
+    This function converts a 3-dimensional phasor image of G and S coordinates
+    into a polar image of phase (φ) and modulation (M) values:
+
+    φ = tan⁻¹(S / G)
+    M = √(G² + S²)
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param axis: The channel axis, default = 2.
+    :return: A 3-dimensional array with the polar phasor values, where phase
+        and modulation are channels 0 and 1 respectively.
+    """
+    ...
+
+def universal_semicircle(samples: int | None = None) -> tuple[list[float], list[float]]:
+    r"""
+    Compute the G and S coordinates of the universal semicircle.
+
+    This function samples the universal semicircle, the locus of all
+    monoexponential decays in phasor space, with evenly spaced G coordinates:
+
+    S = √(G - G²), 0 ≤ G ≤ 1
+
+    :param samples: The number of points along the semicircle, default = 100.
+    :return: The G and S coordinates of the semicircle.
+    """
+    ...
@@ -0,0 +1,29 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def fraction_rgb(fractions: list[npt.NDArray[np.float64]], colors: list[tuple[int, int, int]] | None = None, intensity: npt.NDArray[np.float64] | None = None) -> npt.NDArray[np.uint8]:
+    r"""
+    Render two or three component fraction images into an RGB image.
+
+    This function maps component fraction images (e.g. from phasor unmixing)
+    into a single 8-bit RGB image. Each component is assigned a color and each
+    pixel's color is the fraction weighted sum of the component colors:
+
+    RGB = I * Σ fᵢ * Cᵢ
+
+    Where "fᵢ" is the fraction (clamped between 0.0 and 1.0) and "Cᵢ" the color
+    of component "i" and "I" is the optional normalized intensity (e.g. photon
+    count) modulation.
+
+    :param fractions: Two or three 2-dimensional component fraction images. All
+        fraction images must have the same shape.
+    :param colors: The RGB color of each component. If "None", red, green and
+        blue are used for the first, second and third components respectively.
+    :param intensity: An optional 2-dimensional intensity image (e.g. photon
+        counts) used to modulate the brightness of each pixel.
+    :return: The 3-dimensional (row, col, ch) RGB image.
+    """
+    ...
@@ -0,0 +1,436 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def adaptive_smooth(data: npt.ArrayLike, min_counts: float | None = None, max_radius: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Adaptively smooth the decays of a 3-dimensional decay image along the decay
+    axis.
+
+    This function smooths each pixel's decay with a moving average whose width
+    adapts to the local photon counts: each time bin after the decay peak is
+    averaged over the smallest window of ±h bins (h ≤ "max_radius") that holds
+    at least "min_counts" photons. Bins up to the peak (i.e. the rising edge
+    shaped by the IRF) are not smoothed, and windows do not extend over the
+    peak or past the end of the decay.
+
+    :param data: I(t), the decay data image.
+    :param min_counts: The minimum photon count of a smoothing window, default
+        = 10.0.
+    :param max_radius: The maximum half-width of a smoothing window in time
+        bins, default = 8.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The smoothed decay image.
+    """
+    ...
+
+def bin_spatial(data: npt.ArrayLike, binning: str | None = None, factor: int | None = None, radius: int | None = None, axis: int | None = None, axes: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Spatially bin the decay histograms of a 3-dimensional decay image.
+
+    This function sums the decay histograms of neighboring pixels. The "block"
+    scheme sums non-overlapping "factor" x "factor" blocks and conserves the
+    total photon count. The sliding "square" and "circular" schemes keep the
+    image size, each pixel being the sum of the decays within "radius" pixels.
+
+    :param data: I(t), the decay data image.
+    :param binning: The binning scheme, "block", "square" or "circular",
+        default = "square".
+    :param factor: The block size of the "block" scheme, default = 2.
+    :param radius: The kernel radius of the sliding schemes, default = 1.
+    :param axis: The decay or lifetime axis, default = 2.
+    :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+        the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+        and "l" are the x, y, z, channel, time and lifetime axes. The decay
+        axis is the "l" axis if present, otherwise the "t" axis.
+    :return: The binned decay image.
+    """
+    ...
+
+def curves(data: npt.ArrayLike, period: float, harmonic: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of many 1-dimensional
+    decay curves.
+
+    This function computes the phasor coordinates of each curve of a
+    (n_curves, n_bins) matrix in parallel (e.g. the decays of thousands of
+    ROIs or flow cytometry events), loading the waveform tables once. Curves
+    without photons are NaN.
+
+    :param data: I(t), the 2-dimensional matrix of decay curves.
+    :param period: The period.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of samples along "axis").
+    :param axis: The decay or lifetime axis, default = 1.
+    :return: The (n_curves, 2) coordinates, where G and S are columns 0 and 1
+        respectively.
+    """
+    ...
+
+def estimated_background_image(data: npt.ArrayLike, fraction: float | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Estimate the background count per time bin of a 3-dimensional decay image.
+
+    This function estimates the constant background of each pixel's decay as
+    the mean count of the lowest "fraction" of its time bins.
+
+    :param data: I(t), the decay data image.
+    :param fraction: The fraction of lowest count bins averaged, default = 0.1.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The estimated background count per time bin of each pixel.
+    """
+    ...
+
+def gated_image(data: npt.ArrayLike, period: float, mask: npt.NDArray[np.bool_] | None = None, harmonic: int | None = None, axis: int | None = None, axes: str | None = None, min_photons: float | None = None, min_quality: float | None = None, fill: float | None = None, out: npt.NDArray[np.float64] | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.bool_]]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+    image with photon count and histogram quality gating.
+
+    This function computes the phasor image while gating each pixel by its total
+    photon count and histogram quality in the same pass. Pixels that fail either
+    gate are set to the "fill" value and marked as invalid in the returned
+    validity mask.
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are computed. Pixels outside of the mask are set to 0.0 and are
+        invalid.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of samples along "axis").
+    :param axis: The decay or lifetime axis, default = 2.
+    :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+        the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+        and "l" are the x, y, z, channel, time and lifetime axes. The decay
+        axis is the "l" axis if present, otherwise the "t" axis.
+    :param min_photons: The minimum total photon count of a pixel's decay. If
+        "None", pixels are not gated by photon count.
+    :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+        decay. If "None", pixels are not gated by histogram quality.
+    :param fill: The G and S value of pixels that fail a gate, default = NaN.
+    :param out: An optional float64 (row, col, 2) output array to write the
+        real and imaginary coordinates into, instead of allocating a new array.
+    :return: A tuple of the real and imaginary coordinates as a 3-dimensional
+        (row, col, ch) image, where G and S are indexed at 0 and 1 respectively
+        on the channel axis, and the 2-dimensional validity mask where "true"
+        pixels passed all gates. If "out" is given, "out" is returned as the
+        coordinates image.
+    """
+    ...
+
+def histogram_quality(data: list[float], threshold: float | None = None) -> float:
+    r"""
+    Compute the histogram quality of a 1-dimensional decay curve.
+
+    This function computes the histogram quality of a decay curve as the
+    fraction of time bins with counts greater than or equal to the bin
+    threshold:
+
+    Q = |{i : I(tᵢ) ≥ threshold}| / n
+
+    :param data: I(t), the 1-dimensional decay curve.
+    :param threshold: The minimum count of a populated time bin, default = 1.0.
+    :return: The histogram quality, ranging between 0.0 and 1.0.
+    """
+    ...
+
+def histogram_quality_image(data: npt.ArrayLike, metric: str | None = None, threshold: float | None = None, fraction: float | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the histogram quality image of a 3-dimensional decay image.
+
+    This function computes the histogram quality of each pixel's decay with
+    one of the following measures:
+
+    - "count": the fraction of time bins with counts greater than or equal to
+      "threshold".
+    - "fisher": a Fisher information proxy of the decay shape,
+      F = Σ 2 (I(tᵢ₊₁) - I(tᵢ))² / (I(tᵢ₊₁) + I(tᵢ)).
+    - "sbr": the signal to background photon ratio, with the background
+      estimated from the lowest "fraction" of time bins.
+
+    :param data: I(t), the decay data image.
+    :param metric: The quality measure, "count", "fisher" or "sbr", default =
+        "count".
+    :param threshold: The minimum count of a populated time bin of the "count"
+        measure, default = 1.0.
+    :param fraction: The fraction of lowest count bins used as background by
+        the "sbr" measure, default = 0.1.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The histogram quality of each pixel.
+    """
+    ...
+
+def image(data: npt.ArrayLike, period: float, mask: npt.NDArray[np.bool_] | None = None, harmonic: int | None = None, axis: int | None = None, axes: str | None = None, min_photons: float | None = None, min_quality: float | None = None, out: npt.NDArray[np.float64] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+    image.
+
+    The real (G) and imaginary (S) components are calculated using the normalized
+    sine and cosine Fourier transforms:
+
+    S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+    G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+
+    Pixels can optionally be gated by their total photon count and histogram
+    quality. Pixels that fail either gate are set to NaN.
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are computed. Pixels outside of the mask are set to 0.0.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of samples along "axis").
+    :param axis: The decay or lifetime axis, default = 2.
+    :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+        the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+        and "l" are the x, y, z, channel, time and lifetime axes. The decay
+        axis is the "l" axis if present, otherwise the "t" axis.
+    :param min_photons: The minimum total photon count of a pixel's decay. If
+        "None", pixels are not gated by photon count.
+    :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+        decay. If "None", pixels are not gated by histogram quality.
+    :param out: An optional float64 (row, col, 2) output array to write the
+        real and imaginary coordinates into, instead of allocating a new array.
+    :return: The real and imaginary coordinates as a 3-dimensional (row, col, ch)
+        image, where G and S are indexed at 0 and 1 respectively on the channel axis.
+        If "out" is given, "out" is returned.
+    """
+    ...
+
+def image_multiharmonic(data: npt.ArrayLike, period: float, harmonics: list[int], mask: npt.NDArray[np.bool_] | None = None, axis: int | None = None, axes: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+    image at multiple harmonics.
+
+    This function computes the phasor image for each harmonic "n" in
+    "harmonics" into a single stacked image:
+
+    Gₙ = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+    Sₙ = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param harmonics: The harmonic values. Each harmonic must be greater than 0
+        and can not exceed the Nyquist limit (i.e. half the number of samples
+        along "axis").
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are computed. Pixels outside of the mask are set to 0.0.
+    :param axis: The decay or lifetime axis, default = 2.
+    :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+        the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+        and "l" are the x, y, z, channel, time and lifetime axes. The decay
+        axis is the "l" axis if present, otherwise the "t" axis.
+    :return: The real and imaginary coordinates as a 4-dimensional
+        (harmonic, row, col, ch) image, where the harmonic axis follows the
+        order of "harmonics" and G and S are indexed at 0 and 1 respectively on
+        the channel axis.
+    """
+    ...
+
+def image_with_time_points(data: npt.ArrayLike, period: float, time_points: list[float], mask: npt.NDArray[np.bool_] | None = None, harmonic: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+    image sampled at non-uniform time points.
+
+    This function computes the phasor image for decays whose time bins are
+    not uniform (e.g. TCSPC hardware with nonlinear time bins). Each bin is
+    weighted by its width, from its time point to the next one (or to "period"
+    for the last bin):
+
+    G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+    S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+
+    :param data: I(t), the decay data image.
+    :param period: The period.
+    :param time_points: The start time of each time bin, strictly increasing
+        from 0.0 and below "period", with the length of the decay axis.
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are computed. Pixels outside of the mask are set to 0.0.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed half the number of time bins.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The real and imaginary coordinates as a 3-dimensional
+        (row, col, ch) image, where G and S are indexed at 0 and 1
+        respectively on the channel axis.
+    """
+    ...
+
+def imaginary(data: list[float], period: float, harmonic: int | None = None) -> float:
+    r"""
+    Compute the imaginary (S) component of a 1-dimensional decay curve.
+
+    The imaginary (S) component is calculated using the normalized sine Fourier
+    transform:
+
+    S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+
+    Where 'n' and 'ω' are harmonic and omega values respectively.
+
+    :param data: I(t), the 1-dimensional decay curve.
+    :param period: The period.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of samples in "data").
+    :return: The imaginary component, S.
+    """
+    ...
+
+def imaginary_with_time_points(data: list[float], period: float, time_points: list[float], harmonic: int | None = None) -> float:
+    r"""
+    Compute the imaginary (S) component of a 1-dimensional decay curve sampled
+    at non-uniform time points.
+
+    Each time bin is weighted by its width, from its time point to the next
+    one (or to "period" for the last bin):
+
+    S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+
+    :param data: I(t), the 1-dimensional decay curve.
+    :param period: The period.
+    :param time_points: The start time of each time bin, strictly increasing
+        from 0.0 and below "period", with the length of "data".
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed half the number of samples in "data".
+    :return: The imaginary component, S.
+    """
+    ...
+
+def peak_bin_image(data: npt.ArrayLike, axis: int | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Compute the peak time bin image of a 3-dimensional decay image.
+
+    This function finds the time bin index of the maximum count of each pixel's
+    decay. Ties resolve to the first bin.
+
+    :param data: I(t), the decay data image.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The peak time bin index of each pixel.
+    """
+    ...
+
+def pileup_correct(data: npt.ArrayLike, count_rate: float, repetition_rate: float, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Correct the pile-up distortion of a 3-dimensional TCSPC decay image.
+
+    This function applies the Coates correction to each pixel's decay:
+
+    N = Σ nᵢ * f / r
+    nᵢ' = -N ln(1 - nᵢ / (N - Σⱼ₍ⱼ₌₀..ᵢ₋₁₎ nⱼ))
+
+    Where "nᵢ" are the counts of time bin "i", "f" is the excitation repetition
+    rate, "r" is the detected count rate and "N" is the number of excitation
+    cycles of the pixel. The first time bin must be the earliest after
+    excitation. This function creates a new array and does not mutate the input
+    array.
+
+    :param data: I(t), the decay data image.
+    :param count_rate: The detected photon count rate while acquiring a pixel,
+        in the unit of "repetition_rate".
+    :param repetition_rate: The excitation (laser) repetition rate.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The pile-up corrected decay image.
+    """
+    ...
+
+def pileup_correct_mut(data: npt.NDArray[np.float64], count_rate: float, repetition_rate: float, axis: int | None = None) -> None:
+    r"""
+    Correct the pile-up distortion of a 3-dimensional TCSPC decay image in
+    place.
+
+    This function applies the Coates pile-up correction to each pixel's decay,
+    see "pileup_correct". This function mutates the input data and does not
+    create a new array.
+
+    :param data: I(t), the float64 decay data image.
+    :param count_rate: The detected photon count rate while acquiring a pixel,
+        in the unit of "repetition_rate".
+    :param repetition_rate: The excitation (laser) repetition rate.
+    :param axis: The decay or lifetime axis, default = 2.
+    """
+    ...
+
+def real(data: list[float], period: float, harmonic: int | None = None) -> float:
+    r"""
+    Compute the real (G) component of a 1-dimensional decay curve.
+
+    The real (G) component is calculated using the normalized cosine Fourier
+    transform:
+
+    G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+
+    Where 'n' and 'ω' are harmonic and omega values respectively.
+
+    :param data: I(t), the 1-dimensional decay curve.
+    :param period: The period.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of samples in "data").
+    :return: The real component, G.
+    """
+    ...
+
+def real_with_time_points(data: list[float], period: float, time_points: list[float], harmonic: int | None = None) -> float:
+    r"""
+    Compute the real (G) component of a 1-dimensional decay curve sampled at
+    non-uniform time points.
+
+    Each time bin is weighted by its width, from its time point to the next
+    one (or to "period" for the last bin):
+
+    G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+
+    :param data: I(t), the 1-dimensional decay curve.
+    :param period: The period.
+    :param time_points: The start time of each time bin, strictly increasing
+        from 0.0 and below "period", with the length of "data".
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed half the number of samples in "data".
+    :return: The real component, G.
+    """
+    ...
+
+def total_counts_image(data: npt.ArrayLike, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the total photon count image of a 3-dimensional decay image.
+
+    :param data: I(t), the decay data image.
+    :param axis: The decay or lifetime axis, default = 2.
+    :return: The sum of each pixel's decay.
+    """
+    ...
+
+def volume(data: npt.ArrayLike, period: float, mask: npt.NDArray[np.bool_] | None = None, harmonic: int | None = None, binning: str | None = None, factor: int | None = None, radius: int | None = None, z_radius: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of a 4-dimensional decay
+    volume.
+
+    This function computes the phasor coordinates of each voxel of a
+    (z, row, col, bin) decay volume (e.g. a light-sheet FLIM volume). Before
+    phasor computation, the decays can be spatially binned within each slice
+    (see "bin_spatial") and summed over "z_radius" neighboring slices along z,
+    binning volumes with a coarser axial sampling anisotropically.
+
+    :param data: I(t), the 4-dimensional (z, row, col, bin) decay volume.
+    :param period: The period.
+    :param mask: An optional 3-dimensional (z, row, col) boolean mask, only
+        voxels set to "true" are computed. Voxels outside of the mask are set
+        to 0.0. The mask must match the binned volume shape.
+    :param harmonic: The harmonic value, default = 1. The harmonic must be
+        greater than 0 and can not exceed the Nyquist limit (i.e. half the
+        number of time bins).
+    :param binning: The spatial binning scheme within each slice, "block",
+        "square" or "circular". If "None", the slices are not binned.
+    :param factor: The block size of the "block" scheme, default = 2.
+    :param radius: The kernel radius of the sliding schemes, default = 1.
+    :param z_radius: The number of slices summed above and below each slice,
+        default = 0.
+    :return: The real and imaginary coordinates as a 4-dimensional
+        (z, row, col, ch) volume, where G and S are indexed at 0 and 1
+        respectively on the channel axis.
+    """
+    ...
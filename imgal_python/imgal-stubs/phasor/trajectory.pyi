@@ -0,0 +1,76 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def image_series(data: npt.ArrayLike, period: float, mask: npt.NDArray[np.bool_] | None = None, harmonic: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the real and imaginary (G, S) coordinates of each frame in a
+    4-dimensional time-lapse decay stack.
+
+    This function computes the phasor image of each frame in a time-lapse decay
+    stack with the shape (t, row, col, bin). The per-frame phasor images are
+    stacked along the first axis.
+
+    :param data: I(t), the 4-dimensional (t, row, col, bin) time-lapse decay
+        stack.
+    :param period: The period.
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are computed. Pixels outside of the mask are set to 0.0.
+    :param harmonic: The harmonic value, default = 1.
+    :return: The per-frame real and imaginary coordinates as a 4-dimensional
+        (t, row, col, ch) image, where G and S are indexed at 0 and 1
+        respectively on the channel axis.
+    """
+    ...
+
+def roi_trajectory(data: npt.NDArray[np.float64], roi: npt.NDArray[np.bool_]) -> tuple[list[float], list[float]]:
+    r"""
+    Compute the mean ROI phasor trajectory of a phasor time series.
+
+    This function computes the mean G and S coordinates of the pixels within a
+    region of interest (ROI) for each frame of a 4-dimensional (t, row, col, ch)
+    phasor time series. NaN pixels (e.g. gated pixels) are ignored.
+
+    :param data: The 4-dimensional (t, row, col, ch) phasor time series, where
+        G and S are channels 0 and 1 respectively.
+    :param roi: The 2-dimensional boolean ROI mask, where "true" pixels are in
+        the ROI.
+    :return: The mean G and S coordinates of the ROI for each frame.
+    """
+    ...
+
+def statistics(g: list[float], s: list[float], frame_interval: float | None = None) -> tuple[float, float, float, float]:
+    r"""
+    Compute the displacement, direction, and velocity statistics of a phasor
+    trajectory.
+
+    This function computes statistics of a phasor trajectory, a sequence of
+    (G, S) coordinates over time:
+
+    D = √((Gₙ - G₀)² + (Sₙ - S₀)²)
+    θ = tan⁻¹((Sₙ - S₀) / (Gₙ - G₀))
+    L = Σ √((Gᵢ₊₁ - Gᵢ)² + (Sᵢ₊₁ - Sᵢ)²)
+    v = L / (n * Δt)
+
+    :param g: The G coordinates of the trajectory.
+    :param s: The S coordinates of the trajectory.
+    :param frame_interval: The time between frames, default = 1.0.
+    :return: The net displacement, direction (radians), path length and mean
+        velocity of the trajectory.
+    """
+    ...
+
+def statistics_image(data: npt.NDArray[np.float64], frame_interval: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the per-pixel trajectory statistics of a phasor time series.
+
+    :param data: The 4-dimensional (t, row, col, ch) phasor time series, where
+        G and S are channels 0 and 1 respectively.
+    :param frame_interval: The time between frames, default = 1.0.
+    :return: A 3-dimensional (row, col, ch) array with the net displacement,
+        direction, path length and mean velocity at channels 0, 1, 2 and 3
+        respectively.
+    """
+    ...
@@ -0,0 +1,55 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def fit_line(data: npt.NDArray[np.float64], omega: float, weights: npt.NDArray[np.float64] | None = None, mask: npt.NDArray[np.bool_] | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Find the two component lifetimes and fraction images of a phasor image.
+
+    This function performs a graphical global analysis of a phasor image whose
+    pixels are mixtures of the same two monoexponential components. A line is
+    fitted through the phasor cloud by weighted total least squares and
+    intersected with the universal semicircle, (G - 0.5)² + S² = 0.25. The
+    intersections are the component phasors, whose lifetimes are
+    τ = S / (ωG), and the fraction images of the components are computed for
+    every pixel (see "fractions").
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param omega: The angular frequency.
+    :param weights: An optional 2-dimensional weight image (e.g. photon
+        counts). If "None", all pixels have the same weight.
+    :param mask: An optional 2-dimensional boolean mask, only pixels set to
+        "true" are used to fit the line.
+    :param axis: The channel axis, default = 2.
+    :return: A dictionary with the weighted "centroid" (G, S) and unit
+        "direction" (G, S) of the line, the component "taus" (shortest first),
+        their (G, S) "points" on the universal semicircle and the (row, col, ch)
+        "fractions" image of the components.
+    """
+    ...
+
+def fractions(data: npt.NDArray[np.float64], taus: tuple[float, float], omega: float, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the fraction images of two known components of a phasor image.
+
+    This function projects each pixel's phasor onto the line through the
+    phasors of two monoexponential components and computes the fraction of
+    each component:
+
+    f₂ = ((P - P₁) · (P₂ - P₁)) / |P₂ - P₁|²
+    f₁ = 1 - f₂
+
+    Fractions are not clamped and pixels with NaN coordinates remain NaN.
+
+    :param data: The 3-dimensional phasor image, where G and S are channels 0
+        and 1 respectively.
+    :param taus: The lifetimes of the two components.
+    :param omega: The angular frequency.
+    :param axis: The channel axis, default = 2.
+    :return: The (row, col, ch) fraction image, where the fractions of the
+        components of "taus" are channels 0 and 1 respectively.
+    """
+    ...
@@ -0,0 +1,61 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def count_nuclei(data: npt.ArrayLike, background_sigma: float | None = None, smooth_sigma: float | None = None, threshold: float | None = None, min_separation: int | None = None, min_area: int | None = None, spacing: tuple[float, float] | None = None) -> dict[str, Any]:
+    r"""
+    Count and measure the nuclei (or colonies) of a 2-dimensional image.
+
+    This function chains a classic nucleus counting workflow: it subtracts a
+    wide Gaussian background, smooths and thresholds the image (with Otsu's
+    threshold by default), splits touching nuclei with a watershed of the
+    distance transform seeded at its maxima, removes small regions and
+    measures the remaining nuclei.
+
+    :param data: The input 2-dimensional image of bright nuclei.
+    :param background_sigma: The standard deviation in pixels of the Gaussian
+        background estimate, 0.0 disables the background subtraction,
+        default = 25.0.
+    :param smooth_sigma: The standard deviation in pixels of the smoothing
+        applied before thresholding, 0.0 disables the smoothing, default = 1.0.
+    :param threshold: The foreground threshold of the background subtracted
+        image. If "None", Otsu's threshold is used.
+    :param min_separation: The minimum distance in pixels between the seeds of
+        touching nuclei, default = 5.
+    :param min_area: The minimum area in pixels of a nucleus, default = 20.
+    :param spacing: The (row, col) pixel size of the measurements,
+        default = (1.0, 1.0) (pixels).
+    :return: A dictionary with the nucleus "count", the "labels" image and the
+        "properties" list of region property dictionaries (see
+        "segmentation.region_properties").
+    """
+    ...
+
+def run(data: npt.ArrayLike, steps: list[Any]) -> npt.NDArray[Any]:
+    r"""
+    Run a chain of 2-dimensional image processing steps.
+
+    This function builds a pipeline from a list of steps and runs it on a
+    2-dimensional image. The input is converted to floating point once, all
+    Gaussian steps share a single scratch buffer and consecutive Gaussian steps
+    are fused into one pass. The steps are validated before any computation.
+    Each step is given as a name or a (name, parameter) tuple:
+
+    - "gaussian": Gaussian smoothing, the parameter is sigma (required).
+    - "threshold": Manual threshold, the parameter is the threshold value
+      (required).
+    - "threshold_otsu": Otsu's threshold, the parameter is the number of
+      histogram bins, default = 256.
+    - "label": Connected component labeling, the parameter is the pixel
+      connectivity 4 or 8, default = 8.
+
+    For example: [("gaussian", 1.0), "threshold_otsu", "label"].
+
+    :param data: The input 2-dimensional image.
+    :param steps: The list of pipeline steps.
+    :return: The output of the last step, a float64 image, a boolean mask or
+        a label image.
+    """
+    ...
@@ -0,0 +1,115 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def align_channels(data: npt.ArrayLike, reference: int | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Align the channels of a multi-channel 2-dimensional image.
+
+    This function corrects lateral chromatic shifts between channels. The
+    translation of each channel relative to the reference channel is estimated
+    with phase correlation and the channel is resampled with bilinear
+    interpolation. Only translations are corrected.
+
+    :param data: The input 3-dimensional multi-channel image.
+    :param reference: The index of the reference channel, default = 0.
+    :param axis: The channel axis, default = 0.
+    :return: The aligned image and the (row, col) shift applied to each channel
+        with a shape of (channels, 2).
+    """
+    ...
+
+def apply_chromatic_correction(data: npt.ArrayLike, correction: dict[str, Any], axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Correct the chromatic aberration of a multi-channel image.
+
+    This function resamples each channel of a multi-channel image onto the
+    reference channel coordinates with a chromatic aberration model (see
+    "chromatic_correction") and bilinear interpolation. Pixels mapped from
+    outside the image are 0.0.
+
+    :param data: The 3-dimensional multi-channel image.
+    :param correction: The chromatic aberration model dictionary, with at
+        least the "order" and "coefficients" entries.
+    :param axis: The channel axis, default = 0.
+    :return: The corrected image, with the shape of "data".
+    """
+    ...
+
+def chromatic_correction(data: npt.ArrayLike, radius: int, order: int | None = None, reference: int | None = None, max_distance: float | None = None, axis: int | None = None) -> dict[str, Any]:
+    r"""
+    Measure the chromatic aberration of a multi-channel bead image.
+
+    This function localizes the beads of each channel of a multi-color bead
+    image, pairs each reference bead with the nearest bead of every other
+    channel within "max_distance" pixels, and fits a polynomial mapping from the
+    reference to the channel coordinates by least squares, with the terms in
+    the order 1, r, c, r², r·c, c², ... up to "order".
+
+    :param data: The 3-dimensional multi-channel bead image.
+    :param radius: The bead detection and fit radius in pixels.
+    :param order: The polynomial order, default = 1 (affine).
+    :param reference: The index of the reference channel, default = 0.
+    :param max_distance: The maximum distance between paired beads in pixels,
+        default = radius.
+    :param axis: The channel axis, default = 0.
+    :return: A dictionary chromatic aberration model with the "order", the
+        "reference" channel, the (row, col) polynomial "coefficients" of each
+        channel, the root mean square "residual" of each channel in pixels and
+        the number of paired beads "n_beads" of each channel.
+    """
+    ...
+
+def phase_correlation(reference: npt.ArrayLike, moving: npt.ArrayLike) -> tuple[float, float]:
+    r"""
+    Estimate the translation between two 2-dimensional images with phase
+    correlation.
+
+    The peak of the inverse Fourier transform of the normalized cross-power
+    spectrum is refined to subpixel precision with a parabola fit.
+
+    :param reference: The reference 2-dimensional image.
+    :param moving: The moving 2-dimensional image, with the same shape and dtype
+        as "reference".
+    :return: The (row, col) shift to apply to "moving" to align it with
+        "reference".
+    """
+    ...
+
+def stitch(tiles: npt.ArrayLike, positions: list[tuple[float, float]], blend: str | None = None, max_shift: float | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+    r"""
+    Stitch overlapping 2-dimensional tiles into a mosaic.
+
+    This function refines the nominal tile positions of a tiled acquisition and
+    fuses the tiles into a single image. Pairwise offsets of overlapping tiles
+    are estimated with phase correlation, refined by normalized
+    cross-correlation and globally optimized by least squares, with the first
+    tile fixed.
+
+    :param tiles: A list of 2-dimensional tiles with the same dtype.
+    :param positions: The nominal (row, col) position of the top left corner of
+        each tile in pixels.
+    :param blend: The blending mode, "linear" or "feather", default = "feather".
+    :param max_shift: The largest accepted correction of a pairwise offset in
+        pixels, default = no limit.
+    :return: The fused mosaic and the optimized tile positions with a shape of
+        (tiles, 2), relative to the mosaic origin.
+    """
+    ...
+
+def translate(data: npt.ArrayLike, shift: tuple[float, float], fill: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Translate a 2-dimensional image by a subpixel shift.
+
+    The image is shifted with bilinear interpolation, such that
+    output[r, c] = data[r - shift[0], c - shift[1]].
+
+    :param data: The input 2-dimensional image.
+    :param shift: The (row, col) shift in pixels.
+    :param fill: The value of pixels mapped from outside the image, default =
+        0.0.
+    :return: The translated image.
+    """
+    ...
@@ -0,0 +1,241 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def expand_labels(labels: npt.NDArray[np.uintp], distance: int) -> npt.NDArray[np.uintp]:
+    r"""
+    Expand the regions of a 2-dimensional label image into the background.
+
+    This function assigns each background pixel within "distance" pixels of a
+    region to its nearest region, so expanding regions stop where they meet
+    and never overwrite each other.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param distance: The expansion distance in pixels.
+    :return: The expanded label image.
+    """
+    ...
+
+def felzenszwalb(data: npt.ArrayLike, scale: float | None = None, sigma: float | None = None, min_size: int | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Segment a 2-dimensional image with the Felzenszwalb graph-based algorithm.
+
+    This function over-segments an image into regions of similar intensity
+    (i.e. unsupervised region proposals), where intensities are normalized to
+    the range 0.0 to 1.0.
+
+    :param data: The input 2-dimensional image.
+    :param scale: The scale, larger values produce larger regions, default =
+        1.0.
+    :param sigma: The standard deviation of the Gaussian smoothing applied
+        before segmentation, default = 0.8.
+    :param min_size: The minimum region size in pixels, default = 20.
+    :return: The label image, where regions are labeled from 1 in raster order.
+    """
+    ...
+
+def kmeans_features(features: npt.ArrayLike, k: int, max_iterations: int | None = None, standardize: bool | None = None, seed: int | None = None, axis: int | None = None) -> tuple[npt.NDArray[np.uintp], list[list[float]]]:
+    r"""
+    Cluster the per-pixel feature vectors of a 2-dimensional image into "k"
+    classes.
+
+    This function segments an image by k-means clustering of the feature
+    vector of each pixel, assembled as a 3-dimensional stack of feature
+    channels (e.g. intensity, phasor G and S coordinates and texture features).
+    The features are standardized by default, centroids are initialized with
+    k-means++ and refined with Lloyd's algorithm. Pixels with a NaN feature are
+    not clustered.
+
+    :param features: The 3-dimensional stack of feature channels.
+    :param k: The number of classes.
+    :param max_iterations: The maximum number of iterations, default = 100.
+    :param standardize: Whether to standardize each feature before
+        clustering, default = True.
+    :param seed: Pseudorandom number generator seed. If "None", a random seed
+        is drawn.
+    :param axis: The feature axis, default = 2.
+    :return: A tuple of the label map, with the classes labeled from 1 to "k"
+        in order of increasing first feature centroid and 0 for pixels with a
+        NaN feature, and the list of class centroids in feature units.
+    """
+    ...
+
+def label(mask: npt.NDArray[np.bool_], connectivity: str | None = None) -> tuple[npt.NDArray[np.uintp], int]:
+    r"""
+    Label the connected components of a 2-dimensional boolean mask.
+
+    This function labels each connected region of "true" pixels with a unique
+    integer. Labels are consecutive, starting at 1 in raster order of each
+    region's first pixel, while background pixels are 0.
+
+    :param mask: The 2-dimensional boolean mask.
+    :param connectivity: The pixel connectivity, "4" (pixels sharing an edge)
+        or "8" (pixels sharing an edge or a corner), default = "8".
+    :return: A tuple of the label image and the number of labels.
+    """
+    ...
+
+def label_adjacency(labels: npt.NDArray[np.uintp], connectivity: str | None = None) -> list[tuple[int, int]]:
+    r"""
+    Find the pairs of touching labels of a 2-dimensional label image.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+    :return: The sorted list of (a, b) pairs of adjacent labels, with a < b.
+    """
+    ...
+
+def label_boundaries(labels: npt.NDArray[np.uintp], connectivity: str | None = None) -> npt.NDArray[np.bool_]:
+    r"""
+    Extract the boundaries of the regions of a 2-dimensional label image.
+
+    This function marks the labeled pixels that have a neighbor with a
+    different label, including the background, as boundary pixels. The image
+    edges are not boundaries.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+    :return: The boundary mask.
+    """
+    ...
+
+def merge_adjacent(labels: npt.NDArray[np.uintp], predicate: npt.ArrayLike, connectivity: str | None = None, spacing: tuple[float, float] | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Merge the adjacent regions of a 2-dimensional label image that satisfy a
+    predicate.
+
+    This function finds the pairs of touching labels, measures their regions
+    and merges the pairs for which "predicate" returns True. The predicate is
+    called with the region property dictionaries (see "region_properties") of
+    both regions, before any merge.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param predicate: The merge predicate, a callable of two region property
+        dictionaries returning a bool.
+    :param connectivity: The pixel connectivity of adjacency, "4" or "8",
+        default = "8".
+    :param spacing: The (row, col) pixel size of the region measurements,
+        default = (1.0, 1.0) (pixels).
+    :return: The merged label image.
+    """
+    ...
+
+def merge_labels(labels: npt.NDArray[np.uintp], pairs: list[tuple[int, int]]) -> npt.NDArray[np.uintp]:
+    r"""
+    Merge labels of a 2-dimensional label image.
+
+    This function merges each (a, b) pair of labels into a single region.
+    Chains of pairs merge into one region, which takes the smallest label of
+    the chain. Labels are not renumbered, see "relabel_sequential".
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param pairs: The list of (a, b) label pairs to merge.
+    :return: The merged label image.
+    """
+    ...
+
+def propagate_labels(seeds: npt.NDArray[np.uintp], barrier: npt.ArrayLike, regularization: float | None = None, distance: float | None = None, mask: npt.NDArray[np.bool_] | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Expand seed regions (e.g. nuclei) into cells constrained by an intensity
+    barrier (e.g. a membrane channel).
+
+    This function grows each seed region along the shortest paths of an image
+    manifold, where the cost of a step from pixel p to q is
+    √(λ·|p - q|² + (I(q) - I(p))²) with the barrier image I rescaled to 0.0 to
+    1.0, so regions stop at bright barriers. A large "regularization" tends to a
+    Voronoi partition of the seeds (see "expand_labels").
+
+    :param seeds: The 2-dimensional seed label image, where 0 is unlabeled.
+    :param barrier: The 2-dimensional barrier image with the same shape as
+        "seeds".
+    :param regularization: The spatial distance weight λ, default = 0.05.
+    :param distance: The maximum expansion path length in pixels. If "None",
+        the expansion is not limited.
+    :param mask: The 2-dimensional boolean mask of the pixels to expand into.
+        If "None", all pixels are expanded into.
+    :return: The expanded label image, pixels unreachable from a seed are 0.
+    """
+    ...
+
+def random_walker(data: npt.ArrayLike, seeds: npt.NDArray[np.uintp], beta: float | None = None, tolerance: float | None = None, max_iterations: int | None = None) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.uintp]]:
+    r"""
+    Segment a 2-dimensional image with the random walker algorithm.
+
+    This function segments an image from a set of labeled seed pixels. Each
+    unlabeled pixel is assigned the probability that a random walker starting
+    at that pixel first reaches a seed of each label, where walkers rarely
+    cross strong image edges.
+
+    :param data: The input 2-dimensional image.
+    :param seeds: The 2-dimensional seed label image, where unlabeled pixels are
+        0 and seeds are labeled 1 to n_labels.
+    :param beta: The edge weight penalty, default = 130.0.
+    :param tolerance: The relative residual tolerance of the conjugate gradient
+        solver, default = 1e-6.
+    :param max_iterations: The maximum number of conjugate gradient iterations,
+        default = 1000.
+    :return: The label probabilities with a shape of (n_labels, row, col) and
+        the label image.
+    """
+    ...
+
+def region_properties(labels: npt.NDArray[np.uintp], spacing: tuple[float, float] | None = None) -> list[dict[str, Any]]:
+    r"""
+    Measure the labeled regions of a 2-dimensional label image.
+
+    This function measures the area, centroid, bounding box and equivalent
+    diameter of each labeled region. With a pixel size, areas and lengths are
+    reported in physical units (e.g. µm² and µm).
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param spacing: The (row, col) pixel size, default = (1.0, 1.0) (pixels).
+    :return: A list with a dictionary of properties for each region present in
+        "labels", sorted by label, with the "label", "pixel_count", "area",
+        "centroid" (row, col), "bbox" (min row, min col, max row, max col) with
+        exclusive max bounds, and "equivalent_diameter" keys.
+    """
+    ...
+
+def relabel_sequential(labels: npt.NDArray[np.uintp]) -> tuple[npt.NDArray[np.uintp], int]:
+    r"""
+    Relabel a 2-dimensional label image with consecutive labels.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :return: A tuple of the relabeled image, with consecutive labels from 1 in
+        the order of the original labels, and the number of labels.
+    """
+    ...
+
+def shrink_labels(labels: npt.NDArray[np.uintp], distance: int) -> npt.NDArray[np.uintp]:
+    r"""
+    Shrink the regions of a 2-dimensional label image.
+
+    This function removes the labeled pixels within "distance" pixels of a
+    pixel with a different label, including the background.
+
+    :param labels: The 2-dimensional label image, where 0 is background.
+    :param distance: The shrinking distance in pixels.
+    :return: The shrunk label image.
+    """
+    ...
+
+def watershed(surface: npt.ArrayLike, markers: npt.NDArray[np.uintp], mask: npt.NDArray[np.bool_] | None = None, connectivity: str | None = None) -> npt.NDArray[np.uintp]:
+    r"""
+    Segment a 2-dimensional image by marker-controlled watershed.
+
+    This function floods the "surface" from the labeled "markers" in order of
+    increasing surface value, so each pixel receives the label of the marker
+    whose basin reaches it first. A typical use separates touching objects by
+    flooding the negated distance transform of their mask from its maxima.
+
+    :param surface: The 2-dimensional surface to flood.
+    :param markers: The 2-dimensional marker image with the same shape as
+        "surface", where 0 is unlabeled.
+    :param mask: The 2-dimensional boolean mask of the pixels to flood. If
+        "None", all pixels are flooded.
+    :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+    :return: The label image, pixels unreachable from a marker are 0.
+    """
+    ...
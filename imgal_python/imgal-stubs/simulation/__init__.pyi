@@ -0,0 +1,5 @@
+# This file is generated by stub_gen, do not edit.
+
+from . import decay as decay
+from . import instrument as instrument
+from . import noise as noise
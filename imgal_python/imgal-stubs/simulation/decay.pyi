@@ -0,0 +1,225 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def gaussian_exponential_1d(samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float, irf_center: float, irf_width: float) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate a 1-dimensional Gaussian IRF convolved monoexponential or
+    multiexponential decay curve.
+
+    This function generates a 1-dimensonal Gaussian instrument response function
+    (IRF) convolved monoexponential or multiexponential decay curve. The ideal
+    decay curve is defined as the sum of one or more exponential components,
+    each characterized by a lifetime (tau) and fractional intensity:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :param irf_center: The temporal position of the IRF peak within the time range.
+    :param irf_width: The full width at half maximum (FWHM) of the IRF.
+    :return: The 1-dimensonal Gaussian IRF convolved monoexponential
+        or multiexponential decay curve.
+    """
+    ...
+
+def gaussian_exponential_3d(samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float, irf_center: float, irf_width: float, shape: tuple[int, int]) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate a 3-dimensional Gaussian IRF convolved monoexponential or
+    multiexponential decay curve.
+
+    This function generates a 3-dimensonal Gaussian instrument response function
+    (IRF) convolved monoexponential or multiexponential decay curve. The ideal
+    decay curve is defined as the sum of one or more exponential components,
+    each characterized by a lifetime (tau) and fractional intensity:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :param irf_center: The temporal position of the IRF peak within the time range.
+    :param irf_width: The full width at half maximum (FWHM) of the IRF.
+    :param shape: The row and col shape to broadcast the decay curve into.
+    :return: The 3-dimensional Gaussian IRF convolved monoexponential
+        or multiexponential decay curve.
+    """
+    ...
+
+def ideal_exponential_1d(samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate an ideal 1-dimensional monoexponential or multiexponential decay
+    curve.
+
+    This function generates a 1-dimensonal ideal exponential decay curve by
+    computing the sum of one or more exponential components, each characterized
+    by a lifetime (tau) and fractional intensity as defined by:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    where αᵢ are the pre-exponential factors derived from the fractional
+    intensities and lifetimes.
+
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :return: The 1-dimensonal monoexponential or multiexponential
+        decay curve.
+    """
+    ...
+
+def ideal_exponential_1d(samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate an ideal 1-dimensional monoexponential or multiexponential decay
+    curve.
+
+    This function generates a 1-dimensonal ideal exponential decay curve by
+    computing the sum of one or more exponential components, each characterized
+    by a lifetime (tau) and fractional intensity as defined by:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    where αᵢ are the pre-exponential factors derived from the fractional
+    intensities and lifetimes.
+
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :return: The 1-dimensonal monoexponential or multiexponential
+        decay curve.
+    """
+    ...
+
+def ideal_exponential_3d(samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float, shape: tuple[int, int]) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate an ideal 3-dimensional monoexponential or multiexponential decay
+    curve.
+
+    This function generates a 3-dimensonal ideal exponential decay curve by
+    computing the sum of one or more exponential components, each characterized
+    by a lifetime (tau) and fractional intensity as defined by:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    where αᵢ are the pre-exponential factors derived from the fractional
+    intensities and lifetimes.
+
+    <https://doi.org/10.1111/j.1749-6632.1969.tb56231.x>
+
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :param shape: The row and col shape to broadcast the decay curve into.
+    :return: The 3-dimensonal monoexponential or multiexponential
+        decay curve.
+    """
+    ...
+
+def irf_exponential_1d(irf: list[float], samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate a 1-dimensional IRF convolved monoexponential or multiexponential
+    decay curve.
+
+    This function generates a 1-dimensonal instrument response function (IRF)
+    convolved monoexponential or multiexponential decay curve. The ideal
+    decay curve is defined as the sum of one or more exponential components,
+    each characterized by a lifetime (tau) and fractional intensity:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    :param irf: The IRF as a 1-dimensonal array.
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :return: The 1-dimensional IRF convolved monoexponential or
+        multiexponential decay curve.
+    """
+    ...
+
+def irf_exponential_3d(irf: list[float], samples: int, period: float, taus: list[float], fractions: list[float], total_counts: float, shape: tuple[int, int]) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate a 3-dimensional IRF convolved monoexponential or multiexponential
+    decay curve.
+
+    This function generates a 3-dimensonal instrument response function (IRF)
+    convolved monoexponential or multiexponential decay curve. The ideal
+    decay curve is defined as the sum of one or more exponential components,
+    each characterized by a lifetime (tau) and fractional intensity:
+
+    I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+
+    :param irf: The IRF as a 1-dimensonal array.
+    :param samples: The number of discrete points that make up the decay curve.
+    :param period: The period (_i.e._ time interval).
+    :param taus: An array of lifetimes. For a monoexponential decay curve use a
+        single tau value and a fractional intensity of 1.0. For a
+        multiexponential decay curve use two or more tau values, matched with
+        their respective fractional intensity. The "taus" and "fractions" arrays
+        must have the same length. Tau values set to 0.0 will be skipped.
+    :param fractions: An array of fractional intensities for each tau in the "taus"
+        array. The "fractions" array must be the same length as the "taus" array
+        and sum to 1.0. Fraction values set to 0.0 will be skipped.
+    :param total_counts: The total intensity count (_e.g._ photon count) of the
+        decay curve.
+    :param shape: The row and col shape to broadcast the decay curve into.
+    :return: The 3-dimensional IRF convolved monoexponential or
+        multiexponential decay curve.
+    """
+    ...
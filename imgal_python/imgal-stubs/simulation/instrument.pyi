@@ -0,0 +1,49 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def estimate_irf(data: list[float], period: float, tau: float, regularization: float | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Estimate the instrument response function (IRF) from a measured
+    monoexponential reference decay.
+
+    This function recovers an effective IRF from the decay of a reference
+    sample with a known monoexponential lifetime by Tikhonov regularized
+    deconvolution in the frequency domain:
+
+    IRF = F⁻¹[ F(D) F(E)* / (|F(E)|² + λ max|F(E)|²) ]
+
+    Where "D" is the measured decay, "E" is the ideal decay of lifetime "tau"
+    and "λ" is the regularization weight. Negative values of the recovered IRF
+    are clipped to 0.0 and the IRF is normalized to sum to 1.0.
+
+    :param data: The measured 1-dimensional reference decay.
+    :param period: The period (i.e. time interval).
+    :param tau: The known lifetime of the reference decay.
+    :param regularization: The relative Tikhonov regularization weight,
+        default = 1e-3.
+    :return: The normalized IRF with the same length as "data".
+    """
+    ...
+
+def gaussian_irf_1d(bins: int, time_range: float, irf_center: float, irf_width: float) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate a 1-dimensional Gaussian instruement response function (IRF).
+
+    This function creates a Gaussian IRF by converting "full width at half maximum"
+    (FWHM) parameters into a normalized Gaussian distribution. The FWHM is
+    converted to standard deviation using the relationship:
+
+    σ = FWHM / (2 × √(2 × ln(2)))
+
+    where ln(2) ≈ 0.693147 is the natural logarithm of 2.
+
+    :param bins: The number of discrete points to sample the Gaussian distribution.
+    :param time_range: The total time range over which to simulate the IRF.
+    :param irf_center: The temporal position of the IRF peak within the time range.
+    :param irf_width: The full width at half maximum (FWHM) of the IRF.
+    :return : The simulated 1-dimensional IRF curve.
+    """
+    ...
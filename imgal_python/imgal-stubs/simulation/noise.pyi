@@ -0,0 +1,157 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def gaussian_2d(data: npt.ArrayLike, sigma: float, seed: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate additive Gaussian noise on a 2-dimensional array.
+
+    This function adds zero mean Gaussian noise (e.g. detector read noise) with
+    standard deviation "sigma" to each element of a 2-dimensional image.
+
+    This function creates a new array and does not mutate the input array.
+
+    :param data: The input 2-dimensional array.
+    :param sigma: The standard deviation of the noise. Must be finite and
+        non-negative.
+    :param seed: Pseudorandom number generator master seed. Set the "seed" value
+        for reproducible noise. If "None", a random master seed is drawn.
+    :return: A 2-dimensional array of the input data with Gaussian noise
+        applied.
+    """
+    ...
+
+def gaussian_2d_mut(data: npt.NDArray[np.float64], sigma: float, seed: int | None = None) -> None:
+    r"""
+    Simulate additive Gaussian noise on a 2-dimensional array.
+
+    This function adds zero mean Gaussian noise with standard deviation "sigma"
+    to each element of a 2-dimensional image.
+
+    This function mutates the input array and does not create a new array.
+
+    :param data: The input 2-dimensional array to mutate.
+    :param sigma: The standard deviation of the noise. Must be finite and
+        non-negative.
+    :param seed: Pseudorandom number generator master seed. Set the "seed" value
+        for reproducible noise. If "None", a random master seed is drawn.
+    """
+    ...
+
+def poisson_1d(data: npt.ArrayLike, scale: float, seed: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate Poisson noise on a 1-dimensional array.
+
+    The function applies Poisson noise (i.e. shot noise) on a 1-dimensional
+    array of data. An element-wise lambda value (scaled by the "scale" parameter)
+    is used to simulate the Poisson noise with variable signal strength.
+
+    The function creates a new array and does not mutate the input array.f
+
+    :param data: The input 1-dimensional array.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator seed. Set the "seed" value for
+        reproducible noise. If "None", a random seed is drawn.
+    :return: A 1-dimensonal array of the input data with Poisson noise applied.
+    """
+    ...
+
+def poisson_1d_mut(data: npt.NDArray[np.float64], scale: float, seed: int | None) -> None:
+    r"""
+    Simulate Poisson noise on a 1-dimensional array.
+
+    The function applies Poisson noise (i.e. shot noise) on a 1-dimensional
+    array of data. An element-wise lambda value (scaled by the "scale" parameter)
+    is used to simulate the Poisson noise with variable signal strength.
+
+    This function mutates the input array and does not create a new array.
+
+    :param data: The input 1-dimensonal array to mutate.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator seed. Set the "seed" value for
+        reproducible noise. If "None", a random seed is drawn.
+    """
+    ...
+
+def poisson_2d(data: npt.ArrayLike, scale: float, seed: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate Poisson noise on a 2-dimensional array.
+
+    This function applies Poisson noise (i.e. shot noise) on a 2-dimensional
+    image. An element-wise lambda value (scaled by the "scale" parameter) is
+    used to simulate Poisson noise with variable signal strength.
+
+    This function creates a new array and does not mutate the input array.
+
+    :param data: The input 2-dimensional array.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator master seed. Each row draws from
+        its own random stream derived from the master seed and the row index.
+        Set the "seed" value for reproducible noise. If "None", a random master
+        seed is drawn.
+    :return: A 2-dimensional array of the input data with Poisson noise
+        applied.
+    """
+    ...
+
+def poisson_2d_mut(data: npt.NDArray[np.float64], scale: float, seed: int | None = None) -> None:
+    r"""
+    Simulate Poisson noise on a 2-dimensional array.
+
+    This function applies Poisson noise (i.e. shot noise) on a 2-dimensional
+    image. An element-wise lambda value (scaled by the "scale" parameter) is
+    used to simulate Poisson noise with variable signal strength.
+
+    This function mutates the input array and does not create a new array.
+
+    :param data: The input 2-dimensional array to mutate.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator master seed. Set the "seed" value
+        for reproducible noise. If "None", a random master seed is drawn.
+    """
+    ...
+
+def poisson_3d(data: npt.ArrayLike, scale: float, seed: int | None = None, axis: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Simulate Poisson noise on a 3-dimensional array.
+
+    This function applies Poisson noise (i.e. shot noise) on a 3-dimensional
+    array of data. An element-wise lambda value (scaled by the "scale" parameter)
+    is used to simulate Poisson noise with variable signal strength.
+
+    This function creates a new array and does not mutate the input array.
+
+
+    :param data: The input 3-dimensional array.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator master seed. Each lane along
+        "axis" draws from its own random stream derived from the master seed
+        and the lane index. Set the "seed" value for reproducible noise. If
+        "None", a random master seed is drawn.
+    :param axis: The signal data axis, default = 2.
+    :return: A 3-dimensional array of the input data with Poisson noise
+        applied.
+    """
+    ...
+
+def poisson_3d_mut(data: npt.NDArray[np.float64], scale: float, seed: int | None = None, axis: int | None = None) -> None:
+    r"""
+    Simulate Poisson noise on a 3-dimensional array.
+
+    This function applies Poisson noise (i.e. shot noise) on a 3-dimensional
+    array of data. An element-wise lambda value (scaled by the "scale" parameter)
+    is used to simulate Poisson noise with variable signal strength.
+
+    This function mutates the input array and does not create a new array.
+
+    :param data: The input 3-dimensional array to mutate.
+    :param scale: The scale factor.
+    :param seed: Pseudorandom number generator master seed. Each lane along
+        "axis" draws from its own random stream derived from the master seed
+        and the lane index. Set the "seed" value for reproducible noise. If
+        "None", a random master seed is drawn.
+    :param axis: The signal data axis, default = 2.
+    """
+    ...
@@ -0,0 +1,485 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def adu_to_photons(data: npt.ArrayLike, gain: float, offset: float) -> npt.NDArray[np.float64]:
+    r"""
+    Convert an n-dimensional array of raw detector counts into photon units.
+
+    p = (x - o) / g
+
+    :param data: The input n-dimensional array of raw detector counts.
+    :param gain: The detector gain in ADU per photoelectron.
+    :param offset: The detector offset in ADU.
+    :return: The n-dimensional array in photoelectrons, clamped to be
+        non-negative.
+    """
+    ...
+
+def bin_index(value: float, range: tuple[float, float], bins: int) -> int | None:
+    r"""
+    Find the bin index of a value in an evenly spaced histogram.
+
+    This function finds the bin of a value in a histogram with "bins" evenly
+    spaced bins between the "range" minimum and maximum. The last bin includes
+    the maximum value.
+
+    :param value: The value to bin.
+    :param range: The (min, max) range of the histogram.
+    :param bins: The number of histogram bins. Must be greater than 0.
+    :return: The bin index of the value, or "None" if the value is outside of
+        the range or NaN.
+    """
+    ...
+
+def brenner_gradient(data: npt.ArrayLike) -> float:
+    r"""
+    Compute the Brenner gradient focus score of a 2-dimensional image.
+
+    F = 1/N Σ (I(r, c + 2) - I(r, c))²
+
+    :param data: The input 2-dimensional image.
+    :return: The Brenner gradient focus score. If the image has less than 3
+        columns, 0.0 is returned.
+    """
+    ...
+
+def effective_sample_size(weights: list[float]) -> float:
+    r"""
+    Compute the effective sample size (ESS) of a weighted sample set.
+
+    This function computes the effective sample size (ESS) of a weighted sample
+    set. Only the weights of the associated sample set are needed. The ESS is
+    defined as:
+
+    ESS = (Σ wᵢ)² / Σ (wᵢ²)
+
+    :param weights: A slice of non-negative weights where each element represents
+        the weight of an associated sample.
+    :return: The effective number of independent samples.
+    """
+    ...
+
+def effective_sample_size_axis(weights: npt.NDArray[np.float64], axis: int) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the effective sample size (ESS) of weighted sample sets along an
+    axis of an n-dimensional array.
+
+    :param weights: The n-dimensional array of non-negative weights.
+    :param axis: The sample axis.
+    :return: The effective number of independent samples of each lane, with the
+        sample axis removed.
+    """
+    ...
+
+def estimate_snr(data: npt.ArrayLike, signal_mask: npt.NDArray[np.bool_] | None = None, background_mask: npt.NDArray[np.bool_] | None = None, gain: float | None = None) -> float:
+    r"""
+    Estimate the signal-to-noise ratio (SNR) of an image or image region.
+
+    If a background region is given, SNR = (μₛ - μ_b) / σ_b, where the noise is
+    measured from the background. Otherwise the image is assumed to be limited
+    by Poisson noise, SNR = √(μₛ / g).
+
+    :param data: The input n-dimensional image.
+    :param signal_mask: An optional boolean mask of the signal region. If
+        "None", all elements not in the background region are used.
+    :param background_mask: An optional boolean mask of the background region.
+        If "None", the Poisson noise model is used.
+    :param gain: The detector gain in counts per photon, used by the Poisson
+        noise model, default = 1.0.
+    :return: The estimated linear signal-to-noise ratio.
+    """
+    ...
+
+def focus_curve(data: npt.ArrayLike, metric: str | None = None, threshold: float | None = None, cutoff: float | None = None, axis: int | None = None) -> list[float]:
+    r"""
+    Compute the focus scores of each frame in a 3-dimensional stack.
+
+    :param data: The input 3-dimensional stack.
+    :param metric: The focus metric, "brenner", "tenengrad",
+        "normalized_variance" or "spectral", default = "brenner".
+    :param threshold: The Tenengrad squared gradient threshold, default = 0.0.
+    :param cutoff: The spectral normalized radial frequency cutoff, default =
+        0.2.
+    :param axis: The frame axis, default = 0.
+    :return: The focus score of each frame.
+    """
+    ...
+
+def histogram2d(x: list[float], y: list[float], bins: tuple[int, int] | None = None, ranges: tuple[tuple[float, float], tuple[float, float]] | None = None, weights: list[float] | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the 2-dimensional histogram of paired values.
+
+    This function computes a 2-dimensional histogram of paired (x, y) values
+    (e.g. phasor coordinates or the pixel intensities of two channels). Pairs
+    with either value outside of its range are not counted.
+
+    :param x: The first values of each pair. Must be the same length as "y".
+    :param y: The second values of each pair. Must be the same length as "x".
+    :param bins: The number of (x, y) bins, default = (256, 256).
+    :param ranges: The ((x_min, x_max), (y_min, y_max)) ranges of the
+        histogram. If "None", the minimum and maximum values of "x" and "y" are
+        used.
+    :param weights: Optional weights, one per pair.
+    :return: The 2-dimensional (x_bins, y_bins) histogram.
+    """
+    ...
+
+def max(data: npt.ArrayLike) -> float:
+    r"""
+    Find the maximum value in an n-dimensional array.
+
+    This function iterates through all elements of an n-dimensional array to
+    determine the maximum value.
+
+    :param data: The input n-dimensional array view.
+    :return: The maximum value in the input data array.
+    """
+    ...
+
+def median(data: list[float]) -> float:
+    r"""
+    Compute the median of a sequence of numbers.
+
+    For an even number of values the median is the mean of the two middle
+    values.
+
+    :param data: The sequence of numbers.
+    :return: The median. If "data" is empty, NaN.
+    """
+    ...
+
+def min(data: npt.ArrayLike) -> float:
+    r"""
+    Find the minimum value in an n-dimensional array.
+
+    This function iterates through all elements of an n-dimensional array to
+    determine the minimum value.
+
+    :param data: The input n-dimensional array view.
+    :return: The minimum value in the input data array.
+    """
+    ...
+
+def min_max(data: npt.ArrayLike) -> tuple[float, float]:
+    r"""
+    Find the minimum and maximum values in an n-dimensional array.
+
+    This function iterates through all elements of an n-dimensional array to
+    determine the minimum and maximum values.
+
+    :param data: The input n-dimensional array view.
+    :return: A tuple containing the minimum and maximum values (_i.e._
+        (min, max)) in the given array. If the array is empty a minimum and
+        maximum value of 0 is returned in the tuple.
+    """
+    ...
+
+def normalized_variance(data: npt.ArrayLike) -> float:
+    r"""
+    Compute the normalized variance focus score of a 2-dimensional image.
+
+    F = 1/(Nμ) Σ (I(r, c) - μ)²
+
+    :param data: The input 2-dimensional image.
+    :return: The normalized variance focus score.
+    """
+    ...
+
+def p_adjust(data: npt.NDArray[np.float64], method: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Adjust a p-value map for multiple comparisons.
+
+    This function adjusts the p-values of an n-dimensional map (e.g. one
+    p-value per pixel) for the number of tests, the number of non-NaN p-values.
+    The Bonferroni correction controls the family-wise error rate and the
+    Benjamini-Hochberg procedure controls the false discovery rate.
+
+    :param data: The n-dimensional p-value map.
+    :param method: The correction method, "bonferroni" or "bh"
+        (Benjamini-Hochberg), default = "bh".
+    :return: The adjusted p-values, with the same shape as "data". NaN p-values
+        stay NaN and are not counted as tests.
+    """
+    ...
+
+def photon_transfer_curve(frames: npt.ArrayLike, axis: int | None = None, dark_frames: npt.ArrayLike | None = None) -> tuple[float, float, float]:
+    r"""
+    Estimate the detector gain, read noise and offset from a stack of repeated
+    frames.
+
+    This function fits the photon transfer curve, σ²(x) = g(μ(x) - o) + σᵣ²,
+    over the temporal mean and variance of each pixel. If "dark_frames" are
+    given, the offset and read noise are measured from the dark frames,
+    otherwise the read noise is assumed to be 0.0.
+
+    :param frames: The 3-dimensional stack of repeated frames.
+    :param axis: The frame axis, default = 0.
+    :param dark_frames: An optional stack of repeated dark frames with the same
+        dtype as "frames".
+    :return: The gain (ADU per photoelectron), read noise (photoelectrons) and
+        offset (ADU).
+    """
+    ...
+
+def rank(data: npt.ArrayLike, method: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Rank the values of an n-dimensional array.
+
+    This function ranks the values of an n-dimensional array in ascending order,
+    starting at 1. NaN values are ranked after all other values. Tied values are
+    ranked according to "method", for example the values [10, 20, 20, 30] are
+    ranked:
+
+    average: [1.0, 2.5, 2.5, 4.0]
+    dense:   [1.0, 2.0, 2.0, 3.0]
+    min:     [1.0, 2.0, 2.0, 4.0]
+    ordinal: [1.0, 2.0, 3.0, 4.0]
+
+    :param data: The input n-dimensional array.
+    :param method: The tie handling method, "average", "dense", "min" or
+        "ordinal", default = "average".
+    :return: The ranks, with the same shape as "data".
+    """
+    ...
+
+def reduce_axis(data: npt.ArrayLike, axis: int, reduction: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Reduce an n-dimensional array along an axis.
+
+    This function reduces each 1-dimensional lane along the given axis to a
+    single value (e.g. a sum or maximum intensity projection) in parallel,
+    producing an (n - 1)-dimensional array.
+
+    :param data: The input n-dimensional array.
+    :param axis: The axis to reduce.
+    :param reduction: The reduction applied to each lane, "sum", "mean", "max",
+        "min" or "std", default = "sum".
+    :return: The reduced (n - 1)-dimensional array.
+    """
+    ...
+
+def spectral_focus(data: npt.ArrayLike, cutoff: float | None = None) -> float:
+    r"""
+    Compute the power spectrum focus score of a 2-dimensional image.
+
+    This function computes the fraction of the (mean subtracted) image power
+    spectrum above a radial frequency cutoff, normalized to the Nyquist
+    frequency.
+
+    :param data: The input 2-dimensional image.
+    :param cutoff: The normalized radial frequency cutoff, default = 0.2.
+    :return: The high frequency power fraction, between 0.0 and 1.0.
+    """
+    ...
+
+def sum(data: list[float], summation: str | None = None) -> float:
+    r"""
+    Compute the sum of a sequence of numbers.
+
+    :param data: The sequence of numbers.
+    :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+        default = "naive".
+    :return: The sum.
+    """
+    ...
+
+def tenengrad(data: npt.ArrayLike, threshold: float | None = None) -> float:
+    r"""
+    Compute the Tenengrad (Sobel gradient energy) focus score of a 2-dimensional
+    image.
+
+    F = 1/N Σ (Gₓ² + Gᵧ²),  for Gₓ² + Gᵧ² > T
+
+    :param data: The input 2-dimensional image.
+    :param threshold: The squared gradient magnitude threshold, default = 0.0.
+    :return: The Tenengrad focus score.
+    """
+    ...
+
+def weighted_correlation(data_a: list[float], data_b: list[float], weights: list[float]) -> float:
+    r"""
+    Compute the weighted Pearson correlation coefficient of two sample sets.
+
+    :param data_a: The first sample set. Must be the same length as "data_b"
+        and "weights".
+    :param data_b: The second sample set. Must be the same length as "data_a"
+        and "weights".
+    :param weights: The non-negative weights of each sample pair.
+    :return: The weighted correlation coefficient, ranging between -1.0 and 1.0.
+    """
+    ...
+
+def weighted_correlation_axis(data_a: npt.NDArray[np.float64], data_b: npt.NDArray[np.float64], weights: npt.NDArray[np.float64], axis: int) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the weighted Pearson correlation coefficients of paired sample sets
+    along an axis of two n-dimensional arrays.
+
+    :param data_a: The first n-dimensional sample array.
+    :param data_b: The second n-dimensional sample array. Must have the same
+        shape as "data_a".
+    :param weights: The n-dimensional array of non-negative weights. Must have
+        the same shape as "data_a".
+    :param axis: The sample axis.
+    :return: The weighted correlation coefficient of each lane pair, with the
+        sample axis removed.
+    """
+    ...
+
+def weighted_inversions(data: npt.ArrayLike, weights: npt.NDArray[np.float64]) -> float:
+    r"""
+    Count the weighted inversions of an n-dimensional array.
+
+    This function counts the weighted inversions (i.e. pairs of values out of
+    ascending order) of an n-dimensional array in row-major order, where each
+    inversion (i, j) has the weight wᵢ * wⱼ. The inputs are not mutated.
+
+    :param data: The input n-dimensional array.
+    :param weights: The weights of each value, the same shape as "data".
+    :return: The weighted inversion count.
+    """
+    ...
+
+def weighted_kendall_tau_b(data_a: list[float], data_b: list[float], weights: list[float]) -> float:
+    r"""
+    Compute the weighted Kendall's Tau-b rank correlation coefficient.
+
+    This function calculates a weighted Kendall's Tau-b rank correlation
+    coefficient between two datasets. This implementation uses a weighted merge
+    sort to count discordant pairs (inversions), and applies tie corrections for
+    both variables to compute the final Tau-b coefficient. Here the weighted
+    observations contribute unequally to the final correlation coefficient.
+
+    The weighted Kendall's Tau-b is calculated using:
+
+    τ_b = (C - D) / √((n₀ - n₁)(n₀ - n₂))
+
+    Where:
+    - `C` = number of weighted concordant pairs
+    - `D` = number of weighted discordant pairs
+    - `n₀` = total weighted pairs = `((Σwᵢ)² - Σwᵢ²) / 2`
+    - `n₁` = weighted pairs tied in the first variable
+    - `n₂` = weighted pairs tied in the second variable
+
+    :param data_a: The first dataset for correlation analysis. Must be the same
+        length as `data_b`.
+    :param data_b: The second dataset for correlation analysis. Must be the same
+        length as `data_a`.
+    :param weights: The associated weights for each observation pait. Must be the
+        same length as both input datasets.
+    :return: The weighted Kendall's Tau-b correlation coefficient, ranging
+        between -1.0 (negative correlation), 0.0 (no correlation) and 1.0
+        (positive correlation).
+    """
+    ...
+
+def weighted_kendall_tau_b_with_ties(data_a: list[float], data_b: list[float], weights: list[float]) -> tuple[float, float, float, float]:
+    r"""
+    Compute the weighted Kendall's Tau-b rank correlation coefficient and its
+    tie correction terms.
+
+    This function computes the weighted Kendall's Tau-b rank correlation
+    coefficient and also returns the weighted tie correction terms used to
+    compute it.
+
+    :param data_a: The first dataset for correlation analysis. Must be the same
+        length as `data_b`.
+    :param data_b: The second dataset for correlation analysis. Must be the same
+        length as `data_a`.
+    :param weights: The associated weights for each observation pait. Must be the
+        same length as both input datasets.
+    :return: The weighted Kendall's Tau-b correlation coefficient and the
+        weighted number of pairs tied in the first variable, the second variable
+        and both variables.
+    """
+    ...
+
+def weighted_mean(data: list[float], weights: list[float]) -> float:
+    r"""
+    Compute the weighted mean of a sample set.
+
+    μ = Σ wᵢxᵢ / Σ wᵢ
+
+    :param data: The sample set. Must be the same length as "weights".
+    :param weights: The non-negative weights of each sample.
+    :return: The weighted mean. If the sum of weights is 0.0, NaN is returned.
+    """
+    ...
+
+def weighted_mean_axis(data: npt.NDArray[np.float64], weights: npt.NDArray[np.float64], axis: int) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the weighted mean of sample sets along an axis of an n-dimensional
+    array.
+
+    :param data: The n-dimensional sample array. Must have the same shape as
+        "weights".
+    :param weights: The n-dimensional array of non-negative weights.
+    :param axis: The sample axis.
+    :return: The weighted mean of each lane, with the sample axis removed.
+    """
+    ...
+
+def weighted_merge_sort_mut(data: npt.ArrayLike, weights: npt.NDArray[np.float64]) -> float:
+    r"""
+    Sort 1-dimensional arrays of values and their associated weights.
+
+    This function performs a bottom up merge sort on the input 1-dimensional
+    data array along with it's associated weights. Both the "data" and "weights"
+    arrays are mutated during the sorting. The output of this function is a
+    weighted inversion count.
+
+    :param data: A 1-dimensional array/slice of numbers of the same length as
+       "weights".
+    :param weights: A 1-dimensional array/slice of weights of the same length as
+       "data".
+    :return: The number of swaps needed to sort the input array.
+    """
+    ...
+
+def weighted_variance(data: list[float], weights: list[float], unbiased: bool | None = None) -> float:
+    r"""
+    Compute the weighted variance of a sample set.
+
+    The biased (population) variance is Σ wᵢ(xᵢ - μ)² / V₁ and the unbiased
+    reliability weighted variance is Σ wᵢ(xᵢ - μ)² / (V₁ - V₂ / V₁), where "V₁"
+    is the sum of weights and "V₂" is the sum of squared weights.
+
+    :param data: The sample set. Must be the same length as "weights".
+    :param weights: The non-negative weights of each sample.
+    :param unbiased: If true, the unbiased reliability weighted variance is
+        computed, default = False.
+    :return: The weighted variance.
+    """
+    ...
+
+def weighted_variance_axis(data: npt.NDArray[np.float64], weights: npt.NDArray[np.float64], axis: int, unbiased: bool | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the weighted variance of sample sets along an axis of an
+    n-dimensional array.
+
+    :param data: The n-dimensional sample array. Must have the same shape as
+        "weights".
+    :param weights: The n-dimensional array of non-negative weights.
+    :param axis: The sample axis.
+    :param unbiased: If true, the unbiased reliability weighted variance is
+        computed, default = False.
+    :return: The weighted variance of each lane, with the sample axis removed.
+    """
+    ...
+
+def z_to_p(data: npt.NDArray[np.float64], tail: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Convert a z-score map into a p-value map.
+
+    This function converts each z-score of an n-dimensional map (e.g. the SACA
+    colocalization z-score image) into the p-value of a standard normal test
+    statistic.
+
+    :param data: The n-dimensional z-score map.
+    :param tail: The tail of the test, "two", "upper" or "lower", default =
+        "two".
+    :return: The p-values, with the same shape as "data".
+    """
+    ...
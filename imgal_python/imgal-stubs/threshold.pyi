@@ -0,0 +1,49 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def manual_mask(data: npt.ArrayLike, threshold: float) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a boolean mask from a threshold value.
+
+    This function computes a threshold mask (as a boolean array) from the input
+    image at the given threshold value.
+
+    :param data: An n-dimensional image or array
+    :param threshold: The image pixel threshold value.
+    :return: A boolean array of the same shape as the input image
+        with pixels that are greater than the threshold value set as "true"
+        and pixels that are below the threshold value set as "false".
+    """
+    ...
+
+def otsu_mask(data: npt.ArrayLike, bins: int | None = None) -> npt.NDArray[np.bool_]:
+    r"""
+    Create a boolean mask with Otsu's threshold.
+
+    This function computes Otsu's threshold and creates a threshold mask (as a
+    boolean array) of the input image.
+
+    :param data: An n-dimensional image or array.
+    :param bins: The number of histogram bins, default = 256.
+    :return: A boolean array of the same shape as the input image with pixels
+        that are greater than the threshold value set as "true".
+    """
+    ...
+
+def otsu_value(data: npt.ArrayLike, bins: int | None = None) -> float:
+    r"""
+    Compute Otsu's threshold value of an n-dimensional image.
+
+    This function computes the threshold that separates the image histogram
+    into a background and a foreground class with the maximum between class
+    variance. Values greater than the threshold are foreground.
+
+    :param data: An n-dimensional image or array.
+    :param bins: The number of histogram bins, default = 256.
+    :return: Otsu's threshold value. If all values of "data" are equal, that
+        value is returned.
+    """
+    ...
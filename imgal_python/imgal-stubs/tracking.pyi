@@ -0,0 +1,25 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def link_particles(detections: list[npt.NDArray[np.float64]], max_distance: float, max_gap: int | None = None, method: str | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Link per-frame particle detections into trajectories.
+
+    This function links the particle detections of consecutive frames into
+    trajectories, where the cost of a link is the squared Euclidean distance.
+    Unlinked detections start new tracks and tracks may skip up to "max_gap"
+    frames.
+
+    :param detections: A list with the detections of each frame, as 2-dimensional
+        arrays with a shape of (n_detections, n_dims).
+    :param max_distance: The maximum link distance.
+    :param max_gap: The maximum number of skipped frames, default = 0.
+    :param method: The linking method, "lap" or "nearest_neighbor", default =
+        "lap".
+    :return: The track table with a shape of (n_detections, 2 + n_dims), where
+        each row is (track_id, frame, coordinates...).
+    """
+    ...
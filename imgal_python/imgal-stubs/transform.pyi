@@ -0,0 +1,35 @@
+# This file is generated by stub_gen, do not edit.
+from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+
+def dwt2(data: npt.ArrayLike, wavelet: str | None = None, levels: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the multilevel 2-dimensional discrete wavelet transform.
+
+    This function computes the separable 2-dimensional discrete wavelet
+    transform (DWT) of an image with an orthogonal wavelet and periodic
+    boundaries. The coefficients are stored in the Mallat layout, where each
+    level splits the current approximation into the LL (top left), LH (top
+    right), HL (bottom left) and HH (bottom right) subbands.
+
+    :param data: The input 2-dimensional image. Both dimensions must be
+        divisible by 2^levels.
+    :param wavelet: The wavelet, "haar", "db2" or "db4", default = "haar".
+    :param levels: The number of decomposition levels, default = 1.
+    :return: The wavelet coefficients in the Mallat layout.
+    """
+    ...
+
+def idwt2(data: npt.NDArray[np.float64], wavelet: str | None = None, levels: int | None = None) -> npt.NDArray[np.float64]:
+    r"""
+    Compute the multilevel 2-dimensional inverse discrete wavelet transform.
+
+    :param data: The 2-dimensional wavelet coefficients in the Mallat layout.
+    :param wavelet: The wavelet used for the forward transform, "haar", "db2" or
+        "db4", default = "haar".
+    :param levels: The number of decomposition levels, default = 1.
+    :return: The reconstructed image.
+    """
+    ...
@@ -0,0 +1,404 @@
+//! Generate the Python type stubs of the imgal Python bindings.
+//!
+//! This tool parses the `#[pyfunction]` bindings in `src/functions` and the
+//! submodule registrations in `src/child_modules` and writes a PEP 561 stub
+//! package, `imgal-stubs`, with one `.pyi` file per (sub)module. The stubs are
+//! shipped with the wheel so IDEs and type checkers (_e.g._ mypy and pyright)
+//! can check `imgal` usage. Regenerate the checked-in stubs after changing the
+//! bindings with:
+//!
+//! ```text
+//! cargo run -p imgal_python --bin stub_gen
+//! ```
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const GENERATED: &str = "# This file is generated by stub_gen, do not edit.\n";
+const STUB_IMPORTS: &str = "from typing import Any
+
+import numpy as np
+import numpy.typing as npt
+";
+
+/// A parsed Python function binding.
+struct Function {
+    name: String,
+    params: Vec<String>,
+    ret: String,
+    doc: Vec<String>,
+}
+
+/// A Python (sub)module with its functions and child module keys.
+#[derive(Default)]
+struct Module {
+    name: String,
+    functions: Vec<String>,
+    children: Vec<String>,
+}
+
+fn main() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    // parse the function bindings of each "*_functions.rs" file
+    let mut functions: BTreeMap<String, Function> = BTreeMap::new();
+    for path in rust_files(&root.join("src/functions")) {
+        let file = path.file_stem().unwrap().to_string_lossy().to_string();
+        let src = fs::read_to_string(&path).unwrap();
+        for (rust_name, f) in parse_functions(&src) {
+            functions.insert(format!("{}::{}", file, rust_name), f);
+        }
+    }
+
+    // parse the module tree from the submodule registrations
+    let mut modules: BTreeMap<String, Module> = BTreeMap::new();
+    let mut top: Vec<String> = Vec::new();
+    for path in rust_files(&root.join("src/child_modules")) {
+        let file = path.file_stem().unwrap().to_string_lossy().to_string();
+        let src = fs::read_to_string(&path).unwrap();
+        parse_registrations(&file, &src, &mut modules, &mut top);
+    }
+    top.sort_by(|a, b| modules[a].name.cmp(&modules[b].name));
+
+    // write the stub package
+    let stub_dir = root.join("imgal-stubs");
+    let mut init = String::from(GENERATED);
+    for key in &top {
+        let name = &modules[key].name;
+        init.push_str(&format!("from . import {} as {}\n", name, name));
+        write_module(&stub_dir, key, &modules, &functions);
+    }
+    write_if_changed(&stub_dir.join("__init__.pyi"), &init);
+}
+
+/// List the Rust source files of a directory in sorted order.
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "rs") && !p.ends_with("mod.rs"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Parse the `#[pyfunction]` bindings of a source file, keyed by Rust name.
+fn parse_functions(src: &str) -> Vec<(String, Function)> {
+    let lines: Vec<&str> = src.lines().collect();
+    let mut output = Vec::new();
+    let mut doc: Vec<String> = Vec::new();
+    let mut is_pyfunction = false;
+    let mut py_name: Option<String> = None;
+    let mut signature: Option<Vec<String>> = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim();
+        if let Some(d) = line.strip_prefix("///") {
+            doc.push(d.strip_prefix(' ').unwrap_or(d).to_string());
+        } else if line == "#[pyfunction]" {
+            is_pyfunction = true;
+        } else if let Some(n) = line.strip_prefix("#[pyo3(name = \"") {
+            py_name = Some(n.trim_end_matches("\")]").to_string());
+        } else if let Some(s) = line.strip_prefix("#[pyo3(signature = (") {
+            signature = Some(split_top_level(s.trim_end_matches("))]")));
+        } else if line.starts_with("pub fn ") && is_pyfunction {
+            // collect the function header up to the opening body brace
+            let mut header = String::new();
+            while i < lines.len() {
+                header.push_str(lines[i].trim());
+                header.push(' ');
+                if lines[i].trim_end().ends_with('{') {
+                    break;
+                }
+                i += 1;
+            }
+            let (rust_name, f) = parse_header(&header, py_name.take(), signature.take(), &doc);
+            output.push((rust_name, f));
+            doc.clear();
+            is_pyfunction = false;
+        } else if !line.starts_with("#[") {
+            doc.clear();
+            is_pyfunction = false;
+            py_name = None;
+            signature = None;
+        }
+        i += 1;
+    }
+
+    output
+}
+
+/// Parse a function header into a Python function stub.
+fn parse_header(
+    header: &str,
+    py_name: Option<String>,
+    signature: Option<Vec<String>>,
+    doc: &[String],
+) -> (String, Function) {
+    let rest = header.trim_start_matches("pub fn ");
+    let name_end = rest.find(['<', '(']).unwrap();
+    let rust_name = rest[..name_end].to_string();
+    let open = rest.find('(').unwrap();
+    let close = matching(rest, open);
+    let ret = rest[close + 1..]
+        .trim()
+        .trim_end_matches('{')
+        .trim()
+        .strip_prefix("->")
+        .map(|r| r.split(" where ").next().unwrap().trim().to_string())
+        .unwrap_or_else(|| "()".to_string());
+
+    // map the Rust parameters (without the GIL token) to Python parameters
+    let mut types: BTreeMap<String, String> = BTreeMap::new();
+    let mut order: Vec<String> = Vec::new();
+    for p in split_top_level(&rest[open + 1..close]) {
+        let Some((n, t)) = p.split_once(':') else {
+            continue;
+        };
+        let n = n.trim().trim_start_matches("mut ").to_string();
+        let t = t.trim().to_string();
+        if t.starts_with("Python") {
+            continue;
+        }
+        order.push(n.clone());
+        types.insert(n, t);
+    }
+    let params = signature
+        .unwrap_or(order)
+        .iter()
+        .map(|p| match p.split_once('=') {
+            Some((n, _)) => format!("{}: {} = None", n.trim(), py_type(&types[n.trim()], true)),
+            None => format!("{}: {}", p, py_type(&types[p.as_str()], true)),
+        })
+        .collect();
+
+    let f = Function {
+        name: py_name.unwrap_or_else(|| rust_name.clone()),
+        params,
+        ret: py_type(&ret, false),
+        doc: doc.to_vec(),
+    };
+    (rust_name, f)
+}
+
+/// Parse the (sub)module registrations of a child module source file.
+///
+/// Modules are keyed by their source file and variable name, since submodule
+/// names (_e.g._ "render") are not unique across parent modules.
+fn parse_registrations(
+    file: &str,
+    src: &str,
+    modules: &mut BTreeMap<String, Module>,
+    top: &mut Vec<String>,
+) {
+    let key = |var: &str| format!("{}::{}", file, var);
+    let mut pending: Option<String> = None;
+    for line in src.lines().map(|l| l.trim()) {
+        if line.starts_with("let ") && line.contains("PyModule::new(") {
+            let var = &line[4..line.find(" =").unwrap()];
+            let name = line.split('"').nth(1).unwrap().to_string();
+            modules.entry(key(var)).or_default().name = name;
+        } else if let Some(var) = line.strip_suffix(".add_function(wrap_pyfunction!(") {
+            pending = Some(key(var));
+        } else if let Some((var, rest)) = line.split_once(".add_function(wrap_pyfunction!(") {
+            // the whole registration fits on one line
+            let path = rest.split(',').next().unwrap().to_string();
+            modules.get_mut(&key(var)).unwrap().functions.push(path);
+        } else if let Some(k) = pending.take() {
+            // the function path follows on the next line
+            let path = line.trim_end_matches(',').to_string();
+            modules.get_mut(&k).unwrap().functions.push(path);
+        } else if let Some((parent, child)) = line.split_once(".add_submodule(&") {
+            let child = key(child.trim_end_matches(")?;").trim_end_matches(')'));
+            if parent == "parent_module" {
+                top.push(child);
+            } else {
+                modules.get_mut(&key(parent)).unwrap().children.push(child);
+            }
+        }
+    }
+}
+
+/// Write the stub of a module, as a package if it has child modules.
+fn write_module(
+    dir: &Path,
+    key: &str,
+    modules: &BTreeMap<String, Module>,
+    functions: &BTreeMap<String, Function>,
+) {
+    let module = &modules[key];
+    let name = &module.name;
+    let mut stub = String::from(GENERATED);
+    let mut children = module.children.clone();
+    children.sort_by(|a, b| modules[a].name.cmp(&modules[b].name));
+    if !module.functions.is_empty() {
+        stub.push_str(STUB_IMPORTS);
+    }
+    if !children.is_empty() {
+        stub.push('\n');
+    }
+    for child in &children {
+        let child_name = &modules[child].name;
+        stub.push_str(&format!("from . import {} as {}\n", child_name, child_name));
+    }
+    let mut fns: Vec<&Function> = module.functions.iter().map(|p| &functions[p]).collect();
+    fns.sort_by(|a, b| a.name.cmp(&b.name));
+    for f in fns {
+        stub.push_str(&format!(
+            "\ndef {}({}) -> {}:\n",
+            f.name,
+            f.params.join(", "),
+            f.ret
+        ));
+        if !f.doc.is_empty() {
+            stub.push_str("    r\"\"\"\n");
+            for d in &f.doc {
+                if d.is_empty() {
+                    stub.push('\n');
+                } else {
+                    stub.push_str(&format!("    {}\n", d));
+                }
+            }
+            stub.push_str("    \"\"\"\n");
+        }
+        stub.push_str("    ...\n");
+    }
+
+    if children.is_empty() {
+        write_if_changed(&dir.join(format!("{}.pyi", name)), &stub);
+    } else {
+        let sub_dir = dir.join(name);
+        for child in &children {
+            write_module(&sub_dir, child, modules, functions);
+        }
+        write_if_changed(&sub_dir.join("__init__.pyi"), &stub);
+    }
+}
+
+/// Map a Rust binding type to a Python type annotation.
+fn py_type(ty: &str, input: bool) -> String {
+    let ty = ty
+        .trim()
+        .trim_start_matches('&')
+        .trim_start_matches("'static ");
+    let ty = ty.trim_start_matches("mut ");
+    if ty == "()" {
+        return "None".to_string();
+    }
+    if let Some(inner) = ty.strip_prefix('(') {
+        let elems: Vec<String> = split_top_level(inner.strip_suffix(')').unwrap())
+            .iter()
+            .map(|e| py_type(e, input))
+            .collect();
+        return format!("tuple[{}]", elems.join(", "));
+    }
+    if let Some(inner) = ty.strip_prefix('[') {
+        let (elem, n) = inner.strip_suffix(']').unwrap().split_once(';').unwrap();
+        let n: usize = n.trim().parse().unwrap();
+        return format!("tuple[{}]", vec![py_type(elem, input); n].join(", "));
+    }
+    let (head, args) = match ty.find('<') {
+        Some(i) => (
+            &ty[..i],
+            split_top_level(&ty[i + 1..ty.len() - 1])
+                .into_iter()
+                .filter(|a| !a.starts_with('\''))
+                .collect::<Vec<String>>(),
+        ),
+        None => (ty, Vec::new()),
+    };
+    match head {
+        "PyResult" | "Bound" => py_type(&args[0], input),
+        "Option" => format!("{} | None", py_type(&args[0], input)),
+        "Vec" => format!("list[{}]", py_type(&args[0], input)),
+        "PyAny" if input => "npt.ArrayLike".to_string(),
+        "PyAny" => "npt.NDArray[Any]".to_string(),
+        "f32" | "f64" => "float".to_string(),
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" => {
+            "int".to_string()
+        }
+        "bool" => "bool".to_string(),
+        "str" | "String" => "str".to_string(),
+        "PyDict" => "dict[str, Any]".to_string(),
+        "PyTuple" => "tuple[Any, ...]".to_string(),
+        h if h.starts_with("PyReadonlyArray")
+            || h.starts_with("PyReadwriteArray")
+            || h.starts_with("PyArray") =>
+        {
+            format!("npt.NDArray[{}]", np_dtype(&args[0]))
+        }
+        _ => "Any".to_string(),
+    }
+}
+
+/// Map a Rust element type to a numpy scalar type.
+fn np_dtype(ty: &str) -> &'static str {
+    match ty.trim() {
+        "f32" => "np.float32",
+        "f64" => "np.float64",
+        "u8" => "np.uint8",
+        "u16" => "np.uint16",
+        "u32" => "np.uint32",
+        "u64" => "np.uint64",
+        "usize" => "np.uintp",
+        "i8" => "np.int8",
+        "i16" => "np.int16",
+        "i32" => "np.int32",
+        "i64" => "np.int64",
+        "isize" => "np.intp",
+        "bool" => "np.bool_",
+        _ => "Any",
+    }
+}
+
+/// Split a comma separated list at the top nesting level.
+fn split_top_level(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in s.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+/// Index of the parenthesis matching the one at `open`.
+fn matching(s: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in s.char_indices().skip(open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("Unbalanced parenthesis in function header: {}", s);
+}
+
+/// Write a file only if its content changed.
+fn write_if_changed(path: &Path, content: &str) {
+    if fs::read_to_string(path).is_ok_and(|c| c == content) {
+        return;
+    }
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
@@ -23,6 +23,38 @@ pub fn register_colocalization_module(parent_module: &Bound<'_, PyModule>) -> Py
         colocalization_functions::colocalization_saca_significance_mask,
         &colocalization_module
     )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_saca_2d_intermediate,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_saca_3d_intermediate,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_pearson,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_manders,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_pairwise_matrix,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_icq,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_bootstrap_ci,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_costes_threshold,
+        &colocalization_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&colocalization_module)
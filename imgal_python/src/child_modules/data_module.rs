@@ -0,0 +1,23 @@
+use pyo3::prelude::*;
+
+use crate::functions::data_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "data" submodule.
+pub fn register_data_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let data_module = PyModule::new(parent_module.py(), "data")?;
+
+    // add module to Python's sys.modules
+    py_import_module("data");
+
+    // add data submodule functions
+    data_module.add_function(wrap_pyfunction!(data_functions::data_beads, &data_module)?)?;
+    data_module.add_function(wrap_pyfunction!(data_functions::data_cells, &data_module)?)?;
+    data_module.add_function(wrap_pyfunction!(
+        data_functions::data_flim_cube,
+        &data_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&data_module)
+}
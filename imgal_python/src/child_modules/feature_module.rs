@@ -0,0 +1,33 @@
+use pyo3::prelude::*;
+
+use crate::functions::feature_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "feature" submodule
+pub fn register_feature_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let feature_module = PyModule::new(parent_module.py(), "feature")?;
+
+    // add module to Python's sys.modules
+    py_import_module("feature");
+
+    // add feature submodule functions
+    feature_module.add_function(wrap_pyfunction!(
+        feature_functions::feature_structure_tensor_2d,
+        &feature_module
+    )?)?;
+    feature_module.add_function(wrap_pyfunction!(
+        feature_functions::feature_structure_tensor_3d,
+        &feature_module
+    )?)?;
+    feature_module.add_function(wrap_pyfunction!(
+        feature_functions::feature_local_binary_pattern,
+        &feature_module
+    )?)?;
+    feature_module.add_function(wrap_pyfunction!(
+        feature_functions::feature_local_binary_pattern_histogram,
+        &feature_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&feature_module)
+}
@@ -19,6 +19,54 @@ pub fn register_filter_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         filter_functions::filter_fft_deconvolve_1d,
         &filter_module
     )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_destripe,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_wavelet_denoise,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian_2d,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_mean,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_guided,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_kuwahara,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gradient,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_divergence,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_laplacian,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_poisson_solve,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_temporal,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_delta_f_over_f,
+        &filter_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&filter_module)
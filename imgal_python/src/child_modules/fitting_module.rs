@@ -0,0 +1,97 @@
+use pyo3::prelude::*;
+
+use crate::functions::fitting_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "fitting" submodule
+pub fn register_fitting_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let fitting_module = PyModule::new(parent_module.py(), "fitting")?;
+
+    // add module to Python's sys.modules
+    py_import_module("fitting");
+
+    // add fitting submodule functions
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_prony,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_prony_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_tail_fit,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_tail_fit_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_chi_square,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_durbin_watson,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_reduced_chi_square,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_reduced_chi_square_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_weighted_residuals,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_weighted_residuals_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_select_model,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_select_model_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_gaussian_2d,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_gaussian_3d,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_mem_lifetime_distribution,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_mem_lifetime_distribution_image,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_mem_roi_distribution,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_laguerre_basis,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_laguerre_fit,
+        &fitting_module
+    )?)?;
+    fitting_module.add_function(wrap_pyfunction!(
+        fitting_functions::fitting_laguerre_image,
+        &fitting_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&fitting_module)
+}
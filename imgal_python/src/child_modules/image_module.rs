@@ -6,16 +6,162 @@ use crate::utils::py_import_module;
 /// Python bindings for the "image" submodule.
 pub fn register_image_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let image_module = PyModule::new(parent_module.py(), "image")?;
+    let annotate_module = PyModule::new(parent_module.py(), "annotate")?;
+    let math_module = PyModule::new(parent_module.py(), "math")?;
+    let render_module = PyModule::new(parent_module.py(), "render")?;
 
     // add module to Python's sys.modules
     py_import_module("image");
+    py_import_module("image.annotate");
+    py_import_module("image.math");
+    py_import_module("image.render");
 
     // add image submodule functions
     image_module.add_function(wrap_pyfunction!(
         image_functions::image_histogram,
         &image_module
     )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_histogram_weighted,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_histogram_u16,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_anscombe,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_anscombe_inverse,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(image_functions::image_pad, &image_module)?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_crop,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_crop_center,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_montage,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_stack_from_slices,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_distance_transform,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_integral_image,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_integral_image_squared,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_box_sum,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_saturation_mask,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_saturation_counts,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_saturation_region_counts,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_ratio,
+        &image_module
+    )?)?;
+    image_module.add_function(wrap_pyfunction!(
+        image_functions::image_ratio_to_concentration,
+        &image_module
+    )?)?;
+
+    // add image::math submodule functions
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_binary,
+        &math_module
+    )?)?;
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_binary_mut,
+        &math_module
+    )?)?;
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_scalar,
+        &math_module
+    )?)?;
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_scalar_mut,
+        &math_module
+    )?)?;
+    math_module.add_function(wrap_pyfunction!(image_functions::math_abs, &math_module)?)?;
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_abs_mut,
+        &math_module
+    )?)?;
+    math_module.add_function(wrap_pyfunction!(image_functions::math_clip, &math_module)?)?;
+    math_module.add_function(wrap_pyfunction!(
+        image_functions::math_clip_mut,
+        &math_module
+    )?)?;
+
+    // add image::annotate submodule functions
+    annotate_module.add_function(wrap_pyfunction!(
+        image_functions::annotate_draw_scale_bar,
+        &annotate_module
+    )?)?;
+    annotate_module.add_function(wrap_pyfunction!(
+        image_functions::annotate_draw_outlines,
+        &annotate_module
+    )?)?;
+    annotate_module.add_function(wrap_pyfunction!(
+        image_functions::annotate_draw_polyline,
+        &annotate_module
+    )?)?;
+    annotate_module.add_function(wrap_pyfunction!(
+        image_functions::annotate_draw_text,
+        &annotate_module
+    )?)?;
+    annotate_module.add_function(wrap_pyfunction!(
+        image_functions::annotate_text_size,
+        &annotate_module
+    )?)?;
+
+    // add image::render submodule functions
+    render_module.add_function(wrap_pyfunction!(
+        image_functions::render_lifetime_rgb,
+        &render_module
+    )?)?;
+    render_module.add_function(wrap_pyfunction!(
+        image_functions::render_apply_colormap,
+        &render_module
+    )?)?;
+    render_module.add_function(wrap_pyfunction!(
+        image_functions::render_overlay_mask,
+        &render_module
+    )?)?;
+    render_module.add_function(wrap_pyfunction!(
+        image_functions::render_overlay_labels,
+        &render_module
+    )?)?;
 
     // attach to parent module
+    image_module.add_submodule(&annotate_module)?;
+    image_module.add_submodule(&math_module)?;
+    image_module.add_submodule(&render_module)?;
     parent_module.add_submodule(&image_module)
 }
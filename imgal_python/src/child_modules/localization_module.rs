@@ -0,0 +1,33 @@
+use pyo3::prelude::*;
+
+use crate::functions::localization_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "localization" submodule.
+pub fn register_localization_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let localization_module = PyModule::new(parent_module.py(), "localization")?;
+
+    // add module to Python's sys.modules
+    py_import_module("localization");
+
+    // add localization submodule functions
+    localization_module.add_function(wrap_pyfunction!(
+        localization_functions::localization_localize,
+        &localization_module
+    )?)?;
+    localization_module.add_function(wrap_pyfunction!(
+        localization_functions::localization_drift_correct,
+        &localization_module
+    )?)?;
+    localization_module.add_function(wrap_pyfunction!(
+        localization_functions::localization_render,
+        &localization_module
+    )?)?;
+    localization_module.add_function(wrap_pyfunction!(
+        localization_functions::localization_phasor_position,
+        &localization_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&localization_module)
+}
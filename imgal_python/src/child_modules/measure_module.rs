@@ -0,0 +1,53 @@
+use pyo3::prelude::*;
+
+use crate::functions::measure_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "measure" submodule.
+pub fn register_measure_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let measure_module = PyModule::new(parent_module.py(), "measure")?;
+
+    // add module to Python's sys.modules
+    py_import_module("measure");
+
+    // add measure submodule functions
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_stack_qc,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_qc_outliers,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_kymograph,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_profile_line,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_esf_lsf_mtf,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_fit_psf,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_anisotropy,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_detect_transients,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_per_label_analysis,
+        &measure_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&measure_module)
+}
@@ -1,11 +1,23 @@
 pub mod colocalization_module;
+pub mod data_module;
 pub mod distribution_module;
+pub mod feature_module;
 pub mod filter_module;
+pub mod fitting_module;
 pub mod image_module;
 pub mod integration_module;
 pub mod kernel_module;
+pub mod localization_module;
+pub mod measure_module;
+pub mod motion_module;
+pub mod ops_module;
 pub mod parameter_module;
 pub mod phasor_module;
+pub mod pipeline_module;
+pub mod registration_module;
+pub mod segmentation_module;
 pub mod simulation_module;
 pub mod statistics_module;
 pub mod threshold_module;
+pub mod tracking_module;
+pub mod transform_module;
@@ -0,0 +1,29 @@
+use pyo3::prelude::*;
+
+use crate::functions::motion_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "motion" submodule
+pub fn register_motion_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let motion_module = PyModule::new(parent_module.py(), "motion")?;
+
+    // add module to Python's sys.modules
+    py_import_module("motion");
+
+    // add motion submodule functions
+    motion_module.add_function(wrap_pyfunction!(
+        motion_functions::motion_lucas_kanade,
+        &motion_module
+    )?)?;
+    motion_module.add_function(wrap_pyfunction!(
+        motion_functions::motion_farneback,
+        &motion_module
+    )?)?;
+    motion_module.add_function(wrap_pyfunction!(
+        motion_functions::motion_optical_flow_3d,
+        &motion_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&motion_module)
+}
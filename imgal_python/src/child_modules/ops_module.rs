@@ -0,0 +1,20 @@
+use pyo3::prelude::*;
+
+use crate::functions::ops_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "ops" submodule.
+pub fn register_ops_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let ops_module = PyModule::new(parent_module.py(), "ops")?;
+
+    // add module to Python's sys.modules
+    py_import_module("ops");
+
+    // add ops submodule functions
+    ops_module.add_function(wrap_pyfunction!(ops_functions::ops_info, &ops_module)?)?;
+    ops_module.add_function(wrap_pyfunction!(ops_functions::ops_list_ops, &ops_module)?)?;
+    ops_module.add_function(wrap_pyfunction!(ops_functions::ops_run, &ops_module)?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&ops_module)
+}
@@ -6,17 +6,95 @@ use crate::utils::py_import_module;
 /// Python binding for the "phasor" submodule.
 pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let phasor_module = PyModule::new(parent_module.py(), "phasor")?;
+    let analysis_module = PyModule::new(parent_module.py(), "analysis")?;
+    let batch_module = PyModule::new(parent_module.py(), "batch")?;
     let calibration_module = PyModule::new(parent_module.py(), "calibration")?;
+    let denoise_module = PyModule::new(parent_module.py(), "denoise")?;
+    let frequency_domain_module = PyModule::new(parent_module.py(), "frequency_domain")?;
+    let gate_scan_module = PyModule::new(parent_module.py(), "gate_scan")?;
+    let multi_harmonic_module = PyModule::new(parent_module.py(), "multi_harmonic")?;
     let plot_module = PyModule::new(parent_module.py(), "plot")?;
+    let render_module = PyModule::new(parent_module.py(), "render")?;
     let time_domain_module = PyModule::new(parent_module.py(), "time_domain")?;
+    let trajectory_module = PyModule::new(parent_module.py(), "trajectory")?;
+    let unmixing_module = PyModule::new(parent_module.py(), "unmixing")?;
 
     // add module to python's sys.modules
     py_import_module("phasor");
+    py_import_module("phasor.analysis");
+    py_import_module("phasor.batch");
     py_import_module("phasor.calibration");
+    py_import_module("phasor.denoise");
+    py_import_module("phasor.frequency_domain");
+    py_import_module("phasor.gate_scan");
+    py_import_module("phasor.multi_harmonic");
     py_import_module("phasor.plot");
+    py_import_module("phasor.render");
     py_import_module("phasor.time_domain");
+    py_import_module("phasor.trajectory");
+    py_import_module("phasor.unmixing");
+
+    // add phasor::analysis submodule functions
+    analysis_module.add_function(wrap_pyfunction!(
+        phasor_functions::analysis_harmonic_consistency,
+        &analysis_module
+    )?)?;
+
+    // add phasor::frequency_domain submodule functions
+    frequency_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::frequency_domain_from_phase_steps,
+        &frequency_domain_module
+    )?)?;
+
+    // add phasor::gate_scan submodule functions
+    gate_scan_module.add_function(wrap_pyfunction!(
+        phasor_functions::gate_scan_decay,
+        &gate_scan_module
+    )?)?;
+    gate_scan_module.add_function(wrap_pyfunction!(
+        phasor_functions::gate_scan_image,
+        &gate_scan_module
+    )?)?;
+
+    // add phasor::multi_harmonic submodule functions
+    multi_harmonic_module.add_function(wrap_pyfunction!(
+        phasor_functions::multi_harmonic_inverse_variance_mean,
+        &multi_harmonic_module
+    )?)?;
+    multi_harmonic_module.add_function(wrap_pyfunction!(
+        phasor_functions::multi_harmonic_lifetime_image,
+        &multi_harmonic_module
+    )?)?;
 
     // add phasor::time_domain submodule functions
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_bin_spatial,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_estimated_background_image,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_peak_bin_image,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_total_counts_image,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_gated_image,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_histogram_quality,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_histogram_quality_image,
+        &time_domain_module
+    )?)?;
     time_domain_module.add_function(wrap_pyfunction!(
         phasor_functions::time_domain_image,
         &time_domain_module
@@ -29,6 +107,48 @@ pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         phasor_functions::time_domain_real,
         &time_domain_module
     )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_volume,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_pileup_correct,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_pileup_correct_mut,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_adaptive_smooth,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_image_with_time_points,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_imaginary_with_time_points,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_real_with_time_points,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_curves,
+        &time_domain_module
+    )?)?;
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_image_multiharmonic,
+        &time_domain_module
+    )?)?;
+
+    // add phasor::batch submodule functions
+    batch_module.add_function(wrap_pyfunction!(
+        phasor_functions::batch_process,
+        &batch_module
+    )?)?;
 
     // add phasor::calibration submodule functions
     calibration_module.add_function(wrap_pyfunction!(
@@ -47,8 +167,30 @@ pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         phasor_functions::calibration_modulation_and_phase,
         &calibration_module
     )?)?;
+    calibration_module.add_function(wrap_pyfunction!(
+        phasor_functions::calibration_estimate_modulation_and_phase,
+        &calibration_module
+    )?)?;
+    calibration_module.add_function(wrap_pyfunction!(
+        phasor_functions::calibration_robust_from_image,
+        &calibration_module
+    )?)?;
+    calibration_module.add_function(wrap_pyfunction!(
+        phasor_functions::calibration_diagnostics,
+        &calibration_module
+    )?)?;
+
+    // add phasor::denoise submodule functions
+    denoise_module.add_function(wrap_pyfunction!(
+        phasor_functions::denoise_image,
+        &denoise_module
+    )?)?;
 
     // add phasor::plot submodule functions
+    plot_module.add_function(wrap_pyfunction!(
+        phasor_functions::plot_apparent_lifetimes,
+        &plot_module
+    )?)?;
     plot_module.add_function(wrap_pyfunction!(
         phasor_functions::plot_modulation,
         &plot_module
@@ -65,10 +207,69 @@ pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         phasor_functions::plot_monoexponential_coordinates,
         &plot_module
     )?)?;
+    plot_module.add_function(wrap_pyfunction!(
+        phasor_functions::plot_from_polar,
+        &plot_module
+    )?)?;
+    plot_module.add_function(wrap_pyfunction!(
+        phasor_functions::plot_semicircle_points,
+        &plot_module
+    )?)?;
+    plot_module.add_function(wrap_pyfunction!(
+        phasor_functions::plot_to_polar,
+        &plot_module
+    )?)?;
+    plot_module.add_function(wrap_pyfunction!(
+        phasor_functions::plot_universal_semicircle,
+        &plot_module
+    )?)?;
+
+    // add phasor::render submodule functions
+    render_module.add_function(wrap_pyfunction!(
+        phasor_functions::render_fraction_rgb,
+        &render_module
+    )?)?;
+
+    // add phasor::trajectory submodule functions
+    trajectory_module.add_function(wrap_pyfunction!(
+        phasor_functions::trajectory_image_series,
+        &trajectory_module
+    )?)?;
+    trajectory_module.add_function(wrap_pyfunction!(
+        phasor_functions::trajectory_roi_trajectory,
+        &trajectory_module
+    )?)?;
+    trajectory_module.add_function(wrap_pyfunction!(
+        phasor_functions::trajectory_statistics,
+        &trajectory_module
+    )?)?;
+    trajectory_module.add_function(wrap_pyfunction!(
+        phasor_functions::trajectory_statistics_image,
+        &trajectory_module
+    )?)?;
+
+    // add phasor::unmixing submodule functions
+    unmixing_module.add_function(wrap_pyfunction!(
+        phasor_functions::unmixing_fit_line,
+        &unmixing_module
+    )?)?;
+    unmixing_module.add_function(wrap_pyfunction!(
+        phasor_functions::unmixing_fractions,
+        &unmixing_module
+    )?)?;
 
     // attach phasor submodule before attaching to the parent module
+    phasor_module.add_submodule(&analysis_module)?;
+    phasor_module.add_submodule(&batch_module)?;
     phasor_module.add_submodule(&calibration_module)?;
+    phasor_module.add_submodule(&denoise_module)?;
+    phasor_module.add_submodule(&frequency_domain_module)?;
+    phasor_module.add_submodule(&gate_scan_module)?;
+    phasor_module.add_submodule(&multi_harmonic_module)?;
     phasor_module.add_submodule(&plot_module)?;
+    phasor_module.add_submodule(&render_module)?;
     phasor_module.add_submodule(&time_domain_module)?;
+    phasor_module.add_submodule(&trajectory_module)?;
+    phasor_module.add_submodule(&unmixing_module)?;
     parent_module.add_submodule(&phasor_module)
 }
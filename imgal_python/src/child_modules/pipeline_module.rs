@@ -0,0 +1,25 @@
+use pyo3::prelude::*;
+
+use crate::functions::pipeline_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "pipeline" submodule.
+pub fn register_pipeline_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let pipeline_module = PyModule::new(parent_module.py(), "pipeline")?;
+
+    // add module to Python's sys.modules
+    py_import_module("pipeline");
+
+    // add pipeline submodule functions
+    pipeline_module.add_function(wrap_pyfunction!(
+        pipeline_functions::pipeline_run,
+        &pipeline_module
+    )?)?;
+    pipeline_module.add_function(wrap_pyfunction!(
+        pipeline_functions::pipeline_count_nuclei,
+        &pipeline_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&pipeline_module)
+}
@@ -0,0 +1,41 @@
+use pyo3::prelude::*;
+
+use crate::functions::registration_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "registration" submodule
+pub fn register_registration_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let registration_module = PyModule::new(parent_module.py(), "registration")?;
+
+    // add module to Python's sys.modules
+    py_import_module("registration");
+
+    // add registration submodule functions
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_align_channels,
+        &registration_module
+    )?)?;
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_phase_correlation,
+        &registration_module
+    )?)?;
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_translate,
+        &registration_module
+    )?)?;
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_stitch,
+        &registration_module
+    )?)?;
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_chromatic_correction,
+        &registration_module
+    )?)?;
+    registration_module.add_function(wrap_pyfunction!(
+        registration_functions::registration_apply_chromatic_correction,
+        &registration_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&registration_module)
+}
@@ -0,0 +1,73 @@
+use pyo3::prelude::*;
+
+use crate::functions::segmentation_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "segmentation" submodule
+pub fn register_segmentation_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let segmentation_module = PyModule::new(parent_module.py(), "segmentation")?;
+
+    // add module to Python's sys.modules
+    py_import_module("segmentation");
+
+    // add segmentation submodule functions
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_random_walker,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_felzenszwalb,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_label,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_region_properties,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_watershed,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_kmeans_features,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_relabel_sequential,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_merge_labels,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_merge_adjacent,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_label_adjacency,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_label_boundaries,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_expand_labels,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_shrink_labels,
+        &segmentation_module
+    )?)?;
+    segmentation_module.add_function(wrap_pyfunction!(
+        segmentation_functions::segmentation_propagate_labels,
+        &segmentation_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&segmentation_module)
+}
@@ -47,6 +47,10 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
     )?)?;
 
     // add simulation::instrument submodule functions
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_estimate_irf,
+        &instrument_module
+    )?)?;
     instrument_module.add_function(wrap_pyfunction!(
         simulation_functions::instrument_gaussian_irf_1d,
         &instrument_module
@@ -69,6 +73,22 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         simulation_functions::noise_poisson_3d_mut,
         &noise_module
     )?)?;
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_poisson_2d,
+        &noise_module
+    )?)?;
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_poisson_2d_mut,
+        &noise_module
+    )?)?;
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_gaussian_2d,
+        &noise_module
+    )?)?;
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_gaussian_2d_mut,
+        &noise_module
+    )?)?;
 
     // attach simulation submodules before attaching to the parent module
     simulation_module.add_submodule(&decay_module)?;
@@ -39,6 +39,102 @@ pub fn register_statistics_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         statistics_functions::statistics_weighted_merge_sort_mut,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_kendall_tau_b_with_ties,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_effective_sample_size_axis,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_mean,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_mean_axis,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_variance,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_variance_axis,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_correlation,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_correlation_axis,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_histogram2d,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_reduce_axis,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_estimate_snr,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_photon_transfer_curve,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_adu_to_photons,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_brenner_gradient,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_tenengrad,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_normalized_variance,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_spectral_focus,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_focus_curve,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_bin_index,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_rank,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_inversions,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_p_adjust,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_z_to_p,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_median,
+        &statistics_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&statistics_module)
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+use crate::functions::tracking_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "tracking" submodule
+pub fn register_tracking_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let tracking_module = PyModule::new(parent_module.py(), "tracking")?;
+
+    // add module to Python's sys.modules
+    py_import_module("tracking");
+
+    // add tracking submodule functions
+    tracking_module.add_function(wrap_pyfunction!(
+        tracking_functions::tracking_link_particles,
+        &tracking_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&tracking_module)
+}
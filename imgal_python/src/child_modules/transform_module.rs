@@ -0,0 +1,25 @@
+use pyo3::prelude::*;
+
+use crate::functions::transform_functions;
+use crate::utils::py_import_module;
+
+// Python bindings for the "transform" submodule
+pub fn register_transform_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let transform_module = PyModule::new(parent_module.py(), "transform")?;
+
+    // add module to Python's sys.modules
+    py_import_module("transform");
+
+    // add transform submodule functions
+    transform_module.add_function(wrap_pyfunction!(
+        transform_functions::transform_dwt2,
+        &transform_module
+    )?)?;
+    transform_module.add_function(wrap_pyfunction!(
+        transform_functions::transform_idwt2,
+        &transform_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&transform_module)
+}
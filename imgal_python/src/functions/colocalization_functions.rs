@@ -1,14 +1,288 @@
 use std::f64;
 
+use numpy::ndarray::{Array1, ArrayViewD, CowArray, IxDyn};
 use numpy::{
-    IntoPyArray, PyArray2, PyArray3, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
+    IntoPyArray, PyArray2, PyArray3, PyArray4, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
     PyReadonlyArrayDyn,
 };
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 
+use crate::dispatch_array;
 use crate::error::map_array_error;
-use imgal::colocalization;
+use imgal::colocalization::{self, BootstrapMetric, PairwiseMetric, SacaKernel, SacaOptions};
+use imgal::error::ImgalError;
+
+/// Compute the Pearson correlation coefficient between two images.
+///
+/// This function computes the Pearson correlation coefficient (PCC) between
+/// the pixel intensities of two n-dimensional images.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: A boolean mask with the same shape as the images, only pixels
+///     inside the mask are used. If "None", all pixels are used.
+/// :return: The Pearson correlation coefficient, ranging between -1.0
+///     (anti-correlation), 0.0 (no correlation) and 1.0 (correlation).
+#[pyfunction]
+#[pyo3(name = "pearson")]
+#[pyo3(signature = (data_a, data_b, mask=None))]
+pub fn colocalization_pearson<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
+) -> PyResult<f64> {
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        colocalization::pearson(
+            apply_mask(arr_a.as_array(), mask.as_ref())?.view(),
+            apply_mask(arr_b.as_array(), mask.as_ref())?.view(),
+        )
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the intensity correlation quotient (ICQ) between two images.
+///
+/// This function computes the intensity correlation quotient (ICQ) between two
+/// n-dimensional images, the fraction of pixels with a positive product of the
+/// differences from the mean minus 0.5.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: A boolean mask with the same shape as the images, only pixels
+///     inside the mask are used. If "None", all pixels are used.
+/// :return: The intensity correlation quotient, ranging between -0.5
+///     (segregated staining), 0.0 (random staining) and 0.5 (dependent
+///     staining).
+#[pyfunction]
+#[pyo3(name = "icq")]
+#[pyo3(signature = (data_a, data_b, mask=None))]
+pub fn colocalization_icq<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
+) -> PyResult<f64> {
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        colocalization::icq(
+            apply_mask(arr_a.as_array(), mask.as_ref())?.view(),
+            apply_mask(arr_b.as_array(), mask.as_ref())?.view(),
+        )
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute a bootstrap confidence interval for a colocalization metric.
+///
+/// This function estimates a percentile bootstrap confidence interval for a
+/// colocalization metric between two images. Each bootstrap replicate
+/// resamples the pixels of the images with replacement and recomputes the
+/// metric. Setting "block_size" resamples non-overlapping square (or cube)
+/// blocks of pixels instead, preserving spatial correlation within each block.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param metric: The colocalization metric, "pearson", "manders_m1",
+///     "manders_m2" or "icq", default = "pearson".
+/// :param threshold_a: Pixel intensity threshold value for image "A" used by
+///     the Manders' metrics, default = 0.0.
+/// :param threshold_b: Pixel intensity threshold value for image "B" used by
+///     the Manders' metrics, default = 0.0.
+/// :param n_resamples: The number of bootstrap replicates, default = 1000.
+/// :param confidence: The confidence level, between 0.0 and 1.0,
+///     default = 0.95.
+/// :param block_size: The side length of the resampled blocks in pixels,
+///     default = 1 (i.e. pixel resampling).
+/// :param seed: Pseudorandom number generator seed for reproducible intervals.
+/// :return: The metric of the input images and the lower and upper bounds of
+///     the confidence interval.
+#[pyfunction]
+#[pyo3(name = "bootstrap_ci")]
+#[pyo3(signature = (data_a, data_b, metric=None, threshold_a=None, threshold_b=None, n_resamples=None, confidence=None, block_size=None, seed=None))]
+pub fn colocalization_bootstrap_ci<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    metric: Option<&str>,
+    threshold_a: Option<f64>,
+    threshold_b: Option<f64>,
+    n_resamples: Option<usize>,
+    confidence: Option<f64>,
+    block_size: Option<usize>,
+    seed: Option<u64>,
+) -> PyResult<(f64, f64, f64)> {
+    let threshold_a = threshold_a.unwrap_or(0.0);
+    let threshold_b = threshold_b.unwrap_or(0.0);
+    let metric = match metric.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("pearson") => BootstrapMetric::Pearson,
+        Some("manders_m1") => BootstrapMetric::MandersM1 {
+            threshold_a,
+            threshold_b,
+        },
+        Some("manders_m2") => BootstrapMetric::MandersM2 {
+            threshold_a,
+            threshold_b,
+        },
+        Some("icq") => BootstrapMetric::Icq,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown metric, supported metrics are \"pearson\", \"manders_m1\", \"manders_m2\", and \"icq\".",
+            ));
+        }
+    };
+    dispatch_array!(data_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        colocalization::bootstrap_ci(
+            arr_a.as_array(),
+            arr_b.as_array(),
+            metric,
+            n_resamples,
+            confidence,
+            block_size,
+            seed,
+        )
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the Costes automatic thresholds of two images.
+///
+/// This function computes the Costes automatic thresholds, separating
+/// correlated signal from uncorrelated background, for use with "manders". The
+/// threshold of image "A" is lowered along the orthogonal regression line of
+/// the pixel intensities until the Pearson correlation coefficient of the
+/// pixels below either threshold drops to 0.0.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: A boolean mask with the same shape as the images, only pixels
+///     inside the mask are used. If "None", all pixels are used.
+/// :return: The thresholds of image "A" and "B".
+#[pyfunction]
+#[pyo3(name = "costes_threshold")]
+#[pyo3(signature = (data_a, data_b, mask=None))]
+pub fn colocalization_costes_threshold<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
+) -> PyResult<(f64, f64)> {
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        colocalization::costes_threshold(
+            apply_mask(arr_a.as_array(), mask.as_ref())?.view(),
+            apply_mask(arr_b.as_array(), mask.as_ref())?.view(),
+        )
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the Manders' colocalization coefficients between two images.
+///
+/// This function computes the thresholded Manders' colocalization coefficients
+/// (M1 and M2) between two n-dimensional images. M1 is the fraction of image
+/// "A"'s intensity in pixels where image "B" is above its threshold, and M2 is
+/// the fraction of image "B"'s intensity in pixels where image "A" is above its
+/// threshold.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param threshold_a: Pixel intensity threshold value for image "A",
+///     default = 0.
+/// :param threshold_b: Pixel intensity threshold value for image "B",
+///     default = 0.
+/// :param mask: A boolean mask with the same shape as the images, only pixels
+///     inside the mask are used. If "None", all pixels are used.
+/// :return: The Manders' M1 and M2 coefficients.
+#[pyfunction]
+#[pyo3(name = "manders")]
+#[pyo3(signature = (data_a, data_b, threshold_a=None, threshold_b=None, mask=None))]
+pub fn colocalization_manders<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    threshold_a: Option<f64>,
+    threshold_b: Option<f64>,
+    mask: Option<PyReadonlyArrayDyn<'py, bool>>,
+) -> PyResult<(f64, f64)> {
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        colocalization::manders(
+            apply_mask(arr_a.as_array(), mask.as_ref())?.view(),
+            apply_mask(arr_b.as_array(), mask.as_ref())?.view(),
+            threshold_a.map(|t| t as T),
+            threshold_b.map(|t| t as T),
+        )
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute a colocalization metric for every channel pair of a multichannel
+/// image.
+///
+/// This function computes the chosen colocalization metric between every pair
+/// of channels in a multichannel image and returns a (c, c) matrix of the
+/// results. For the Manders' metric entry (i, j) is the fraction of channel
+/// "i"'s intensity colocalized with channel "j". Optionally the per-pair maps
+/// are returned for each pair (i, j) with i < j, ordered row by row.
+///
+/// :param data: The 3-dimensional (c, row, col) or 4-dimensional
+///     (c, pln, row, col) multichannel image.
+/// :param metric: The colocalization metric, "pearson", "manders" or "saca",
+///     default = "pearson".
+/// :param thresholds: The per-channel pixel intensity thresholds used by the
+///     Manders' and SACA metrics, default = 0 for all channels.
+/// :param return_maps: If true, the per-pair maps are returned,
+///     default = False.
+/// :return: The (c, c) pairwise metric matrix and the optional list of per-pair
+///     maps.
+#[pyfunction]
+#[pyo3(name = "pairwise_matrix")]
+#[pyo3(signature = (data, metric=None, thresholds=None, return_maps=None))]
+pub fn colocalization_pairwise_matrix<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    metric: Option<&str>,
+    thresholds: Option<Vec<f64>>,
+    return_maps: Option<bool>,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Option<Vec<Bound<'py, PyArrayDyn<f64>>>>,
+)> {
+    let metric = match metric.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("pearson") => PairwiseMetric::Pearson,
+        Some("manders") => PairwiseMetric::Manders,
+        Some("saca") => PairwiseMetric::Saca(None),
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown metric, supported metrics are \"pearson\", \"manders\", and \"saca\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr: T| {
+        let th: Option<Vec<T>> = thresholds.map(|v| v.iter().map(|&t| t as T).collect());
+        colocalization::pairwise_matrix(arr.as_array(), metric, th.as_deref(), return_maps)
+            .map(|(m, maps)| {
+                (
+                    m.into_pyarray(py),
+                    maps.map(|v| v.into_iter().map(|a| a.into_pyarray(py)).collect()),
+                )
+            })
+            .map_err(map_array_error)
+    })
+}
 
 /// Compute colocalization strength using 2-dimensional Spatially Adaptive
 /// Colocalization Analysis (SACA)
@@ -33,58 +307,53 @@ use imgal::colocalization;
 /// :param threshold_b: Pixel intensity threshold value for image "B". Pixels
 ///     below this value are given a weight of 0.0 if the pixel is in the
 ///     circular neighborhood.
+/// :param tu: The total number of iterations (i.e. scales), default = 15.
+/// :param tl: The iteration after which the stopping rule is applied, must be
+///     less than "tu", default = 8.
+/// :param step_size: The kernel radius growth factor per iteration, must be
+///     greater than 1.0, default = 1.15.
+/// :param lambda_multiplier: The stopping rule threshold multiplier, must be
+///     greater than 0.0, default = 1.0.
+/// :param z_scale: The z-score scaling, must be greater than 0.0,
+///     default = 1.5.
+/// :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+///     default = "weighted".
 /// :return: The pixel-wise _z-score_ indicating colocalization or
 ///     anti-colocalization by its sign and the degree or strength of the
 ///     relationship through its absolute values.
 #[pyfunction]
 #[pyo3(name = "saca_2d")]
+#[pyo3(signature = (data_a, data_b, threshold_a, threshold_b, tu=None, tl=None, step_size=None, lambda_multiplier=None, z_scale=None, kernel=None))]
 pub fn colocalization_saca_2d<'py>(
     py: Python<'py>,
     data_a: Bound<'py, PyAny>,
     data_b: Bound<'py, PyAny>,
     threshold_a: f64,
     threshold_b: f64,
+    tu: Option<usize>,
+    tl: Option<usize>,
+    step_size: Option<f64>,
+    lambda_multiplier: Option<f64>,
+    z_scale: Option<f64>,
+    kernel: Option<&str>,
 ) -> PyResult<Bound<'py, PyArray2<f64>>> {
-    if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<u8>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<u8>>()?;
-        colocalization::saca_2d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as u8,
-            threshold_b as u8,
-        )
+    let options = saca_options(tu, tl, step_size, lambda_multiplier, z_scale, kernel)?;
+    dispatch_array!(data_a, PyReadonlyArray2, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArray2<T>>()?;
+        let arr_a = arr_a.as_array();
+        let arr_b = arr_b.as_array();
+        py.allow_threads(|| {
+            colocalization::saca_2d(
+                arr_a,
+                arr_b,
+                threshold_a as T,
+                threshold_b as T,
+                Some(options),
+            )
+        })
         .map(|output| output.into_pyarray(py))
         .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<u16>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<u16>>()?;
-        colocalization::saca_2d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as u16,
-            threshold_b as u16,
-        )
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<f32>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<f32>>()?;
-        colocalization::saca_2d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as f32,
-            threshold_b as f32,
-        )
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<f64>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<f64>>()?;
-        colocalization::saca_2d(arr_a.as_array(), arr_b.as_array(), threshold_a, threshold_b)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    })
 }
 
 /// Compute colocalization strength using 3-dimensional Spatially Adaptive
@@ -110,58 +379,207 @@ pub fn colocalization_saca_2d<'py>(
 /// :param threshold_b: Pixel intensity threshold value for image "B". Pixels
 ///     below this value are given a weight of 0.0 if the pixel is in the
 ///     circular neighborhood.
+/// :param tu: The total number of iterations (i.e. scales), default = 15.
+/// :param tl: The iteration after which the stopping rule is applied, must be
+///     less than "tu", default = 8.
+/// :param step_size: The kernel radius growth factor per iteration, must be
+///     greater than 1.0, default = 1.15.
+/// :param lambda_multiplier: The stopping rule threshold multiplier, must be
+///     greater than 0.0, default = 1.0.
+/// :param z_scale: The z-score scaling, must be greater than 0.0,
+///     default = 1.5.
+/// :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+///     default = "weighted".
 /// :return: The pixel-wise _z-score_ indicating colocalization or
 ///     anti-colocalization by its sign and the degree or strength of the
 ///     relationship through its absolute values.
 #[pyfunction]
 #[pyo3(name = "saca_3d")]
+#[pyo3(signature = (data_a, data_b, threshold_a, threshold_b, tu=None, tl=None, step_size=None, lambda_multiplier=None, z_scale=None, kernel=None))]
 pub fn colocalization_saca_3d<'py>(
     py: Python<'py>,
     data_a: Bound<'py, PyAny>,
     data_b: Bound<'py, PyAny>,
     threshold_a: f64,
     threshold_b: f64,
+    tu: Option<usize>,
+    tl: Option<usize>,
+    step_size: Option<f64>,
+    lambda_multiplier: Option<f64>,
+    z_scale: Option<f64>,
+    kernel: Option<&str>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
-    if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<u8>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<u8>>()?;
-        colocalization::saca_3d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as u8,
-            threshold_b as u8,
-        )
+    let options = saca_options(tu, tl, step_size, lambda_multiplier, z_scale, kernel)?;
+    dispatch_array!(data_a, PyReadonlyArray3, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArray3<T>>()?;
+        let arr_a = arr_a.as_array();
+        let arr_b = arr_b.as_array();
+        py.allow_threads(|| {
+            colocalization::saca_3d(
+                arr_a,
+                arr_b,
+                threshold_a as T,
+                threshold_b as T,
+                Some(options),
+            )
+        })
         .map(|output| output.into_pyarray(py))
         .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<u16>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<u16>>()?;
-        colocalization::saca_3d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as u16,
-            threshold_b as u16,
-        )
-        .map(|output| output.into_pyarray(py))
+    })
+}
+
+/// Compute 2-dimensional SACA colocalization strength and record the
+/// intermediate results of each iteration.
+///
+/// This function computes the pixel-wise z-score of "saca_2d" and also records
+/// a snapshot of the per-pixel Kendall's Tau-b and square root of the effective
+/// sample size ("√n") after each iteration (i.e. scale). The snapshots show how
+/// the adaptive neighborhood of each pixel converges.
+///
+/// :param data_a: The 2-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: Ihe 2-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+///     below this value are given a weight of 0.0 if the pixel is in the
+///     neighborhood.
+/// :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+///     below this value are given a weight of 0.0 if the pixel is in the
+///     neighborhood.
+/// :param tu: The total number of iterations (i.e. scales), default = 15.
+/// :param tl: The iteration after which the stopping rule is applied, must be
+///     less than "tu", default = 8.
+/// :param step_size: The kernel radius growth factor per iteration, must be
+///     greater than 1.0, default = 1.15.
+/// :param lambda_multiplier: The stopping rule threshold multiplier, must be
+///     greater than 0.0, default = 1.0.
+/// :param z_scale: The z-score scaling, must be greater than 0.0,
+///     default = 1.5.
+/// :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+///     default = "weighted".
+/// :return: The pixel-wise z-score, the per-iteration Kendall's Tau-b
+///     snapshots and the per-iteration "√n" snapshots. The snapshots are
+///     stacked along the first axis.
+#[pyfunction]
+#[pyo3(name = "saca_2d_intermediate")]
+#[pyo3(signature = (data_a, data_b, threshold_a, threshold_b, tu=None, tl=None, step_size=None, lambda_multiplier=None, z_scale=None, kernel=None))]
+pub fn colocalization_saca_2d_intermediate<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    threshold_a: f64,
+    threshold_b: f64,
+    tu: Option<usize>,
+    tl: Option<usize>,
+    step_size: Option<f64>,
+    lambda_multiplier: Option<f64>,
+    z_scale: Option<f64>,
+    kernel: Option<&str>,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray3<f64>>,
+)> {
+    let options = saca_options(tu, tl, step_size, lambda_multiplier, z_scale, kernel)?;
+    dispatch_array!(data_a, PyReadonlyArray2, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArray2<T>>()?;
+        let arr_a = arr_a.as_array();
+        let arr_b = arr_b.as_array();
+        py.allow_threads(|| {
+            colocalization::saca_2d_intermediate(
+                arr_a,
+                arr_b,
+                threshold_a as T,
+                threshold_b as T,
+                Some(options),
+            )
+        })
+        .map(|(z, tau, sqrt_n)| {
+            (
+                z.into_pyarray(py),
+                tau.into_pyarray(py),
+                sqrt_n.into_pyarray(py),
+            )
+        })
         .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<f32>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<f32>>()?;
-        colocalization::saca_3d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as f32,
-            threshold_b as f32,
-        )
-        .map(|output| output.into_pyarray(py))
+    })
+}
+
+/// Compute 3-dimensional SACA colocalization strength and record the
+/// intermediate results of each iteration.
+///
+/// This function computes the pixel-wise z-score of "saca_3d" and also records
+/// a snapshot of the per-pixel Kendall's Tau-b and square root of the effective
+/// sample size ("√n") after each iteration (i.e. scale). The snapshots show how
+/// the adaptive neighborhood of each pixel converges.
+///
+/// :param data_a: The 3-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: Ihe 3-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param threshold_a: Pixel intensity threshold value for image "A". Pixels
+///     below this value are given a weight of 0.0 if the pixel is in the
+///     neighborhood.
+/// :param threshold_b: Pixel intensity threshold value for image "B". Pixels
+///     below this value are given a weight of 0.0 if the pixel is in the
+///     neighborhood.
+/// :param tu: The total number of iterations (i.e. scales), default = 15.
+/// :param tl: The iteration after which the stopping rule is applied, must be
+///     less than "tu", default = 8.
+/// :param step_size: The kernel radius growth factor per iteration, must be
+///     greater than 1.0, default = 1.15.
+/// :param lambda_multiplier: The stopping rule threshold multiplier, must be
+///     greater than 0.0, default = 1.0.
+/// :param z_scale: The z-score scaling, must be greater than 0.0,
+///     default = 1.5.
+/// :param kernel: The neighborhood kernel, "weighted", "circle" or "gaussian",
+///     default = "weighted".
+/// :return: The pixel-wise z-score, the per-iteration Kendall's Tau-b
+///     snapshots and the per-iteration "√n" snapshots. The snapshots are
+///     stacked along the first axis.
+#[pyfunction]
+#[pyo3(name = "saca_3d_intermediate")]
+#[pyo3(signature = (data_a, data_b, threshold_a, threshold_b, tu=None, tl=None, step_size=None, lambda_multiplier=None, z_scale=None, kernel=None))]
+pub fn colocalization_saca_3d_intermediate<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    threshold_a: f64,
+    threshold_b: f64,
+    tu: Option<usize>,
+    tl: Option<usize>,
+    step_size: Option<f64>,
+    lambda_multiplier: Option<f64>,
+    z_scale: Option<f64>,
+    kernel: Option<&str>,
+) -> PyResult<(
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray4<f64>>,
+    Bound<'py, PyArray4<f64>>,
+)> {
+    let options = saca_options(tu, tl, step_size, lambda_multiplier, z_scale, kernel)?;
+    dispatch_array!(data_a, PyReadonlyArray3, |arr_a: T| {
+        let arr_b = data_b.extract::<PyReadonlyArray3<T>>()?;
+        let arr_a = arr_a.as_array();
+        let arr_b = arr_b.as_array();
+        py.allow_threads(|| {
+            colocalization::saca_3d_intermediate(
+                arr_a,
+                arr_b,
+                threshold_a as T,
+                threshold_b as T,
+                Some(options),
+            )
+        })
+        .map(|(z, tau, sqrt_n)| {
+            (
+                z.into_pyarray(py),
+                tau.into_pyarray(py),
+                sqrt_n.into_pyarray(py),
+            )
+        })
         .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<f64>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<f64>>()?;
-        colocalization::saca_3d(arr_a.as_array(), arr_b.as_array(), threshold_a, threshold_b)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    })
 }
 
 /// Create a significant pixel mask from a pixel-wise z-score array.
@@ -193,3 +611,62 @@ pub fn colocalization_saca_significance_mask<'py>(
         ));
     }
 }
+
+/// Build SACA options from optional Python parameters.
+fn saca_options(
+    tu: Option<usize>,
+    tl: Option<usize>,
+    step_size: Option<f64>,
+    lambda_multiplier: Option<f64>,
+    z_scale: Option<f64>,
+    kernel: Option<&str>,
+) -> PyResult<SacaOptions> {
+    let default = SacaOptions::default();
+    let kernel = match kernel.map(|k| k.to_lowercase()).as_deref() {
+        None | Some("weighted") => SacaKernel::Weighted,
+        Some("circle") => SacaKernel::Circle,
+        Some("gaussian") => SacaKernel::Gaussian,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown kernel, supported kernels are \"weighted\", \"circle\", and \"gaussian\".",
+            ));
+        }
+    };
+
+    Ok(SacaOptions {
+        tu: tu.unwrap_or(default.tu),
+        tl: tl.unwrap_or(default.tl),
+        step_size: step_size.unwrap_or(default.step_size),
+        lambda_multiplier: lambda_multiplier.unwrap_or(default.lambda_multiplier),
+        z_scale: z_scale.unwrap_or(default.z_scale),
+        kernel,
+    })
+}
+
+/// Select the pixels of an image inside an optional mask.
+fn apply_mask<'a, T>(
+    data: ArrayViewD<'a, T>,
+    mask: Option<&ArrayViewD<bool>>,
+) -> PyResult<CowArray<'a, T, IxDyn>>
+where
+    T: Clone,
+{
+    match mask {
+        None => Ok(CowArray::from(data)),
+        Some(m) => {
+            if m.shape() != data.shape() {
+                return Err(map_array_error(ImgalError::MismatchedArrayShapes {
+                    shape_a: data.shape().to_vec(),
+                    shape_b: m.shape().to_vec(),
+                }));
+            }
+            let selected: Array1<T> = data
+                .iter()
+                .zip(m.iter())
+                .filter(|&(_, &keep)| keep)
+                .map(|(v, _)| v.clone())
+                .collect();
+            Ok(CowArray::from(selected.into_dyn()))
+        }
+    }
+}
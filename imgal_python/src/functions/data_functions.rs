@@ -0,0 +1,48 @@
+use numpy::{IntoPyArray, PyArray2, PyArray3};
+use pyo3::prelude::*;
+
+use imgal::data;
+
+/// Create a synthetic fluorescent beads image.
+///
+/// This function generates a 2-dimensional (row, col) 128 x 128 image of 32
+/// diffraction limited beads, modeled as Gaussian spots with a standard
+/// deviation of 1.5 pixels, on a dim background with Poisson noise applied.
+/// The image is identical across calls.
+///
+/// :return: The synthetic beads image.
+#[pyfunction]
+#[pyo3(name = "beads")]
+pub fn data_beads(py: Python) -> Bound<PyArray2<u16>> {
+    data::beads().into_pyarray(py)
+}
+
+/// Create a synthetic fluorescent cells image.
+///
+/// This function generates a 2-dimensional (row, col) 256 x 256 image of 24
+/// elliptical cells with a bright center on a dim background, with Poisson
+/// noise applied. Cells may touch or overlap. The image is identical across
+/// calls.
+///
+/// :return: The synthetic cells image.
+#[pyfunction]
+#[pyo3(name = "cells")]
+pub fn data_cells(py: Python) -> Bound<PyArray2<u16>> {
+    data::cells().into_pyarray(py)
+}
+
+/// Create a synthetic fluorescence lifetime imaging (FLIM) decay image.
+///
+/// This function generates a 3-dimensional (row, col, time) 64 x 64 x 256
+/// decay image over a 12.5 ns period with Poisson noise applied. The left half
+/// of the image has a monoexponential lifetime of 1.0 ns and the right half a
+/// monoexponential lifetime of 3.0 ns, with 2000 and 4000 total photon counts
+/// per pixel in the top and bottom halves respectively. The decay axis is 2.
+/// The image is identical across calls.
+///
+/// :return: The synthetic FLIM decay image.
+#[pyfunction]
+#[pyo3(name = "flim_cube")]
+pub fn data_flim_cube(py: Python) -> Bound<PyArray3<f64>> {
+    data::flim_cube().into_pyarray(py)
+}
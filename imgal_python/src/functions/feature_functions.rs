@@ -0,0 +1,131 @@
+use numpy::{IntoPyArray, PyArray2, PyArray3, PyArray4, PyReadonlyArray2, PyReadonlyArray3};
+use pyo3::prelude::*;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::feature;
+
+/// Compute the local binary pattern (LBP) codes of a 2-dimensional image.
+///
+/// This function compares each pixel with "points" neighbors sampled evenly on
+/// a circle of the given radius. With "uniform" set, the rotation invariant
+/// uniform mapping is used, giving "points + 2" codes.
+///
+/// :param data: The input 2-dimensional image.
+/// :param radius: The radius of the sampling circle in pixels, default = 1.0.
+/// :param points: The number of circular sampling points, default = 8.
+/// :param uniform: If "True", use the rotation invariant uniform mapping,
+///     default = True.
+/// :return: The LBP code of each pixel.
+#[pyfunction]
+#[pyo3(name = "local_binary_pattern")]
+#[pyo3(signature = (data, radius=None, points=None, uniform=None))]
+pub fn feature_local_binary_pattern<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Option<f64>,
+    points: Option<usize>,
+    uniform: Option<bool>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| feature::local_binary_pattern(
+        arr.as_array(),
+        radius,
+        points,
+        uniform
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Compute normalized local binary pattern histograms per labeled region.
+///
+/// :param codes: The LBP codes.
+/// :param n_bins: The number of histogram bins, "points + 2" for uniform codes
+///     or "2^points" otherwise.
+/// :param labels: The region label image with the same shape as "codes", label
+///     0 is ignored. If "None", a single histogram of the whole image is
+///     computed.
+/// :return: The histograms with a shape of (max_label + 1, n_bins), where row
+///     "i" is the histogram of label "i".
+#[pyfunction]
+#[pyo3(name = "local_binary_pattern_histogram")]
+#[pyo3(signature = (codes, n_bins, labels=None))]
+pub fn feature_local_binary_pattern_histogram<'py>(
+    py: Python<'py>,
+    codes: PyReadonlyArray2<usize>,
+    n_bins: usize,
+    labels: Option<PyReadonlyArray2<usize>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    feature::local_binary_pattern_histogram(
+        codes.as_array(),
+        n_bins,
+        labels.as_ref().map(|l| l.as_array()),
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error)
+}
+
+/// Compute the local orientation, coherence and energy of a 2-dimensional
+/// image with the structure tensor.
+///
+/// This function computes the Gaussian weighted structure tensor of each pixel.
+/// The orientation is the direction of the local structure in radians between
+/// -pi/2 and pi/2, measured from the column axis towards the row axis. The
+/// coherence ranges from 0.0 (isotropic) to 1.0 (perfectly oriented) and the
+/// energy is the tensor trace.
+///
+/// :param data: The input 2-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian integration window,
+///     default = 2.0.
+/// :return: The orientation, coherence and energy maps.
+#[pyfunction]
+#[pyo3(name = "structure_tensor_2d")]
+#[pyo3(signature = (data, sigma=None))]
+pub fn feature_structure_tensor_2d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: Option<f64>,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| feature::structure_tensor_2d(
+        arr.as_array(),
+        sigma
+    )
+    .map(|(o, c, e)| (o.into_pyarray(py), c.into_pyarray(py), e.into_pyarray(py)))
+    .map_err(map_array_error))
+}
+
+/// Compute the local orientation, coherence and energy of a 3-dimensional
+/// image with the structure tensor.
+///
+/// This function computes the 3 x 3 structure tensor of each voxel. The local
+/// orientation is the unit eigenvector of the smallest eigenvalue, the
+/// direction of least intensity change (e.g. along a fiber).
+///
+/// :param data: The input 3-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian integration window,
+///     default = 2.0.
+/// :return: The orientation vectors with a shape of (pln, row, col, 3), and the
+///     coherence and energy maps.
+#[pyfunction]
+#[pyo3(name = "structure_tensor_3d")]
+#[pyo3(signature = (data, sigma=None))]
+pub fn feature_structure_tensor_3d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: Option<f64>,
+) -> PyResult<(
+    Bound<'py, PyArray4<f64>>,
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray3<f64>>,
+)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| feature::structure_tensor_3d(
+        arr.as_array(),
+        sigma
+    )
+    .map(|(o, c, e)| (o.into_pyarray(py), c.into_pyarray(py), e.into_pyarray(py)))
+    .map_err(map_array_error))
+}
@@ -1,7 +1,16 @@
-use numpy::{IntoPyArray, PyArray1};
+use numpy::{
+    IntoPyArray, PyArray1, PyArray2, PyArrayDyn, PyReadonlyArray2, PyReadonlyArrayDyn,
+    PyReadwriteArray2,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use imgal::filter;
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::filter::{
+    self, Baseline, DestripeMethod, ShrinkageRule, StripeDirection, TemporalFilter,
+};
+use imgal::transform::Wavelet;
 
 /// Convolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
 ///
@@ -52,3 +61,468 @@ pub fn filter_fft_deconvolve_1d(
     let output = filter::fft_deconvolve_1d(&a, &b, epsilon);
     Ok(output.into_pyarray(py))
 }
+
+/// Remove row and/or column fixed-pattern noise from a 2-dimensional image.
+///
+/// This function removes stripe artifacts caused by per-line offsets, such as
+/// the column noise of sCMOS sensors or the line noise of resonant scanners.
+/// The "median" method subtracts the deviation of each line median from a
+/// moving median of the line medians. The "fft" method removes the Fourier
+/// coefficients of line offsets above a cutoff frequency.
+///
+/// :param data: The input 2-dimensional image.
+/// :param direction: The stripe orientation, "horizontal", "vertical" or
+///     "both", default = "both".
+/// :param method: The stripe removal method, "median" or "fft", default =
+///     "median".
+/// :param window: The moving median window in lines of the "median" method,
+///     default = 31.
+/// :param width: The notch width in frequency bins of the "fft" method,
+///     default = 1.
+/// :param cutoff: The highest preserved frequency bin along the stripe profile
+///     of the "fft" method, default = 2.
+/// :return: The destriped image.
+#[pyfunction]
+#[pyo3(name = "destripe")]
+#[pyo3(signature = (data, direction=None, method=None, window=None, width=None, cutoff=None))]
+pub fn filter_destripe<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    direction: Option<String>,
+    method: Option<String>,
+    window: Option<usize>,
+    width: Option<usize>,
+    cutoff: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let direction = match direction.map(|d| d.to_lowercase()).as_deref() {
+        None | Some("both") => StripeDirection::Both,
+        Some("horizontal") => StripeDirection::Horizontal,
+        Some("vertical") => StripeDirection::Vertical,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown direction, supported directions are \"horizontal\", \"vertical\", and \"both\".",
+            ));
+        }
+    };
+    let method = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("median") => DestripeMethod::Median {
+            window: window.unwrap_or(31),
+        },
+        Some("fft") => DestripeMethod::FftNotch {
+            width: width.unwrap_or(1),
+            cutoff: cutoff.unwrap_or(2),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"median\" and \"fft\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| filter::destripe(arr, Some(direction), Some(method)))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Denoise a 2-dimensional image by wavelet shrinkage.
+///
+/// This function denoises an image by soft-thresholding its wavelet detail
+/// coefficients. If the noise standard deviation is not given, it is estimated
+/// from the finest diagonal subband.
+///
+/// :param data: The input 2-dimensional image.
+/// :param wavelet: The wavelet, "haar", "db2" or "db4", default = "haar".
+/// :param levels: The number of decomposition levels, default = 3.
+/// :param rule: The threshold rule, "bayes" or "visu", default = "bayes".
+/// :param sigma: The noise standard deviation. If "None", it is estimated from
+///     the data.
+/// :return: The denoised image.
+#[pyfunction]
+#[pyo3(name = "wavelet_denoise")]
+#[pyo3(signature = (data, wavelet=None, levels=None, rule=None, sigma=None))]
+pub fn filter_wavelet_denoise<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    wavelet: Option<String>,
+    levels: Option<usize>,
+    rule: Option<String>,
+    sigma: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let wavelet = match wavelet.map(|w| w.to_lowercase()).as_deref() {
+        None | Some("haar") => Wavelet::Haar,
+        Some("db2") => Wavelet::Db2,
+        Some("db4") => Wavelet::Db4,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown wavelet, supported wavelets are \"haar\", \"db2\", and \"db4\".",
+            ));
+        }
+    };
+    let rule = match rule.map(|r| r.to_lowercase()).as_deref() {
+        None | Some("bayes") => ShrinkageRule::BayesShrink,
+        Some("visu") => ShrinkageRule::VisuShrink,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown rule, supported rules are \"bayes\" and \"visu\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| filter::wavelet_denoise(arr, wavelet, levels, Some(rule), sigma))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Smooth a 2-dimensional image with a Gaussian filter.
+///
+/// This function convolves a 2-dimensional image with a normalized Gaussian
+/// kernel, truncated at 4 sigma. The kernel is separable, so rows and columns
+/// are filtered in two 1-dimensional passes. Pixels outside of the image are
+/// replaced by the nearest edge pixel.
+///
+/// :param data: The input 2-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian kernel in pixels. If
+///     "sigma" is 0.0, the image is returned unfiltered.
+/// :param out: An optional float64 output array of the same shape as "data" to
+///     write the smoothed image into, default = None.
+/// :return: The smoothed image. If "out" is given, "out" is returned.
+#[pyfunction]
+#[pyo3(name = "gaussian_2d")]
+#[pyo3(signature = (data, sigma, out=None))]
+pub fn filter_gaussian_2d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: f64,
+    out: Option<PyReadwriteArray2<'py, f64>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        match out {
+            // write the smoothed image directly into "out"
+            Some(mut o) => {
+                let o_arr = o.as_array_mut();
+                py.allow_threads(|| filter::gaussian_2d_into(arr, sigma, o_arr))
+                    .map_err(map_array_error)?;
+                Ok((**o).clone())
+            }
+            None => py
+                .allow_threads(|| filter::gaussian_2d(arr, sigma))
+                .map(|output| output.into_pyarray(py))
+                .map_err(map_array_error),
+        }
+    })
+}
+
+/// Compute the gradient of a 2-dimensional image.
+///
+/// This function computes the forward differences of an image along the rows
+/// and columns. The differences across the last row and column are 0.0 (i.e.
+/// Neumann boundaries), so that "divergence(*gradient(I))" equals
+/// "laplacian(I)".
+///
+/// :param data: The input 2-dimensional image.
+/// :return: The row and column gradient images.
+#[pyfunction]
+#[pyo3(name = "gradient")]
+pub fn filter_gradient<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>)> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        let (grad_r, grad_c) = py.allow_threads(|| filter::gradient(arr));
+        Ok((grad_r.into_pyarray(py), grad_c.into_pyarray(py)))
+    })
+}
+
+/// Compute the divergence of a 2-dimensional vector field.
+///
+/// This function computes the backward differences of a vector field (e.g. a
+/// modified image gradient) along the rows and columns. The field is 0.0
+/// before the first and across the last row and column (i.e. Neumann
+/// boundaries), matching "gradient".
+///
+/// :param grad_row: The row component of the vector field.
+/// :param grad_col: The column component of the vector field.
+/// :return: The divergence image.
+#[pyfunction]
+#[pyo3(name = "divergence")]
+pub fn filter_divergence<'py>(
+    py: Python<'py>,
+    grad_row: PyReadonlyArray2<'py, f64>,
+    grad_col: PyReadonlyArray2<'py, f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let (gr, gc) = (grad_row.as_array(), grad_col.as_array());
+    py.allow_threads(|| filter::divergence(gr, gc))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the Laplacian of a 2-dimensional image.
+///
+/// This function computes the 5-point discrete Laplacian of an image, the sum
+/// of the differences between each pixel and its 4-connected neighbors within
+/// the image (i.e. Neumann boundaries).
+///
+/// :param data: The input 2-dimensional image.
+/// :return: The Laplacian image.
+#[pyfunction]
+#[pyo3(name = "laplacian")]
+pub fn filter_laplacian<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        Ok(py.allow_threads(|| filter::laplacian(arr)).into_pyarray(py))
+    })
+}
+
+/// Solve the Poisson equation of a 2-dimensional image.
+///
+/// This function computes the image "u" whose Laplacian equals the right-hand
+/// side "f", ∇²u = f, with Neumann boundaries, using the FFT of the mirrored
+/// image. The solution is unique up to a constant, set by "mean". Solving with
+/// the divergence of a modified gradient field reconstructs an image from its
+/// edited gradients, e.g. for gradient-domain background removal or seamless
+/// blending.
+///
+/// :param rhs: The right-hand side, "f".
+/// :param mean: The mean of the solution, default = 0.0.
+/// :return: The solution image, "u".
+#[pyfunction]
+#[pyo3(name = "poisson_solve")]
+#[pyo3(signature = (rhs, mean=None))]
+pub fn filter_poisson_solve<'py>(
+    py: Python<'py>,
+    rhs: PyReadonlyArray2<'py, f64>,
+    mean: Option<f64>,
+) -> Bound<'py, PyArray2<f64>> {
+    let rhs = rhs.as_array();
+    py.allow_threads(|| filter::poisson_solve(rhs, mean))
+        .into_pyarray(py)
+}
+
+/// Smooth a 2-dimensional image with a guided filter.
+///
+/// This function smooths an input image "p" (e.g. a lifetime map) with the
+/// edges of a guidance image "I" (e.g. the intensity image). Within each
+/// (2 * radius + 1)² window, the output is modeled as a linear transform of the
+/// guide, fitted to the input by regularized least squares:
+///
+/// a = cov(I, p) / (var(I) + ε)
+/// b = mean(p) - a * mean(I)
+/// q = mean(a) * I + mean(b)
+///
+/// Where the guide is flat (var ≪ ε) the input is averaged, while edges of the
+/// guide (var ≫ ε) are preserved in the output. Windows are truncated at the
+/// image edges. Non-finite input pixels propagate to all windows that contain
+/// them and should be filled beforehand.
+///
+/// :param data: The input 2-dimensional image to smooth, "p".
+/// :param guide: The 2-dimensional guidance image, "I". The input image can be
+///     its own guide for edge-preserving smoothing.
+/// :param radius: The window radius in pixels.
+/// :param epsilon: The regularization "ε" (ε > 0), in squared guide units.
+///     Guide edges with a variance well above "epsilon" are preserved.
+/// :return: The smoothed image.
+#[pyfunction]
+#[pyo3(name = "guided")]
+pub fn filter_guided<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray2<'py, f64>,
+    guide: Bound<'py, PyAny>,
+    radius: usize,
+    epsilon: f64,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let data = data.as_array();
+    dispatch_array!(guide, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| filter::guided(data, arr, radius, epsilon))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Smooth a 2-dimensional image with a generalized Kuwahara filter.
+///
+/// This function divides the circular window of "radius" pixels around each
+/// pixel into "sectors" equal angular sectors, each including the center pixel,
+/// and computes the mean and variance of every sector. By default the output is
+/// the mean of the sector with the lowest variance (i.e. the classic Kuwahara
+/// filter for 4 sectors). With a "sharpness" q, all sectors are blended with
+/// weights that decrease with their variance:
+///
+/// wₖ = 1 / (1 + σₖ²)^(q / 2)
+///
+/// Which avoids the blocky artifacts of the hard selection. Windows are
+/// truncated at the image edges.
+///
+/// :param data: The input 2-dimensional image.
+/// :param radius: The window radius in pixels. If "radius" is 0, the image is
+///     returned unfiltered.
+/// :param sectors: The number of angular sectors, default = 4.
+/// :param sharpness: The weighting exponent "q" of the sectors. If "None", the
+///     sector with the lowest variance is selected.
+/// :return: The smoothed image.
+#[pyfunction]
+#[pyo3(name = "kuwahara")]
+#[pyo3(signature = (data, radius, sectors=None, sharpness=None))]
+pub fn filter_kuwahara<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: usize,
+    sectors: Option<usize>,
+    sharpness: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| filter::kuwahara(arr, radius, sectors, sharpness))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Smooth an n-dimensional array with a mean (box) filter.
+///
+/// This function replaces each element of an n-dimensional array (e.g. a 2D
+/// image or 3D stack) by the mean of the (2 * radius + 1)ⁿ box centered on it.
+/// The box sums are computed from the integral image of the array, so the
+/// filter runs in constant time per element independently of "radius". Boxes
+/// are truncated at the array edges and averaged over the elements within the
+/// array only.
+///
+/// :param data: The input n-dimensional array.
+/// :param radius: The box radius in elements. If "radius" is 0, the array is
+///     returned unfiltered.
+/// :return: The smoothed array.
+#[pyfunction]
+#[pyo3(name = "mean")]
+pub fn filter_mean<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: usize,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        Ok(py
+            .allow_threads(|| filter::mean(arr, radius))
+            .into_pyarray(py))
+    })
+}
+
+/// Smooth an n-dimensional stack along its time axis.
+///
+/// This function filters the time trace of each pixel of an n-dimensional
+/// stack along "axis" with a moving average, a moving median or causal
+/// exponential smoothing, s(t) = α·x(t) + (1 - α)·s(t - 1). Moving windows are
+/// centered and truncated at the start and end of the traces.
+///
+/// :param data: The input n-dimensional stack.
+/// :param method: The temporal filter, "mean", "median" or "exponential",
+///     default = "mean".
+/// :param window: The moving window in frames of the "mean" and "median"
+///     methods, default = 5.
+/// :param alpha: The smoothing factor in (0.0, 1.0] of the "exponential"
+///     method, default = 0.3.
+/// :param axis: The time axis, default = 0.
+/// :return: The smoothed stack, with the shape of "data".
+#[pyfunction]
+#[pyo3(name = "temporal")]
+#[pyo3(signature = (data, method=None, window=None, alpha=None, axis=None))]
+pub fn filter_temporal<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<String>,
+    window: Option<usize>,
+    alpha: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let window = window.unwrap_or(5);
+    let filter = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("mean") => TemporalFilter::MovingAverage { window },
+        Some("median") => TemporalFilter::MovingMedian { window },
+        Some("exponential") => TemporalFilter::Exponential {
+            alpha: alpha.unwrap_or(0.3),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"mean\", \"median\", and \"exponential\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| filter::temporal(arr, filter, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the relative fluorescence change (ΔF/F₀) of an n-dimensional
+/// stack.
+///
+/// This function computes (F(t) - F₀) / F₀ of the time trace of each pixel of
+/// an n-dimensional stack along "axis", where the baseline F₀ is either the
+/// mean of the frames in "start..end" or a running percentile of the "window"
+/// frames centered on each frame. Frames with a zero baseline are NaN.
+///
+/// :param data: The input n-dimensional stack, with the background
+///     subtracted.
+/// :param baseline: The baseline estimate, "frames" or "percentile",
+///     default = "frames".
+/// :param start: The first baseline frame of the "frames" baseline,
+///     default = 0.
+/// :param end: The end (exclusive) baseline frame of the "frames" baseline,
+///     default = the number of frames.
+/// :param percentile: The percentile (0.0 to 100.0) of the "percentile"
+///     baseline, default = 10.0.
+/// :param window: The window in frames of the "percentile" baseline,
+///     default = 31.
+/// :param axis: The time axis, default = 0.
+/// :return: The ΔF/F₀ stack, with the shape of "data".
+#[pyfunction]
+#[pyo3(name = "delta_f_over_f")]
+#[pyo3(signature = (data, baseline=None, start=None, end=None, percentile=None, window=None, axis=None))]
+pub fn filter_delta_f_over_f<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    baseline: Option<String>,
+    start: Option<usize>,
+    end: Option<usize>,
+    percentile: Option<f64>,
+    window: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let frames = match baseline.map(|b| b.to_lowercase()).as_deref() {
+        None | Some("frames") => true,
+        Some("percentile") => false,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown baseline, supported baselines are \"frames\" and \"percentile\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        let baseline = if frames {
+            Baseline::Frames {
+                start: start.unwrap_or(0),
+                end: end
+                    .unwrap_or_else(|| arr.shape().get(axis.unwrap_or(0)).copied().unwrap_or(0)),
+            }
+        } else {
+            Baseline::Percentile {
+                percentile: percentile.unwrap_or(10.0),
+                window: window.unwrap_or(31),
+            }
+        };
+        py.allow_threads(|| filter::delta_f_over_f(arr, baseline, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
@@ -0,0 +1,746 @@
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::fitting::{
+    self, GaussianFit, LaguerreFit, LifetimeDistribution, MemOptions, ModelCriterion,
+};
+
+/// Compute the chi-square statistic of a model fit to a decay curve.
+///
+/// This function computes the chi-square statistic between measured photon
+/// counts and the evaluated model curve, using the Poisson (Neyman) variance
+/// of the data:
+///
+/// χ² = Σₖ (Dₖ - Mₖ)² / max(Dₖ, 1)
+///
+/// :param data: The measured 1-dimensional decay curve.
+/// :param model: The evaluated model curve, the same length as "data".
+/// :return: The chi-square statistic.
+#[pyfunction]
+#[pyo3(name = "chi_square")]
+pub fn fitting_chi_square(data: Vec<f64>, model: Vec<f64>) -> PyResult<f64> {
+    fitting::chi_square(&data, &model).map_err(map_array_error)
+}
+
+/// Compute the Durbin-Watson statistic of fit residuals.
+///
+/// This function computes the Durbin-Watson statistic, a test for serial
+/// correlation of consecutive residuals:
+///
+/// d = Σₖ (rₖ - rₖ₋₁)² / Σₖ rₖ²
+///
+/// :param residuals: The (weighted) residuals of a fit.
+/// :return: The Durbin-Watson statistic, between 0.0 and 4.0, where 2.0
+///     indicates uncorrelated residuals.
+#[pyfunction]
+#[pyo3(name = "durbin_watson")]
+pub fn fitting_durbin_watson(residuals: Vec<f64>) -> f64 {
+    fitting::durbin_watson(&residuals)
+}
+
+/// Estimate multiexponential lifetimes of a decay with Prony's method.
+///
+/// This function estimates the lifetimes and amplitudes of a sum of
+/// exponentials with the least squares Prony method, modeling the decay
+/// samples after "start" as:
+///
+/// I(k) = Σᵢ Aᵢ × zᵢᵏ,  zᵢ = exp(-Δt/τᵢ)
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param components: The number of exponential components.
+/// :param start: The first bin of the fitted range, default = the peak bin.
+/// :return: The lifetimes and amplitudes (at the "start" bin) of each
+///     component, sorted by increasing lifetime. Components with non-physical
+///     roots are NaN.
+#[pyfunction]
+#[pyo3(name = "prony")]
+#[pyo3(signature = (data, period, components, start=None))]
+pub fn fitting_prony(
+    py: Python,
+    data: Vec<f64>,
+    period: f64,
+    components: usize,
+    start: Option<usize>,
+) -> PyResult<(Bound<PyArray1<f64>>, Bound<PyArray1<f64>>)> {
+    fitting::prony(&data, period, components, start)
+        .map(|(t, a)| (t.into_pyarray(py), a.into_pyarray(py)))
+        .map_err(map_array_error)
+}
+
+/// Estimate per-pixel multiexponential lifetimes of a 3-dimensional decay
+/// image with Prony's method.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param period: The period (i.e. time interval).
+/// :param components: The number of exponential components.
+/// :param start: The first bin of the fitted range, default = the peak bin of
+///     each pixel.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The lifetime and amplitude images with shape
+///     (rows, cols, components).
+#[pyfunction]
+#[pyo3(name = "prony_image")]
+#[pyo3(signature = (data, period, components, start=None, axis=None))]
+pub fn fitting_prony_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    components: usize,
+    start: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray3<f64>>)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| fitting::prony_image(arr, period, components, start, axis))
+            .map(|(t, a)| (t.into_pyarray(py), a.into_pyarray(py)))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the reduced chi-square statistic of a model fit to a decay curve.
+///
+/// χ²ᵣ = χ² / (n - p)
+///
+/// :param data: The measured 1-dimensional decay curve.
+/// :param model: The evaluated model curve, the same length as "data".
+/// :param n_params: The number of free model parameters.
+/// :return: The reduced chi-square statistic.
+#[pyfunction]
+#[pyo3(name = "reduced_chi_square")]
+pub fn fitting_reduced_chi_square(
+    data: Vec<f64>,
+    model: Vec<f64>,
+    n_params: usize,
+) -> PyResult<f64> {
+    fitting::reduced_chi_square(&data, &model, n_params).map_err(map_array_error)
+}
+
+/// Compute the per-pixel reduced chi-square image of a model fit to a
+/// 3-dimensional decay image.
+///
+/// :param data: The measured 3-dimensional decay image.
+/// :param model: The evaluated model image, the same shape as "data".
+/// :param n_params: The number of free model parameters.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The reduced chi-square image.
+#[pyfunction]
+#[pyo3(name = "reduced_chi_square_image")]
+#[pyo3(signature = (data, model, n_params, axis=None))]
+pub fn fitting_reduced_chi_square_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    model: PyReadonlyArray3<f64>,
+    n_params: usize,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let model = model.as_array();
+        py.allow_threads(|| fitting::reduced_chi_square_image(arr, model, n_params, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Select the number of exponential components of a decay curve.
+///
+/// This function fits 1 to "max_components" exponential components to a
+/// 1-dimensional decay curve (or the summed decay of a region of interest)
+/// with Prony's method and selects the preferred model order with the Akaike
+/// (AIC) or Bayesian (BIC) information criterion or a sequential F-test of the
+/// nested models.
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param criterion: The model selection criterion, "aic", "bic" or "ftest",
+///     default = "bic".
+/// :param alpha: The significance level of the "ftest" criterion,
+///     default = 0.05.
+/// :param max_components: The maximum number of exponential components,
+///     default = 3.
+/// :param start: The first bin of the fitted range, default = the peak bin.
+/// :return: The selected number of components, 0 if no order could be fit.
+#[pyfunction]
+#[pyo3(name = "select_model")]
+#[pyo3(signature = (data, period, criterion=None, alpha=None, max_components=None, start=None))]
+pub fn fitting_select_model(
+    data: Vec<f64>,
+    period: f64,
+    criterion: Option<String>,
+    alpha: Option<f64>,
+    max_components: Option<usize>,
+    start: Option<usize>,
+) -> PyResult<usize> {
+    let criterion = parse_criterion(criterion, alpha)?;
+    fitting::select_model(&data, period, criterion, max_components, start).map_err(map_array_error)
+}
+
+/// Select the number of exponential components of each pixel of a
+/// 3-dimensional decay image.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param period: The period (i.e. time interval).
+/// :param criterion: The model selection criterion, "aic", "bic" or "ftest",
+///     default = "bic".
+/// :param alpha: The significance level of the "ftest" criterion,
+///     default = 0.05.
+/// :param max_components: The maximum number of exponential components,
+///     default = 3.
+/// :param start: The first bin of the fitted range, default = the peak bin of
+///     each pixel.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The selected number of components of each pixel, 0 for pixels
+///     that can not be fit.
+#[pyfunction]
+#[pyo3(name = "select_model_image")]
+#[pyo3(signature = (data, period, criterion=None, alpha=None, max_components=None, start=None, axis=None))]
+pub fn fitting_select_model_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    criterion: Option<String>,
+    alpha: Option<f64>,
+    max_components: Option<usize>,
+    start: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    let criterion = parse_criterion(criterion, alpha)?;
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            fitting::select_model_image(arr, period, criterion, max_components, start, axis)
+        })
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Estimate a lifetime by log-linear regression over the tail of a decay.
+///
+/// This function fits a monoexponential model to the tail of a 1-dimensional
+/// decay curve by counts weighted linear regression of the logarithm of the
+/// counts against time:
+///
+/// ln I(t) = ln A - t/τ
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param start: The first bin of the tail range, default = the peak bin.
+/// :param end: The end bin (exclusive) of the tail range, default = the length
+///     of "data".
+/// :return: The lifetime and the amplitude at the "start" bin.
+#[pyfunction]
+#[pyo3(name = "tail_fit")]
+#[pyo3(signature = (data, period, start=None, end=None))]
+pub fn fitting_tail_fit(
+    data: Vec<f64>,
+    period: f64,
+    start: Option<usize>,
+    end: Option<usize>,
+) -> PyResult<(f64, f64)> {
+    fitting::tail_fit(&data, period, start, end).map_err(map_array_error)
+}
+
+/// Estimate per-pixel lifetimes by log-linear regression over the tail of a
+/// 3-dimensional decay image.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param period: The period (i.e. time interval).
+/// :param start: The first bin of the tail range, default = the peak bin of
+///     each pixel.
+/// :param end: The end bin (exclusive) of the tail range, default = the length
+///     of the decay axis.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The lifetime and amplitude images, pixels that can not be fit are
+///     NaN.
+#[pyfunction]
+#[pyo3(name = "tail_fit_image")]
+#[pyo3(signature = (data, period, start=None, end=None, axis=None))]
+pub fn fitting_tail_fit_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    start: Option<usize>,
+    end: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| fitting::tail_fit_image(arr, period, start, end, axis))
+            .map(|(t, a)| (t.into_pyarray(py), a.into_pyarray(py)))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the weighted residuals of a model fit to a decay curve.
+///
+/// rₖ = (Dₖ - Mₖ) / √max(Dₖ, 1)
+///
+/// :param data: The measured 1-dimensional decay curve.
+/// :param model: The evaluated model curve, the same length as "data".
+/// :return: The Poisson weighted residuals.
+#[pyfunction]
+#[pyo3(name = "weighted_residuals")]
+pub fn fitting_weighted_residuals(
+    py: Python,
+    data: Vec<f64>,
+    model: Vec<f64>,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    fitting::weighted_residuals(&data, &model)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the weighted residuals image of a model fit to a 3-dimensional
+/// decay image.
+///
+/// :param data: The measured 3-dimensional decay image.
+/// :param model: The evaluated model image, the same shape as "data".
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The Poisson weighted residuals image.
+#[pyfunction]
+#[pyo3(name = "weighted_residuals_image")]
+#[pyo3(signature = (data, model, axis=None))]
+pub fn fitting_weighted_residuals_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    model: PyReadonlyArray3<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let model = model.as_array();
+        py.allow_threads(|| fitting::weighted_residuals_image(arr, model, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Parse a model selection criterion name.
+fn parse_criterion(criterion: Option<String>, alpha: Option<f64>) -> PyResult<ModelCriterion> {
+    match criterion.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("bic") => Ok(ModelCriterion::Bic),
+        Some("aic") => Ok(ModelCriterion::Aic),
+        Some("ftest") => Ok(ModelCriterion::FTest {
+            alpha: alpha.unwrap_or(0.05),
+        }),
+        Some(_) => Err(PyErr::new::<PyValueError, _>(
+            "Unknown criterion, supported criteria are \"aic\", \"bic\", and \"ftest\".",
+        )),
+    }
+}
+
+/// Fit a 2-dimensional Gaussian to a single peak.
+///
+/// This function fits an axis-aligned Gaussian with a constant offset to an
+/// image containing a single peak (e.g. a cropped spot or bead) by
+/// Levenberg-Marquardt least squares:
+///
+/// f(r, c) = offset + A · exp(-(r - cᵣ)² / 2σᵣ² - (c - c꜀)² / 2σ꜀²)
+///
+/// :param data: The 2-dimensional image of the peak.
+/// :param max_iterations: The maximum number of Levenberg-Marquardt
+///     iterations, default = 200.
+/// :return: A dictionary with the "amplitude", "offset", (row, col) "center"
+///     and "sigma", and the sum of squared "residual".
+#[pyfunction]
+#[pyo3(name = "gaussian_2d")]
+#[pyo3(signature = (data, max_iterations=None))]
+pub fn fitting_gaussian_2d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    max_iterations: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let fit = dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| fitting::gaussian_2d(arr, max_iterations))
+            .map_err(map_array_error)
+    })?;
+
+    gaussian_fit_dict(py, fit)
+}
+
+/// Fit a 3-dimensional Gaussian to a single peak.
+///
+/// This function fits an axis-aligned Gaussian with a constant offset to a
+/// volume containing a single peak by Levenberg-Marquardt least squares:
+///
+/// f(p, r, c) = offset + A · exp(-Σₖ (xₖ - cₖ)² / 2σₖ²)
+///
+/// :param data: The 3-dimensional volume of the peak.
+/// :param max_iterations: The maximum number of Levenberg-Marquardt
+///     iterations, default = 200.
+/// :return: A dictionary with the "amplitude", "offset", (pln, row, col)
+///     "center" and "sigma", and the sum of squared "residual".
+#[pyfunction]
+#[pyo3(name = "gaussian_3d")]
+#[pyo3(signature = (data, max_iterations=None))]
+pub fn fitting_gaussian_3d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    max_iterations: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let fit = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| fitting::gaussian_3d(arr, max_iterations))
+            .map_err(map_array_error)
+    })?;
+
+    gaussian_fit_dict(py, fit)
+}
+
+/// Convert a Gaussian fit into a Python dictionary.
+fn gaussian_fit_dict(py: Python<'_>, fit: GaussianFit) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("amplitude", fit.amplitude)?;
+    dict.set_item("offset", fit.offset)?;
+    dict.set_item("center", fit.center)?;
+    dict.set_item("sigma", fit.sigma)?;
+    dict.set_item("residual", fit.residual)?;
+
+    Ok(dict)
+}
+
+/// Recover a lifetime distribution from a decay curve with the maximum entropy
+/// method.
+///
+/// This function models a decay as a nonnegative distribution of amplitudes
+/// over a logarithmically spaced lifetime grid, optionally convolved with an
+/// instrument response function (IRF), and maximizes the entropy of the
+/// distribution relative to a flat model while fitting the data:
+///
+/// I(t) = IRF(t) ⊛ Σⱼ aⱼ exp(-t/τⱼ)
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param irf: The instrument response function with the length of "data". If
+///     "None", the decay is fitted from the start bin without convolution.
+/// :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+///     the grid spans from the bin width to the period.
+/// :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+/// :param regularization: The relative entropy weight. If "None", the weight
+///     is chosen so the reduced chi-square of the fit is close to 1.0.
+/// :param max_iterations: The maximum number of Newton iterations per solve,
+///     default = 100.
+/// :param start: The first fitted bin. If "None", the peak bin is used
+///     without an IRF and the first bin with an IRF.
+/// :return: A dictionary with the "lifetimes" grid and their "amplitudes",
+///     the amplitude weighted "mean_lifetime", the
+///     "intensity_weighted_lifetime", the "log_width" of the distribution,
+///     the "regularization" weight and the "reduced_chi_square" of the fit.
+#[pyfunction]
+#[pyo3(name = "mem_lifetime_distribution")]
+#[pyo3(signature = (data, period, irf=None, lifetime_range=None, n_lifetimes=None, regularization=None, max_iterations=None, start=None))]
+pub fn fitting_mem_lifetime_distribution<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    period: f64,
+    irf: Option<Vec<f64>>,
+    lifetime_range: Option<(f64, f64)>,
+    n_lifetimes: Option<usize>,
+    regularization: Option<f64>,
+    max_iterations: Option<usize>,
+    start: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let options = mem_options(
+        lifetime_range,
+        n_lifetimes,
+        regularization,
+        max_iterations,
+        start,
+    );
+    let dist = py
+        .allow_threads(|| {
+            fitting::mem_lifetime_distribution(&data, period, irf.as_deref(), Some(options))
+        })
+        .map_err(map_array_error)?;
+
+    lifetime_distribution_dict(py, dist)
+}
+
+/// Recover the lifetime distribution of each pixel of a 3-dimensional decay
+/// image with the maximum entropy method.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param period: The period (i.e. time interval).
+/// :param irf: The instrument response function with the length of the decay
+///     axis. If "None", each decay is fitted from its start bin without
+///     convolution.
+/// :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+///     the grid spans from the bin width to the period.
+/// :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+/// :param regularization: The relative entropy weight. If "None", the weight
+///     is chosen per pixel so the reduced chi-square is close to 1.0.
+/// :param max_iterations: The maximum number of Newton iterations per solve,
+///     default = 100.
+/// :param start: The first fitted bin. If "None", the peak bin of each pixel
+///     is used without an IRF and the first bin with an IRF.
+/// :param mask: The 2-dimensional boolean mask of the pixels to fit. If
+///     "None", all pixels are fitted.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The amplitude weighted mean lifetime, intensity weighted mean
+///     lifetime and log-lifetime width images. Pixels without counts or
+///     outside of "mask" are NaN.
+#[pyfunction]
+#[pyo3(name = "mem_lifetime_distribution_image")]
+#[pyo3(signature = (data, period, irf=None, lifetime_range=None, n_lifetimes=None, regularization=None, max_iterations=None, start=None, mask=None, axis=None))]
+pub fn fitting_mem_lifetime_distribution_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    irf: Option<Vec<f64>>,
+    lifetime_range: Option<(f64, f64)>,
+    n_lifetimes: Option<usize>,
+    regularization: Option<f64>,
+    max_iterations: Option<usize>,
+    start: Option<usize>,
+    mask: Option<PyReadonlyArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    let options = mem_options(
+        lifetime_range,
+        n_lifetimes,
+        regularization,
+        max_iterations,
+        start,
+    );
+    let m = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            fitting::mem_lifetime_distribution_image(
+                arr,
+                period,
+                irf.as_deref(),
+                Some(options),
+                m,
+                axis,
+            )
+        })
+        .map(|(a, i, w)| (a.into_pyarray(py), i.into_pyarray(py), w.into_pyarray(py)))
+        .map_err(map_array_error)
+    })
+}
+
+/// Recover the lifetime distribution of a region of a 3-dimensional decay
+/// image with the maximum entropy method.
+///
+/// This function sums the decay curves of the pixels of "mask" into a single
+/// high count decay and recovers its lifetime distribution.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param mask: The 2-dimensional boolean mask of the region.
+/// :param period: The period (i.e. time interval).
+/// :param irf: The instrument response function with the length of the decay
+///     axis. If "None", the decay is fitted from the start bin without
+///     convolution.
+/// :param lifetime_range: The (min, max) lifetimes of the grid. If "None",
+///     the grid spans from the bin width to the period.
+/// :param n_lifetimes: The number of lifetimes of the grid, default = 32.
+/// :param regularization: The relative entropy weight. If "None", the weight
+///     is chosen so the reduced chi-square of the fit is close to 1.0.
+/// :param max_iterations: The maximum number of Newton iterations per solve,
+///     default = 100.
+/// :param start: The first fitted bin. If "None", the peak bin is used
+///     without an IRF and the first bin with an IRF.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: A dictionary of the region's lifetime distribution, see
+///     "mem_lifetime_distribution".
+#[pyfunction]
+#[pyo3(name = "mem_roi_distribution")]
+#[pyo3(signature = (data, mask, period, irf=None, lifetime_range=None, n_lifetimes=None, regularization=None, max_iterations=None, start=None, axis=None))]
+pub fn fitting_mem_roi_distribution<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    mask: PyReadonlyArray2<bool>,
+    period: f64,
+    irf: Option<Vec<f64>>,
+    lifetime_range: Option<(f64, f64)>,
+    n_lifetimes: Option<usize>,
+    regularization: Option<f64>,
+    max_iterations: Option<usize>,
+    start: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let options = mem_options(
+        lifetime_range,
+        n_lifetimes,
+        regularization,
+        max_iterations,
+        start,
+    );
+    let m = mask.as_array();
+    let dist = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            fitting::mem_roi_distribution(arr, m, period, irf.as_deref(), Some(options), axis)
+        })
+        .map_err(map_array_error)
+    })?;
+
+    lifetime_distribution_dict(py, dist)
+}
+
+/// Build MEM options from optional parameters.
+fn mem_options(
+    lifetime_range: Option<(f64, f64)>,
+    n_lifetimes: Option<usize>,
+    regularization: Option<f64>,
+    max_iterations: Option<usize>,
+    start: Option<usize>,
+) -> MemOptions {
+    let default = MemOptions::default();
+    MemOptions {
+        lifetime_range,
+        n_lifetimes: n_lifetimes.unwrap_or(default.n_lifetimes),
+        regularization,
+        max_iterations: max_iterations.unwrap_or(default.max_iterations),
+        start,
+    }
+}
+
+/// Convert a lifetime distribution into a Python dictionary.
+fn lifetime_distribution_dict(
+    py: Python<'_>,
+    dist: LifetimeDistribution,
+) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("lifetimes", dist.lifetimes)?;
+    dict.set_item("amplitudes", dist.amplitudes)?;
+    dict.set_item("mean_lifetime", dist.mean_lifetime)?;
+    dict.set_item(
+        "intensity_weighted_lifetime",
+        dist.intensity_weighted_lifetime,
+    )?;
+    dict.set_item("log_width", dist.log_width)?;
+    dict.set_item("regularization", dist.regularization)?;
+    dict.set_item("reduced_chi_square", dist.reduced_chi_square)?;
+
+    Ok(dict)
+}
+
+/// Create a discrete Laguerre basis.
+///
+/// This function computes the orthonormal discrete Laguerre functions of
+/// orders 0 to "order - 1", which decay more slowly as "alpha" approaches
+/// 1.0.
+///
+/// :param n: The number of samples of each function.
+/// :param order: The number of basis functions, must be greater than 0.
+/// :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive).
+/// :return: The basis with shape (order, n).
+#[pyfunction]
+#[pyo3(name = "laguerre_basis")]
+pub fn fitting_laguerre_basis(
+    py: Python,
+    n: usize,
+    order: usize,
+    alpha: f64,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    fitting::laguerre_basis(n, order, alpha)
+        .map(|b| b.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Estimate the average lifetime of a decay curve by Laguerre expansion.
+///
+/// This function expands the fluorescence impulse response on a discrete
+/// Laguerre basis, optionally convolved with an instrument response function
+/// (IRF), by linear least squares, without iterations or starting values.
+/// The average lifetime is the first moment of the impulse response "h":
+///
+/// τ = Σₖ tₖ h(k) / Σₖ h(k)
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param order: The number of basis functions, default = 5.
+/// :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive). If
+///     "None", the value of a fixed grid with the smallest squared residual is
+///     used.
+/// :param irf: The instrument response function with the length of "data". If
+///     "None", the decay is fitted from the peak bin without convolution.
+/// :return: A dictionary with the expansion "coefficients", the Laguerre
+///     "alpha", the "impulse_response", the "mean_lifetime" and the
+///     "reduced_chi_square" of the fit.
+#[pyfunction]
+#[pyo3(name = "laguerre_fit")]
+#[pyo3(signature = (data, period, order=None, alpha=None, irf=None))]
+pub fn fitting_laguerre_fit<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    period: f64,
+    order: Option<usize>,
+    alpha: Option<f64>,
+    irf: Option<Vec<f64>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let fit = fitting::laguerre_fit(&data, period, order, alpha, irf.as_deref())
+        .map_err(map_array_error)?;
+
+    laguerre_fit_dict(py, fit)
+}
+
+/// Compute an average lifetime image of a 3-dimensional decay image by
+/// Laguerre expansion.
+///
+/// The basis and its least squares projection are computed once and shared
+/// by all pixels. If "alpha" is "None" it is selected from the summed decay
+/// of the image, and without an IRF all pixels are fitted from the peak bin
+/// of the summed decay.
+///
+/// :param data: The 3-dimensional decay image.
+/// :param period: The period (i.e. time interval).
+/// :param order: The number of basis functions, default = 5.
+/// :param alpha: The Laguerre parameter, between 0.0 and 1.0 (exclusive). If
+///     "None", it is selected from the summed decay.
+/// :param irf: The instrument response function with the length of the decay
+///     axis. If "None", the decays are fitted from the peak bin without
+///     convolution.
+/// :param mask: The 2-dimensional boolean mask of the pixels to fit. If
+///     "None", all pixels are fitted.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The average lifetime image. Pixels without counts or outside of
+///     "mask" are NaN.
+#[pyfunction]
+#[pyo3(name = "laguerre_image")]
+#[pyo3(signature = (data, period, order=None, alpha=None, irf=None, mask=None, axis=None))]
+pub fn fitting_laguerre_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    order: Option<usize>,
+    alpha: Option<f64>,
+    irf: Option<Vec<f64>>,
+    mask: Option<PyReadonlyArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            fitting::laguerre_image(arr, period, order, alpha, irf.as_deref(), m, axis)
+        })
+        .map(|t| t.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Convert a Laguerre fit into a Python dictionary.
+fn laguerre_fit_dict(py: Python<'_>, fit: LaguerreFit) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("coefficients", fit.coefficients)?;
+    dict.set_item("alpha", fit.alpha)?;
+    dict.set_item("impulse_response", fit.impulse_response)?;
+    dict.set_item("mean_lifetime", fit.mean_lifetime)?;
+    dict.set_item("reduced_chi_square", fit.reduced_chi_square)?;
+
+    Ok(dict)
+}
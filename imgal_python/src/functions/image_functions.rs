@@ -1,8 +1,15 @@
-use numpy::PyReadonlyArrayDyn;
-use pyo3::exceptions::PyTypeError;
+use numpy::{
+    IntoPyArray, PyArray2, PyArray3, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
+    PyReadonlyArrayDyn, PyReadwriteArray3, PyReadwriteArrayDyn,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use imgal::image;
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::image::annotate::{self, Corner, ScaleBarOptions};
+use imgal::image::render::{self, Colormap};
+use imgal::image::{self, MathOp, PadMode, RatioCalibration, math};
 
 /// Compute the image histogram from an n-dimensional array.
 ///
@@ -11,27 +18,994 @@ use imgal::image;
 ///
 /// :param data: The input n-dimensional array to construct the histogram from.
 /// :param bins: The number of bins to use for the histogram, default = 256.
+/// :param range: The (min, max) range of the histogram. If "None", the minimum
+///     and maximum values of "data" are used.
 /// :return: The histogram of the input n-dimensional array of size `bins`.
 ///     Each element represents the count of values falling into the
 ///     corresponding bin.
 #[pyfunction]
 #[pyo3(name = "histogram")]
-#[pyo3(signature = (data, bins=None))]
-pub fn image_histogram<'py>(data: Bound<'py, PyAny>, bins: Option<usize>) -> PyResult<Vec<i64>> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
+#[pyo3(signature = (data, bins=None, range=None))]
+pub fn image_histogram<'py>(
+    data: Bound<'py, PyAny>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+) -> PyResult<Vec<i64>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| Ok(image::histogram(
+        arr.as_array(),
+        bins,
+        range
+    )))
+}
+
+/// Compute a weighted and/or density normalized image histogram from an
+/// n-dimensional array.
+///
+/// This function computes an image histogram where each value optionally
+/// contributes its weight instead of 1 to its bin. If "density" is true, the
+/// histogram is normalized such that its integral over the range is 1.0.
+///
+/// :param data: The input n-dimensional array to construct the histogram from.
+/// :param bins: The number of bins to use for the histogram, default = 256.
+/// :param range: The (min, max) range of the histogram. If "None", the minimum
+///     and maximum values of "data" are used.
+/// :param weights: An optional n-dimensional array of weights, one per value.
+///     Must have the same shape as "data".
+/// :param density: If true, the histogram is normalized to a probability
+///     density, default = False.
+/// :return: The histogram of size "bins" and the "bins + 1" bin edges.
+#[pyfunction]
+#[pyo3(name = "histogram_weighted")]
+#[pyo3(signature = (data, bins=None, range=None, weights=None, density=None))]
+pub fn image_histogram_weighted<'py>(
+    data: Bound<'py, PyAny>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+    weights: Option<PyReadonlyArrayDyn<f64>>,
+    density: Option<bool>,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    let w = weights.as_ref().map(|w| w.as_array());
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| image::histogram_weighted(
+        arr.as_array(),
+        bins,
+        range,
+        w,
+        density
+    )
+    .map_err(map_array_error))
+}
+
+/// Compute the exact-count histogram of an unsigned 16-bit n-dimensional array.
+///
+/// This function computes an exact-count histogram with one bin per integer
+/// value, from 0 to the maximum value of the input array.
+///
+/// :param data: The input unsigned 16-bit n-dimensional array.
+/// :return: The histogram of size "max + 1", where element "i" is the count of
+///     values equal to "i".
+#[pyfunction]
+#[pyo3(name = "histogram_u16")]
+pub fn image_histogram_u16(data: PyReadonlyArrayDyn<u16>) -> Vec<i64> {
+    image::histogram_u16(data.as_array())
+}
+
+/// Render a lifetime map as an intensity weighted pseudocolor RGB image.
+///
+/// This function renders the standard FLIM display, an HSV-style composite
+/// where the color (hue) encodes the lifetime and the brightness (value)
+/// encodes the intensity:
+///
+/// RGB = C((τ - τₘᵢₙ) / (τₘₐₓ - τₘᵢₙ)) * I / Iₘₐₓ
+///
+/// Where "C" is the colormap. Pixels with a NaN lifetime are rendered black.
+///
+/// :param tau_map: The 2-dimensional lifetime image.
+/// :param intensity: The 2-dimensional intensity image (e.g. photon counts).
+///     Must have the same shape as "tau_map".
+/// :param colormap: The colormap name used to encode the lifetime, one of
+///     "viridis", "magma", "inferno", "plasma", "cividis", "turbo" or "hsv",
+///     default = "hsv".
+/// :param tau_range: The (min, max) lifetime range mapped to the colormap. If
+///     "None", the minimum and maximum finite lifetimes of "tau_map" are used.
+/// :return: The 3-dimensional (row, col, ch) RGB image.
+#[pyfunction]
+#[pyo3(name = "lifetime_rgb")]
+#[pyo3(signature = (tau_map, intensity, colormap=None, tau_range=None))]
+pub fn render_lifetime_rgb<'py>(
+    py: Python<'py>,
+    tau_map: PyReadonlyArray2<f64>,
+    intensity: Bound<'py, PyAny>,
+    colormap: Option<&str>,
+    tau_range: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let cmap = match colormap {
+        Some(name) => Some(
+            Colormap::from_name(name)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown colormap \"{}\".", name)))?,
+        ),
+        None => None,
+    };
+    dispatch_array!(intensity, PyReadonlyArray2, |arr| render::lifetime_rgb(
+        tau_map.as_array(),
+        arr.as_array(),
+        cmap,
+        tau_range
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Render a 2-dimensional image as a pseudocolor RGB image.
+///
+/// This function maps each pixel value to a color of a colormap (e.g. to
+/// export a lifetime or ratio map as an 8-bit RGB image):
+///
+/// RGB = C((x - min) / (max - min))
+///
+/// Where "C" is the colormap. Values outside of the range are clamped and NaN
+/// pixels are rendered black.
+///
+/// :param data: The 2-dimensional image.
+/// :param colormap: The colormap name, one of "viridis", "magma", "inferno",
+///     "plasma", "cividis", "turbo" or "hsv", default = "viridis".
+/// :param range: The (min, max) value range mapped to the colormap. If
+///     "None", the minimum and maximum finite values of "data" are used.
+/// :return: The 3-dimensional (row, col, ch) RGB image.
+#[pyfunction]
+#[pyo3(name = "apply_colormap")]
+#[pyo3(signature = (data, colormap=None, range=None))]
+pub fn render_apply_colormap<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    colormap: Option<&str>,
+    range: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let cmap = match colormap {
+        Some(name) => Some(
+            Colormap::from_name(name)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown colormap \"{}\".", name)))?,
+        ),
+        None => None,
+    };
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| render::apply_colormap(arr, cmap, range))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Overlay a boolean mask on a 2-dimensional grayscale image.
+///
+/// This function renders "data" in grayscale and blends the mask pixels with
+/// a solid color:
+///
+/// RGB = (1 - α) · gray + α · color
+///
+/// Where "α" is the opacity. NaN pixels are rendered black.
+///
+/// :param data: The 2-dimensional grayscale image.
+/// :param mask: The 2-dimensional boolean mask, with the shape of "data".
+/// :param color: The RGB color of the mask, default = (255, 0, 0) (red).
+/// :param alpha: The opacity of the mask, between 0.0 and 1.0, default = 0.5.
+/// :param range: The (min, max) value range mapped from black to white. If
+///     "None", the minimum and maximum finite values of "data" are used.
+/// :return: The 3-dimensional (row, col, ch) RGB image.
+#[pyfunction]
+#[pyo3(name = "overlay_mask")]
+#[pyo3(signature = (data, mask, color=None, alpha=None, range=None))]
+pub fn render_overlay_mask<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    mask: PyReadonlyArray2<'py, bool>,
+    color: Option<[u8; 3]>,
+    alpha: Option<f64>,
+    range: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let mask = mask.as_array();
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| render::overlay_mask(arr, mask, color, alpha, range))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Overlay a label image on a 2-dimensional grayscale image.
+///
+/// This function renders "data" in grayscale and blends each labeled region
+/// with a distinct color, so segmentations can be checked visually. Colors
+/// are spread around the hue circle by the golden angle. NaN pixels are
+/// rendered black.
+///
+/// :param data: The 2-dimensional grayscale image.
+/// :param labels: The 2-dimensional label image, with the shape of "data",
+///     where 0 is background.
+/// :param alpha: The opacity of the labels, between 0.0 and 1.0, default =
+///     0.5.
+/// :param range: The (min, max) value range mapped from black to white. If
+///     "None", the minimum and maximum finite values of "data" are used.
+/// :return: The 3-dimensional (row, col, ch) RGB image.
+#[pyfunction]
+#[pyo3(name = "overlay_labels")]
+#[pyo3(signature = (data, labels, alpha=None, range=None))]
+pub fn render_overlay_labels<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    labels: PyReadonlyArray2<'py, usize>,
+    alpha: Option<f64>,
+    range: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let labels = labels.as_array();
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| render::overlay_labels(arr, labels, alpha, range))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Burn a scale bar into an RGB image in place.
+///
+/// This function draws a horizontal bar of "length" physical units, i.e.
+/// "length / pixel_size" pixels, in a corner of an RGB image (e.g. the output
+/// of "apply_colormap"), with an optional text label centered above the bar
+/// (below for the top corners).
+///
+/// :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+///     place.
+/// :param pixel_size: The physical pixel size (e.g. µm per pixel).
+/// :param length: The physical length of the scale bar, in the unit of
+///     "pixel_size".
+/// :param corner: The image corner of the scale bar, "top_left", "top_right",
+///     "bottom_left" or "bottom_right", default = "bottom_right".
+/// :param color: The RGB color of the bar and label, default =
+///     (255, 255, 255).
+/// :param thickness: The bar thickness in pixels, default = 4.
+/// :param margin: The distance between the bar and the image edges in pixels,
+///     default = 8.
+/// :param label: The label text drawn next to the bar (e.g. "10 µm"). If
+///     None, no label is drawn.
+/// :param font_scale: The integer font scale of the label, default = 1.
+#[pyfunction]
+#[pyo3(name = "draw_scale_bar")]
+#[pyo3(signature = (rgb, pixel_size, length, corner=None, color=None, thickness=None, margin=None, label=None, font_scale=None))]
+pub fn annotate_draw_scale_bar(
+    mut rgb: PyReadwriteArray3<u8>,
+    pixel_size: f64,
+    length: f64,
+    corner: Option<&str>,
+    color: Option<[u8; 3]>,
+    thickness: Option<usize>,
+    margin: Option<usize>,
+    label: Option<String>,
+    font_scale: Option<usize>,
+) -> PyResult<()> {
+    let default = ScaleBarOptions::default();
+    let corner = match corner {
+        Some("top_left") => Corner::TopLeft,
+        Some("top_right") => Corner::TopRight,
+        Some("bottom_left") => Corner::BottomLeft,
+        None | Some("bottom_right") => Corner::BottomRight,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown corner, supported corners are \"top_left\", \"top_right\", \"bottom_left\", and \"bottom_right\".",
+            ));
+        }
+    };
+    let options = ScaleBarOptions {
+        corner,
+        color: color.unwrap_or(default.color),
+        thickness: thickness.unwrap_or(default.thickness),
+        margin: margin.unwrap_or(default.margin),
+        label,
+        font_scale: font_scale.unwrap_or(default.font_scale),
+    };
+    annotate::draw_scale_bar(rgb.as_array_mut(), pixel_size, length, Some(options))
+        .map_err(map_array_error)
+}
+
+/// Burn the outlines of the regions of a label image into an RGB image in
+/// place.
+///
+/// This function colors the boundary pixels of each labeled region, e.g. to
+/// check a segmentation on an exported image. Without a color, each region is
+/// outlined with its distinct label color (see "overlay_labels").
+///
+/// :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+///     place.
+/// :param labels: The 2-dimensional label image with the spatial shape of
+///     "rgb", where 0 is background.
+/// :param color: The RGB color of the outlines. If None, each region uses its
+///     label color.
+#[pyfunction]
+#[pyo3(name = "draw_outlines")]
+#[pyo3(signature = (rgb, labels, color=None))]
+pub fn annotate_draw_outlines(
+    mut rgb: PyReadwriteArray3<u8>,
+    labels: PyReadonlyArray2<usize>,
+    color: Option<[u8; 3]>,
+) -> PyResult<()> {
+    annotate::draw_outlines(rgb.as_array_mut(), labels.as_array(), color).map_err(map_array_error)
+}
+
+/// Burn a polyline (e.g. a ROI outline or a line profile path) into an RGB
+/// image in place.
+///
+/// This function draws 1 pixel wide line segments between consecutive
+/// vertices. Pixels outside of the image are clipped.
+///
+/// :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+///     place.
+/// :param vertices: The (row, col) vertices of the polyline, in pixels.
+/// :param color: The RGB color of the polyline.
+/// :param closed: Whether to connect the last vertex to the first (i.e. a
+///     polygon), default = False.
+#[pyfunction]
+#[pyo3(name = "draw_polyline")]
+#[pyo3(signature = (rgb, vertices, color, closed=None))]
+pub fn annotate_draw_polyline(
+    mut rgb: PyReadwriteArray3<u8>,
+    vertices: Vec<(f64, f64)>,
+    color: [u8; 3],
+    closed: Option<bool>,
+) -> PyResult<()> {
+    annotate::draw_polyline(
+        rgb.as_array_mut(),
+        &vertices,
+        color,
+        closed.unwrap_or(false),
+    )
+    .map_err(map_array_error)
+}
+
+/// Burn a text label into an RGB image in place.
+///
+/// This function draws text with an embedded 5x7 pixel bitmap font, scaled by
+/// an integer factor. The font covers digits, letters, "µ", the space and
+/// common punctuation, other characters are drawn as "?". Lines are separated
+/// by newlines and text outside of the image is clipped.
+///
+/// :param rgb: The 3-dimensional (row, col, ch) uint8 RGB image, modified in
+///     place.
+/// :param text: The text to draw.
+/// :param position: The (row, col) position of the top left corner of the
+///     text.
+/// :param color: The RGB color of the text.
+/// :param scale: The integer font scale, default = 1.
+#[pyfunction]
+#[pyo3(name = "draw_text")]
+#[pyo3(signature = (rgb, text, position, color, scale=None))]
+pub fn annotate_draw_text(
+    mut rgb: PyReadwriteArray3<u8>,
+    text: &str,
+    position: (usize, usize),
+    color: [u8; 3],
+    scale: Option<usize>,
+) -> PyResult<()> {
+    annotate::draw_text(rgb.as_array_mut(), text, position, color, scale).map_err(map_array_error)
+}
+
+/// Compute the size of a text label drawn with "draw_text".
+///
+/// :param text: The text to measure.
+/// :param scale: The integer font scale, default = 1.
+/// :return: The (height, width) of the text in pixels.
+#[pyfunction]
+#[pyo3(name = "text_size")]
+#[pyo3(signature = (text, scale=None))]
+pub fn annotate_text_size(text: &str, scale: Option<usize>) -> (usize, usize) {
+    annotate::text_size(text, scale)
+}
+
+/// Apply the Anscombe variance-stabilizing transform to an n-dimensional array.
+///
+/// This function applies the Anscombe transform to Poisson distributed data
+/// (e.g. photon counts), converting it to approximately Gaussian distributed
+/// data with unit variance:
+///
+/// A(x) = 2√(x + 3/8)
+///
+/// :param data: The input n-dimensional array of Poisson distributed values.
+/// :return: The variance-stabilized n-dimensional array.
+#[pyfunction]
+#[pyo3(name = "anscombe")]
+pub fn image_anscombe<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| Ok(image::anscombe(
+        arr.as_array()
+    )
+    .into_pyarray(py)))
+}
+
+/// Apply the inverse Anscombe transform to an n-dimensional array.
+///
+/// This function maps Anscombe transformed data back to the Poisson domain.
+///
+/// :param data: The input n-dimensional array of Anscombe transformed values.
+/// :param unbiased: If "True", the closed-form approximation of the exact
+///     unbiased inverse is used, otherwise the algebraic inverse is used,
+///     default = True.
+/// :return: The n-dimensional array in the Poisson domain.
+#[pyfunction]
+#[pyo3(name = "anscombe_inverse")]
+#[pyo3(signature = (data, unbiased=None))]
+pub fn image_anscombe_inverse<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<f64>,
+    unbiased: Option<bool>,
+) -> Bound<'py, PyArrayDyn<f64>> {
+    image::anscombe_inverse(data.as_array(), unbiased).into_pyarray(py)
+}
+
+/// Pad an n-dimensional array.
+///
+/// This function pads each axis of an n-dimensional array by the given number
+/// of elements before and after the data. The padded array has the same dtype
+/// as the input array.
+///
+/// :param data: The input n-dimensional array.
+/// :param pad_width: The number of elements padded (before, after) each axis.
+/// :param mode: The padding mode, "constant", "reflect", "edge" or "wrap",
+///     default = "constant".
+/// :param constant: The padding value for the "constant" mode, default = 0.
+/// :return: The padded array.
+#[pyfunction]
+#[pyo3(name = "pad")]
+#[pyo3(signature = (data, pad_width, mode=None, constant=None))]
+pub fn image_pad<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    pad_width: Vec<(usize, usize)>,
+    mode: Option<String>,
+    constant: Option<f64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mode = match mode.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("constant") => PadMode::Constant,
+        Some("reflect") => PadMode::Reflect,
+        Some("edge") => PadMode::Edge,
+        Some("wrap") => PadMode::Wrap,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown mode, supported modes are \"constant\", \"reflect\", \"edge\", and \"wrap\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr: T| image::pad(
+        arr.as_array(),
+        &pad_width,
+        mode,
+        constant.map(|c| c as T)
+    )
+    .map(|output| output.into_pyarray(py).into_any())
+    .map_err(map_array_error))
+}
+
+/// Crop a region of an n-dimensional array.
+///
+/// :param data: The input n-dimensional array.
+/// :param start: The start index of the region along each axis.
+/// :param shape: The shape of the region.
+/// :return: The cropped region with the same dtype as the input array.
+#[pyfunction]
+#[pyo3(name = "crop")]
+pub fn image_crop<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    start: Vec<usize>,
+    shape: Vec<usize>,
+) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| image::crop(
+        arr.as_array(),
+        &start,
+        &shape
+    )
+    .map(|output| output.into_pyarray(py).into_any())
+    .map_err(map_array_error))
+}
+
+/// Crop the center region of an n-dimensional array.
+///
+/// If the difference between the array and region lengths is odd, the extra
+/// element is removed from the end of the axis.
+///
+/// :param data: The input n-dimensional array.
+/// :param shape: The shape of the region.
+/// :return: The cropped center region with the same dtype as the input array.
+#[pyfunction]
+#[pyo3(name = "crop_center")]
+pub fn image_crop_center<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    shape: Vec<usize>,
+) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| image::crop_center(
+        arr.as_array(),
+        &shape
+    )
+    .map(|output| output.into_pyarray(py).into_any())
+    .map_err(map_array_error))
+}
+
+/// Tile a stack of 2-dimensional images into a single montage image.
+///
+/// This function arranges the images of a (pln, row, col) stack on a grid in
+/// row-major order. Tiles are separated by "spacing" pixels of "border_value",
+/// which also fills the outer border and any empty grid cells.
+///
+/// :param stack: The input 3-dimensional stack, with images along axis 0.
+/// :param columns: The number of grid columns, default = ceil(sqrt(pln)).
+/// :param spacing: The border width in pixels between and around tiles,
+///     default = 0.
+/// :param border_value: The intensity value of the borders and empty cells,
+///     default = 0.
+/// :return: The montage image with the same dtype as the input stack.
+#[pyfunction]
+#[pyo3(name = "montage")]
+#[pyo3(signature = (stack, columns=None, spacing=None, border_value=None))]
+pub fn image_montage<'py>(
+    py: Python<'py>,
+    stack: Bound<'py, PyAny>,
+    columns: Option<usize>,
+    spacing: Option<usize>,
+    border_value: Option<f64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(stack, PyReadonlyArray3, |arr: T| image::montage(
+        arr.as_array(),
+        columns,
+        spacing,
+        border_value.map(|b| b as T),
+    )
+    .map(|output| output.into_pyarray(py).into_any())
+    .map_err(map_array_error))
+}
+
+/// Assemble a 3-dimensional stack from 2-dimensional slices.
+///
+/// :param slices: A list of 2-dimensional slices, all with the same shape and
+///     dtype.
+/// :return: The stack with a shape of (len(slices), row, col).
+#[pyfunction]
+#[pyo3(name = "stack_from_slices")]
+pub fn image_stack_from_slices<'py>(
+    py: Python<'py>,
+    slices: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(slices, Vec<PyReadonlyArray2>, |arrs| {
+        image::stack_from_slices(&arrs.iter().map(|a| a.as_array()).collect::<Vec<_>>())
+            .map(|output| output.into_pyarray(py).into_any())
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the Euclidean distance transform of a 2-dimensional boolean mask.
+///
+/// This function computes, for each "true" (foreground) pixel, the exact
+/// Euclidean distance to the nearest "false" (background) pixel, while
+/// background pixels are 0.0. Anisotropic pixel sizes are supported exactly.
+///
+/// :param mask: The 2-dimensional boolean mask.
+/// :param spacing: The (row, col) pixel size, distances are reported in the
+///     unit of the pixel size (e.g. µm), default = (1.0, 1.0) (pixels).
+/// :return: The distance transform. If "mask" has no background pixels, all
+///     distances are infinite.
+#[pyfunction]
+#[pyo3(name = "distance_transform")]
+#[pyo3(signature = (mask, spacing=None))]
+pub fn image_distance_transform<'py>(
+    py: Python<'py>,
+    mask: PyReadonlyArray2<'py, bool>,
+    spacing: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let mask = mask.as_array();
+    py.allow_threads(|| image::distance_transform(mask, spacing.map(|(r, c)| [r, c])))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the integral image (summed-area table) of an n-dimensional array.
+///
+/// This function computes the cumulative sum of the input array along every
+/// axis. The integral image has one more element than the input along each
+/// axis, with a leading zero row (plane) on every axis, so that the sum of any
+/// box of the input is computed in constant time (see "box_sum").
+///
+/// :param data: The input n-dimensional array (e.g. a 2D image or 3D stack).
+/// :return: The integral image, with a shape of the input shape + 1 on every
+///     axis.
+#[pyfunction]
+#[pyo3(name = "integral_image")]
+pub fn image_integral_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        Ok(py
+            .allow_threads(|| image::integral_image(arr))
+            .into_pyarray(py))
+    })
+}
+
+/// Compute the squared integral image of an n-dimensional array.
+///
+/// This function computes the integral image (see "integral_image") of the
+/// squared input values. Together with the integral image, the variance of any
+/// box of "N" elements is computed in constant time:
+///
+/// Var = (S₂ - S² / N) / N
+///
+/// :param data: The input n-dimensional array (e.g. a 2D image or 3D stack).
+/// :return: The squared integral image, with a shape of the input shape + 1 on
+///     every axis.
+#[pyfunction]
+#[pyo3(name = "integral_image_squared")]
+pub fn image_integral_image_squared<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        Ok(py
+            .allow_threads(|| image::integral_image_squared(arr))
+            .into_pyarray(py))
+    })
+}
+
+/// Compute the sum of a box of an array from its integral image.
+///
+/// :param integral: The integral image, as returned by "integral_image" or
+///     "integral_image_squared".
+/// :param start: The first index of the box on each axis (inclusive).
+/// :param end: The last index of the box on each axis (exclusive).
+/// :return: The sum of the box. Empty boxes sum to 0.0.
+#[pyfunction]
+#[pyo3(name = "box_sum")]
+pub fn image_box_sum(
+    integral: PyReadonlyArrayDyn<f64>,
+    start: Vec<usize>,
+    end: Vec<usize>,
+) -> PyResult<f64> {
+    image::box_sum(integral.as_array(), &start, &end).map_err(map_array_error)
+}
+
+/// Create a boolean mask of the saturated pixels of an n-dimensional image.
+///
+/// This function flags the pixels at or above a saturation level. Saturated
+/// pixels hold clipped intensities, which silently bias downstream analyses
+/// (e.g. phasor coordinates or colocalization coefficients) and should be
+/// excluded or reported.
+///
+/// :param data: The n-dimensional image.
+/// :param value: The saturation level, default = the maximum value of the
+///     dtype (e.g. 65535 for uint16). Set the level explicitly for detectors
+///     that saturate below the dtype maximum (e.g. 4095 for a 12-bit camera
+///     stored as uint16).
+/// :return: The saturation mask, where "True" pixels are saturated.
+#[pyfunction]
+#[pyo3(name = "saturation_mask")]
+#[pyo3(signature = (data, value=None))]
+pub fn image_saturation_mask<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    value: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        Ok(image::saturation_mask(arr.as_array(), value).into_pyarray(py))
+    })
+}
+
+/// Count the saturated pixels of each frame of an n-dimensional image.
+///
+/// :param data: The n-dimensional image.
+/// :param value: The saturation level, default = the maximum value of the
+///     dtype.
+/// :param axis: The frame axis, default = 0.
+/// :return: The number of saturated pixels of each frame.
+#[pyfunction]
+#[pyo3(name = "saturation_counts")]
+#[pyo3(signature = (data, value=None, axis=None))]
+pub fn image_saturation_counts<'py>(
+    data: Bound<'py, PyAny>,
+    value: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Vec<usize>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        image::saturation_counts(arr.as_array(), value, axis).map_err(map_array_error)
+    })
+}
+
+/// Count the saturated pixels of each region of a labeled n-dimensional image.
+///
+/// :param data: The n-dimensional image.
+/// :param labels: The label image, with the same shape as "data".
+/// :param value: The saturation level, default = the maximum value of the
+///     dtype.
+/// :return: The number of saturated pixels of each label, indexed by label from
+///     0 (i.e. the background) to the largest label.
+#[pyfunction]
+#[pyo3(name = "saturation_region_counts")]
+#[pyo3(signature = (data, labels, value=None))]
+pub fn image_saturation_region_counts<'py>(
+    data: Bound<'py, PyAny>,
+    labels: PyReadonlyArrayDyn<'py, usize>,
+    value: Option<f64>,
+) -> PyResult<Vec<usize>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        image::saturation_region_counts(arr.as_array(), labels.as_array(), value)
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the ratio image of two channels with its propagated uncertainty.
+///
+/// This function computes the background subtracted ratio of two channels of
+/// a ratiometric indicator (e.g. Fura-2 or a pH sensor) and its Poisson (shot
+/// noise) uncertainty, assuming the channels are in photon counts. Pixels
+/// where either background subtracted channel is at or below "threshold" are
+/// NaN.
+///
+/// :param channel_a: The numerator channel.
+/// :param channel_b: The denominator channel, with the same shape and dtype as
+///     "channel_a".
+/// :param background_a: The background of the numerator, default = 0.0.
+/// :param background_b: The background of the denominator, default = 0.0.
+/// :param threshold: The minimum background subtracted intensity of both
+///     channels, default = 0.0.
+/// :param clamp: The (min, max) range the ratio is clamped to. If "None", the
+///     ratio is not clamped.
+/// :return: The ratio image and its uncertainty.
+#[pyfunction]
+#[pyo3(name = "ratio")]
+#[pyo3(signature = (channel_a, channel_b, background_a=None, background_b=None, threshold=None, clamp=None))]
+pub fn image_ratio<'py>(
+    py: Python<'py>,
+    channel_a: Bound<'py, PyAny>,
+    channel_b: Bound<'py, PyAny>,
+    background_a: Option<f64>,
+    background_b: Option<f64>,
+    threshold: Option<f64>,
+    clamp: Option<(f64, f64)>,
+) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    dispatch_array!(channel_a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = channel_b.extract::<PyReadonlyArrayDyn<T>>()?;
+        image::ratio(
+            arr_a.as_array(),
+            arr_b.as_array(),
+            background_a,
+            background_b,
+            threshold,
+            clamp,
+        )
+        .map(|(ratio, sigma)| (ratio.into_pyarray(py), sigma.into_pyarray(py)))
+        .map_err(map_array_error)
+    })
+}
+
+/// Convert a ratio image into an analyte concentration image.
+///
+/// This function calibrates a ratio image with the Grynkiewicz equation,
+/// c = Kd · β · (R - R_min) / (R_max - R), and propagates the ratio
+/// uncertainty. Ratios outside of the (R_min, R_max) range are NaN.
+///
+/// :param ratio: The ratio image.
+/// :param uncertainty: The ratio uncertainty image, with the same shape as
+///     "ratio".
+/// :param kd: The dissociation constant of the indicator.
+/// :param r_min: The ratio of the analyte free indicator.
+/// :param r_max: The ratio of the analyte saturated indicator.
+/// :param beta: The ratio of the free to bound intensity of the denominator
+///     channel, default = 1.0.
+/// :return: The concentration image and its uncertainty.
+#[pyfunction]
+#[pyo3(name = "ratio_to_concentration")]
+#[pyo3(signature = (ratio, uncertainty, kd, r_min, r_max, beta=None))]
+pub fn image_ratio_to_concentration<'py>(
+    py: Python<'py>,
+    ratio: PyReadonlyArrayDyn<'py, f64>,
+    uncertainty: PyReadonlyArrayDyn<'py, f64>,
+    kd: f64,
+    r_min: f64,
+    r_max: f64,
+    beta: Option<f64>,
+) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    let calibration = RatioCalibration {
+        kd,
+        r_min,
+        r_max,
+        beta: beta.unwrap_or(1.0),
+    };
+    image::ratio_to_concentration(ratio.as_array(), uncertainty.as_array(), calibration)
+        .map(|(conc, sigma)| (conc.into_pyarray(py), sigma.into_pyarray(py)))
+        .map_err(map_array_error)
+}
+
+/// Apply an elementwise operation between two n-dimensional images.
+///
+/// This function computes "op" between each element of "a" and the matching
+/// element of "b" in floating point and converts the result back to the dtype
+/// of "a". Integer results are rounded to the nearest integer and saturated at
+/// the dtype range (e.g. subtracting a brighter background from a uint16 image
+/// clips at 0 instead of wrapping around), NaN results are 0. Image "b" is
+/// broadcast to the shape of "a" with NumPy rules.
+///
+/// :param a: The first n-dimensional image.
+/// :param b: The second n-dimensional image, broadcastable to the shape of "a"
+///     and with the same dtype as "a".
+/// :param op: The elementwise operation, "add", "subtract", "multiply",
+///     "divide", "min" or "max".
+/// :return: The result image, with the shape and dtype of "a".
+#[pyfunction]
+#[pyo3(name = "binary")]
+pub fn math_binary<'py>(
+    py: Python<'py>,
+    a: Bound<'py, PyAny>,
+    b: Bound<'py, PyAny>,
+    op: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let op = math_op(op)?;
+    dispatch_array!(a, PyReadonlyArrayDyn, |arr_a: T| {
+        let arr_b = b.extract::<PyReadonlyArrayDyn<T>>()?;
+        math::binary(arr_a.as_array(), arr_b.as_array(), op)
+            .map(|output| output.into_pyarray(py).into_any())
+            .map_err(map_array_error)
+    })
+}
+
+/// Apply an elementwise operation between two n-dimensional images in place.
+///
+/// This function computes "op" between each element of "a" and the matching
+/// element of "b" like "binary" and writes the result into "a".
+///
+/// :param a: The first n-dimensional image, overwritten with the result.
+/// :param b: The second n-dimensional image, broadcastable to the shape of "a"
+///     and with the same dtype as "a".
+/// :param op: The elementwise operation, "add", "subtract", "multiply",
+///     "divide", "min" or "max".
+#[pyfunction]
+#[pyo3(name = "binary_mut")]
+pub fn math_binary_mut<'py>(a: Bound<'py, PyAny>, b: Bound<'py, PyAny>, op: &str) -> PyResult<()> {
+    let op = math_op(op)?;
+    dispatch_array!(a, PyReadwriteArrayDyn, |arr_a: T| {
+        let mut arr_a = arr_a;
+        let arr_b = b.extract::<PyReadonlyArrayDyn<T>>()?;
+        math::binary_mut(arr_a.as_array_mut(), arr_b.as_array(), op).map_err(map_array_error)
+    })
+}
+
+/// Apply an elementwise operation between an n-dimensional image and a scalar.
+///
+/// This function computes "op" between each element of "data" and "value",
+/// with the dtype conversion of "binary".
+///
+/// :param data: The n-dimensional image.
+/// :param value: The scalar value, the second operand of "op".
+/// :param op: The elementwise operation, "add", "subtract", "multiply",
+///     "divide", "min" or "max".
+/// :return: The result image, with the shape and dtype of "data".
+#[pyfunction]
+#[pyo3(name = "scalar")]
+pub fn math_scalar<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    value: f64,
+    op: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let op = math_op(op)?;
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        Ok(math::scalar(arr.as_array(), value, op)
+            .into_pyarray(py)
+            .into_any())
+    })
+}
+
+/// Apply an elementwise operation between an n-dimensional image and a scalar
+/// in place.
+///
+/// :param data: The n-dimensional image, overwritten with the result.
+/// :param value: The scalar value, the second operand of "op".
+/// :param op: The elementwise operation, "add", "subtract", "multiply",
+///     "divide", "min" or "max".
+#[pyfunction]
+#[pyo3(name = "scalar_mut")]
+pub fn math_scalar_mut<'py>(data: Bound<'py, PyAny>, value: f64, op: &str) -> PyResult<()> {
+    let op = math_op(op)?;
+    dispatch_array!(data, PyReadwriteArrayDyn, |arr| {
+        let mut arr = arr;
+        math::scalar_mut(arr.as_array_mut(), value, op);
+        Ok(())
+    })
+}
+
+/// Compute the absolute value of an n-dimensional image.
+///
+/// :param data: The n-dimensional image.
+/// :return: The absolute value image, with the shape and dtype of "data". The
+///     absolute value of a signed integer minimum saturates at the dtype
+///     maximum.
+#[pyfunction]
+#[pyo3(name = "abs")]
+pub fn math_abs<'py>(py: Python<'py>, data: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        Ok(math::abs(arr.as_array()).into_pyarray(py).into_any())
+    })
+}
+
+/// Compute the absolute value of an n-dimensional image in place.
+///
+/// :param data: The n-dimensional image, overwritten with its absolute value.
+#[pyfunction]
+#[pyo3(name = "abs_mut")]
+pub fn math_abs_mut<'py>(data: Bound<'py, PyAny>) -> PyResult<()> {
+    dispatch_array!(data, PyReadwriteArrayDyn, |arr| {
+        let mut arr = arr;
+        math::abs_mut(arr.as_array_mut());
+        Ok(())
+    })
+}
+
+/// Clip the values of an n-dimensional image to a range.
+///
+/// This function replaces the values below "min" by "min" and the values above
+/// "max" by "max". The bounds are converted to the dtype of "data" (i.e.
+/// rounded and saturated for integer dtypes). NaN values are unchanged.
+///
+/// :param data: The n-dimensional image.
+/// :param min: The lower bound. If "None", the values are not clipped from
+///     below.
+/// :param max: The upper bound. If "None", the values are not clipped from
+///     above.
+/// :return: The clipped image, with the shape and dtype of "data".
+#[pyfunction]
+#[pyo3(name = "clip")]
+#[pyo3(signature = (data, min=None, max=None))]
+pub fn math_clip<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        math::clip(arr.as_array(), min, max)
+            .map(|output| output.into_pyarray(py).into_any())
+            .map_err(map_array_error)
+    })
+}
+
+/// Clip the values of an n-dimensional image to a range in place.
+///
+/// :param data: The n-dimensional image, overwritten with the clipped values.
+/// :param min: The lower bound. If "None", the values are not clipped from
+///     below.
+/// :param max: The upper bound. If "None", the values are not clipped from
+///     above.
+#[pyfunction]
+#[pyo3(name = "clip_mut")]
+#[pyo3(signature = (data, min=None, max=None))]
+pub fn math_clip_mut<'py>(
+    data: Bound<'py, PyAny>,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> PyResult<()> {
+    dispatch_array!(data, PyReadwriteArrayDyn, |arr| {
+        let mut arr = arr;
+        math::clip_mut(arr.as_array_mut(), min, max).map_err(map_array_error)
+    })
+}
+
+/// Parse an elementwise operation name.
+fn math_op(op: &str) -> PyResult<MathOp> {
+    match op.to_lowercase().as_str() {
+        "add" => Ok(MathOp::Add),
+        "subtract" => Ok(MathOp::Subtract),
+        "multiply" => Ok(MathOp::Multiply),
+        "divide" => Ok(MathOp::Divide),
+        "min" => Ok(MathOp::Min),
+        "max" => Ok(MathOp::Max),
+        _ => Err(PyErr::new::<PyValueError, _>(
+            "Unknown op, supported ops are \"add\", \"subtract\", \"multiply\", \"divide\", \"min\", and \"max\".",
+        )),
     }
 }
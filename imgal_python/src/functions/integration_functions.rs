@@ -1,5 +1,7 @@
 use pyo3::prelude::*;
 
+use crate::error::map_array_error;
+use crate::utils::parse_summation;
 use imgal::integration;
 
 /// Integrate a curve with Simpson's 1/3 rule and the trapezoid rule.
@@ -18,12 +20,19 @@ use imgal::integration;
 ///
 /// :param x: The 1-dimensional data to integrate.
 /// :param delta_x: The width between data points, defualt = 1.0.
+/// :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+///     default = "naive".
 /// :return: The computed integral.
 #[pyfunction]
 #[pyo3(name = "composite_simpson")]
-#[pyo3(signature = (x, delta_x=None))]
-pub fn integration_composite_simpson(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
-    integration::composite_simpson(&x, delta_x)
+#[pyo3(signature = (x, delta_x=None, summation=None))]
+pub fn integration_composite_simpson(
+    x: Vec<f64>,
+    delta_x: Option<f64>,
+    summation: Option<&str>,
+) -> PyResult<f64> {
+    let summation = parse_summation(summation)?;
+    Ok(integration::composite_simpson(&x, delta_x, summation))
 }
 
 /// Integrate a curve with the midpoint rule.
@@ -35,12 +44,19 @@ pub fn integration_composite_simpson(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
 ///
 /// :param x: The 1-dimensional data to integrate.
 /// :param delta_x: The width between data points, default = 1.0.
+/// :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+///     default = "naive".
 /// :return: The computed integral.
 #[pyfunction]
 #[pyo3(name = "midpoint")]
-#[pyo3(signature = (x, delta_x=None))]
-pub fn integration_midpoint(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
-    integration::midpoint(&x, delta_x)
+#[pyo3(signature = (x, delta_x=None, summation=None))]
+pub fn integration_midpoint(
+    x: Vec<f64>,
+    delta_x: Option<f64>,
+    summation: Option<&str>,
+) -> PyResult<f64> {
+    let summation = parse_summation(summation)?;
+    Ok(integration::midpoint(&x, delta_x, summation))
 }
 
 /// Integrate a curve with Simpson's 1/3 rule.
@@ -55,10 +71,17 @@ pub fn integration_midpoint(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
 /// :param x: The 1-dimensional data to integrate with an even number of
 ///    subintervals.
 /// :param delta_x: The width between data points, defualt = 1.0.
+/// :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+///     default = "naive".
 /// :return: The computed integral.
 #[pyfunction]
 #[pyo3(name = "simpson")]
-#[pyo3(signature = (x, delta_x=None))]
-pub fn integration_simpson(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
-    integration::simpson(&x, delta_x).unwrap()
+#[pyo3(signature = (x, delta_x=None, summation=None))]
+pub fn integration_simpson(
+    x: Vec<f64>,
+    delta_x: Option<f64>,
+    summation: Option<&str>,
+) -> PyResult<f64> {
+    let summation = parse_summation(summation)?;
+    integration::simpson(&x, delta_x, summation).map_err(map_array_error)
 }
@@ -54,15 +54,15 @@ pub fn neighborhood_sphere(py: Python, radius: usize) -> PyResult<Bound<PyArray3
 /// "falloff_radius" rate.
 ///
 /// :param circle_radius: The radius of the circle in pixels. Must be greater than
-///    0.
+///     0.
 /// :param falloff_radius: A scaling factor that determines how quickly weights
-///    decay with distance. Larger values result in a slower falloff with a
-///    broader circle. Small values result in a faster falloff with a tighter
-///    circle.
+///     decay with distance. Larger values result in a slower falloff with a
+///     broader circle. Small values result in a faster falloff with a tighter
+///     circle.
 /// :param initial_value: The maximum weight value at the center of the kernel,
-///    default = 1.0.
+///     default = 1.0.
 /// :return: A 2-dimensional square array with side lengths
-///    of "radius * 2 + 1" with a weighted circular neighborhood.
+///     of "radius * 2 + 1" with a weighted circular neighborhood.
 #[pyfunction]
 #[pyo3(name = "weighted_circle")]
 #[pyo3(signature = (circle_radius, falloff_radius, initial_value=None))]
@@ -111,3 +111,67 @@ pub fn neighborhood_weighted_sphere(
         .map(|output| output.into_pyarray(py))
         .map_err(map_array_error)
 }
+
+/// Create a 2-dimensional square kernel with a Gaussian weighted circle
+/// neighborhood.
+///
+/// This function creates a square kernel representing a Gaussian weighted
+/// circle of the specified radius (i.e. the neighborhood). Points within the
+/// radius are assigned a weight using the Gaussian function of their
+/// Euclidean distance from the center point, while points outside are set to
+/// 0.0:
+///
+/// w(d) = w₀ * exp(-d² / 2σ²)
+///
+/// :param circle_radius: The radius of the circle in pixels. Must be greater
+///     than 0.
+/// :param sigma: The standard deviation of the Gaussian function, in pixels.
+/// :param initial_value: The maximum weight value at the center of the kernel,
+///     default = 1.0.
+/// :return: A 2-dimensional square array with side lengths of
+///     "radius * 2 + 1" with a Gaussian weighted circular neighborhood.
+#[pyfunction]
+#[pyo3(name = "gaussian_circle")]
+#[pyo3(signature = (circle_radius, sigma, initial_value=None))]
+pub fn neighborhood_gaussian_circle(
+    py: Python,
+    circle_radius: usize,
+    sigma: f64,
+    initial_value: Option<f64>,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    kernel::neighborhood::gaussian_circle(circle_radius, sigma, initial_value)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Create a 3-dimensional cube kernel with a Gaussian weighted sphere
+/// neighborhood.
+///
+/// This function creates a cube kernel representing a Gaussian weighted
+/// sphere of the specified radius (i.e. the neighborhood). Points within the
+/// radius are assigned a weight using the Gaussian function of their
+/// Euclidean distance from the center point, while points outside are set to
+/// 0.0:
+///
+/// w(d) = w₀ * exp(-d² / 2σ²)
+///
+/// :param sphere_radius: The radius of the sphere in voxels. Must be greater
+///     than 0.
+/// :param sigma: The standard deviation of the Gaussian function, in voxels.
+/// :param initial_value: The maximum weight value at the center of the kernel,
+///     default = 1.0.
+/// :return: A 3-dimensional cube array with side lengths of
+///     "radius * 2 + 1" with a Gaussian weighted spherical neighborhood.
+#[pyfunction]
+#[pyo3(name = "gaussian_sphere")]
+#[pyo3(signature = (sphere_radius, sigma, initial_value=None))]
+pub fn neighborhood_gaussian_sphere(
+    py: Python,
+    sphere_radius: usize,
+    sigma: f64,
+    initial_value: Option<f64>,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    kernel::neighborhood::gaussian_sphere(sphere_radius, sigma, initial_value)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
@@ -0,0 +1,436 @@
+use numpy::{
+    IntoPyArray, PyArray1, PyArray2, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
+    PyReadonlyArrayDyn,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::measure::{self, Interpolation};
+use imgal::statistics::FocusMetric;
+
+/// Compute per-frame quality control metrics of a 3-dimensional stack.
+///
+/// This function computes, for each frame of a (t, row, col) stack, the mean
+/// and median intensity, the fraction of saturated pixels, a focus score and
+/// the Poisson signal-to-noise ratio. Frames that are outliers in any column
+/// (see "qc_outliers") can then be rejected automatically before batch
+/// analyses.
+///
+/// :param data: The input 3-dimensional stack.
+/// :param saturation: The saturation level, default = the maximum value of the
+///     dtype.
+/// :param metric: The focus metric, "brenner", "tenengrad",
+///     "normalized_variance" or "spectral", default = "normalized_variance".
+/// :param threshold: The Tenengrad squared gradient threshold, default = 0.0.
+/// :param cutoff: The spectral normalized radial frequency cutoff, default =
+///     0.2.
+/// :param gain: The detector gain in counts per photon, used by the SNR
+///     estimate, default = 1.0.
+/// :param axis: The frame axis, default = 0.
+/// :return: A dictionary of per-frame columns, "mean", "median", "saturation"
+///     (the saturated fraction), "focus" and "snr".
+#[pyfunction]
+#[pyo3(name = "stack_qc")]
+#[pyo3(signature = (data, saturation=None, metric=None, threshold=None, cutoff=None, gain=None, axis=None))]
+pub fn measure_stack_qc<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    saturation: Option<f64>,
+    metric: Option<String>,
+    threshold: Option<f64>,
+    cutoff: Option<f64>,
+    gain: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let metric = match metric.map(|m| m.to_lowercase()).as_deref() {
+        Some("brenner") => FocusMetric::Brenner,
+        Some("tenengrad") => FocusMetric::Tenengrad {
+            threshold: threshold.unwrap_or(0.0),
+        },
+        None | Some("normalized_variance") => FocusMetric::NormalizedVariance,
+        Some("spectral") => FocusMetric::Spectral {
+            cutoff: cutoff.unwrap_or(0.2),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown metric, supported metrics are \"brenner\", \"tenengrad\", \"normalized_variance\", and \"spectral\".",
+            ));
+        }
+    };
+    let qc = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::stack_qc(arr, saturation, Some(metric), gain, axis))
+            .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("mean", qc.mean)?;
+    dict.set_item("median", qc.median)?;
+    dict.set_item("saturation", qc.saturation)?;
+    dict.set_item("focus", qc.focus)?;
+    dict.set_item("snr", qc.snr)?;
+
+    Ok(dict)
+}
+
+/// Find the outlier frames of a quality control metric.
+///
+/// This function flags the values whose robust z-score exceeds a threshold:
+///
+/// z = |x - median(x)| / (1.4826 * MAD(x))
+///
+/// Where "MAD" is the median absolute deviation, which is insensitive to the
+/// outliers themselves (e.g. a few defocused or saturated frames).
+///
+/// :param values: The metric values, one per frame.
+/// :param threshold: The robust z-score threshold, default = 3.5.
+/// :return: The indices of the outlier frames. If the MAD is 0.0, only values
+///     different from the median are outliers.
+#[pyfunction]
+#[pyo3(name = "qc_outliers")]
+#[pyo3(signature = (values, threshold=None))]
+pub fn measure_qc_outliers(values: Vec<f64>, threshold: Option<f64>) -> Vec<usize> {
+    measure::qc_outliers(&values, threshold)
+}
+
+/// Create a kymograph of a 3-dimensional stack along a polyline path.
+///
+/// This function samples each frame of a (t, row, col) stack (e.g. an
+/// intensity or lifetime time series) along a polyline path, creating a
+/// (t, distance) kymograph where moving objects appear as slanted lines whose
+/// slope is their velocity. The path is sampled at unit (pixel) spacing with
+/// bilinear interpolation, averaging "width" points perpendicular to the path.
+/// Samples without any point in the image are NaN.
+///
+/// :param data: The input 3-dimensional stack.
+/// :param path: The (row, col) vertices of the polyline path, in pixels.
+/// :param width: The path width in pixels, default = 1.
+/// :param axis: The frame (time) axis, default = 0.
+/// :return: The kymograph with a shape of (t, distance).
+#[pyfunction]
+#[pyo3(name = "kymograph")]
+#[pyo3(signature = (data, path, width=None, axis=None))]
+pub fn measure_kymograph<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    path: Vec<(f64, f64)>,
+    width: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::kymograph(arr, &path, width, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Sample an intensity profile along a line of a 2 or 3-dimensional image.
+///
+/// This function samples an image along the straight line from "start" to
+/// "end" at round(length) + 1 evenly spaced points (i.e. about one sample per
+/// pixel, including both end points), like the "plot profile" tool of ImageJ.
+/// With a "width" greater than 1, each sample is the mean of points spaced one
+/// pixel apart perpendicular to the line. Samples without any point in the
+/// image are NaN.
+///
+/// :param data: The input 2 or 3-dimensional image.
+/// :param start: The start point of the line, one coordinate per axis.
+/// :param end: The end point of the line, one coordinate per axis.
+/// :param width: The line width in pixels, default = 1.
+/// :param interpolation: The interpolation method, "nearest", "linear" or
+///     "cubic", default = "linear".
+/// :return: A tuple of the distance of each sample from "start" and the
+///     profile values.
+#[pyfunction]
+#[pyo3(name = "profile_line")]
+#[pyo3(signature = (data, start, end, width=None, interpolation=None))]
+pub fn measure_profile_line<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    start: Vec<f64>,
+    end: Vec<f64>,
+    width: Option<usize>,
+    interpolation: Option<String>,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let interpolation = match interpolation.map(|i| i.to_lowercase()).as_deref() {
+        Some("nearest") => Interpolation::Nearest,
+        None | Some("linear") => Interpolation::Linear,
+        Some("cubic") => Interpolation::Cubic,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown interpolation, supported interpolations are \"nearest\", \"linear\", and \"cubic\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::profile_line(arr, &start, &end, width, Some(interpolation)))
+            .map(|(d, p)| (d.into_pyarray(py), p.into_pyarray(py)))
+            .map_err(map_array_error)
+    })
+}
+
+/// Estimate the edge, line spread and modulation transfer functions of a
+/// slanted edge.
+///
+/// This function measures the resolution of an imaging system from a region of
+/// interest containing a single straight edge slightly tilted from the pixel
+/// grid, with the slanted-edge method of ISO 12233. The pixels are projected
+/// onto the edge normal into an oversampled edge spread function (ESF), which
+/// is differentiated into the line spread function (LSF). The modulation
+/// transfer function (MTF) is the normalized magnitude of the Fourier
+/// transform of the Hamming windowed LSF.
+///
+/// :param data: The 2-dimensional region of interest containing the edge.
+/// :param oversample: The number of ESF bins per pixel, default = 4.
+/// :return: A dictionary with the "distance" of each ESF and LSF sample from
+///     the edge in pixels, the "esf", the "lsf", the "frequency" of each MTF
+///     sample in cycles per pixel, the "mtf" and the edge "angle" in degrees.
+#[pyfunction]
+#[pyo3(name = "esf_lsf_mtf")]
+#[pyo3(signature = (data, oversample=None))]
+pub fn measure_esf_lsf_mtf<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    oversample: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let resp = dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::esf_lsf_mtf(arr, oversample))
+            .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("distance", resp.distance)?;
+    dict.set_item("esf", resp.esf)?;
+    dict.set_item("lsf", resp.lsf)?;
+    dict.set_item("frequency", resp.frequency)?;
+    dict.set_item("mtf", resp.mtf)?;
+    dict.set_item("angle", resp.angle)?;
+
+    Ok(dict)
+}
+
+/// Measure the point spread function of a 2 or 3-dimensional bead image.
+///
+/// This function detects sub-resolution beads as local intensity maxima above
+/// "threshold" that are the brightest pixel within "radius" pixels, and fits
+/// each bead's neighborhood with an axis-aligned Gaussian. The full width at
+/// half maximum (FWHM) along each axis measures the resolution of the
+/// microscope, and the lateral asymmetry reveals astigmatism or coma.
+///
+/// :param data: The 2 or 3-dimensional bead image.
+/// :param radius: The radius of the detection and fit neighborhood in pixels,
+///     which should be about twice the expected FWHM.
+/// :param threshold: The minimum peak intensity of a bead, default = the mean
+///     plus 3 standard deviations of the image.
+/// :param spacing: The pixel size of each axis, default = 1.0 (pixels).
+/// :return: A dictionary with per-bead lists, "center" (in pixels), "fwhm"
+///     (in the unit of the pixel size), "amplitude", "offset" and
+///     "asymmetry", and the summary statistics "fwhm_mean", "fwhm_std" and
+///     "asymmetry_mean".
+#[pyfunction]
+#[pyo3(name = "fit_psf")]
+#[pyo3(signature = (data, radius, threshold=None, spacing=None))]
+pub fn measure_fit_psf<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: usize,
+    threshold: Option<f64>,
+    spacing: Option<Vec<f64>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let report = dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::fit_psf(arr, radius, threshold, spacing.as_deref()))
+            .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    let beads = &report.beads;
+    dict.set_item(
+        "center",
+        beads.iter().map(|b| b.center.clone()).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "fwhm",
+        beads.iter().map(|b| b.fwhm.clone()).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "amplitude",
+        beads.iter().map(|b| b.amplitude).collect::<Vec<_>>(),
+    )?;
+    dict.set_item("offset", beads.iter().map(|b| b.offset).collect::<Vec<_>>())?;
+    dict.set_item(
+        "asymmetry",
+        beads.iter().map(|b| b.asymmetry).collect::<Vec<_>>(),
+    )?;
+    dict.set_item("fwhm_mean", report.fwhm_mean)?;
+    dict.set_item("fwhm_std", report.fwhm_std)?;
+    dict.set_item("asymmetry_mean", report.asymmetry_mean)?;
+
+    Ok(dict)
+}
+
+/// Compute the steady-state fluorescence anisotropy image of a pair of
+/// polarized channels.
+///
+/// This function computes the per-pixel steady-state anisotropy
+/// r = (I∥ - G·I⊥) / (I∥ + 2G·I⊥) of the background subtracted parallel and
+/// perpendicular polarized emission channels. Pixels where the total intensity
+/// I∥ + 2G·I⊥ is at or below "threshold", or where a channel is NaN, are NaN.
+///
+/// :param i_parallel: The parallel polarized channel.
+/// :param i_perpendicular: The perpendicular polarized channel, with the same
+///     shape and dtype as "i_parallel".
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :param background_parallel: The background of the parallel channel,
+///     default = 0.0.
+/// :param background_perpendicular: The background of the perpendicular
+///     channel, default = 0.0.
+/// :param threshold: The minimum total intensity, default = 0.0.
+/// :return: The anisotropy image.
+#[pyfunction]
+#[pyo3(name = "anisotropy")]
+#[pyo3(signature = (i_parallel, i_perpendicular, g_factor=None, background_parallel=None, background_perpendicular=None, threshold=None))]
+pub fn measure_anisotropy<'py>(
+    py: Python<'py>,
+    i_parallel: Bound<'py, PyAny>,
+    i_perpendicular: Bound<'py, PyAny>,
+    g_factor: Option<f64>,
+    background_parallel: Option<f64>,
+    background_perpendicular: Option<f64>,
+    threshold: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(i_parallel, PyReadonlyArrayDyn, |arr_par: T| {
+        let arr_perp = i_perpendicular.extract::<PyReadonlyArrayDyn<T>>()?;
+        measure::anisotropy(
+            arr_par.as_array(),
+            arr_perp.as_array(),
+            g_factor,
+            background_parallel,
+            background_perpendicular,
+            threshold,
+        )
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Detect transient events in intensity traces.
+///
+/// This function detects transients (e.g. calcium or voltage indicator spikes)
+/// in each row of a 2-dimensional (trace, t) array of time traces. The baseline
+/// of each trace is its median and the noise is the scaled median absolute
+/// deviation. An event is a run of at least "min_duration" consecutive frames
+/// above the baseline plus "threshold" noise standard deviations.
+///
+/// :param traces: The 2-dimensional (trace, t) array of time traces.
+/// :param threshold: The detection threshold in noise standard deviations
+///     above the baseline, default = 3.0.
+/// :param min_duration: The minimum number of frames above the threshold,
+///     default = 1.
+/// :return: A dictionary of event columns, "trace", "start", "peak", "end"
+///     (exclusive), "amplitude" (above the baseline), "duration" (in frames)
+///     and "rise_time" (10% to 90%, in frames).
+#[pyfunction]
+#[pyo3(name = "detect_transients")]
+#[pyo3(signature = (traces, threshold=None, min_duration=None))]
+pub fn measure_detect_transients<'py>(
+    py: Python<'py>,
+    traces: Bound<'py, PyAny>,
+    threshold: Option<f64>,
+    min_duration: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let events = dispatch_array!(traces, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| measure::detect_transients(arr, threshold, min_duration))
+            .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("trace", events.iter().map(|e| e.trace).collect::<Vec<_>>())?;
+    dict.set_item("start", events.iter().map(|e| e.start).collect::<Vec<_>>())?;
+    dict.set_item("peak", events.iter().map(|e| e.peak).collect::<Vec<_>>())?;
+    dict.set_item("end", events.iter().map(|e| e.end).collect::<Vec<_>>())?;
+    dict.set_item(
+        "amplitude",
+        events.iter().map(|e| e.amplitude).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "duration",
+        events.iter().map(|e| e.duration).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "rise_time",
+        events.iter().map(|e| e.rise_time).collect::<Vec<_>>(),
+    )?;
+
+    Ok(dict)
+}
+
+/// Measure the intensity, colocalization and lifetime of each region of a
+/// 2-dimensional label image.
+///
+/// This function computes, for each labeled region (e.g. a cell), the
+/// intensity statistics of two channels, their Pearson correlation and
+/// thresholded Manders' coefficients, the mean (G, S) coordinates of a phasor
+/// image and the apparent lifetimes of the mean coordinates, in a single
+/// parallel pass over the image. Columns that need a missing input are NaN.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param channel_a: The first intensity channel, with the shape of "labels".
+/// :param channel_b: The second intensity channel, with the shape of "labels".
+/// :param phasor: The 3-dimensional phasor image, where G and S are channels
+///     0 and 1 respectively, with the spatial shape of "labels".
+/// :param omega: The angular frequency of the apparent lifetimes. If None,
+///     the lifetime columns are NaN.
+/// :param thresholds: The (A, B) intensity thresholds of the Manders'
+///     coefficients, default = (0.0, 0.0).
+/// :param axis: The channel axis of "phasor", default = 2.
+/// :return: A dictionary of columns with one value per label, convertible to
+///     a DataFrame (e.g. "pandas.DataFrame(table)").
+#[pyfunction]
+#[pyo3(name = "per_label_analysis")]
+#[pyo3(signature = (labels, channel_a=None, channel_b=None, phasor=None, omega=None, thresholds=None, axis=None))]
+pub fn measure_per_label_analysis<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    channel_a: Option<PyReadonlyArray2<'py, f64>>,
+    channel_b: Option<PyReadonlyArray2<'py, f64>>,
+    phasor: Option<PyReadonlyArray3<'py, f64>>,
+    omega: Option<f64>,
+    thresholds: Option<(f64, f64)>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let labels = labels.as_array();
+    let channel_a = channel_a.as_ref().map(|a| a.as_array());
+    let channel_b = channel_b.as_ref().map(|b| b.as_array());
+    let phasor = phasor.as_ref().map(|p| p.as_array());
+    let table = py
+        .allow_threads(|| {
+            measure::per_label_analysis(
+                labels, channel_a, channel_b, phasor, omega, thresholds, axis,
+            )
+        })
+        .map_err(map_array_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("label", table.label)?;
+    dict.set_item("pixel_count", table.pixel_count)?;
+    dict.set_item("mean_a", table.mean_a)?;
+    dict.set_item("std_a", table.std_a)?;
+    dict.set_item("min_a", table.min_a)?;
+    dict.set_item("max_a", table.max_a)?;
+    dict.set_item("mean_b", table.mean_b)?;
+    dict.set_item("std_b", table.std_b)?;
+    dict.set_item("min_b", table.min_b)?;
+    dict.set_item("max_b", table.max_b)?;
+    dict.set_item("pearson", table.pearson)?;
+    dict.set_item("manders_m1", table.manders_m1)?;
+    dict.set_item("manders_m2", table.manders_m2)?;
+    dict.set_item("mean_g", table.mean_g)?;
+    dict.set_item("mean_s", table.mean_s)?;
+    dict.set_item("tau_phase", table.tau_phase)?;
+    dict.set_item("tau_modulation", table.tau_modulation)?;
+
+    Ok(dict)
+}
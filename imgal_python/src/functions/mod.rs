@@ -1,11 +1,23 @@
 pub mod colocalization_functions;
+pub mod data_functions;
 pub mod distribution_functions;
+pub mod feature_functions;
 pub mod filter_functions;
+pub mod fitting_functions;
 pub mod image_functions;
 pub mod integration_functions;
 pub mod kernel_functions;
+pub mod localization_functions;
+pub mod measure_functions;
+pub mod motion_functions;
+pub mod ops_functions;
 pub mod parameter_functions;
 pub mod phasor_functions;
+pub mod pipeline_functions;
+pub mod registration_functions;
+pub mod segmentation_functions;
 pub mod simulation_functions;
 pub mod statistics_functions;
 pub mod threshold_functions;
+pub mod tracking_functions;
+pub mod transform_functions;
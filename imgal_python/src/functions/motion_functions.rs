@@ -0,0 +1,137 @@
+use numpy::{IntoPyArray, PyArray2, PyArray3, PyArray4, PyReadonlyArray2, PyReadonlyArray3};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::motion::{self, FlowMethod};
+
+/// Compute the dense optical flow between two frames with the Lucas-Kanade
+/// method.
+///
+/// This function estimates the displacement of every pixel between two frames
+/// by assuming brightness constancy and a constant displacement within a
+/// Gaussian weighted window around each pixel.
+///
+/// :param prev: The first (earlier) 2-dimensional frame.
+/// :param next: The second (later) 2-dimensional frame, with the same shape and
+///     dtype as "prev".
+/// :param window_radius: The radius of the Gaussian weighted window,
+///     default = 3.
+/// :param min_eigenvalue: The minimum eigenvalue of the gradient structure
+///     tensor for a reliable estimate, default = 1e-6.
+/// :return: The flow field with a shape of (row, col, 2), where [..., 0] is
+///     the row displacement and [..., 1] is the column displacement, and the
+///     flow magnitude map.
+#[pyfunction]
+#[pyo3(name = "lucas_kanade")]
+#[pyo3(signature = (prev, next, window_radius=None, min_eigenvalue=None))]
+pub fn motion_lucas_kanade<'py>(
+    py: Python<'py>,
+    prev: Bound<'py, PyAny>,
+    next: Bound<'py, PyAny>,
+    window_radius: Option<usize>,
+    min_eigenvalue: Option<f64>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<f64>>)> {
+    dispatch_array!(prev, PyReadonlyArray2, |arr: T| {
+        let next = next.extract::<PyReadonlyArray2<T>>()?;
+        motion::lucas_kanade(
+            arr.as_array(),
+            next.as_array(),
+            window_radius,
+            min_eigenvalue,
+        )
+        .map(|(flow, mag)| (flow.into_pyarray(py), mag.into_pyarray(py)))
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the dense optical flow between two frames with the Farnebäck
+/// polynomial expansion method.
+///
+/// :param prev: The first (earlier) 2-dimensional frame.
+/// :param next: The second (later) 2-dimensional frame, with the same shape and
+///     dtype as "prev".
+/// :param window_radius: The radius of the Gaussian weighted displacement
+///     window, default = 5.
+/// :param poly_radius: The radius of the polynomial expansion neighborhood,
+///     default = 3.
+/// :param iterations: The number of refinement iterations, default = 3.
+/// :return: The flow field with a shape of (row, col, 2), where [..., 0] is
+///     the row displacement and [..., 1] is the column displacement, and the
+///     flow magnitude map.
+#[pyfunction]
+#[pyo3(name = "farneback")]
+#[pyo3(signature = (prev, next, window_radius=None, poly_radius=None, iterations=None))]
+pub fn motion_farneback<'py>(
+    py: Python<'py>,
+    prev: Bound<'py, PyAny>,
+    next: Bound<'py, PyAny>,
+    window_radius: Option<usize>,
+    poly_radius: Option<usize>,
+    iterations: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<f64>>)> {
+    dispatch_array!(prev, PyReadonlyArray2, |arr: T| {
+        let next = next.extract::<PyReadonlyArray2<T>>()?;
+        motion::farneback(
+            arr.as_array(),
+            next.as_array(),
+            window_radius,
+            poly_radius,
+            iterations,
+        )
+        .map(|(flow, mag)| (flow.into_pyarray(py), mag.into_pyarray(py)))
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the dense optical flow between consecutive frames of a time-lapse.
+///
+/// :param data: The input 3-dimensional time-lapse.
+/// :param method: The optical flow method, "lucas_kanade" or "farneback",
+///     default = "lucas_kanade".
+/// :param window_radius: The radius of the Gaussian weighted window, default =
+///     3 for "lucas_kanade" and 5 for "farneback".
+/// :param min_eigenvalue: The Lucas-Kanade minimum eigenvalue, default = 1e-6.
+/// :param poly_radius: The Farnebäck polynomial expansion radius, default = 3.
+/// :param iterations: The Farnebäck refinement iterations, default = 3.
+/// :param axis: The time axis, default = 0.
+/// :return: The flow fields with a shape of (t - 1, row, col, 2) and the flow
+///     magnitude maps with a shape of (t - 1, row, col).
+#[pyfunction]
+#[pyo3(name = "optical_flow_3d")]
+#[pyo3(signature = (data, method=None, window_radius=None, min_eigenvalue=None, poly_radius=None, iterations=None, axis=None))]
+pub fn motion_optical_flow_3d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<String>,
+    window_radius: Option<usize>,
+    min_eigenvalue: Option<f64>,
+    poly_radius: Option<usize>,
+    iterations: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray4<f64>>, Bound<'py, PyArray3<f64>>)> {
+    let method = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("lucas_kanade") => FlowMethod::LucasKanade {
+            window_radius: window_radius.unwrap_or(3),
+            min_eigenvalue: min_eigenvalue.unwrap_or(1e-6),
+        },
+        Some("farneback") => FlowMethod::Farneback {
+            window_radius: window_radius.unwrap_or(5),
+            poly_radius: poly_radius.unwrap_or(3),
+            iterations: iterations.unwrap_or(3),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"lucas_kanade\" and \"farneback\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray3, |arr| motion::optical_flow_3d(
+        arr.as_array(),
+        method,
+        axis
+    )
+    .map(|(flow, mag)| (flow.into_pyarray(py), mag.into_pyarray(py)))
+    .map_err(map_array_error))
+}
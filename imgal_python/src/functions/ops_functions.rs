@@ -0,0 +1,171 @@
+use std::sync::OnceLock;
+
+use numpy::{IntoPyArray, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::ops::{Op, OpParameter, OpRegistry, OpValue, ValueType};
+use imgal::traits::numeric::ToFloat64;
+
+/// The builtin op registry, created on first use.
+fn registry() -> &'static OpRegistry {
+    static REGISTRY: OnceLock<OpRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(OpRegistry::with_builtins)
+}
+
+/// Look up a builtin op by name.
+fn get_op(name: &str) -> PyResult<&'static dyn Op> {
+    registry()
+        .get(name)
+        .ok_or_else(|| PyErr::new::<PyValueError, _>(format!("Unknown op \"{}\".", name)))
+}
+
+/// The Python name of a value type.
+fn value_type_name(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::Image => "image",
+        ValueType::Mask => "mask",
+        ValueType::Labels => "labels",
+        ValueType::Float => "float",
+        ValueType::Int => "int",
+    }
+}
+
+/// Convert op parameter metadata to a list of Python dictionaries.
+fn params_to_list<'py>(py: Python<'py>, params: &[OpParameter]) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for p in params {
+        let dict = PyDict::new(py);
+        dict.set_item("name", p.name)?;
+        dict.set_item("description", p.description)?;
+        dict.set_item("type", value_type_name(p.value_type))?;
+        dict.set_item("required", p.required)?;
+        dict.set_item("min", p.min)?;
+        dict.set_item("max", p.max)?;
+        list.append(dict)?;
+    }
+
+    Ok(list)
+}
+
+/// Convert a Python object to an op value of the given type.
+fn to_op_value(value: &Bound<'_, PyAny>, value_type: ValueType) -> PyResult<OpValue> {
+    Ok(match value_type {
+        ValueType::Image => OpValue::Image(dispatch_array!(value, PyReadonlyArrayDyn, |arr| {
+            Ok(arr.as_array().mapv(|v| v.to_f64()))
+        })?),
+        ValueType::Mask => OpValue::Mask(
+            value
+                .extract::<PyReadonlyArrayDyn<bool>>()?
+                .as_array()
+                .to_owned(),
+        ),
+        ValueType::Labels => OpValue::Labels(
+            value
+                .extract::<PyReadonlyArrayDyn<usize>>()?
+                .as_array()
+                .to_owned(),
+        ),
+        ValueType::Float => OpValue::Float(value.extract()?),
+        ValueType::Int => OpValue::Int(value.extract()?),
+    })
+}
+
+/// Convert an op value to a Python object.
+fn from_op_value(py: Python<'_>, value: OpValue) -> PyResult<Bound<'_, PyAny>> {
+    Ok(match value {
+        OpValue::Image(arr) => arr.into_pyarray(py).into_any(),
+        OpValue::Mask(arr) => arr.into_pyarray(py).into_any(),
+        OpValue::Labels(arr) => arr.into_pyarray(py).into_any(),
+        OpValue::Float(v) => v.into_pyobject(py)?.into_any(),
+        OpValue::Int(v) => v.into_pyobject(py)?.into_any(),
+    })
+}
+
+/// List the names of all available ops.
+///
+/// Op names are namespaced by module, for example "filter.gaussian_2d".
+///
+/// :return: The sorted list of op names.
+#[pyfunction]
+#[pyo3(name = "list_ops")]
+pub fn ops_list_ops() -> Vec<&'static str> {
+    registry().iter().map(|op| op.info().name).collect()
+}
+
+/// Get the metadata of an op.
+///
+/// This function describes an op's inputs and outputs so that user interfaces
+/// can be built from them. Each input and output is a dictionary with the
+/// "name", "description", "type" ("image", "mask", "labels", "float" or
+/// "int"), "required", "min" and "max" keys.
+///
+/// :param name: The op name.
+/// :return: A dictionary with the op "name", "description", "inputs" and
+///     "outputs".
+#[pyfunction]
+#[pyo3(name = "info")]
+pub fn ops_info<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyDict>> {
+    let info = get_op(name)?.info();
+    let dict = PyDict::new(py);
+    dict.set_item("name", info.name)?;
+    dict.set_item("description", info.description)?;
+    dict.set_item("inputs", params_to_list(py, info.inputs)?)?;
+    dict.set_item("outputs", params_to_list(py, info.outputs)?)?;
+
+    Ok(dict)
+}
+
+/// Run an op by name.
+///
+/// This function converts the inputs to the types described by the op
+/// metadata (see "info"), validates them and runs the op. Images are
+/// converted to float64.
+///
+/// :param name: The op name.
+/// :param inputs: A dictionary of the op inputs by name. Optional inputs may
+///     be omitted.
+/// :return: A tuple of the op outputs, in the order of the op metadata.
+#[pyfunction]
+#[pyo3(name = "run")]
+pub fn ops_run<'py>(
+    py: Python<'py>,
+    name: &str,
+    inputs: Bound<'py, PyDict>,
+) -> PyResult<Bound<'py, PyTuple>> {
+    let op = get_op(name)?;
+    let params = op.info().inputs;
+    for key in inputs.keys() {
+        let key: String = key.extract()?;
+        if !params.iter().any(|p| p.name == key) {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "Unknown input \"{}\" for op \"{}\".",
+                key, name
+            )));
+        }
+    }
+    let values = params
+        .iter()
+        .map(|p| {
+            inputs
+                .get_item(p.name)?
+                .filter(|v| !v.is_none())
+                .map(|v| to_op_value(&v, p.value_type))
+                .transpose()
+        })
+        .collect::<PyResult<Vec<Option<OpValue>>>>()?;
+    let outputs = py
+        .allow_threads(|| op.run(&values))
+        .map_err(map_array_error)?;
+
+    PyTuple::new(
+        py,
+        outputs
+            .into_iter()
+            .map(|v| from_op_value(py, v))
+            .collect::<PyResult<Vec<_>>>()?,
+    )
+}
@@ -1,11 +1,64 @@
 use numpy::{
-    IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3, PyReadwriteArray3,
+    IntoPyArray, PyArray2, PyArray3, PyArray4, PyReadonlyArray2, PyReadonlyArray3,
+    PyReadonlyArray4, PyReadwriteArray3,
 };
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
+use crate::dispatch_array;
 use crate::error::map_array_error;
-use imgal::phasor::{calibration, plot, time_domain};
+use crate::utils::{check_output_shape, resolve_decay_axis};
+use imgal::phasor::batch::{self, PhasorSettings};
+use imgal::phasor::calibration::{CalibrationIssue, CenterEstimator, RobustOptions};
+use imgal::phasor::denoise::{self, PhasorFilter};
+use imgal::phasor::multi_harmonic::LifetimeEstimate;
+use imgal::phasor::time_domain::{PhasorGate, QualityMetric, SpatialBinning};
+use imgal::phasor::{
+    analysis, calibration, frequency_domain, gate_scan, multi_harmonic, plot, render, time_domain,
+    trajectory, unmixing,
+};
+use imgal::traits::numeric::ToFloat64;
+use imgal::transform::Wavelet;
+
+/// Test the consistency of the apparent lifetimes of a 3-dimensional decay
+/// image across harmonics.
+///
+/// This function computes the apparent phase (τφ) and modulation (τM)
+/// lifetimes of each pixel at each harmonic. All apparent lifetimes of a
+/// monoexponential decay are equal, so the multi-exponentiality index of each
+/// pixel is the coefficient of variation of its apparent lifetimes:
+///
+/// index = std(τ) / mean(τ)
+///
+/// An index close to 0.0 marks pixels where simple phasor interpretation is
+/// valid. Pixels without photons are NaN.
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period.
+/// :param harmonics: The harmonics to compare, default = [1, 2]. Each
+///     harmonic must be greater than 0 and can not exceed the Nyquist limit.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: A tuple of the multi-exponentiality index image and the apparent
+///     lifetime image, where τφ(n) and τM(n) of the i-th harmonic are channels
+///     2i and 2i + 1 respectively.
+#[pyfunction]
+#[pyo3(name = "harmonic_consistency")]
+#[pyo3(signature = (data, period, harmonics=None, axis=None))]
+pub fn analysis_harmonic_consistency<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    harmonics: Option<Vec<u32>>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, Bound<'py, PyArray3<f64>>)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| analysis::harmonic_consistency(arr, period, harmonics.as_deref(), axis))
+            .map(|(index, taus)| (index.into_pyarray(py), taus.into_pyarray(py)))
+            .map_err(map_array_error)
+    })
+}
 
 /// Calibrate a real and imaginary (G, S) coordinates.
 ///
@@ -54,36 +107,43 @@ pub fn calibration_coordinates(g: f64, s: f64, modulation: f64, phase: f64) -> (
 /// :param modulation: The modulation to scale the input (G, S) coordinates.
 /// :param phase: The phase, φ angle, to rotate the input (G, S) coordinates.
 /// :param axis: The channel axis, default = 2.
+/// :param out: An optional float64 output array with the same shape as "data"
+///     to write the calibrated values into, instead of allocating a new array.
+///     "out" must not be the same array as "data", use "image_mut" to
+///     calibrate in place.
 /// :return: A 3-dimensional array with the calibrated phasor values, where
-///     calibrated G and S are channels 0 and 1 respectively.
+///     calibrated G and S are channels 0 and 1 respectively. If "out" is
+///     given, "out" is returned.
 #[pyfunction]
 #[pyo3(name = "image")]
-#[pyo3(signature = (data, modulation, phase, axis=None))]
+#[pyo3(signature = (data, modulation, phase, axis=None, out=None))]
 pub fn calibration_image<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     modulation: f64,
     phase: f64,
     axis: Option<usize>,
+    out: Option<PyReadwriteArray3<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        match out {
+            // copy the input into "out" and calibrate it in place
+            Some(mut o) => {
+                check_output_shape(o.as_array().shape(), arr.shape())?;
+                let mut o_arr = o.as_array_mut();
+                py.allow_threads(|| {
+                    o_arr.zip_mut_with(&arr, |o, v| *o = v.to_f64());
+                    calibration::image_mut(o_arr, modulation, phase, axis);
+                });
+                Ok((**o).clone())
+            }
+            None => Ok(py
+                .allow_threads(|| calibration::image(arr, modulation, phase, axis))
+                .into_pyarray(py)),
+        }
+    })
 }
 
 /// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
@@ -138,6 +198,279 @@ pub fn calibration_modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) ->
     calibration::modulation_and_phase(g, s, tau, omega)
 }
 
+/// Find the modulation and phase calibration values from many measured
+/// coordinates.
+///
+/// This function estimates the center of the measured (G, S) coordinates of
+/// a monoexponential reference, coordinate by coordinate, and computes the
+/// calibration values of the center. The median and trimmed mean are robust
+/// to a few saturated or background pixels that skew the mean. Non-finite
+/// coordinates are ignored.
+///
+/// :param g: The measured real (G) values.
+/// :param s: The measured imaginary (S) values, the same length as "g".
+/// :param tau: The lifetime, τ.
+/// :param omega: The angular frequency, ω.
+/// :param estimator: The center estimator, "mean", "median" or "trimmed",
+///     default = "median".
+/// :param trim: The fraction of the smallest and of the largest values
+///     discarded by the "trimmed" estimator, default = 0.1.
+/// :return: The modulation and phase calibration values, (M, φ).
+#[pyfunction]
+#[pyo3(name = "estimate_modulation_and_phase")]
+#[pyo3(signature = (g, s, tau, omega, estimator=None, trim=None))]
+pub fn calibration_estimate_modulation_and_phase(
+    g: Vec<f64>,
+    s: Vec<f64>,
+    tau: f64,
+    omega: f64,
+    estimator: Option<String>,
+    trim: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    let estimator = parse_estimator(estimator, trim)?;
+    calibration::estimate_modulation_and_phase(&g, &s, tau, omega, Some(estimator))
+        .map_err(map_array_error)
+}
+
+/// Find the modulation and phase calibration values of a 3-dimensional
+/// phasor image with iterative outlier rejection.
+///
+/// This function calibrates a phasor image of a monoexponential reference
+/// while rejecting pixels that do not behave like the reference. Each
+/// iteration estimates the calibration values from the current pixels,
+/// calibrates them and rejects the pixels whose distance "d" to the expected
+/// monoexponential coordinates is:
+///
+/// d > threshold · 1.4826 · median(d)
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param tau: The lifetime of the reference, τ.
+/// :param omega: The angular frequency, ω.
+/// :param mask: The 2-dimensional boolean mask of the reference pixels. If
+///     "None", all pixels are used.
+/// :param estimator: The center estimator, "mean", "median" or "trimmed",
+///     default = "median".
+/// :param trim: The fraction of the smallest and of the largest values
+///     discarded by the "trimmed" estimator, default = 0.1.
+/// :param threshold: The rejection threshold in robust standard deviations,
+///     default = 3.0.
+/// :param max_iterations: The maximum number of rejection iterations,
+///     default = 10.
+/// :param axis: The channel axis, default = 2.
+/// :return: A dictionary with the "modulation" and "phase" calibration
+///     values, the boolean "inliers" image and the number of "iterations".
+#[pyfunction]
+#[pyo3(name = "robust_from_image")]
+#[pyo3(signature = (data, tau, omega, mask=None, estimator=None, trim=None, threshold=None, max_iterations=None, axis=None))]
+pub fn calibration_robust_from_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    tau: f64,
+    omega: f64,
+    mask: Option<PyReadonlyArray2<bool>>,
+    estimator: Option<String>,
+    trim: Option<f64>,
+    threshold: Option<f64>,
+    max_iterations: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let default = RobustOptions::default();
+    let options = RobustOptions {
+        estimator: parse_estimator(estimator, trim)?,
+        threshold: threshold.unwrap_or(default.threshold),
+        max_iterations: max_iterations.unwrap_or(default.max_iterations),
+    };
+    let m = mask.as_ref().map(|m| m.as_array());
+    let cal = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| calibration::robust_from_image(arr, tau, omega, m, Some(options), axis))
+            .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("modulation", cal.modulation)?;
+    dict.set_item("phase", cal.phase)?;
+    dict.set_item("inliers", cal.inliers.into_pyarray(py))?;
+    dict.set_item("iterations", cal.iterations)?;
+
+    Ok(dict)
+}
+
+/// Check the calibration of a 3-dimensional phasor image of a
+/// monoexponential control.
+///
+/// This function compares the calibrated (G, S) coordinates of a control of
+/// known lifetime with the universal circle and the expected monoexponential
+/// coordinates. An "off_circle" issue is reported if the median signed
+/// distance to the universal circle exceeds "tolerance", and a
+/// "lifetime_mismatch" issue if the control lies on the circle more than
+/// "tolerance" away from the expected coordinates, from a phase error or a
+/// period (or bin width) mismatch.
+///
+/// :param data: The 3-dimensional calibrated phasor image, where G and S are
+///     channels 0 and 1 respectively.
+/// :param tau: The lifetime of the control, τ.
+/// :param omega: The angular frequency, ω.
+/// :param mask: The 2-dimensional boolean mask of the control pixels. If
+///     "None", all pixels are used.
+/// :param tolerance: The distance in phasor units beyond which an issue is
+///     reported, default = 0.02.
+/// :param axis: The channel axis, default = 2.
+/// :return: A dictionary with the median "center" and "expected" (G, S)
+///     coordinates, the "center_distance", the "circle_distance", the
+///     "spread", the apparent "phase_lifetime" and "modulation_lifetime", the
+///     "suggested_modulation", "suggested_phase" and "suggested_period_scale"
+///     corrections and the list of "issues".
+#[pyfunction]
+#[pyo3(name = "diagnostics")]
+#[pyo3(signature = (data, tau, omega, mask=None, tolerance=None, axis=None))]
+pub fn calibration_diagnostics<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    tau: f64,
+    omega: f64,
+    mask: Option<PyReadonlyArray2<bool>>,
+    tolerance: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    let diag = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| calibration::diagnostics(arr, tau, omega, m, tolerance, axis))
+            .map_err(map_array_error)
+    })?;
+    let issues: Vec<&str> = diag
+        .issues
+        .iter()
+        .map(|i| match i {
+            CalibrationIssue::OffCircle => "off_circle",
+            CalibrationIssue::LifetimeMismatch => "lifetime_mismatch",
+        })
+        .collect();
+    let dict = PyDict::new(py);
+    dict.set_item("center", diag.center)?;
+    dict.set_item("expected", diag.expected)?;
+    dict.set_item("center_distance", diag.center_distance)?;
+    dict.set_item("circle_distance", diag.circle_distance)?;
+    dict.set_item("spread", diag.spread)?;
+    dict.set_item("phase_lifetime", diag.phase_lifetime)?;
+    dict.set_item("modulation_lifetime", diag.modulation_lifetime)?;
+    dict.set_item("suggested_modulation", diag.suggested_modulation)?;
+    dict.set_item("suggested_phase", diag.suggested_phase)?;
+    dict.set_item("suggested_period_scale", diag.suggested_period_scale)?;
+    dict.set_item("issues", issues)?;
+
+    Ok(dict)
+}
+
+/// Parse a center estimator name.
+fn parse_estimator(estimator: Option<String>, trim: Option<f64>) -> PyResult<CenterEstimator> {
+    match estimator.map(|e| e.to_lowercase()).as_deref() {
+        None | Some("median") => Ok(CenterEstimator::Median),
+        Some("mean") => Ok(CenterEstimator::Mean),
+        Some("trimmed") => Ok(CenterEstimator::Trimmed(trim.unwrap_or(0.1))),
+        Some(_) => Err(PyErr::new::<PyValueError, _>(
+            "Unknown estimator, supported estimators are \"mean\", \"median\", and \"trimmed\".",
+        )),
+    }
+}
+
+/// Denoise the real and imaginary (G, S) coordinates of a 3-dimensional phasor
+/// image.
+///
+/// This function spatially filters the G and S coordinates of a phasor image
+/// independently, reducing the scatter of the phasor cloud without spatially
+/// binning the photons of the decay data. Pixels with NaN coordinates are
+/// excluded from the median and Gaussian neighborhoods and remain NaN.
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param filter: The spatial filter, "median", "gaussian" or "wavelet",
+///     default = "median".
+/// :param size: The median filter window size, default = 3.
+/// :param sigma: The Gaussian filter standard deviation, default = 1.0.
+/// :param wavelet: The wavelet of the wavelet filter, "haar", "db2" or "db4",
+///     default = "haar".
+/// :param levels: The decomposition levels of the wavelet filter, default = 3.
+/// :param repeat: The number of times the filter is applied, default = 1.
+/// :param axis: The channel axis, default = 2.
+/// :return: The denoised phasor image.
+#[pyfunction]
+#[pyo3(name = "image")]
+#[pyo3(signature = (data, filter=None, size=None, sigma=None, wavelet=None, levels=None, repeat=None, axis=None))]
+pub fn denoise_image<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<f64>,
+    filter: Option<String>,
+    size: Option<usize>,
+    sigma: Option<f64>,
+    wavelet: Option<String>,
+    levels: Option<usize>,
+    repeat: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let wavelet = match wavelet.map(|w| w.to_lowercase()).as_deref() {
+        None | Some("haar") => Wavelet::Haar,
+        Some("db2") => Wavelet::Db2,
+        Some("db4") => Wavelet::Db4,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown wavelet, supported wavelets are \"haar\", \"db2\", and \"db4\".",
+            ));
+        }
+    };
+    let filter = match filter.map(|f| f.to_lowercase()).as_deref() {
+        None | Some("median") => PhasorFilter::Median {
+            size: size.unwrap_or(3),
+        },
+        Some("gaussian") => PhasorFilter::Gaussian {
+            sigma: sigma.unwrap_or(1.0),
+        },
+        Some("wavelet") => PhasorFilter::Wavelet {
+            wavelet,
+            levels: levels.unwrap_or(3),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown filter, supported filters are \"median\", \"gaussian\", and \"wavelet\".",
+            ));
+        }
+    };
+    let data = data.as_array();
+    py.allow_threads(|| denoise::image(data, filter, repeat, axis))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the apparent phase and modulation lifetimes of a 3-dimensional
+/// phasor image.
+///
+/// This function back-projects the (G, S) coordinates of each pixel to the
+/// apparent phase and modulation lifetimes:
+///
+/// tau_phi = S / (omega * G)
+/// tau_m = sqrt(1 / (G^2 + S^2) - 1) / omega
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param omega: The angular frequency.
+/// :param axis: The channel axis, default = 2.
+/// :return: A 3-dimensional array with the apparent lifetimes, where tau_phi
+///     and tau_m are channels 0 and 1 respectively.
+#[pyfunction]
+#[pyo3(name = "apparent_lifetimes")]
+#[pyo3(signature = (data, omega, axis=None))]
+pub fn plot_apparent_lifetimes<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<f64>,
+    omega: f64,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    plot::apparent_lifetimes(data.as_array(), omega, axis)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
 /// Compute the modulation of phasor G and S coordinates.
 ///
 /// This function calculates the modulation (M) of phasor G and S coordinates
@@ -173,6 +506,91 @@ pub fn plot_phase(g: f64, s: f64) -> f64 {
     plot::phase(g, s)
 }
 
+/// Compute the G and S coordinates of a 3-dimensional polar phasor image.
+///
+/// This function is the inverse of "to_polar". It converts a 3-dimensional
+/// polar phasor image of phase (φ) and modulation (M) values back into G and S
+/// coordinates using:
+///
+/// G = M * cos(φ)
+/// S = M * sin(φ)
+///
+/// :param data: The 3-dimensional polar phasor image, where phase and
+///     modulation are channels 0 and 1 respectively.
+/// :param axis: The channel axis, default = 2.
+/// :return: A 3-dimensional array with the G and S coordinates, where G and S
+///     are channels 0 and 1 respectively.
+#[pyfunction]
+#[pyo3(name = "from_polar")]
+#[pyo3(signature = (data, axis=None))]
+pub fn plot_from_polar<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    plot::from_polar(data.as_array(), axis)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the G and S coordinates of monoexponential decays on the universal
+/// semicircle.
+///
+/// This function computes the monoexponential (G, S) coordinates for each
+/// lifetime in "taus". The returned points are reference markers on the
+/// universal semicircle, useful as plot layers (e.g. lifetime ticks).
+///
+/// :param taus: The lifetimes of the monoexponential decays.
+/// :param omega: The angular frequency.
+/// :return: The G and S coordinates of each lifetime in "taus".
+#[pyfunction]
+#[pyo3(name = "semicircle_points")]
+pub fn plot_semicircle_points(taus: Vec<f64>, omega: f64) -> (Vec<f64>, Vec<f64>) {
+    plot::semicircle_points(&taus, omega)
+}
+
+/// Compute the phase and modulation polar image of a 3-dimensional phasor image.
+///
+/// This function converts a 3-dimensional phasor image of G and S coordinates
+/// into a polar image of phase (φ) and modulation (M) values:
+///
+/// φ = tan⁻¹(S / G)
+/// M = √(G² + S²)
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param axis: The channel axis, default = 2.
+/// :return: A 3-dimensional array with the polar phasor values, where phase
+///     and modulation are channels 0 and 1 respectively.
+#[pyfunction]
+#[pyo3(name = "to_polar")]
+#[pyo3(signature = (data, axis=None))]
+pub fn plot_to_polar<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    plot::to_polar(data.as_array(), axis)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the G and S coordinates of the universal semicircle.
+///
+/// This function samples the universal semicircle, the locus of all
+/// monoexponential decays in phasor space, with evenly spaced G coordinates:
+///
+/// S = √(G - G²), 0 ≤ G ≤ 1
+///
+/// :param samples: The number of points along the semicircle, default = 100.
+/// :return: The G and S coordinates of the semicircle.
+#[pyfunction]
+#[pyo3(name = "universal_semicircle")]
+#[pyo3(signature = (samples=None))]
+pub fn plot_universal_semicircle(samples: Option<usize>) -> (Vec<f64>, Vec<f64>) {
+    plot::universal_semicircle(samples)
+}
+
 /// Compute the G and S coordinates for a monoexponential decay.
 ///
 /// This function computes the G and S coordinates for a monoexponential decay
@@ -219,6 +637,433 @@ pub fn plot_map_mask<'py>(
         .map_err(map_array_error)
 }
 
+/// Render two or three component fraction images into an RGB image.
+///
+/// This function maps component fraction images (e.g. from phasor unmixing)
+/// into a single 8-bit RGB image. Each component is assigned a color and each
+/// pixel's color is the fraction weighted sum of the component colors:
+///
+/// RGB = I * Σ fᵢ * Cᵢ
+///
+/// Where "fᵢ" is the fraction (clamped between 0.0 and 1.0) and "Cᵢ" the color
+/// of component "i" and "I" is the optional normalized intensity (e.g. photon
+/// count) modulation.
+///
+/// :param fractions: Two or three 2-dimensional component fraction images. All
+///     fraction images must have the same shape.
+/// :param colors: The RGB color of each component. If "None", red, green and
+///     blue are used for the first, second and third components respectively.
+/// :param intensity: An optional 2-dimensional intensity image (e.g. photon
+///     counts) used to modulate the brightness of each pixel.
+/// :return: The 3-dimensional (row, col, ch) RGB image.
+#[pyfunction]
+#[pyo3(name = "fraction_rgb")]
+#[pyo3(signature = (fractions, colors=None, intensity=None))]
+pub fn render_fraction_rgb<'py>(
+    py: Python<'py>,
+    fractions: Vec<PyReadonlyArray2<f64>>,
+    colors: Option<Vec<[u8; 3]>>,
+    intensity: Option<PyReadonlyArray2<f64>>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let views: Vec<_> = fractions.iter().map(|f| f.as_array()).collect();
+    render::fraction_rgb(
+        &views,
+        colors.as_deref(),
+        intensity.as_ref().map(|i| i.as_array()),
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates and the DC image of a
+/// phase-stepped homodyne image stack.
+///
+/// This function fits each pixel's phase-stepped intensities (camera-based
+/// frequency-domain FLIM) with:
+///
+/// Iₖ = a + b * cos(φₖ) + c * sin(φₖ)
+///
+/// by linear least squares and returns DC = a, G = b / a and S = c / a. Bad
+/// steps (e.g. dropped or saturated frames) are detected with leave-one-out
+/// predictions: the step with the largest relative prediction error is
+/// excluded while its error is greater than "tolerance" and at least 4 steps
+/// remain. The coordinates are uncalibrated.
+///
+/// :param data: The phase-stepped image stack.
+/// :param phases: The phase step angles in radians, default = 2πk/K (i.e. K
+///     equally spaced steps over one period).
+/// :param tolerance: The maximum relative leave-one-out error of a good step,
+///     default = 0.1.
+/// :param axis: The phase step axis, default = 0.
+/// :return: A tuple of the real and imaginary coordinates as a 3-dimensional
+///     (row, col, ch) image, where G and S are indexed at 0 and 1 respectively
+///     on the channel axis, the DC image and the indices of the excluded bad
+///     steps.
+#[pyfunction]
+#[pyo3(name = "from_phase_steps")]
+#[pyo3(signature = (data, phases=None, tolerance=None, axis=None))]
+pub fn frequency_domain_from_phase_steps<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    phases: Option<Vec<f64>>,
+    tolerance: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<(
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Vec<usize>,
+)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            frequency_domain::from_phase_steps(arr, phases.as_deref(), tolerance, axis)
+        })
+        .map(|(gs, dc, bad)| (gs.into_pyarray(py), dc.into_pyarray(py), bad))
+        .map_err(map_array_error)
+    })
+}
+
+/// Reconstruct a 1-dimensional decay curve from a gate-scan acquisition.
+///
+/// Gated detectors (e.g. gated ICCD or SPAD cameras) integrate the decay within
+/// a gate of width "width" opened at each delay. This function deconvolves the
+/// overlap of the gates by regularized (ridge) least squares:
+///
+/// I = (AᵀA + λ s 1)⁻¹ Aᵀ G
+///
+/// Where "A" holds the fraction of each time bin covered by each gate (gates
+/// wrap around the period) and "s" is the mean diagonal value of AᵀA. The
+/// decay can be used with the "time_domain" phasor functions with the same
+/// period.
+///
+/// :param data: The gate values, one per delay.
+/// :param delays: The gate opening delays, in the unit of "period".
+/// :param width: The gate width, in the unit of "period".
+/// :param period: The period.
+/// :param bins: The number of time bins of the reconstructed decay, default =
+///     the number of gates.
+/// :param regularization: The relative ridge regularization "λ", default =
+///     1e-3.
+/// :return: The reconstructed decay curve, in counts per time bin.
+#[pyfunction]
+#[pyo3(name = "decay")]
+#[pyo3(signature = (data, delays, width, period, bins=None, regularization=None))]
+pub fn gate_scan_decay(
+    data: Vec<f64>,
+    delays: Vec<f64>,
+    width: f64,
+    period: f64,
+    bins: Option<usize>,
+    regularization: Option<f64>,
+) -> PyResult<Vec<f64>> {
+    gate_scan::decay(&data, &delays, width, period, bins, regularization).map_err(map_array_error)
+}
+
+/// Reconstruct a 3-dimensional decay image from a gate-scan image stack.
+///
+/// This function reconstructs each pixel's decay from its gate values, see
+/// "decay".
+///
+/// :param data: The gate-scan image stack.
+/// :param delays: The gate opening delays, in the unit of "period".
+/// :param width: The gate width, in the unit of "period".
+/// :param period: The period.
+/// :param bins: The number of time bins of the reconstructed decays, default =
+///     the number of gates.
+/// :param regularization: The relative ridge regularization "λ", default =
+///     1e-3.
+/// :param axis: The gate axis, default = 2.
+/// :return: The reconstructed (row, col, bin) decay image.
+#[pyfunction]
+#[pyo3(name = "image")]
+#[pyo3(signature = (data, delays, width, period, bins=None, regularization=None, axis=None))]
+pub fn gate_scan_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    delays: Vec<f64>,
+    width: f64,
+    period: f64,
+    bins: Option<usize>,
+    regularization: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            gate_scan::image(arr, &delays, width, period, bins, regularization, axis)
+        })
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Adaptively smooth the decays of a 3-dimensional decay image along the decay
+/// axis.
+///
+/// This function smooths each pixel's decay with a moving average whose width
+/// adapts to the local photon counts: each time bin after the decay peak is
+/// averaged over the smallest window of ±h bins (h ≤ "max_radius") that holds
+/// at least "min_counts" photons. Bins up to the peak (i.e. the rising edge
+/// shaped by the IRF) are not smoothed, and windows do not extend over the
+/// peak or past the end of the decay.
+///
+/// :param data: I(t), the decay data image.
+/// :param min_counts: The minimum photon count of a smoothing window, default
+///     = 10.0.
+/// :param max_radius: The maximum half-width of a smoothing window in time
+///     bins, default = 8.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The smoothed decay image.
+#[pyfunction]
+#[pyo3(name = "adaptive_smooth")]
+#[pyo3(signature = (data, min_counts=None, max_radius=None, axis=None))]
+pub fn time_domain_adaptive_smooth<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    min_counts: Option<f64>,
+    max_radius: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| time_domain::adaptive_smooth(arr, min_counts, max_radius, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Combine estimates with an inverse-variance weighted mean.
+///
+/// This function computes the minimum variance combination of independent
+/// estimates of the same quantity:
+///
+/// x̄ = Σ (xᵢ / σᵢ²) / Σ (1 / σᵢ²)
+/// σ̄² = 1 / Σ (1 / σᵢ²)
+///
+/// Estimates with a non-finite value or a non-finite or non-positive variance
+/// are skipped.
+///
+/// :param estimates: The estimates, "xᵢ".
+/// :param variances: The variance of each estimate, "σᵢ²".
+/// :return: The weighted mean and its variance. If no estimate is valid,
+///     (NaN, NaN) is returned.
+#[pyfunction]
+#[pyo3(name = "inverse_variance_mean")]
+pub fn multi_harmonic_inverse_variance_mean(
+    estimates: Vec<f64>,
+    variances: Vec<f64>,
+) -> PyResult<(f64, f64)> {
+    multi_harmonic::inverse_variance_mean(&estimates, &variances).map_err(map_array_error)
+}
+
+/// Compute variance-weighted lifetimes of a 3-dimensional decay image from
+/// multiple harmonics.
+///
+/// This function computes the apparent lifetime of each pixel at each
+/// harmonic and merges them into one estimate with an inverse-variance
+/// weighted mean. The variances follow from the Poisson statistics of the
+/// decay's photons, including the covariance of G and S at each harmonic.
+/// Pixels without photons are NaN.
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period.
+/// :param harmonics: The harmonics to merge, default = [1, 2]. Each harmonic
+///     must be greater than 0 and can not exceed the Nyquist limit.
+/// :param estimate: The apparent lifetime estimate, "phase" or "modulation",
+///     default = "phase".
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: A tuple of the merged lifetime image and its variance image.
+#[pyfunction]
+#[pyo3(name = "lifetime_image")]
+#[pyo3(signature = (data, period, harmonics=None, estimate=None, axis=None))]
+pub fn multi_harmonic_lifetime_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    harmonics: Option<Vec<u32>>,
+    estimate: Option<String>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>)> {
+    let estimate = match estimate.map(|e| e.to_lowercase()).as_deref() {
+        None | Some("phase") => LifetimeEstimate::Phase,
+        Some("modulation") => LifetimeEstimate::Modulation,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown estimate, supported estimates are \"phase\" and \"modulation\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            multi_harmonic::lifetime_image(arr, period, harmonics.as_deref(), Some(estimate), axis)
+        })
+        .map(|(tau, var)| (tau.into_pyarray(py), var.into_pyarray(py)))
+        .map_err(map_array_error)
+    })
+}
+
+/// Spatially bin the decay histograms of a 3-dimensional decay image.
+///
+/// This function sums the decay histograms of neighboring pixels. The "block"
+/// scheme sums non-overlapping "factor" x "factor" blocks and conserves the
+/// total photon count. The sliding "square" and "circular" schemes keep the
+/// image size, each pixel being the sum of the decays within "radius" pixels.
+///
+/// :param data: I(t), the decay data image.
+/// :param binning: The binning scheme, "block", "square" or "circular",
+///     default = "square".
+/// :param factor: The block size of the "block" scheme, default = 2.
+/// :param radius: The kernel radius of the sliding schemes, default = 1.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+///     the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+///     and "l" are the x, y, z, channel, time and lifetime axes. The decay
+///     axis is the "l" axis if present, otherwise the "t" axis.
+/// :return: The binned decay image.
+#[pyfunction]
+#[pyo3(name = "bin_spatial")]
+#[pyo3(signature = (data, binning=None, factor=None, radius=None, axis=None, axes=None))]
+pub fn time_domain_bin_spatial<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    binning: Option<String>,
+    factor: Option<usize>,
+    radius: Option<usize>,
+    axis: Option<usize>,
+    axes: Option<String>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let binning = match binning.map(|b| b.to_lowercase()).as_deref() {
+        None | Some("square") => SpatialBinning::Square {
+            radius: radius.unwrap_or(1),
+        },
+        Some("circular") => SpatialBinning::Circular {
+            radius: radius.unwrap_or(1),
+        },
+        Some("block") => SpatialBinning::Block {
+            factor: factor.unwrap_or(2),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown binning, supported binnings are \"block\", \"square\", and \"circular\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let axis = resolve_decay_axis(axis, axes.as_deref(), arr.shape())?;
+        py.allow_threads(|| time_domain::bin_spatial(arr, binning, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image with photon count and histogram quality gating.
+///
+/// This function computes the phasor image while gating each pixel by its total
+/// photon count and histogram quality in the same pass. Pixels that fail either
+/// gate are set to the "fill" value and marked as invalid in the returned
+/// validity mask.
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period.
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are computed. Pixels outside of the mask are set to 0.0 and are
+///     invalid.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of samples along "axis").
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+///     the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+///     and "l" are the x, y, z, channel, time and lifetime axes. The decay
+///     axis is the "l" axis if present, otherwise the "t" axis.
+/// :param min_photons: The minimum total photon count of a pixel's decay. If
+///     "None", pixels are not gated by photon count.
+/// :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+///     decay. If "None", pixels are not gated by histogram quality.
+/// :param fill: The G and S value of pixels that fail a gate, default = NaN.
+/// :param out: An optional float64 (row, col, 2) output array to write the
+///     real and imaginary coordinates into, instead of allocating a new array.
+/// :return: A tuple of the real and imaginary coordinates as a 3-dimensional
+///     (row, col, ch) image, where G and S are indexed at 0 and 1 respectively
+///     on the channel axis, and the 2-dimensional validity mask where "true"
+///     pixels passed all gates. If "out" is given, "out" is returned as the
+///     coordinates image.
+#[pyfunction]
+#[pyo3(name = "gated_image")]
+#[pyo3(signature = (data, period, mask=None, harmonic=None, axis=None, axes=None, min_photons=None, min_quality=None, fill=None, out=None))]
+pub fn time_domain_gated_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    mask: Option<PyReadonlyArray2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    axes: Option<String>,
+    min_photons: Option<f64>,
+    min_quality: Option<f64>,
+    fill: Option<f64>,
+    out: Option<PyReadwriteArray3<'py, f64>>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<bool>>)> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    let gate = PhasorGate {
+        min_photons,
+        min_quality,
+        fill: fill.unwrap_or(f64::NAN),
+    };
+    // pattern match and extract allowed array types
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let axis = resolve_decay_axis(axis, axes.as_deref(), arr.shape())?;
+        match out {
+            // write the coordinates directly into "out"
+            Some(mut o) => {
+                let o_arr = o.as_array_mut();
+                let valid = py
+                    .allow_threads(|| {
+                        time_domain::gated_image_into(
+                            arr,
+                            period,
+                            m,
+                            harmonic,
+                            axis,
+                            Some(gate),
+                            o_arr,
+                        )
+                    })
+                    .map_err(map_array_error)?;
+                Ok(((**o).clone(), valid.into_pyarray(py)))
+            }
+            None => py
+                .allow_threads(|| {
+                    time_domain::gated_image(arr, period, m, harmonic, axis, Some(gate))
+                })
+                .map(|(gs, valid)| (gs.into_pyarray(py), valid.into_pyarray(py)))
+                .map_err(map_array_error),
+        }
+    })
+}
+
+/// Compute the histogram quality of a 1-dimensional decay curve.
+///
+/// This function computes the histogram quality of a decay curve as the
+/// fraction of time bins with counts greater than or equal to the bin
+/// threshold:
+///
+/// Q = |{i : I(tᵢ) ≥ threshold}| / n
+///
+/// :param data: I(t), the 1-dimensional decay curve.
+/// :param threshold: The minimum count of a populated time bin, default = 1.0.
+/// :return: The histogram quality, ranging between 0.0 and 1.0.
+#[pyfunction]
+#[pyo3(name = "histogram_quality")]
+#[pyo3(signature = (data, threshold=None))]
+pub fn time_domain_histogram_quality(data: Vec<f64>, threshold: Option<f64>) -> f64 {
+    time_domain::histogram_quality(&data, threshold)
+}
+
 /// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
 /// image.
 ///
@@ -228,69 +1073,264 @@ pub fn plot_map_mask<'py>(
 /// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
 /// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
 ///
+/// Pixels can optionally be gated by their total photon count and histogram
+/// quality. Pixels that fail either gate are set to NaN.
+///
 /// :param data: I(t), the decay data image.
 /// :param period: The period.
-/// :param harmonic: The harmonic value, default = 1.0.
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are computed. Pixels outside of the mask are set to 0.0.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of samples along "axis").
 /// :param axis: The decay or lifetime axis, default = 2.
+/// :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+///     the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+///     and "l" are the x, y, z, channel, time and lifetime axes. The decay
+///     axis is the "l" axis if present, otherwise the "t" axis.
+/// :param min_photons: The minimum total photon count of a pixel's decay. If
+///     "None", pixels are not gated by photon count.
+/// :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+///     decay. If "None", pixels are not gated by histogram quality.
+/// :param out: An optional float64 (row, col, 2) output array to write the
+///     real and imaginary coordinates into, instead of allocating a new array.
 /// :return: The real and imaginary coordinates as a 3-dimensional (row, col, ch)
 ///     image, where G and S are indexed at 0 and 1 respectively on the channel axis.
+///     If "out" is given, "out" is returned.
 #[pyfunction]
 #[pyo3(name = "image")]
-#[pyo3(signature = (data, period, mask=None, harmonic=None, axis=None))]
+#[pyo3(signature = (data, period, mask=None, harmonic=None, axis=None, axes=None, min_photons=None, min_quality=None, out=None))]
 pub fn time_domain_image<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     period: f64,
     mask: Option<PyReadonlyArray2<bool>>,
-    harmonic: Option<f64>,
+    harmonic: Option<u32>,
     axis: Option<usize>,
+    axes: Option<String>,
+    min_photons: Option<f64>,
+    min_quality: Option<f64>,
+    out: Option<PyReadwriteArray3<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    let gate = PhasorGate {
+        min_photons,
+        min_quality,
+        ..Default::default()
+    };
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let axis = resolve_decay_axis(axis, axes.as_deref(), arr.shape())?;
+        match out {
+            // write the coordinates directly into "out"
+            Some(mut o) => {
+                let o_arr = o.as_array_mut();
+                py.allow_threads(|| {
+                    time_domain::image_into(arr, period, m, harmonic, axis, Some(gate), o_arr)
+                })
+                .map_err(map_array_error)?;
+                Ok((**o).clone())
+            }
+            None => py
+                .allow_threads(|| time_domain::image(arr, period, m, harmonic, axis, Some(gate)))
                 .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
+                .map_err(map_array_error),
         }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
+    })
+}
+
+/// Compute the real and imaginary (G, S) coordinates of many 1-dimensional
+/// decay curves.
+///
+/// This function computes the phasor coordinates of each curve of a
+/// (n_curves, n_bins) matrix in parallel (e.g. the decays of thousands of
+/// ROIs or flow cytometry events), loading the waveform tables once. Curves
+/// without photons are NaN.
+///
+/// :param data: I(t), the 2-dimensional matrix of decay curves.
+/// :param period: The period.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of samples along "axis").
+/// :param axis: The decay or lifetime axis, default = 1.
+/// :return: The (n_curves, 2) coordinates, where G and S are columns 0 and 1
+///     respectively.
+#[pyfunction]
+#[pyo3(name = "curves")]
+#[pyo3(signature = (data, period, harmonic=None, axis=None))]
+pub fn time_domain_curves<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| time_domain::curves(arr, period, harmonic, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image at multiple harmonics.
+///
+/// This function computes the phasor image for each harmonic "n" in
+/// "harmonics" into a single stacked image:
+///
+/// Gₙ = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// Sₙ = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period.
+/// :param harmonics: The harmonic values. Each harmonic must be greater than 0
+///     and can not exceed the Nyquist limit (i.e. half the number of samples
+///     along "axis").
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are computed. Pixels outside of the mask are set to 0.0.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :param axes: An optional axis order string (e.g. "tyx" or "yxl") to infer
+///     the decay axis from instead of "axis", where "x", "y", "z", "c", "t"
+///     and "l" are the x, y, z, channel, time and lifetime axes. The decay
+///     axis is the "l" axis if present, otherwise the "t" axis.
+/// :return: The real and imaginary coordinates as a 4-dimensional
+///     (harmonic, row, col, ch) image, where the harmonic axis follows the
+///     order of "harmonics" and G and S are indexed at 0 and 1 respectively on
+///     the channel axis.
+#[pyfunction]
+#[pyo3(name = "image_multiharmonic")]
+#[pyo3(signature = (data, period, harmonics, mask=None, axis=None, axes=None))]
+pub fn time_domain_image_multiharmonic<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    harmonics: Vec<u32>,
+    mask: Option<PyReadonlyArray2<bool>>,
+    axis: Option<usize>,
+    axes: Option<String>,
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        let axis = resolve_decay_axis(axis, axes.as_deref(), arr.shape())?;
+        py.allow_threads(|| time_domain::image_multiharmonic(arr, period, &harmonics, m, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image sampled at non-uniform time points.
+///
+/// This function computes the phasor image for decays whose time bins are
+/// not uniform (e.g. TCSPC hardware with nonlinear time bins). Each bin is
+/// weighted by its width, from its time point to the next one (or to "period"
+/// for the last bin):
+///
+/// G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+/// S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period.
+/// :param time_points: The start time of each time bin, strictly increasing
+///     from 0.0 and below "period", with the length of the decay axis.
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are computed. Pixels outside of the mask are set to 0.0.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed half the number of time bins.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The real and imaginary coordinates as a 3-dimensional
+///     (row, col, ch) image, where G and S are indexed at 0 and 1
+///     respectively on the channel axis.
+#[pyfunction]
+#[pyo3(name = "image_with_time_points")]
+#[pyo3(signature = (data, period, time_points, mask=None, harmonic=None, axis=None))]
+pub fn time_domain_image_with_time_points<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    time_points: Vec<f64>,
+    mask: Option<PyReadonlyArray2<bool>>,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            time_domain::image_with_time_points(arr, period, &time_points, m, harmonic, axis)
+        })
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 4-dimensional decay
+/// volume.
+///
+/// This function computes the phasor coordinates of each voxel of a
+/// (z, row, col, bin) decay volume (e.g. a light-sheet FLIM volume). Before
+/// phasor computation, the decays can be spatially binned within each slice
+/// (see "bin_spatial") and summed over "z_radius" neighboring slices along z,
+/// binning volumes with a coarser axial sampling anisotropically.
+///
+/// :param data: I(t), the 4-dimensional (z, row, col, bin) decay volume.
+/// :param period: The period.
+/// :param mask: An optional 3-dimensional (z, row, col) boolean mask, only
+///     voxels set to "true" are computed. Voxels outside of the mask are set
+///     to 0.0. The mask must match the binned volume shape.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of time bins).
+/// :param binning: The spatial binning scheme within each slice, "block",
+///     "square" or "circular". If "None", the slices are not binned.
+/// :param factor: The block size of the "block" scheme, default = 2.
+/// :param radius: The kernel radius of the sliding schemes, default = 1.
+/// :param z_radius: The number of slices summed above and below each slice,
+///     default = 0.
+/// :return: The real and imaginary coordinates as a 4-dimensional
+///     (z, row, col, ch) volume, where G and S are indexed at 0 and 1
+///     respectively on the channel axis.
+#[pyfunction]
+#[pyo3(name = "volume")]
+#[pyo3(signature = (data, period, mask=None, harmonic=None, binning=None, factor=None, radius=None, z_radius=None))]
+pub fn time_domain_volume<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    mask: Option<PyReadonlyArray3<bool>>,
+    harmonic: Option<u32>,
+    binning: Option<String>,
+    factor: Option<usize>,
+    radius: Option<usize>,
+    z_radius: Option<usize>,
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    let binning = match binning.map(|b| b.to_lowercase()).as_deref() {
+        None => None,
+        Some("square") => Some(SpatialBinning::Square {
+            radius: radius.unwrap_or(1),
+        }),
+        Some("circular") => Some(SpatialBinning::Circular {
+            radius: radius.unwrap_or(1),
+        }),
+        Some("block") => Some(SpatialBinning::Block {
+            factor: factor.unwrap_or(2),
+        }),
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown binning, supported binnings are \"block\", \"square\", and \"circular\".",
+            ));
         }
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    };
+    let m = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArray4, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| time_domain::volume(arr, period, m, harmonic, binning, z_radius))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
 }
 
 /// Compute the imaginary (S) component of a 1-dimensional decay curve.
@@ -304,13 +1344,43 @@ pub fn time_domain_image<'py>(
 ///
 /// :param data: I(t), the 1-dimensional decay curve.
 /// :param period: The period.
-/// :param harmonic: The harmonic value, default = 1.0.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of samples in "data").
 /// :return: The imaginary component, S.
 #[pyfunction]
 #[pyo3(name = "imaginary")]
 #[pyo3(signature = (data, period, harmonic=None))]
-pub fn time_domain_imaginary(data: Vec<f64>, period: f64, harmonic: Option<f64>) -> f64 {
-    time_domain::imaginary(&data, period, harmonic)
+pub fn time_domain_imaginary(data: Vec<f64>, period: f64, harmonic: Option<u32>) -> PyResult<f64> {
+    time_domain::imaginary(&data, period, harmonic).map_err(map_array_error)
+}
+
+/// Compute the imaginary (S) component of a 1-dimensional decay curve sampled
+/// at non-uniform time points.
+///
+/// Each time bin is weighted by its width, from its time point to the next
+/// one (or to "period" for the last bin):
+///
+/// S = Σ(I(tᵢ) * sin(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+///
+/// :param data: I(t), the 1-dimensional decay curve.
+/// :param period: The period.
+/// :param time_points: The start time of each time bin, strictly increasing
+///     from 0.0 and below "period", with the length of "data".
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed half the number of samples in "data".
+/// :return: The imaginary component, S.
+#[pyfunction]
+#[pyo3(name = "imaginary_with_time_points")]
+#[pyo3(signature = (data, period, time_points, harmonic=None))]
+pub fn time_domain_imaginary_with_time_points(
+    data: Vec<f64>,
+    period: f64,
+    time_points: Vec<f64>,
+    harmonic: Option<u32>,
+) -> PyResult<f64> {
+    time_domain::imaginary_with_time_points(&data, period, &time_points, harmonic)
+        .map_err(map_array_error)
 }
 
 /// Compute the real (G) component of a 1-dimensional decay curve.
@@ -324,11 +1394,470 @@ pub fn time_domain_imaginary(data: Vec<f64>, period: f64, harmonic: Option<f64>)
 ///
 /// :param data: I(t), the 1-dimensional decay curve.
 /// :param period: The period.
-/// :param harmonic: The harmonic value, default = 1.0.
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed the Nyquist limit (i.e. half the
+///     number of samples in "data").
 /// :return: The real component, G.
 #[pyfunction]
 #[pyo3(name = "real")]
 #[pyo3(signature = (data, period, harmonic=None))]
-pub fn time_domain_real(data: Vec<f64>, period: f64, harmonic: Option<f64>) -> f64 {
-    time_domain::real(&data, period, harmonic)
+pub fn time_domain_real(data: Vec<f64>, period: f64, harmonic: Option<u32>) -> PyResult<f64> {
+    time_domain::real(&data, period, harmonic).map_err(map_array_error)
+}
+
+/// Compute the real (G) component of a 1-dimensional decay curve sampled at
+/// non-uniform time points.
+///
+/// Each time bin is weighted by its width, from its time point to the next
+/// one (or to "period" for the last bin):
+///
+/// G = Σ(I(tᵢ) * cos(nωtᵢ) * Δtᵢ) / Σ(I(tᵢ) * Δtᵢ)
+///
+/// :param data: I(t), the 1-dimensional decay curve.
+/// :param period: The period.
+/// :param time_points: The start time of each time bin, strictly increasing
+///     from 0.0 and below "period", with the length of "data".
+/// :param harmonic: The harmonic value, default = 1. The harmonic must be
+///     greater than 0 and can not exceed half the number of samples in "data".
+/// :return: The real component, G.
+#[pyfunction]
+#[pyo3(name = "real_with_time_points")]
+#[pyo3(signature = (data, period, time_points, harmonic=None))]
+pub fn time_domain_real_with_time_points(
+    data: Vec<f64>,
+    period: f64,
+    time_points: Vec<f64>,
+    harmonic: Option<u32>,
+) -> PyResult<f64> {
+    time_domain::real_with_time_points(&data, period, &time_points, harmonic)
+        .map_err(map_array_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of each frame in a
+/// 4-dimensional time-lapse decay stack.
+///
+/// This function computes the phasor image of each frame in a time-lapse decay
+/// stack with the shape (t, row, col, bin). The per-frame phasor images are
+/// stacked along the first axis.
+///
+/// :param data: I(t), the 4-dimensional (t, row, col, bin) time-lapse decay
+///     stack.
+/// :param period: The period.
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are computed. Pixels outside of the mask are set to 0.0.
+/// :param harmonic: The harmonic value, default = 1.
+/// :return: The per-frame real and imaginary coordinates as a 4-dimensional
+///     (t, row, col, ch) image, where G and S are indexed at 0 and 1
+///     respectively on the channel axis.
+#[pyfunction]
+#[pyo3(name = "image_series")]
+#[pyo3(signature = (data, period, mask=None, harmonic=None))]
+pub fn trajectory_image_series<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    mask: Option<PyReadonlyArray2<bool>>,
+    harmonic: Option<u32>,
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    let m = mask.as_ref().map(|m| m.as_array());
+    // pattern match and extract allowed array types
+    dispatch_array!(data, PyReadonlyArray4, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| trajectory::image_series(arr, period, m, harmonic))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the mean ROI phasor trajectory of a phasor time series.
+///
+/// This function computes the mean G and S coordinates of the pixels within a
+/// region of interest (ROI) for each frame of a 4-dimensional (t, row, col, ch)
+/// phasor time series. NaN pixels (e.g. gated pixels) are ignored.
+///
+/// :param data: The 4-dimensional (t, row, col, ch) phasor time series, where
+///     G and S are channels 0 and 1 respectively.
+/// :param roi: The 2-dimensional boolean ROI mask, where "true" pixels are in
+///     the ROI.
+/// :return: The mean G and S coordinates of the ROI for each frame.
+#[pyfunction]
+#[pyo3(name = "roi_trajectory")]
+pub fn trajectory_roi_trajectory(
+    data: PyReadonlyArray4<f64>,
+    roi: PyReadonlyArray2<bool>,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    trajectory::roi_trajectory(data.as_array(), roi.as_array()).map_err(map_array_error)
+}
+
+/// Compute the displacement, direction, and velocity statistics of a phasor
+/// trajectory.
+///
+/// This function computes statistics of a phasor trajectory, a sequence of
+/// (G, S) coordinates over time:
+///
+/// D = √((Gₙ - G₀)² + (Sₙ - S₀)²)
+/// θ = tan⁻¹((Sₙ - S₀) / (Gₙ - G₀))
+/// L = Σ √((Gᵢ₊₁ - Gᵢ)² + (Sᵢ₊₁ - Sᵢ)²)
+/// v = L / (n * Δt)
+///
+/// :param g: The G coordinates of the trajectory.
+/// :param s: The S coordinates of the trajectory.
+/// :param frame_interval: The time between frames, default = 1.0.
+/// :return: The net displacement, direction (radians), path length and mean
+///     velocity of the trajectory.
+#[pyfunction]
+#[pyo3(name = "statistics")]
+#[pyo3(signature = (g, s, frame_interval=None))]
+pub fn trajectory_statistics(
+    g: Vec<f64>,
+    s: Vec<f64>,
+    frame_interval: Option<f64>,
+) -> PyResult<(f64, f64, f64, f64)> {
+    trajectory::statistics(&g, &s, frame_interval).map_err(map_array_error)
+}
+
+/// Compute the per-pixel trajectory statistics of a phasor time series.
+///
+/// :param data: The 4-dimensional (t, row, col, ch) phasor time series, where
+///     G and S are channels 0 and 1 respectively.
+/// :param frame_interval: The time between frames, default = 1.0.
+/// :return: A 3-dimensional (row, col, ch) array with the net displacement,
+///     direction, path length and mean velocity at channels 0, 1, 2 and 3
+///     respectively.
+#[pyfunction]
+#[pyo3(name = "statistics_image")]
+#[pyo3(signature = (data, frame_interval=None))]
+pub fn trajectory_statistics_image<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray4<f64>,
+    frame_interval: Option<f64>,
+) -> Bound<'py, PyArray3<f64>> {
+    trajectory::statistics_image(data.as_array(), frame_interval).into_pyarray(py)
+}
+
+/// Estimate the background count per time bin of a 3-dimensional decay image.
+///
+/// This function estimates the constant background of each pixel's decay as
+/// the mean count of the lowest "fraction" of its time bins.
+///
+/// :param data: I(t), the decay data image.
+/// :param fraction: The fraction of lowest count bins averaged, default = 0.1.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The estimated background count per time bin of each pixel.
+#[pyfunction]
+#[pyo3(name = "estimated_background_image")]
+#[pyo3(signature = (data, fraction=None, axis=None))]
+pub fn time_domain_estimated_background_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    fraction: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        time_domain::estimated_background_image(arr.as_array(), fraction, axis)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the peak time bin image of a 3-dimensional decay image.
+///
+/// This function finds the time bin index of the maximum count of each pixel's
+/// decay. Ties resolve to the first bin.
+///
+/// :param data: I(t), the decay data image.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The peak time bin index of each pixel.
+#[pyfunction]
+#[pyo3(name = "peak_bin_image")]
+#[pyo3(signature = (data, axis=None))]
+pub fn time_domain_peak_bin_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| time_domain::peak_bin_image(
+        arr.as_array(),
+        axis
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Correct the pile-up distortion of a 3-dimensional TCSPC decay image.
+///
+/// This function applies the Coates correction to each pixel's decay:
+///
+/// N = Σ nᵢ * f / r
+/// nᵢ' = -N ln(1 - nᵢ / (N - Σⱼ₍ⱼ₌₀..ᵢ₋₁₎ nⱼ))
+///
+/// Where "nᵢ" are the counts of time bin "i", "f" is the excitation repetition
+/// rate, "r" is the detected count rate and "N" is the number of excitation
+/// cycles of the pixel. The first time bin must be the earliest after
+/// excitation. This function creates a new array and does not mutate the input
+/// array.
+///
+/// :param data: I(t), the decay data image.
+/// :param count_rate: The detected photon count rate while acquiring a pixel,
+///     in the unit of "repetition_rate".
+/// :param repetition_rate: The excitation (laser) repetition rate.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The pile-up corrected decay image.
+#[pyfunction]
+#[pyo3(name = "pileup_correct")]
+#[pyo3(signature = (data, count_rate, repetition_rate, axis=None))]
+pub fn time_domain_pileup_correct<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    count_rate: f64,
+    repetition_rate: f64,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| time_domain::pileup_correct(arr, count_rate, repetition_rate, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Correct the pile-up distortion of a 3-dimensional TCSPC decay image in
+/// place.
+///
+/// This function applies the Coates pile-up correction to each pixel's decay,
+/// see "pileup_correct". This function mutates the input data and does not
+/// create a new array.
+///
+/// :param data: I(t), the float64 decay data image.
+/// :param count_rate: The detected photon count rate while acquiring a pixel,
+///     in the unit of "repetition_rate".
+/// :param repetition_rate: The excitation (laser) repetition rate.
+/// :param axis: The decay or lifetime axis, default = 2.
+#[pyfunction]
+#[pyo3(name = "pileup_correct_mut")]
+#[pyo3(signature = (data, count_rate, repetition_rate, axis=None))]
+pub fn time_domain_pileup_correct_mut(
+    mut data: PyReadwriteArray3<f64>,
+    count_rate: f64,
+    repetition_rate: f64,
+    axis: Option<usize>,
+) -> PyResult<()> {
+    let arr = data.as_array_mut();
+    time_domain::pileup_correct_mut(arr, count_rate, repetition_rate, axis).map_err(map_array_error)
+}
+
+/// Compute the total photon count image of a 3-dimensional decay image.
+///
+/// :param data: I(t), the decay data image.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The sum of each pixel's decay.
+#[pyfunction]
+#[pyo3(name = "total_counts_image")]
+#[pyo3(signature = (data, axis=None))]
+pub fn time_domain_total_counts_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        time_domain::total_counts_image(arr.as_array(), axis)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Compute the histogram quality image of a 3-dimensional decay image.
+///
+/// This function computes the histogram quality of each pixel's decay with
+/// one of the following measures:
+///
+/// - "count": the fraction of time bins with counts greater than or equal to
+///   "threshold".
+/// - "fisher": a Fisher information proxy of the decay shape,
+///   F = Σ 2 (I(tᵢ₊₁) - I(tᵢ))² / (I(tᵢ₊₁) + I(tᵢ)).
+/// - "sbr": the signal to background photon ratio, with the background
+///   estimated from the lowest "fraction" of time bins.
+///
+/// :param data: I(t), the decay data image.
+/// :param metric: The quality measure, "count", "fisher" or "sbr", default =
+///     "count".
+/// :param threshold: The minimum count of a populated time bin of the "count"
+///     measure, default = 1.0.
+/// :param fraction: The fraction of lowest count bins used as background by
+///     the "sbr" measure, default = 0.1.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The histogram quality of each pixel.
+#[pyfunction]
+#[pyo3(name = "histogram_quality_image")]
+#[pyo3(signature = (data, metric=None, threshold=None, fraction=None, axis=None))]
+pub fn time_domain_histogram_quality_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    metric: Option<String>,
+    threshold: Option<f64>,
+    fraction: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let metric = match metric.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("count") => QualityMetric::CountThreshold {
+            threshold: threshold.unwrap_or(1.0),
+        },
+        Some("fisher") => QualityMetric::FisherInformation,
+        Some("sbr") => QualityMetric::SignalToBackground {
+            fraction: fraction.unwrap_or(0.1),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown metric, supported metrics are \"count\", \"fisher\", and \"sbr\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        time_domain::histogram_quality_image(arr.as_array(), &metric, axis)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Find the two component lifetimes and fraction images of a phasor image.
+///
+/// This function performs a graphical global analysis of a phasor image whose
+/// pixels are mixtures of the same two monoexponential components. A line is
+/// fitted through the phasor cloud by weighted total least squares and
+/// intersected with the universal semicircle, (G - 0.5)² + S² = 0.25. The
+/// intersections are the component phasors, whose lifetimes are
+/// τ = S / (ωG), and the fraction images of the components are computed for
+/// every pixel (see "fractions").
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param omega: The angular frequency.
+/// :param weights: An optional 2-dimensional weight image (e.g. photon
+///     counts). If "None", all pixels have the same weight.
+/// :param mask: An optional 2-dimensional boolean mask, only pixels set to
+///     "true" are used to fit the line.
+/// :param axis: The channel axis, default = 2.
+/// :return: A dictionary with the weighted "centroid" (G, S) and unit
+///     "direction" (G, S) of the line, the component "taus" (shortest first),
+///     their (G, S) "points" on the universal semicircle and the (row, col, ch)
+///     "fractions" image of the components.
+#[pyfunction]
+#[pyo3(name = "fit_line")]
+#[pyo3(signature = (data, omega, weights=None, mask=None, axis=None))]
+pub fn unmixing_fit_line<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<'py, f64>,
+    omega: f64,
+    weights: Option<PyReadonlyArray2<'py, f64>>,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let w = weights.as_ref().map(|w| w.as_array());
+    let m = mask.as_ref().map(|m| m.as_array());
+    let arr = data.as_array();
+    let fit = py
+        .allow_threads(|| unmixing::fit_line(arr, omega, w, m, axis))
+        .map_err(map_array_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("centroid", fit.centroid)?;
+    dict.set_item("direction", fit.direction)?;
+    dict.set_item("taus", fit.taus)?;
+    dict.set_item("points", fit.points)?;
+    dict.set_item("fractions", fit.fractions.into_pyarray(py))?;
+
+    Ok(dict)
+}
+
+/// Compute the fraction images of two known components of a phasor image.
+///
+/// This function projects each pixel's phasor onto the line through the
+/// phasors of two monoexponential components and computes the fraction of
+/// each component:
+///
+/// f₂ = ((P - P₁) · (P₂ - P₁)) / |P₂ - P₁|²
+/// f₁ = 1 - f₂
+///
+/// Fractions are not clamped and pixels with NaN coordinates remain NaN.
+///
+/// :param data: The 3-dimensional phasor image, where G and S are channels 0
+///     and 1 respectively.
+/// :param taus: The lifetimes of the two components.
+/// :param omega: The angular frequency.
+/// :param axis: The channel axis, default = 2.
+/// :return: The (row, col, ch) fraction image, where the fractions of the
+///     components of "taus" are channels 0 and 1 respectively.
+#[pyfunction]
+#[pyo3(name = "fractions")]
+#[pyo3(signature = (data, taus, omega, axis=None))]
+pub fn unmixing_fractions<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray3<'py, f64>,
+    taus: (f64, f64),
+    omega: f64,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let arr = data.as_array();
+    py.allow_threads(|| unmixing::fractions(arr, [taus.0, taus.1], omega, axis))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the calibrated and gated phasor images of a batch of decay
+/// datasets.
+///
+/// This function processes a list of 3-dimensional decay images (e.g. the
+/// files of an acquisition session) with shared settings: each dataset's
+/// phasor image is computed with photon count and histogram quality gating
+/// (see "time_domain.gated_image") and calibrated. The settings are validated
+/// once, and the waveform tables are computed once per decay length and
+/// reused by all datasets with the same number of time bins.
+///
+/// :param datasets: A list of decay images, I(t), all with the same dtype.
+/// :param period: The period (i.e. time interval).
+/// :param harmonic: The harmonic value, default = 1.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :param calibration: The (modulation, phase) calibration applied to the
+///     phasor images. If "None", the phasor images are not calibrated.
+/// :param min_photons: The minimum total photon count of a pixel's decay. If
+///     "None", pixels are not gated by photon count.
+/// :param min_quality: The minimum histogram quality (0.0 to 1.0) of a pixel's
+///     decay. If "None", pixels are not gated by histogram quality.
+/// :param fill: The G and S value of pixels that fail a gate, default = NaN.
+/// :return: A list with the (row, col, ch) phasor image and the validity mask
+///     of each dataset, in the order of "datasets".
+#[pyfunction]
+#[pyo3(name = "process")]
+#[pyo3(signature = (datasets, period, harmonic=None, axis=None, calibration=None, min_photons=None, min_quality=None, fill=None))]
+pub fn batch_process<'py>(
+    py: Python<'py>,
+    datasets: Bound<'py, PyAny>,
+    period: f64,
+    harmonic: Option<u32>,
+    axis: Option<usize>,
+    calibration: Option<(f64, f64)>,
+    min_photons: Option<f64>,
+    min_quality: Option<f64>,
+    fill: Option<f64>,
+) -> PyResult<Vec<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<bool>>)>> {
+    let settings = PhasorSettings {
+        harmonic,
+        axis,
+        calibration,
+        min_photons,
+        min_quality,
+        fill,
+        ..PhasorSettings::new(period)
+    };
+    // pattern match and extract allowed array types
+    dispatch_array!(datasets, Vec<PyReadonlyArray3>, |arrs| {
+        let views: Vec<_> = arrs.iter().map(|a| a.as_array()).collect();
+        py.allow_threads(|| batch::process(views, settings)?.collect::<Result<Vec<_>, _>>())
+            .map(|outputs| {
+                outputs
+                    .into_iter()
+                    .map(|(gs, valid)| (gs.into_pyarray(py), valid.into_pyarray(py)))
+                    .collect()
+            })
+            .map_err(map_array_error)
+    })
 }
@@ -0,0 +1,234 @@
+use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::registration::{self, BlendMode, ChromaticCorrection};
+
+/// Align the channels of a multi-channel 2-dimensional image.
+///
+/// This function corrects lateral chromatic shifts between channels. The
+/// translation of each channel relative to the reference channel is estimated
+/// with phase correlation and the channel is resampled with bilinear
+/// interpolation. Only translations are corrected.
+///
+/// :param data: The input 3-dimensional multi-channel image.
+/// :param reference: The index of the reference channel, default = 0.
+/// :param axis: The channel axis, default = 0.
+/// :return: The aligned image and the (row, col) shift applied to each channel
+///     with a shape of (channels, 2).
+#[pyfunction]
+#[pyo3(name = "align_channels")]
+#[pyo3(signature = (data, reference=None, axis=None))]
+pub fn registration_align_channels<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    reference: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<f64>>)> {
+    dispatch_array!(data, PyReadonlyArray3, |arr| registration::align_channels(
+        arr.as_array(),
+        reference,
+        axis
+    )
+    .map(|(a, s)| (a.into_pyarray(py), s.into_pyarray(py)))
+    .map_err(map_array_error))
+}
+
+/// Estimate the translation between two 2-dimensional images with phase
+/// correlation.
+///
+/// The peak of the inverse Fourier transform of the normalized cross-power
+/// spectrum is refined to subpixel precision with a parabola fit.
+///
+/// :param reference: The reference 2-dimensional image.
+/// :param moving: The moving 2-dimensional image, with the same shape and dtype
+///     as "reference".
+/// :return: The (row, col) shift to apply to "moving" to align it with
+///     "reference".
+#[pyfunction]
+#[pyo3(name = "phase_correlation")]
+pub fn registration_phase_correlation<'py>(
+    reference: Bound<'py, PyAny>,
+    moving: Bound<'py, PyAny>,
+) -> PyResult<(f64, f64)> {
+    dispatch_array!(reference, PyReadonlyArray2, |r: T| {
+        let m = moving.extract::<PyReadonlyArray2<T>>()?;
+        registration::phase_correlation(r.as_array(), m.as_array()).map_err(map_array_error)
+    })
+}
+
+/// Translate a 2-dimensional image by a subpixel shift.
+///
+/// The image is shifted with bilinear interpolation, such that
+/// output[r, c] = data[r - shift[0], c - shift[1]].
+///
+/// :param data: The input 2-dimensional image.
+/// :param shift: The (row, col) shift in pixels.
+/// :param fill: The value of pixels mapped from outside the image, default =
+///     0.0.
+/// :return: The translated image.
+#[pyfunction]
+#[pyo3(name = "translate")]
+#[pyo3(signature = (data, shift, fill=None))]
+pub fn registration_translate<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    shift: (f64, f64),
+    fill: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(registration::translate(
+        arr.as_array(),
+        shift,
+        fill
+    )
+    .into_pyarray(py)))
+}
+
+/// Stitch overlapping 2-dimensional tiles into a mosaic.
+///
+/// This function refines the nominal tile positions of a tiled acquisition and
+/// fuses the tiles into a single image. Pairwise offsets of overlapping tiles
+/// are estimated with phase correlation, refined by normalized
+/// cross-correlation and globally optimized by least squares, with the first
+/// tile fixed.
+///
+/// :param tiles: A list of 2-dimensional tiles with the same dtype.
+/// :param positions: The nominal (row, col) position of the top left corner of
+///     each tile in pixels.
+/// :param blend: The blending mode, "linear" or "feather", default = "feather".
+/// :param max_shift: The largest accepted correction of a pairwise offset in
+///     pixels, default = no limit.
+/// :return: The fused mosaic and the optimized tile positions with a shape of
+///     (tiles, 2), relative to the mosaic origin.
+#[pyfunction]
+#[pyo3(name = "stitch")]
+#[pyo3(signature = (tiles, positions, blend=None, max_shift=None))]
+pub fn registration_stitch<'py>(
+    py: Python<'py>,
+    tiles: Bound<'py, PyAny>,
+    positions: Vec<(f64, f64)>,
+    blend: Option<String>,
+    max_shift: Option<f64>,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>)> {
+    let blend = match blend.map(|b| b.to_lowercase()).as_deref() {
+        None | Some("feather") => BlendMode::Feather,
+        Some("linear") => BlendMode::Linear,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown blend mode, supported blend modes are \"linear\" and \"feather\".",
+            ));
+        }
+    };
+    dispatch_array!(tiles, Vec<PyReadonlyArray2>, |arrs| {
+        let views: Vec<_> = arrs.iter().map(|a| a.as_array()).collect();
+        registration::stitch(&views, &positions, Some(blend), max_shift)
+            .map(|(m, p)| (m.into_pyarray(py), p.into_pyarray(py)))
+            .map_err(map_array_error)
+    })
+}
+
+/// Measure the chromatic aberration of a multi-channel bead image.
+///
+/// This function localizes the beads of each channel of a multi-color bead
+/// image, pairs each reference bead with the nearest bead of every other
+/// channel within "max_distance" pixels, and fits a polynomial mapping from the
+/// reference to the channel coordinates by least squares, with the terms in
+/// the order 1, r, c, r², r·c, c², ... up to "order".
+///
+/// :param data: The 3-dimensional multi-channel bead image.
+/// :param radius: The bead detection and fit radius in pixels.
+/// :param order: The polynomial order, default = 1 (affine).
+/// :param reference: The index of the reference channel, default = 0.
+/// :param max_distance: The maximum distance between paired beads in pixels,
+///     default = radius.
+/// :param axis: The channel axis, default = 0.
+/// :return: A dictionary chromatic aberration model with the "order", the
+///     "reference" channel, the (row, col) polynomial "coefficients" of each
+///     channel, the root mean square "residual" of each channel in pixels and
+///     the number of paired beads "n_beads" of each channel.
+#[pyfunction]
+#[pyo3(name = "chromatic_correction")]
+#[pyo3(signature = (data, radius, order=None, reference=None, max_distance=None, axis=None))]
+pub fn registration_chromatic_correction<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: usize,
+    order: Option<usize>,
+    reference: Option<usize>,
+    max_distance: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let model = dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            registration::chromatic_correction(arr, radius, order, reference, max_distance, axis)
+        })
+        .map_err(map_array_error)
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("order", model.order)?;
+    dict.set_item("reference", model.reference)?;
+    dict.set_item("coefficients", model.coefficients)?;
+    dict.set_item("residual", model.residual)?;
+    dict.set_item("n_beads", model.n_beads)?;
+
+    Ok(dict)
+}
+
+/// Correct the chromatic aberration of a multi-channel image.
+///
+/// This function resamples each channel of a multi-channel image onto the
+/// reference channel coordinates with a chromatic aberration model (see
+/// "chromatic_correction") and bilinear interpolation. Pixels mapped from
+/// outside the image are 0.0.
+///
+/// :param data: The 3-dimensional multi-channel image.
+/// :param correction: The chromatic aberration model dictionary, with at
+///     least the "order" and "coefficients" entries.
+/// :param axis: The channel axis, default = 0.
+/// :return: The corrected image, with the shape of "data".
+#[pyfunction]
+#[pyo3(name = "apply_chromatic_correction")]
+#[pyo3(signature = (data, correction, axis=None))]
+pub fn registration_apply_chromatic_correction<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    correction: Bound<'py, PyDict>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let entry = |key: &str| {
+        correction.get_item(key)?.ok_or_else(|| {
+            PyErr::new::<PyKeyError, _>(format!("Missing correction entry \"{}\".", key))
+        })
+    };
+    let coefficients: Vec<(Vec<f64>, Vec<f64>)> = entry("coefficients")?.extract()?;
+    let n = coefficients.len();
+    let model = ChromaticCorrection {
+        order: entry("order")?.extract()?,
+        reference: correction
+            .get_item("reference")?
+            .map_or(Ok(0), |v| v.extract())?,
+        coefficients,
+        residual: vec![f64::NAN; n],
+        n_beads: vec![0; n],
+    };
+    let n_terms = (model.order + 1) * (model.order + 2) / 2;
+    if model
+        .coefficients
+        .iter()
+        .any(|(r, c)| r.len() != n_terms || c.len() != n_terms)
+    {
+        return Err(PyErr::new::<PyValueError, _>(
+            "The number of coefficients does not match the polynomial order.",
+        ));
+    }
+    dispatch_array!(data, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| registration::apply_chromatic_correction(arr, &model, axis))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
@@ -0,0 +1,472 @@
+use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::segmentation::{self, Connectivity, RegionProperties};
+
+/// Segment a 2-dimensional image with the random walker algorithm.
+///
+/// This function segments an image from a set of labeled seed pixels. Each
+/// unlabeled pixel is assigned the probability that a random walker starting
+/// at that pixel first reaches a seed of each label, where walkers rarely
+/// cross strong image edges.
+///
+/// :param data: The input 2-dimensional image.
+/// :param seeds: The 2-dimensional seed label image, where unlabeled pixels are
+///     0 and seeds are labeled 1 to n_labels.
+/// :param beta: The edge weight penalty, default = 130.0.
+/// :param tolerance: The relative residual tolerance of the conjugate gradient
+///     solver, default = 1e-6.
+/// :param max_iterations: The maximum number of conjugate gradient iterations,
+///     default = 1000.
+/// :return: The label probabilities with a shape of (n_labels, row, col) and
+///     the label image.
+#[pyfunction]
+#[pyo3(name = "random_walker")]
+#[pyo3(signature = (data, seeds, beta=None, tolerance=None, max_iterations=None))]
+pub fn segmentation_random_walker<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    seeds: PyReadonlyArray2<usize>,
+    beta: Option<f64>,
+    tolerance: Option<f64>,
+    max_iterations: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray3<f64>>, Bound<'py, PyArray2<usize>>)> {
+    let seeds = seeds.as_array();
+    dispatch_array!(data, PyReadonlyArray2, |arr| segmentation::random_walker(
+        arr.as_array(),
+        seeds,
+        beta,
+        tolerance,
+        max_iterations
+    )
+    .map(|(p, l)| (p.into_pyarray(py), l.into_pyarray(py)))
+    .map_err(map_array_error))
+}
+
+/// Segment a 2-dimensional image with the Felzenszwalb graph-based algorithm.
+///
+/// This function over-segments an image into regions of similar intensity
+/// (i.e. unsupervised region proposals), where intensities are normalized to
+/// the range 0.0 to 1.0.
+///
+/// :param data: The input 2-dimensional image.
+/// :param scale: The scale, larger values produce larger regions, default =
+///     1.0.
+/// :param sigma: The standard deviation of the Gaussian smoothing applied
+///     before segmentation, default = 0.8.
+/// :param min_size: The minimum region size in pixels, default = 20.
+/// :return: The label image, where regions are labeled from 1 in raster order.
+#[pyfunction]
+#[pyo3(name = "felzenszwalb")]
+#[pyo3(signature = (data, scale=None, sigma=None, min_size=None))]
+pub fn segmentation_felzenszwalb<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    scale: Option<f64>,
+    sigma: Option<f64>,
+    min_size: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| segmentation::felzenszwalb(
+        arr.as_array(),
+        scale,
+        sigma,
+        min_size
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Label the connected components of a 2-dimensional boolean mask.
+///
+/// This function labels each connected region of "true" pixels with a unique
+/// integer. Labels are consecutive, starting at 1 in raster order of each
+/// region's first pixel, while background pixels are 0.
+///
+/// :param mask: The 2-dimensional boolean mask.
+/// :param connectivity: The pixel connectivity, "4" (pixels sharing an edge)
+///     or "8" (pixels sharing an edge or a corner), default = "8".
+/// :return: A tuple of the label image and the number of labels.
+#[pyfunction]
+#[pyo3(name = "label")]
+#[pyo3(signature = (mask, connectivity=None))]
+pub fn segmentation_label<'py>(
+    py: Python<'py>,
+    mask: PyReadonlyArray2<'py, bool>,
+    connectivity: Option<String>,
+) -> PyResult<(Bound<'py, PyArray2<usize>>, usize)> {
+    let connectivity = match connectivity.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("8") | Some("eight") => Connectivity::Eight,
+        Some("4") | Some("four") => Connectivity::Four,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown connectivity, supported connectivities are \"4\" and \"8\".",
+            ));
+        }
+    };
+    let (labels, count) = segmentation::label(mask.as_array(), Some(connectivity));
+
+    Ok((labels.into_pyarray(py), count))
+}
+
+/// Measure the labeled regions of a 2-dimensional label image.
+///
+/// This function measures the area, centroid, bounding box and equivalent
+/// diameter of each labeled region. With a pixel size, areas and lengths are
+/// reported in physical units (e.g. µm² and µm).
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param spacing: The (row, col) pixel size, default = (1.0, 1.0) (pixels).
+/// :return: A list with a dictionary of properties for each region present in
+///     "labels", sorted by label, with the "label", "pixel_count", "area",
+///     "centroid" (row, col), "bbox" (min row, min col, max row, max col) with
+///     exclusive max bounds, and "equivalent_diameter" keys.
+#[pyfunction]
+#[pyo3(name = "region_properties")]
+#[pyo3(signature = (labels, spacing=None))]
+pub fn segmentation_region_properties<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    spacing: Option<(f64, f64)>,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let props = segmentation::region_properties(labels.as_array(), spacing.map(|(r, c)| [r, c]))
+        .map_err(map_array_error)?;
+    props.into_iter().map(|p| region_dict(py, p)).collect()
+}
+
+/// Segment a 2-dimensional image by marker-controlled watershed.
+///
+/// This function floods the "surface" from the labeled "markers" in order of
+/// increasing surface value, so each pixel receives the label of the marker
+/// whose basin reaches it first. A typical use separates touching objects by
+/// flooding the negated distance transform of their mask from its maxima.
+///
+/// :param surface: The 2-dimensional surface to flood.
+/// :param markers: The 2-dimensional marker image with the same shape as
+///     "surface", where 0 is unlabeled.
+/// :param mask: The 2-dimensional boolean mask of the pixels to flood. If
+///     "None", all pixels are flooded.
+/// :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+/// :return: The label image, pixels unreachable from a marker are 0.
+#[pyfunction]
+#[pyo3(name = "watershed")]
+#[pyo3(signature = (surface, markers, mask=None, connectivity=None))]
+pub fn segmentation_watershed<'py>(
+    py: Python<'py>,
+    surface: Bound<'py, PyAny>,
+    markers: PyReadonlyArray2<'py, usize>,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    connectivity: Option<String>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    let connectivity = match connectivity.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("8") | Some("eight") => Connectivity::Eight,
+        Some("4") | Some("four") => Connectivity::Four,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown connectivity, supported connectivities are \"4\" and \"8\".",
+            ));
+        }
+    };
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(surface, PyReadonlyArray2, |arr| {
+        segmentation::watershed(arr.as_array(), markers.as_array(), mask, Some(connectivity))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Cluster the per-pixel feature vectors of a 2-dimensional image into "k"
+/// classes.
+///
+/// This function segments an image by k-means clustering of the feature
+/// vector of each pixel, assembled as a 3-dimensional stack of feature
+/// channels (e.g. intensity, phasor G and S coordinates and texture features).
+/// The features are standardized by default, centroids are initialized with
+/// k-means++ and refined with Lloyd's algorithm. Pixels with a NaN feature are
+/// not clustered.
+///
+/// :param features: The 3-dimensional stack of feature channels.
+/// :param k: The number of classes.
+/// :param max_iterations: The maximum number of iterations, default = 100.
+/// :param standardize: Whether to standardize each feature before
+///     clustering, default = True.
+/// :param seed: Pseudorandom number generator seed. If "None", a random seed
+///     is drawn.
+/// :param axis: The feature axis, default = 2.
+/// :return: A tuple of the label map, with the classes labeled from 1 to "k"
+///     in order of increasing first feature centroid and 0 for pixels with a
+///     NaN feature, and the list of class centroids in feature units.
+#[pyfunction]
+#[pyo3(name = "kmeans_features")]
+#[pyo3(signature = (features, k, max_iterations=None, standardize=None, seed=None, axis=None))]
+pub fn segmentation_kmeans_features<'py>(
+    py: Python<'py>,
+    features: Bound<'py, PyAny>,
+    k: usize,
+    max_iterations: Option<usize>,
+    standardize: Option<bool>,
+    seed: Option<u64>,
+    axis: Option<usize>,
+) -> PyResult<(Bound<'py, PyArray2<usize>>, Vec<Vec<f64>>)> {
+    dispatch_array!(features, PyReadonlyArray3, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| {
+            segmentation::kmeans_features(arr, k, max_iterations, standardize, seed, axis)
+        })
+        .map(|(labels, centroids)| (labels.into_pyarray(py), centroids))
+        .map_err(map_array_error)
+    })
+}
+
+/// Relabel a 2-dimensional label image with consecutive labels.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :return: A tuple of the relabeled image, with consecutive labels from 1 in
+///     the order of the original labels, and the number of labels.
+#[pyfunction]
+#[pyo3(name = "relabel_sequential")]
+pub fn segmentation_relabel_sequential<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+) -> (Bound<'py, PyArray2<usize>>, usize) {
+    let (output, count) = segmentation::relabel_sequential(labels.as_array());
+
+    (output.into_pyarray(py), count)
+}
+
+/// Merge labels of a 2-dimensional label image.
+///
+/// This function merges each (a, b) pair of labels into a single region.
+/// Chains of pairs merge into one region, which takes the smallest label of
+/// the chain. Labels are not renumbered, see "relabel_sequential".
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param pairs: The list of (a, b) label pairs to merge.
+/// :return: The merged label image.
+#[pyfunction]
+#[pyo3(name = "merge_labels")]
+pub fn segmentation_merge_labels<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    pairs: Vec<(usize, usize)>,
+) -> Bound<'py, PyArray2<usize>> {
+    segmentation::merge_labels(labels.as_array(), &pairs).into_pyarray(py)
+}
+
+/// Merge the adjacent regions of a 2-dimensional label image that satisfy a
+/// predicate.
+///
+/// This function finds the pairs of touching labels, measures their regions
+/// and merges the pairs for which "predicate" returns True. The predicate is
+/// called with the region property dictionaries (see "region_properties") of
+/// both regions, before any merge.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param predicate: The merge predicate, a callable of two region property
+///     dictionaries returning a bool.
+/// :param connectivity: The pixel connectivity of adjacency, "4" or "8",
+///     default = "8".
+/// :param spacing: The (row, col) pixel size of the region measurements,
+///     default = (1.0, 1.0) (pixels).
+/// :return: The merged label image.
+#[pyfunction]
+#[pyo3(name = "merge_adjacent")]
+#[pyo3(signature = (labels, predicate, connectivity=None, spacing=None))]
+pub fn segmentation_merge_adjacent<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    predicate: Bound<'py, PyAny>,
+    connectivity: Option<String>,
+    spacing: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    let connectivity = match connectivity.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("8") | Some("eight") => Connectivity::Eight,
+        Some("4") | Some("four") => Connectivity::Four,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown connectivity, supported connectivities are \"4\" and \"8\".",
+            ));
+        }
+    };
+    // keep the first Python error raised by the predicate
+    let mut error: Option<PyErr> = None;
+    let output = segmentation::merge_adjacent(
+        labels.as_array(),
+        |a, b| {
+            if error.is_some() {
+                return false;
+            }
+            let result = region_dict(py, a.clone())
+                .and_then(|da| Ok((da, region_dict(py, b.clone())?)))
+                .and_then(|(da, db)| predicate.call1((da, db))?.is_truthy());
+            result.unwrap_or_else(|e| {
+                error = Some(e);
+                false
+            })
+        },
+        Some(connectivity),
+        spacing.map(|(r, c)| [r, c]),
+    )
+    .map_err(map_array_error)?;
+    match error {
+        Some(e) => Err(e),
+        None => Ok(output.into_pyarray(py)),
+    }
+}
+
+/// Find the pairs of touching labels of a 2-dimensional label image.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+/// :return: The sorted list of (a, b) pairs of adjacent labels, with a < b.
+#[pyfunction]
+#[pyo3(name = "label_adjacency")]
+#[pyo3(signature = (labels, connectivity=None))]
+pub fn segmentation_label_adjacency(
+    labels: PyReadonlyArray2<usize>,
+    connectivity: Option<String>,
+) -> PyResult<Vec<(usize, usize)>> {
+    let connectivity = match connectivity.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("8") | Some("eight") => Connectivity::Eight,
+        Some("4") | Some("four") => Connectivity::Four,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown connectivity, supported connectivities are \"4\" and \"8\".",
+            ));
+        }
+    };
+
+    Ok(segmentation::label_adjacency(
+        labels.as_array(),
+        Some(connectivity),
+    ))
+}
+
+/// Extract the boundaries of the regions of a 2-dimensional label image.
+///
+/// This function marks the labeled pixels that have a neighbor with a
+/// different label, including the background, as boundary pixels. The image
+/// edges are not boundaries.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param connectivity: The pixel connectivity, "4" or "8", default = "8".
+/// :return: The boundary mask.
+#[pyfunction]
+#[pyo3(name = "label_boundaries")]
+#[pyo3(signature = (labels, connectivity=None))]
+pub fn segmentation_label_boundaries<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    connectivity: Option<String>,
+) -> PyResult<Bound<'py, PyArray2<bool>>> {
+    let connectivity = match connectivity.map(|c| c.to_lowercase()).as_deref() {
+        None | Some("8") | Some("eight") => Connectivity::Eight,
+        Some("4") | Some("four") => Connectivity::Four,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown connectivity, supported connectivities are \"4\" and \"8\".",
+            ));
+        }
+    };
+
+    Ok(segmentation::label_boundaries(labels.as_array(), Some(connectivity)).into_pyarray(py))
+}
+
+/// Expand the regions of a 2-dimensional label image into the background.
+///
+/// This function assigns each background pixel within "distance" pixels of a
+/// region to its nearest region, so expanding regions stop where they meet
+/// and never overwrite each other.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param distance: The expansion distance in pixels.
+/// :return: The expanded label image.
+#[pyfunction]
+#[pyo3(name = "expand_labels")]
+pub fn segmentation_expand_labels<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    distance: usize,
+) -> Bound<'py, PyArray2<usize>> {
+    segmentation::expand_labels(labels.as_array(), distance).into_pyarray(py)
+}
+
+/// Shrink the regions of a 2-dimensional label image.
+///
+/// This function removes the labeled pixels within "distance" pixels of a
+/// pixel with a different label, including the background.
+///
+/// :param labels: The 2-dimensional label image, where 0 is background.
+/// :param distance: The shrinking distance in pixels.
+/// :return: The shrunk label image.
+#[pyfunction]
+#[pyo3(name = "shrink_labels")]
+pub fn segmentation_shrink_labels<'py>(
+    py: Python<'py>,
+    labels: PyReadonlyArray2<'py, usize>,
+    distance: usize,
+) -> Bound<'py, PyArray2<usize>> {
+    segmentation::shrink_labels(labels.as_array(), distance).into_pyarray(py)
+}
+
+/// Expand seed regions (e.g. nuclei) into cells constrained by an intensity
+/// barrier (e.g. a membrane channel).
+///
+/// This function grows each seed region along the shortest paths of an image
+/// manifold, where the cost of a step from pixel p to q is
+/// √(λ·|p - q|² + (I(q) - I(p))²) with the barrier image I rescaled to 0.0 to
+/// 1.0, so regions stop at bright barriers. A large "regularization" tends to a
+/// Voronoi partition of the seeds (see "expand_labels").
+///
+/// :param seeds: The 2-dimensional seed label image, where 0 is unlabeled.
+/// :param barrier: The 2-dimensional barrier image with the same shape as
+///     "seeds".
+/// :param regularization: The spatial distance weight λ, default = 0.05.
+/// :param distance: The maximum expansion path length in pixels. If "None",
+///     the expansion is not limited.
+/// :param mask: The 2-dimensional boolean mask of the pixels to expand into.
+///     If "None", all pixels are expanded into.
+/// :return: The expanded label image, pixels unreachable from a seed are 0.
+#[pyfunction]
+#[pyo3(name = "propagate_labels")]
+#[pyo3(signature = (seeds, barrier, regularization=None, distance=None, mask=None))]
+pub fn segmentation_propagate_labels<'py>(
+    py: Python<'py>,
+    seeds: PyReadonlyArray2<'py, usize>,
+    barrier: Bound<'py, PyAny>,
+    regularization: Option<f64>,
+    distance: Option<f64>,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    let mask = mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(barrier, PyReadonlyArray2, |arr| {
+        segmentation::propagate_labels(
+            seeds.as_array(),
+            arr.as_array(),
+            regularization,
+            distance,
+            mask,
+        )
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+    })
+}
+
+/// Convert region properties into a Python dictionary.
+pub(crate) fn region_dict<'py>(
+    py: Python<'py>,
+    p: RegionProperties,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("label", p.label)?;
+    dict.set_item("pixel_count", p.pixel_count)?;
+    dict.set_item("area", p.area)?;
+    dict.set_item("centroid", p.centroid)?;
+    dict.set_item("bbox", p.bbox)?;
+    dict.set_item("equivalent_diameter", p.equivalent_diameter)?;
+
+    Ok(dict)
+}
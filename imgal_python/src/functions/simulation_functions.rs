@@ -1,9 +1,10 @@
 use numpy::{
-    IntoPyArray, PyArray1, PyArray3, PyReadonlyArray1, PyReadonlyArray3, PyReadwriteArray1,
-    PyReadwriteArray3,
+    IntoPyArray, PyArray1, PyArray2, PyArray3, PyReadonlyArray1, PyReadonlyArray2,
+    PyReadonlyArray3, PyReadwriteArray1, PyReadwriteArray2, PyReadwriteArray3,
 };
 use pyo3::prelude::*;
 
+use crate::dispatch_array;
 use crate::error::map_array_error;
 use imgal::simulation;
 
@@ -290,6 +291,40 @@ pub fn decay_irf_exponential_3d(
     .map_err(map_array_error)
 }
 
+/// Estimate the instrument response function (IRF) from a measured
+/// monoexponential reference decay.
+///
+/// This function recovers an effective IRF from the decay of a reference
+/// sample with a known monoexponential lifetime by Tikhonov regularized
+/// deconvolution in the frequency domain:
+///
+/// IRF = F⁻¹[ F(D) F(E)* / (|F(E)|² + λ max|F(E)|²) ]
+///
+/// Where "D" is the measured decay, "E" is the ideal decay of lifetime "tau"
+/// and "λ" is the regularization weight. Negative values of the recovered IRF
+/// are clipped to 0.0 and the IRF is normalized to sum to 1.0.
+///
+/// :param data: The measured 1-dimensional reference decay.
+/// :param period: The period (i.e. time interval).
+/// :param tau: The known lifetime of the reference decay.
+/// :param regularization: The relative Tikhonov regularization weight,
+///     default = 1e-3.
+/// :return: The normalized IRF with the same length as "data".
+#[pyfunction]
+#[pyo3(name = "estimate_irf")]
+#[pyo3(signature = (data, period, tau, regularization=None))]
+pub fn instrument_estimate_irf(
+    py: Python,
+    data: Vec<f64>,
+    period: f64,
+    tau: f64,
+    regularization: Option<f64>,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    simulation::instrument::estimate_irf(&data, period, tau, regularization)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
 /// Simulate a 1-dimensional Gaussian instruement response function (IRF).
 ///
 /// This function creates a Gaussian IRF by converting "full width at half maximum"
@@ -328,9 +363,8 @@ pub fn instrument_gaussian_irf_1d(
 ///
 /// :param data: The input 1-dimensional array.
 /// :param scale: The scale factor.
-/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
-///     homogenous noise to the input array. If "None", then heterogenous noise
-///     is applied to the input array.
+/// :param seed: Pseudorandom number generator seed. Set the "seed" value for
+///     reproducible noise. If "None", a random seed is drawn.
 /// :return: A 1-dimensonal array of the input data with Poisson noise applied.
 #[pyfunction]
 #[pyo3(name = "poisson_1d")]
@@ -342,23 +376,10 @@ pub fn noise_poisson_1d<'py>(
     seed: Option<u64>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray1<u8>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<u16>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<f32>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<f64>>() {
+    dispatch_array!(data, PyReadonlyArray1, |arr| {
         let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        Ok(output.into_pyarray(py))
+    })
 }
 
 /// Simulate Poisson noise on a 1-dimensional array.
@@ -371,9 +392,8 @@ pub fn noise_poisson_1d<'py>(
 ///
 /// :param data: The input 1-dimensonal array to mutate.
 /// :param scale: The scale factor.
-/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
-///     homogenous noise to the input array. If "None", then heterogenous noise
-///     is applied to the input array.
+/// :param seed: Pseudorandom number generator seed. Set the "seed" value for
+///     reproducible noise. If "None", a random seed is drawn.
 #[pyfunction]
 #[pyo3(name = "poisson_1d_mut")]
 #[pyo3(signature= (data, scale, seed=None))]
@@ -383,6 +403,112 @@ pub fn noise_poisson_1d_mut(mut data: PyReadwriteArray1<f64>, scale: f64, seed:
     simulation::noise::poisson_1d_mut(d, scale, seed);
 }
 
+/// Simulate Poisson noise on a 2-dimensional array.
+///
+/// This function applies Poisson noise (i.e. shot noise) on a 2-dimensional
+/// image. An element-wise lambda value (scaled by the "scale" parameter) is
+/// used to simulate Poisson noise with variable signal strength.
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// :param data: The input 2-dimensional array.
+/// :param scale: The scale factor.
+/// :param seed: Pseudorandom number generator master seed. Each row draws from
+///     its own random stream derived from the master seed and the row index.
+///     Set the "seed" value for reproducible noise. If "None", a random master
+///     seed is drawn.
+/// :return: A 2-dimensional array of the input data with Poisson noise
+///     applied.
+#[pyfunction]
+#[pyo3(name = "poisson_2d")]
+#[pyo3(signature = (data, scale, seed=None))]
+pub fn noise_poisson_2d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    scale: f64,
+    seed: Option<u64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(
+        simulation::noise::poisson_2d(arr.as_array(), scale, seed).into_pyarray(py)
+    ))
+}
+
+/// Simulate Poisson noise on a 2-dimensional array.
+///
+/// This function applies Poisson noise (i.e. shot noise) on a 2-dimensional
+/// image. An element-wise lambda value (scaled by the "scale" parameter) is
+/// used to simulate Poisson noise with variable signal strength.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// :param data: The input 2-dimensional array to mutate.
+/// :param scale: The scale factor.
+/// :param seed: Pseudorandom number generator master seed. Set the "seed" value
+///     for reproducible noise. If "None", a random master seed is drawn.
+#[pyfunction]
+#[pyo3(name = "poisson_2d_mut")]
+#[pyo3(signature = (data, scale, seed=None))]
+pub fn noise_poisson_2d_mut(mut data: PyReadwriteArray2<f64>, scale: f64, seed: Option<u64>) {
+    let arr = data.as_array_mut();
+    simulation::noise::poisson_2d_mut(arr, scale, seed);
+}
+
+/// Simulate additive Gaussian noise on a 2-dimensional array.
+///
+/// This function adds zero mean Gaussian noise (e.g. detector read noise) with
+/// standard deviation "sigma" to each element of a 2-dimensional image.
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// :param data: The input 2-dimensional array.
+/// :param sigma: The standard deviation of the noise. Must be finite and
+///     non-negative.
+/// :param seed: Pseudorandom number generator master seed. Set the "seed" value
+///     for reproducible noise. If "None", a random master seed is drawn.
+/// :return: A 2-dimensional array of the input data with Gaussian noise
+///     applied.
+#[pyfunction]
+#[pyo3(name = "gaussian_2d")]
+#[pyo3(signature = (data, sigma, seed=None))]
+pub fn noise_gaussian_2d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: f64,
+    seed: Option<u64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_array!(data, PyReadonlyArray2, |arr| {
+        simulation::noise::gaussian_2d(arr.as_array(), sigma, seed)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
+}
+
+/// Simulate additive Gaussian noise on a 2-dimensional array.
+///
+/// This function adds zero mean Gaussian noise with standard deviation "sigma"
+/// to each element of a 2-dimensional image.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// :param data: The input 2-dimensional array to mutate.
+/// :param sigma: The standard deviation of the noise. Must be finite and
+///     non-negative.
+/// :param seed: Pseudorandom number generator master seed. Set the "seed" value
+///     for reproducible noise. If "None", a random master seed is drawn.
+#[pyfunction]
+#[pyo3(name = "gaussian_2d_mut")]
+#[pyo3(signature = (data, sigma, seed=None))]
+pub fn noise_gaussian_2d_mut(
+    mut data: PyReadwriteArray2<f64>,
+    sigma: f64,
+    seed: Option<u64>,
+) -> PyResult<()> {
+    let arr = data.as_array_mut();
+    simulation::noise::gaussian_2d_mut(arr, sigma, seed).map_err(map_array_error)
+}
+
 /// Simulate Poisson noise on a 3-dimensional array.
 ///
 /// This function applies Poisson noise (i.e. shot noise) on a 3-dimensional
@@ -394,9 +520,10 @@ pub fn noise_poisson_1d_mut(mut data: PyReadwriteArray1<f64>, scale: f64, seed:
 ///
 /// :param data: The input 3-dimensional array.
 /// :param scale: The scale factor.
-/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
-///     homogenous noise to the input array. If "None", then heterogenous noise
-///     is applied to the input array.
+/// :param seed: Pseudorandom number generator master seed. Each lane along
+///     "axis" draws from its own random stream derived from the master seed
+///     and the lane index. Set the "seed" value for reproducible noise. If
+///     "None", a random master seed is drawn.
 /// :param axis: The signal data axis, default = 2.
 /// :return: A 3-dimensional array of the input data with Poisson noise
 ///     applied.
@@ -411,27 +538,14 @@ pub fn noise_poisson_3d<'py>(
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_array!(data, PyReadonlyArray3, |arr| simulation::noise::poisson_3d(
+        arr.as_array(),
+        scale,
+        seed,
+        axis
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
 }
 
 /// Simulate Poisson noise on a 3-dimensional array.
@@ -444,9 +558,10 @@ pub fn noise_poisson_3d<'py>(
 ///
 /// :param data: The input 3-dimensional array to mutate.
 /// :param scale: The scale factor.
-/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
-///     homogenous noise to the input array. If "None", then heterogenous noise
-///     is applied to the input array.
+/// :param seed: Pseudorandom number generator master seed. Each lane along
+///     "axis" draws from its own random stream derived from the master seed
+///     and the lane index. Set the "seed" value for reproducible noise. If
+///     "None", a random master seed is drawn.
 /// :param axis: The signal data axis, default = 2.
 #[pyfunction]
 #[pyo3(name = "poisson_3d_mut")]
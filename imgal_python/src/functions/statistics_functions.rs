@@ -1,9 +1,15 @@
-use numpy::{PyReadonlyArrayDyn, PyReadwriteArray1};
-use pyo3::exceptions::PyTypeError;
+use numpy::{
+    IntoPyArray, PyArray2, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3, PyReadonlyArrayDyn,
+    PyReadwriteArray1,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use crate::dispatch_array;
 use crate::error::map_array_error;
+use crate::utils::parse_summation;
 use imgal::statistics;
+use imgal::statistics::{FocusMetric, PAdjustMethod, RankMethod, Reduction, Tail};
 
 /// Compute the effective sample size (ESS) of a weighted sample set.
 ///
@@ -32,23 +38,9 @@ pub fn statistics_effective_sample_size(weights: Vec<f64>) -> f64 {
 #[pyfunction]
 #[pyo3(name = "max")]
 pub fn statistics_max<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| Ok(
+        statistics::max(arr.as_array()) as f64
+    ))
 }
 
 /// Find the minimum value in an n-dimensional array.
@@ -61,23 +53,9 @@ pub fn statistics_max<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
 #[pyfunction]
 #[pyo3(name = "min")]
 pub fn statistics_min<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| Ok(
+        statistics::min(arr.as_array()) as f64
+    ))
 }
 /// Find the minimum and maximum values in an n-dimensional array.
 ///
@@ -91,33 +69,24 @@ pub fn statistics_min<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
 #[pyfunction]
 #[pyo3(name = "min_max")]
 pub fn statistics_min_max<'py>(data: Bound<'py, PyAny>) -> PyResult<(f64, f64)> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
         let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        Ok((mm.0 as f64, mm.1 as f64))
+    })
 }
 
 /// Compute the sum of a sequence of numbers.
 ///
 /// :param data: The sequence of numbers.
+/// :param summation: The summation algorithm, "naive", "pairwise" or "kahan",
+///     default = "naive".
 /// :return: The sum.
 #[pyfunction]
 #[pyo3(name = "sum")]
-pub fn statistics_sum(data: Vec<f64>) -> f64 {
-    statistics::sum(&data)
+#[pyo3(signature = (data, summation=None))]
+pub fn statistics_sum(data: Vec<f64>, summation: Option<&str>) -> PyResult<f64> {
+    let summation = parse_summation(summation)?;
+    Ok(statistics::compensated_sum(&data, summation))
 }
 
 /// Compute the weighted Kendall's Tau-b rank correlation coefficient.
@@ -135,9 +104,9 @@ pub fn statistics_sum(data: Vec<f64>) -> f64 {
 /// Where:
 /// - `C` = number of weighted concordant pairs
 /// - `D` = number of weighted discordant pairs
-/// - `n₀` = total weighted pairs = `(Σwᵢ)² - Σwᵢ²`
-/// - `n₁` = weighted tie correction for first variable
-/// - `n₂` = weighted tie correction for second variable
+/// - `n₀` = total weighted pairs = `((Σwᵢ)² - Σwᵢ²) / 2`
+/// - `n₁` = weighted pairs tied in the first variable
+/// - `n₂` = weighted pairs tied in the second variable
 ///
 /// :param data_a: The first dataset for correlation analysis. Must be the same
 ///     length as `data_b`.
@@ -160,6 +129,33 @@ pub fn statistics_weighted_kendall_tau_b(
         .map_err(map_array_error)
 }
 
+/// Compute the weighted Kendall's Tau-b rank correlation coefficient and its
+/// tie correction terms.
+///
+/// This function computes the weighted Kendall's Tau-b rank correlation
+/// coefficient and also returns the weighted tie correction terms used to
+/// compute it.
+///
+/// :param data_a: The first dataset for correlation analysis. Must be the same
+///     length as `data_b`.
+/// :param data_b: The second dataset for correlation analysis. Must be the same
+///     length as `data_a`.
+/// :param weights: The associated weights for each observation pait. Must be the
+///     same length as both input datasets.
+/// :return: The weighted Kendall's Tau-b correlation coefficient and the
+///     weighted number of pairs tied in the first variable, the second variable
+///     and both variables.
+#[pyfunction]
+#[pyo3(name = "weighted_kendall_tau_b_with_ties")]
+pub fn statistics_weighted_kendall_tau_b_with_ties(
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    weights: Vec<f64>,
+) -> PyResult<(f64, f64, f64, f64)> {
+    statistics::weighted_kendall_tau_b_with_ties(&data_a, &data_b, &weights)
+        .map_err(map_array_error)
+}
+
 /// Sort 1-dimensional arrays of values and their associated weights.
 ///
 /// This function performs a bottom up merge sort on the input 1-dimensional
@@ -179,44 +175,600 @@ pub fn statistics_weighted_merge_sort_mut<'py>(
     mut weights: PyReadwriteArray1<f64>,
 ) -> PyResult<f64> {
     // pattern match and extract the allowed array type
-    if let Ok(mut d) = data.extract::<PyReadwriteArray1<u8>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<u16>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<f32>>() {
-        return statistics::weighted_merge_sort_mut(
+    dispatch_array!(data, PyReadwriteArray1, |d| {
+        let mut d = d;
+        statistics::weighted_merge_sort_mut(
             d.as_slice_mut().unwrap(),
             weights.as_slice_mut().unwrap(),
         )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<f64>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<i32>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        .map_err(map_array_error)
+    })
+}
+
+/// Compute the effective sample size (ESS) of weighted sample sets along an
+/// axis of an n-dimensional array.
+///
+/// :param weights: The n-dimensional array of non-negative weights.
+/// :param axis: The sample axis.
+/// :return: The effective number of independent samples of each lane, with the
+///     sample axis removed.
+#[pyfunction]
+#[pyo3(name = "effective_sample_size_axis")]
+pub fn statistics_effective_sample_size_axis<'py>(
+    py: Python<'py>,
+    weights: PyReadonlyArrayDyn<f64>,
+    axis: usize,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    statistics::effective_sample_size_axis(weights.as_array(), axis)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the weighted mean of a sample set.
+///
+/// μ = Σ wᵢxᵢ / Σ wᵢ
+///
+/// :param data: The sample set. Must be the same length as "weights".
+/// :param weights: The non-negative weights of each sample.
+/// :return: The weighted mean. If the sum of weights is 0.0, NaN is returned.
+#[pyfunction]
+#[pyo3(name = "weighted_mean")]
+pub fn statistics_weighted_mean(data: Vec<f64>, weights: Vec<f64>) -> PyResult<f64> {
+    statistics::weighted_mean(&data, &weights).map_err(map_array_error)
+}
+
+/// Compute the weighted mean of sample sets along an axis of an n-dimensional
+/// array.
+///
+/// :param data: The n-dimensional sample array. Must have the same shape as
+///     "weights".
+/// :param weights: The n-dimensional array of non-negative weights.
+/// :param axis: The sample axis.
+/// :return: The weighted mean of each lane, with the sample axis removed.
+#[pyfunction]
+#[pyo3(name = "weighted_mean_axis")]
+pub fn statistics_weighted_mean_axis<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<f64>,
+    weights: PyReadonlyArrayDyn<f64>,
+    axis: usize,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    statistics::weighted_mean_axis(data.as_array(), weights.as_array(), axis)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the weighted variance of a sample set.
+///
+/// The biased (population) variance is Σ wᵢ(xᵢ - μ)² / V₁ and the unbiased
+/// reliability weighted variance is Σ wᵢ(xᵢ - μ)² / (V₁ - V₂ / V₁), where "V₁"
+/// is the sum of weights and "V₂" is the sum of squared weights.
+///
+/// :param data: The sample set. Must be the same length as "weights".
+/// :param weights: The non-negative weights of each sample.
+/// :param unbiased: If true, the unbiased reliability weighted variance is
+///     computed, default = False.
+/// :return: The weighted variance.
+#[pyfunction]
+#[pyo3(name = "weighted_variance")]
+#[pyo3(signature = (data, weights, unbiased=None))]
+pub fn statistics_weighted_variance(
+    data: Vec<f64>,
+    weights: Vec<f64>,
+    unbiased: Option<bool>,
+) -> PyResult<f64> {
+    statistics::weighted_variance(&data, &weights, unbiased).map_err(map_array_error)
+}
+
+/// Compute the weighted variance of sample sets along an axis of an
+/// n-dimensional array.
+///
+/// :param data: The n-dimensional sample array. Must have the same shape as
+///     "weights".
+/// :param weights: The n-dimensional array of non-negative weights.
+/// :param axis: The sample axis.
+/// :param unbiased: If true, the unbiased reliability weighted variance is
+///     computed, default = False.
+/// :return: The weighted variance of each lane, with the sample axis removed.
+#[pyfunction]
+#[pyo3(name = "weighted_variance_axis")]
+#[pyo3(signature = (data, weights, axis, unbiased=None))]
+pub fn statistics_weighted_variance_axis<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<f64>,
+    weights: PyReadonlyArrayDyn<f64>,
+    axis: usize,
+    unbiased: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    statistics::weighted_variance_axis(data.as_array(), weights.as_array(), axis, unbiased)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Compute the weighted Pearson correlation coefficient of two sample sets.
+///
+/// :param data_a: The first sample set. Must be the same length as "data_b"
+///     and "weights".
+/// :param data_b: The second sample set. Must be the same length as "data_a"
+///     and "weights".
+/// :param weights: The non-negative weights of each sample pair.
+/// :return: The weighted correlation coefficient, ranging between -1.0 and 1.0.
+#[pyfunction]
+#[pyo3(name = "weighted_correlation")]
+pub fn statistics_weighted_correlation(
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    weights: Vec<f64>,
+) -> PyResult<f64> {
+    statistics::weighted_correlation(&data_a, &data_b, &weights).map_err(map_array_error)
+}
+
+/// Compute the weighted Pearson correlation coefficients of paired sample sets
+/// along an axis of two n-dimensional arrays.
+///
+/// :param data_a: The first n-dimensional sample array.
+/// :param data_b: The second n-dimensional sample array. Must have the same
+///     shape as "data_a".
+/// :param weights: The n-dimensional array of non-negative weights. Must have
+///     the same shape as "data_a".
+/// :param axis: The sample axis.
+/// :return: The weighted correlation coefficient of each lane pair, with the
+///     sample axis removed.
+#[pyfunction]
+#[pyo3(name = "weighted_correlation_axis")]
+pub fn statistics_weighted_correlation_axis<'py>(
+    py: Python<'py>,
+    data_a: PyReadonlyArrayDyn<f64>,
+    data_b: PyReadonlyArrayDyn<f64>,
+    weights: PyReadonlyArrayDyn<f64>,
+    axis: usize,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    statistics::weighted_correlation_axis(
+        data_a.as_array(),
+        data_b.as_array(),
+        weights.as_array(),
+        axis,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error)
+}
+
+/// Find the bin index of a value in an evenly spaced histogram.
+///
+/// This function finds the bin of a value in a histogram with "bins" evenly
+/// spaced bins between the "range" minimum and maximum. The last bin includes
+/// the maximum value.
+///
+/// :param value: The value to bin.
+/// :param range: The (min, max) range of the histogram.
+/// :param bins: The number of histogram bins. Must be greater than 0.
+/// :return: The bin index of the value, or "None" if the value is outside of
+///     the range or NaN.
+#[pyfunction]
+#[pyo3(name = "bin_index")]
+pub fn statistics_bin_index(value: f64, range: (f64, f64), bins: usize) -> Option<usize> {
+    statistics::bin_index(value, range, bins)
+}
+
+/// Compute the 2-dimensional histogram of paired values.
+///
+/// This function computes a 2-dimensional histogram of paired (x, y) values
+/// (e.g. phasor coordinates or the pixel intensities of two channels). Pairs
+/// with either value outside of its range are not counted.
+///
+/// :param x: The first values of each pair. Must be the same length as "y".
+/// :param y: The second values of each pair. Must be the same length as "x".
+/// :param bins: The number of (x, y) bins, default = (256, 256).
+/// :param ranges: The ((x_min, x_max), (y_min, y_max)) ranges of the
+///     histogram. If "None", the minimum and maximum values of "x" and "y" are
+///     used.
+/// :param weights: Optional weights, one per pair.
+/// :return: The 2-dimensional (x_bins, y_bins) histogram.
+#[pyfunction]
+#[pyo3(name = "histogram2d")]
+#[pyo3(signature = (x, y, bins=None, ranges=None, weights=None))]
+pub fn statistics_histogram2d<'py>(
+    py: Python<'py>,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    bins: Option<(usize, usize)>,
+    ranges: Option<((f64, f64), (f64, f64))>,
+    weights: Option<Vec<f64>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    statistics::histogram2d(&x, &y, bins, ranges, weights.as_deref())
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Reduce an n-dimensional array along an axis.
+///
+/// This function reduces each 1-dimensional lane along the given axis to a
+/// single value (e.g. a sum or maximum intensity projection) in parallel,
+/// producing an (n - 1)-dimensional array.
+///
+/// :param data: The input n-dimensional array.
+/// :param axis: The axis to reduce.
+/// :param reduction: The reduction applied to each lane, "sum", "mean", "max",
+///     "min" or "std", default = "sum".
+/// :return: The reduced (n - 1)-dimensional array.
+#[pyfunction]
+#[pyo3(name = "reduce_axis")]
+#[pyo3(signature = (data, axis, reduction=None))]
+pub fn statistics_reduce_axis<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: usize,
+    reduction: Option<String>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let reduction = match reduction.map(|r| r.to_lowercase()).as_deref() {
+        None | Some("sum") => Reduction::Sum,
+        Some("mean") => Reduction::Mean,
+        Some("max") => Reduction::Max,
+        Some("min") => Reduction::Min,
+        Some("std") => Reduction::Std,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown reduction, supported reductions are \"sum\", \"mean\", \"max\", \"min\", and \"std\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| statistics::reduce_axis(
+        arr.as_array(),
+        axis,
+        reduction
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Estimate the signal-to-noise ratio (SNR) of an image or image region.
+///
+/// If a background region is given, SNR = (μₛ - μ_b) / σ_b, where the noise is
+/// measured from the background. Otherwise the image is assumed to be limited
+/// by Poisson noise, SNR = √(μₛ / g).
+///
+/// :param data: The input n-dimensional image.
+/// :param signal_mask: An optional boolean mask of the signal region. If
+///     "None", all elements not in the background region are used.
+/// :param background_mask: An optional boolean mask of the background region.
+///     If "None", the Poisson noise model is used.
+/// :param gain: The detector gain in counts per photon, used by the Poisson
+///     noise model, default = 1.0.
+/// :return: The estimated linear signal-to-noise ratio.
+#[pyfunction]
+#[pyo3(name = "estimate_snr")]
+#[pyo3(signature = (data, signal_mask=None, background_mask=None, gain=None))]
+pub fn statistics_estimate_snr<'py>(
+    data: Bound<'py, PyAny>,
+    signal_mask: Option<PyReadonlyArrayDyn<bool>>,
+    background_mask: Option<PyReadonlyArrayDyn<bool>>,
+    gain: Option<f64>,
+) -> PyResult<f64> {
+    let sig = signal_mask.as_ref().map(|m| m.as_array());
+    let bg = background_mask.as_ref().map(|m| m.as_array());
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| statistics::estimate_snr(
+        arr.as_array(),
+        sig,
+        bg,
+        gain
+    )
+    .map_err(map_array_error))
+}
+
+/// Estimate the detector gain, read noise and offset from a stack of repeated
+/// frames.
+///
+/// This function fits the photon transfer curve, σ²(x) = g(μ(x) - o) + σᵣ²,
+/// over the temporal mean and variance of each pixel. If "dark_frames" are
+/// given, the offset and read noise are measured from the dark frames,
+/// otherwise the read noise is assumed to be 0.0.
+///
+/// :param frames: The 3-dimensional stack of repeated frames.
+/// :param axis: The frame axis, default = 0.
+/// :param dark_frames: An optional stack of repeated dark frames with the same
+///     dtype as "frames".
+/// :return: The gain (ADU per photoelectron), read noise (photoelectrons) and
+///     offset (ADU).
+#[pyfunction]
+#[pyo3(name = "photon_transfer_curve")]
+#[pyo3(signature = (frames, axis=None, dark_frames=None))]
+pub fn statistics_photon_transfer_curve<'py>(
+    frames: Bound<'py, PyAny>,
+    axis: Option<usize>,
+    dark_frames: Option<Bound<'py, PyAny>>,
+) -> PyResult<(f64, f64, f64)> {
+    dispatch_array!(frames, PyReadonlyArray3, |arr: T| {
+        let dark = match dark_frames.as_ref() {
+            Some(d) => Some(d.extract::<PyReadonlyArray3<T>>()?),
+            None => None,
+        };
+        statistics::photon_transfer_curve(arr.as_array(), axis, dark.as_ref().map(|d| d.as_array()))
+            .map_err(map_array_error)
+    })
+}
+
+/// Convert an n-dimensional array of raw detector counts into photon units.
+///
+/// p = (x - o) / g
+///
+/// :param data: The input n-dimensional array of raw detector counts.
+/// :param gain: The detector gain in ADU per photoelectron.
+/// :param offset: The detector offset in ADU.
+/// :return: The n-dimensional array in photoelectrons, clamped to be
+///     non-negative.
+#[pyfunction]
+#[pyo3(name = "adu_to_photons")]
+pub fn statistics_adu_to_photons<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    gain: f64,
+    offset: f64,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| statistics::adu_to_photons(
+        arr.as_array(),
+        gain,
+        offset
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Compute the Brenner gradient focus score of a 2-dimensional image.
+///
+/// F = 1/N Σ (I(r, c + 2) - I(r, c))²
+///
+/// :param data: The input 2-dimensional image.
+/// :return: The Brenner gradient focus score. If the image has less than 3
+///     columns, 0.0 is returned.
+#[pyfunction]
+#[pyo3(name = "brenner_gradient")]
+pub fn statistics_brenner_gradient<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(
+        statistics::brenner_gradient(arr.as_array())
+    ))
+}
+
+/// Compute the Tenengrad (Sobel gradient energy) focus score of a 2-dimensional
+/// image.
+///
+/// F = 1/N Σ (Gₓ² + Gᵧ²),  for Gₓ² + Gᵧ² > T
+///
+/// :param data: The input 2-dimensional image.
+/// :param threshold: The squared gradient magnitude threshold, default = 0.0.
+/// :return: The Tenengrad focus score.
+#[pyfunction]
+#[pyo3(name = "tenengrad")]
+#[pyo3(signature = (data, threshold=None))]
+pub fn statistics_tenengrad<'py>(data: Bound<'py, PyAny>, threshold: Option<f64>) -> PyResult<f64> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(statistics::tenengrad(
+        arr.as_array(),
+        threshold
+    )))
+}
+
+/// Compute the normalized variance focus score of a 2-dimensional image.
+///
+/// F = 1/(Nμ) Σ (I(r, c) - μ)²
+///
+/// :param data: The input 2-dimensional image.
+/// :return: The normalized variance focus score.
+#[pyfunction]
+#[pyo3(name = "normalized_variance")]
+pub fn statistics_normalized_variance<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(
+        statistics::normalized_variance(arr.as_array())
+    ))
+}
+
+/// Compute the power spectrum focus score of a 2-dimensional image.
+///
+/// This function computes the fraction of the (mean subtracted) image power
+/// spectrum above a radial frequency cutoff, normalized to the Nyquist
+/// frequency.
+///
+/// :param data: The input 2-dimensional image.
+/// :param cutoff: The normalized radial frequency cutoff, default = 0.2.
+/// :return: The high frequency power fraction, between 0.0 and 1.0.
+#[pyfunction]
+#[pyo3(name = "spectral_focus")]
+#[pyo3(signature = (data, cutoff=None))]
+pub fn statistics_spectral_focus<'py>(
+    data: Bound<'py, PyAny>,
+    cutoff: Option<f64>,
+) -> PyResult<f64> {
+    dispatch_array!(data, PyReadonlyArray2, |arr| Ok(
+        statistics::spectral_focus(arr.as_array(), cutoff)
+    ))
+}
+
+/// Compute the focus scores of each frame in a 3-dimensional stack.
+///
+/// :param data: The input 3-dimensional stack.
+/// :param metric: The focus metric, "brenner", "tenengrad",
+///     "normalized_variance" or "spectral", default = "brenner".
+/// :param threshold: The Tenengrad squared gradient threshold, default = 0.0.
+/// :param cutoff: The spectral normalized radial frequency cutoff, default =
+///     0.2.
+/// :param axis: The frame axis, default = 0.
+/// :return: The focus score of each frame.
+#[pyfunction]
+#[pyo3(name = "focus_curve")]
+#[pyo3(signature = (data, metric=None, threshold=None, cutoff=None, axis=None))]
+pub fn statistics_focus_curve<'py>(
+    data: Bound<'py, PyAny>,
+    metric: Option<String>,
+    threshold: Option<f64>,
+    cutoff: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Vec<f64>> {
+    let metric = match metric.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("brenner") => FocusMetric::Brenner,
+        Some("tenengrad") => FocusMetric::Tenengrad {
+            threshold: threshold.unwrap_or(0.0),
+        },
+        Some("normalized_variance") => FocusMetric::NormalizedVariance,
+        Some("spectral") => FocusMetric::Spectral {
+            cutoff: cutoff.unwrap_or(0.2),
+        },
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown metric, supported metrics are \"brenner\", \"tenengrad\", \"normalized_variance\", and \"spectral\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray3, |arr| statistics::focus_curve(
+        arr.as_array(),
+        metric,
+        axis
+    )
+    .map_err(map_array_error))
+}
+
+/// Rank the values of an n-dimensional array.
+///
+/// This function ranks the values of an n-dimensional array in ascending order,
+/// starting at 1. NaN values are ranked after all other values. Tied values are
+/// ranked according to "method", for example the values [10, 20, 20, 30] are
+/// ranked:
+///
+/// average: [1.0, 2.5, 2.5, 4.0]
+/// dense:   [1.0, 2.0, 2.0, 3.0]
+/// min:     [1.0, 2.0, 2.0, 4.0]
+/// ordinal: [1.0, 2.0, 3.0, 4.0]
+///
+/// :param data: The input n-dimensional array.
+/// :param method: The tie handling method, "average", "dense", "min" or
+///     "ordinal", default = "average".
+/// :return: The ranks, with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "rank")]
+#[pyo3(signature = (data, method=None))]
+pub fn statistics_rank<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<String>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let method = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("average") => RankMethod::Average,
+        Some("dense") => RankMethod::Dense,
+        Some("min") => RankMethod::Min,
+        Some("ordinal") => RankMethod::Ordinal,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"average\", \"dense\", \"min\", and \"ordinal\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        Ok(py
+            .allow_threads(|| statistics::rank(arr, Some(method)))
+            .into_pyarray(py))
+    })
+}
+
+/// Count the weighted inversions of an n-dimensional array.
+///
+/// This function counts the weighted inversions (i.e. pairs of values out of
+/// ascending order) of an n-dimensional array in row-major order, where each
+/// inversion (i, j) has the weight wᵢ * wⱼ. The inputs are not mutated.
+///
+/// :param data: The input n-dimensional array.
+/// :param weights: The weights of each value, the same shape as "data".
+/// :return: The weighted inversion count.
+#[pyfunction]
+#[pyo3(name = "weighted_inversions")]
+pub fn statistics_weighted_inversions<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    weights: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<f64> {
+    let w = weights.as_array();
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| statistics::weighted_inversions(arr, w))
+            .map_err(map_array_error)
+    })
+}
+
+/// Adjust a p-value map for multiple comparisons.
+///
+/// This function adjusts the p-values of an n-dimensional map (e.g. one
+/// p-value per pixel) for the number of tests, the number of non-NaN p-values.
+/// The Bonferroni correction controls the family-wise error rate and the
+/// Benjamini-Hochberg procedure controls the false discovery rate.
+///
+/// :param data: The n-dimensional p-value map.
+/// :param method: The correction method, "bonferroni" or "bh"
+///     (Benjamini-Hochberg), default = "bh".
+/// :return: The adjusted p-values, with the same shape as "data". NaN p-values
+///     stay NaN and are not counted as tests.
+#[pyfunction]
+#[pyo3(name = "p_adjust")]
+#[pyo3(signature = (data, method=None))]
+pub fn statistics_p_adjust<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    method: Option<String>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let method = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("bh") | Some("benjamini_hochberg") => PAdjustMethod::BenjaminiHochberg,
+        Some("bonferroni") => PAdjustMethod::Bonferroni,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"bonferroni\" and \"bh\".",
+            ));
+        }
+    };
+    let arr = data.as_array();
+    py.allow_threads(|| statistics::p_adjust(arr, Some(method)))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
+
+/// Convert a z-score map into a p-value map.
+///
+/// This function converts each z-score of an n-dimensional map (e.g. the SACA
+/// colocalization z-score image) into the p-value of a standard normal test
+/// statistic.
+///
+/// :param data: The n-dimensional z-score map.
+/// :param tail: The tail of the test, "two", "upper" or "lower", default =
+///     "two".
+/// :return: The p-values, with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "z_to_p")]
+#[pyo3(signature = (data, tail=None))]
+pub fn statistics_z_to_p<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArrayDyn<'py, f64>,
+    tail: Option<String>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let tail = match tail.map(|t| t.to_lowercase()).as_deref() {
+        None | Some("two") => Tail::Two,
+        Some("upper") => Tail::Upper,
+        Some("lower") => Tail::Lower,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown tail, supported tails are \"two\", \"upper\", and \"lower\".",
+            ));
+        }
+    };
+    let arr = data.as_array();
+    Ok(py
+        .allow_threads(|| statistics::z_to_p(arr, Some(tail)))
+        .into_pyarray(py))
+}
+
+/// Compute the median of a sequence of numbers.
+///
+/// For an even number of values the median is the mean of the two middle
+/// values.
+///
+/// :param data: The sequence of numbers.
+/// :return: The median. If "data" is empty, NaN.
+#[pyfunction]
+#[pyo3(name = "median")]
+pub fn statistics_median(mut data: Vec<f64>) -> f64 {
+    statistics::median(&mut data)
 }
@@ -1,7 +1,8 @@
 use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
-use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
 
+use crate::dispatch_array;
+use crate::error::map_array_error;
 use imgal::threshold;
 
 /// Create a boolean mask from a threshold value.
@@ -21,21 +22,52 @@ pub fn threshold_manual_mask<'py>(
     data: Bound<'py, PyAny>,
     threshold: f64,
 ) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as u8);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as u16);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as f32);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold);
-        return Ok(output.into_pyarray(py));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr: T| {
+        let output = threshold::manual_mask(arr.as_array(), threshold as T);
+        Ok(output.into_pyarray(py))
+    })
+}
+
+/// Compute Otsu's threshold value of an n-dimensional image.
+///
+/// This function computes the threshold that separates the image histogram
+/// into a background and a foreground class with the maximum between class
+/// variance. Values greater than the threshold are foreground.
+///
+/// :param data: An n-dimensional image or array.
+/// :param bins: The number of histogram bins, default = 256.
+/// :return: Otsu's threshold value. If all values of "data" are equal, that
+///     value is returned.
+#[pyfunction]
+#[pyo3(name = "otsu_value")]
+#[pyo3(signature = (data, bins=None))]
+pub fn threshold_otsu_value<'py>(data: Bound<'py, PyAny>, bins: Option<usize>) -> PyResult<f64> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        threshold::otsu_value(arr.as_array(), bins).map_err(map_array_error)
+    })
+}
+
+/// Create a boolean mask with Otsu's threshold.
+///
+/// This function computes Otsu's threshold and creates a threshold mask (as a
+/// boolean array) of the input image.
+///
+/// :param data: An n-dimensional image or array.
+/// :param bins: The number of histogram bins, default = 256.
+/// :return: A boolean array of the same shape as the input image with pixels
+///     that are greater than the threshold value set as "true".
+#[pyfunction]
+#[pyo3(name = "otsu_mask")]
+#[pyo3(signature = (data, bins=None))]
+pub fn threshold_otsu_mask<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    bins: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    dispatch_array!(data, PyReadonlyArrayDyn, |arr| {
+        let arr = arr.as_array();
+        py.allow_threads(|| threshold::otsu_mask(arr, bins))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_array_error)
+    })
 }
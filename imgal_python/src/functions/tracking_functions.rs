@@ -0,0 +1,46 @@
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error::map_array_error;
+use imgal::tracking::{self, LinkMethod};
+
+/// Link per-frame particle detections into trajectories.
+///
+/// This function links the particle detections of consecutive frames into
+/// trajectories, where the cost of a link is the squared Euclidean distance.
+/// Unlinked detections start new tracks and tracks may skip up to "max_gap"
+/// frames.
+///
+/// :param detections: A list with the detections of each frame, as 2-dimensional
+///     arrays with a shape of (n_detections, n_dims).
+/// :param max_distance: The maximum link distance.
+/// :param max_gap: The maximum number of skipped frames, default = 0.
+/// :param method: The linking method, "lap" or "nearest_neighbor", default =
+///     "lap".
+/// :return: The track table with a shape of (n_detections, 2 + n_dims), where
+///     each row is (track_id, frame, coordinates...).
+#[pyfunction]
+#[pyo3(name = "link_particles")]
+#[pyo3(signature = (detections, max_distance, max_gap=None, method=None))]
+pub fn tracking_link_particles<'py>(
+    py: Python<'py>,
+    detections: Vec<PyReadonlyArray2<'py, f64>>,
+    max_distance: f64,
+    max_gap: Option<usize>,
+    method: Option<String>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let method = match method.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("lap") => LinkMethod::Lap,
+        Some("nearest_neighbor") => LinkMethod::NearestNeighbor,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown method, supported methods are \"lap\" and \"nearest_neighbor\".",
+            ));
+        }
+    };
+    let views: Vec<_> = detections.iter().map(|d| d.as_array()).collect();
+    tracking::link_particles(&views, max_distance, max_gap, Some(method))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
@@ -0,0 +1,79 @@
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::dispatch_array;
+use crate::error::map_array_error;
+use imgal::transform::{self, Wavelet};
+
+/// Compute the multilevel 2-dimensional discrete wavelet transform.
+///
+/// This function computes the separable 2-dimensional discrete wavelet
+/// transform (DWT) of an image with an orthogonal wavelet and periodic
+/// boundaries. The coefficients are stored in the Mallat layout, where each
+/// level splits the current approximation into the LL (top left), LH (top
+/// right), HL (bottom left) and HH (bottom right) subbands.
+///
+/// :param data: The input 2-dimensional image. Both dimensions must be
+///     divisible by 2^levels.
+/// :param wavelet: The wavelet, "haar", "db2" or "db4", default = "haar".
+/// :param levels: The number of decomposition levels, default = 1.
+/// :return: The wavelet coefficients in the Mallat layout.
+#[pyfunction]
+#[pyo3(name = "dwt2")]
+#[pyo3(signature = (data, wavelet=None, levels=None))]
+pub fn transform_dwt2<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    wavelet: Option<String>,
+    levels: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let wavelet = match wavelet.map(|w| w.to_lowercase()).as_deref() {
+        None | Some("haar") => Wavelet::Haar,
+        Some("db2") => Wavelet::Db2,
+        Some("db4") => Wavelet::Db4,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown wavelet, supported wavelets are \"haar\", \"db2\", and \"db4\".",
+            ));
+        }
+    };
+    dispatch_array!(data, PyReadonlyArray2, |arr| transform::dwt2(
+        arr.as_array(),
+        wavelet,
+        levels
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_array_error))
+}
+
+/// Compute the multilevel 2-dimensional inverse discrete wavelet transform.
+///
+/// :param data: The 2-dimensional wavelet coefficients in the Mallat layout.
+/// :param wavelet: The wavelet used for the forward transform, "haar", "db2" or
+///     "db4", default = "haar".
+/// :param levels: The number of decomposition levels, default = 1.
+/// :return: The reconstructed image.
+#[pyfunction]
+#[pyo3(name = "idwt2")]
+#[pyo3(signature = (data, wavelet=None, levels=None))]
+pub fn transform_idwt2<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray2<f64>,
+    wavelet: Option<String>,
+    levels: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let wavelet = match wavelet.map(|w| w.to_lowercase()).as_deref() {
+        None | Some("haar") => Wavelet::Haar,
+        Some("db2") => Wavelet::Db2,
+        Some("db4") => Wavelet::Db4,
+        Some(_) => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Unknown wavelet, supported wavelets are \"haar\", \"db2\", and \"db4\".",
+            ));
+        }
+    };
+    transform::idwt2(data.as_array(), wavelet, levels)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_array_error)
+}
@@ -1,9 +1,11 @@
 use pyo3::prelude::*;
 
 use super::child_modules::{
-    colocalization_module, distribution_module, filter_module, image_module, integration_module,
-    kernel_module, parameter_module, phasor_module, simulation_module, statistics_module,
-    threshold_module,
+    colocalization_module, data_module, distribution_module, feature_module, filter_module,
+    fitting_module, image_module, integration_module, kernel_module, localization_module,
+    measure_module, motion_module, ops_module, parameter_module, phasor_module, pipeline_module,
+    registration_module, segmentation_module, simulation_module, statistics_module,
+    threshold_module, tracking_module, transform_module,
 };
 
 /// Python binding for the imgal parent module.
@@ -11,15 +13,27 @@ use super::child_modules::{
 fn imgal_parent_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // register child modules
     colocalization_module::register_colocalization_module(m)?;
+    data_module::register_data_module(m)?;
     distribution_module::register_distribution_module(m)?;
+    feature_module::register_feature_module(m)?;
     filter_module::register_filter_module(m)?;
+    fitting_module::register_fitting_module(m)?;
     image_module::register_image_module(m)?;
     integration_module::register_integration_module(m)?;
     kernel_module::register_kernel_module(m)?;
+    localization_module::register_localization_module(m)?;
+    measure_module::register_measure_module(m)?;
+    motion_module::register_motion_module(m)?;
+    ops_module::register_ops_module(m)?;
     parameter_module::register_parameter_module(m)?;
     phasor_module::register_phasor_module(m)?;
+    pipeline_module::register_pipeline_module(m)?;
+    registration_module::register_registration_module(m)?;
+    segmentation_module::register_segmentation_module(m)?;
     simulation_module::register_simulation_module(m)?;
     statistics_module::register_statistics_module(m)?;
     threshold_module::register_threshold_module(m)?;
+    tracking_module::register_tracking_module(m)?;
+    transform_module::register_transform_module(m)?;
     Ok(())
 }
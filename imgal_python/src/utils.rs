@@ -1,7 +1,13 @@
 use std::ffi::CString;
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use crate::error::map_array_error;
+use imgal::error::ImgalError;
+use imgal::image::ImageMeta;
+use imgal::statistics::Summation;
+
 /// Add a child module to Python's sys.modules dict.
 ///
 /// # Description
@@ -23,3 +29,149 @@ pub fn py_import_module(module_name: &str) {
         py.run(c_str_cmd.as_c_str(), None, None).unwrap();
     });
 }
+
+/// Check that a caller-provided output array has the expected shape.
+pub fn check_output_shape(out_shape: &[usize], shape: &[usize]) -> PyResult<()> {
+    if out_shape != shape {
+        return Err(map_array_error(ImgalError::MismatchedArrayShapes {
+            shape_a: shape.to_vec(),
+            shape_b: out_shape.to_vec(),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Resolve the decay axis of a 3-dimensional decay image.
+///
+/// # Description
+///
+/// The decay axis is either given positionally with `axis` or inferred from an
+/// axis order string with `axes` (_e.g._ `"tyx"`), where the decay axis is the
+/// lifetime ("l") axis if present, otherwise the time ("t") axis.
+///
+/// # Arguments
+///
+/// * `axis`: The optional positional decay axis.
+/// * `axes`: The optional axis order string of the image.
+/// * `shape`: The shape of the image.
+///
+/// # Returns
+///
+/// * `Ok(Option<usize>)`: The decay axis, `None` if neither `axis` nor `axes`
+///    are given.
+/// * `Err(PyErr)`: If both `axis` and `axes` are given. If `axes` is invalid,
+///    does not match the number of image dimensions or has no decay axis.
+pub fn resolve_decay_axis(
+    axis: Option<usize>,
+    axes: Option<&str>,
+    shape: &[usize],
+) -> PyResult<Option<usize>> {
+    let Some(order) = axes else {
+        return Ok(axis);
+    };
+    if axis.is_some() {
+        return Err(PyErr::new::<PyValueError, _>(
+            "Only one of \"axis\" and \"axes\" can be given.",
+        ));
+    }
+    let meta = ImageMeta::from_order(order).map_err(map_array_error)?;
+    if meta.ndim() != shape.len() {
+        return Err(map_array_error(ImgalError::MismatchedArrayLengths {
+            a_arr_len: shape.len(),
+            b_arr_len: meta.ndim(),
+        }));
+    }
+
+    meta.decay_axis().map(Some).ok_or_else(|| {
+        PyErr::new::<PyValueError, _>("The axis order has no decay axis, \"l\" or \"t\".")
+    })
+}
+
+/// Parse a summation algorithm name.
+///
+/// # Arguments
+///
+/// * `summation`: The optional summation algorithm name, "naive", "pairwise"
+///    or "kahan".
+///
+/// # Returns
+///
+/// * `Ok(Option<Summation>)`: The summation algorithm, `None` if not given.
+/// * `Err(PyErr)`: If the summation algorithm name is unknown.
+pub fn parse_summation(summation: Option<&str>) -> PyResult<Option<Summation>> {
+    match summation.map(|s| s.to_lowercase()).as_deref() {
+        None => Ok(None),
+        Some("naive") => Ok(Some(Summation::Naive)),
+        Some("pairwise") => Ok(Some(Summation::Pairwise)),
+        Some("kahan") => Ok(Some(Summation::Kahan)),
+        Some(_) => Err(PyErr::new::<PyValueError, _>(
+            "Unknown summation, supported summations are \"naive\", \"pairwise\", and \"kahan\".",
+        )),
+    }
+}
+
+/// Dispatch a numpy array to a generic expression over the supported dtypes.
+///
+/// # Description
+///
+/// This macro tries to extract `$data` as a `$array<T>` numpy array (or a
+/// `Vec<$array<T>>` list of numpy arrays) for each supported dtype (u8, u16,
+/// u32, u64, i16, i32, f32 and f64) and evaluates `$body` with the extracted
+/// array bound to `$arr`. With the `|arr: T|` form, `T` is a type alias of the
+/// matched dtype within `$body` (_e.g._ to extract a second array of the same
+/// dtype). If no dtype matches, a `TypeError` is returned.
+///
+/// # Arguments
+///
+/// * `$data`: The `Bound<PyAny>` input array.
+/// * `$array`: The numpy array type to extract (_e.g._ `PyReadonlyArray2`).
+/// * `$arr`: The name of the extracted array within `$body`.
+/// * `$t`: The optional name of the dtype alias within `$body`.
+/// * `$body`: The expression to evaluate, returning a `PyResult`.
+#[macro_export]
+macro_rules! dispatch_array {
+    (@arms $label:lifetime, $data:expr, [], $array:ident, $arr:ident, $t:ident, $body:expr, $($ty:ty)+) => {
+        $(
+            if let Ok($arr) = $data.extract::<$array<$ty>>() {
+                #[allow(dead_code)]
+                type $t = $ty;
+                let output = $body;
+                break $label output;
+            }
+        )+
+    };
+    (@arms $label:lifetime, $data:expr, [Vec], $array:ident, $arr:ident, $t:ident, $body:expr, $($ty:ty)+) => {
+        $(
+            if let Ok($arr) = $data.extract::<Vec<$array<$ty>>>() {
+                #[allow(dead_code)]
+                type $t = $ty;
+                let output = $body;
+                break $label output;
+            }
+        )+
+    };
+    (@dispatch $data:expr, [$($vec:ident)?], $array:ident, $arr:ident, $t:ident, $body:expr) => {
+        'dispatch: {
+            $crate::dispatch_array!(
+                @arms 'dispatch, $data, [$($vec)?], $array, $arr, $t, $body,
+                u8 u16 u32 u64 i16 i32 f32 f64
+            );
+            Err(pyo3::PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "Unsupported array dtype, supported array dtypes are u8, u16, u32, u64, i16, i32, f32, and f64.",
+            ))
+        }
+    };
+    ($data:expr, Vec<$array:ident>, |$arr:ident| $body:expr) => {
+        $crate::dispatch_array!(@dispatch $data, [Vec], $array, $arr, DispatchType, $body)
+    };
+    ($data:expr, Vec<$array:ident>, |$arr:ident: $t:ident| $body:expr) => {
+        $crate::dispatch_array!(@dispatch $data, [Vec], $array, $arr, $t, $body)
+    };
+    ($data:expr, $array:ident, |$arr:ident| $body:expr) => {
+        $crate::dispatch_array!(@dispatch $data, [], $array, $arr, DispatchType, $body)
+    };
+    ($data:expr, $array:ident, |$arr:ident: $t:ident| $body:expr) => {
+        $crate::dispatch_array!(@dispatch $data, [], $array, $arr, $t, $body)
+    };
+}